@@ -0,0 +1,117 @@
+// # Program Description:
+// This program embeds witness communicators in the event loop of an application, the way an application
+// built on the crate would, through its public API only. Every node runs a single `select!` loop over the
+// commands of the application, the witness rounds delivered to the node (`witness_deliveries`), and a
+// shutdown signal, then shuts its handles down cooperatively. It exits with status 1 if a node misses a
+// round or delivers one out of order.
+// # Usage:
+// * cargo run --example app_event_loop [thread_count]
+
+use std::{env, process, time::Duration};
+use futures::{StreamExt, future::join_all};
+use tokio::sync::{mpsc, watch};
+
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::ReliableCommunication;
+use rust_project::round::Round;
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+// The number of values every application proposes.
+const PROPOSALS: u32 = 3;
+
+// # Enum Description:
+// This enum represents the commands an application hands to its node.
+//
+// # Variants:
+// * Propose - Broadcast a value in the next witness round of the node.
+enum AppCommand {
+    Propose(String),
+}
+
+// # Function Description:
+// This function runs the event loop of a node: it broadcasts the values proposed by the application in
+// consecutive rounds, forwards every delivered round to the application, and stops once the application
+// signals the shutdown.
+// # Parameters:
+// * id - The ID of the node.
+// * witness_communicator - The communicator of the node.
+// * commands - The receiver of the commands of the application.
+// * delivered - The sender on which the delivered rounds are forwarded to the application, with their number of values.
+// * shutdown - The receiver of the shutdown signal of the application.
+async fn run_node(id: u32, mut witness_communicator: WitnessCommunicator<String>, mut commands: mpsc::Receiver<AppCommand>, delivered: mpsc::Sender<(u32, Round, usize)>, mut shutdown: watch::Receiver<bool>) {
+    let reliable_handle = witness_communicator.initialize_reliable_handle();
+    let witness_handle = witness_communicator.initialize_witness_handle();
+    let mut deliveries = Box::pin(witness_communicator.witness_deliveries(Round(0)));
+    let mut next_round = Round(0);
+
+    loop {
+        tokio::select! {
+            Some(command) = commands.recv() => match command {
+                AppCommand::Propose(value) => {
+                    witness_communicator.witness_broadcast(value, next_round).await;
+                    next_round = next_round.next();
+                },
+            },
+            Some((round_number, values)) = deliveries.next() => {
+                let _ = delivered.send((id, round_number, values.len())).await;
+            },
+            _ = shutdown.changed() => break,
+        }
+    }
+
+    let witness_report = witness_communicator.shutdown_witness_handle(witness_handle).await;
+    let reliable_report = witness_communicator.shutdown_reliable_handle(reliable_handle).await;
+    println!("id: {id}, stopped: witness {witness_report:?}, reliable {reliable_report:?}");
+}
+
+#[tokio::main]
+async fn main() {
+    let thread_count = env::args().nth(1).and_then(|count| count.parse().ok()).unwrap_or(4);
+    let config = ClusterConfig::new(thread_count);
+    let quorum = (thread_count - config.get_thresholds().get_faulty_threads() as u32) as usize;
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+
+    let (delivered_tx, mut delivered_rx) = mpsc::channel(64);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut command_senders = vec![];
+    let mut nodes = vec![];
+    for id in 0..thread_count {
+        let (command_tx, command_rx) = mpsc::channel(16);
+        command_senders.push(command_tx);
+        nodes.push(tokio::spawn(run_node(id, witness_hub.create_witness_communicator(), command_rx, delivered_tx.clone(), shutdown_rx.clone())));
+    }
+
+    for proposal in 0..PROPOSALS {
+        for (id, command_tx) in command_senders.iter().enumerate() {
+            let _ = command_tx.send(AppCommand::Propose(format!("proposal {proposal} of node {id}"))).await;
+        }
+    }
+
+    // every node must deliver every round, in order, with a quorum of values
+    let mut next_rounds = vec![Round(0); thread_count as usize];
+    let mut passed = true;
+    while next_rounds.iter().any(|round_number| *round_number < Round(PROPOSALS)) {
+        match tokio::time::timeout(Duration::from_secs(10), delivered_rx.recv()).await {
+            Ok(Some((id, round_number, values))) => {
+                let in_order = round_number == next_rounds[id as usize];
+                println!("id: {id}, round {round_number} delivered with {values} values{}", if in_order { "" } else { " out of order" });
+                passed &= in_order && values >= quorum;
+                next_rounds[id as usize] = round_number.next();
+            },
+            _ => {
+                println!("rounds not delivered in time: next rounds {next_rounds:?}");
+                passed = false;
+                break
+            },
+        }
+    }
+
+    let _ = shutdown_tx.send(true);
+    passed &= join_all(nodes).await.into_iter().all(|result| result.is_ok());
+    println!("application event loop example: {}", if passed { "passed" } else { "failed" });
+    if !passed {
+        process::exit(1);
+    }
+}
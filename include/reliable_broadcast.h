@@ -0,0 +1,56 @@
+/*
+ * C ABI of the reliable broadcast core, exported by the library built with the `ffi` feature:
+ *
+ *     cargo rustc --lib --release --features ffi --crate-type cdylib
+ *
+ * A core is the reliable handle of a single thread of a cluster, driven in lock step. It never touches
+ * the network: the caller feeds it the frames it receives (rb_core_feed), makes it broadcast
+ * (rb_core_broadcast), and polls the actions it emitted (rb_core_poll), routing every sent frame to the
+ * core of its destination thread, the core itself included. Frames are opaque bytes, tagged with
+ * their lane.
+ */
+#ifndef RELIABLE_BROADCAST_H
+#define RELIABLE_BROADCAST_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A frame to send to the thread `destination`. */
+#define RB_ACTION_SENT 0
+/* A frame delivered to the core's own thread, e.g. a reliably delivered message. */
+#define RB_ACTION_DELIVERED 1
+
+typedef struct ReliableCore ReliableCore;
+
+typedef struct RbAction {
+    uint32_t kind;
+    uint32_t destination;
+    /* Valid until the next call on the core; not NUL-terminated. */
+    const uint8_t *frame;
+    size_t length;
+} RbAction;
+
+/* Creates the core of thread `id` of a cluster of `thread_count` threads, or returns NULL. */
+ReliableCore *rb_core_new(uint32_t thread_count, uint32_t id);
+
+/* Releases a core. Accepts NULL. */
+void rb_core_free(ReliableCore *core);
+
+/* Feeds a frame to a core. Returns the number of actions emitted, or -1 on invalid arguments. */
+int64_t rb_core_feed(ReliableCore *core, const uint8_t *frame, size_t length);
+
+/* Makes a core reliably broadcast a UTF-8 message. Returns the number of actions emitted, or -1. */
+int64_t rb_core_broadcast(ReliableCore *core, const uint8_t *message, size_t length, uint32_t instance_number, uint32_t round_number);
+
+/* Writes the next action of a core to `action`. Returns 1 if written, 0 if none is left, or -1. */
+int32_t rb_core_poll(ReliableCore *core, RbAction *action);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -1,13 +1,29 @@
 use core::panic;
-use std::{vec, fmt::Debug, hash::Hash, collections::{HashMap, HashSet}};
+use std::{vec, fmt::Debug, hash::Hash, collections::{HashMap, HashSet}, marker::PhantomData, time::Duration};
+use bytes::Bytes;
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
-use tokio::{task::JoinHandle, sync::mpsc::{self, Receiver, Sender}};
+use tokio::{task::JoinHandle, sync::{broadcast, oneshot, mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender}}};
+use tracing::Instrument;
 use async_trait::async_trait; 
 
-use crate::{basic::{BasicCommunication, BasicQueues, Message, MessageChannels, RecvObject}}; 
-use crate::reliable::{ReliableCommunication, Signal, SignalType, ChannelType, ObjectContent, SignalChannels, ReliableInstanceMonitor}; 
-use crate::witness::{WitnessCommunication, WitnessRoundMonitor, WitnessRoundCount, WitnessRoundContent, Report, ReportType, ReportChannels}; 
-use crate::json::{JsonConversion};
+use crate::delivered::Delivered;
+use crate::{basic::{BasicCommunication, BasicQueues, CollectError, Message, MessageChannels, RecvObject, validate_wiring}}; 
+use crate::reliable::{ReliableCommunication, Signal, SignalType, ChannelType, ObjectContent, SignalChannels, ReliableHandleCommand, DeliveryWatches}; 
+use crate::witness::{WitnessCommunication, WitnessRoundMonitor, WitnessRoundCount, WitnessRoundContent, Report, ReportType, ReportChannels, WitnessHandleCommand, RoundSubscriptions, ValueOrdering}; 
+use crate::json::{FrameKind, JsonConversion, WireFormat, frame_kinds};
+use crate::multiplex::{Lane, LaneReceivers, open_lanes};
+use crate::drive::{HandleMode, PolledHandle, PolledHandles, ShutdownResponder, ShutdownReport};
+use crate::config::{ClusterConfig, Thresholds};
+use crate::events::{Event, DecodeFailureReporter};
+use crate::metrics::{FrameStatistics, Metrics, RoundStatus};
+use crate::health::RunningHandle;
+use crate::inspect::Inspect;
+use crate::retention::RetentionTracker;
+use crate::trace::TraceSampler;
+use crate::capabilities::{Capabilities, Protocol};
+use crate::round::{Instance, Round};
+use crate::logging::log;
+use crate::descriptor::{MessageTypeDescriptor, PhaseDescriptor, ProtocolDescriptor, Quorum};
 
 // # Struct Description:
 // The struct initializes per-thread communication channels and coordinates 
@@ -18,54 +34,80 @@ use crate::json::{JsonConversion};
 // # Fields:
 // * aggregated_witness_communicators - A vector of `AggregatedWitnessCommunicator` 
 //   instances, each assigned to a specific thread for handling message exchange.
+// * config - The configuration shared by every communicator created from this hub.
 //
 pub struct AggregatedWitnessHub<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    aggregated_witness_communicators: Vec<AggregatedWitnessCommunicator<T>>
+    aggregated_witness_communicators: Vec<AggregatedWitnessCommunicator<T>>,
+    config: ClusterConfig,
 }
  
 impl<T> AggregatedWitnessHub<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    pub fn new(transmitters: Vec<Sender<String>>, mut receivers: Vec<Receiver<String>>, thread_count: u32) -> Self {  
-        let mut aggregated_witness_communicators = vec![];
-        let mut reliable_handle_transmitters = vec![];
-        let mut reliable_handle_receivers = vec![];
-
-        let mut witness_handle_transmitters = vec![];
-        let mut witness_handle_receivers = vec![];
-
-        for _ in 0..(thread_count) {
-            let (reliable_handle_tx, reliable_handle_rx) = mpsc::channel(256); 
-            let (witness_handle_tx, witness_handle_rx) = mpsc::channel(256); 
-
-            reliable_handle_transmitters.push(reliable_handle_tx);
-            reliable_handle_receivers.push(reliable_handle_rx);
+    pub fn new(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, thread_count: u32) -> Self {  
+        Self::with_config(transmitters, receivers, ClusterConfig::new(thread_count))
+    }
 
-            witness_handle_transmitters.push(witness_handle_tx);
-            witness_handle_receivers.push(witness_handle_rx);
+    // # Function Description:
+    // This function creates a hub whose quorums are weighted: every Echo, Vote, value, and witness counts
+    // for the weight (stake) of its sender, and the thresholds are derived from the total weight following
+    // the quorum configuration of `config` (see `QuorumConfig::with_weights`).
+    //
+    // # Parameters:
+    // * transmitters - The transmitter of every thread.
+    // * receivers - The receiver of every thread.
+    // * config - The configuration of the cluster.
+    // * weights - The weight of every thread, indexed by thread ID.
+    //
+    // # Returns:
+    // * The hub, or a description of why the weights cannot be used (see `QuorumConfig::thresholds`): weights
+    //   that do not cover every thread, a total weight of zero, or a total that does not fit a `u64`.
+    pub fn with_weights(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, config: ClusterConfig, weights: Vec<u64>) -> Result<Self, String> {
+        let quorum_config = config.get_quorum_config().clone().with_weights(weights);
+        Ok(Self::with_config(transmitters, receivers, config.with_quorum_config(quorum_config)?))
+    }
 
-        }
+    pub fn with_config(transmitters: Vec<Sender<Bytes>>, mut receivers: Vec<Receiver<Bytes>>, config: ClusterConfig) -> Self {  
+        let thread_count = config.get_thread_count();
+        let mut aggregated_witness_communicators = vec![];
         
         for i in 0..(thread_count) {
-            let reliable_handle_rx = reliable_handle_receivers.remove(0);
-            let witness_handle_rx = witness_handle_receivers.remove(0);
-            let rx: Receiver<String> = receivers.remove(0);
-            aggregated_witness_communicators.push(AggregatedWitnessCommunicator::new(transmitters.clone(), rx, 
-                thread_count, i as u32, reliable_handle_transmitters.clone(), reliable_handle_rx, witness_handle_transmitters.clone(), witness_handle_rx));
+            let lanes = open_lanes(i, config.link_receiver(i, receivers.remove(0)), &config);
+            let (witness_command_tx, witness_command_rx) = mpsc::unbounded_channel(); 
+            let (proof_tx, proof_rx) = mpsc::channel(config.get_buffer_size()); 
+            aggregated_witness_communicators.push(AggregatedWitnessCommunicator::new(config.link_transmitters(i, &transmitters), lanes, 
+                i, witness_command_tx, witness_command_rx, proof_tx, proof_rx, config.clone()));
         }
         
         Self {
-            aggregated_witness_communicators
+            aggregated_witness_communicators,
+            config
         }
     }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
  
     pub fn create_aggregated_witness_communicator(&mut self) -> AggregatedWitnessCommunicator<T>{
         self.aggregated_witness_communicators.remove(0)
     }
+
+    // # Method Description:
+    // This method checks that every channel of the hub reaches the thread it is meant for (see `validate_wiring`).
+    // It must be called before any communicator is created from the hub.
+    // # Returns:
+    // * `Ok(())` if the wiring is correct, or a description of every mis-wired channel.
+    pub async fn validate(&mut self) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        validate_wiring(&mut self.aggregated_witness_communicators).await
+    }
  }
 
 // # Struct Description:
@@ -86,6 +128,17 @@ where
 //   reliable broadcast messages.
 // * witness_handle_rx - A receiver for handling incoming 
 //   witness report messages.
+// * witness_command_tx - A transmitter for sending commands (e.g. round subscriptions) to the witness handle.
+// * witness_command_rx - A receiver for commands, taken by the witness handle.
+// * proof_tx - A transmitter handed to the witness handle for delivering aggregation proofs.
+// * proofs - An `AggregationProofs` instance buffering the aggregation proofs produced by the witness handle.
+// * reliable_command_tx - A transmitter for sending commands (e.g. inspection queries) to the reliable handle.
+// * reliable_command_rx - A receiver for commands, taken by the reliable handle.
+// * event_tx - A transmitter on which the background handles publish `Event`s.
+// * metrics - The metrics of the thread, in which the reliable handle records its phase latencies.
+// * delivery_watches - The watch channels on which the reliable handle publishes deliveries.
+// * polled_handles - The parts of the thread driven manually in `HandleMode::Polling`.
+// * config - The configuration shared by every communicator created from the same hub.
 pub struct AggregatedWitnessCommunicator<T>
 where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
@@ -95,23 +148,43 @@ where
     signal_channels: SignalChannels<T>, 
     report_channels: ReportChannels<T>,
     queues: BasicQueues<T>,
-    reliable_handle_rx: Option<Receiver<String>>, 
-    witness_handle_rx: Option<Receiver<String>>, 
+    reliable_handle_rx: Option<Receiver<Bytes>>, 
+    witness_handle_rx: Option<Receiver<Bytes>>, 
+    witness_command_tx: UnboundedSender<WitnessHandleCommand<T>>,
+    witness_command_rx: Option<UnboundedReceiver<WitnessHandleCommand<T>>>,
+    proof_tx: Sender<AggregationProof<T>>,
+    proofs: AggregationProofs<T>,
+    reliable_command_tx: UnboundedSender<ReliableHandleCommand>,
+    reliable_command_rx: Option<UnboundedReceiver<ReliableHandleCommand>>,
+    event_tx: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
 }
 
 impl<T> AggregatedWitnessCommunicator<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    fn new(transmitters: Vec<Sender<String>>, receiver: Receiver<String>, 
-            thread_count: u32, id: u32, reliable_handle_transmitters: Vec<Sender<String>>, reliable_handle_rx: Receiver<String>, witness_handle_transmitters: Vec<Sender<String>>, witness_handle_rx: Receiver<String>) -> Self {
-        let basic_channels = MessageChannels::new(transmitters.clone());
-        let signal_channels = SignalChannels::new(reliable_handle_transmitters.clone());
-        let report_channels = ReportChannels::new(witness_handle_transmitters.clone());
+    fn new(transmitters: Vec<Sender<Bytes>>, lanes: LaneReceivers, id: u32, 
+            witness_command_tx: UnboundedSender<WitnessHandleCommand<T>>, witness_command_rx: UnboundedReceiver<WitnessHandleCommand<T>>, proof_tx: Sender<AggregationProof<T>>, proof_rx: Receiver<AggregationProof<T>>, config: ClusterConfig) -> Self {
+        let basic_channels = MessageChannels::new(transmitters.clone()).with_codec(config.get_codec());
+        let metrics = lanes.metrics;
+        let signal_channels = SignalChannels::new(transmitters.clone()).with_dissemination(id, &config).with_metrics(&metrics).with_signing(id, &config).with_journal(id, &config).with_codec(config.get_codec()).with_batching(&config);
+        let report_channels = ReportChannels::new(transmitters).with_codec(config.get_codec());
 
-        let queues = BasicQueues::new(receiver, thread_count);
-        let reliable_handle_rx = Some(reliable_handle_rx);
-        let witness_handle_rx = Some(witness_handle_rx);
+        let (event_tx, _) = broadcast::channel(config.get_buffer_size());
+        let queues = BasicQueues::new(lanes.basic, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy())
+            .with_validation(id, &config).with_metrics(metrics.clone()).with_event_channel(event_tx.clone());
+        let (reliable_command_tx, reliable_command_rx) = mpsc::unbounded_channel();
+        let reliable_command_rx = Some(reliable_command_rx);
+        let delivery_watches = DeliveryWatches::new();
+        let reliable_handle_rx = Some(lanes.signal);
+        let witness_handle_rx = Some(lanes.report);
+        let witness_command_rx = Some(witness_command_rx);
+        let proofs = AggregationProofs::new(proof_rx);
+        let polled_handles = PolledHandles::new(lanes.demultiplexer);
 
         Self {
             id, 
@@ -121,6 +194,17 @@ where
             queues,
             reliable_handle_rx,
             witness_handle_rx,
+            witness_command_tx,
+            witness_command_rx,
+            proof_tx,
+            proofs,
+            reliable_command_tx,
+            reliable_command_rx,
+            event_tx,
+            metrics,
+            delivery_watches,
+            polled_handles,
+            config,
         }
     }
 }
@@ -145,9 +229,9 @@ where
     //
     // # Returns:
     // * A future that completes once the broadcast has been enqueued.
-    fn aggregated_witness_broadcast(&mut self, message: T, round_number: u32) -> impl Future<Output = ()> {
+    fn aggregated_witness_broadcast(&mut self, message: T, round_number: Round) -> impl Future<Output = ()> {
         let protocol_information = String::from("aggregated witness");
-        let instance_number = 0; 
+        let instance_number = Instance(0);
         let sent_message = Message::new(protocol_information, *self.get_id(), message, None, Some(instance_number), round_number);
         let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
         self.get_signal_channels().broadcast_signal(input)
@@ -157,26 +241,147 @@ where
     // This function collects the messages delivered for the specified round of the aggregated witness communication protocol 
     // from the communicator’s `BasicQueues`.
     // Ensures the object retrieved is a collection (`Vec<Message>`) and returns it; otherwise, panic. 
+    // Collecting a round again answers according to the configured `RepeatedCollectPolicy`.
+    // Also panics if the round cannot be collected (see `try_aggregated_witness_collect`).
     //
     // # Parameters:
     // * round_number - The round of the protocol this collection belongs to.
     //
     // # Returns:
     // * A `Vec<Message>` containing the collected witness messages.
-    async fn aggregated_witness_collect(&mut self, round_number: u32) -> Vec<Message<T>>{
+    async fn aggregated_witness_collect(&mut self, round_number: Round) -> Vec<Message<T>>{
+        match self.try_aggregated_witness_collect(round_number).await {
+            Ok(collection) => collection,
+            Err(error) => panic!("Error: aggregated witness round {round_number} could not be collected: {error:?}"),
+        }
+    }
+
+    // # Method Description:
+    // This method collects the round like `aggregated_witness_collect`, in the `Delivered` envelope shared by
+    // every protocol.
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // # Returns:
+    // * The `Delivered` collection of the round.
+    async fn aggregated_witness_collect_delivered(&mut self, round_number: Round) -> Delivered<T> {
+        let messages = self.aggregated_witness_collect(round_number).await;
+        Delivered::from_collection(*self.get_id(), String::from("aggregated witness"), round_number, messages)
+    }
+
+    // # Method Description:
+    // This method collects the round like `aggregated_witness_collect`, but returns an error instead of panicking if
+    // the round was aborted by an undecodable frame, which only happens under `DecodePolicy::Strict`, or was
+    // already collected under `RepeatedCollectPolicy::AlreadyConsumed`.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    //
+    // # Returns:
+    // * The collected `Message`s, or the `CollectError` preventing the collection.
+    async fn try_aggregated_witness_collect(&mut self, round_number: Round) -> Result<Vec<Message<T>>, CollectError> {
         let protocol_information = String::from("aggregated witness");
         let thread_id = self.get_id().clone();
 
-        match self.get_queues().basic_recv(Some(thread_id), protocol_information, Some(0), round_number).await {
+        match self.get_queues().collect_round(thread_id, protocol_information, Some(Instance(0)), round_number).await? {
             RecvObject::Message(_) => {panic!("Error: retreived Message instead of Vec<Message>")},
             RecvObject::Collection(report) => {
-                println!("aggregated witness collected: {:?}", &report.get_messages());    
-                let collection = report.get_messages().clone();
-                return collection;
+                log!(info, "aggregated witness collected: {:?}", &report.get_messages());    
+                Ok(report.get_messages().clone())
             },
         }
     }
 
+    // # Method Description:
+    // This method collects the round like `try_aggregated_witness_collect`, but stops waiting after a timeout,
+    // e.g. so that an application can retry the round or change views on top of it. A round delivered after
+    // the timeout can still be collected.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // * timeout - How long to wait for the round.
+    //
+    // # Returns:
+    // * The collected `Message`s, or the `CollectError` preventing the collection (`CollectError::Timeout` if
+    //   the round was not delivered in time).
+    async fn aggregated_witness_collect_with_timeout(&mut self, round_number: Round, timeout: Duration) -> Result<Vec<Message<T>>, CollectError> {
+        match tokio::time::timeout(timeout, self.try_aggregated_witness_collect(round_number)).await {
+            Ok(collection) => collection,
+            Err(_) => Err(CollectError::Timeout(String::from("aggregated witness"), round_number)),
+        }
+    }
+
+    // # Function Description: 
+    // This function subscribes to the values delivered by the specified round of the aggregated witness 
+    // protocol. The subscription is served by the witness handle and succeeds regardless of whether the 
+    // round was delivered before or after the call.
+    //
+    // # Parameters:
+    // * round_number - The round of the protocol to subscribe to.
+    //
+    // # Returns:
+    // * A `oneshot::Receiver` that yields the values delivered in the round.
+    fn aggregated_witness_subscribe(&self, round_number: Round) -> oneshot::Receiver<Vec<Message<T>>> {
+        let protocol_information = String::from("aggregated witness");
+        let (responder, subscription) = oneshot::channel();
+        let command = WitnessHandleCommand::Subscribe(protocol_information, round_number, responder);
+        let _ = self.get_witness_command_channel().send(command);
+        subscription
+    }
+
+    // # Function Description: 
+    // This function subscribes to the values held by the witness handle when the specified aggregation level 
+    // of a round completed (level 1: witnesses, level 2: aggregated witnesses, and so on up to the configured 
+    // aggregation depth), e.g. to measure how each level of the hierarchy progresses.
+    //
+    // # Parameters:
+    // * round_number - The round of the protocol to subscribe to.
+    // * level - The aggregation level, between 1 and the configured aggregation depth.
+    //
+    // # Returns:
+    // * A `oneshot::Receiver` that yields the values held when the level completed.
+    fn aggregated_witness_subscribe_level(&self, round_number: Round, level: u32) -> oneshot::Receiver<Vec<Message<T>>> {
+        let aggregation_depth = self.get_config().get_aggregation_depth();
+        if level == 0 || level > aggregation_depth {
+            panic!("Error: level {level} outside of the aggregation depth ({aggregation_depth})");
+        }
+        let (responder, subscription) = oneshot::channel();
+        let command = WitnessHandleCommand::Subscribe(level_protocol_information(level), round_number, responder);
+        let _ = self.get_witness_command_channel().send(command);
+        subscription
+    }
+
+    // # Function Description: 
+    // This function collects the values held by the witness handle when the specified aggregation level of a 
+    // round completed. Collecting the last level is equivalent to `aggregated_witness_collect`, but levels may
+    // be collected in any order and more than once.
+    //
+    // # Parameters:
+    // * round_number - The round of the protocol this collection belongs to.
+    // * level - The aggregation level, between 1 and the configured aggregation depth.
+    //
+    // # Returns:
+    // * A `Vec<Message>` containing the values held when the level completed.
+    async fn aggregated_witness_collect_level(&mut self, round_number: Round, level: u32) -> Vec<Message<T>> {
+        match self.aggregated_witness_subscribe_level(round_number, level).await {
+            Ok(collection) => collection,
+            Err(_) => panic!("Error: witness handle terminated before level {level} of round {round_number} completed"),
+        }
+    }
+
+    // # Function Description: 
+    // This function retrieves the `AggregationProof` produced by the witness handle once the aggregated 
+    // witness quorum of the specified round completed, blocking until it is available. The proof is 
+    // self-contained and may be serialized with `write_json` for an external verifier.
+    //
+    // # Parameters:
+    // * round_number - The round of the protocol the proof belongs to.
+    //
+    // # Returns:
+    // * The `AggregationProof` justifying the values delivered in the round.
+    async fn aggregated_witness_proof(&mut self, round_number: Round) -> AggregationProof<T> {
+        self.get_aggregation_proofs().recv(round_number).await
+    }
+
     // # Function Description:
     // This function iterates over all aggregated reports in the given round content and attempts to 
     // upgrade them into aggregated witnesses if their component reports are present in 
@@ -184,12 +389,13 @@ where
     //
     // # Parameters:
     // * thread_id - The ID of the calling thread.
+    // * thresholds - The thresholds of the cluster, giving the weight every aggregated witness counts for.
     // * count - Mutable reference to the round’s count tracker (`WitnessRoundCount`).
     // * content - Mutable reference to the round’s content tracker (`WitnessRoundContent`).
-    fn update_aggregated_witnesses(thread_id: u32, count: &mut WitnessRoundCount, content: &mut WitnessRoundContent<T>) {
+    fn update_aggregated_witnesses(thread_id: u32, thresholds: &Thresholds, count: &mut WitnessRoundCount, content: &mut WitnessRoundContent<T>) {
         for aggregated_report in &mut content.aggregated_reports {
             if aggregated_report.get_report_type() == &ReportType::Report {
-                Self::initialize_aggregated_witnesses(thread_id, aggregated_report, &mut content.aggregated_witnesses, count, content.witnesses.clone());
+                Self::initialize_aggregated_witnesses(thread_id, thresholds, aggregated_report, &mut content.aggregated_witnesses, count, content.witnesses.clone());
             }
         }
     }
@@ -197,15 +403,16 @@ where
     // # Function Description: 
     // This function checks whether the reports within a given aggregated report form a subset of the 
     // known witnesses. If so, converts the aggregated report’s type into `Witness`, 
-    // adds it to the aggregated witnesses list, and updates the count.
+    // adds it to the aggregated witnesses list, and adds the weight of its sender to the count.
     //
     // # Parameters:
     // * thread_id - The ID of the calling thread.
+    // * thresholds - The thresholds of the cluster, giving the weight every aggregated witness counts for.
     // * aggregated_report - The mutable aggregated report candidate.
     // * aggregated_witnesses - The collection of aggregated witnesses to update.
     // * count - Mutable reference to the round’s count tracker.
     // * witnesses - The set of known witness reports for comparison.
-    fn initialize_aggregated_witnesses(thread_id: u32, aggregated_report: &mut AggregatedReport<T>, aggregated_witnesses: &mut Vec<AggregatedReport<T>>,count: &mut WitnessRoundCount, witnesses: Vec<Report<T>>) {
+    fn initialize_aggregated_witnesses(thread_id: u32, thresholds: &Thresholds, aggregated_report: &mut AggregatedReport<T>, aggregated_witnesses: &mut Vec<AggregatedReport<T>>,count: &mut WitnessRoundCount, witnesses: Vec<Report<T>>) {
         let witnesses_set: HashSet<Report<T>> = witnesses.into_iter().collect();
         let aggregated_report_set: HashSet<Report<T>> = aggregated_report.get_reports().clone().into_iter().collect();
 
@@ -213,11 +420,37 @@ where
             aggregated_report.report_type = ReportType::Witness;
             aggregated_witnesses.push(aggregated_report.clone());
 
-            println!("id: {thread_id}: converted aggregated report by id: {} to an aggregated witness", aggregated_report.get_id());
-            count.aggregated_witnesses += 1; 
+            log!(debug, "id: {thread_id}: converted aggregated report by id: {} to an aggregated witness", aggregated_report.get_id());
+            count.aggregated_witnesses += thresholds.get_weight(aggregated_report.get_id()); 
         }       
     }
 
+    // # Function Description:
+    // This function iterates over the aggregated reports of an aggregation level above 2 and upgrades them
+    // into aggregated witnesses of the level once every aggregated report they combine is a known
+    // aggregated witness of the level below.
+    //
+    // # Parameters:
+    // * thread_id - The ID of the calling thread.
+    // * thresholds - The thresholds of the cluster, giving the weight every aggregated witness counts for.
+    // * count - Mutable reference to the round’s count tracker (`WitnessRoundCount`).
+    // * content - Mutable reference to the round’s content tracker (`WitnessRoundContent`).
+    // * level - The aggregation level, above 2.
+    fn update_nested_witnesses(thread_id: u32, thresholds: &Thresholds, count: &mut WitnessRoundCount, content: &mut WitnessRoundContent<T>, level: u32) {
+        let lower_witnesses = content.get_level_witnesses(level - 1).to_vec();
+        let Some(nested_reports) = content.nested_reports.get_mut(&level) else { return };
+
+        for nested_report in nested_reports.iter_mut().filter(|nested_report| nested_report.get_report_type() == &ReportType::Report) {
+            if nested_report.get_aggregated_reports().iter().all(|aggregated_report| lower_witnesses.contains(aggregated_report)) {
+                nested_report.report_type = ReportType::Witness;
+                content.nested_witnesses.entry(level).or_default().push(nested_report.clone());
+
+                log!(debug, "id: {thread_id}: converted level {level} aggregated report by id: {} to an aggregated witness", nested_report.get_id());
+                *count.nested_witnesses.entry(level).or_default() += thresholds.get_weight(nested_report.get_id());
+            }
+        }
+    }
+
     // # Function Description
     // This function constructs a new aggregated report from the thread’s collected witnesses 
     // and reliably broadcasts it using the provided `SignalChannels`.
@@ -227,23 +460,476 @@ where
     // * thread_signal_channel - Reference to the thread’s signal channels for broadcasting.
     // * content - Mutable reference to the round’s content.
     // * round_number - The round of the protocol this broadcast belongs to.
+    // * statistics - The statistics embedded in the aggregated report, if any.
     //
     // # Returns:
     // * A future that completes once the broadcast has been enqueued.
-    async fn reliable_broadcast_aggregated_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, round_number: u32){
+    async fn reliable_broadcast_aggregated_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, round_number: Round, statistics: Option<FrameStatistics>){
         let protocol_information = String::from("aggregated witness");
-        let instance_number = 0; 
-        let aggregated_report = AggregatedReport::new(ReportType::Report, protocol_information, thread_id, content.witnesses.clone(), instance_number, round_number); 
+        let instance_number = Instance(0);
+        let aggregated_report = AggregatedReport::new(ReportType::Report, protocol_information, thread_id, content.witnesses.clone(), instance_number, round_number)
+            .with_statistics(statistics); 
         let input = Signal::new(SignalType::Input, ObjectContent::AggregatedReport(aggregated_report.clone()), aggregated_report.get_instance_number(), aggregated_report.get_round_number());
-        println!("id: {thread_id}, broadcasting aggregated report...");
+        log!(debug, "id: {thread_id}, broadcasting aggregated report...");
         thread_signal_channel.broadcast_signal(input).await;
     }
+
+    // # Function Description
+    // This function constructs a new aggregated report of an aggregation level above 2 from the thread’s 
+    // aggregated witnesses of the level below, and reliably broadcasts it.
+    //
+    // # Parameters:
+    // * thread_id - The ID of the calling thread.
+    // * thread_signal_channel - Reference to the thread’s signal channels for broadcasting.
+    // * content - Mutable reference to the round’s content.
+    // * level - The aggregation level of the report, above 2.
+    // * round_number - The round of the protocol this broadcast belongs to.
+    // * statistics - The statistics embedded in the aggregated report, if any.
+    async fn reliable_broadcast_nested_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, level: u32, round_number: Round, statistics: Option<FrameStatistics>){
+        let protocol_information = String::from("aggregated witness");
+        let instance_number = Instance(0);
+        let nested_report = AggregatedReport::new_nested(level, ReportType::Report, protocol_information, thread_id, content.get_level_witnesses(level - 1).to_vec(), instance_number, round_number)
+            .with_statistics(statistics); 
+        let input = Signal::new(SignalType::Input, ObjectContent::AggregatedReport(nested_report), instance_number, round_number);
+        log!(debug, "id: {thread_id}, broadcasting level {level} aggregated report...");
+        thread_signal_channel.broadcast_signal(input).await;
+    }
+
+    fn get_aggregation_proofs(&mut self) -> &mut AggregationProofs<T>;
+}
+
+// # Function Description:
+// This function names the subscriptions to an aggregation level of the aggregated witness protocol.
+//
+// # Parameters:
+// * level - The aggregation level.
+//
+// # Returns:
+// * The protocol information under which the completion of the level is recorded.
+fn level_protocol_information(level: u32) -> String {
+    format!("aggregated witness level {level}")
+}
+
+// # Struct Description:
+// This struct holds the state of a thread's aggregated witness handle and processes the witness-related
+// objects (`Message`, `Report`, `AggregatedReport`) and commands it receives, for each round.
+// Reports and aggregated reports that cannot yet be validated are buffered in their round and 
+// re-evaluated whenever the values or witnesses they depend on arrive, and every delivered round
+// is recorded to serve round subscriptions made at any time. Collected values follow the configured
+// `ValueOrdering`. Aggregated witness rounds run the configured number of aggregation levels: every
+// completed level is recorded for level subscriptions and feeds the report of the level above, and
+// the round is delivered once the last level completes. Delivered rounds are collected according to the
+// configured `RetentionPolicy`, after which they can no longer be inspected nor subscribed to, and their
+// late frames are ignored.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * thread_channel - The channels on which delivered rounds are sent.
+// * thread_signal_channel - The channels on which reports and aggregated reports are reliably broadcast.
+// * thread_count - The number of threads in the cluster.
+// * metrics - The thread's metrics, in which the status of every round is recorded.
+// * decode_failures - The reporter of the frames that cannot be decoded.
+// * event_channel - The channel on which `Event::RoundCollected` events are published.
+// * receiver - The receiver of the thread's `Report` lane.
+// * command_receiver - The receiver of the commands sent to the handle.
+// * proof_tx - The transmitter on which the aggregation proof of every delivered round is sent.
+// * processing_delay - The processing delay of the thread's `Fault::Slowdown`, if any.
+// * value_ordering - The order in which the values of a round are kept.
+// * faulty_threads - The number of faulty threads tolerated, recorded in the aggregation proofs.
+// * validity_threshold - The number of values (and witnesses) needed to report (and deliver) a round.
+// * thresholds - The thresholds of the cluster, giving the weight every value and witness counts for.
+// * aggregation_depth - The number of aggregation levels run by aggregated witness rounds.
+// * level_thresholds - The number of witnesses needed to complete every aggregation level, indexed from level 1.
+// * witness_monitor - The monitor of every round, by round number.
+// * subscriptions - The round subscriptions made so far.
+// * retention - The tracker of the delivered rounds, which decides when they are collected.
+// * trace_sampler - The sampler deciding which rounds record their round status.
+// * frame_statistics - Whether the reports and aggregated reports broadcast embed their `FrameStatistics`.
+// * stopped - Whether the handle was shut down (see `WitnessCommunication::shutdown_witness_handle`).
+// * running - The record of the handle running in the thread's `HealthProbe`, marked stopped once the handle stops.
+pub struct AggregatedWitnessHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    thread_id: u32,
+    thread_channel: MessageChannels<T>,
+    thread_signal_channel: SignalChannels<T>,
+    thread_count: u32,
+    metrics: Metrics,
+    decode_failures: DecodeFailureReporter<T>,
+    event_channel: broadcast::Sender<Event<T>>,
+    receiver: Receiver<Bytes>,
+    command_receiver: UnboundedReceiver<WitnessHandleCommand<T>>,
+    proof_tx: Sender<AggregationProof<T>>,
+    processing_delay: Option<Duration>,
+    value_ordering: ValueOrdering,
+    faulty_threads: u64,
+    validity_threshold: u64,
+    thresholds: Thresholds,
+    aggregation_depth: u32,
+    level_thresholds: Vec<u64>,
+    witness_monitor: HashMap<Round, WitnessRoundMonitor<T>>,
+    subscriptions: RoundSubscriptions<T>,
+    retention: RetentionTracker<Round>,
+    trace_sampler: TraceSampler,
+    frame_statistics: bool,
+    stopped: bool,
+    running: RunningHandle,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<T, C> AggregatedWitnessHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: AggregatedWitnessCommunication<T> + 'static,
+{
+    fn new(communicator: &mut C, proof_tx: Sender<AggregationProof<T>>) -> Self {
+        let thread_id = *communicator.get_id();
+        let thread_channel = communicator.get_channels().clone();
+        let thread_count = thread_channel.get_channels().len() as u32;
+        let thresholds = communicator.get_config().get_thresholds().clone();
+        let aggregation_depth = communicator.get_config().get_aggregation_depth();
+
+        Self {
+            thread_id,
+            thread_channel,
+            thread_signal_channel: communicator.get_signal_channels().clone(),
+            thread_count,
+            metrics: communicator.get_metrics().clone(),
+            decode_failures: DecodeFailureReporter::new(communicator, Lane::Report),
+            event_channel: communicator.get_event_channel().clone(),
+            receiver: communicator.take_witness_handle_rx(),
+            command_receiver: communicator.take_witness_command_rx(),
+            proof_tx,
+            processing_delay: communicator.get_config().get_fault_script().get_processing_delay(thread_id),
+            value_ordering: communicator.get_config().get_value_ordering(),
+            faulty_threads: thresholds.get_faulty_threads(),
+            validity_threshold: thresholds.get_validity_threshold(),
+            aggregation_depth,
+            level_thresholds: (1..=aggregation_depth.max(2)).map(|level| thresholds.get_level_threshold(level)).collect(),
+            thresholds,
+            witness_monitor: HashMap::new(),
+            subscriptions: RoundSubscriptions::new(),
+            retention: RetentionTracker::new(communicator.get_config().get_retention_policy()),
+            trace_sampler: communicator.get_config().get_trace_sampler(),
+            frame_statistics: communicator.get_config().get_frame_statistics(),
+            stopped: false,
+            running: communicator.get_metrics().get_health_probe().start_handle("aggregated witness"),
+            _marker: PhantomData,
+        }
+    }
+
+    // # Method Description:
+    // This method processes commands and frames as they arrive, until the handle's task is aborted or the
+    // handle is shut down.
+    async fn run(mut self) {
+        loop {
+            tokio::select! {
+                Some(command) = self.command_receiver.recv() => {
+                    if let Some(responder) = self.process_command(command) {
+                        self.shutdown(responder).await;
+                        return
+                    }
+                }
+                Some(received_object) = self.receiver.recv() => {
+                    if let Some(delay) = self.processing_delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    self.process_object(received_object).await;
+                }
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method processes a single command sent to the handle.
+    //
+    // # Parameters:
+    // * command - The command.
+    //
+    // # Returns:
+    // * The responder of a `Shutdown` command, which the caller must answer by shutting the handle down.
+    fn process_command(&mut self, command: WitnessHandleCommand<T>) -> Option<ShutdownResponder> {
+        match command {
+            // the responder of a collected round is dropped, closing the subscription
+            WitnessHandleCommand::Subscribe(protocol_information, round_number, responder) => {
+                if !self.retention.is_collected(&round_number) {
+                    self.subscriptions.subscribe(protocol_information, round_number, responder);
+                }
+            },
+            WitnessHandleCommand::Inspect(round_number, responder) => {
+                let _ = responder.send(self.witness_monitor.get(&round_number).map(|monitor| monitor.snapshot()));
+            },
+            WitnessHandleCommand::Snapshot(responder) => {
+                let _ = responder.send(self.witness_monitor.iter().map(|(round_number, monitor)| (*round_number, monitor.clone())).collect());
+            },
+            WitnessHandleCommand::Shutdown(responder) => return Some(responder),
+        }
+        None
+    }
+
+    // # Method Description:
+    // This method shuts the handle down: it processes the frames already received, then reports the rounds
+    // whose last aggregation level was not delivered.
+    //
+    // # Parameters:
+    // * responder - The sender on which the `ShutdownReport` is answered.
+    async fn shutdown(&mut self, responder: ShutdownResponder) {
+        let mut flushed = 0;
+        while let Ok(received_object) = self.receiver.try_recv() {
+            self.process_object(received_object).await;
+            flushed += 1;
+        }
+        let mut open: Vec<Round> = self.witness_monitor.iter()
+            .filter(|(_, monitor)| match self.aggregation_depth {
+                1 => !monitor.state.witnesses,
+                2 => !monitor.state.aggregated_witnesses,
+                aggregation_depth => !monitor.state.is_level_complete(aggregation_depth),
+            })
+            .map(|(round_number, _)| *round_number)
+            .collect();
+        open.sort();
+        log!(debug, "id: {}, aggregated witness handle stopped, {} frames flushed, {} rounds open", self.thread_id, flushed, open.len());
+        self.stopped = true;
+        self.running.stop();
+        let open = open.iter().map(|round_number| format!("aggregated witness round {round_number}")).collect();
+        let _ = responder.send(ShutdownReport::new(self.thread_id, String::from("aggregated witness"), flushed, open));
+    }
+
+    // # Method Description:
+    // This method processes a single value, report, or aggregated report received on the thread's `Report` lane.
+    //
+    // # Parameters:
+    // * received_object - The serialized `Message`, `Report`, or `AggregatedReport`.
+    async fn process_object(&mut self, received_object: Bytes) {
+        self.metrics.get_health_probe().record_frame();
+        let object: ObjectContent<T>; 
+        if let Ok(message) = Message::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::Message(message);
+        } else if let Ok(report) = Report::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::Report(report);
+        } else if let Ok(aggregated_report) = AggregatedReport::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::AggregatedReport(aggregated_report);
+        } else {
+            self.decode_failures.report(&received_object).await;
+            return
+        }
+        // everything logged while the object is processed is emitted in the span of its round
+        let span = tracing::debug_span!("round", protocol = "aggregated witness", round = %object.get_round_number(), node = self.thread_id);
+        self.process_round_object(object).instrument(span).await;
+    }
+
+    // # Method Description:
+    // This method processes a value, report, or aggregated report decoded by `process_object`.
+    //
+    // # Parameters:
+    // * object - The value, report, or aggregated report.
+    async fn process_round_object(&mut self, object: ObjectContent<T>) {
+        let round_number =  object.get_round_number(); 
+        let protocol_information = object.get_protocol_information().clone();
+        for collected in self.retention.collect() {
+            self.witness_monitor.remove(&collected);
+            self.subscriptions.forget(collected);
+            let _ = self.event_channel.send(Event::RoundCollected(self.thread_id, String::from("aggregated witness"), collected));
+        }
+        if self.retention.is_collected(&round_number) {
+            return
+        }
+        let _ =  self.witness_monitor.entry(round_number).or_insert(WitnessRoundMonitor::new());
+
+        let instance = self.witness_monitor.get_mut(&round_number).unwrap(); 
+        let content = &mut instance.content;
+        let state = &mut instance.state;
+        let count = &mut instance.count;
+
+        match object {
+            ObjectContent::Message(message) => {
+                if !content.values.contains(&message) {
+                    count.values += self.thresholds.get_weight(message.get_id());  
+                    self.value_ordering.insert(&mut content.values, message);
+                    C::update_witnesses(self.thread_id, &self.thresholds, count, content);
+                    C::update_aggregated_witnesses(self.thread_id, &self.thresholds, count, content);
+                }
+            },
+            ObjectContent::Report(report) => {
+                if !content.reports.contains(&report) {
+                    content.reports.push(report);
+                    count.reports += 1;  
+                    let report = content.reports.get_mut((count.reports - 1) as usize).unwrap(); 
+                    C::initialize_witnesses(self.thread_id, &self.thresholds, report, &mut content.witnesses, count, content.values.clone()); 
+                    C::update_aggregated_witnesses(self.thread_id, &self.thresholds, count, content);
+                }
+            },
+            ObjectContent::AggregatedReport(nested_report) if nested_report.get_level() > 2 => {
+                let level = nested_report.get_level();
+                let nested_reports = content.nested_reports.entry(level).or_default();
+                if level <= self.aggregation_depth && !nested_reports.contains(&nested_report) {
+                    nested_reports.push(nested_report);
+                    *count.nested_reports.entry(level).or_default() += 1;
+                }
+            },
+            ObjectContent::AggregatedReport(aggregated_report) => {
+                if !content.aggregated_reports.contains(&aggregated_report) {
+                    content.aggregated_reports.push(aggregated_report);
+                    count.aggregated_reports += 1;  
+                    let aggregated_report = content.aggregated_reports.get_mut((count.aggregated_reports - 1) as usize).unwrap(); 
+                    C::initialize_aggregated_witnesses(self.thread_id, &self.thresholds, aggregated_report, &mut content.aggregated_witnesses, count, content.witnesses.clone()); 
+                }
+            },
+            ObjectContent::BarycentricReport(_) => {
+                panic!("Error: received incompatible object type (BarycentricReport) for aggregated witness broadcast");
+            },
+            ObjectContent::Commitment(_) => {
+                panic!("Error: received incompatible object type (Commitment) for aggregated witness broadcast");
+            },
+            ObjectContent::Fragment(_) => {
+                panic!("Error: received incompatible object type (Fragment) for aggregated witness broadcast");
+            },
+            ObjectContent::Extension { .. } => {
+                panic!("Error: received incompatible object type (Extension) for aggregated witness broadcast");
+            },
+        }
+
+        for level in 3..=self.aggregation_depth {
+            C::update_nested_witnesses(self.thread_id, &self.thresholds, count, content, level);
+        }
+
+        if count.values >= self.validity_threshold && state.report == false {
+            let statistics = self.frame_statistics.then(|| FrameStatistics::new(content.values.len(), content.witnesses.len(), &self.thresholds));
+            C::reliable_broadcast_report(self.thread_id, &self.thread_signal_channel, content, None, round_number, protocol_information.clone(), statistics).await;
+            state.report = true; 
+        }
+
+        if count.witnesses >= self.level_thresholds[0] && state.witnesses == false {
+            if protocol_information == "witness"{
+                let protocol_information = String::from("witness");
+                let instance_number = Instance(0);
+                let values = Report::new(ReportType::Witness, protocol_information.clone(), self.thread_id, content.values.clone(), None, instance_number, round_number); 
+                self.thread_channel.send_values(self.thread_id, values).await;
+                self.subscriptions.complete(protocol_information, round_number, &content.values);
+                state.witnesses = true; 
+            } else {
+                self.subscriptions.complete(level_protocol_information(1), round_number, &content.values);
+                if self.aggregation_depth == 1 {
+                    Self::deliver(self.thread_id, &self.thread_channel, &mut self.subscriptions, round_number, &content.values).await;
+                } else {
+                    let statistics = self.frame_statistics.then(|| FrameStatistics::new(content.values.len(), content.witnesses.len(), &self.thresholds));
+                    C::reliable_broadcast_aggregated_report(self.thread_id, &self.thread_signal_channel, content, round_number, statistics).await;
+                }
+                state.witnesses = true; 
+            }
+        }
+
+        if count.aggregated_witnesses >= self.level_thresholds[1] && state.aggregated_witnesses == false {
+            self.subscriptions.complete(level_protocol_information(2), round_number, &content.values);
+            if self.aggregation_depth == 2 {
+                Self::deliver(self.thread_id, &self.thread_channel, &mut self.subscriptions, round_number, &content.values).await;
+            } else {
+                let statistics = self.frame_statistics.then(|| FrameStatistics::new(content.values.len(), content.get_level_witnesses(2).len(), &self.thresholds));
+                C::reliable_broadcast_nested_report(self.thread_id, &self.thread_signal_channel, content, 3, round_number, statistics).await;
+            }
+
+            let proof = AggregationProof::new(self.thread_id, round_number, self.thread_count, self.faulty_threads, self.validity_threshold, content)
+                .with_weights(self.thresholds.get_weights().clone());
+            let _ = self.proof_tx.send(proof).await;
+            state.aggregated_witnesses = true; 
+        }
+
+        for level in 3..=self.aggregation_depth {
+            if count.get_level_witnesses(level) >= self.level_thresholds[(level - 1) as usize] && !state.is_level_complete(level) {
+                self.subscriptions.complete(level_protocol_information(level), round_number, &content.values);
+                if level == self.aggregation_depth {
+                    Self::deliver(self.thread_id, &self.thread_channel, &mut self.subscriptions, round_number, &content.values).await;
+                } else {
+                    let statistics = self.frame_statistics.then(|| FrameStatistics::new(content.values.len(), content.get_level_witnesses(level).len(), &self.thresholds));
+                    C::reliable_broadcast_nested_report(self.thread_id, &self.thread_signal_channel, content, level + 1, round_number, statistics).await;
+                }
+                state.nested_witnesses.insert(level);
+            }
+        }
+
+        let delivered = match (protocol_information.as_str(), self.aggregation_depth) {
+            ("witness", _) | (_, 1) => state.witnesses,
+            (_, 2) => state.aggregated_witnesses,
+            (_, aggregation_depth) => state.is_level_complete(aggregation_depth),
+        };
+        if delivered {
+            self.retention.complete(round_number);
+        }
+
+        let round_status = match protocol_information.as_str() {
+            "witness" => RoundStatus::new(protocol_information, None, round_number, delivered)
+                .with_stage("values", count.values, self.validity_threshold)
+                .with_stage("witnesses", count.witnesses, self.level_thresholds[0]),
+            _ => {
+                let mut round_status = RoundStatus::new(String::from("aggregated witness"), None, round_number, delivered)
+                    .with_stage("values", count.values, self.validity_threshold)
+                    .with_stage("witnesses", count.witnesses, self.level_thresholds[0]);
+                for level in 2..=self.aggregation_depth {
+                    round_status = round_status.with_stage(&format!("level {level} witnesses"), count.get_level_witnesses(level), self.level_thresholds[(level - 1) as usize]);
+                }
+                round_status
+            },
+        };
+        if self.trace_sampler.is_sampled(&format!("{}::round::{}", round_status.get_protocol_information(), round_number)) {
+            self.metrics.record_round_status(round_status);
+        }
+    }
+
+    // # Method Description:
+    // This method delivers an aggregated witness round, once its last aggregation level completed.
+    //
+    // # Parameters:
+    // * thread_id - The ID of the thread.
+    // * thread_channel - The channels on which the round is delivered.
+    // * subscriptions - The round subscriptions, completed with the delivered values.
+    // * round_number - The delivered round.
+    // * values - The values delivered in the round.
+    async fn deliver(thread_id: u32, thread_channel: &MessageChannels<T>, subscriptions: &mut RoundSubscriptions<T>, round_number: Round, values: &[Message<T>]) {
+        let protocol_information = String::from("aggregated witness");
+        let instance_number = Instance(0);
+        let report = Report::new(ReportType::Witness, protocol_information.clone(), thread_id, values.to_vec(), None, instance_number, round_number); 
+        thread_channel.send_values(thread_id, report).await;
+        subscriptions.complete(protocol_information, round_number, values);
+    }
+}
+
+#[async_trait]
+impl<T, C> PolledHandle for AggregatedWitnessHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: AggregatedWitnessCommunication<T> + 'static,
+{
+    async fn process_pending(&mut self) -> usize {
+        if self.stopped {
+            // commands sent to a stopped handle are dropped, so that their senders are not left waiting
+            while self.command_receiver.try_recv().is_ok() {}
+            return 0
+        }
+        let mut processed = 0;
+        while let Ok(command) = self.command_receiver.try_recv() {
+            processed += 1;
+            if let Some(responder) = self.process_command(command) {
+                self.shutdown(responder).await;
+                return processed
+            }
+        }
+        while let Ok(received_object) = self.receiver.try_recv() {
+            self.process_object(received_object).await;
+            processed += 1;
+        }
+        processed
+    }
 }
 
 impl<T> AggregatedWitnessCommunication<T> for AggregatedWitnessCommunicator<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
-{}
+{
+    fn get_aggregation_proofs(&mut self) -> &mut AggregationProofs<T> {
+        &mut self.proofs
+    }
+}
 
 #[async_trait]
 impl<T> WitnessCommunication<T> for AggregatedWitnessCommunicator<T>
@@ -252,114 +938,24 @@ where
 {
 
     // # Method Description: 
-    // This method spawns an asynchronous background task that listens for and processes incoming
-    // witness-related objects (`Message`, `Report`, `AggregatedReport`) for each round.
+    // This method starts the thread's aggregated witness handle, which listens for and processes incoming
+    // witness-related objects (`Message`, `Report`, `AggregatedReport`) for each round (see
+    // `AggregatedWitnessHandleLoop`). In `HandleMode::Spawned` the handle runs in its own task; in
+    // `HandleMode::Polling` it is parked in the communicator and only processes frames and commands when
+    // the communicator is driven.
     //
     // # Returns:
-    // * `JoinHandle<()>` — representing the spawned asynchronous task that runs indefinitely.
+    // * `JoinHandle<()>` — representing the spawned asynchronous task that runs indefinitely (already completed in polling mode).
 
     fn initialize_witness_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing aggregated witness handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let thread_count = thread_channel.get_channels().len() as u32; 
-        let mut receiver = self.take_witness_handle_rx(); 
-
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let mut witness_monitor: HashMap<u32, WitnessRoundMonitor<T>> = HashMap::new();
-    
-        let handle = tokio::spawn(async move {
-            loop  {
-                tokio::select! {
-                    Some(received_object) = receiver.recv() => {
-                        let object: ObjectContent<T>; 
-                        if let Ok(message) = Message::read_json(&received_object) {
-                            object = ObjectContent::Message(message);
-                        } else if let Ok(report) = Report::read_json(&received_object) {
-                            object = ObjectContent::Report(report);
-                        } else if let Ok(aggregated_report) = AggregatedReport::read_json(&received_object) {
-                            object = ObjectContent::AggregatedReport(aggregated_report);
-                        } else {
-                            continue
-                        }
-
-                        let round_number =  object.get_round_number(); 
-                        let protocol_information = object.get_protocol_information().clone();
-                        let _ =  witness_monitor.entry(round_number).or_insert(WitnessRoundMonitor::new());
-
-                        let instance = witness_monitor.get_mut(&round_number).unwrap(); 
-                        let content = &mut instance.content;
-                        let state = &mut instance.state;
-                        let count = &mut instance.count;
-
-                        match object {
-                            ObjectContent::Message(message) => {
-                                if !content.values.contains(&message) {
-                                    content.values.push(message);
-                                    count.values += 1;  
-
-                                    if count.values >= validity_threshold {
-                                        Self::update_witnesses(thread_id, count, content);
-                                    }
-                                    if count.aggregated_witnesses >= validity_threshold {
-                                        Self::update_aggregated_witnesses(thread_id, count, content);
-                                    }
-                                }
-                            },
-                            ObjectContent::Report(report) => {
-                                if !content.reports.contains(&report) {
-                                    content.reports.push(report);
-                                    count.reports += 1;  
-                                    let report = content.reports.get_mut((count.reports - 1) as usize).unwrap(); 
-                                    Self::initialize_witnesses(thread_id, report, &mut content.witnesses, count, content.values.clone()); 
-                                }
-                            },
-                            ObjectContent::AggregatedReport(aggregated_report) => {
-                                if !content.aggregated_reports.contains(&aggregated_report) {
-                                    content.aggregated_reports.push(aggregated_report);
-                                    count.aggregated_reports += 1;  
-                                    let aggregated_report = content.aggregated_reports.get_mut((count.aggregated_reports - 1) as usize).unwrap(); 
-                                    Self::initialize_aggregated_witnesses(thread_id, aggregated_report, &mut content.aggregated_witnesses, count, content.witnesses.clone()); 
-                                }
-                            },
-                            ObjectContent::BarycentricReport(_) => {
-                                panic!("Error: received incompatible object type (BarycentricReport) for aggregated witness broadcast");
-                            }
-                        }
-
-                        if count.values >= validity_threshold && state.report == false {
-                            Self::reliable_broadcast_report(thread_id, &thread_signal_channel, content, None, round_number, protocol_information.clone()).await;
-                            state.report = true; 
-                        }
-
-                        if count.witnesses >= validity_threshold && state.witnesses == false {
-                            if protocol_information == "witness"{
-                                let protocol_information = String::from("witness");
-                                let instance_number = 0; 
-                                let values = Report::new(ReportType::Witness, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number); 
-                                thread_channel.send_values(thread_id, values).await;
-                                state.witnesses = true; 
-                            } else {
-                                Self::reliable_broadcast_aggregated_report(thread_id, &thread_signal_channel, content, round_number).await;
-                                state.witnesses = true; 
-                            }
-                        }
-
-                        if count.aggregated_witnesses >= validity_threshold && state.aggregated_witnesses == false {
-                            let protocol_information = String::from("aggregated witness");
-                            let instance_number = 0; 
-                            let values = Report::new(ReportType::Witness, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number); 
-                            thread_channel.send_values(thread_id, values).await;
-                            state.aggregated_witnesses = true; 
-                        }
-                    }
-                }
-            }
-        });
-        handle
+        log!(debug, "initializing aggregated witness handle...");
+
+        let proof_tx = self.proof_tx.clone();
+        let witness_handle: AggregatedWitnessHandleLoop<T, Self> = AggregatedWitnessHandleLoop::new(self, proof_tx);
+        match self.get_config().get_handle_mode() {
+            HandleMode::Spawned => tokio::spawn(witness_handle.run()),
+            HandleMode::Polling => self.get_polled_handles().park(Box::new(witness_handle)),
+        }
     } 
 
 
@@ -373,15 +969,17 @@ where
     // * `content` — The `WitnessRoundContent` containing the collected values.
     // * `round_number` — The current round number of the witness protocol.
     // * `protocol_information` — String describing the active protocol type.
+    // * `statistics` — The statistics embedded in the report, if any.
     //
     // # Returns:
     // * `Future<()>` — resolves once the broadcast has been sent.
-    async fn reliable_broadcast_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, _dimension: Option<u32>, round_number: u32, protocol_information: String){
+    async fn reliable_broadcast_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, _dimension: Option<u32>, round_number: Round, protocol_information: String, statistics: Option<FrameStatistics>){
         let protocol_information = protocol_information;
-        let instance_number = 0; 
-        let report = Report::new(ReportType::Report, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number); 
+        let instance_number = Instance(0);
+        let report = Report::new(ReportType::Report, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number)
+            .with_statistics(statistics); 
         let input = Signal::new(SignalType::Input, ObjectContent::Report(report.clone()), report.get_instance_number(), report.get_round_number());
-        println!("id: {thread_id}, broadcasting report...");
+        log!(debug, "id: {thread_id}, broadcasting report...");
         thread_signal_channel.broadcast_signal(input).await;
     }
 
@@ -389,9 +987,17 @@ where
         &self.report_channels
     }
 
-    fn take_witness_handle_rx(&mut self) -> Receiver<String> {
+    fn take_witness_handle_rx(&mut self) -> Receiver<Bytes> {
         self.witness_handle_rx.take().unwrap()
     }
+
+    fn take_witness_command_rx(&mut self) -> UnboundedReceiver<WitnessHandleCommand<T>> {
+        self.witness_command_rx.take().unwrap()
+    }
+
+    fn get_witness_command_channel(&self) -> &UnboundedSender<WitnessHandleCommand<T>> {
+        &self.witness_command_tx
+    }
 }
 
 #[async_trait]
@@ -403,105 +1009,43 @@ where
         &self.signal_channels
     }
 
-    fn take_reliable_handle_rx(&mut self) -> Receiver<String> {
+    fn get_delivery_report_channels(&self) -> Option<&ReportChannels<T>> {
+        Some(&self.report_channels)
+    }
+
+    fn get_round_command_channel(&self) -> Option<&UnboundedSender<WitnessHandleCommand<T>>> {
+        Some(self.get_witness_command_channel())
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::from_config(self.get_config(), &[Protocol::Reliable, Protocol::AggregatedWitness])
+    }
+
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>> {
+        &self.event_tx
+    }
+
+    fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T> {
+        &self.delivery_watches
+    }
+
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes> {
         self.reliable_handle_rx.take().unwrap()
     }
 
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand> {
+        self.reliable_command_rx.take().unwrap()
+    }
 
-    // # Method Description: 
-    // This method spawns an asynchronous background task that manages the Reliable Broadcast protocol.
-    // It listens for signals (`Input`, `Echo`, `Vote`) on the reliable handle channel and
-    // enforces the reliable broadcast thresholds to ensure consistent message delivery.
-    //
-    // # Returns:
-    // * `JoinHandle<()>` — representing the spawned asynchronous task running the reliable broadcast.
-//
-    fn initialize_reliable_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing reliable handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let report_channel = self.get_report_channels().clone(); 
-        let thread_count = report_channel.get_handle_channels().len() as u32; 
-        let mut receiver = self.take_reliable_handle_rx(); 
-
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let agreement_threshold = faulty_threads + 1;
-        let mut reliable_broadcast_monitor: HashMap<String, ReliableInstanceMonitor> = HashMap::new();
-
-        let handle = tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    Some(received_signal) = receiver.recv() => {
-                        let signal = match Signal::read_json(&received_signal) {
-                            Ok(correct_signal) => correct_signal,
-                            Err(_)=> { continue },
-                        };
-
-                        let instance_id = Self::get_instance_id(thread_id, signal.clone()); 
-
-                        if let SignalType::Input = signal.get_signal() {
-                            match reliable_broadcast_monitor.get(&instance_id) {
-                                Some(_) => {
-                                    panic!("Error: instance id ({}) already used", instance_id)
-                                },
-                                None => {
-                                    reliable_broadcast_monitor.insert(instance_id.clone(), ReliableInstanceMonitor::new());
-                                },
-                            }
-                        }
-
-                        let instance = reliable_broadcast_monitor.get_mut(&instance_id).unwrap(); 
-                        let state = &mut instance.state; 
-                        let count = &mut instance.count; 
-
-                        match signal.get_signal()
-                        {
-                            SignalType::Input => {
-                                if state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Echo => {
-                                count.echo += 1;
-
-                                if count.echo >= validity_threshold && state.vote == false{
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else if count.echo >= agreement_threshold && state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Vote => {
-                                count.vote += 1;
-    
-                                if count.vote >= validity_threshold && state.deliver == false {
-                                    if signal.get_content().get_protocol_information() == "reliable" {
-                                        let channel = ChannelType::MessageChannels(thread_channel.clone());
-                                         Self::upon_vote(thread_id, channel, signal).await;
-                                    } else {
-                                        let channel = ChannelType::ReportChannels(report_channel.clone());
-                                        Self::upon_vote(thread_id, channel, signal).await;
-                                    }
-                                   
-                                    state.deliver = true;
-                                } else if count.vote >= agreement_threshold && state.vote == false {
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else { continue }
-                            }
-                        }
-                    }
-                }
-            }
-        });
-        handle
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand> {
+        &self.reliable_command_tx
     }
 
+
     // # Method Description: 
     // This method processes an `Input` signal in the reliable broadcast protocol,
     // converts the input into an `Echo` signal and broadcasts it.
@@ -547,7 +1091,7 @@ where
     // # Returns:
     // * `Future<()>` — resolves when the object has been delivered to the correct channel.
     async fn upon_vote(thread_id: u32, channel: ChannelType<T>, signal: Signal<T>)  {
-        let object = signal.get_content().clone(); 
+        let object = signal.into_content();
 
         match channel {
             ChannelType::MessageChannels(thread_channel) => {
@@ -558,7 +1102,7 @@ where
             ChannelType::ReportChannels(report_channel) => {
                 match object {
                     ObjectContent::Message(message) => {
-                        println!("sent: {:?}", &message.get_message());
+                        log!(trace, "sent: {:?}", &message.get_message());
                         report_channel.send_message(thread_id, message).await;     
                     }
                     ObjectContent::Report(report) => {
@@ -569,7 +1113,16 @@ where
                     },
                     ObjectContent::BarycentricReport(_) => {
                         panic!("Error: received incompatible object type (BarycentricReport) for aggregated witness broadcast");
-                    }
+                    },
+                    ObjectContent::Commitment(_) => {
+                        panic!("Error: received incompatible object type (Commitment) for aggregated witness broadcast");
+                    },
+                    ObjectContent::Fragment(_) => {
+                        panic!("Error: received incompatible object type (Fragment) for aggregated witness broadcast");
+                    },
+                    ObjectContent::Extension { .. } => {
+                        panic!("Error: received incompatible object type (Extension) for aggregated witness broadcast");
+                    },
                 }
             },
         }
@@ -591,28 +1144,51 @@ where
     fn get_id(& self) -> &u32 {
         &self.id
     }
+
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
 }
 
 // # Struct Description:
 // This struct represents a collection of individual `Report` objects combined
 // into a single higher-level structure, used in witness-based reliable communication
-// protocols for consensus and verification.
+// protocols for consensus and verification. At aggregation level 2, it combines witness
+// reports; at every level above, it combines the aggregated witnesses of the level below.
 //
 // # Fields:
 // * report_type - Defines the type of this aggregated report, such as `Report` or `Witness`.
 // * protocol_information - String metadata describing the protocol associated with this aggregated report.
 // * id - The identifier of the node or thread that created this aggregated report.
-// * reports - A vector of `Report` objects that were collected and combined.
+// * level - The aggregation level of the report (2 for the aggregated reports of witnesses).
+// * reports - A vector of `Report` objects that were collected and combined (level 2).
+// * aggregated_reports - A vector of aggregated witnesses of the level below that were combined (levels above 2).
 // * instance_number - The instance of the protocol execution this aggregated report belongs to.
 // * round_number - The communication round within the broadcast protocol to maintain ordering and separation.
+// * statistics - The statistics of the aggregated report when it was created, if its creator embeds them.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AggregatedReport<T>{
     report_type: ReportType,
     protocol_information: String, 
     id: u32, 
+    #[serde(default = "default_level")]
+    level: u32,
     reports: Vec<Report<T>>, 
-    instance_number: u32,
-    round_number: u32
+    #[serde(default = "Vec::new")]
+    aggregated_reports: Vec<AggregatedReport<T>>,
+    instance_number: Instance,
+    round_number: Round,
+    #[serde(default)]
+    statistics: Option<FrameStatistics>
+}
+
+// Aggregated reports serialized before aggregation levels were introduced are level 2 reports.
+fn default_level() -> u32 {
+    2
 }
 
 impl<T> AggregatedReport<T>
@@ -631,31 +1207,353 @@ where
         self.id
     }
 
+    pub fn get_level(&self) -> u32 {
+        self.level
+    }
+
     pub fn get_reports(&self) -> &Vec<Report<T>> {
         &self.reports
     }
 
-    pub fn get_instance_number(&self) -> u32 {
+    pub fn get_aggregated_reports(&self) -> &Vec<AggregatedReport<T>> {
+        &self.aggregated_reports
+    }
+
+    pub fn get_instance_number(&self) -> Instance {
         self.instance_number
     }
 
-    pub fn get_round_number(&self) -> u32 {
+    pub fn get_round_number(&self) -> Round {
         self.round_number
     }
 
-    pub fn new(report_type: ReportType, protocol_information: String, id: u32, reports: Vec<Report<T>>, instance_number: u32, round_number: u32) -> Self {
+    pub fn get_statistics(&self) -> Option<&FrameStatistics> {
+        self.statistics.as_ref()
+    }
+
+    pub fn new(report_type: ReportType, protocol_information: String, id: u32, reports: Vec<Report<T>>, instance_number: Instance, round_number: Round) -> Self {
         Self {
             report_type,
             protocol_information,
             id, 
+            level: 2,
             reports,
+            aggregated_reports: vec![],
+            instance_number,
+            round_number,
+            statistics: None
+        }
+    }
+
+    // # Method Description:
+    // This method creates an aggregated report above level 2, combining aggregated witnesses of the level below.
+    //
+    // # Parameters:
+    // * level - The aggregation level of the report, above 2.
+    // * report_type - The type of the report.
+    // * protocol_information - The protocol of the report.
+    // * id - The ID of the thread creating the report.
+    // * aggregated_reports - The aggregated witnesses of level `level - 1` combined in the report.
+    // * instance_number - The instance of the report.
+    // * round_number - The round of the report.
+    //
+    // # Returns:
+    // * The aggregated report.
+    pub fn new_nested(level: u32, report_type: ReportType, protocol_information: String, id: u32, aggregated_reports: Vec<AggregatedReport<T>>, instance_number: Instance, round_number: Round) -> Self {
+        if level <= 2 {
+            panic!("Error: nested aggregated reports start at level 3");
+        }
+        Self {
+            report_type,
+            protocol_information,
+            id, 
+            level,
+            reports: vec![],
+            aggregated_reports,
             instance_number,
-            round_number
+            round_number,
+            statistics: None
         }
     }
+
+    // # Method Description:
+    // This method embeds statistics in the aggregated report (see `ClusterConfig::with_frame_statistics`).
+    //
+    // # Parameters:
+    // * statistics - The statistics, if any.
+    //
+    // # Returns:
+    // * The updated aggregated report.
+    pub fn with_statistics(mut self, statistics: Option<FrameStatistics>) -> Self {
+        self.statistics = statistics;
+        self
+    }
 }
 
 impl<T> JsonConversion<AggregatedReport<T>> for AggregatedReport<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
-{}
\ No newline at end of file
+{}
+
+impl<T> FrameKind for AggregatedReport<T> {
+    const FRAME_KIND: u64 = frame_kinds::AGGREGATED_REPORT_FRAME;
+}
+
+// # Struct Description:
+// This struct is a self-contained proof that an aggregated witness quorum completed for a round.
+// It carries the aggregated witnesses forming the quorum, the witness reports they were built from,
+// the values delivered, and the thresholds in force, so that an external verifier can re-check the 
+// outcome from the serialized JSON alone without access to the live cluster.
+//
+// # Fields:
+// * id - The ID of the thread that completed the quorum and produced the proof.
+// * round_number - The round of the protocol the proof belongs to.
+// * thread_count - The number of threads `n` participating in the round.
+// * faulty_threads - The number of tolerated faulty threads `t` used by the handle.
+// * validity_threshold - The quorum size used by the handle for witnesses and aggregated witnesses.
+// * weights - The weight of every thread with weighted quorums, in which case `t` and the quorum are weights,
+//   or empty if every thread counts once.
+// * aggregated_witnesses - The aggregated reports that were upgraded to aggregated witnesses.
+// * witnesses - The witness reports contained in the aggregated witnesses.
+// * values - The values delivered for the round.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AggregationProof<T> {
+    id: u32,
+    round_number: Round,
+    thread_count: u32,
+    faulty_threads: u64,
+    validity_threshold: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    weights: Vec<u64>,
+    aggregated_witnesses: Vec<AggregatedReport<T>>,
+    witnesses: Vec<Report<T>>,
+    values: Vec<Message<T>>,
+}
+
+impl<T> AggregationProof<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(id: u32, round_number: Round, thread_count: u32, faulty_threads: u64, validity_threshold: u64, content: &WitnessRoundContent<T>) -> Self {
+        let aggregated_witnesses = content.aggregated_witnesses.clone();
+        let mut witnesses: Vec<Report<T>> = vec![];
+        for aggregated_witness in &aggregated_witnesses {
+            for report in aggregated_witness.get_reports() {
+                if !witnesses.contains(report) {
+                    witnesses.push(report.clone());
+                }
+            }
+        }
+        let values = content.values.clone();
+
+        Self {
+            id,
+            round_number,
+            thread_count,
+            faulty_threads,
+            validity_threshold,
+            weights: vec![],
+            aggregated_witnesses,
+            witnesses,
+            values
+        }
+    }
+
+    pub fn with_weights(mut self, weights: Vec<u64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_thread_count(&self) -> u32 {
+        self.thread_count
+    }
+
+    pub fn get_faulty_threads(&self) -> u64 {
+        self.faulty_threads
+    }
+
+    pub fn get_validity_threshold(&self) -> u64 {
+        self.validity_threshold
+    }
+
+    pub fn get_weights(&self) -> &Vec<u64> {
+        &self.weights
+    }
+
+    // # Method Description:
+    // This method provides how much a set of distinct threads counts towards the quorum of the proof.
+    fn get_combined_weight(&self, ids: &HashSet<u32>) -> u64 {
+        match self.weights.is_empty() {
+            true => ids.len() as u64,
+            false => ids.iter().map(|id| self.weights.get(*id as usize).copied().unwrap_or(0)).sum(),
+        }
+    }
+
+    pub fn get_aggregated_witnesses(&self) -> &Vec<AggregatedReport<T>> {
+        &self.aggregated_witnesses
+    }
+
+    pub fn get_witnesses(&self) -> &Vec<Report<T>> {
+        &self.witnesses
+    }
+
+    pub fn get_values(&self) -> &Vec<Message<T>> {
+        &self.values
+    }
+
+    // # Method Description:
+    // This method checks the proof without access to the cluster. It verifies that the thresholds 
+    // are consistent with `n` (the total weight with weighted quorums): `t` is at most `(n - 1) / 3`
+    // and the quorum is `n - t` or `n - t + 1`. It then verifies that a quorum of aggregated witnesses from
+    // distinct threads exists for the round, that every aggregated witness contains a quorum of witness
+    // reports, and that every witness report only contains delivered values.
+    //
+    // # Returns:
+    // * `Ok(())` if the proof is valid, otherwise an `Err` describing the first violated condition.
+    pub fn verify(&self) -> Result<(), String> {
+        if !self.weights.is_empty() && self.weights.len() != self.thread_count as usize {
+            return Err(format!("Error: {} weights inconsistent with thread count ({})", self.weights.len(), self.thread_count));
+        }
+        let total = self.get_combined_weight(&(0..self.thread_count).collect());
+        if total == 0 || self.faulty_threads > (total - 1) / 3 {
+            return Err(format!("Error: faulty threads ({}) inconsistent with thread count ({})", self.faulty_threads, total));
+        }
+        if self.validity_threshold < total - self.faulty_threads || self.validity_threshold > total - self.faulty_threads + 1 {
+            return Err(format!("Error: validity threshold ({}) inconsistent with thread count ({})", self.validity_threshold, total));
+        }
+
+        let creators: HashSet<u32> = self.aggregated_witnesses.iter().map(|aggregated_witness| aggregated_witness.get_id()).collect();
+        if self.get_combined_weight(&creators) < self.validity_threshold {
+            return Err(format!("Error: {} distinct aggregated witnesses below validity threshold ({})", creators.len(), self.validity_threshold));
+        }
+
+        let values_set: HashSet<&Message<T>> = self.values.iter().collect();
+        for aggregated_witness in &self.aggregated_witnesses {
+            if aggregated_witness.get_round_number() != self.round_number {
+                return Err(format!("Error: aggregated witness by id: {} belongs to round {}", aggregated_witness.get_id(), aggregated_witness.get_round_number()));
+            }
+            let reporters: HashSet<u32> = aggregated_witness.get_reports().iter().map(|report| report.get_id()).collect();
+            if self.get_combined_weight(&reporters) < self.validity_threshold {
+                return Err(format!("Error: aggregated witness by id: {} contains {} witnesses, below validity threshold ({})", aggregated_witness.get_id(), reporters.len(), self.validity_threshold));
+            }
+            for report in aggregated_witness.get_reports() {
+                if !self.witnesses.contains(report) {
+                    return Err(format!("Error: aggregated witness by id: {} contains unknown report by id: {}", aggregated_witness.get_id(), report.get_id()));
+                }
+            }
+        }
+
+        for witness in &self.witnesses {
+            if !witness.get_messages().iter().all(|message| values_set.contains(message)) {
+                return Err(format!("Error: witness by id: {} contains undelivered values", witness.get_id()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> JsonConversion<AggregationProof<T>> for AggregationProof<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{}
+
+// # Struct Description:
+// This struct buffers the aggregation proofs delivered by a thread's witness handle, 
+// allowing them to be retrieved by round regardless of the order in which rounds complete.
+//
+// # Fields:
+// * rx - The receiver on which the witness handle delivers aggregation proofs.
+// * proofs - A hashmap of received proofs, keyed by round number, that have not yet been retrieved.
+pub struct AggregationProofs<T> {
+    rx: Receiver<AggregationProof<T>>,
+    proofs: HashMap<Round, AggregationProof<T>>,
+}
+
+impl<T> AggregationProofs<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(rx: Receiver<AggregationProof<T>>) -> Self {
+        let proofs = HashMap::new();
+        Self {
+            rx,
+            proofs
+        }
+    }
+
+    // # Method Description:
+    // This method retrieves the proof of the specified round, buffering proofs of other rounds 
+    // received in the meantime.
+    //
+    // # Parameters:
+    // * round_number - The round of the protocol the proof belongs to.
+    //
+    // # Returns:
+    // * The `AggregationProof` of the round, once available.
+    pub async fn recv(&mut self, round_number: Round) -> AggregationProof<T> {
+        loop {
+            if let Some(proof) = self.proofs.remove(&round_number) {
+                return proof;
+            }
+            match self.rx.recv().await {
+                Some(proof) => {
+                    self.proofs.insert(proof.get_round_number(), proof);
+                },
+                None => panic!("Error: aggregation proof channel closed"),
+            }
+        }
+    }
+}
+
+// # Function Description:
+// This function describes aggregated witness broadcast as the aggregated witness handle runs it (see
+// `AggregatedWitnessHandleLoop`), with as many levels as the aggregation depth of the cluster, for the
+// tooling reading `ProtocolDescriptor`s.
+//
+// # Parameters:
+// * config - The configuration of the cluster, giving the aggregation depth, in which the quorums are evaluated.
+//
+// # Returns:
+// * The descriptor of aggregated witness broadcast.
+pub fn protocol_descriptor(config: &ClusterConfig) -> ProtocolDescriptor {
+    let aggregation_depth = config.get_aggregation_depth();
+    let level_report = |level: u32| match level {
+        2 => String::from("Aggregated report"),
+        level => format!("Level {level} aggregated report"),
+    };
+    let mut descriptor = ProtocolDescriptor::new(Protocol::AggregatedWitness, "aggregated witness", "witness broadcast whose witnesses are aggregated over several levels, each level reliably broadcasting the witnesses of the previous one", config)
+        .with_builds_on(&[Protocol::Reliable])
+        .with_message_type(MessageTypeDescriptor::new("Value", "ObjectContent::Message", Lane::Report, "the value of a thread in the round, reliably broadcast"))
+        .with_message_type(MessageTypeDescriptor::new("Report", "ObjectContent::Report", Lane::Report, "the values a thread received once they reached the validity quorum, reliably broadcast"));
+    for level in 2..=aggregation_depth {
+        let description = format!("the witnesses of level {} a thread received once they reached the validity quorum, reliably broadcast", level - 1);
+        descriptor = descriptor.with_message_type(MessageTypeDescriptor::new(&level_report(level), "ObjectContent::AggregatedReport", Lane::Report, &description));
+    }
+    descriptor = descriptor
+        .with_message_type(MessageTypeDescriptor::new("Collection", "ReportType::Witness", Lane::Basic, "the delivered values of the round, queued for aggregated_witness_collect"))
+        .with_phase(PhaseDescriptor::new("values", "every thread reliably broadcasts its value, and reports the values it received once they reach the validity quorum")
+            .with_threshold("Value", Quorum::Validity)
+            .with_sends(&["Report"]), config);
+    let level_sends = |level: u32| match level == aggregation_depth {
+        true => String::from("Collection"),
+        false => level_report(level + 1),
+    };
+    descriptor = descriptor.with_phase(PhaseDescriptor::new("witnesses", "a report becomes a witness once the thread received every value it holds; once the witnesses reach the validity quorum, the thread aggregates them")
+        .with_threshold("Report", Quorum::Validity)
+        .with_sends(&[&level_sends(1)]), config);
+    for level in 2..=aggregation_depth {
+        let description = format!("an aggregated report of level {level} becomes a witness once the thread holds every witness it aggregates; once these witnesses reach the validity quorum, the thread aggregates them");
+        descriptor = descriptor.with_phase(PhaseDescriptor::new(&format!("level {level} witnesses"), &description)
+            .with_threshold(&level_report(level), Quorum::Validity)
+            .with_sends(&[&level_sends(level)]), config);
+    }
+    descriptor
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+
+use crate::witness::WitnessCommunication;
+use crate::round::Round;
+use crate::logging::log;
+
+// The number of fixed-point units in 1.0: values are exchanged with a resolution of 10^-6.
+pub const FIXED_POINT_SCALE: i64 = 1_000_000;
+
+// # Struct Description:
+// This struct is a fixed-point number, the value approximate agreement threads broadcast to each other.
+// Floating-point values are neither `Eq` nor `Hash`, which witness payloads must be, so values are rounded
+// to the nearest multiple of 10^-6 and exchanged as integers. It is serialized as a bare JSON number.
+//
+// # Fields:
+// * 0 - The value, in units of 1 / `FIXED_POINT_SCALE`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct FixedPoint(i64);
+
+impl FixedPoint {
+    pub fn from_raw(units: i64) -> Self {
+        Self(units)
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * FIXED_POINT_SCALE as f64).round() as i64)
+    }
+
+    pub fn get_raw(&self) -> i64 {
+        self.0
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.0 as f64 / FIXED_POINT_SCALE as f64
+    }
+}
+
+impl fmt::Display for FixedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+// # Struct Description:
+// This struct runs approximate agreement over the witness rounds of a communicator, so that the threads of
+// a cluster holding real-valued inputs output values within epsilon of each other and within the range of
+// the correct inputs. In every round, a thread broadcasts its value and collects the values confirmed by
+// the witnesses of the round; it discards the f lowest and the f highest of them, and adopts the average of
+// the remaining extremes (the midpoint of the trimmed values). The trimmed values of a correct thread lie
+// within the range of the correct values, and the collections of two correct threads share at least n - f
+// values, so every round halves the spread of the correct values.
+//
+// Inputs are clamped to bounds known to every thread, from which the number of rounds is derived, so every
+// thread runs the same rounds and no thread stops before the others. Agreements use consecutive witness
+// rounds of the communicator, starting at `start_round`, so the communicator should be dedicated to
+// approximate agreement and every thread should run its agreements in the same order.
+//
+// # Fields:
+// * communicator - The communicator whose witness handle carries the values.
+// * epsilon - The largest distance allowed between the outputs of two correct threads, in fixed point.
+// * bounds - The lowest and highest inputs, in fixed point.
+// * rounds - The number of rounds of every agreement.
+// * next_round - The first witness round of the next agreement.
+// * decisions - The output of every agreement the thread ran, in order.
+pub struct ApproximateAgreement<C> {
+    communicator: C,
+    epsilon: FixedPoint,
+    bounds: (FixedPoint, FixedPoint),
+    rounds: u32,
+    next_round: Round,
+    decisions: Vec<f64>,
+}
+
+impl<C> ApproximateAgreement<C>
+where
+    C: WitnessCommunication<FixedPoint> + Send,
+{
+    // # Method Description:
+    // This method creates an approximate agreement over a communicator, and derives the number of rounds
+    // from the bounds and epsilon. Halving the spread rounds it up by at most one unit per round, and the
+    // rounding errors of all rounds add up to less than two units, which are kept out of the budget.
+    //
+    // # Parameters:
+    // * communicator - The witness communicator dedicated to the agreement.
+    // * epsilon - The largest distance allowed between the outputs of two correct threads.
+    // * bounds - The lowest and highest inputs; inputs outside of them are clamped.
+    //
+    // # Returns:
+    // * The agreement, or a description of why epsilon or the bounds are invalid.
+    pub fn new(communicator: C, epsilon: f64, bounds: (f64, f64)) -> Result<Self, String> {
+        if !epsilon.is_finite() || !bounds.0.is_finite() || !bounds.1.is_finite() {
+            return Err(String::from("approximate agreement epsilon and bounds must be finite"))
+        }
+        if bounds.0 > bounds.1 {
+            return Err(format!("approximate agreement lower bound {} exceeds upper bound {}", bounds.0, bounds.1))
+        }
+        let epsilon = FixedPoint::from_f64(epsilon);
+        if epsilon.get_raw() < 3 {
+            return Err(format!("approximate agreement epsilon {epsilon} is below three fixed-point units"))
+        }
+        let bounds = (FixedPoint::from_f64(bounds.0), FixedPoint::from_f64(bounds.1));
+        let budget = (epsilon.get_raw() - 2) as i128;
+        let mut spread = (bounds.1.get_raw() - bounds.0.get_raw()) as i128;
+        let mut rounds = 0;
+        while spread > budget {
+            spread = (spread + 1) / 2;
+            rounds += 1;
+        }
+        Ok(Self {
+            communicator,
+            epsilon,
+            bounds,
+            rounds,
+            next_round: Round(0),
+            decisions: vec![],
+        })
+    }
+
+    pub fn with_start_round(mut self, start_round: Round) -> Self {
+        self.next_round = start_round;
+        self
+    }
+
+    pub fn get_communicator(&mut self) -> &mut C {
+        &mut self.communicator
+    }
+
+    pub fn get_epsilon(&self) -> f64 {
+        self.epsilon.to_f64()
+    }
+
+    pub fn get_bounds(&self) -> (f64, f64) {
+        (self.bounds.0.to_f64(), self.bounds.1.to_f64())
+    }
+
+    pub fn get_rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn get_next_round(&self) -> Round {
+        self.next_round
+    }
+
+    pub fn get_decisions(&self) -> &Vec<f64> {
+        &self.decisions
+    }
+
+    // # Method Description:
+    // This method runs an agreement: it clamps the input of the thread to the bounds, then runs every round
+    // of the agreement over consecutive witness rounds.
+    //
+    // # Parameters:
+    // * value - The input of the thread.
+    //
+    // # Returns:
+    // * The output of the thread, within epsilon of the output of every correct thread.
+    pub async fn agree(&mut self, value: f64) -> f64 {
+        let thread_id = *self.communicator.get_id();
+        let faulty_threads = self.communicator.get_config().get_thresholds().get_faulty_threads() as usize;
+        let start_round = self.next_round;
+        let mut estimate = FixedPoint::from_f64(value.clamp(self.bounds.0.to_f64(), self.bounds.1.to_f64()));
+
+        for round in 0..self.rounds {
+            let round_number = start_round + round;
+            self.communicator.witness_broadcast(estimate, round_number).await;
+            let mut values: Vec<i64> = self.communicator.witness_collect(round_number).await
+                .iter()
+                .map(|message| message.get_message().get_raw())
+                .collect();
+            values.sort_unstable();
+            let trimmed = faulty_threads.min(values.len().saturating_sub(1) / 2);
+            let (lowest, highest) = (values[trimmed], values[values.len() - 1 - trimmed]);
+            estimate = FixedPoint::from_raw(lowest + (highest - lowest) / 2);
+        }
+
+        self.next_round = start_round + self.rounds;
+        let decision = estimate.to_f64();
+        log!(info, { node = thread_id, round = %start_round }, "id: {thread_id}, approximate agreement from round {start_round} decided {decision} after {} rounds", self.rounds);
+        self.decisions.push(decision);
+        decision
+    }
+}
@@ -1,13 +1,29 @@
 use core::panic;
-use std::{vec, fmt::Debug, hash::Hash, collections::{HashMap}};
+use std::{vec, fmt::Debug, hash::Hash, collections::{HashMap}, marker::PhantomData, time::Duration};
+use bytes::Bytes;
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
-use tokio::{task::JoinHandle, sync::mpsc::{self, Receiver, Sender}};
+use tokio::{task::JoinHandle, sync::{broadcast, oneshot, mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender}}};
+use tracing::Instrument;
 use async_trait::async_trait; 
 
-use crate:: basic::{BasicCommunication, BasicQueues, Message, MessageChannels, RecvObject}; 
-use crate::reliable::{ReliableCommunication, Signal, SignalType, ChannelType, ObjectContent, SignalChannels, ReliableInstanceMonitor}; 
+use crate::delivered::Delivered;
+use crate:: basic::{BasicCommunication, BasicQueues, CollectError, Message, MessageChannels, RecvObject, validate_wiring}; 
+use crate::reliable::{ReliableCommunication, Signal, SignalType, ChannelType, ObjectContent, SignalChannels, ReliableHandleCommand, DeliveryWatches}; 
 use crate::witness::{Report, ReportType, ReportChannels};
-use crate::json::{JsonConversion};
+use crate::json::{FrameKind, JsonConversion, WireFormat, frame_kinds};
+use crate::multiplex::{Lane, LaneReceivers, open_lanes};
+use crate::drive::{HandleMode, PolledHandle, PolledHandles};
+use crate::config::{ClusterConfig, Thresholds};
+use crate::events::{Event, DecodeFailureReporter};
+use crate::metrics::{Metrics, RoundStatus};
+use crate::health::RunningHandle;
+use crate::inspect::{Inspect, InspectResponder, BarycentricRoundSnapshot};
+use crate::retention::RetentionTracker;
+use crate::trace::TraceSampler;
+use crate::capabilities::{Capabilities, Protocol};
+use crate::round::{Instance, Round};
+use crate::logging::log;
+use crate::descriptor::{MessageTypeDescriptor, PhaseDescriptor, ProtocolDescriptor, Quorum};
 
 // # Trait Description:
 // This trait defines the communication behavior for threads participating in the Barycentric Agreement protocol, 
@@ -30,18 +46,18 @@ where
     // 
     // # Parameters:
     // * thread_id - ID of the thread evaluating trust across reports.
+    // * thresholds - the thresholds the thread counts with, giving the weight every report counts for.
     // * agreement_threshold - the number of occurrences required to consider a message trusted.
-    // * count - a mutable reference to the `BarycentricRoundCount`, tracking per-round message counts.
     // * content - a mutable reference to the `BarycentricRoundContent` containing the reports and messages.
     //
     // # Returns:
     // * a vector of trusted `Message` objects recognized in the current round.
-    fn initialize_trusted(thread_id: u32, agreement_threshold: u32, count: &mut BarycentricRoundCount, content: &mut BarycentricRoundContent<T>) -> Vec<Message<T>>{
-        let mut trusted_monitor: Vec<u32> = vec![];
+    fn initialize_trusted(thread_id: u32, thresholds: &Thresholds, agreement_threshold: u64, content: &mut BarycentricRoundContent<T>) -> Vec<Message<T>>{
+        let mut trusted_monitor: Vec<u64> = vec![];
         let mut trusted: Vec<Message<T>> = vec![];
-        let initial_message = Message::new("".to_string(), 0, T::default(), None, None, 0); 
+        let initial_message = Message::new("".to_string(), 0, T::default(), None, None, Round(0)); 
 
-        for _ in 0..count.messages {
+        for _ in 0..content.messages.len() {
             trusted_monitor.push(0);
         }
 
@@ -50,7 +66,7 @@ where
                 let id = report_message.get_id() as usize;
                 if let Some(message) = content.messages.get(id) {
                     if message == report_message && message != &initial_message {
-                        trusted_monitor[id] += 1; 
+                        trusted_monitor[id] += thresholds.get_weight(barycentric_report.get_id()); 
                     }
                 }
             }
@@ -64,7 +80,7 @@ where
             }
         }
         if trusted.len() > 0 {
-            println!("id: {}, recognize trusted values: {:?}", thread_id, trusted);
+            log!(debug, "id: {}, recognize trusted values: {:?}", thread_id, trusted);
         }
         return trusted
     }
@@ -77,20 +93,21 @@ where
     // 
     // # Parameters:
     // * _thread_id - The ID of the current thread (not used directly in this function).
+    // * thresholds - The thresholds the thread counts with, giving the weight every buddy counts for.
     // * messages - A mutable reference to the current thread’s vector of `Message` objects.
     // * buddies - A mutable reference to a boolean vector indicating buddy status for each peer.
     // * barycentric_reports - A mutable reference to a vector of received `BarycentricReport` objects.
     // * count - A mutable reference to the `BarycentricRoundCount` used for tracking buddies.
-    fn initialize_buddies(_thread_id: u32, messages: &mut Vec<Message<T>>, buddies: &mut Vec<bool>, barycentric_reports: &mut Vec<BarycentricReport<T>>, count: &mut BarycentricRoundCount) {
+    fn initialize_buddies(_thread_id: u32, thresholds: &Thresholds, messages: &mut Vec<Message<T>>, buddies: &mut Vec<bool>, barycentric_reports: &mut Vec<BarycentricReport<T>>, count: &mut BarycentricRoundCount) {
         count.buddies = 0;  
-        let initial_message = Message::new("".to_string(), 0, T::default(), None, None, 0); 
-        let initial_report = BarycentricReport::new("".to_string(), 0, vec![initial_message.clone()], 0, 0);
+        let initial_message = Message::new("".to_string(), 0, T::default(), None, None, Round(0)); 
+        let initial_report = BarycentricReport::new("".to_string(), 0, vec![initial_message.clone()], Instance(0), Round(0));
 
         for barycentric_report in barycentric_reports {
             let id = barycentric_report.get_id() as usize;
             if messages == barycentric_report.get_messages() && barycentric_report != &initial_report {
                 buddies[id] = true; 
-                count.buddies += 1;  
+                count.buddies += thresholds.get_weight(id as u32);  
             } else {
                 buddies[id] = false; 
             }
@@ -110,38 +127,215 @@ where
     //
     // # Returns:
     // * A future resolving to `()` once the broadcast operation is complete.
-    fn barycentric_agreement(&mut self, message: T, round_number: u32) -> impl Future<Output = ()> {
+    fn barycentric_agreement(&mut self, message: T, round_number: Round) -> impl Future<Output = ()> {
         let protocol_information = String::from("barycentric");
-        let instance_number = 0; 
+        let instance_number = Instance(0);
         let sent_message = Message::new(protocol_information, *self.get_id(), message, None, Some(instance_number), round_number);
         let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
         self.get_signal_channels().broadcast_signal(input)
     }
 
+    // # Method Description:
+    // This method starts a multi-dimensional barycentric agreement on a point, whose coordinates are agreed
+    // on in independent barycentric rounds, one per dimension. Coordinate `d` is broadcast as a message of
+    // dimension `d`, in instance `d` of the round, so the barycentric handle monitors and delivers every
+    // dimension on its own; the collection of a dimension is retrieved with `barycentric_collect_dimension`.
+    // A round runs either a one-dimensional agreement or a multi-dimensional one, since the one-dimensional
+    // agreement shares instance 0 with dimension 0.
+    //
+    // # Parameters:
+    // * point - The coordinates of the point proposed by the thread, by dimension.
+    // * round_number - The round number of the agreement.
+    async fn barycentric_agreement_nd(&mut self, point: Vec<T>, round_number: Round) {
+        let protocol_information = String::from("barycentric");
+        for (dimension, coordinate) in point.into_iter().enumerate() {
+            let dimension = dimension as u32;
+            let instance_number = Instance(dimension);
+            let sent_message = Message::new(protocol_information.clone(), *self.get_id(), coordinate, Some(dimension), Some(instance_number), round_number);
+            let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
+            self.get_signal_channels().broadcast_signal(input).await;
+        }
+    }
+
     // # Function Description:
     // This method collects all messages received during the Barycentric Agreement round. It waits for 
     // a `Collection` object (a `BarycentricReport`) to be received, extracts its contained 
-    // messages, and returns them for further processing.
+    // messages, and returns them for further processing. Collecting a round again answers according to
+    // the configured `RepeatedCollectPolicy`. Panics if the round cannot be collected (see `try_barycentric_collect`).
     // 
     // # Parameters:
     // * round_number - The round number for which collection is performed.
     //
     // # Returns:
     // * A vector of `Message` objects aggregated from the collected reports.
-    async fn barycentric_collect(&mut self, round_number: u32) -> Vec<Message<T>>{
+    async fn barycentric_collect(&mut self, round_number: Round) -> Vec<Message<T>>{
+        match self.try_barycentric_collect(round_number).await {
+            Ok(collection) => collection,
+            Err(error) => panic!("Error: barycentric agreement round {round_number} could not be collected: {error:?}"),
+        }
+    }
+
+    // # Method Description:
+    // This method collects the round like `barycentric_collect`, in the `Delivered` envelope shared by every
+    // protocol.
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // # Returns:
+    // * The `Delivered` collection of the round.
+    async fn barycentric_collect_delivered(&mut self, round_number: Round) -> Delivered<T> {
+        let messages = self.barycentric_collect(round_number).await;
+        Delivered::from_collection(*self.get_id(), String::from("barycentric"), round_number, messages)
+    }
+
+    // # Method Description:
+    // This method collects the round like `barycentric_collect`, together with the `BuddyCertificate` of the
+    // buddies that justified its delivery, e.g. to verify the buddy mechanism offline. Panics if the round
+    // cannot be collected.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    //
+    // # Returns:
+    // * The collected `Message`s and the certificate of the delivery.
+    async fn barycentric_collect_certified(&mut self, round_number: Round) -> (Vec<Message<T>>, BuddyCertificate) {
+        match self.try_barycentric_collect_certified(round_number).await {
+            Ok(collection) => collection,
+            Err(error) => panic!("Error: barycentric agreement round {round_number} could not be collected: {error:?}"),
+        }
+    }
+
+    // # Method Description:
+    // This method collects the round like `barycentric_collect`, but returns an error instead of panicking if
+    // the round was aborted by an undecodable frame, which only happens under `DecodePolicy::Strict`, or was
+    // already collected under `RepeatedCollectPolicy::AlreadyConsumed`.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    //
+    // # Returns:
+    // * The collected `Message`s, or the `CollectError` preventing the collection.
+    async fn try_barycentric_collect(&mut self, round_number: Round) -> Result<Vec<Message<T>>, CollectError> {
+        let (messages, _) = self.try_barycentric_collect_certified(round_number).await?;
+        Ok(messages)
+    }
+
+    // # Method Description:
+    // This method collects the round like `try_barycentric_collect`, but stops waiting after a timeout, e.g. so
+    // that an application can retry the round or change views on top of it. A round delivered after the
+    // timeout can still be collected.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // * timeout - How long to wait for the round.
+    //
+    // # Returns:
+    // * The collected `Message`s, or the `CollectError` preventing the collection (`CollectError::Timeout` if
+    //   the round was not delivered in time).
+    async fn barycentric_collect_with_timeout(&mut self, round_number: Round, timeout: Duration) -> Result<Vec<Message<T>>, CollectError> {
+        match tokio::time::timeout(timeout, self.try_barycentric_collect(round_number)).await {
+            Ok(collection) => collection,
+            Err(_) => Err(CollectError::Timeout(String::from("barycentric"), round_number)),
+        }
+    }
+
+    // # Method Description:
+    // This method collects the round like `barycentric_collect_certified`, but returns an error instead of
+    // panicking if the round cannot be collected.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    //
+    // # Returns:
+    // * The collected `Message`s and the certificate of the delivery, or the `CollectError` preventing the collection.
+    async fn try_barycentric_collect_certified(&mut self, round_number: Round) -> Result<(Vec<Message<T>>, BuddyCertificate), CollectError> {
         let protocol_information = String::from("barycentric");
         let thread_id = self.get_id().clone();
 
-        match self.get_queues().basic_recv(Some(thread_id), protocol_information, Some(0), round_number).await {
+        match self.get_queues().collect_round(thread_id, protocol_information, Some(Instance(0)), round_number).await? {
             RecvObject::Message(_) => {panic!("Error: retreived Message instead of Vec<Message>")},
             RecvObject::Collection(report) => {
-                println!("Agreement collected: {:?}", &report.get_messages());    
-                let collection = report.get_messages().clone();
-                return collection;
+                log!(info, "Agreement collected: {:?}", &report.get_messages());    
+                let certificate = match report.get_certificate() {
+                    Some(certificate) => certificate.clone(),
+                    None => panic!("Error: barycentric agreement round {round_number} delivered without a buddy certificate"),
+                };
+                Ok((report.get_messages().clone(), certificate))
             },
         }
     }
 
+    // # Method Description:
+    // This method collects one dimension of a multi-dimensional round started with `barycentric_agreement_nd`.
+    // Panics if the dimension cannot be collected (see `try_barycentric_collect_dimension`).
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // * dimension - The dimension to collect.
+    //
+    // # Returns:
+    // * The trusted coordinates of the dimension, as `Message`s of that dimension.
+    async fn barycentric_collect_dimension(&mut self, round_number: Round, dimension: u32) -> Vec<Message<T>> {
+        match self.try_barycentric_collect_dimension(round_number, dimension).await {
+            Ok(collection) => collection,
+            Err(error) => panic!("Error: dimension {dimension} of barycentric agreement round {round_number} could not be collected: {error:?}"),
+        }
+    }
+
+    // # Method Description:
+    // This method collects one dimension of a multi-dimensional round like `barycentric_collect_dimension`, but
+    // returns an error instead of panicking if the dimension cannot be collected.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // * dimension - The dimension to collect.
+    //
+    // # Returns:
+    // * The trusted coordinates of the dimension, or the `CollectError` preventing the collection.
+    async fn try_barycentric_collect_dimension(&mut self, round_number: Round, dimension: u32) -> Result<Vec<Message<T>>, CollectError> {
+        let protocol_information = String::from("barycentric");
+        let thread_id = *self.get_id();
+
+        match self.get_queues().collect_round(thread_id, protocol_information, Some(Instance(dimension)), round_number).await? {
+            RecvObject::Message(_) => {panic!("Error: retreived Message instead of Vec<Message>")},
+            RecvObject::Collection(report) => {
+                log!(info, "Agreement collected in dimension {dimension}: {:?}", &report.get_messages());
+                Ok(report.get_messages().clone())
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method collects every dimension of a multi-dimensional round, in order of dimension.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // * dimensions - The number of dimensions of the agreed points.
+    //
+    // # Returns:
+    // * The trusted coordinates of every dimension, by dimension.
+    async fn barycentric_collect_nd(&mut self, round_number: Round, dimensions: u32) -> Vec<Vec<Message<T>>> {
+        let mut collections = vec![];
+        for dimension in 0..dimensions {
+            collections.push(self.barycentric_collect_dimension(round_number, dimension).await);
+        }
+        collections
+    }
+
+    // # Method Description:
+    // This method queries the barycentric handle for a snapshot of a round it monitors. The query is
+    // answered by the handle itself, once it has processed the frames received before it.
+    //
+    // # Parameters:
+    // * round_number - The round number to inspect.
+    //
+    // # Returns:
+    // * A `oneshot::Receiver` that yields the `BarycentricRoundSnapshot` of the round, or `None` if the handle has not seen it.
+    fn barycentric_inspect(&self, round_number: Round) -> oneshot::Receiver<Option<BarycentricRoundSnapshot>> {
+        let (responder, snapshot) = oneshot::channel();
+        let _ = self.get_barycentric_command_channel().send(BarycentricHandleCommand::Inspect(round_number, responder));
+        snapshot
+    }
+
     // # Function Description:
     // This function creates a `BarycentricReport` representing the current round’s state, including all 
     // locally known messages. This report is used for reliable dissemination among peers 
@@ -156,10 +350,10 @@ where
     //
     // # Returns:
     // * A new `BarycentricReport` object encapsulating the current round’s data.
-    fn create_barycentric_report(thread_id: u32, content: &mut BarycentricRoundContent<T>, round_number: u32, protocol_information: String, count: &mut BarycentricRoundCount) -> BarycentricReport<T>{
+    fn create_barycentric_report(thread_id: u32, content: &mut BarycentricRoundContent<T>, round_number: Round, protocol_information: String, count: &mut BarycentricRoundCount) -> BarycentricReport<T>{
         let protocol_information = protocol_information;
-        let instance_number = count.messages; 
-        BarycentricReport::new(protocol_information, thread_id, content.messages.clone(), instance_number, round_number)
+        let instance_number = Instance(count.messages);
+        BarycentricReport::new(protocol_information, thread_id, content.messages.clone(), instance_number, round_number).with_dimension(content.dimension)
     }
 
     // # Function Description:
@@ -170,17 +364,290 @@ where
     // # Parameters:
     // * barycentric_handle - The asynchronous join handle for the Barycentric task being terminated.
     fn terminate_barycentric_handle(&self, barycentric_handle: JoinHandle<()>) {
-        println!("id: {}, terminating barycentric_handle...", self.get_id());
+        log!(debug, "id: {}, terminating barycentric_handle...", self.get_id());
         barycentric_handle.abort();
     }
 
-    async fn reliable_broadcast_barycentric_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut BarycentricRoundContent<T>, round_number: u32, protocol_information: String, count: &mut BarycentricRoundCount); 
+    async fn reliable_broadcast_barycentric_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut BarycentricRoundContent<T>, round_number: Round, protocol_information: String, count: &mut BarycentricRoundCount); 
     fn initialize_barycentric_handle(&mut self) -> JoinHandle<()>; 
-    fn take_barycentric_handle_rx(&mut self) -> Receiver<String>;
+    fn take_barycentric_handle_rx(&mut self) -> Receiver<Bytes>;
+    fn take_barycentric_command_rx(&mut self) -> UnboundedReceiver<BarycentricHandleCommand>;
+    fn get_barycentric_command_channel(&self) -> &UnboundedSender<BarycentricHandleCommand>;
     fn get_report_channels(&self) -> &ReportChannels<T>;
 
 }
 
+// # Enum Description:
+// This enum represents a command sent by a communicator to its barycentric handle.
+//
+// # Variants:
+// * Inspect - Requests a snapshot of a round, answered immediately on the enclosed `oneshot::Sender`
+//   (with `None` if the handle has not seen the round).
+pub enum BarycentricHandleCommand {
+    Inspect(Round, InspectResponder<Option<BarycentricRoundSnapshot>>),
+}
+
+// # Struct Description:
+// This struct holds the state of a thread's barycentric handle and processes the messages and barycentric
+// reports it receives according to the Barycentric Agreement protocol. It maintains a per-round monitor
+// that tracks message counts, trusted values, and buddy relationships across participating threads,
+// rebroadcasts received messages as a barycentric report, and uses the collected barycentric reports
+// to identify “trusted” values, establishing `buddy` processors in the network. Delivered rounds are
+// collected according to the configured `RetentionPolicy`, after which they can no longer be inspected,
+// and their late frames are ignored.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * thread_channel - The channels on which the trusted values of a round are delivered.
+// * thread_signal_channel - The channels on which barycentric reports are reliably broadcast.
+// * thread_count - The number of threads in the cluster.
+// * metrics - The thread's metrics, in which the status of every round is recorded.
+// * decode_failures - The reporter of the frames that cannot be decoded.
+// * event_channel - The channel on which `Event::RoundCollected` events are published.
+// * receiver - The receiver of the thread's `Report` lane.
+// * command_receiver - The receiver of the commands sent to the handle.
+// * processing_delay - The processing delay of the thread's `Fault::Slowdown`, if any.
+// * validity_threshold - The number of messages (and buddies) needed to trust (and deliver) a round.
+// * agreement_threshold - The number of barycentric reports needed to trust a value.
+// * thresholds - The thresholds the thread counts with (see `SelfCounting`), giving the weight every message,
+//   barycentric report, and buddy counts for.
+// * barycentric_monitor - The monitor of every round, by round number and dimension (`None` outside of
+//   multi-dimensional rounds).
+// * retention - The tracker of the delivered rounds, which decides when they are collected.
+// * trace_sampler - The sampler deciding which rounds record their round status.
+// * _running - The record of the handle running in the thread's `HealthProbe`, marked stopped once the handle stops.
+pub struct BarycentricHandleLoop<T, C>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
+{
+    thread_id: u32,
+    thread_channel: MessageChannels<T>,
+    thread_signal_channel: SignalChannels<T>,
+    thread_count: u32,
+    metrics: Metrics,
+    decode_failures: DecodeFailureReporter<T>,
+    event_channel: broadcast::Sender<Event<T>>,
+    receiver: Receiver<Bytes>,
+    command_receiver: UnboundedReceiver<BarycentricHandleCommand>,
+    processing_delay: Option<Duration>,
+    validity_threshold: u64,
+    agreement_threshold: u64,
+    thresholds: Thresholds,
+    barycentric_monitor: HashMap<(Round, Option<u32>), BarycentricRoundMonitor<T>>,
+    retention: RetentionTracker<(Round, Option<u32>)>,
+    trace_sampler: TraceSampler,
+    _running: RunningHandle,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<T, C> BarycentricHandleLoop<T, C>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
+    C: BarycentricCommunication<T> + 'static,
+{
+    fn new(communicator: &mut C) -> Self {
+        let thread_id = *communicator.get_id();
+        let thread_channel = communicator.get_channels().clone();
+        let thread_count = thread_channel.get_channels().len() as u32;
+        let thresholds = communicator.get_config().get_counting_thresholds(thread_id);
+
+        Self {
+            thread_id,
+            thread_channel,
+            thread_signal_channel: communicator.get_signal_channels().clone(),
+            thread_count,
+            metrics: communicator.get_metrics().clone(),
+            decode_failures: DecodeFailureReporter::new(communicator, Lane::Report),
+            event_channel: communicator.get_event_channel().clone(),
+            receiver: communicator.take_barycentric_handle_rx(),
+            command_receiver: communicator.take_barycentric_command_rx(),
+            processing_delay: communicator.get_config().get_fault_script().get_processing_delay(thread_id),
+            validity_threshold: thresholds.get_validity_threshold(),
+            agreement_threshold: thresholds.get_agreement_threshold(),
+            thresholds,
+            barycentric_monitor: HashMap::new(),
+            retention: RetentionTracker::new(communicator.get_config().get_retention_policy()),
+            trace_sampler: communicator.get_config().get_trace_sampler(),
+            _running: communicator.get_metrics().get_health_probe().start_handle("barycentric"),
+            _marker: PhantomData,
+        }
+    }
+
+    // # Method Description:
+    // This method processes commands and frames as they arrive, until the handle's task is aborted.
+    async fn run(mut self) {
+        loop {
+            tokio::select! {
+                Some(command) = self.command_receiver.recv() => {
+                    self.process_command(command);
+                }
+                Some(received_object) = self.receiver.recv() => {
+                    if let Some(delay) = self.processing_delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    self.process_object(received_object).await;
+                }
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method processes a single command sent to the handle.
+    //
+    // # Parameters:
+    // * command - The command.
+    fn process_command(&mut self, command: BarycentricHandleCommand) {
+        match command {
+            BarycentricHandleCommand::Inspect(round_number, responder) => {
+                let _ = responder.send(self.barycentric_monitor.get(&(round_number, None)).map(|monitor| monitor.snapshot()));
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method processes a single message or barycentric report received on the thread's `Report` lane.
+    //
+    // # Parameters:
+    // * received_object - The serialized `Message` or `BarycentricReport`.
+    async fn process_object(&mut self, received_object: Bytes) {
+        self.metrics.get_health_probe().record_frame();
+        let object: ObjectContent<T>; 
+        if let Ok(message) = Message::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::Message(message);
+        } else if let Ok(barycentric_report) = BarycentricReport::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::BarycentricReport(barycentric_report);
+        } else {
+            self.decode_failures.report(&received_object).await;
+            return
+        }
+        // everything logged while the object is processed is emitted in the span of its round
+        let span = tracing::debug_span!("round", protocol = "barycentric", round = %object.get_round_number(), node = self.thread_id);
+        self.process_round_object(object).instrument(span).await;
+    }
+
+    // # Method Description:
+    // This method processes a message or barycentric report decoded by `process_object`.
+    //
+    // # Parameters:
+    // * object - The message or barycentric report.
+    async fn process_round_object(&mut self, object: ObjectContent<T>) {
+        let round_number =  object.get_round_number(); 
+        let protocol_information = object.get_protocol_information().clone();
+        let dimension = match &object {
+            ObjectContent::Message(message) => message.get_dimension(),
+            ObjectContent::BarycentricReport(barycentric_report) => barycentric_report.get_dimension(),
+            _ => None,
+        };
+        for collected in self.retention.collect() {
+            self.barycentric_monitor.remove(&collected);
+            let _ = self.event_channel.send(Event::RoundCollected(self.thread_id, String::from("barycentric"), collected.0));
+        }
+        if self.retention.is_collected(&(round_number, dimension)) {
+            return
+        }
+        let _ =  self.barycentric_monitor.entry((round_number, dimension)).or_insert(BarycentricRoundMonitor::<T>::new(self.thread_count).with_dimension(dimension));
+
+        let instance = self.barycentric_monitor.get_mut(&(round_number, dimension)).unwrap(); 
+        let content = &mut instance.content;
+        let state = &mut instance.state;
+        let count = &mut instance.count;
+
+        match object {
+            ObjectContent::Message(message) => {
+                if !content.messages.contains(&message) {
+                    let id = message.get_id();
+                    content.messages[id as usize] = message; 
+                    count.messages += 1;  
+                    count.counted_messages += self.thresholds.get_weight(id);  
+                    C::reliable_broadcast_barycentric_report(self.thread_id, &self.thread_signal_channel, content, round_number, protocol_information, count).await;
+                }
+                
+                if count.counted_messages >= self.validity_threshold && state.messages == false {
+                    state.messages = true; 
+                }
+                
+            },
+            ObjectContent::Report(_) => {
+                panic!("Error: received incompatible object type (Report) for barycentric agreement");
+            },
+            ObjectContent::AggregatedReport(_) => {                        
+                panic!("Error: received incompatible object type (AggregatedReport) for barycentric agreement");
+            },
+            ObjectContent::BarycentricReport(barycentric_report) => {     
+                let id = barycentric_report.get_id();
+
+                // a report is numbered after the messages it holds, so a report overtaken by a later one is stale
+                if barycentric_report.get_instance_number() >= content.barycentric_reports[id as usize].get_instance_number() {
+                    content.barycentric_reports[id as usize] = barycentric_report; 
+                }
+                // content.barycentric_reports.insert(barycentric_report.get_id() as usize, barycentric_report);
+                count.barycentric_reports += self.thresholds.get_weight(id);  
+            },
+            ObjectContent::Commitment(_) => {
+                panic!("Error: received incompatible object type (Commitment) for barycentric agreement");
+            },
+            ObjectContent::Fragment(_) => {
+                panic!("Error: received incompatible object type (Fragment) for barycentric agreement");
+            },
+            ObjectContent::Extension { .. } => {
+                panic!("Error: received incompatible object type (Extension) for barycentric agreement");
+            },
+        }
+
+        if count.barycentric_reports >= self.agreement_threshold && state.trusted == false {
+            //confirm approach of using RB barycentric reports to check for a trusted message
+            if C::initialize_trusted(self.thread_id, &self.thresholds, self.agreement_threshold, content).len() > 0 {
+                state.trusted = true;
+            }
+        }
+
+        // buddies are re-evaluated on every object, as the last message may arrive after the reports when links reorder frames
+        if state.messages && state.trusted && !state.buddies {
+            C::initialize_buddies(self.thread_id, &self.thresholds, &mut content.messages, &mut content.buddies, &mut content.barycentric_reports, count);
+        }
+
+        if count.buddies >= self.validity_threshold && state.buddies == false {
+            // every dimension of a multi-dimensional round is delivered in the instance of its number
+            let protocol_information = String::from("barycentric");
+            let instance_number = Instance(dimension.unwrap_or(0));
+            let trusted_messages = C::initialize_trusted(self.thread_id, &self.thresholds, self.agreement_threshold, content).clone();
+            let certificate = BuddyCertificate::new(self.thread_id, round_number, self.validity_threshold, &content.buddies, &content.barycentric_reports);
+            let values = Report::new(ReportType::Witness, protocol_information, self.thread_id, trusted_messages, dimension, instance_number, round_number).with_certificate(certificate); 
+            self.thread_channel.send_values(self.thread_id, values).await;
+            state.buddies = true;
+            self.retention.complete((round_number, dimension));
+        }
+
+        let round_status = RoundStatus::new(String::from("barycentric"), dimension.map(|dimension| format!("dimension {dimension}")), round_number, state.buddies)
+            .with_stage("messages", count.counted_messages, self.validity_threshold)
+            .with_stage("barycentric reports", count.barycentric_reports, self.agreement_threshold)
+            .with_stage("buddies", count.buddies, self.validity_threshold);
+        if self.trace_sampler.is_sampled(&format!("{}::round::{}", round_status.get_protocol_information(), round_number)) {
+            self.metrics.record_round_status(round_status);
+        }
+    }
+}
+
+#[async_trait]
+impl<T, C> PolledHandle for BarycentricHandleLoop<T, C>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
+    C: BarycentricCommunication<T> + 'static,
+{
+    async fn process_pending(&mut self) -> usize {
+        let mut processed = 0;
+        while let Ok(command) = self.command_receiver.try_recv() {
+            self.process_command(command);
+            processed += 1;
+        }
+        while let Ok(received_object) = self.receiver.try_recv() {
+            self.process_object(received_object).await;
+            processed += 1;
+        }
+        processed
+    }
+}
+
 // # Struct Description:
 // This struct manages a collection of `BarycentricCommunicator` instances, each representing a thread
 // participating in the Barycentric Agreement protocol. It is responsible for initializing, storing, 
@@ -190,52 +657,60 @@ where
 // # Fields:
 // * barycentric_communicators - A vector containing all `BarycentricCommunicator` instances managed 
 //   by this hub, each encapsulating the communication logic for a single participating thread.
+// * config - The configuration shared by every communicator created from this hub.
 pub struct BarycentricHub<T> 
 where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
 {
-    barycentric_communicators: Vec<BarycentricCommunicator<T>>
+    barycentric_communicators: Vec<BarycentricCommunicator<T>>,
+    config: ClusterConfig,
 }
  
 impl<T> BarycentricHub<T>
 where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
 {
-    pub fn new(transmitters: Vec<Sender<String>>, mut receivers: Vec<Receiver<String>>, thread_count: u32) -> Self {  
-        let mut barycentric_communicators = vec![];
-        let mut reliable_handle_transmitters = vec![];
-        let mut reliable_handle_receivers = vec![];
-
-        let mut barycentric_handle_transmitters = vec![];
-        let mut barycentric_handle_receivers = vec![];
-
-        for _ in 0..(thread_count) {
-            let (reliable_handle_tx, reliable_handle_rx) = mpsc::channel(256); 
-            let (barycentric_handle_tx, barycentric_handle_rx) = mpsc::channel(256); 
-
-            reliable_handle_transmitters.push(reliable_handle_tx);
-            reliable_handle_receivers.push(reliable_handle_rx);
+    pub fn new(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, thread_count: u32) -> Self {  
+        Self::with_config(transmitters, receivers, ClusterConfig::new(thread_count))
+    }
 
-            barycentric_handle_transmitters.push(barycentric_handle_tx);
-            barycentric_handle_receivers.push(barycentric_handle_rx);
+    pub fn with_config(transmitters: Vec<Sender<Bytes>>, mut receivers: Vec<Receiver<Bytes>>, config: ClusterConfig) -> Self {  
+        if config.get_thresholds().is_weighted() {
+            panic!("Error: weighted quorums are not supported by barycentric agreement, whose buddy certificates count threads");
         }
+        let thread_count = config.get_thread_count();
+        let mut barycentric_communicators = vec![];
         
         for i in 0..(thread_count) {
-            let reliable_handle_rx = reliable_handle_receivers.remove(0);
-            let barycentric_handle_rx = barycentric_handle_receivers.remove(0);
-            let rx: Receiver<String> = receivers.remove(0);
-            barycentric_communicators.push(BarycentricCommunicator::new(transmitters.clone(), rx, 
-                thread_count, i as u32, reliable_handle_transmitters.clone(), reliable_handle_rx, barycentric_handle_transmitters.clone(), barycentric_handle_rx));
+            let lanes = open_lanes(i, config.link_receiver(i, receivers.remove(0)), &config);
+            barycentric_communicators.push(BarycentricCommunicator::new(config.link_transmitters(i, &transmitters), lanes, i, config.clone()));
         }
         
         Self {
-            barycentric_communicators
+            barycentric_communicators,
+            config
         }
     }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
  
     pub fn create_barycentric_communicator(&mut self) -> BarycentricCommunicator<T>{
         self.barycentric_communicators.remove(0)
     }
+
+    // # Method Description:
+    // This method checks that every channel of the hub reaches the thread it is meant for (see `validate_wiring`).
+    // It must be called before any communicator is created from the hub.
+    // # Returns:
+    // * `Ok(())` if the wiring is correct, or a description of every mis-wired channel.
+    pub async fn validate(&mut self) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        validate_wiring(&mut self.barycentric_communicators).await
+    }
  }
 
 // # Struct Description:
@@ -254,6 +729,15 @@ where
 //   during protocol execution.
 // * reliable_handle_rx - A receiver dedicated to listening for incoming reliable broadcast signals.
 // * barycentric_handle_rx - A receiver dedicated to listening for incoming barycentric broadcast signals.
+// * barycentric_command_tx - A transmitter for sending commands (e.g. inspection queries) to the barycentric handle.
+// * barycentric_command_rx - A receiver for commands, taken by the barycentric handle.
+// * reliable_command_tx - A transmitter for sending commands (e.g. inspection queries) to the reliable handle.
+// * reliable_command_rx - A receiver for commands, taken by the reliable handle.
+// * event_tx - A transmitter on which the background handles publish `Event`s.
+// * metrics - The metrics of the thread, in which the reliable handle records its phase latencies.
+// * delivery_watches - The watch channels on which the reliable handle publishes deliveries.
+// * polled_handles - The parts of the thread driven manually in `HandleMode::Polling`.
+// * config - The configuration shared by every communicator created from the same hub.
 pub struct BarycentricCommunicator<T>
 where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
@@ -263,22 +747,39 @@ where
     signal_channels: SignalChannels<T>, 
     report_channels: ReportChannels<T>,
     queues: BasicQueues<T>,
-    reliable_handle_rx: Option<Receiver<String>>, 
-    barycentric_handle_rx: Option<Receiver<String>>, 
+    reliable_handle_rx: Option<Receiver<Bytes>>, 
+    barycentric_handle_rx: Option<Receiver<Bytes>>, 
+    barycentric_command_tx: UnboundedSender<BarycentricHandleCommand>,
+    barycentric_command_rx: Option<UnboundedReceiver<BarycentricHandleCommand>>,
+    reliable_command_tx: UnboundedSender<ReliableHandleCommand>,
+    reliable_command_rx: Option<UnboundedReceiver<ReliableHandleCommand>>,
+    event_tx: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
 }
 
 impl<T> BarycentricCommunicator<T>
 where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
 {
-    fn new(transmitters: Vec<Sender<String>>, receiver: Receiver<String>, 
-            thread_count: u32, id: u32, reliable_handle_transmitters: Vec<Sender<String>>, reliable_handle_rx: Receiver<String>, barycentric_handle_transmitters: Vec<Sender<String>>, barycentric_handle_rx: Receiver<String>) -> Self {
-        let basic_channels = MessageChannels::new(transmitters.clone());
-        let signal_channels = SignalChannels::new(reliable_handle_transmitters.clone());
-        let report_channels = ReportChannels::new(barycentric_handle_transmitters.clone());
-        let queues = BasicQueues::new(receiver, thread_count);
-        let reliable_handle_rx = Some(reliable_handle_rx);
-        let barycentric_handle_rx = Some(barycentric_handle_rx);
+    fn new(transmitters: Vec<Sender<Bytes>>, lanes: LaneReceivers, id: u32, config: ClusterConfig) -> Self {
+        let basic_channels = MessageChannels::new(transmitters.clone()).with_codec(config.get_codec());
+        let metrics = lanes.metrics;
+        let signal_channels = SignalChannels::new(transmitters.clone()).with_dissemination(id, &config).with_metrics(&metrics).with_signing(id, &config).with_journal(id, &config).with_codec(config.get_codec()).with_batching(&config);
+        let report_channels = ReportChannels::new(transmitters).with_codec(config.get_codec());
+        let (event_tx, _) = broadcast::channel(config.get_buffer_size());
+        let queues = BasicQueues::new(lanes.basic, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy())
+            .with_validation(id, &config).with_metrics(metrics.clone()).with_event_channel(event_tx.clone());
+        let (reliable_command_tx, reliable_command_rx) = mpsc::unbounded_channel();
+        let reliable_command_rx = Some(reliable_command_rx);
+        let delivery_watches = DeliveryWatches::new();
+        let reliable_handle_rx = Some(lanes.signal);
+        let barycentric_handle_rx = Some(lanes.report);
+        let (barycentric_command_tx, barycentric_command_rx) = mpsc::unbounded_channel();
+        let barycentric_command_rx = Some(barycentric_command_rx);
+        let polled_handles = PolledHandles::new(lanes.demultiplexer);
 
         Self {
             id, 
@@ -288,6 +789,15 @@ where
             queues,
             reliable_handle_rx,
             barycentric_handle_rx,
+            barycentric_command_tx,
+            barycentric_command_rx,
+            reliable_command_tx,
+            reliable_command_rx,
+            event_tx,
+            metrics,
+            delivery_watches,
+            polled_handles,
+            config,
         }
     }
 }
@@ -298,107 +808,22 @@ where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
 {
     // # Method Description:
-    // This method spawns an asynchronous background task responsible for managing the lifecycle 
-    // of barycentric agreement rounds. Each spawned task listens for incoming messages or 
-    // barycentric reports and processes them according to the Barycentric Agreement protocol. 
-    // The method maintains a per-round monitor that tracks message counts, trusted values, 
-    // and buddy relationships across participating threads The spawned task ensures
-    // rebroadcast of received messages as a barycentric report, collecting Barycentric reports 
-    // and useing it to identify “trusted” values, establishing `buddy` processors in the network.
+    // This method starts the thread's barycentric handle, responsible for managing the lifecycle 
+    // of barycentric agreement rounds (see `BarycentricHandleLoop`). In `HandleMode::Spawned` the handle
+    // runs in its own task; in `HandleMode::Polling` it is parked in the communicator and only processes
+    // frames when the communicator is driven.
     //
     // # Returns:
     // * `JoinHandle<()>` - A handle to the asynchronous task that continuously listens for 
-    //   and processes barycentric communication events in the background.
+    //   and processes barycentric communication events in the background (already completed in polling mode).
     fn initialize_barycentric_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing barycentric handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let thread_count = thread_channel.get_channels().len() as u32; 
-        let mut receiver = self.take_barycentric_handle_rx(); 
-
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let agreement_threshold = faulty_threads + 1;
-
-        let mut barycentric_monitor: HashMap<u32, BarycentricRoundMonitor<T>> = HashMap::new();
-    
-        let handle = tokio::spawn(async move {
-            loop  {
-                tokio::select! {
-                    Some(received_object) = receiver.recv() => {
-                        let object: ObjectContent<T>; 
-                        if let Ok(message) = Message::read_json(&received_object) {
-                            object = ObjectContent::Message(message);
-                        } else if let Ok(barycentric_report) = BarycentricReport::read_json(&received_object) {
-                            object = ObjectContent::BarycentricReport(barycentric_report);
-                        } else {
-                            continue
-                        }
-
-                        let round_number =  object.get_round_number(); 
-                        let protocol_information = object.get_protocol_information().clone();
-                        let _ =  barycentric_monitor.entry(round_number).or_insert(BarycentricRoundMonitor::<T>::new(thread_count));
-
-                        let instance = barycentric_monitor.get_mut(&round_number).unwrap(); 
-                        let content = &mut instance.content;
-                        let state = &mut instance.state;
-                        let count = &mut instance.count;
-
-                        match object {
-                            ObjectContent::Message(message) => {
-                                if !content.messages.contains(&message) {
-                                    let id = message.get_id();
-                                    content.messages[id as usize] = message; 
-                                    count.messages += 1;  
-                                    Self::reliable_broadcast_barycentric_report(thread_id, &thread_signal_channel, content, round_number, protocol_information, count).await;
-                                }
-                                
-                                if count.messages >= validity_threshold && state.messages == false {
-                                    state.messages = true; 
-                                }
-                                
-                            },
-                            ObjectContent::Report(_) => {
-                                panic!("Error: received incompatible object type (Report) for barycentric agreement");
-                            },
-                            ObjectContent::AggregatedReport(_) => {                        
-                                panic!("Error: received incompatible object type (AggregatedReport) for barycentric agreement");
-                            },
-                            ObjectContent::BarycentricReport(barycentric_report) => {     
-                                let id = barycentric_report.get_id();
-
-                                content.barycentric_reports[id as usize] = barycentric_report; 
-                                // content.barycentric_reports.insert(barycentric_report.get_id() as usize, barycentric_report);
-                                count.barycentric_reports += 1;  
-                                
-                                if state.messages == true && state.trusted == true {
-                                    Self::initialize_buddies(thread_id, &mut content.messages, &mut content.buddies, &mut content.barycentric_reports, count);
-                                }
-                            },
-                        }
-
-                        if count.barycentric_reports >= agreement_threshold && state.trusted == false {
-                            //confirm approach of using RB barycentric reports to check for a trusted message
-                            if Self::initialize_trusted(thread_id, agreement_threshold, count, content).len() > 0 {
-                                state.trusted = true;
-                            }
-                        }
-
-                        if count.buddies >= validity_threshold && state.buddies == false {
-                            let protocol_information = String::from("barycentric");
-                            let instance_number = 0; 
-                            let trusted_messages = Self::initialize_trusted(thread_id, agreement_threshold, count, content).clone();
-                            let values = Report::new(ReportType::Witness, protocol_information, thread_id, trusted_messages, None, instance_number, round_number); 
-                            thread_channel.send_values(thread_id, values).await;
-                            state.buddies = true;
-                        } 
-                    }
-                }
-            }
-        });
-        handle
+        log!(debug, "initializing barycentric handle...");
+
+        let barycentric_handle: BarycentricHandleLoop<T, Self> = BarycentricHandleLoop::new(self);
+        match self.get_config().get_handle_mode() {
+            HandleMode::Spawned => tokio::spawn(barycentric_handle.run()),
+            HandleMode::Polling => self.get_polled_handles().park(Box::new(barycentric_handle)),
+        }
     } 
 
     // # Method Description:
@@ -413,10 +838,10 @@ where
     // * `round_number` - The current barycentric round identifier.
     // * `protocol_information` - A string describing the protocol context ("barycentric").
     // * `count` - A mutable reference to the round counter tracking messages and reports.
-    async fn reliable_broadcast_barycentric_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut BarycentricRoundContent<T>, round_number: u32, protocol_information: String, count: &mut BarycentricRoundCount){
+    async fn reliable_broadcast_barycentric_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut BarycentricRoundContent<T>, round_number: Round, protocol_information: String, count: &mut BarycentricRoundCount){
         let barycentric_report = Self::create_barycentric_report(thread_id, content, round_number, protocol_information, count); 
         let input = Signal::new(SignalType::Input, ObjectContent::BarycentricReport(barycentric_report.clone()), barycentric_report.get_instance_number(), barycentric_report.get_round_number());
-        println!("id: {thread_id}, broadcasting barycentric_report...");
+        log!(debug, "id: {thread_id}, broadcasting barycentric_report...");
         thread_signal_channel.broadcast_signal(input).await;
     }
 
@@ -424,9 +849,17 @@ where
         &self.report_channels
     }
 
-    fn take_barycentric_handle_rx(&mut self) -> Receiver<String> {
+    fn take_barycentric_handle_rx(&mut self) -> Receiver<Bytes> {
         self.barycentric_handle_rx.take().unwrap()
     }
+
+    fn take_barycentric_command_rx(&mut self) -> UnboundedReceiver<BarycentricHandleCommand> {
+        self.barycentric_command_rx.take().unwrap()
+    }
+
+    fn get_barycentric_command_channel(&self) -> &UnboundedSender<BarycentricHandleCommand> {
+        &self.barycentric_command_tx
+    }
 }
 
 #[async_trait]
@@ -438,100 +871,36 @@ where
         &self.signal_channels
     }
 
-    fn take_reliable_handle_rx(&mut self) -> Receiver<String> {
+    fn get_delivery_report_channels(&self) -> Option<&ReportChannels<T>> {
+        Some(&self.report_channels)
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::from_config(self.get_config(), &[Protocol::Reliable, Protocol::Barycentric])
+    }
+
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>> {
+        &self.event_tx
+    }
+
+    fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T> {
+        &self.delivery_watches
+    }
+
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes> {
         self.reliable_handle_rx.take().unwrap()
     }
 
-    // # Method Description:
-    // This method spawns an asynchronous background task that manages reliable broadcast signals.
-    // It listens for incoming signals, updates the state of each instance,
-    // broadcasts signals based on protocol thresholds, and delivers messages or reports when conditions are met.
-    // # Returns:
-    // * A `JoinHandle<()>` representing the spawned async task.
-    fn initialize_reliable_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing reliable handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let report_channel = self.get_report_channels().clone(); 
-        let thread_count = report_channel.get_handle_channels().len() as u32; 
-        let mut receiver = self.take_reliable_handle_rx(); 
-
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let agreement_threshold = faulty_threads + 1;
-        let mut reliable_broadcast_monitor: HashMap<String, ReliableInstanceMonitor> = HashMap::new();
-
-        let handle = tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    Some(received_signal) = receiver.recv() => {
-                        let signal = match Signal::read_json(&received_signal) {
-                            Ok(correct_signal) => correct_signal,
-                            Err(_)=> { continue },
-                        };
-
-                        let instance_id = Self::get_instance_id(thread_id, signal.clone()); 
-
-                        if let SignalType::Input = signal.get_signal() {
-                            match reliable_broadcast_monitor.get(&instance_id) {
-                                Some(_) => {
-                                    panic!("Error: instance id ({}) already used", instance_id)
-                                },
-                                None => {
-                                    reliable_broadcast_monitor.insert(instance_id.clone(), ReliableInstanceMonitor::new());
-                                },
-                            }
-                        }
-
-                        let instance = reliable_broadcast_monitor.get_mut(&instance_id).unwrap(); 
-                        let state = &mut instance.state; 
-                        let count = &mut instance.count; 
-
-                        match signal.get_signal()
-                        {
-                            SignalType::Input => {
-                                if state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Echo => {
-                                count.echo += 1;
-
-                                if count.echo >= validity_threshold && state.vote == false{
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else if count.echo >= agreement_threshold && state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Vote => {
-                                count.vote += 1;
-    
-                                if count.vote >= validity_threshold && state.deliver == false {
-                                    if signal.get_content().get_protocol_information() == "reliable" {
-                                        let channel = ChannelType::MessageChannels(thread_channel.clone());
-                                         Self::upon_vote(thread_id, channel, signal).await;
-                                    } else {
-                                        let channel = ChannelType::ReportChannels(report_channel.clone());
-                                        Self::upon_vote(thread_id, channel, signal).await;
-                                    }
-                                   
-                                    state.deliver = true;
-                                } else if count.vote >= agreement_threshold && state.vote == false {
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else { continue }
-                            }
-                        }
-                    }
-                }
-            }
-        });
-        handle
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand> {
+        self.reliable_command_rx.take().unwrap()
+    }
+
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand> {
+        &self.reliable_command_tx
     }
 
     // # Method Description:
@@ -571,7 +940,7 @@ where
     // * channel - The channel used to deliver the final message (`MessageChannels` or `ReportChannels`).
     // * signal - The received `Vote` signal.
     async fn upon_vote(thread_id: u32, channel: ChannelType<T>, signal: Signal<T>)  {
-        let object = signal.get_content().clone(); 
+        let object = signal.into_content();
 
         match channel {
             ChannelType::MessageChannels(thread_channel) => {
@@ -593,6 +962,15 @@ where
                     ObjectContent::BarycentricReport(barycentric_report) => {
                         report_channel.send_barycentric_report(thread_id, barycentric_report).await;
                     },
+                    ObjectContent::Commitment(_) => {
+                        panic!("Error: received incompatible object type (Commitment) for barycentric agreement");
+                    },
+                    ObjectContent::Fragment(_) => {
+                        panic!("Error: received incompatible object type (Fragment) for barycentric agreement");
+                    },
+                    ObjectContent::Extension { .. } => {
+                        panic!("Error: received incompatible object type (Extension) for barycentric agreement");
+                    },
                 }
             },
         }
@@ -613,6 +991,14 @@ where
     fn get_id(& self) -> &u32 {
         &self.id
     }
+
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
 }
 
 // # Struct Description:
@@ -626,13 +1012,16 @@ where
 // * messages - A vector of `Message`s collected by this thread for the current round.
 // * instance_number - The consensus instance number associated with this report.
 // * round_number - The round number of the protocol in which this report was generated.
+// * dimension - The dimension of the messages of the report, in multi-dimensional rounds.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct BarycentricReport<T>{
     protocol_information: String, 
     id: u32, 
     messages: Vec<Message<T>>, 
-    instance_number: u32,
-    round_number: u32
+    instance_number: Instance,
+    round_number: Round,
+    #[serde(default)]
+    dimension: Option<u32>,
 }
 
 impl<T> BarycentricReport<T> 
@@ -651,29 +1040,198 @@ where
         &self.messages
     }
 
-    pub fn get_instance_number(&self) -> u32 {
+    pub fn get_instance_number(&self) -> Instance {
         self.instance_number
     }
 
-    pub fn get_round_number(&self) -> u32 {
+    pub fn get_round_number(&self) -> Round {
         self.round_number
     }
 
-    pub fn new(protocol_information: String, id: u32, messages: Vec<Message<T>>, instance_number: u32, round_number: u32) -> Self {
+    pub fn get_dimension(&self) -> Option<u32> {
+        self.dimension
+    }
+
+    pub fn new(protocol_information: String, id: u32, messages: Vec<Message<T>>, instance_number: Instance, round_number: Round) -> Self {
         Self {
             protocol_information,
             id, 
             messages,
             instance_number,
-            round_number
+            round_number,
+            dimension: None,
         }
     }
+
+    pub fn with_dimension(mut self, dimension: Option<u32>) -> Self {
+        self.dimension = dimension;
+        self
+    }
 }
 
 impl<T> JsonConversion<BarycentricReport<T>> for BarycentricReport<T> where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {}
 
+impl<T> FrameKind for BarycentricReport<T> {
+    const FRAME_KIND: u64 = frame_kinds::BARYCENTRIC_REPORT_FRAME;
+}
+
+// # Struct Description:
+// This struct attests that a thread was a buddy of the delivering thread: the barycentric report it
+// reliably broadcast carried the same message set as the delivering thread.
+//
+// # Fields:
+// * id - The ID of the buddy.
+// * instance_number - The instance number of the buddy's matching barycentric report.
+// * report_digest - The digest of the buddy's matching barycentric report.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BuddyAttestation {
+    id: u32,
+    instance_number: Instance,
+    report_digest: u64,
+}
+
+impl BuddyAttestation {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_instance_number(&self) -> Instance {
+        self.instance_number
+    }
+
+    pub fn get_report_digest(&self) -> u64 {
+        self.report_digest
+    }
+}
+
+// # Struct Description:
+// This struct is the output certificate of a barycentric agreement round: the buddies that justified
+// its delivery, with the digests of their matching reports. It is attached to the delivered `Report`
+// and collected with `barycentric_collect_certified`, so experiments can check the buddy mechanism
+// offline, against the barycentric reports recorded by any thread (see `verify`).
+//
+// # Fields:
+// * id - The ID of the delivering thread.
+// * round_number - The delivered round.
+// * validity_threshold - The number of buddies needed to deliver.
+// * messages_digest - The digest of the message set carried by every matching report.
+// * attestations - The attestation of every buddy, by increasing thread ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BuddyCertificate {
+    id: u32,
+    round_number: Round,
+    validity_threshold: u64,
+    messages_digest: u64,
+    attestations: Vec<BuddyAttestation>,
+}
+
+impl BuddyCertificate {
+    // # Method Description:
+    // This method certifies the buddies of a round at the time it is delivered.
+    //
+    // # Parameters:
+    // * id - The ID of the delivering thread.
+    // * round_number - The delivered round.
+    // * validity_threshold - The number of buddies needed to deliver.
+    // * buddies - Whether every thread is a buddy, by thread ID.
+    // * barycentric_reports - The latest barycentric report received from every thread, by thread ID.
+    //
+    // # Returns:
+    // * A `BuddyCertificate` with an attestation for every buddy.
+    pub fn new<T>(id: u32, round_number: Round, validity_threshold: u64, buddies: &[bool], barycentric_reports: &[BarycentricReport<T>]) -> Self
+    where
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let matching_reports: Vec<&BarycentricReport<T>> = buddies.iter().zip(barycentric_reports)
+            .filter(|(buddy, _)| **buddy)
+            .map(|(_, barycentric_report)| barycentric_report)
+            .collect();
+        let messages_digest = matching_reports.first().map(|barycentric_report| messages_digest(barycentric_report.get_messages())).unwrap_or_default();
+        let attestations = matching_reports.iter()
+            .map(|barycentric_report| BuddyAttestation {
+                id: barycentric_report.get_id(),
+                instance_number: barycentric_report.get_instance_number(),
+                report_digest: barycentric_report.json_digest(),
+            })
+            .collect();
+
+        Self {
+            id,
+            round_number,
+            validity_threshold,
+            messages_digest,
+            attestations,
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_validity_threshold(&self) -> u64 {
+        self.validity_threshold
+    }
+
+    pub fn get_messages_digest(&self) -> u64 {
+        self.messages_digest
+    }
+
+    pub fn get_attestations(&self) -> &Vec<BuddyAttestation> {
+        &self.attestations
+    }
+
+    pub fn get_buddy_ids(&self) -> Vec<u32> {
+        self.attestations.iter().map(|attestation| attestation.id).collect()
+    }
+
+    // # Method Description:
+    // This method checks the certificate against recorded barycentric reports: the certificate must attest
+    // enough distinct buddies, and every attestation must match a recorded report of the round, sent by the
+    // buddy with the attested instance number and digest, and carrying the certified message set.
+    //
+    // # Parameters:
+    // * barycentric_reports - The barycentric reports recorded for the round, in any order.
+    //
+    // # Returns:
+    // * `true` if the certificate justifies the delivery.
+    pub fn verify<T>(&self, barycentric_reports: &[BarycentricReport<T>]) -> bool
+    where
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let distinct = self.attestations.windows(2).all(|pair| pair[0].id < pair[1].id);
+        distinct && self.attestations.len() as u64 >= self.validity_threshold && self.attestations.iter().all(|attestation| {
+            barycentric_reports.iter().any(|barycentric_report| barycentric_report.get_id() == attestation.id
+                && barycentric_report.get_round_number() == self.round_number
+                && barycentric_report.get_instance_number() == attestation.instance_number
+                && barycentric_report.json_digest() == attestation.report_digest
+                && messages_digest(barycentric_report.get_messages()) == self.messages_digest)
+        })
+    }
+}
+
+impl JsonConversion<BuddyCertificate> for BuddyCertificate {}
+
+// # Function Description:
+// This function computes the digest of the message set carried by a barycentric report.
+//
+// # Parameters:
+// * messages - The message set.
+//
+// # Returns:
+// * A `u64` digest of the message set.
+fn messages_digest<T>(messages: &[Message<T>]) -> u64
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    messages.iter().fold(0xcbf29ce484222325, |digest, message| (digest ^ message.get_digest()).wrapping_mul(0x100000001b3))
+}
+
 // # Struct Description:
 // This struct monitors the state of a single round in the barycentric agreement protocol,
 // aggregating the collected content, current state flags, and count of received messages and reports.
@@ -705,6 +1263,11 @@ where
             count
         }
     }
+
+    pub fn with_dimension(mut self, dimension: Option<u32>) -> Self {
+        self.content.dimension = dimension;
+        self
+    }
 }
 
 // # Struct Description:
@@ -740,13 +1303,15 @@ impl BarycentricRoundState {
 // * messages - A vector of `Message`s collected in this round.
 // * barycentric_reports - A vector of `BarycentricReport`s received in this round.
 // * buddies - A vector of booleans representing whether each peer is considered a buddy for this round.
+// * dimension - The dimension agreed on in this round, in multi-dimensional rounds.
 pub struct BarycentricRoundContent<T> 
 where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
 {
     pub messages: Vec<Message<T>>,
     pub barycentric_reports: Vec<BarycentricReport<T>>,
-    pub buddies: Vec<bool>
+    pub buddies: Vec<bool>,
+    pub dimension: Option<u32>,
 }
 
 impl<T> BarycentricRoundContent<T> 
@@ -754,8 +1319,8 @@ where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
 {
     pub fn new(thread_count: u32) -> Self {
-        let initial_message = Message::new("".to_string(), 0, T::default(), None, None, 0); 
-        let initial_report = BarycentricReport::new("".to_string(), 0, vec![initial_message.clone()], 0, 0);
+        let initial_message = Message::new("".to_string(), 0, T::default(), None, None, Round(0)); 
+        let initial_report = BarycentricReport::new("".to_string(), 0, vec![initial_message.clone()], Instance(0), Round(0));
         let messages = vec![initial_message; thread_count as usize];
         let barycentric_reports = vec![initial_report; thread_count as usize];
         let buddies = vec![false; thread_count as usize];
@@ -763,7 +1328,8 @@ where
         Self {
             messages,
             barycentric_reports,
-            buddies
+            buddies,
+            dimension: None,
         }
     }
 }
@@ -772,25 +1338,54 @@ where
 // This struct keeps numerical counts for tracking progress in a barycentric round.
 //
 // # Fields:
-// * messages - The number of messages received in this round.
+// * messages - The number of messages received in this round, which numbers the reports of the thread.
+// * counted_messages - The weight the messages received in this round count for (see `SelfCounting`).
 // * barycentric_reports - The number of barycentric reports received in this round.
 // * buddies - The number of confirmed buddies in this round.
 pub struct BarycentricRoundCount {
     pub messages: u32,
-    pub barycentric_reports: u32, 
-    pub buddies: u32
+    pub counted_messages: u64,
+    pub barycentric_reports: u64, 
+    pub buddies: u64
 }
 
 impl BarycentricRoundCount {
     pub fn new() -> Self {
         let messages = 0; 
+        let counted_messages = 0; 
         let barycentric_reports = 0; 
         let buddies = 0; 
         Self {
             messages,
+            counted_messages,
             barycentric_reports,
             buddies
         }
     }
 }
 
+
+// # Function Description:
+// This function describes barycentric agreement as the barycentric handle runs it (see
+// `BarycentricHandleLoop`), for the tooling reading `ProtocolDescriptor`s.
+//
+// # Parameters:
+// * config - The configuration of the cluster, in which the quorums are evaluated.
+//
+// # Returns:
+// * The descriptor of barycentric agreement.
+pub fn protocol_descriptor(config: &ClusterConfig) -> ProtocolDescriptor {
+    ProtocolDescriptor::new(Protocol::Barycentric, "barycentric", "barycentric agreement: every correct thread delivers trusted values, certified by the buddies whose reports match its own messages", config)
+        .with_builds_on(&[Protocol::Reliable])
+        .with_message_type(MessageTypeDescriptor::new("Message", "ObjectContent::Message", Lane::Report, "the value of a thread in the round, reliably broadcast with barycentric_agreement"))
+        .with_message_type(MessageTypeDescriptor::new("Barycentric report", "ObjectContent::BarycentricReport", Lane::Report, "the messages a thread received so far, reliably broadcast after every new message"))
+        .with_message_type(MessageTypeDescriptor::new("Collection", "ReportType::Witness", Lane::Basic, "the trusted values of the round with their BuddyCertificate, queued for barycentric_collect"))
+        .with_phase(PhaseDescriptor::new("messages", "every thread reliably broadcasts its value, and a barycentric report of its messages after every new one; the phase completes once the messages reach the validity quorum")
+            .with_threshold("Message", Quorum::Validity)
+            .with_sends(&["Barycentric report"]), config)
+        .with_phase(PhaseDescriptor::new("barycentric reports", "a message held by barycentric reports reaching the agreement quorum is trusted")
+            .with_threshold("Barycentric report", Quorum::Agreement), config)
+        .with_phase(PhaseDescriptor::new("buddies", "once its messages are complete and a message is trusted, a thread counts as buddies the threads whose report holds exactly its messages, and delivers the trusted messages once they reach the validity quorum")
+            .with_threshold("Barycentric report", Quorum::Validity)
+            .with_sends(&["Collection"]), config)
+}
@@ -1,13 +1,37 @@
 use core::panic;
-use std::{vec, fmt::Debug, hash::Hash, marker::PhantomData};
-use tokio::sync::mpsc::{Receiver, Sender};
-use std::collections::{HashMap, VecDeque};
+use std::{vec, fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc, time::{Duration, Instant}};
+use bytes::Bytes;
+use tokio::{sync::{broadcast, mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender}}, time};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use futures::future::join_all;
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
 use async_trait::async_trait; 
+use rand::rngs::StdRng;
 
-use crate::json::{JsonConversion};
+use crate::json::{Codec, Frame, FrameKind, JsonConversion, WireFormat, frame_kinds};
+use crate::config::ClusterConfig;
+use crate::multiplex::{Lane, tag_frame, open_lanes, read_attribution};
+use crate::drive::{PolledHandles, drive};
 use crate::witness::Report;
+use crate::events::{AbandonedCollect, DecodeFailure, Event};
+use crate::metrics::Metrics;
+use crate::delivered::Delivered;
+use crate::membership::MembershipCertificate;
+use crate::roster::{Roster, send_frame};
+use crate::round::{Instance, Round};
+use crate::ordering::{CAUSAL, OrderedDelivery, VectorClock};
+use crate::logging::log;
+use crate::retention::{RetentionPolicy, RetentionTracker};
+
+// How long `validate_wiring` waits for the probes of every thread to arrive.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The number of quarantined frames kept by a thread; older frames are dropped first.
+const QUARANTINE_LIMIT: usize = 1024;
+
+// The number of received messages a thread remembers to drop their copies, whatever its retention policy
+// keeps; older messages are forgotten first.
+const DUPLICATE_WINDOW: usize = 4096;
 
 // # Trait Description:
 // A trait that defines basic communication behavior for a node in a distributed system:
@@ -20,6 +44,19 @@ where
     fn get_channels(&self) -> &MessageChannels<T>;
     fn get_queues(&mut self) -> &mut BasicQueues<T>;
     fn get_id(& self) -> &u32;
+    fn get_config(&self) -> &ClusterConfig;
+    fn get_polled_handles(&mut self) -> &mut PolledHandles;
+
+    // # Method Description:
+    // This method creates the seeded RNG a randomized component of this thread must draw from,
+    // derived from the experiment seed held by the hub configuration.
+    // # Parameters
+    // * `component` - A name identifying the randomized component (e.g. "gossip").
+    // # Returns
+    // A `StdRng` that reproduces the same sequence whenever the experiment is re-run with the same seed.
+    fn create_rng(&self, component: &str) -> StdRng {
+        self.get_config().create_rng(*self.get_id(), component)
+    }
 
     // # Method Description:
     // This method sends a message to a specific node by ID.
@@ -29,23 +66,28 @@ where
     // * `round_number` - The current communication round, to track consensus or protocol progress.
     // # Returns
     // A future that sends the message and resolves when the send operation completes.
-    fn basic_send(&mut self, id: u32, message: T, round_number: u32) -> impl Future<Output = ()> {
+    fn basic_send(&mut self, id: u32, message: T, round_number: Round) -> impl Future<Output = ()> {
         let protocol_information = String::from("basic") ;
         let sent_message = Message::new(protocol_information ,*self.get_id(), message, None, None, round_number); 
+        let sent_message = self.get_queues().stamp(sent_message);
+        let sent_message = self.get_queues().number(sent_message);
         self.get_channels().send_message(id, sent_message)
     }
 
     // # Method Description:
-    // This method broadcasts a message to all other nodes.
+    // This method broadcasts a message to every node, including this one unless self-delivery is
+    // disabled (see `ClusterConfig::with_deliver_to_self`).
     // # Parameters
     // * `message` - The message content to broadcast.
     // * `round_number` - The current communication round, to track consensus or protocol progress.
     // # Returns
     // A future that broadcasts the message to all peers and resolves when all sends complete.
-    fn basic_broadcast(&mut self, message: T, round_number: u32) -> impl Future<Output = ()> {
+    fn basic_broadcast(&mut self, message: T, round_number: Round) -> impl Future<Output = ()> {
         let protocol_information = String::from("basic") ;
         let sent_message = Message::new(protocol_information, *self.get_id(), message, None, None, round_number);
-        self.get_channels().broadcast_message(sent_message)
+        let sent_message = self.get_queues().stamp(sent_message);
+        let sent_message = self.get_queues().number(sent_message);
+        self.get_channels().broadcast_message(sent_message, self.get_config().get_deliver_to_self())
     }
 
     // # Method Description:
@@ -55,7 +97,7 @@ where
     // * `round_number` - The current communication round, to track consensus or protocol progress.
     // # Returns
     // A `Message` instance received from the local queue, once available.
-    async fn basic_recv(&mut self, id: Option<u32>, round_number: u32) -> Message<T> {
+    async fn basic_recv(&mut self, id: Option<u32>, round_number: Round) -> Message<T> {
         let protocol_information = String::from("basic") ;
         match
         self.get_queues().basic_recv(id, protocol_information, None, round_number).await {
@@ -65,38 +107,119 @@ where
             RecvObject::Collection(_) => {panic!("Error: retreived Vec<Message> instead of Message")},
         }
     }
+
+    // # Method Description:
+    // This method receives the next available message like `basic_recv`, in the `Delivered` envelope shared
+    // by every protocol.
+    // # Parameters
+    // * `id` - Optional ID of the sender to filter by; if `None`, receives any message.
+    // * `round_number` - The current communication round, to track consensus or protocol progress.
+    // # Returns
+    // The `Delivered` value, once available.
+    async fn basic_recv_delivered(&mut self, id: Option<u32>, round_number: Round) -> Delivered<T> {
+        let message = self.basic_recv(id, round_number).await;
+        Delivered::from_message(*self.get_id(), message)
+    }
+
+    // # Method Description:
+    // This method receives the next available message like `basic_recv`, but stops waiting after a timeout,
+    // e.g. so that an application can retry or change views instead of blocking forever. A message
+    // delivered after the timeout stays in the local queue.
+    // # Parameters
+    // * `id` - Optional ID of the sender to filter by; if `None`, receives any message.
+    // * `round_number` - The current communication round, to track consensus or protocol progress.
+    // * `timeout` - How long to wait for the message.
+    // # Returns
+    // The `Message`, or `RecvError::Timeout` if none was delivered in time.
+    async fn basic_recv_with_timeout(&mut self, id: Option<u32>, round_number: Round, timeout: Duration) -> Result<Message<T>, RecvError> {
+        time::timeout(timeout, self.basic_recv(id, round_number)).await
+            .map_err(|_| RecvError::Timeout(String::from("basic"), None, round_number))
+    }
+
+    // # Method Description:
+    // This method receives up to `max` already-delivered messages from the local queue in one call,
+    // e.g. so that a downstream state machine can apply commands in batches. It waits until the
+    // deadline only if no matching message has been delivered yet.
+    // # Parameters
+    // * `id` - Optional ID of the sender to filter by; if `None`, receives messages from any sender.
+    // * `round_number` - The current communication round, to track consensus or protocol progress.
+    // * `max` - The maximum number of messages to return.
+    // * `deadline` - The instant after which the method returns even if nothing was delivered.
+    // # Returns
+    // A vector of up to `max` `Message`s, in delivery order per sender; empty if the deadline passed first.
+    async fn basic_recv_batch(&mut self, id: Option<u32>, round_number: Round, max: usize, deadline: Instant) -> Vec<Message<T>> {
+        let protocol_information = String::from("basic");
+        self.get_queues().recv_batch(id, protocol_information, round_number, max, deadline).await
+    }
+
+    // # Method Description:
+    // This method sets the application-level validation of the messages delivered to the local queues, by
+    // basic or reliable broadcast. A message the validator rejects is quarantined (see `quarantined`)
+    // instead of being stored, so it is never received. The validator sees the whole message, so it can
+    // accept different payloads in different rounds.
+    // # Parameters
+    // * `validator` - A function returning a description of why a message is rejected, if it is.
+    fn set_payload_validator(&mut self, validator: impl Fn(&Message<T>) -> Result<(), String> + Send + Sync + 'static) {
+        self.get_queues().set_payload_validator(Arc::new(validator));
+    }
+
+    // # Method Description:
+    // This method returns the frames the local queues quarantined instead of storing them: frames larger
+    // than `ClusterConfig::with_max_payload_size`, frames that are not a message of the expected type, and
+    // messages rejected by the payload validator. Frames already received are checked first. Every frame
+    // names the peer it was received from over a network transport, so that the sources of malformed
+    // traffic can be identified whatever the frames claim; the quarantined frames are also counted per
+    // sender in the thread's metrics (`MetricsReport::get_quarantined_senders`).
+    // # Returns
+    // The quarantined frames, oldest first, up to the last 1024.
+    fn quarantined(&mut self) -> Vec<QuarantinedFrame> {
+        self.get_queues().store_pending();
+        self.get_queues().get_quarantined().iter().cloned().collect()
+    }
 }
 
 
 // # Struct Description:
 // This struct manages multiple BasicCommunicator instances to support message passing
 // between asynchronous threads. It assigns each communicator a receiver and shares a
-// common set of transmitters for inter-thread communication.
+// common set of transmitters for inter-thread communication. Each receiver is demultiplexed
+// into per-lane queues, of which basic communicators only read the `Basic` lane.
 
 // # Fields:
 // * basic_communicators - A vector of BasicCommunicator instances initialized for each thread
+// * config - The configuration shared by every communicator created from this hub
 pub struct BasicHub<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
     basic_communicators: Vec<BasicCommunicator<T>>,
+    config: ClusterConfig,
 }
 
 impl<T> BasicHub<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    pub fn new(transmitters: Vec<Sender<String>>, mut receivers: Vec<Receiver<String>>, thread_count: u32) -> Self {
+    pub fn new(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, thread_count: u32) -> Self {
+        Self::with_config(transmitters, receivers, ClusterConfig::new(thread_count))
+    }
+
+    pub fn with_config(transmitters: Vec<Sender<Bytes>>, mut receivers: Vec<Receiver<Bytes>>, config: ClusterConfig) -> Self {
         let mut basic_communicators = vec![];
-        for i in 0..thread_count {
-            let rx = receivers.remove(0); 
-            basic_communicators.push(BasicCommunicator::new(transmitters.clone(), rx, thread_count, i as u32));
+        for i in 0..config.get_thread_count() {
+            let lanes = open_lanes(i, config.link_receiver(i, receivers.remove(0)), &config); 
+            basic_communicators.push(BasicCommunicator::new(config.link_transmitters(i, &transmitters), lanes.basic, PolledHandles::new(lanes.demultiplexer), i, config.clone()));
         }
         Self {
-            basic_communicators
+            basic_communicators,
+            config
         }
     }
 
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
     // # Method Description:
     // This method removes and returns the first available BasicCommunicator from the hub.
     // # Returns:
@@ -104,6 +227,18 @@ where
     pub fn create_basic_communicator(&mut self) -> BasicCommunicator<T>{
         self.basic_communicators.remove(0)
     }
+
+    // # Method Description:
+    // This method checks that every channel of the hub reaches the thread it is meant for (see `validate_wiring`).
+    // It must be called before any communicator is created from the hub.
+    // # Returns:
+    // * `Ok(())` if the wiring is correct, or a description of every mis-wired channel.
+    pub async fn validate(&mut self) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        validate_wiring(&mut self.basic_communicators).await
+    }
 }
 
 // # Struct Description:
@@ -114,6 +249,8 @@ where
 // * id - The thread’s unique ID.
 // * channels - A struct encapsulating all transmitters for outgoing messages.
 // * queues - A struct that handles incoming messages via the thread’s local receiver.
+// * polled_handles - The parts of the thread driven manually in `HandleMode::Polling`.
+// * config - The configuration shared by every communicator created from the same hub.
 pub struct BasicCommunicator<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
@@ -121,20 +258,24 @@ where
     id: u32,
     channels: MessageChannels<T>, 
     queues: BasicQueues<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
 }
 
 impl<T> BasicCommunicator<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    fn new(transmitters: Vec<Sender<String>>, rx: Receiver<String>, thread_count: u32, id: u32) -> Self {
-        let channels = MessageChannels::<T>::new(transmitters);
-        let queues = BasicQueues::new(rx, thread_count);
+    fn new(transmitters: Vec<Sender<Bytes>>, rx: Receiver<Bytes>, polled_handles: PolledHandles, id: u32, config: ClusterConfig) -> Self {
+        let channels = MessageChannels::<T>::new(transmitters).with_codec(config.get_codec());
+        let queues = BasicQueues::new(rx, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy()).with_validation(id, &config);
 
         Self {
             id, 
             channels,
-            queues
+            queues,
+            polled_handles,
+            config
         }
     }
 }
@@ -154,6 +295,13 @@ where
         &self.id
     }
 
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
 }
 
 
@@ -164,6 +312,8 @@ where
 // It holds a list of channel transmitters to facilitate direct and broadcast communication.
 // # Fields:
 // * tx_vec - A vector of cloned transmitters for sending messages to a specific thread.
+// * codec - The wire format of the frames sent.
+// * roster - The live membership of the cluster, whose current members are the only recipients of broadcasts, if threads join and leave at runtime.
 
 /*
 The PhantomData<T> is included as a field in the struct as the generic parameter T 
@@ -176,7 +326,9 @@ pub struct MessageChannels<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    tx_vec: Vec<Sender<String>>,
+    tx_vec: Arc<Vec<Sender<Bytes>>>,
+    codec: Codec,
+    roster: Option<Roster>,
     _marker: PhantomData<T>,
 }
 
@@ -185,7 +337,7 @@ where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
     // # Method Description:
-    // Ths method sends a message to a specific thread using its ID. The message is serialized to JSON.
+    // Ths method sends a message to a specific thread using its ID. The message is serialized in the wire format of the channels.
     // # Parameters:
     // * id - The recipient thread’s ID
     // * message - The `Message` sent to the specified thread.
@@ -193,8 +345,8 @@ where
         async move {
             match self.get_channels().get(id as usize) {
                 Some(channel) => {
-                    let _ = channel.send(message.write_json()).await;
-                    println!("sent: {:?}", &message.get_message());
+                    let _ = channel.send(tag_frame(Lane::Basic, message.write_frame(self.codec))).await;
+                    log!(trace, "sent: {:?}", &message.get_message());
 
                 },
                 None => panic!("Error: failed to find channel"),
@@ -203,32 +355,103 @@ where
     }
 
     // # Method Description:
-    // This method broadcasts a message to all threads in the system. Each message is cloned
-    // and sent individually to each thread’s channel.
+    // This method broadcasts a message to all threads in the system. The message is encoded once,
+    // and its frame is sent individually to each thread’s channel.
     // # Parameters:
     // * message - The `Message` broadcasted to all threads.
-    pub(crate) fn broadcast_message(&self, message: Message<T>) -> impl Future<Output = ()> {
+    // * deliver_to_self - Whether the message is also sent to its sender.
+    pub(crate) fn broadcast_message(&self, message: Message<T>, deliver_to_self: bool) -> impl Future<Output = ()> {
         let mut send_fns= vec![];
-        for tx in self.get_channels() {
-            let sent_message = message.clone();
-            println!("broadcast: {:?}", & sent_message.get_message());
-            send_fns.push(tx.send(sent_message.write_json()));
+        let frame = tag_frame(Lane::Basic, message.write_frame(self.codec));
+        for (id, tx) in self.get_channels().iter().enumerate() {
+            if !deliver_to_self && id as u32 == message.get_id() {
+                continue
+            }
+            if self.roster.as_ref().is_some_and(|roster| !roster.is_member(id as u32)) {
+                continue
+            }
+            log!(trace, "broadcast: {:?}", message.get_message());
+            send_fns.push(send_frame(tx, frame.clone()));
         }; 
         async move {
             join_all(send_fns).await; 
         }
     }   
 
-    pub fn get_channels(&self) -> &Vec<Sender<String>> {
-        &self.tx_vec
+    // # Method Description:
+    // This method notifies a thread's `BasicQueues` that a round was aborted by an undecodable frame
+    // (see `DecodePolicy::Strict`). It is only sent by a thread's handles to the thread itself.
+    // # Parameters:
+    // * id - The ID of the thread whose round was aborted.
+    // * failure - The `DecodeFailure` that aborted the round.
+    pub(crate) async fn send_decode_failure(&self, id: u32, failure: DecodeFailure) {
+        match self.get_channels().get(id as usize) {
+            Some(channel) => {
+                let _ = channel.send(tag_frame(Lane::Basic, failure.write_frame(self.codec))).await;
+            },
+            None => panic!("Error: failed to find channel"),
+        }
+    }
+
+    // # Method Description:
+    // This method sends a wiring probe (see `validate_wiring`) over the transmitter to a thread.
+    // # Parameters:
+    // * sender - The ID of the sending thread.
+    // * recipient - The ID of the thread the transmitter is expected to reach.
+    pub(crate) async fn send_probe(&self, sender: u32, recipient: u32) {
+        match self.get_channels().get(recipient as usize) {
+            Some(channel) => {
+                let _ = channel.send(tag_frame(Lane::Basic, Probe::new(sender, recipient).write_frame(self.codec))).await;
+            },
+            None => panic!("Error: failed to find channel"),
+        }
+    }
+
+    // # Method Description:
+    // This method provides the transmitters of the channels: those of every thread that ever joined the
+    // cluster with a roster, the transmitters the channels were built with otherwise.
+    pub fn get_channels(&self) -> Arc<Vec<Sender<Bytes>>> {
+        match &self.roster {
+            Some(roster) => roster.get_transmitters(),
+            None => self.tx_vec.clone(),
+        }
     }
 
-    pub fn new(tx_vec: Vec<Sender<String>>) -> Self {
+    pub fn new(tx_vec: Vec<Sender<Bytes>>) -> Self {
         Self {
-            tx_vec,
+            tx_vec: Arc::new(tx_vec),
+            codec: Codec::Json,
+            roster: None,
             _marker: PhantomData,
         }
     }
+
+    // # Method Description:
+    // This method makes the channels reach the threads of a roster, and only broadcast to its current members.
+    //
+    // # Parameters:
+    // * roster - The live membership of the cluster.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_roster(mut self, roster: &Roster) -> Self {
+        self.roster = Some(roster.clone());
+        self
+    }
+
+    // # Method Description:
+    // This method sets the wire format of the frames sent over the channels.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
 }
 
 
@@ -240,70 +463,460 @@ where
 //
 // # Fields: 
 // * rx - a incoming asynchronous channel for receiving raw messages
-// * queues - a hashmap where each key corresponds to a sender thread's ID,
-//            and each value is a queue of parsed `Message`s received from that sender.
+// * queues - a map where each key corresponds to a sender thread's ID, in ID order,
+//            and each value is the indexed queue of the objects received from that sender (see `SenderQueue`).
+// * aborts - the decode failures that aborted rounds of the thread (see `DecodePolicy::Strict`).
+// * collected - the collection of every round collected so far and not dropped by the retention policy, by protocol, instance and round.
+// * collected_retention - the rounds of `collected`, dropped following the retention policy of the cluster (see `RetentionPolicy`).
+// * repeated_collect_policy - how a round that was already collected is collected again.
+// * probes - the wiring probes received and not taken yet (see `validate_wiring`).
+// * id - the ID of the thread, recorded in its quarantined frames.
+// * max_payload_size - the size above which frames are quarantined, if any.
+// * payload_validator - the application-level validation of the messages, if any.
+// * quarantine - the frames quarantined so far, oldest first.
+// * codec - the wire format of the frames received.
+// * membership - the signed membership of the cluster, outside of which messages are quarantined, if any.
+// * metrics - the metrics of the communicator, in which quarantined frames are counted, if it has any.
+// * roster - the live membership of the cluster, whose threads that joined after the queues were created get a queue on their first message, if any.
+// * event_channel - the channel on which `Event::AbandonedCollect` events are published, if any.
+// * abandon_tx - the channel on which the guards of abandoned collections hand their rounds back to the queues.
+// * abandon_rx - the receiving end of `abandon_tx`.
+// * abandoned - the abandoned collections whose round was not delivered yet, by protocol, instance and round.
+// * reclaimed - the collection of every abandoned round delivered since, kept for the next collect of the round.
+// * next_sender - the ID from which `basic_recv` searches the queues of every sender next, one past the last sender it returned a message of.
+// * ordered_delivery - the ordering state of the FIFO and causal broadcasts of the thread.
+// * clock - the vector clock of the thread, if the communicators maintain vector clocks (see `ClusterConfig::with_vector_clocks`).
+// * stored - the messages stored so far and not dropped by the retention policy, by digest, so that a duplicated message is stored once.
+// * stored_retention - the digests of `stored`, dropped following the retention policy of the cluster.
+// * stored_order - the digests of `stored`, in the order they were stored, of which only the last `DUPLICATE_WINDOW` are kept.
+// * sent - the number of basic messages the thread sent so far, with which it numbers the next one.
+// * eviction - how the objects nobody receives are evicted from the queues (see `QueueEviction`).
 pub struct BasicQueues<T> 
 where 
     T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
 {
-    rx: Receiver<String>,
-    queues: HashMap<u32, VecDeque<RecvObject<T>>>,
+    rx: Receiver<Bytes>,
+    queues: BTreeMap<u32, SenderQueue<T>>,
+    aborts: Vec<DecodeFailure>,
+    collected: HashMap<CollectKey, RecvObject<T>>,
+    collected_retention: RetentionTracker<CollectKey>,
+    repeated_collect_policy: RepeatedCollectPolicy,
+    probes: Vec<Probe>,
+    id: u32,
+    max_payload_size: Option<usize>,
+    payload_validator: Option<PayloadValidator<T>>,
+    quarantine: VecDeque<QuarantinedFrame>,
+    metrics: Option<Metrics>,
+    codec: Codec,
+    membership: Option<MembershipCertificate>,
+    roster: Option<Roster>,
+    event_channel: Option<broadcast::Sender<Event<T>>>,
+    abandon_tx: UnboundedSender<(u32, CollectKey)>,
+    abandon_rx: UnboundedReceiver<(u32, CollectKey)>,
+    abandoned: HashSet<CollectKey>,
+    reclaimed: HashMap<CollectKey, RecvObject<T>>,
+    next_sender: u32,
+    ordered_delivery: OrderedDelivery,
+    clock: Option<VectorClock>,
+    stored: HashMap<u64, Vec<Message<T>>>,
+    stored_retention: RetentionTracker<u64>,
+    stored_order: VecDeque<u64>,
+    sent: u64,
+    eviction: QueueEviction,
 }
 
+// The protocol, instance and round identifying a collection.
+type CollectKey = (String, Option<Instance>, Round);
+
 impl<T> BasicQueues<T>
 where
     T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
 {
 
-    pub fn get_receiver(&mut self) -> &mut Receiver<String> {
+    pub fn get_receiver(&mut self) -> &mut Receiver<Bytes> {
         &mut self.rx
     }
 
-    pub fn get_queues(&mut self) -> &mut HashMap<u32, VecDeque<RecvObject<T>>> {
+    pub fn get_queues(&mut self) -> &mut BTreeMap<u32, SenderQueue<T>> {
         &mut self.queues
     }
 
-    pub fn new(rx: Receiver<String>, thread_count: u32) -> Self {
-        let mut queues: HashMap<u32, VecDeque<RecvObject<T>>> = HashMap::new(); 
+    pub fn get_ordered_delivery(&mut self) -> &mut OrderedDelivery {
+        &mut self.ordered_delivery
+    }
+
+    pub fn get_clock(&self) -> Option<&VectorClock> {
+        self.clock.as_ref()
+    }
+
+    // # Method Description:
+    // This method stamps a message the thread sends with its vector clock, after counting the send in it,
+    // if the communicators maintain vector clocks.
+    //
+    // # Parameters:
+    // * message - The message.
+    //
+    // # Returns:
+    // * The message, stamped if the communicators maintain vector clocks.
+    pub(crate) fn stamp(&mut self, message: Message<T>) -> Message<T> {
+        match &mut self.clock {
+            Some(clock) => {
+                clock.increment(self.id);
+                message.with_clock(clock.clone())
+            },
+            None => message,
+        }
+    }
+
+    // # Method Description:
+    // This method numbers a basic message the thread sends, so that its recipients tell a second identical
+    // send apart from a copy of the first one, e.g. a frame duplicated by the network.
+    //
+    // # Parameters:
+    // * message - The message.
+    //
+    // # Returns:
+    // * The numbered message.
+    pub(crate) fn number(&mut self, message: Message<T>) -> Message<T> {
+        self.sent += 1;
+        message.with_sequence(self.sent)
+    }
+
+    // # Method Description:
+    // This method merges the vector clock of a message the thread receives into its own, and counts the
+    // receipt in it. Causal broadcasts carry the broadcasts they depend on rather than the clock of their
+    // sender, and are not merged.
+    //
+    // # Parameters:
+    // * message - The message.
+    fn observe(&mut self, message: &Message<T>) {
+        if let Some(clock) = &mut self.clock
+            && let Some(received) = message.get_clock()
+            && message.get_protocol_information() != CAUSAL
+        {
+            clock.merge(received);
+            clock.increment(self.id);
+            log!(trace, "{} clock after receiving from {}: {:?}", self.id, message.get_id(), clock.get_entries());
+        }
+    }
+
+    pub fn new(rx: Receiver<Bytes>, thread_count: u32) -> Self {
+        let mut queues: BTreeMap<u32, SenderQueue<T>> = BTreeMap::new(); 
         for i in 0..thread_count {
-            let buffer: VecDeque<RecvObject<T>> = VecDeque::new();
-            queues.insert(i, buffer);
+            queues.insert(i, SenderQueue::new());
         }
+        let (abandon_tx, abandon_rx) = mpsc::unbounded_channel();
 
         Self {
             rx,
-            queues
+            queues,
+            aborts: vec![],
+            collected: HashMap::new(),
+            collected_retention: RetentionTracker::new(RetentionPolicy::KeepAll),
+            repeated_collect_policy: RepeatedCollectPolicy::default(),
+            probes: vec![],
+            id: 0,
+            max_payload_size: None,
+            payload_validator: None,
+            quarantine: VecDeque::new(),
+            metrics: None,
+            codec: Codec::Json,
+            membership: None,
+            roster: None,
+            event_channel: None,
+            abandon_tx,
+            abandon_rx,
+            abandoned: HashSet::new(),
+            reclaimed: HashMap::new(),
+            next_sender: 0,
+            ordered_delivery: OrderedDelivery::new(),
+            clock: None,
+            stored: HashMap::new(),
+            stored_retention: RetentionTracker::new(RetentionPolicy::KeepAll),
+            stored_order: VecDeque::new(),
+            sent: 0,
+            eviction: QueueEviction::Never,
+        }
+    }
+
+    // # Method Description:
+    // This method makes the queues accept the messages of the threads joining a roster after they were created.
+    //
+    // # Parameters:
+    // * roster - The live membership of the cluster.
+    //
+    // # Returns:
+    // * The updated queues.
+    pub fn with_roster(mut self, roster: &Roster) -> Self {
+        self.roster = Some(roster.clone());
+        self
+    }
+
+    // # Method Description:
+    // This method provides the queue of a sender, creating it if the sender joined the roster after the
+    // queues were created.
+    //
+    // # Parameters:
+    // * id - The ID of the sender.
+    //
+    // # Returns:
+    // * The queue of the sender, `None` if the sender is unknown.
+    fn get_queue(&mut self, id: u32) -> Option<&mut SenderQueue<T>> {
+        if !self.queues.contains_key(&id) && self.roster.as_ref().is_some_and(|roster| roster.is_known(id)) {
+            self.queues.insert(id, SenderQueue::new());
+        }
+        self.queues.get_mut(&id)
+    }
+
+    // # Method Description:
+    // This method makes the queues publish an `Event::AbandonedCollect` whenever a caller stops waiting for
+    // the collection of a round.
+    //
+    // # Parameters:
+    // * event_channel - The event channel of the communicator.
+    //
+    // # Returns:
+    // * The updated queues.
+    pub fn with_event_channel(mut self, event_channel: broadcast::Sender<Event<T>>) -> Self {
+        self.event_channel = Some(event_channel);
+        self
+    }
+
+    pub fn with_repeated_collect_policy(mut self, repeated_collect_policy: RepeatedCollectPolicy) -> Self {
+        self.repeated_collect_policy = repeated_collect_policy;
+        self
+    }
+
+    // # Method Description:
+    // This method sets how the queues decode and validate the frames they receive.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster, holding the wire format, the maximum payload size, the
+    //   membership, the eviction of the objects nobody receives, and the retention of the collected rounds
+    //   and stored messages.
+    //
+    // # Returns:
+    // * The updated queues.
+    pub fn with_validation(mut self, id: u32, config: &ClusterConfig) -> Self {
+        self.id = id;
+        self.max_payload_size = config.get_max_payload_size();
+        self.codec = config.get_codec();
+        self.membership = config.get_membership().cloned();
+        self.clock = config.get_vector_clocks().then(VectorClock::new);
+        self.eviction = config.get_queue_eviction();
+        self.collected_retention = RetentionTracker::new(config.get_retention_policy());
+        self.stored_retention = RetentionTracker::new(config.get_retention_policy());
+        self
+    }
+
+    // # Method Description:
+    // This method counts the quarantined frames in the metrics of the communicator.
+    //
+    // # Returns:
+    // * The updated queues.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn set_payload_validator(&mut self, payload_validator: PayloadValidator<T>) {
+        self.payload_validator = Some(payload_validator);
+    }
+
+    pub fn get_quarantined(&self) -> &VecDeque<QuarantinedFrame> {
+        &self.quarantine
+    }
+
+    // # Method Description:
+    // This method quarantines a frame, counting it in the metrics of the communicator.
+    //
+    // # Parameters:
+    // * peer - The peer the frame was received from, if a network transport attributed it.
+    // * source - The ID of the thread that sent the frame, according to the frame, if it could be read.
+    // * round_number - The round of the frame, if known.
+    // * reason - Why the frame is quarantined.
+    // * frame - The frame.
+    fn quarantine(&mut self, peer: Option<u32>, source: Option<u32>, round_number: Option<Round>, reason: QuarantineReason, frame: Bytes) {
+        let mut quarantined = QuarantinedFrame::new(self.id, source, round_number, reason, frame);
+        if let Some(peer) = peer {
+            quarantined = quarantined.with_peer(peer);
+        }
+        log!(warn, { node = self.id }, "id: {}, quarantining frame from {:?}: {:?}", self.id, quarantined.get_sender(), quarantined.get_reason());
+        if let Some(metrics) = &self.metrics {
+            metrics.record_quarantined(quarantined.get_reason().get_kind(), quarantined.get_sender());
+        }
+        if self.quarantine.len() == QUARANTINE_LIMIT {
+            self.quarantine.pop_front();
+        }
+        self.quarantine.push_back(quarantined);
+    }
+
+    // # Method Description:
+    // This method retrieves the collection a handle delivered for a round from the thread's own queue,
+    // like `basic_recv`, unless the round was aborted by an undecodable frame first. A collection
+    // delivered before the abort is still returned. The collection is kept, so collecting the round again
+    // returns it (or `CollectError::AlreadyConsumed`, see `RepeatedCollectPolicy`) instead of waiting for a
+    // second delivery that never comes. Once the retention policy of the cluster dropped the collection,
+    // collecting the round again returns `CollectError::AlreadyConsumed` under either policy.
+    //
+    // The collection is drop-safe: if the returned future is dropped while waiting (e.g. by a timeout or a
+    // `select!`), the round is handed back to the queues, which publish `Event::AbandonedCollect` and keep
+    // its collection, once delivered, for the next collect of the round instead of leaving it in the queue.
+    //
+    // # Parameters:
+    // * id - The ID of the thread, whose handles deliver the collection.
+    // * protocol_information - A `String` describing the protocol context.
+    // * instance_number - Optional `Instance` specifying the instance of the collection.
+    // * round_number - A `Round` identifying the round of the collection.
+    //
+    // # Returns:
+    // * `Ok(RecvObject)` once the collection is delivered, or the `CollectError` preventing it.
+    pub(crate) async fn collect_round(&mut self, id: u32, protocol_information: String, instance_number: Option<Instance>, round_number: Round) -> Result<RecvObject<T>, CollectError> {
+        let key = (protocol_information, instance_number, round_number);
+        self.drop_retained();
+        if let Some(object) = self.collected.get(&key) {
+            return match self.repeated_collect_policy {
+                RepeatedCollectPolicy::Cached => Ok(object.clone()),
+                RepeatedCollectPolicy::AlreadyConsumed => Err(CollectError::AlreadyConsumed(key.0, round_number)),
+            }
+        }
+        if self.collected_retention.is_collected(&key) {
+            return Err(CollectError::AlreadyConsumed(key.0, round_number))
+        }
+        self.reclaim_abandoned();
+        self.abandoned.remove(&key);
+        if let Some(object) = self.reclaimed.remove(&key) {
+            self.keep_collected(key, object.clone());
+            return Ok(object)
+        }
+        let guard = CollectGuard::new(id, key.clone(), self.abandon_tx.clone(), self.event_channel.clone());
+        loop {
+            let queue = match self.get_queue(id) {
+                Some(queue) => queue,
+                None => panic!("Error: queue not found"),
+            };
+            if let Some(object) = queue.take(&key) {
+                guard.disarm();
+                self.keep_collected(key, object.clone());
+                return Ok(object)
+            }
+            if let Some(failure) = self.aborts.iter().find(|failure| failure.aborts_round(round_number)) {
+                guard.disarm();
+                return Err(CollectError::Aborted(failure.clone()))
+            }
+            self.store_message().await;
+        }
+    }
+
+    // # Method Description:
+    // This method keeps the collection of a round for the next collects of the round, until the retention
+    // policy of the cluster drops it.
+    //
+    // # Parameters:
+    // * key - The protocol, instance and round of the collection.
+    // * object - The collection.
+    fn keep_collected(&mut self, key: CollectKey, object: RecvObject<T>) {
+        self.collected_retention.complete(key.clone());
+        self.collected.insert(key, object);
+    }
+
+    // # Method Description:
+    // This method drops the collections and the stored messages the retention policy of the cluster no
+    // longer keeps. A message dropped from `stored` is stored again if a copy of it arrives later.
+    fn drop_retained(&mut self) {
+        for key in self.collected_retention.collect() {
+            self.collected.remove(&key);
+        }
+        for digest in self.stored_retention.collect() {
+            self.stored.remove(&digest);
+        }
+    }
+
+    // # Method Description:
+    // This method records a message as stored, unless an identical message was stored before. Messages are
+    // looked up by digest, then compared whole, so that two distinct messages with the same digest are both
+    // stored. Basic messages are numbered by their sender (see `number`), so only a copy of one send is
+    // identical to it. Only the last `DUPLICATE_WINDOW` messages are remembered, so that the record does not
+    // grow with every message under `RetentionPolicy::KeepAll`.
+    //
+    // # Parameters:
+    // * message - The message.
+    //
+    // # Returns:
+    // * `true` if an identical message was stored before and not forgotten since.
+    fn is_duplicate(&mut self, message: &Message<T>) -> bool {
+        let digest = message.get_digest();
+        let messages = self.stored.entry(digest).or_default();
+        if messages.contains(message) {
+            return true
+        }
+        messages.push(message.clone());
+        self.stored_retention.complete(digest);
+        self.stored_order.push_back(digest);
+        if self.stored_order.len() > DUPLICATE_WINDOW && let Some(forgotten) = self.stored_order.pop_front()
+            && let Some(messages) = self.stored.get_mut(&forgotten)
+        {
+            messages.remove(0);
+            if messages.is_empty() {
+                self.stored.remove(&forgotten);
+            }
+        }
+        false
+    }
+
+    // # Method Description:
+    // This method takes the rounds handed back by the guards of abandoned collections. The collection of a
+    // round already delivered is moved from the queue to the reclaimed collections; the other rounds are
+    // recorded, so that their collection is reclaimed as soon as it is received.
+    fn reclaim_abandoned(&mut self) {
+        while let Ok((id, key)) = self.abandon_rx.try_recv() {
+            let queue = match self.get_queue(id) {
+                Some(queue) => queue,
+                None => panic!("Error: queue not found"),
+            };
+            match queue.take(&key) {
+                Some(object) => {
+                    self.reclaimed.insert(key, object);
+                },
+                None => {
+                    self.abandoned.insert(key);
+                },
+            }
         }
     }
     
     // # Method Description: 
     // This method retrieves a message from the appropriate local queue. If a specific `id` is provided, 
-    // it targets that sender's queue; otherwise, it searches across all queues and returns the first 
-    // matching message. The function continuously checks queues until a matching message is found and 
-    // blocks asynchronously until a message matching the given parameters is available. 
+    // it targets that sender's queue; otherwise, it stores the frames already received, then searches the
+    // queues round-robin, starting from the sender after the one it last returned a message of, so that
+    // no sender with matching messages waits for more than one message of every other sender. The 
+    // function continuously checks queues until a matching message is found and blocks asynchronously 
+    // until a message matching the given parameters is available. 
     //
     // # Parameters:
     // * id - Optional `u32` representing the sender's thread ID. If `None`, any available queue is searched.
     // * protocol_information - A `String` describing the protocol context.
-    // * instance_number - Optional `u32` specifying the communication instance for messages received using the reliable broadcast protocol.
-    // * round_number - A `u32` identifying the round of the protocol to match the correct message.
+    // * instance_number - Optional `Instance` specifying the communication instance for messages received using the reliable broadcast protocol.
+    // * round_number - A `Round` identifying the round of the protocol to match the correct message.
     //
     // # Returns:
     // * A `RecvObject`, that may be either:
     //   - `RecvObject::Message` containing a `Message`
     //   - `RecvObject::Collection` containing a collection of `Message`s.
-    pub(crate) async fn basic_recv(&mut self, id: Option<u32>, protocol_information: String, instance_number: Option<u32>, round_number: u32) -> RecvObject<T> {
+    pub(crate) async fn basic_recv(&mut self, id: Option<u32>, protocol_information: String, instance_number: Option<Instance>, round_number: Round) -> RecvObject<T> {
         match id {
             Some(id) => {
                 loop {
-                    let queue = match self.get_queues().get_mut(&id) {
+                    let queue = match self.get_queue(id) {
                         Some(queue) => queue,
                         None => panic!("Error: queue not found"),
                     };
                     if !queue.is_empty() {
                         match Self::retreive_message(queue, &protocol_information, instance_number, round_number) {
                             Some(RecvObject::Message(message)) => {
-                                println!("{} received(specified): {:?}", message.get_protocol_information(),message.get_message());                               
+                                log!(trace, "{} received(specified): {:?}", message.get_protocol_information(),message.get_message());                               
+                                self.observe(&message);
                                 return RecvObject::Message(message)
                             },
                             Some(RecvObject::Collection(collection)) => {return RecvObject::Collection(collection)},
@@ -314,13 +927,25 @@ where
                 }
             },
             None => {
+                self.store_pending();
                 loop {
-                    for set in self.get_queues() {
-                        let queue = set.1; 
+                    let mut ids: Vec<u32> = self.get_queues().keys().copied().collect();
+                    let start = ids.partition_point(|id| *id < self.next_sender);
+                    ids.rotate_left(start);
+                    for id in ids {
+                        let queue = match self.get_queues().get_mut(&id) {
+                            Some(queue) => queue,
+                            None => panic!("Error: queue not found"),
+                        };
                         if !queue.is_empty() {
-                            match Self::retreive_message(queue, &protocol_information, instance_number, round_number) {
+                            let object = Self::retreive_message(queue, &protocol_information, instance_number, round_number);
+                            if object.is_some() {
+                                self.next_sender = id + 1;
+                            }
+                            match object {
                                 Some(RecvObject::Message(message)) => {
-                                    println!("{} received(any): {:?}", message.get_protocol_information(),message.get_message());                               
+                                    log!(trace, "{} received(any): {:?}", message.get_protocol_information(),message.get_message());                               
+                                    self.observe(&message);
                                     return RecvObject::Message(message)
                                 },
                                 Some(RecvObject::Collection(collection)) => {
@@ -336,25 +961,106 @@ where
         }
     }
 
+    // # Method Description:
+    // This method retrieves the first message of the given protocol, of any instance and round, that the
+    // ordering state of the thread allows to receive, e.g. the next FIFO broadcast of a sender. The queues
+    // of every sender are searched from the one after the last sender a message was received from, as in
+    // `basic_recv`, so that no sender is starved.
+    //
+    // # Parameters:
+    // * protocol_information - A `String` describing the protocol context.
+    // * deliverable - Whether a message can be received, given the ordering state of the thread.
+    //
+    // # Returns:
+    // * The first receivable `Message`, once available.
+    pub(crate) async fn recv_ordered(&mut self, protocol_information: &str, deliverable: impl Fn(&OrderedDelivery, &Message<T>) -> bool) -> Message<T> {
+        self.store_pending();
+        loop {
+            let mut ids: Vec<u32> = self.queues.keys().copied().collect();
+            let start = ids.partition_point(|id| *id < self.next_sender);
+            ids.rotate_left(start);
+            for id in ids {
+                let Some(queue) = self.queues.get_mut(&id) else {
+                    panic!("Error: queue not found")
+                };
+                let ordered_delivery = &self.ordered_delivery;
+                if let Some(RecvObject::Message(message)) = queue.take_first(|object| matches!(object, RecvObject::Message(message)
+                    if message.get_protocol_information() == protocol_information && deliverable(ordered_delivery, message))) {
+                    log!(trace, "{} received(ordered): {:?}", message.get_protocol_information(), message.get_message());
+                    self.next_sender = id + 1;
+                    self.observe(&message);
+                    return message
+                }
+            }
+            self.store_message().await;
+        }
+    }
+
+    // # Method Description:
+    // This method retrieves up to `max` messages of the given protocol and round, of any instance, from
+    // the appropriate local queues. Frames already received on the channel are stored first without
+    // waiting; the method only waits for new frames, until the deadline, while no message matches.
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing the sender's thread ID. If `None`, the queues of every sender are searched, in ID order.
+    // * protocol_information - A `String` describing the protocol context.
+    // * round_number - A `Round` identifying the round of the protocol to match the correct messages.
+    // * max - The maximum number of messages to retrieve.
+    // * deadline - The instant after which the method stops waiting for new frames.
+    //
+    // # Returns:
+    // * A vector of up to `max` matching `Message`s, empty if none arrived before the deadline.
+    pub(crate) async fn recv_batch(&mut self, id: Option<u32>, protocol_information: String, round_number: Round, max: usize, deadline: Instant) -> Vec<Message<T>> {
+        let ids: Vec<u32> = match id {
+            Some(id) => vec![id],
+            None => self.get_queues().keys().copied().collect(),
+        };
+
+        let mut batch = vec![];
+        loop {
+            self.store_pending();
+            for id in &ids {
+                let queue = match self.get_queues().get_mut(id) {
+                    Some(queue) => queue,
+                    None => panic!("Error: queue not found"),
+                };
+                while batch.len() < max {
+                    match queue.take_first(|object| matches!(object, RecvObject::Message(message) 
+                        if message.get_protocol_information() == &protocol_information && message.get_round_number() == round_number)) {
+                        Some(RecvObject::Message(message)) => batch.push(message),
+                        _ => break,
+                    }
+                }
+            }
+            if !batch.is_empty() || max == 0 {
+                log!(trace, "{} received(batch): {} messages", protocol_information, batch.len());
+                for message in &batch {
+                    self.observe(message);
+                }
+                return batch
+            }
+            if time::timeout_at(time::Instant::from_std(deadline), self.store_message()).await.is_err() {
+                return batch
+            }
+        }
+    }
+
     // # Function Description:
-    // This function searches a given queue for a message that matches the specified 
+    // This function looks up the index of a given queue for a message that matches the specified 
     // protocol information, instance number, and round number. If such a message exists, it is 
     // removed from the queue and returned; otherwise, the function returns `None`.
     //
     // # Parameters:
-    // * queue - A mutable reference to a `VecDeque<RecvObject>` representing the message queue.
+    // * queue - A mutable reference to the `SenderQueue` of a sender.
     // * protocol_information - A `String` describing the protocol context to match against.
-    // * instance_number - Optional `u32` specifying the communication instance to filter messages.
-    // * round_number - A `u32` identifying the round of the protocol.
+    // * instance_number - Optional `Instance` specifying the communication instance to filter messages.
+    // * round_number - A `Round` identifying the round of the protocol.
     //
     // # Returns:
     // * `Some(RecvObject)` if a matching message is found and removed from the queue.
     // * `None` if no matching message exists in the queue.
-    fn retreive_message(queue: &mut VecDeque<RecvObject<T>>, protocol_information: &String, instance_number: Option<u32>,round_number: u32) -> Option<RecvObject<T>>{
-        match queue.iter().position(|object| object.get_protocol_information() == protocol_information && object.get_instance_number() == instance_number && object.get_round_number() == round_number) {
-            Some(index) => return queue.remove(index),
-            None => return None, 
-        }
+    fn retreive_message(queue: &mut SenderQueue<T>, protocol_information: &str, instance_number: Option<Instance>,round_number: Round) -> Option<RecvObject<T>>{
+        queue.take(&(protocol_information.to_string(), instance_number, round_number))
     }
 
     // # Method Description:
@@ -363,34 +1069,673 @@ where
     async fn store_message(&mut self) {
         tokio::select! {
             Some(received_message) = self.get_receiver().recv() => {
-                let object: RecvObject<T>; 
-                if let Ok(message) = Message::read_json(&received_message) {
-                    object = RecvObject::Message(message);
-                } else if let Ok(collection) = Report::read_json(&received_message) {
-                    object = RecvObject::Collection(collection);
-                } else {
+                self.store_frame(received_message);
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method stores every frame already received on the thread's channel, without waiting for new ones.
+    //
+    // # Returns:
+    // * The number of frames received.
+    pub(crate) fn store_pending(&mut self) -> usize {
+        let mut received = 0;
+        while let Ok(received_message) = self.get_receiver().try_recv() {
+            self.store_frame(received_message);
+            received += 1;
+        }
+        received
+    }
+
+    // # Method Description:
+    // This method removes and returns the wiring probes received so far.
+    fn take_probes(&mut self) -> Vec<Probe> {
+        std::mem::take(&mut self.probes)
+    }
+
+    // # Method Description:
+    // This method parses a frame received from the thread's channel and stores it into the
+    // appropriate local queue based on the sender ID. Notifications of aborted rounds and wiring
+    // probes are recorded, and frames that are too large, cannot be parsed, or are rejected by the
+    // payload validator are quarantined, attributed to the peer they were received from if a network
+    // transport attributed them. Storing is idempotent: a message identical to one stored before (e.g. a
+    // frame a transport sent again after a reconnection) is not stored twice, whether the first copy is
+    // still waiting in the queue of its sender or was already received, until the retention policy of the
+    // cluster drops it.
+    //
+    // # Parameters:
+    // * received_message - The received frame, attributed to its peer by network transports.
+    fn store_frame(&mut self, received_message: Bytes) {
+        self.reclaim_abandoned();
+        self.drop_retained();
+        let (peer, received_message) = read_attribution(&received_message);
+        if let Some(max_payload_size) = self.max_payload_size.filter(|max_payload_size| received_message.len() > *max_payload_size) {
+            let failure = DecodeFailure::new(self.id, Lane::Basic, &received_message);
+            self.quarantine(peer, failure.get_source(), failure.get_round_number(), QuarantineReason::Size(received_message.len(), max_payload_size), received_message);
+            return;
+        }
+        let object = match BasicFrame::read(self.codec, &received_message) {
+            Ok(BasicFrame::Message(message)) => {
+                if self.membership.as_ref().is_some_and(|membership| !membership.is_member(message.get_id())) {
+                    self.quarantine(peer, Some(message.get_id()), Some(message.get_round_number()), QuarantineReason::NonMember(message.get_id()), received_message);
                     return;
                 }
-
-                match self.get_queues().get_mut(& object.get_id())
-                {
-                    Some(queue) => {
-                        match &object {
-                            RecvObject::Message(message) => {
-                                println!("stored: {:?}", message.get_message());                               
-                            },
-                            RecvObject::Collection(collection) => {
-                                println!("stored: Report by id: {}", collection.get_id());
-                            }
-                        }
-                        queue.push_back(object);
-                    },
-                    None => panic!("Error: failed to find buffer"), 
+                if let Some(Err(reason)) = self.payload_validator.as_ref().map(|validator| validator(&message)) {
+                    self.quarantine(peer, Some(message.get_id()), Some(message.get_round_number()), QuarantineReason::Predicate(reason), received_message);
+                    return;
                 }
+                RecvObject::Message(message)
+            },
+            Ok(BasicFrame::Collection(collection)) => RecvObject::Collection(collection),
+            Ok(BasicFrame::Probe(probe)) => {
+                self.probes.push(probe);
+                return;
+            },
+            Ok(BasicFrame::Abort(failure)) => {
+                log!(warn, "round aborted: {:?}", failure.get_round_number());
+                self.aborts.push(failure);
+                return;
+            },
+            Err(error) => {
+                let failure = DecodeFailure::new(self.id, Lane::Basic, &received_message);
+                self.quarantine(peer, failure.get_source(), failure.get_round_number(), QuarantineReason::Schema(error), received_message);
+                return;
+            },
+        };
+
+        if let RecvObject::Collection(collection) = &object {
+            let key = (collection.get_protocol_information().clone(), Some(collection.get_instance_number()), collection.get_round_number());
+            if self.abandoned.remove(&key) {
+                log!(trace, "reclaimed: Report by id: {}", collection.get_id());
+                self.reclaimed.insert(key, object);
+                return;
             }
         }
-    }
-}
+
+        // a message is stored once, even if a copy arrives after it was taken, e.g. when the network duplicates frames
+        let id = self.id;
+        let duplicate = match &object {
+            RecvObject::Message(message) => self.is_duplicate(message),
+            RecvObject::Collection(_) => false,
+        };
+        match self.get_queue(object.get_id())
+        {
+            Some(queue) => {
+                if duplicate && let RecvObject::Message(message) = &object {
+                    log!(debug, { node = id }, "id: {id}, not storing a duplicate message of {} in round {}", message.get_id(), message.get_round_number());
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_duplicate(message.get_id());
+                    }
+                    return;
+                }
+                match &object {
+                    RecvObject::Message(message) => {
+                        log!(trace, "stored: {:?}", message.get_message());                               
+                    },
+                    RecvObject::Collection(collection) => {
+                        log!(trace, "stored: Report by id: {}", collection.get_id());
+                    }
+                }
+                queue.push_back(object);
+            },
+            None => panic!("Error: failed to find buffer"), 
+        }
+        self.evict_unclaimed();
+    }
+
+    // # Method Description:
+    // This method evicts the objects nobody received from the queue of every sender, following the
+    // `QueueEviction` of the cluster, and counts them in the metrics of the communicator.
+    fn evict_unclaimed(&mut self) {
+        if self.eviction == QueueEviction::Never {
+            return
+        }
+        let id = self.id;
+        for (sender, queue) in self.queues.iter_mut() {
+            for object in queue.evict(self.eviction) {
+                log!(debug, { node = id }, "id: {id}, evicting {} of {sender} in round {}, never received", object.get_protocol_information(), object.get_round_number());
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_evicted(*sender);
+                }
+            }
+        }
+    }
+}
+
+// # Struct Description:
+// This struct holds the objects received from a sender and not received yet by the thread. They are indexed
+// by protocol, instance and round, so that the first object of a round is found in constant time whatever the
+// number of objects waiting, and kept in the order they were stored, for the searches matching a predicate
+// (e.g. `BasicQueues::recv_ordered`), for snapshots, and for eviction (see `QueueEviction`).
+//
+// # Fields:
+// * objects - The objects waiting, with the instant they were stored, by sequence number, in the order they were stored.
+// * index - The sequence numbers of the objects of every protocol, instance and round, oldest first.
+// * front - The sequence number of the first object, decreased by the objects queued ahead of the others.
+// * next - The sequence number of the next object stored.
+#[derive(Debug, Clone)]
+pub struct SenderQueue<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    objects: BTreeMap<u64, (RecvObject<T>, Instant)>,
+    index: HashMap<CollectKey, VecDeque<u64>>,
+    front: u64,
+    next: u64,
+}
+
+impl<T> Default for SenderQueue<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SenderQueue<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self {
+            objects: BTreeMap::new(),
+            index: HashMap::new(),
+            front: u64::MAX / 2,
+            next: u64::MAX / 2,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    // # Method Description:
+    // This method iterates over the objects waiting, in the order they were stored.
+    pub fn iter(&self) -> impl Iterator<Item = &RecvObject<T>> {
+        self.objects.values().map(|(object, _)| object)
+    }
+
+    // # Method Description:
+    // This method stores an object behind the objects waiting.
+    //
+    // # Parameters:
+    // * object - The object.
+    pub fn push_back(&mut self, object: RecvObject<T>) {
+        let sequence = self.next;
+        self.next += 1;
+        self.index.entry(object_key(&object)).or_default().push_back(sequence);
+        self.objects.insert(sequence, (object, Instant::now()));
+    }
+
+    // # Method Description:
+    // This method stores an object ahead of the objects waiting, e.g. when a snapshot is restored.
+    //
+    // # Parameters:
+    // * object - The object.
+    pub fn push_front(&mut self, object: RecvObject<T>) {
+        self.front -= 1;
+        let sequence = self.front;
+        self.index.entry(object_key(&object)).or_default().push_front(sequence);
+        self.objects.insert(sequence, (object, Instant::now()));
+    }
+
+    // # Method Description:
+    // This method takes the first object of a protocol, instance and round.
+    //
+    // # Parameters:
+    // * key - The protocol, instance and round.
+    //
+    // # Returns:
+    // * The object stored first among the objects of the key, `None` if none is waiting.
+    pub fn take(&mut self, key: &CollectKey) -> Option<RecvObject<T>> {
+        let sequences = self.index.get_mut(key)?;
+        let sequence = sequences.pop_front()?;
+        if sequences.is_empty() {
+            self.index.remove(key);
+        }
+        self.objects.remove(&sequence).map(|(object, _)| object)
+    }
+
+    // # Method Description:
+    // This method takes the first object, in the order they were stored, matching a predicate.
+    //
+    // # Parameters:
+    // * predicate - The predicate.
+    //
+    // # Returns:
+    // * The first matching object, `None` if none is waiting.
+    pub fn take_first(&mut self, predicate: impl Fn(&RecvObject<T>) -> bool) -> Option<RecvObject<T>> {
+        let sequence = self.objects.iter().find(|(_, (object, _))| predicate(object)).map(|(sequence, _)| *sequence)?;
+        self.remove(sequence)
+    }
+
+    // # Method Description:
+    // This method evicts the objects nobody claimed, following an eviction policy.
+    //
+    // # Parameters:
+    // * eviction - The eviction policy.
+    //
+    // # Returns:
+    // * The evicted objects, oldest first.
+    pub fn evict(&mut self, eviction: QueueEviction) -> Vec<RecvObject<T>> {
+        let mut evicted = vec![];
+        while let Some((sequence, (_, stored))) = self.objects.first_key_value() {
+            let expired = match eviction {
+                QueueEviction::Never => false,
+                QueueEviction::GracePeriod(grace_period) => stored.elapsed() >= grace_period,
+                QueueEviction::Capacity(capacity) => self.objects.len() > capacity,
+            };
+            if !expired {
+                break
+            }
+            let sequence = *sequence;
+            evicted.extend(self.remove(sequence));
+        }
+        evicted
+    }
+
+    fn remove(&mut self, sequence: u64) -> Option<RecvObject<T>> {
+        let (object, _) = self.objects.remove(&sequence)?;
+        let key = object_key(&object);
+        if let Some(sequences) = self.index.get_mut(&key) {
+            sequences.retain(|indexed| *indexed != sequence);
+            if sequences.is_empty() {
+                self.index.remove(&key);
+            }
+        }
+        Some(object)
+    }
+}
+
+// # Function Description:
+// This function provides the protocol, instance and round an object is indexed by in the queue of its sender.
+//
+// # Parameters:
+// * object - The object.
+fn object_key<T>(object: &RecvObject<T>) -> CollectKey
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    (object.get_protocol_information().clone(), object.get_instance_number(), object.get_round_number())
+}
+
+// # Struct Description:
+// This struct guards a collection in progress (see `BasicQueues::collect_round`). Dropped armed, i.e. when the
+// future collecting the round is dropped before the round is delivered, it hands the round back to the
+// queues and publishes `Event::AbandonedCollect`.
+//
+// # Fields:
+// * id - The ID of the thread collecting the round.
+// * key - The protocol, instance and round of the collection, `None` once the guard is disarmed.
+// * abandon_tx - The channel on which the round is handed back to the queues.
+// * event_channel - The channel on which the event is published, if any.
+struct CollectGuard<T> 
+where 
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    id: u32,
+    key: Option<CollectKey>,
+    abandon_tx: UnboundedSender<(u32, CollectKey)>,
+    event_channel: Option<broadcast::Sender<Event<T>>>,
+}
+
+impl<T> CollectGuard<T>
+where 
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    fn new(id: u32, key: CollectKey, abandon_tx: UnboundedSender<(u32, CollectKey)>, event_channel: Option<broadcast::Sender<Event<T>>>) -> Self {
+        Self {
+            id,
+            key: Some(key),
+            abandon_tx,
+            event_channel
+        }
+    }
+
+    // # Method Description:
+    // This method disarms the guard once the collection completed.
+    fn disarm(mut self) {
+        self.key = None;
+    }
+}
+
+impl<T> Drop for CollectGuard<T>
+where 
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    fn drop(&mut self) {
+        let Some(key) = self.key.take() else {
+            return
+        };
+        log!(debug, { node = self.id }, "id: {}, abandoned collection of round {}", self.id, key.2);
+        if let Some(event_channel) = &self.event_channel {
+            let _ = event_channel.send(Event::AbandonedCollect(AbandonedCollect::new(self.id, key.0.clone(), key.1, key.2)));
+        }
+        let _ = self.abandon_tx.send((self.id, key));
+    }
+}
+
+// # Struct Description:
+// This struct is the frame sent by `validate_wiring` over every transmitter of every thread. It never
+// reaches the local queues: the receiving `BasicQueues` records it until the validation takes it.
+//
+// # Fields:
+// * sender - The ID of the thread that sent the probe.
+// * recipient - The ID of the thread the transmitter used was expected to reach.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct Probe {
+    sender: u32,
+    recipient: u32,
+}
+
+impl Probe {
+    fn new(sender: u32, recipient: u32) -> Self {
+        Self {
+            sender,
+            recipient
+        }
+    }
+}
+
+impl JsonConversion<Probe> for Probe {}
+
+impl FrameKind for Probe {
+    const FRAME_KIND: u64 = frame_kinds::PROBE_FRAME;
+}
+
+// # Function Description:
+// This function checks that the communicators of a hub are wired as their IDs say, before an experiment
+// relies on it: every thread sends a probe over its transmitter to every thread, and every thread must
+// receive exactly one probe from every thread, each addressed to itself. Mis-ordered transmitters or
+// receivers (e.g. when assembling the channels of a hub by hand) otherwise show up as a run that silently
+// hangs. Probes are consumed by the validation, so they leave nothing in the queues. Every thread must be
+// hosted by this process, and emulated loss may fail the validation.
+//
+// # Parameters:
+// * communicators - Every communicator of the hub, none of them handed out yet.
+//
+// # Returns:
+// * `Ok(())` if the wiring is correct, or a description of every mis-wired channel.
+pub(crate) async fn validate_wiring<T, C>(communicators: &mut [C]) -> Result<(), String>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: BasicCommunication<T>,
+{
+    let thread_count = match communicators.first() {
+        Some(communicator) => communicator.get_config().get_thread_count(),
+        None => return Err(String::from("every communicator of the hub was created already")),
+    };
+    if communicators.len() != thread_count as usize {
+        return Err(format!("{} of {} communicators were created already", thread_count as usize - communicators.len(), thread_count))
+    }
+
+    // every thread receives one probe at a time, so the probes never fill a bounded channel
+    for sender in 0..communicators.len() {
+        let id = *communicators[sender].get_id();
+        for recipient in 0..thread_count {
+            communicators[sender].get_channels().send_probe(id, recipient).await;
+        }
+        for communicator in communicators.iter_mut() {
+            drive(communicator).await;
+        }
+    }
+
+    let deadline = Instant::now() + PROBE_TIMEOUT;
+    let mut probes: Vec<Vec<Probe>> = communicators.iter().map(|_| vec![]).collect();
+    loop {
+        for (index, communicator) in communicators.iter_mut().enumerate() {
+            drive(communicator).await;
+            probes[index].extend(communicator.get_queues().take_probes());
+        }
+        if probes.iter().all(|received| received.len() >= thread_count as usize) || Instant::now() >= deadline {
+            break
+        }
+        time::sleep(Duration::from_millis(1)).await;
+    }
+
+    let mut errors = vec![];
+    for (communicator, received) in communicators.iter().zip(probes) {
+        let id = *communicator.get_id();
+        let mut misdirected: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for probe in received.iter().filter(|probe| probe.recipient != id) {
+            misdirected.entry(probe.recipient).or_default().push(probe.sender);
+        }
+        for (recipient, senders) in misdirected {
+            errors.push(format!("thread {id} received the probes sent to thread {recipient} by threads {senders:?}"));
+        }
+        let missing: Vec<u32> = (0..thread_count).filter(|sender| !received.iter().any(|probe| probe.sender == *sender && probe.recipient == id)).collect();
+        if !missing.is_empty() {
+            errors.push(format!("thread {id} received no probe from threads {missing:?}"));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+// # Enum Description:
+// This enum represents how a communicator answers a collect call (e.g. `witness_collect`) for a round it
+// already collected. The delivered collection is consumed from the queue by the first call, so without
+// a policy a second call would wait forever.
+//
+// # Variants:
+// * Cached - The collection delivered to the first call is returned again.
+// * AlreadyConsumed - The call fails with `CollectError::AlreadyConsumed`, e.g. to catch protocol code
+//   that collects a round twice by mistake.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum RepeatedCollectPolicy {
+    #[default]
+    Cached,
+    AlreadyConsumed,
+}
+
+// # Enum Description:
+// This enum represents how the local queues of a thread evict the objects nobody claims, e.g. the messages
+// of a round the application moved past, or of a sender it never receives from, which would otherwise be
+// kept for the lifetime of the communicator. Evicted objects are counted in the metrics of the thread, by
+// sender (see `Metrics::get_evicted`).
+//
+// # Variants:
+// * Never - Objects are kept until they are received.
+// * GracePeriod - Objects are evicted once they waited for the given duration without being received.
+// * Capacity - At most the given number of objects are kept per sender; the ones stored first are evicted first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum QueueEviction {
+    #[default]
+    Never,
+    GracePeriod(Duration),
+    Capacity(usize),
+}
+
+// # Enum Description:
+// This enum represents why a round could not be collected (e.g. by `try_witness_collect`).
+//
+// # Variants:
+// * Aborted - The round was aborted by an undecodable frame, under `DecodePolicy::Strict`.
+// * AlreadyConsumed - The round, of the given protocol and round number, was already collected, under
+//   `RepeatedCollectPolicy::AlreadyConsumed`.
+// * Timeout - The round, of the given protocol and round number, was not delivered before the timeout of
+//   a `*_collect_with_timeout` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectError {
+    Aborted(DecodeFailure),
+    AlreadyConsumed(String, Round),
+    Timeout(String, Round),
+}
+
+// # Enum Description:
+// This enum represents why a message could not be received (e.g. by `reliable_recv_with_timeout`).
+//
+// # Variants:
+// * Timeout - No message of the given protocol, instance number, and round number was delivered before
+//   the timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecvError {
+    Timeout(String, Option<Instance>, Round),
+}
+
+// A function validating the messages delivered to the local queues (see `BasicCommunication::set_payload_validator`).
+pub type PayloadValidator<T> = Arc<dyn Fn(&Message<T>) -> Result<(), String> + Send + Sync>;
+
+// # Enum Description:
+// This enum represents why the local queues of a thread quarantined a frame.
+//
+// # Variants:
+// * Size - The frame is larger than the configured maximum. Carries its size and the maximum, in bytes.
+// * Schema - The frame is not a message, collection, or notification of the expected payload type.
+//   Carries the decoding error.
+// * Predicate - The message was rejected by the payload validator. Carries the validator's reason.
+// * NonMember - The message was sent by a thread that is not a member of the configured membership.
+//   Carries the ID of the sender.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuarantineReason {
+    Size(usize, usize),
+    Schema(String),
+    Predicate(String),
+    NonMember(u32),
+}
+
+impl QuarantineReason {
+    // # Method Description:
+    // This method names the kind of validation that failed, as counted in the thread's metrics.
+    //
+    // # Returns:
+    // * "size", "schema", "predicate", or "non-member".
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            QuarantineReason::Size(..) => "size",
+            QuarantineReason::Schema(_) => "schema",
+            QuarantineReason::Predicate(_) => "predicate",
+            QuarantineReason::NonMember(_) => "non-member",
+        }
+    }
+}
+
+// # Enum Description:
+// This enum represents the frames received on the `Basic` lane of a thread, told apart by the kind of
+// their frame (see `Frame`) instead of by trying to decode them as every type in turn.
+//
+// # Variants:
+// * Message - A message sent by a thread.
+// * Collection - A collection delivered by one of the thread's handles.
+// * Probe - A wiring probe (see `validate_wiring`).
+// * Abort - The notification of a round aborted by an undecodable frame (see `DecodePolicy::Strict`).
+enum BasicFrame<T> {
+    Message(Message<T>),
+    Collection(Report<T>),
+    Probe(Probe),
+    Abort(DecodeFailure),
+}
+
+impl<T> BasicFrame<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Function Description:
+    // This function decodes a frame received on the `Basic` lane. Frames of an unknown kind are decoded
+    // as messages, so that the error names the type a frame was expected to be.
+    //
+    // # Parameters:
+    // * codec - The wire format of the frame.
+    // * received_frame - The frame, without attribution.
+    //
+    // # Returns:
+    // * The decoded frame, or a description of the decoding error.
+    fn read(codec: Codec, received_frame: &Bytes) -> Result<Self, String> {
+        let frame = Frame::parse(received_frame)?;
+        if frame.is::<Report<T>>() {
+            frame.read_payload(codec).map(BasicFrame::Collection)
+        } else if frame.is::<Probe>() {
+            frame.read_payload(codec).map(BasicFrame::Probe)
+        } else if frame.is::<DecodeFailure>() {
+            frame.read_payload(codec).map(BasicFrame::Abort)
+        } else {
+            frame.read_payload(codec).map(BasicFrame::Message)
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is a frame quarantined by the local queues of a thread, kept with the reason it was
+// rejected so that operators can see rejected traffic instead of losing it silently.
+//
+// # Fields:
+// * id - The ID of the thread that quarantined the frame.
+// * peer - The peer the frame was received from, as authenticated by the handshake of its connection, if a
+//   network transport attributed it (see `attribute_frame`).
+// * source - The ID of the thread that sent the frame, according to the frame, if it could be read.
+// * round_number - The round of the frame, if it could be read.
+// * reason - Why the frame was quarantined.
+// * frame - The frame, as received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedFrame {
+    id: u32,
+    peer: Option<u32>,
+    source: Option<u32>,
+    round_number: Option<Round>,
+    reason: QuarantineReason,
+    frame: Bytes,
+}
+
+impl QuarantinedFrame {
+    pub fn new(id: u32, source: Option<u32>, round_number: Option<Round>, reason: QuarantineReason, frame: Bytes) -> Self {
+        Self {
+            id,
+            peer: None,
+            source,
+            round_number,
+            reason,
+            frame
+        }
+    }
+
+    pub fn with_peer(mut self, peer: u32) -> Self {
+        self.peer = Some(peer);
+        self
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_peer(&self) -> Option<u32> {
+        self.peer
+    }
+
+    pub fn get_source(&self) -> Option<u32> {
+        self.source
+    }
+
+    // # Method Description:
+    // This method attributes the frame to its sender: the peer it was received from, which the frame cannot
+    // forge, or else the thread the frame claims to be sent by.
+    //
+    // # Returns:
+    // * The ID of the sender, `None` if the frame was not attributed and could not be read.
+    pub fn get_sender(&self) -> Option<u32> {
+        self.peer.or(self.source)
+    }
+
+    pub fn get_round_number(&self) -> Option<Round> {
+        self.round_number
+    }
+
+    pub fn get_reason(&self) -> &QuarantineReason {
+        &self.reason
+    }
+
+    pub fn get_frame(&self) -> &Bytes {
+        &self.frame
+    }
+}
 
 // # Enum Description:
 // This enum represents the type of object that may be received from a communication queue.
@@ -400,7 +1745,8 @@ where
 // # Variants:
 // * Message - Wraps a single `Message` instance received from another thread.
 // * Collection - Wraps a `Report` instance, representing a collection of `Message`s.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub enum RecvObject<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned+ PartialEq + Eq + Hash,
@@ -426,13 +1772,13 @@ where
             RecvObject::Collection(report) => report.get_protocol_information(),
         }
     }
-    pub fn get_instance_number(&self) -> Option<u32>{
+    pub fn get_instance_number(&self) -> Option<Instance> {
         match self {
             RecvObject::Message(message) => message.get_instance_number(),
             RecvObject::Collection(report) => Some(report.get_instance_number()),
         }
     }
-    pub fn get_round_number(&self) -> u32{
+    pub fn get_round_number(&self) -> Round {
         match self {
             RecvObject::Message(message) => message.get_round_number(),
             RecvObject::Collection(report) => report.get_round_number(),
@@ -450,8 +1796,11 @@ where
 // * protocol_information - A `String` containing the type of the executed protocol.
 // * id - A `u32` representing the ID of the thread that sent the message.
 // * message - A `String` containing the actual message payload.
-// * instance_number - An optional `u32` identifying the instance of the protocol this message belongs to.
-// * round_number - A `u32` indicating the round in which this message was sent, used for reliable broadcast or ordering.
+// * instance_number - An optional `Instance` identifying the instance of the protocol this message belongs to.
+// * round_number - A `Round` indicating the round in which this message was sent, used for reliable broadcast or ordering.
+// * epoch - The membership epoch the message was broadcast in (see `Roster`), 0 in a cluster whose membership never changed.
+// * clock - The vector clock of the message, if it was broadcast with causal broadcast or its sender maintains a vector clock.
+// * sequence - The number of the message among the basic messages of its sender, 0 for the messages of other protocols.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 //pub struct Message<T = String> {
 pub struct Message<T> {
@@ -459,8 +1808,14 @@ pub struct Message<T> {
     id: u32, 
     message: T,
     dimension: Option<u32>,
-    instance_number: Option<u32>,
-    round_number: u32
+    instance_number: Option<Instance>,
+    round_number: Round,
+    #[serde(default)]
+    epoch: u32,
+    #[serde(default)]
+    clock: Option<VectorClock>,
+    #[serde(default)]
+    sequence: u64,
 }
 
 //explanation of DeserializeOwned: 
@@ -492,26 +1847,91 @@ where
         &self.message
     }
 
-    pub fn get_instance_number(&self) -> Option<u32>{
+    pub fn get_instance_number(&self) -> Option<Instance> {
         self.instance_number
     }
 
-    pub fn get_round_number(&self) -> u32{
+    pub fn get_round_number(&self) -> Round {
         self.round_number
     }
 
+    pub fn get_epoch(&self) -> u32 {
+        self.epoch
+    }
 
+    pub fn get_clock(&self) -> Option<&VectorClock> {
+        self.clock.as_ref()
+    }
 
-    pub fn new(protocol_information: String, id: u32, message: T, dimension: Option<u32>,instance_number: Option<u32>, round_number: u32) -> Self {
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    // # Method Description:
+    // This method computes the FNV-1a digest of the message from its JSON representation (see `JsonConversion::json_digest`),
+    // so every thread computes the same digest for the same message.
+    // # Returns:
+    // * A `u64` digest of the message.
+    pub fn get_digest(&self) -> u64 {
+        self.json_digest()
+    }
+
+    pub fn new(protocol_information: String, id: u32, message: T, dimension: Option<u32>,instance_number: Option<Instance>, round_number: Round) -> Self {
         Self {
             protocol_information, 
             id,
             message,
             dimension,
             instance_number,
-            round_number
+            round_number,
+            epoch: 0,
+            clock: None,
+            sequence: 0,
         }
     }
+
+    // # Method Description:
+    // This method stamps the message with the membership epoch it is broadcast in.
+    //
+    // # Parameters:
+    // * epoch - The current epoch of the sender's roster.
+    //
+    // # Returns:
+    // * The stamped message.
+    pub fn with_epoch(mut self, epoch: u32) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    // # Method Description:
+    // This method stamps the message with the vector clock of its causal broadcast, or of its sender.
+    //
+    // # Parameters:
+    // * clock - The vector clock (see `OrderedDelivery::next_causal_clock` and `BasicQueues::stamp`).
+    //
+    // # Returns:
+    // * The stamped message.
+    pub fn with_clock(mut self, clock: VectorClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    // # Method Description:
+    // This method numbers the message among the basic messages of its sender.
+    //
+    // # Parameters:
+    // * sequence - The number of the message (see `BasicQueues::number`).
+    //
+    // # Returns:
+    // * The numbered message.
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
 }
 
-impl<T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash> JsonConversion<Message<T>> for Message<T> {}
\ No newline at end of file
+impl<T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash> JsonConversion<Message<T>> for Message<T> {}
+
+impl<T> FrameKind for Message<T> {
+    const FRAME_KIND: u64 = frame_kinds::MESSAGE_FRAME;
+}
\ No newline at end of file
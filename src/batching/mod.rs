@@ -0,0 +1,185 @@
+use std::{collections::HashMap, sync::{Arc, Mutex}, time::Duration};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize};
+use tokio::sync::mpsc::{self, Sender, UnboundedReceiver, UnboundedSender};
+
+use crate::json::{Codec, FrameKind, JsonConversion, WireFormat, frame_kinds};
+use crate::metrics::Metrics;
+use crate::multiplex::{Lane, tag_frame};
+
+// # Struct Description:
+// This struct configures the batching of the signals a thread sends (see `ClusterConfig::with_signal_batching`):
+// instead of sending every Echo and Vote in its own frame, the signal channels of the thread hold the
+// signals destined to a peer for a short window, and send them to the peer in a single `SignalBatch` frame.
+//
+// # Fields:
+// * window - How long the first signal held for a peer waits for more signals before the batch is sent.
+// * max_signals - The number of signals that sends a batch without waiting for the end of its window.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignalBatching {
+    window: Duration,
+    max_signals: usize,
+}
+
+impl SignalBatching {
+    pub fn new(window: Duration, max_signals: usize) -> Self {
+        Self {
+            window,
+            max_signals: max_signals.max(1),
+        }
+    }
+
+    pub fn get_window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn get_max_signals(&self) -> usize {
+        self.max_signals
+    }
+}
+
+// # Struct Description:
+// This struct is the frame a thread sends on the `Signal` lane to carry several signal frames to a peer at
+// once. The reliable handle of the peer unpacks it, and processes every frame it carries as if it had been
+// received on its own.
+//
+// # Fields:
+// * frames - The signal frames, encoded in the codec of the cluster and untagged, in the order they were sent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SignalBatch {
+    frames: Vec<Bytes>,
+}
+
+impl SignalBatch {
+    pub(crate) fn new(frames: Vec<Bytes>) -> Self {
+        Self {
+            frames
+        }
+    }
+
+    pub(crate) fn into_frames(self) -> Vec<Bytes> {
+        self.frames
+    }
+}
+
+impl JsonConversion<SignalBatch> for SignalBatch {}
+
+impl FrameKind for SignalBatch {
+    const FRAME_KIND: u64 = frame_kinds::SIGNAL_BATCH_FRAME;
+}
+
+// # Struct Description:
+// This struct counts the frames sent by the signal batcher of a thread.
+//
+// # Fields:
+// * batches - The number of frames the batcher sent, batched or not.
+// * signals - The number of signals those frames carried.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct BatchCount {
+    batches: u64,
+    signals: u64,
+}
+
+impl BatchCount {
+    pub(crate) fn record(&mut self, signals: usize) {
+        self.batches += 1;
+        self.signals += signals as u64;
+    }
+
+    pub fn get_batches(&self) -> u64 {
+        self.batches
+    }
+
+    pub fn get_signals(&self) -> u64 {
+        self.signals
+    }
+}
+
+// # Struct Description:
+// This struct batches the signal frames a thread sends, per peer. The first frame queued for a peer starts a
+// task owning a clone of the peer's transmitter, which coalesces the frames queued for the peer within the
+// window of the batching, or up to its maximum number of signals, into a `SignalBatch`. A batch of a single
+// frame is sent as the frame itself, so that batching costs nothing to a thread sending little.
+//
+// # Fields:
+// * batching - The batching of the cluster.
+// * codec - The wire format of the batches.
+// * metrics - The metrics of the owning thread, in which the batches sent are counted, if any.
+// * queues - The queue of the frames held for every peer, by ID.
+#[derive(Debug)]
+pub(crate) struct SignalBatcher {
+    batching: SignalBatching,
+    codec: Codec,
+    metrics: Option<Metrics>,
+    queues: Mutex<HashMap<u32, UnboundedSender<Bytes>>>,
+}
+
+impl SignalBatcher {
+    pub(crate) fn new(batching: SignalBatching, codec: Codec, metrics: Option<Metrics>) -> Self {
+        Self {
+            batching,
+            codec,
+            metrics,
+            queues: Mutex::default(),
+        }
+    }
+
+    // # Method Description:
+    // This method queues a signal frame for a peer. The returned future owns a clone of the batcher and of
+    // the transmitter, like `send_frame`, so that it can be joined with the frames sent directly.
+    //
+    // # Parameters:
+    // * peer - The ID of the peer.
+    // * transmitter - The transmitter of the peer.
+    // * frame - The signal frame, encoded and untagged.
+    pub(crate) fn send(self: &Arc<Self>, peer: u32, transmitter: &Sender<Bytes>, frame: Bytes) -> impl Future<Output = ()> + use<> {
+        let batcher = self.clone();
+        let transmitter = transmitter.clone();
+        async move {
+            batcher.enqueue(peer, transmitter, frame);
+        }
+    }
+
+    fn enqueue(&self, peer: u32, transmitter: Sender<Bytes>, frame: Bytes) {
+        let mut queues = self.queues.lock().unwrap();
+        if let Some(queue) = queues.get(&peer) && !queue.is_closed() {
+            let _ = queue.send(frame);
+            return
+        }
+        let (queue, frames) = mpsc::unbounded_channel();
+        let _ = queue.send(frame);
+        queues.insert(peer, queue);
+        tokio::spawn(flush_batches(frames, transmitter, self.batching, self.codec, self.metrics.clone()));
+    }
+}
+
+// # Function Description:
+// This function sends the frames queued for a peer, in batches, until the batcher is dropped or the peer
+// hangs up.
+//
+// # Parameters:
+// * frames - The receiver of the frames queued for the peer.
+// * transmitter - The transmitter of the peer.
+// * batching - The batching of the cluster.
+// * codec - The wire format of the batches.
+// * metrics - The metrics in which the batches sent are counted, if any.
+async fn flush_batches(mut frames: UnboundedReceiver<Bytes>, transmitter: Sender<Bytes>, batching: SignalBatching, codec: Codec, metrics: Option<Metrics>) {
+    while let Some(first) = frames.recv().await {
+        let deadline = tokio::time::Instant::now() + batching.get_window();
+        let mut batch = vec![first];
+        while batch.len() < batching.get_max_signals()
+            && let Ok(Some(frame)) = tokio::time::timeout_at(deadline, frames.recv()).await {
+            batch.push(frame);
+        }
+        if let Some(metrics) = &metrics {
+            metrics.record_signal_batch(batch.len());
+        }
+        let frame = match batch.len() {
+            1 => batch.remove(0),
+            _ => SignalBatch::new(batch).write_frame(codec),
+        };
+        if transmitter.send(tag_frame(Lane::Signal, frame)).await.is_err() {
+            return
+        }
+    }
+}
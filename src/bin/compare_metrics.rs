@@ -0,0 +1,61 @@
+// # Program Description: 
+// This program compares the metric exports of two runs (e.g. the `metrics.jsonl` written before and after a
+// protocol change), and reports the deltas of the latency percentiles, frame counts, and bytes of every
+// protocol, flagging the increases beyond a threshold as regressions.
+// # Usage:
+// * compare_metrics <baseline.jsonl> <candidate.jsonl> [threshold percent, default 10]
+
+use std::{env, fs, process}; 
+use rust_project::metrics::{MetricsComparison, MetricsReport, read_metrics_export};
+
+// # Function Description:
+// This function reads the metric export of a run, exiting with status 2 if it cannot be read or parsed.
+// # Parameters:
+// * path - the path of the export.
+// # Returns
+// * the `MetricsReport`s of the run.
+fn read_export(path: &str) -> Vec<MetricsReport> {
+    let data = fs::read_to_string(path).expect("Error: metrics export could not be read");
+    match read_metrics_export(&data) {
+        Ok(reports) => reports,
+        Err(error) => {
+            eprintln!("Error: metrics export {path} could not be parsed: {error}");
+            process::exit(2);
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let (baseline_path, candidate_path) = match (args.get(1), args.get(2)) {
+        (Some(baseline_path), Some(candidate_path)) => (baseline_path, candidate_path),
+        _ => {
+            eprintln!("usage: compare_metrics <baseline.jsonl> <candidate.jsonl> [threshold percent]");
+            process::exit(2);
+        }
+    };
+    let threshold: f64 = match args.get(3) {
+        Some(threshold) => threshold.parse().expect("Error: threshold must be a number"),
+        None => 10.0,
+    };
+
+    let comparison = MetricsComparison::new(&read_export(baseline_path), &read_export(candidate_path), threshold);
+    println!("{:<24} {:<36} {:>12} {:>12} {:>9}", "protocol", "metric", "baseline", "candidate", "change");
+    for delta in comparison.get_deltas() {
+        let change = match delta.get_change() {
+            Some(change) => format!("{change:+.1}%"),
+            None if delta.get_candidate() > 0 => String::from("new"),
+            None => String::from("-"),
+        };
+        let flag = if delta.get_regression() { "  REGRESSION" } else { "" };
+        println!("{:<24} {:<36} {:>12} {:>12} {:>9}{flag}", delta.get_protocol_information(), delta.get_metric(), delta.get_baseline(), delta.get_candidate(), change);
+    }
+
+    let regressions = comparison.get_regressions();
+    if regressions.is_empty() {
+        println!("no regression beyond {threshold}%");
+    } else {
+        println!("{} regressions beyond {threshold}%", regressions.len());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,81 @@
+// # Program Description:
+// This program creates the signed membership certificate of a cluster whose nodes run in separate processes
+// (see `cargo run -- <n> tcp_node <seed> <id> <membership file> --identity=<identity file>`), without any
+// process holding the secret key of another node:
+// 1. every node creates its identity (`init`), which prints its public key;
+// 2. the operator writes the membership document from the addresses and public keys of the nodes (`document`);
+// 3. every node signs the document with its own identity (`sign`), in any order, on the same certificate file.
+// The certificate is complete, and loadable by the nodes, once every member signed it.
+// # Usage:
+// * create_membership init <id> <identity.json>
+// * create_membership document <comma-separated address of every node, ordered by ID> <comma-separated hex public key of every node, ordered by ID> <membership.json>
+// * create_membership sign <identity.json> <membership.json>
+
+use std::{env, net::SocketAddr, path::Path, process};
+use rust_project::identity::IdentityStore;
+use rust_project::membership::{Member, MembershipCertificate, MembershipDocument};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let arguments: Vec<&str> = args.iter().skip(1).map(String::as_str).collect();
+    let result = match arguments.as_slice() {
+        ["init", id, identity] => id.parse().map_err(|error| format!("invalid id: {error}")).and_then(|id| init(id, Path::new(identity))),
+        ["document", addresses, public_keys, path] => document(addresses, public_keys, Path::new(path)),
+        ["sign", identity, path] => sign(Path::new(identity), Path::new(path)),
+        _ => {
+            eprintln!("usage: create_membership init <id> <identity.json>");
+            eprintln!("       create_membership document <addresses> <public keys> <membership.json>");
+            eprintln!("       create_membership sign <identity.json> <membership.json>");
+            process::exit(2);
+        }
+    };
+    if let Err(error) = result {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    }
+}
+
+// # Function Description:
+// This function loads the identity of a node, creating it the first time, and prints its public key.
+fn init(id: u32, identity: &Path) -> Result<(), String> {
+    let identity = IdentityStore::load_or_create(identity, id)?;
+    println!("{}", encode_hex(identity.get_key_pair().get_public_key()));
+    Ok(())
+}
+
+// # Function Description:
+// This function writes the unsigned certificate of the members given by their addresses and public keys.
+fn document(addresses: &str, public_keys: &str, path: &Path) -> Result<(), String> {
+    let addresses: Vec<SocketAddr> = addresses.split(',').map(str::parse).collect::<Result<_, _>>()
+        .map_err(|error| format!("invalid address list: {error}"))?;
+    let public_keys: Vec<Vec<u8>> = public_keys.split(',').map(decode_hex).collect::<Option<_>>()
+        .ok_or_else(|| String::from("invalid public key list"))?;
+    if public_keys.len() != addresses.len() {
+        return Err(format!("{} public keys given for {} addresses", public_keys.len(), addresses.len()))
+    }
+    let members = addresses.into_iter().zip(public_keys).enumerate()
+        .map(|(id, (address, public_key))| Member::new(id as u32, public_key, address))
+        .collect();
+    let certificate = MembershipCertificate::new(MembershipDocument::new(members));
+    certificate.save(path)?;
+    println!("membership of {} nodes written to {}, to be signed by every node", certificate.get_member_count(), path.display());
+    Ok(())
+}
+
+// # Function Description:
+// This function adds the signature of a node, with its own identity, to a certificate being signed.
+fn sign(identity: &Path, path: &Path) -> Result<(), String> {
+    let identity = IdentityStore::load(identity)?;
+    let certificate = MembershipCertificate::load_unverified(path)?.signed_by(&identity)?;
+    certificate.save(path)?;
+    println!("node {} signed {} ({}/{} signatures)", identity.get_id(), path.display(), certificate.get_signature_count(), certificate.get_member_count());
+    Ok(())
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len()).step_by(2).map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok()).collect()
+}
@@ -0,0 +1,81 @@
+// # Program Description:
+// This program queries a recorded delivery trace (e.g. the `trace.jsonl` written by `cargo run -- 4 trace`)
+// by node, protocol, sender, instance, round, and delivery time, e.g. every delivery of an instance, or what
+// a node had delivered by a given time of the run. Every matching record is printed as a line of JSON,
+// followed by the number of matches. The trace is read line by line.
+// # Usage:
+// * query_trace <trace.jsonl> [--node=<ID>] [--protocol=<name>] [--sender=<ID>] [--instance=<number>]
+//   [--round=<number>] [--from=<seconds>] [--until=<seconds>]
+
+use std::{env, fs::File, io::BufReader, process, time::Duration};
+use rust_project::json::JsonConversion;
+use rust_project::round::{Instance, Round};
+use rust_project::trace::{TraceQuery, query_trace};
+
+const USAGE: &str = "usage: query_trace <trace.jsonl> [--node=<ID>] [--protocol=<name>] [--sender=<ID>] [--instance=<number>] [--round=<number>] [--from=<seconds>] [--until=<seconds>]";
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let Some(path) = args.get(1) else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+    let query = match parse_query(&args[2..]) {
+        Ok(query) => query,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!("Error: trace {path} could not be read: {error}");
+            process::exit(2);
+        }
+    };
+    let records = match query_trace(BufReader::new(file), &query) {
+        Ok(records) => records,
+        Err(error) => {
+            eprintln!("Error: trace {path} could not be parsed: {error}");
+            process::exit(2);
+        }
+    };
+    for record in &records {
+        println!("{}", record.write_json());
+    }
+    println!("{} deliveries matched", records.len());
+}
+
+// # Function Description:
+// This function builds a query from the command-line filters.
+//
+// # Parameters:
+// * filters - The filters, written `--<name>=<value>`.
+//
+// # Returns:
+// * The query, or a description of the first filter that could not be parsed.
+fn parse_query(filters: &[String]) -> Result<TraceQuery, String> {
+    let mut query = TraceQuery::new();
+    for filter in filters {
+        let Some((name, value)) = filter.strip_prefix("--").and_then(|filter| filter.split_once('=')) else {
+            return Err(format!("unknown argument {filter}"))
+        };
+        let number = || value.parse::<u32>().map_err(|error| format!("{name} must be a number: {error}"));
+        let seconds = || value.parse::<f64>().ok().and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .ok_or(format!("{name} must be a non-negative number of seconds"));
+        query = match name {
+            "node" => query.with_node(number()?),
+            "protocol" => query.with_protocol(value.to_string()),
+            "sender" => query.with_sender(number()?),
+            "instance" => query.with_instance(Instance(number()?)),
+            "round" => query.with_round(Round(number()?)),
+            "from" => query.with_from(seconds()?),
+            "until" => query.with_until(seconds()?),
+            _ => return Err(format!("unknown filter --{name}")),
+        };
+    }
+    Ok(query)
+}
@@ -0,0 +1,37 @@
+// # Program Description:
+// This program replays a recorded log (e.g. the `replay.json` written by `cargo run -- 4 replay`) with the
+// code it was built from, in lock step with the recorded steps, and reports the first step at which the
+// replayed thread emitted other actions than the recorded ones. Running it after a refactor checks that
+// the reliable handle still behaves as it did when the log was recorded.
+// # Usage:
+// * replay_check <replay.json>
+
+use std::{env, path::Path, process};
+use rust_project::replay::ReplayRecording;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = env::args().collect();
+    let Some(path) = args.get(1) else {
+        eprintln!("usage: replay_check <replay.json>");
+        process::exit(2);
+    };
+    let recording = match ReplayRecording::load(Path::new(path)) {
+        Ok(recording) => recording,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            process::exit(2);
+        }
+    };
+    match recording.verify::<String>(recording.get_config().clone()).await {
+        Ok(None) => println!("{} steps of thread {} replayed, no divergence", recording.get_steps().len(), recording.get_id()),
+        Ok(Some(divergence)) => {
+            print!("{divergence}");
+            process::exit(1);
+        },
+        Err(error) => {
+            eprintln!("Error: {error}");
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// # Program Description: 
+// This program verifies an aggregated witness `AggregationProof` serialized to JSON, 
+// without access to the cluster that produced it.
+// # Usage:
+// * verify_aggregation <proof.json>
+
+use std::{env, fs, process}; 
+use rust_project::aggregated_witness::AggregationProof;
+use rust_project::json::JsonConversion;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let path = match args.get(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: verify_aggregation <proof.json>");
+            process::exit(2);
+        }
+    };
+
+    let data = fs::read_to_string(path).expect("Error: proof file could not be read");
+    let proof = match AggregationProof::<String>::read_json(&data) {
+        Ok(proof) => proof,
+        Err(error) => {
+            eprintln!("Error: proof could not be parsed: {error}");
+            process::exit(2);
+        }
+    };
+
+    match proof.verify() {
+        Ok(()) => {
+            println!("valid: round {} delivered by id: {} ({} values, {} aggregated witnesses)", 
+                proof.get_round_number(), proof.get_id(), proof.get_values().len(), proof.get_aggregated_witnesses().len());
+        },
+        Err(error) => {
+            println!("invalid: {error}");
+            process::exit(1);
+        }
+    }
+}
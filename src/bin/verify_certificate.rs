@@ -0,0 +1,45 @@
+// # Program Description: 
+// This program verifies a reliable broadcast `DeliveryCertificate` serialized to JSON, against the
+// membership certificate of the cluster that produced it, without access to the cluster.
+// # Usage:
+// * verify_certificate <certificate.json> <membership.json>
+
+use std::{env, fs, path::Path, process}; 
+use rust_project::certificate::DeliveryCertificate;
+use rust_project::json::JsonConversion;
+use rust_project::membership::MembershipCertificate;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let (Some(path), Some(membership_path)) = (args.get(1), args.get(2)) else {
+        eprintln!("usage: verify_certificate <certificate.json> <membership.json>");
+        process::exit(2);
+    };
+
+    let membership = match MembershipCertificate::load(Path::new(membership_path)) {
+        Ok(membership) => membership,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            process::exit(2);
+        }
+    };
+    let data = fs::read_to_string(path).expect("Error: certificate file could not be read");
+    let certificate = match DeliveryCertificate::<String>::read_json(&data) {
+        Ok(certificate) => certificate,
+        Err(error) => {
+            eprintln!("Error: certificate could not be parsed: {error}");
+            process::exit(2);
+        }
+    };
+
+    match certificate.verify(membership.get_document()) {
+        Ok(()) => {
+            println!("valid: instance {} of round {} by id: {} delivered by id: {} ({} votes)", 
+                certificate.get_instance_number(), certificate.get_round_number(), certificate.get_message().get_id(), certificate.get_id(), certificate.get_votes().len());
+        },
+        Err(error) => {
+            println!("invalid: {error}");
+            process::exit(1);
+        }
+    }
+}
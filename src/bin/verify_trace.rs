@@ -0,0 +1,50 @@
+// # Program Description:
+// This program checks a recorded delivery trace (e.g. the `trace.jsonl` written by `cargo run -- 4 trace`)
+// for agreement and totality violations offline, without re-running the simulation. The trace is read
+// line by line, so large recorded runs are checked in memory proportional to their number of instances.
+// # Usage:
+// * verify_trace <trace.jsonl> <thread count> [comma-separated IDs of faulty nodes]
+
+use std::{env, fs::File, io::BufReader, process};
+use rust_project::trace::{TraceChecker, check_trace};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let (path, thread_count) = match (args.get(1), args.get(2).map(|thread_count| thread_count.parse::<u32>())) {
+        (Some(path), Some(Ok(thread_count))) => (path, thread_count),
+        _ => {
+            eprintln!("usage: verify_trace <trace.jsonl> <thread count> [faulty IDs]");
+            process::exit(2);
+        }
+    };
+    let mut checker = TraceChecker::new(thread_count);
+    if let Some(faulty) = args.get(3) {
+        for id in faulty.split(',') {
+            checker = checker.with_faulty(id.parse().expect("Error: faulty IDs must be numbers"));
+        }
+    }
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!("Error: trace {path} could not be read: {error}");
+            process::exit(2);
+        }
+    };
+    let (records, violations) = match check_trace(BufReader::new(file), checker) {
+        Ok(result) => result,
+        Err(error) => {
+            eprintln!("Error: trace {path} could not be parsed: {error}");
+            process::exit(2);
+        }
+    };
+    for violation in &violations {
+        println!("{violation:?}");
+    }
+    if violations.is_empty() {
+        println!("{records} deliveries checked, no violation");
+    } else {
+        println!("{records} deliveries checked, {} violations", violations.len());
+        process::exit(1);
+    }
+}
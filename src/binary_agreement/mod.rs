@@ -0,0 +1,409 @@
+use std::{collections::{BTreeMap, HashMap, HashSet}, fmt::Debug, time::Duration};
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+
+use crate::reliable::ReliableCommunication;
+use crate::round::{Instance, Round};
+use crate::logging::log;
+
+// How long a thread waits for the next vote of a step before it looks again for the votes of the previous
+// steps that justify the votes it holds.
+const JUSTIFICATION_POLL: Duration = Duration::from_millis(10);
+
+// The number of steps of every round of an instance.
+const STEPS: u32 = 3;
+
+// The reliable round of an instance in which threads announce their decision.
+const DECISION_ROUND: Round = Round(u32::MAX);
+
+// # Enum Description:
+// This enum represents the values binary agreement threads reliably broadcast to each other. Each round `r`
+// of an agreement instance has three steps, broadcast as three consecutive reliable rounds of the instance:
+// estimates in round `3r`, majorities in round `3r + 1`, and proposals in round `3r + 2`. Decisions are
+// broadcast in the last reliable round of the instance.
+//
+// # Variants:
+// * Estimate - The estimate of the thread at the start of the round.
+// * Majority - The majority of the estimates the thread received in the round.
+// * Proposal - The value reported by more than half of the cluster in the second step, if any.
+// * Decided - The value the thread decided.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum BinaryVote {
+    Estimate(bool),
+    Majority(bool),
+    Proposal(Option<bool>),
+    Decided(bool),
+}
+
+// # Struct Description:
+// This struct runs randomized binary agreement (Bracha's algorithm) over the reliable broadcast of a
+// communicator, so that the threads of a cluster decide the same bit for every instance, while at most f of
+// them are Byzantine (n > 3f). In every round, a thread broadcasts its estimate, then the majority of the n - f
+// estimates it received, then proposes the value reported by more than half of the cluster in the second step
+// (or nothing). It decides a value proposed by 2f + 1 of the n - f proposals it received, adopts a value
+// proposed by f + 1 of them, and otherwise adopts the common coin of the round.
+//
+// Reliable broadcast keeps a thread from sending conflicting votes in a step. Votes are also validated: a
+// thread only counts a vote once the votes of the previous step it delivered justify it, i.e. once a correct
+// thread could have sent it after receiving n - f of them. A Byzantine thread can therefore neither propose a
+// value nobody reported nor push the correct threads off a unanimous input. A thread that decides broadcasts
+// its decision, and keeps taking part in the rounds until it delivered 2f + 1 decisions for the value; a
+// thread delivering f + 1 decisions for a value decides it too, since one of them comes from a correct
+// thread. Every correct thread thus terminates, although threads may decide in different rounds.
+//
+// The coin is derived from the experiment seed (see `ClusterConfig::create_rng`), so it is common to every
+// thread and reproducible across runs, but predictable: it is meant for simulations, not for adversaries
+// that know the seed. Instances use the instance numbers of the communicator's reliable broadcast, so the
+// communicator should be dedicated to binary agreement.
+//
+// # Fields:
+// * communicator - The communicator whose reliable handle carries the votes.
+// * estimates - The proposed value of every instance not decided yet, by instance number.
+// * decisions - The decided value of every instance, by instance number.
+pub struct BinaryAgreement<C> {
+    communicator: C,
+    estimates: HashMap<Instance, bool>,
+    decisions: BTreeMap<Instance, bool>,
+}
+
+impl<C> BinaryAgreement<C>
+where
+    C: ReliableCommunication<BinaryVote> + Send,
+{
+    pub fn new(communicator: C) -> Self {
+        Self {
+            communicator,
+            estimates: HashMap::new(),
+            decisions: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_communicator(&mut self) -> &mut C {
+        &mut self.communicator
+    }
+
+    pub fn get_decision(&self, instance_number: Instance) -> Option<bool> {
+        self.decisions.get(&instance_number).copied()
+    }
+
+    pub fn get_decisions(&self) -> &BTreeMap<Instance, bool> {
+        &self.decisions
+    }
+
+    // # Method Description:
+    // This method proposes the value of the thread for an instance, and broadcasts it as the estimate of
+    // the first round. A value proposed again for the same instance is ignored.
+    //
+    // # Parameters:
+    // * value - The proposed bit.
+    // * instance_number - The agreement instance.
+    pub async fn propose(&mut self, value: bool, instance_number: Instance) {
+        if self.estimates.contains_key(&instance_number) || self.decisions.contains_key(&instance_number) {
+            return
+        }
+        self.estimates.insert(instance_number, value);
+        self.communicator.reliable_broadcast(BinaryVote::Estimate(value), instance_number, Round(0)).await;
+    }
+
+    // # Method Description:
+    // This method runs the rounds of an instance until the thread decides. The decision is returned again
+    // by later calls without running the instance again.
+    //
+    // # Parameters:
+    // * instance_number - The agreement instance, which the thread must have proposed a value for.
+    //
+    // # Returns:
+    // * The decided bit.
+    // # Panics:
+    // * If the thread did not propose a value for the instance.
+    pub async fn decide(&mut self, instance_number: Instance) -> bool {
+        match self.try_decide(instance_number).await {
+            Ok(decision) => decision,
+            Err(error) => panic!("Error: {error}"),
+        }
+    }
+
+    // # Method Description:
+    // This method runs the rounds of an instance like `decide`, but returns an error instead of panicking
+    // if the thread did not propose a value for the instance.
+    //
+    // # Parameters:
+    // * instance_number - The agreement instance.
+    //
+    // # Returns:
+    // * The decided bit, or a description of why the instance cannot be decided.
+    pub async fn try_decide(&mut self, instance_number: Instance) -> Result<bool, String> {
+        if let Some(decision) = self.get_decision(instance_number) {
+            return Ok(decision)
+        }
+        let Some(mut estimate) = self.estimates.remove(&instance_number) else {
+            return Err(format!("binary agreement instance {instance_number} was not proposed"))
+        };
+        let thread_id = *self.communicator.get_id();
+        let thread_count = self.communicator.get_config().get_thread_count();
+        let faulty_threads = self.communicator.get_config().get_thresholds().get_faulty_threads() as u32;
+        let mut votes = BinaryVotes::new(thread_count, faulty_threads);
+
+        let mut round: u32 = 0;
+        loop {
+            let estimate_round = Round(STEPS * round);
+            if round > 0 {
+                self.communicator.reliable_broadcast(BinaryVote::Estimate(estimate), instance_number, estimate_round).await;
+            }
+            let Some(estimates) = self.collect_votes(&mut votes, instance_number, estimate_round).await else {
+                break
+            };
+            let majority = count(&estimates, BinaryVote::Estimate(true)) > count(&estimates, BinaryVote::Estimate(false));
+
+            let majority_round = estimate_round.next();
+            self.communicator.reliable_broadcast(BinaryVote::Majority(majority), instance_number, majority_round).await;
+            let Some(majorities) = self.collect_votes(&mut votes, instance_number, majority_round).await else {
+                break
+            };
+            let proposal = [false, true].into_iter().find(|value| 2 * count(&majorities, BinaryVote::Majority(*value)) > thread_count);
+
+            let proposal_round = majority_round.next();
+            self.communicator.reliable_broadcast(BinaryVote::Proposal(proposal), instance_number, proposal_round).await;
+            let Some(proposals) = self.collect_votes(&mut votes, instance_number, proposal_round).await else {
+                break
+            };
+
+            if let Some(value) = [false, true].into_iter().find(|value| count(&proposals, BinaryVote::Proposal(Some(*value))) > 2 * faulty_threads) {
+                log!(info, { node = thread_id, instance = %instance_number, round }, "id: {thread_id}, binary agreement instance {instance_number} decided {value} in round {round}");
+                self.announce(&mut votes, instance_number, value).await;
+            }
+            estimate = match [false, true].into_iter().find(|value| count(&proposals, BinaryVote::Proposal(Some(*value))) > faulty_threads) {
+                Some(value) => value,
+                None => self.coin(instance_number, round),
+            };
+            votes.set_coin(round + 1, self.coin(instance_number, round));
+            round += 1;
+        }
+        let Some(decision) = votes.get_announced() else {
+            return Err(format!("binary agreement instance {instance_number} terminated without a decision"))
+        };
+        self.decisions.insert(instance_number, decision);
+        Ok(decision)
+    }
+
+    // # Method Description:
+    // This method broadcasts the decision of the thread, once.
+    async fn announce(&mut self, votes: &mut BinaryVotes, instance_number: Instance, value: bool) {
+        if votes.get_announced().is_none() {
+            votes.set_announced(value);
+            self.communicator.reliable_broadcast(BinaryVote::Decided(value), instance_number, DECISION_ROUND).await;
+        }
+    }
+
+    // # Method Description:
+    // This method receives the votes of a step until n - f of them are justified, receiving the late votes
+    // of the previous steps of the instance meanwhile, as they may be needed to justify the votes of the step,
+    // and the decisions of the other threads.
+    //
+    // # Parameters:
+    // * votes - The votes of the instance received so far.
+    // * instance_number - The agreement instance.
+    // * round_number - The reliable round of the step.
+    //
+    // # Returns:
+    // * The first n - f justified votes of the step, or `None` once the thread delivered 2f + 1 decisions for
+    //   a value, and can terminate.
+    async fn collect_votes(&mut self, votes: &mut BinaryVotes, instance_number: Instance, round_number: Round) -> Option<Vec<BinaryVote>> {
+        loop {
+            while let Ok(message) = self.communicator.reliable_recv_with_timeout(None, instance_number, DECISION_ROUND, Duration::ZERO).await {
+                if let BinaryVote::Decided(value) = *message.get_message() {
+                    votes.insert_decision(message.get_id(), value);
+                }
+            }
+            if let Some(value) = [false, true].into_iter().find(|value| votes.count_decisions(*value) > votes.faulty_threads) {
+                self.announce(votes, instance_number, value).await;
+                if votes.count_decisions(value) > 2 * votes.faulty_threads {
+                    return None
+                }
+            }
+            votes.justify();
+            if let Some(justified) = votes.get_quorum(round_number) {
+                return Some(justified)
+            }
+            if let Ok(message) = self.communicator.reliable_recv_with_timeout(None, instance_number, round_number, JUSTIFICATION_POLL).await {
+                votes.insert(round_number, message.get_id(), *message.get_message());
+            }
+            for earlier_round in (round_number.0.saturating_sub(STEPS)..round_number.0).map(Round) {
+                while let Ok(message) = self.communicator.reliable_recv_with_timeout(None, instance_number, earlier_round, Duration::ZERO).await {
+                    votes.insert(earlier_round, message.get_id(), *message.get_message());
+                }
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method flips the common coin of a round, identical on every thread of the cluster.
+    //
+    // # Returns:
+    // * The bit of the coin.
+    fn coin(&self, instance_number: Instance, round: u32) -> bool {
+        let component = format!("binary agreement coin {instance_number} {round}");
+        self.communicator.get_config().create_rng(0, &component).gen_bool(0.5)
+    }
+}
+
+// # Struct Description:
+// This struct holds the votes a thread received for an instance, by reliable round, and validates them: a
+// vote is justified once the justified votes of the previous step contain n - f votes from which a correct
+// thread would have sent it. Justification is monotonic, as votes are only ever added, so a vote that is not
+// justified yet is kept until it is, or forever if it never is (e.g. the vote of a Byzantine thread).
+//
+// # Fields:
+// * thread_count - The number of threads of the cluster.
+// * faulty_threads - The number of Byzantine threads tolerated.
+// * pending - The votes received and not justified yet, by round and sender.
+// * justified - The justified votes, by round, in the order they were justified.
+// * received - The senders of the votes received, by round, so that a sender counts once per round.
+// * coins - The coin of every round after the first, drawn when the thread leaves the previous round.
+// * decisions - The decision delivered from every thread, by sender.
+// * announced - The decision the thread broadcast, if it decided.
+struct BinaryVotes {
+    thread_count: u32,
+    faulty_threads: u32,
+    pending: BTreeMap<Round, BTreeMap<u32, BinaryVote>>,
+    justified: BTreeMap<Round, Vec<BinaryVote>>,
+    received: HashMap<Round, HashSet<u32>>,
+    coins: HashMap<u32, bool>,
+    decisions: HashMap<u32, bool>,
+    announced: Option<bool>,
+}
+
+impl BinaryVotes {
+    fn new(thread_count: u32, faulty_threads: u32) -> Self {
+        Self {
+            thread_count,
+            faulty_threads,
+            pending: BTreeMap::new(),
+            justified: BTreeMap::new(),
+            received: HashMap::new(),
+            coins: HashMap::new(),
+            decisions: HashMap::new(),
+            announced: None,
+        }
+    }
+
+    fn get_quorum_size(&self) -> usize {
+        (self.thread_count - self.faulty_threads) as usize
+    }
+
+    fn set_coin(&mut self, round: u32, coin: bool) {
+        self.coins.insert(round, coin);
+    }
+
+    fn get_announced(&self) -> Option<bool> {
+        self.announced
+    }
+
+    fn set_announced(&mut self, value: bool) {
+        self.announced = Some(value);
+    }
+
+    fn insert_decision(&mut self, sender: u32, value: bool) {
+        self.decisions.entry(sender).or_insert(value);
+    }
+
+    fn count_decisions(&self, value: bool) -> u32 {
+        self.decisions.values().filter(|decided| **decided == value).count() as u32
+    }
+
+    // # Method Description:
+    // This method records the vote of a sender in a round, once per sender and round.
+    fn insert(&mut self, round_number: Round, sender: u32, vote: BinaryVote) {
+        if self.received.entry(round_number).or_default().insert(sender) {
+            self.pending.entry(round_number).or_default().insert(sender, vote);
+        }
+    }
+
+    // # Method Description:
+    // This method provides the first n - f justified votes of a round, once there are that many.
+    fn get_quorum(&self, round_number: Round) -> Option<Vec<BinaryVote>> {
+        let justified = self.justified.get(&round_number)?;
+        (justified.len() >= self.get_quorum_size()).then(|| justified[..self.get_quorum_size()].to_vec())
+    }
+
+    // # Method Description:
+    // This method moves the pending votes that became justified to the justified votes, round after round,
+    // so that the votes justified in a round may justify votes of the next one.
+    fn justify(&mut self) {
+        let rounds: Vec<Round> = self.pending.keys().copied().collect();
+        for round_number in rounds {
+            let previous = match round_number.0 {
+                0 => vec![],
+                _ => self.justified.get(&Round(round_number.0 - 1)).cloned().unwrap_or_default(),
+            };
+            let Some(pending) = self.pending.get_mut(&round_number) else {
+                continue
+            };
+            let mut newly_justified = vec![];
+            pending.retain(|_, vote| {
+                let justified = is_justified(*vote, round_number, &previous, self.thread_count, self.faulty_threads, self.coins.get(&(round_number.0 / STEPS)).copied());
+                if justified {
+                    newly_justified.push(*vote);
+                }
+                !justified
+            });
+            self.justified.entry(round_number).or_default().extend(newly_justified);
+        }
+    }
+}
+
+// # Function Description:
+// This function counts the votes equal to a vote.
+fn count(votes: &[BinaryVote], vote: BinaryVote) -> u32 {
+    votes.iter().filter(|counted| **counted == vote).count() as u32
+}
+
+// # Function Description:
+// This function checks whether a vote could have been sent by a correct thread, given the justified votes of
+// the previous step: whether n - f of them lead a correct thread to the vote.
+//
+// # Parameters:
+// * vote - The vote.
+// * round_number - The reliable round of the vote.
+// * previous - The justified votes of the previous step, empty for the estimates of the first round.
+// * thread_count - The number of threads of the cluster.
+// * faulty_threads - The number of Byzantine threads tolerated.
+// * coin - The coin of the round of the vote, if it is an estimate of a round after the first.
+//
+// # Returns:
+// * `true` if the vote is justified.
+fn is_justified(vote: BinaryVote, round_number: Round, previous: &[BinaryVote], thread_count: u32, faulty_threads: u32, coin: Option<bool>) -> bool {
+    let quorum = thread_count - faulty_threads;
+    // the largest number of `value` votes in n - f of the previous votes, given that the others are drawn from `rest`
+    let fill = |value: u32, rest: u32| -> Option<u32> {
+        let taken = value.min(quorum);
+        (quorum - taken <= rest).then_some(taken)
+    };
+    match (round_number.0 % STEPS, vote) {
+        (0, BinaryVote::Estimate(_)) if round_number.0 == 0 => true,
+        (0, BinaryVote::Estimate(value)) => {
+            let supporting = count(previous, BinaryVote::Proposal(Some(value)));
+            let opposing = count(previous, BinaryVote::Proposal(Some(!value)));
+            let empty = count(previous, BinaryVote::Proposal(None));
+            let adopted = fill(supporting, opposing + empty).is_some_and(|taken| taken > faulty_threads);
+            let flipped = coin == Some(value) && supporting.min(faulty_threads) + opposing.min(faulty_threads) + empty >= quorum;
+            adopted || flipped
+        },
+        (1, BinaryVote::Majority(value)) => {
+            let supporting = count(previous, BinaryVote::Estimate(value));
+            let opposing = count(previous, BinaryVote::Estimate(!value));
+            // ties are broken in favor of `false`
+            fill(supporting, opposing).is_some_and(|taken| taken > quorum - taken || (!value && taken == quorum - taken))
+        },
+        (2, BinaryVote::Proposal(Some(value))) => {
+            let supporting = count(previous, BinaryVote::Majority(value));
+            let opposing = count(previous, BinaryVote::Majority(!value));
+            fill(supporting, opposing).is_some_and(|taken| 2 * taken > thread_count)
+        },
+        (2, BinaryVote::Proposal(None)) => {
+            let half = thread_count / 2;
+            count(previous, BinaryVote::Majority(false)).min(half) + count(previous, BinaryVote::Majority(true)).min(half) >= quorum
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,262 @@
+use std::{collections::{HashMap, HashSet}, fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc};
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::{task::JoinHandle, sync::mpsc::Receiver};
+
+use crate::basic::Message;
+use crate::faults::ByzantineBehavior;
+use crate::batching::SignalBatch;
+use crate::json::WireFormat;
+use crate::reliable::{ReliableCommunication, SignalChannels, Signal, SignalType, ObjectContent};
+use crate::round::{Instance, Round};
+use crate::logging::log;
+
+// # Struct Description:
+// This struct wraps the communicator of a thread to make it a Byzantine participant of reliable broadcast,
+// following a `ByzantineBehavior` instead of the protocol, so that the agreement properties of the protocols
+// can be tested against real adversarial behavior rather than crashes only. It replaces the reliable handle
+// of the communicator by a Byzantine handle (see `initialize_byzantine_handle`), which always runs in its own
+// task, whatever the `HandleMode`. Conflicting payloads are derived from the genuine ones by a function
+// given by the test, since the wrapper cannot forge a payload of any type by itself.
+//
+// # Fields:
+// * communicator - The wrapped communicator.
+// * behavior - The attack of the thread.
+// * conflicting - The function deriving a conflicting payload from a genuine one.
+pub struct ByzantineCommunicator<T, C> {
+    communicator: C,
+    behavior: ByzantineBehavior,
+    conflicting: Arc<dyn Fn(&T) -> T + Send + Sync>,
+}
+
+impl<T, C> ByzantineCommunicator<T, C>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T> + 'static,
+{
+    // # Function Description:
+    // This function wraps a communicator whose reliable handle was not initialized.
+    //
+    // # Parameters:
+    // * communicator - The communicator of the Byzantine thread.
+    // * behavior - The attack of the thread.
+    // * conflicting - The function deriving a conflicting payload from a genuine one, which must return a
+    //   different payload for the attacks to be visible.
+    //
+    // # Returns:
+    // * The Byzantine communicator.
+    pub fn new(communicator: C, behavior: ByzantineBehavior, conflicting: impl Fn(&T) -> T + Send + Sync + 'static) -> Self {
+        Self {
+            communicator,
+            behavior,
+            conflicting: Arc::new(conflicting),
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        *self.communicator.get_id()
+    }
+
+    pub fn get_behavior(&self) -> ByzantineBehavior {
+        self.behavior
+    }
+
+    pub fn get_communicator(&mut self) -> &mut C {
+        &mut self.communicator
+    }
+
+    // # Method Description:
+    // This method initiates a reliable broadcast by the Byzantine thread. An equivocating thread sends the
+    // message to one half of the cluster and a conflicting message to the other half; any other thread
+    // broadcasts the message as a correct thread would.
+    //
+    // # Parameters:
+    // * message - The genuine message.
+    // * instance_number - The consensus instance number associated with this broadcast.
+    // * round_number - The round number within the consensus instance.
+    pub async fn reliable_broadcast(&mut self, message: T, instance_number: Instance, round_number: Round) {
+        match self.behavior {
+            ByzantineBehavior::Equivocate => {
+                let sent_message = Message::new(String::from("reliable"), self.get_id(), message, None, Some(instance_number), round_number);
+                let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
+                let thread_count = self.communicator.get_config().get_thread_count();
+                send_conflicting(self.communicator.get_signal_channels(), &self.conflicting, thread_count, input).await;
+            },
+            ByzantineBehavior::DropEchoes | ByzantineBehavior::VoteWithoutEcho => {
+                self.communicator.reliable_broadcast(message, instance_number, round_number).await;
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method starts the Byzantine handle of the thread, in place of its reliable handle: the handle
+    // listens for incoming signals and answers them following the behavior of the thread. It never
+    // delivers any instance.
+    //
+    // # Returns:
+    // * A `JoinHandle` to the spawned task, that runs until explicitly terminated.
+    pub fn initialize_byzantine_handle(&mut self) -> JoinHandle<()> {
+        log!(debug, "id: {}, initializing byzantine handle ({:?})...", self.get_id(), self.behavior);
+
+        let byzantine_handle: ByzantineHandleLoop<T, C> = ByzantineHandleLoop::new(self);
+        tokio::spawn(byzantine_handle.run())
+    }
+
+    // # Method Description:
+    // This method terminates the Byzantine handle of the thread.
+    //
+    // # Parameters:
+    // * byzantine_handle - The `JoinHandle` returned by `initialize_byzantine_handle`.
+    pub fn terminate_byzantine_handle(&self, byzantine_handle: JoinHandle<()>) {
+        log!(debug, "id: {}, terminating byzantine handle...", self.get_id());
+        byzantine_handle.abort();
+    }
+}
+
+// # Struct Description:
+// This struct holds the state of a Byzantine handle and answers the signals it receives.
+//
+// # Fields:
+// * thread_id - The ID of the Byzantine thread.
+// * thread_count - The number of threads in the cluster.
+// * behavior - The attack of the thread.
+// * conflicting - The function deriving a conflicting payload from a genuine one.
+// * thread_signal_channel - The channels on which the handle sends its signals.
+// * receiver - The receiver of the thread's `Signal` lane.
+// * validity_threshold - The number of Echoes after which the handle votes.
+// * instances - The progress of the handle in every instance, by instance ID.
+struct ByzantineHandleLoop<T, C>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    thread_id: u32,
+    thread_count: u32,
+    behavior: ByzantineBehavior,
+    conflicting: Arc<dyn Fn(&T) -> T + Send + Sync>,
+    thread_signal_channel: SignalChannels<T>,
+    receiver: Receiver<Bytes>,
+    validity_threshold: u64,
+    instances: HashMap<String, ByzantineInstance>,
+    _marker: PhantomData<fn() -> C>,
+}
+
+// # Struct Description:
+// This struct records the progress of a Byzantine handle in one instance.
+//
+// # Fields:
+// * input - Whether the handle received the Input of the instance.
+// * echoes - The origins of the Echoes received for the instance.
+// * voted - Whether the handle sent its Vote.
+#[derive(Default)]
+struct ByzantineInstance {
+    input: bool,
+    echoes: HashSet<u32>,
+    voted: bool,
+}
+
+impl<T, C> ByzantineHandleLoop<T, C>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T> + 'static,
+{
+    fn new(byzantine_communicator: &mut ByzantineCommunicator<T, C>) -> Self {
+        let communicator = &mut byzantine_communicator.communicator;
+        Self {
+            thread_id: *communicator.get_id(),
+            thread_count: communicator.get_config().get_thread_count(),
+            behavior: byzantine_communicator.behavior,
+            conflicting: byzantine_communicator.conflicting.clone(),
+            thread_signal_channel: communicator.get_signal_channels().clone(),
+            receiver: communicator.take_reliable_handle_rx(),
+            validity_threshold: communicator.get_config().get_thresholds().get_validity_threshold(),
+            instances: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    // # Method Description:
+    // This method answers signals as they arrive, until the handle's task is aborted.
+    async fn run(mut self) {
+        while let Some(received_signal) = self.receiver.recv().await {
+            self.process_signal(received_signal).await;
+        }
+    }
+
+    // # Method Description:
+    // This method answers a single signal received on the thread's `Signal` lane, or every signal of a
+    // `SignalBatch`. Undecodable signals and Votes are ignored.
+    //
+    // # Parameters:
+    // * received_signal - The serialized signal.
+    async fn process_signal(&mut self, received_signal: Bytes) {
+        let Ok(signal) = Signal::<T>::read_frame(self.thread_signal_channel.get_codec(), &received_signal) else {
+            if let Ok(signal_batch) = SignalBatch::read_frame(self.thread_signal_channel.get_codec(), &received_signal) {
+                for received_signal in signal_batch.into_frames() {
+                    Box::pin(self.process_signal(received_signal)).await;
+                }
+            }
+            return
+        };
+        let instance = self.instances.entry(C::get_instance_id(self.thread_id, signal.clone())).or_default();
+
+        let answer = match signal.get_signal() {
+            SignalType::Input if !instance.input => {
+                instance.input = true;
+                match self.behavior {
+                    ByzantineBehavior::Equivocate => Some(SignalType::Echo),
+                    ByzantineBehavior::DropEchoes => None,
+                    ByzantineBehavior::VoteWithoutEcho => {
+                        instance.voted = true;
+                        Some(SignalType::Vote)
+                    },
+                }
+            },
+            SignalType::Echo => {
+                let Some(origin) = signal.get_origin() else {
+                    return
+                };
+                instance.echoes.insert(origin);
+                if instance.echoes.len() as u64 >= self.validity_threshold && !instance.voted {
+                    instance.voted = true;
+                    Some(SignalType::Vote)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        if let Some(signal_type) = answer {
+            let answer = Signal::new(signal_type, signal.get_content().clone(), signal.get_instance_number(), signal.get_round_number());
+            match self.behavior {
+                ByzantineBehavior::Equivocate => send_conflicting(&self.thread_signal_channel, &self.conflicting, self.thread_count, answer).await,
+                ByzantineBehavior::DropEchoes | ByzantineBehavior::VoteWithoutEcho => self.thread_signal_channel.broadcast_signal(answer).await,
+            }
+        }
+    }
+}
+
+// # Function Description:
+// This function sends a signal to the first half of the cluster, and the same signal carrying a conflicting
+// payload to the second half. Signals whose content is not a message are sent unchanged to every thread.
+//
+// # Parameters:
+// * signal_channels - The channels of the Byzantine thread.
+// * conflicting - The function deriving a conflicting payload from a genuine one.
+// * thread_count - The number of threads in the cluster.
+// * signal - The genuine signal.
+async fn send_conflicting<T>(signal_channels: &SignalChannels<T>, conflicting: &Arc<dyn Fn(&T) -> T + Send + Sync>, thread_count: u32, signal: Signal<T>)
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    let threads: Vec<u32> = (0..thread_count).collect();
+    let (first, second) = threads.split_at((thread_count / 2) as usize);
+    let conflicting_content = match signal.get_content() {
+        ObjectContent::Message(message) => ObjectContent::Message(Message::new(message.get_protocol_information().clone(), message.get_id(),
+            conflicting(message.get_message()), message.get_dimension(), message.get_instance_number(), message.get_round_number())),
+        content => content.clone(),
+    };
+    let conflicting_signal = Signal::new(*signal.get_signal(), conflicting_content, signal.get_instance_number(), signal.get_round_number());
+    signal_channels.send_signal_to(first, signal).await;
+    signal_channels.send_signal_to(second, conflicting_signal).await;
+}
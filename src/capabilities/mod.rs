@@ -0,0 +1,204 @@
+use std::{collections::BTreeSet, fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::config::ClusterConfig;
+use crate::json::{Codec, JsonConversion};
+use crate::reliable::ObjectContent;
+use crate::signing::Authentication;
+
+// # Enum Description:
+// This enum represents the protocols a communicator runs on top of reliable broadcast, each of which
+// delivers its own kinds of content.
+//
+// # Variants:
+// * Reliable - Reliable broadcast of messages, run by every communicator.
+// * Witness - Witness-verified broadcast, delivering reports and commitments (`WitnessCommunicator`).
+// * AggregatedWitness - Witness broadcast with aggregated reports (`AggregatedWitnessCommunicator`).
+// * Barycentric - Barycentric agreement, delivering barycentric reports (`BarycentricCommunicator`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Protocol {
+    Reliable,
+    Witness,
+    AggregatedWitness,
+    Barycentric,
+}
+
+impl Protocol {
+    // Every protocol, the default set a network transport announces.
+    pub const ALL: [Protocol; 4] = [Protocol::Reliable, Protocol::Witness, Protocol::AggregatedWitness, Protocol::Barycentric];
+}
+
+// # Struct Description:
+// This struct is the capability set a node announces to its peers: the protocols it runs, the codec its
+// frames are encoded with, and how its signals are authenticated. Network transports exchange it when a
+// connection opens (see `TcpTransport::with_protocols`), and every reliable handle checks the content it
+// delivers against the capabilities of its communicator (see `ReliableCommunication::get_capabilities`),
+// so that a peer running other protocols is refused or degraded to the common ones instead of crashing
+// the handles with content they cannot deliver.
+//
+// # Fields:
+// * protocols - The protocols the node runs.
+// * codec - The wire format of the node's frames.
+// * authentication - How the node's signals are authenticated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Capabilities {
+    protocols: BTreeSet<Protocol>,
+    codec: Codec,
+    authentication: Authentication,
+}
+
+impl Capabilities {
+    pub fn new(protocols: BTreeSet<Protocol>, codec: Codec, authentication: Authentication) -> Self {
+        Self {
+            protocols,
+            codec,
+            authentication
+        }
+    }
+
+    // # Function Description:
+    // This function creates the capabilities of a node running some protocols in a cluster.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster, giving the codec and the authentication.
+    // * protocols - The protocols the node runs.
+    //
+    // # Returns:
+    // * The capabilities.
+    pub fn from_config(config: &ClusterConfig, protocols: &[Protocol]) -> Self {
+        Self::new(protocols.iter().copied().collect(), config.get_codec(), config.get_authentication())
+    }
+
+    pub fn get_protocols(&self) -> &BTreeSet<Protocol> {
+        &self.protocols
+    }
+
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
+
+    pub fn get_authentication(&self) -> Authentication {
+        self.authentication
+    }
+
+    // # Method Description:
+    // This method negotiates the capabilities of the local node with those a peer announced. Nodes whose
+    // frames are encoded or authenticated differently cannot understand each other and are refused, as are
+    // nodes without any protocol in common. Nodes running different protocols are degraded to the protocols
+    // they have in common: the content of the other protocols is discarded by the handles that receive it.
+    // The negotiation is symmetric, so both ends of a connection reach the same outcome.
+    //
+    // # Parameters:
+    // * remote - The capabilities announced by the peer.
+    //
+    // # Returns:
+    // * The `Negotiation` outcome.
+    pub fn negotiate(&self, remote: &Capabilities) -> Negotiation {
+        if self.codec != remote.codec {
+            return Negotiation::Refused(format!("frames are encoded with {:?} on one end and {:?} on the other", self.codec, remote.codec))
+        }
+        if self.authentication != remote.authentication {
+            return Negotiation::Refused(format!("signals are authenticated with {:?} on one end and {:?} on the other", self.authentication, remote.authentication))
+        }
+        let protocols: BTreeSet<Protocol> = self.protocols.intersection(&remote.protocols).copied().collect();
+        if protocols.is_empty() {
+            return Negotiation::Refused(format!("no protocol in common between {:?} and {:?}", self.protocols, remote.protocols))
+        }
+        let dropped: BTreeSet<Protocol> = self.protocols.symmetric_difference(&remote.protocols).copied().collect();
+        let agreed = Self::new(protocols, self.codec, self.authentication);
+        match dropped.is_empty() {
+            true => Negotiation::Agreed(agreed),
+            false => Negotiation::Degraded(agreed, format!("protocols {dropped:?} are not run on both ends")),
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether the node runs a protocol delivering some content. Messages are delivered
+    // by every protocol.
+    //
+    // # Parameters:
+    // * content - The content to deliver.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of the content the node cannot deliver.
+    pub fn supports<T>(&self, content: &ObjectContent<T>) -> Result<(), String>
+    where
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let (kind, protocols): (&str, &[Protocol]) = match content {
+            ObjectContent::Message(_) => return Ok(()),
+            ObjectContent::Report(_) => ("report", &[Protocol::Witness, Protocol::AggregatedWitness]),
+            ObjectContent::AggregatedReport(_) => ("aggregated report", &[Protocol::AggregatedWitness]),
+            ObjectContent::BarycentricReport(_) => ("barycentric report", &[Protocol::Barycentric]),
+            ObjectContent::Commitment(_) => ("commitment", &[Protocol::Witness]),
+            ObjectContent::Fragment(_) => ("fragment", &[Protocol::Reliable]),
+            ObjectContent::Extension { .. } => ("extension", &[Protocol::Reliable]),
+        };
+        match protocols.iter().any(|protocol| self.protocols.contains(protocol)) {
+            true => Ok(()),
+            false => Err(format!("{kind} of \"{}\" by id: {}, which none of the protocols {:?} delivers", content.get_protocol_information(), content.get_id(), self.protocols)),
+        }
+    }
+}
+
+impl JsonConversion<Capabilities> for Capabilities {}
+
+// # Enum Description:
+// This enum represents the outcome of the negotiation of the capabilities of two nodes.
+//
+// # Variants:
+// * Agreed - Both nodes run the same protocols. Carries the agreed capabilities.
+// * Degraded - The nodes only run some protocols in common. Carries the agreed capabilities, restricted to
+//   the common protocols, and a description of the protocols left out.
+// * Refused - The nodes cannot communicate. Carries a description of why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Negotiation {
+    Agreed(Capabilities),
+    Degraded(Capabilities, String),
+    Refused(String),
+}
+
+// # Struct Description:
+// This struct describes a mismatch between the capabilities of a node and a peer, published by network
+// transports as `ConnectionEvent::CapabilityMismatch` when a connection opens, and by reliable handles as
+// `Event::CapabilityMismatch` when they discard content their communicator cannot deliver.
+//
+// # Fields:
+// * id - The ID of the node that observed the mismatch.
+// * peer - The ID of the peer.
+// * reason - A description of the mismatch.
+// * refused - Whether the peer was refused, rather than degraded to the capabilities in common.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CapabilityMismatch {
+    id: u32,
+    peer: u32,
+    reason: String,
+    refused: bool,
+}
+
+impl CapabilityMismatch {
+    pub fn new(id: u32, peer: u32, reason: String, refused: bool) -> Self {
+        Self {
+            id,
+            peer,
+            reason,
+            refused
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_peer(&self) -> u32 {
+        self.peer
+    }
+
+    pub fn get_reason(&self) -> &String {
+        &self.reason
+    }
+
+    pub fn is_refused(&self) -> bool {
+        self.refused
+    }
+}
@@ -0,0 +1,158 @@
+use std::{collections::BTreeSet, fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::basic::Message;
+use crate::json::JsonConversion;
+use crate::membership::MembershipDocument;
+use crate::reliable::{ObjectContent, Signal, SignalType};
+use crate::round::{Instance, Round};
+
+// # Struct Description:
+// This struct is a quorum certificate for a reliably delivered message: the signatures of the Votes that
+// made the deliverer deliver it, under `Authentication::Ed25519`. Every Vote for an instance carries the
+// same message, so the certificate keeps the message once and a signature per voter, and rebuilds the
+// Votes to verify them. A third party holding the membership document of the cluster can check, offline,
+// that a validity quorum of members voted for the message (see `verify`).
+//
+// # Fields:
+// * id - The ID of the thread that delivered the message and assembled the certificate.
+// * message - The delivered message.
+// * instance_number - The instance the message was delivered in.
+// * round_number - The round the message was delivered in.
+// * votes - The signature of the Vote of every voter in the quorum.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeliveryCertificate<T> {
+    id: u32,
+    message: Message<T>,
+    instance_number: Instance,
+    round_number: Round,
+    votes: Vec<VoteSignature>,
+}
+
+// # Struct Description:
+// This struct is the signature of a Vote in a `DeliveryCertificate`.
+//
+// # Fields:
+// * voter - The ID of the thread that sent and signed the Vote.
+// * signature - The hex-encoded signature of the Vote.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VoteSignature {
+    voter: u32,
+    signature: String,
+}
+
+impl VoteSignature {
+    pub fn get_voter(&self) -> u32 {
+        self.voter
+    }
+
+    pub fn get_signature(&self) -> &String {
+        &self.signature
+    }
+}
+
+impl<T> DeliveryCertificate<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Function Description:
+    // This function assembles the certificate of a delivered message from the signed Votes received for
+    // its instance. Votes for another message, unsigned Votes, and the Votes that do not name their sender
+    // are left out.
+    //
+    // # Parameters:
+    // * id - The ID of the thread that delivered the message.
+    // * message - The delivered message.
+    // * instance_number - The instance the message was delivered in.
+    // * round_number - The round the message was delivered in.
+    // * votes - The Votes received for the instance, once per sender.
+    //
+    // # Returns:
+    // * The certificate.
+    pub fn assemble(id: u32, message: Message<T>, instance_number: Instance, round_number: Round, votes: &[Signal<T>]) -> Self {
+        let content = ObjectContent::Message(message.clone());
+        let votes = votes.iter()
+            .filter(|vote| *vote.get_signal() == SignalType::Vote && *vote.get_content() == content)
+            .filter_map(|vote| {
+                let (Some(voter), Some(signature)) = (vote.get_origin(), vote.get_signature()) else {
+                    return None
+                };
+                Some(VoteSignature { voter, signature: signature.clone() })
+            })
+            .collect();
+        Self {
+            id,
+            message,
+            instance_number,
+            round_number,
+            votes
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_message(&self) -> &Message<T> {
+        &self.message
+    }
+
+    pub fn get_instance_number(&self) -> Instance {
+        self.instance_number
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_votes(&self) -> &Vec<VoteSignature> {
+        &self.votes
+    }
+
+    // # Method Description:
+    // This method rebuilds the signed Vote of a voter, as the voter sent it.
+    //
+    // # Parameters:
+    // * vote - The signature of the Vote.
+    //
+    // # Returns:
+    // * The Vote.
+    fn rebuild_vote(&self, vote: &VoteSignature) -> Signal<T> {
+        Signal::new(SignalType::Vote, ObjectContent::Message(self.message.clone()), self.instance_number, self.round_number)
+            .with_origin(vote.voter)
+            .with_signature(vote.voter, vote.signature.clone())
+    }
+
+    // # Method Description:
+    // This method checks that the certificate proves the delivery of its message: every Vote is signed by
+    // a distinct member of the cluster, and the voters weigh at least the validity threshold of the cluster.
+    //
+    // # Parameters:
+    // * document - The membership document of the cluster, holding the public key of every member and
+    //   the thresholds of the cluster.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of the first problem found.
+    pub fn verify(&self, document: &MembershipDocument) -> Result<(), String> {
+        let mut voters = BTreeSet::new();
+        for vote in &self.votes {
+            if !voters.insert(vote.voter) {
+                return Err(format!("node {} voted more than once", vote.voter))
+            }
+            if !self.rebuild_vote(vote).is_authentic_under(document) {
+                return Err(format!("the signature of the Vote of node {} does not match the certificate", vote.voter))
+            }
+        }
+        let thresholds = document.get_thresholds();
+        let weight: u64 = voters.iter().map(|voter| thresholds.get_weight(*voter)).sum();
+        if weight < thresholds.get_validity_threshold() {
+            return Err(format!("the voters weigh {weight}, below the validity threshold of {}", thresholds.get_validity_threshold()))
+        }
+        Ok(())
+    }
+}
+
+impl<T> JsonConversion<DeliveryCertificate<T>> for DeliveryCertificate<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{}
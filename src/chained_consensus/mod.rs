@@ -0,0 +1,687 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap, VecDeque}, fmt::Debug, hash::Hash, time::{Duration, Instant}};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::basic::Message;
+use crate::json::JsonConversion;
+use crate::leader::{LeaderElection, LeaderSchedule};
+use crate::reliable::ReliableCommunication;
+use crate::round::{Instance, Round};
+use crate::signing::SignalSigner;
+use crate::logging::log;
+
+// The digest standing for the genesis block, which every chain starts from.
+pub const GENESIS: u64 = 0;
+
+// How long a thread waits in a view, by default, before it gives up on the view and changes views.
+const DEFAULT_VIEW_TIMEOUT: Duration = Duration::from_millis(500);
+
+// How long a thread waits for the payloads of its view before it looks again for the proposal of the view.
+const PROPOSAL_POLL: Duration = Duration::from_millis(5);
+
+// # Function Description:
+// This function provides the bytes a thread signs to vote for a block.
+//
+// # Parameters:
+// * view - The view of the block.
+// * block - The digest of the block.
+//
+// # Returns:
+// * The bytes of the vote.
+fn vote_bytes(view: u32, block: u64) -> Vec<u8> {
+    format!("chained consensus vote {view} {block}").into_bytes()
+}
+
+// # Struct Description:
+// This struct is the vote of a thread for a block, signed with the key the thread signs its signals with.
+//
+// # Fields:
+// * view - The view of the block.
+// * block - The digest of the block.
+// * voter - The thread that voted.
+// * signature - The signature of the vote by the voter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SignedVote {
+    view: u32,
+    block: u64,
+    voter: u32,
+    signature: Vec<u8>,
+}
+
+impl SignedVote {
+    // # Function Description:
+    // This function signs the vote of a thread for a block.
+    //
+    // # Parameters:
+    // * view - The view of the block.
+    // * block - The digest of the block.
+    // * signer - The signer of the thread.
+    //
+    // # Returns:
+    // * The signed vote.
+    pub fn sign(view: u32, block: u64, signer: &SignalSigner) -> Self {
+        Self {
+            view,
+            block,
+            voter: signer.get_id(),
+            signature: signer.sign(&vote_bytes(view, block)),
+        }
+    }
+
+    pub fn get_view(&self) -> u32 {
+        self.view
+    }
+
+    pub fn get_block(&self) -> u64 {
+        self.block
+    }
+
+    pub fn get_voter(&self) -> u32 {
+        self.voter
+    }
+
+    // # Method Description:
+    // This method checks that the vote is signed by its voter.
+    //
+    // # Parameters:
+    // * signer - A signer knowing the public key of the voter.
+    //
+    // # Returns:
+    // * `true` if the signature is the voter's.
+    pub fn verify(&self, signer: &SignalSigner) -> bool {
+        signer.verify(self.voter, &vote_bytes(self.view, self.block), &self.signature)
+    }
+}
+
+// # Struct Description:
+// This struct is a quorum certificate: the proof that `n - f` threads voted for a block, made of their
+// signed votes, so that a certificate cannot name a thread that did not vote.
+//
+// # Fields:
+// * view - The view of the certified block.
+// * block - The digest of the certified block.
+// * signatures - The signature of the vote of every voter, by voter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct QuorumCertificate {
+    view: u32,
+    block: u64,
+    signatures: BTreeMap<u32, Vec<u8>>,
+}
+
+impl QuorumCertificate {
+    // # Function Description:
+    // This function creates the certificate of a block from the votes for it.
+    //
+    // # Parameters:
+    // * view - The view of the block.
+    // * block - The digest of the block.
+    // * votes - The signed votes for the block.
+    //
+    // # Returns:
+    // * The certificate, holding the votes for the block only.
+    pub fn new(view: u32, block: u64, votes: &[SignedVote]) -> Self {
+        let signatures = votes.iter()
+            .filter(|vote| vote.view == view && vote.block == block)
+            .map(|vote| (vote.voter, vote.signature.clone()))
+            .collect();
+        Self {
+            view,
+            block,
+            signatures,
+        }
+    }
+
+    // # Function Description:
+    // This function provides the certificate of the genesis block, which needs no vote.
+    //
+    // # Returns:
+    // * The certificate of view 0 for `GENESIS`.
+    pub fn genesis() -> Self {
+        Self::new(0, GENESIS, &[])
+    }
+
+    pub fn get_view(&self) -> u32 {
+        self.view
+    }
+
+    pub fn get_block(&self) -> u64 {
+        self.block
+    }
+
+    pub fn get_voters(&self) -> BTreeSet<u32> {
+        self.signatures.keys().copied().collect()
+    }
+
+    // # Method Description:
+    // This method checks a certificate against the cluster.
+    //
+    // # Parameters:
+    // * thread_count - The number of threads of the cluster.
+    // * quorum - The number of votes a certificate needs.
+    // * signer - A signer knowing the public key of every thread of the cluster.
+    //
+    // # Returns:
+    // * `true` for the genesis certificate, or for a certificate holding the valid signatures of a quorum
+    //   of threads of the cluster.
+    pub fn is_valid(&self, thread_count: u32, quorum: u32, signer: &SignalSigner) -> bool {
+        if *self == Self::genesis() {
+            return true
+        }
+        let bytes = vote_bytes(self.view, self.block);
+        self.signatures.len() as u32 >= quorum
+            && self.signatures.iter().all(|(voter, signature)| *voter < thread_count && signer.verify(*voter, &bytes, signature))
+    }
+}
+
+// # Struct Description:
+// This struct represents a block of the chain: the entry proposed by the leader of a view, chained to the
+// block certified by the certificate it carries.
+//
+// # Fields:
+// * view - The view the block was proposed in.
+// * proposer - The leader that proposed the block.
+// * parent - The digest of the block it extends, which `justify` certifies.
+// * justify - The highest certificate the leader knew of.
+// * entry - The entry of the block, or `None` if the leader had nothing to propose.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Block<T> {
+    view: u32,
+    proposer: u32,
+    parent: u64,
+    justify: QuorumCertificate,
+    entry: Option<T>,
+}
+
+impl<T> Block<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(view: u32, proposer: u32, justify: QuorumCertificate, entry: Option<T>) -> Self {
+        Self {
+            view,
+            proposer,
+            parent: justify.get_block(),
+            justify,
+            entry,
+        }
+    }
+
+    pub fn get_view(&self) -> u32 {
+        self.view
+    }
+
+    pub fn get_proposer(&self) -> u32 {
+        self.proposer
+    }
+
+    pub fn get_parent(&self) -> u64 {
+        self.parent
+    }
+
+    pub fn get_justify(&self) -> &QuorumCertificate {
+        &self.justify
+    }
+
+    pub fn get_entry(&self) -> Option<&T> {
+        self.entry.as_ref()
+    }
+}
+
+impl<T> JsonConversion<Block<T>> for Block<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{}
+
+// # Enum Description:
+// This enum represents the payloads chained consensus threads send to each other. Every payload of view
+// `v` is sent in round `v`, so a thread running a view only reads the payloads of that view.
+//
+// # Variants:
+// * Proposal - The block of the leader of a view, reliably broadcast in the instance and round of the view.
+// * Vote - The signed vote of a thread for the block of the previous view, sent to the leader of the view.
+// * NewView - The highest certificate of a thread that gave up on the previous view, sent to the leader of the view.
+// * BlockRequest - The digest of a certified block a thread did not receive, sent to the voters of its certificate.
+// * BlockResponse - A block requested by a thread, sent back to it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ChainedPayload<T> {
+    Proposal(Block<T>),
+    Vote(SignedVote),
+    NewView(QuorumCertificate),
+    BlockRequest(u64),
+    BlockResponse(Block<T>),
+}
+
+// # Struct Description:
+// This struct runs chained consensus (HotStuff-style) over the signal channels of a reliable communicator, so
+// that the threads of a cluster commit the same log of entries. Views rotate leaders following the
+// `LeaderElection` of the cluster (round-robin if none is configured). The leader of a view reliably
+// broadcasts a block carrying the highest certificate it knows of and extending the block it certifies, so
+// that it cannot propose different blocks to different threads; every thread votes for the block, if it
+// extends the block the thread locked or carries a higher certificate than its lock, by sending its signed
+// vote to the leader of the next view, which certifies the block with `n - f` votes. The three phases of
+// HotStuff are pipelined over consecutive views: a certificate locks the block its block certifies, and
+// once three blocks of consecutive views are certified in a chain, the first one is committed with its
+// ancestors. A thread that receives no proposal before the view timeout sends its highest certificate to
+// the leader of the next view, which proposes on the highest of `n - f` of them.
+//
+// Votes are signed with the key the thread signs its signals with, so the cluster must run under
+// `Authentication::Ed25519`, and certificates and votes whose signatures do not verify are discarded. A
+// thread missing an ancestor of a certified block takes it from the reliable broadcast of its view once it is
+// delivered, and meanwhile requests it from the voters of the certificate, which answer while they run views.
+//
+// Views use the reliable instances and rounds of their number on the communicator, so the communicator
+// should be dedicated to chained consensus, and its reliable handle must be running.
+//
+// # Fields:
+// * communicator - The communicator whose signal channels carry the proposals, and basic channels the votes.
+// * signer - The signer of the thread, which signs its votes and verifies the votes of the others.
+// * leader_schedule - The leader of every view.
+// * view_timeout - How long the thread waits in a view before changing views.
+// * view - The next view the thread runs, from 1 (view 0 is the genesis block).
+// * blocks - The blocks the thread received, by digest.
+// * requested - The view of every certified block the thread requested from its peers, by digest.
+// * high_qc - The highest certificate the thread knows of.
+// * locked_qc - The certificate of the block the thread locked, which every block it votes for must extend
+//   unless it carries a higher certificate.
+// * committed - The digest of the last committed block.
+// * committed_view - The view of the last committed block.
+// * log - The committed entries, in order.
+// * pending - The entries submitted to the thread and not committed yet, which it proposes when leading.
+pub struct ChainedConsensus<C, T> {
+    communicator: C,
+    signer: SignalSigner,
+    leader_schedule: LeaderSchedule,
+    view_timeout: Duration,
+    view: u32,
+    blocks: HashMap<u64, Block<T>>,
+    requested: HashMap<u64, u32>,
+    high_qc: QuorumCertificate,
+    locked_qc: QuorumCertificate,
+    committed: u64,
+    committed_view: u32,
+    log: Vec<Message<T>>,
+    pending: VecDeque<T>,
+}
+
+impl<C, T> ChainedConsensus<C, T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<ChainedPayload<T>> + Send,
+{
+    // # Function Description:
+    // This function wraps a reliable communicator dedicated to chained consensus.
+    //
+    // # Parameters:
+    // * communicator - The communicator.
+    //
+    // # Returns:
+    // * The consensus of the thread, at view 1.
+    // # Panics:
+    // * If the signals of the communicator are not signed.
+    pub fn new(communicator: C) -> Self {
+        let Some(signer) = communicator.get_signal_channels().get_signer().cloned() else {
+            panic!("Error: chained consensus signs its votes, which requires Authentication::Ed25519");
+        };
+        let config = communicator.get_config();
+        let leader_schedule = match config.get_leader_election() {
+            LeaderElection::Disabled => LeaderSchedule::new(LeaderElection::RoundRobin, config.get_thread_count(), config.get_seed()),
+            _ => config.get_leader_schedule(),
+        };
+
+        Self {
+            communicator,
+            signer,
+            leader_schedule,
+            view_timeout: DEFAULT_VIEW_TIMEOUT,
+            view: 1,
+            blocks: HashMap::new(),
+            requested: HashMap::new(),
+            high_qc: QuorumCertificate::genesis(),
+            locked_qc: QuorumCertificate::genesis(),
+            committed: GENESIS,
+            committed_view: 0,
+            log: vec![],
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn with_view_timeout(mut self, view_timeout: Duration) -> Self {
+        self.view_timeout = view_timeout;
+        self
+    }
+
+    pub fn get_communicator(&mut self) -> &mut C {
+        &mut self.communicator
+    }
+
+    pub fn get_view(&self) -> u32 {
+        self.view
+    }
+
+    pub fn get_high_qc(&self) -> &QuorumCertificate {
+        &self.high_qc
+    }
+
+    pub fn get_committed_log(&self) -> &Vec<Message<T>> {
+        &self.log
+    }
+
+    // # Method Description:
+    // This method submits an entry, which the thread proposes the next times it leads a view, until the
+    // entry is committed.
+    //
+    // # Parameters:
+    // * entry - The entry.
+    pub fn submit(&mut self, entry: T) {
+        self.pending.push_back(entry);
+    }
+
+    // # Method Description:
+    // This method runs views, one after the other.
+    //
+    // # Parameters:
+    // * views - The number of views to run.
+    //
+    // # Returns:
+    // * The committed log of the thread: the entries of the committed blocks, by view, each as a message of
+    //   its proposer in the instance and round of its view.
+    pub async fn run(&mut self, views: u32) -> &Vec<Message<T>> {
+        for _ in 0..views {
+            self.run_view().await;
+        }
+        &self.log
+    }
+
+    // # Method Description:
+    // This method runs the next view of the thread: as the leader, it waits for the certificate of the
+    // previous block or for `n - f` new views, then proposes; as every thread, it votes for the proposal of
+    // the leader, or changes views once the view timeout expires. Meanwhile, it answers the block requests
+    // of its peers and receives the blocks it requested.
+    async fn run_view(&mut self) {
+        let thread_id = *self.communicator.get_id();
+        let thread_count = self.communicator.get_config().get_thread_count();
+        let quorum = thread_count - self.communicator.get_config().get_thresholds().get_faulty_threads() as u32;
+        let view = self.view;
+        let leader = self.get_leader(view);
+        let next_leader = self.get_leader(view + 1);
+        let deadline = Instant::now() + self.view_timeout;
+
+        let mut votes: HashMap<u64, BTreeMap<u32, SignedVote>> = HashMap::new();
+        let mut new_views: BTreeSet<u32> = BTreeSet::new();
+        let mut certified = view == 1;
+        let mut proposed = false;
+        let mut voted = false;
+        loop {
+            if leader == thread_id && !proposed && (certified || new_views.len() as u32 >= quorum) {
+                let block = self.propose(view);
+                self.communicator.reliable_broadcast(ChainedPayload::Proposal(block.clone()), Instance(view), Round(view)).await;
+                proposed = true;
+                if let Some(digest) = self.accept_proposal(block).await {
+                    self.vote(view, digest, next_leader).await;
+                    voted = true;
+                }
+            }
+            if leader != thread_id && !voted
+                && let Ok(message) = self.communicator.reliable_recv_with_timeout(Some(leader), Instance(view), Round(view), Duration::ZERO).await
+                && let ChainedPayload::Proposal(block) = message.get_message().clone()
+                && block.get_view() == view && block.get_proposer() == leader
+                && let Some(digest) = self.accept_proposal(block).await {
+                self.vote(view, digest, next_leader).await;
+                voted = true;
+            }
+            self.receive_requested_blocks().await;
+            if voted {
+                break
+            }
+            if Instant::now() >= deadline {
+                log!(info, { node = thread_id, round = view }, "id: {thread_id}, view {view} timed out, changing views with the certificate of view {}", self.high_qc.get_view());
+                self.communicator.basic_send(next_leader, ChainedPayload::NewView(self.high_qc.clone()), Round(view + 1)).await;
+                break
+            }
+
+            let poll = deadline.min(Instant::now() + PROPOSAL_POLL);
+            for message in self.communicator.basic_recv_batch(None, Round(view), usize::MAX, poll).await {
+                let sender = message.get_id();
+                match message.get_message().clone() {
+                    ChainedPayload::Vote(vote) if leader == thread_id && vote.get_view() + 1 == view => {
+                        if !vote.verify(&self.signer) {
+                            log!(warn, { node = thread_id, round = view }, "id: {thread_id}, discarding a vote of view {} whose signature is not the one of id: {}", vote.get_view(), vote.get_voter());
+                            continue
+                        }
+                        let digest = vote.get_block();
+                        let voters = votes.entry(digest).or_default();
+                        voters.insert(vote.get_voter(), vote);
+                        if !certified && voters.len() as u32 >= quorum {
+                            let voters: Vec<SignedVote> = voters.values().cloned().collect();
+                            let certificate = QuorumCertificate::new(view - 1, digest, &voters);
+                            self.fetch_block(&certificate).await;
+                            self.update_high_qc(certificate);
+                            certified = true;
+                        }
+                    },
+                    ChainedPayload::NewView(certificate) if leader == thread_id && certificate.get_view() < view && certificate.is_valid(thread_count, quorum, &self.signer) => {
+                        self.fetch_block(&certificate).await;
+                        self.update_high_qc(certificate);
+                        new_views.insert(sender);
+                    },
+                    ChainedPayload::BlockRequest(digest) => {
+                        if let Some(block) = self.blocks.get(&digest).cloned() {
+                            self.communicator.basic_send(sender, ChainedPayload::BlockResponse(block), Round(view)).await;
+                        }
+                    },
+                    ChainedPayload::BlockResponse(block) => {
+                        let digest = block.json_digest();
+                        if self.requested.remove(&digest).is_some() {
+                            self.store_fetched_block(digest, block);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+        self.view += 1;
+    }
+
+    // # Method Description:
+    // This method signs the vote of the thread for the block of a view, and sends it to the next leader.
+    async fn vote(&mut self, view: u32, digest: u64, next_leader: u32) {
+        let vote = SignedVote::sign(view, digest, &self.signer);
+        self.communicator.basic_send(next_leader, ChainedPayload::Vote(vote), Round(view + 1)).await;
+    }
+
+    // # Method Description:
+    // This method creates the block of a view led by the thread, extending the block of its highest
+    // certificate with its first pending entry that is not already in an uncommitted block of the chain.
+    fn propose(&mut self, view: u32) -> Block<T> {
+        let mut chained = vec![];
+        let mut digest = self.high_qc.get_block();
+        while digest != self.committed && let Some(block) = self.blocks.get(&digest) {
+            chained.extend(block.get_entry());
+            digest = block.get_parent();
+        }
+        let entry = self.pending.iter().find(|entry| !chained.contains(entry)).cloned();
+        Block::new(view, *self.communicator.get_id(), self.high_qc.clone(), entry)
+    }
+
+    // # Method Description:
+    // This method processes the proposal of a view: it stores the block, updates the certificates and commits
+    // following the chain of the block, and decides whether to vote for it.
+    //
+    // # Parameters:
+    // * block - The block proposed by the leader of the view.
+    //
+    // # Returns:
+    // * The digest of the block if the thread votes for it, or `None` if the block is invalid or unsafe.
+    async fn accept_proposal(&mut self, block: Block<T>) -> Option<u64> {
+        let thread_id = *self.communicator.get_id();
+        let thread_count = self.communicator.get_config().get_thread_count();
+        let quorum = thread_count - self.communicator.get_config().get_thresholds().get_faulty_threads() as u32;
+        let justify = block.get_justify().clone();
+        if !justify.is_valid(thread_count, quorum, &self.signer) || justify.get_view() >= block.get_view() || block.get_parent() != justify.get_block() {
+            log!(warn, { node = thread_id, round = block.get_view() }, "id: {thread_id}, discarding the invalid block of view {} proposed by id: {}", block.get_view(), block.get_proposer());
+            return None
+        }
+
+        let digest = block.json_digest();
+        self.blocks.insert(digest, block);
+        self.fetch_block(&justify).await;
+        self.process_certificate(&justify);
+
+        // safety rule: the block must extend the locked block, unless it carries a higher certificate
+        if self.extends(digest, self.locked_qc.get_block()) || justify.get_view() > self.locked_qc.get_view() {
+            Some(digest)
+        } else {
+            log!(warn, { node = thread_id, round = self.view }, "id: {thread_id}, refusing to vote for a block that does not extend the block locked in view {}", self.locked_qc.get_view());
+            None
+        }
+    }
+
+    // # Method Description:
+    // This method processes the certificate carried by a block: the certificate becomes the highest one if
+    // it is, the certificate carried by the certified block locks its block, and three certified blocks of
+    // consecutive views commit the first one.
+    //
+    // # Parameters:
+    // * certificate - The certificate.
+    fn process_certificate(&mut self, certificate: &QuorumCertificate) {
+        self.update_high_qc(certificate.clone());
+        let Some(certified) = self.blocks.get(&certificate.get_block()) else {
+            return
+        };
+        let second = certified.get_justify().clone();
+        if second.get_view() > self.locked_qc.get_view() {
+            self.locked_qc = second.clone();
+        }
+        let Some(locked) = self.blocks.get(&second.get_block()) else {
+            return
+        };
+        let third = locked.get_justify().clone();
+        if certificate.get_view() == second.get_view() + 1 && second.get_view() == third.get_view() + 1 && third.get_view() > self.committed_view {
+            self.commit(third.get_block());
+        }
+    }
+
+    fn update_high_qc(&mut self, certificate: QuorumCertificate) {
+        if certificate.get_view() > self.high_qc.get_view() {
+            self.high_qc = certificate;
+        }
+    }
+
+    // # Method Description:
+    // This method commits a block with its uncommitted ancestors, appending their entries to the log in
+    // chain order. A block whose ancestors the thread did not receive is not committed, and its missing
+    // ancestor is requested from the voters of the certificate that chains it.
+    //
+    // # Parameters:
+    // * digest - The digest of the block.
+    fn commit(&mut self, digest: u64) {
+        let thread_id = *self.communicator.get_id();
+        let mut chain = vec![];
+        let mut ancestor = digest;
+        while ancestor != self.committed {
+            match self.blocks.get(&ancestor) {
+                Some(block) => {
+                    chain.push(block.clone());
+                    ancestor = block.get_parent();
+                },
+                None => {
+                    log!(warn, { node = thread_id }, "id: {thread_id}, cannot commit up to a block whose ancestors were not received yet");
+                    if let Some(child) = chain.last() {
+                        self.requested.entry(ancestor).or_insert(child.get_justify().get_view());
+                    }
+                    return
+                },
+            }
+        }
+
+        for block in chain.into_iter().rev() {
+            log!(info, { node = thread_id, round = block.get_view() }, "id: {thread_id}, committing the block of view {} proposed by id: {}", block.get_view(), block.get_proposer());
+            if let Some(entry) = block.get_entry() {
+                if block.get_proposer() == thread_id && let Some(position) = self.pending.iter().position(|pending| pending == entry) {
+                    self.pending.remove(position);
+                }
+                self.log.push(Message::new(String::from("chained consensus"), block.get_proposer(), entry.clone(), None, Some(Instance(block.get_view())), Round(block.get_view())));
+            }
+            self.committed_view = block.get_view();
+        }
+        self.committed = digest;
+    }
+
+    // # Method Description:
+    // This method checks whether a block extends another one, following the parents of the blocks the thread received.
+    //
+    // # Parameters:
+    // * digest - The digest of the block.
+    // * ancestor - The digest of the other block.
+    //
+    // # Returns:
+    // * `true` if the block is the other block or one of its descendants.
+    fn extends(&self, digest: u64, ancestor: u64) -> bool {
+        let mut current = digest;
+        loop {
+            if current == ancestor {
+                return true
+            }
+            match self.blocks.get(&current) {
+                Some(block) => current = block.get_parent(),
+                None => return false,
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method looks for the block of a certificate of a past view that the thread did not receive while
+    // running the view (e.g. because the view timed out first), in the proposals of the view already
+    // delivered, without waiting. A block not delivered yet is requested from the voters of the certificate,
+    // which hold it, since they voted for it.
+    //
+    // # Parameters:
+    // * certificate - The certificate of the block.
+    async fn fetch_block(&mut self, certificate: &QuorumCertificate) {
+        let (view, digest) = (certificate.get_view(), certificate.get_block());
+        if digest == GENESIS || self.blocks.contains_key(&digest) || view >= self.view {
+            return
+        }
+        let leader = self.get_leader(view);
+        if let Ok(message) = self.communicator.reliable_recv_with_timeout(Some(leader), Instance(view), Round(view), Duration::ZERO).await
+            && let ChainedPayload::Proposal(block) = message.get_message()
+            && block.json_digest() == digest {
+            self.blocks.insert(digest, block.clone());
+            return
+        }
+        if self.requested.insert(digest, view).is_none() {
+            let thread_id = *self.communicator.get_id();
+            log!(info, { node = thread_id, round = self.view }, "id: {thread_id}, requesting the block of view {view} from its voters");
+            for voter in certificate.get_voters().into_iter().filter(|voter| *voter != thread_id) {
+                self.communicator.basic_send(voter, ChainedPayload::BlockRequest(digest), Round(self.view)).await;
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method receives the requested blocks whose proposals were delivered in the meantime, without waiting.
+    async fn receive_requested_blocks(&mut self) {
+        let requested: Vec<(u64, u32)> = self.requested.iter().map(|(digest, view)| (*digest, *view)).collect();
+        for (digest, view) in requested {
+            let leader = self.get_leader(view);
+            if let Ok(message) = self.communicator.reliable_recv_with_timeout(Some(leader), Instance(view), Round(view), Duration::ZERO).await
+                && let ChainedPayload::Proposal(block) = message.get_message()
+                && block.json_digest() == digest {
+                self.requested.remove(&digest);
+                self.store_fetched_block(digest, block.clone());
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method stores a block the thread fetched, and processes its highest certificate again, in case
+    // the block was the missing ancestor of a block to commit.
+    fn store_fetched_block(&mut self, digest: u64, block: Block<T>) {
+        self.blocks.insert(digest, block);
+        let high_qc = self.high_qc.clone();
+        self.process_certificate(&high_qc);
+    }
+
+    fn get_leader(&self, view: u32) -> u32 {
+        self.leader_schedule.get_leader(Round(view)).unwrap_or_default()
+    }
+}
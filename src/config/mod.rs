@@ -0,0 +1,1240 @@
+use std::{collections::{BTreeMap, HashSet}, path::{Path, PathBuf}, sync::Arc, time::Duration};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize};
+use rand::{SeedableRng, rngs::StdRng};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+use crate::reliable::DuplicateInputPolicy;
+use crate::witness::{RoundValues, ValueOrdering};
+use crate::emulation::NetworkEmulation;
+use crate::faults::{Fault, FaultScript};
+use crate::json::{JsonConversion, Codec};
+use crate::drive::HandleMode;
+use crate::events::DecodePolicy;
+use crate::dissemination::Dissemination;
+use crate::storage::{Storage, StorageBackend};
+use crate::basic::{QueueEviction, RepeatedCollectPolicy};
+use crate::signing::Authentication;
+use crate::middleware::{Layer, MiddlewareChain};
+use crate::membership::MembershipCertificate;
+use crate::retention::RetentionPolicy;
+use crate::multiplex::LaneScaling;
+use crate::trace::{TraceSampler, TraceSampling};
+use crate::divergence::DigestGossip;
+use crate::demo::DemoPacing;
+use crate::leader::{LeaderElection, LeaderSchedule};
+use crate::batching::SignalBatching;
+
+// # Struct Description:
+// This struct captures the configuration shared by every communicator handed out by a hub.
+// It is the single place where experiment-wide knobs are stored, so that an entire run
+// can be reproduced from one value. In particular, every randomized component (gossip fanout,
+// committee sampling, coin flips, network emulation) must draw its randomness from an RNG
+// created through `create_rng`, which derives an independent stream from the experiment seed.
+// The configuration is serializable and is embedded in every exported artifact (e.g. `MetricsReport`),
+// so that each artifact records exactly how it was produced.
+//
+// # Fields:
+// * thread_count - The number of threads participating in the experiment.
+// * thresholds - The fault tolerance and quorum thresholds derived from the thread count.
+// * quorum_config - How the thresholds are derived from the thread count.
+// * codec - The wire format of the frames exchanged between threads.
+// * buffer_size - The capacity of every bounded channel created for the cluster.
+// * seed - The experiment seed from which every component RNG is derived.
+// * duplicate_input_policy - How reliable handles react to a second Input for an existing instance.
+// * network_emulation - The emulated network placed between threads, if any.
+// * fault_script - The faults injected into the threads.
+// * value_ordering - The order in which witness handles report and deliver the values of a round.
+// * round_values - Whether the values of a witness round start empty or from the values of the previous round.
+// * handle_mode - Whether background handles run in spawned tasks or are driven manually.
+// * decode_policy - How threads react to frames their handles cannot decode.
+// * aggregation_depth - The number of aggregation levels `k` run by aggregated witness handles.
+// * dissemination - How reliable handles disseminate their Echo and Vote signals.
+// * protocol_dissemination - How reliable handles disseminate the Echo and Vote signals of the protocols
+//   with a dissemination mode of their own, by protocol information.
+// * storage_backend - Where the threads persist their state.
+// * journaling - Whether every thread journals its signals and deliveries in its storage (see `SignalJournal`).
+// * repeated_collect_policy - How communicators answer a collect call for a round they already collected.
+// * authentication - Whether threads sign the signals they broadcast and verify the signals they receive.
+// * deliver_to_self - Whether basic and reliable broadcasts are delivered to the thread that sent them.
+// * layers - The middleware layers every frame goes through, in outbound order.
+// * max_payload_size - The size above which frames delivered to the local queues are quarantined, if any.
+// * membership - The signed membership of the cluster, outside of which frames are rejected, if any.
+// * retention_policy - How long handles keep the state of the instances and rounds they completed.
+// * lane_scaling - How the demultiplexers handle a lane whose queue is full.
+// * trace_sampling - Which instances are traced end-to-end (phase latencies, round statuses, delivery records).
+// * digest_gossip - Whether witness handles gossip the digests of their rounds to detect diverging nodes.
+// * frame_statistics - Whether the reports and aggregated reports broadcast embed their `FrameStatistics`.
+// * demo_pacing - The pacing of the cluster in demo mode, if it runs in demo mode.
+// * vector_clocks - Whether the communicators stamp the basic, reliable, and FIFO messages they send with their vector clock.
+// * fast_path - How long after the first signal of an instance reliable handles deliver it on the Echo signals of every thread, if they do.
+// * leader_election - How the leader of every round, whose "reliable" Inputs are the only ones accepted, is elected.
+// * pacemaker - How long reliable handles wait for the delivery of an instance before changing the view of its round, if they do.
+// * signal_batching - How the signals sent to the same peer are coalesced into a single frame, if they are.
+// * queue_eviction - How the local queues evict the messages and collections nobody receives.
+// * identity_file - The file holding the identity of the node hosted by the process, in network mode, if any.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClusterConfig {
+    thread_count: u32,
+    thresholds: Thresholds,
+    quorum_config: QuorumConfig,
+    codec: Codec,
+    buffer_size: usize,
+    seed: u64,
+    duplicate_input_policy: DuplicateInputPolicy,
+    network_emulation: Option<NetworkEmulation>,
+    fault_script: FaultScript,
+    value_ordering: ValueOrdering,
+    round_values: RoundValues,
+    handle_mode: HandleMode,
+    decode_policy: DecodePolicy,
+    aggregation_depth: u32,
+    dissemination: Dissemination,
+    protocol_dissemination: BTreeMap<String, Dissemination>,
+    storage_backend: StorageBackend,
+    journaling: bool,
+    repeated_collect_policy: RepeatedCollectPolicy,
+    authentication: Authentication,
+    deliver_to_self: bool,
+    layers: Vec<Layer>,
+    max_payload_size: Option<usize>,
+    membership: Option<MembershipCertificate>,
+    retention_policy: RetentionPolicy,
+    lane_scaling: LaneScaling,
+    trace_sampling: TraceSampling,
+    digest_gossip: DigestGossip,
+    frame_statistics: bool,
+    demo_pacing: Option<DemoPacing>,
+    vector_clocks: bool,
+    fast_path: Option<Duration>,
+    leader_election: LeaderElection,
+    pacemaker: Option<Duration>,
+    signal_batching: Option<SignalBatching>,
+    queue_eviction: QueueEviction,
+    identity_file: Option<PathBuf>,
+}
+
+impl ClusterConfig {
+    pub fn new(thread_count: u32) -> Self {
+        let thresholds = Thresholds::new(thread_count);
+        let quorum_config = QuorumConfig::new();
+        let codec = Codec::Json;
+        let buffer_size = 256;
+        let seed = 0;
+        let duplicate_input_policy = DuplicateInputPolicy::Reject;
+        let network_emulation = None;
+        let fault_script = FaultScript::new();
+        let value_ordering = ValueOrdering::Canonical;
+        let round_values = RoundValues::PerRound;
+        let handle_mode = HandleMode::Spawned;
+        let decode_policy = DecodePolicy::Discard;
+        let aggregation_depth = 2;
+        let dissemination = Dissemination::Direct;
+        let protocol_dissemination = BTreeMap::new();
+        let storage_backend = StorageBackend::Memory;
+        let journaling = false;
+        let repeated_collect_policy = RepeatedCollectPolicy::Cached;
+        let authentication = Authentication::Unsigned;
+        let deliver_to_self = true;
+        let layers = vec![];
+        let max_payload_size = None;
+        let membership = None;
+        let retention_policy = RetentionPolicy::KeepAll;
+        let lane_scaling = LaneScaling::Overflow;
+        let trace_sampling = TraceSampling::Full;
+        let digest_gossip = DigestGossip::Disabled;
+        let frame_statistics = false;
+        let demo_pacing = None;
+        let vector_clocks = false;
+        let fast_path = None;
+        let leader_election = LeaderElection::Disabled;
+        let pacemaker = None;
+        let signal_batching = None;
+        let queue_eviction = QueueEviction::Never;
+        let identity_file = None;
+        Self {
+            thread_count,
+            thresholds,
+            quorum_config,
+            codec,
+            buffer_size,
+            seed,
+            duplicate_input_policy,
+            network_emulation,
+            fault_script,
+            value_ordering,
+            round_values,
+            handle_mode,
+            decode_policy,
+            aggregation_depth,
+            dissemination,
+            protocol_dissemination,
+            storage_backend,
+            journaling,
+            repeated_collect_policy,
+            authentication,
+            deliver_to_self,
+            layers,
+            max_payload_size,
+            membership,
+            retention_policy,
+            lane_scaling,
+            trace_sampling,
+            digest_gossip,
+            frame_statistics,
+            demo_pacing,
+            vector_clocks,
+            fast_path,
+            leader_election,
+            pacemaker,
+            signal_batching,
+            queue_eviction,
+            identity_file,
+        }
+    }
+
+    // # Method Description:
+    // This method sets how the thresholds of the cluster are derived from its size (see `QuorumConfig`).
+    // Weighted quorums are counted by the basic, reliable, witness, and aggregated witness protocols: the
+    // barycentric hub refuses them.
+    //
+    // # Parameters:
+    // * quorum_config - The quorum configuration.
+    //
+    // # Returns:
+    // * The updated configuration, or a description of why the quorum configuration cannot be satisfied by
+    //   the cluster (see `QuorumConfig::thresholds`).
+    pub fn with_quorum_config(mut self, quorum_config: QuorumConfig) -> Result<Self, String> {
+        self.thresholds = quorum_config.thresholds(self.thread_count)?;
+        self.quorum_config = quorum_config;
+        Ok(self)
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // # Method Description:
+    // This method sets the wire format of the frames exchanged between the threads of the hub. Every thread
+    // of a cluster must use the same codec, since frames do not record their format.
+    //
+    // # Parameters:
+    // * codec - The wire format.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        if buffer_size == 0 {
+            panic!("Error: the channel buffer size must be positive");
+        }
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    pub fn with_duplicate_input_policy(mut self, duplicate_input_policy: DuplicateInputPolicy) -> Self {
+        self.duplicate_input_policy = duplicate_input_policy;
+        self
+    }
+
+    pub fn with_network_emulation(mut self, network_emulation: NetworkEmulation) -> Self {
+        self.network_emulation = Some(network_emulation);
+        self
+    }
+
+    pub fn with_fault(mut self, fault: Fault) -> Self {
+        self.fault_script = self.fault_script.with_fault(fault);
+        self
+    }
+
+    pub fn with_value_ordering(mut self, value_ordering: ValueOrdering) -> Self {
+        self.value_ordering = value_ordering;
+        self
+    }
+
+    pub fn with_round_values(mut self, round_values: RoundValues) -> Self {
+        self.round_values = round_values;
+        self
+    }
+
+    pub fn with_handle_mode(mut self, handle_mode: HandleMode) -> Self {
+        self.handle_mode = handle_mode;
+        self
+    }
+
+    pub fn with_decode_policy(mut self, decode_policy: DecodePolicy) -> Self {
+        self.decode_policy = decode_policy;
+        self
+    }
+
+    // # Method Description:
+    // This method sets the number of aggregation levels run by aggregated witness handles: level 1
+    // validates reports into witnesses, level 2 aggregates witnesses into aggregated witnesses, and every
+    // further level aggregates the witnesses of the level below. Rounds are delivered once the last level
+    // completes. The default of 2 is the classic aggregated witness protocol.
+    //
+    // # Parameters:
+    // * aggregation_depth - The number of levels `k`, at least 1.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_aggregation_depth(mut self, aggregation_depth: u32) -> Self {
+        if aggregation_depth == 0 {
+            panic!("Error: the aggregation depth must be at least 1");
+        }
+        self.aggregation_depth = aggregation_depth;
+        self
+    }
+
+    // # Method Description:
+    // This method sets how reliable handles disseminate their Echo and Vote signals. Since every hub
+    // owns its configuration, the dissemination mode is selected per hub.
+    //
+    // # Parameters:
+    // * dissemination - `Dissemination::Direct`, `Dissemination::SpanningTree` with a fan-out of at least 1,
+    //   or `Dissemination::Gossip` with a fan-out of at least 1 and a positive anti-entropy interval.
+    //
+    // # Returns:
+    // * The updated configuration.
+    // # Panics:
+    // * If the parameters of the mode are invalid.
+    pub fn with_dissemination(mut self, dissemination: Dissemination) -> Self {
+        if let Err(error) = dissemination.check() {
+            panic!("Error: {error}");
+        }
+        self.dissemination = dissemination;
+        self
+    }
+
+    // # Method Description:
+    // This method sets how reliable handles disseminate the Echo and Vote signals of a single protocol,
+    // in place of the mode of the hub (see `with_dissemination`), e.g. to gossip the signals of a protocol
+    // run by many threads while the others are sent directly.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol (e.g. "reliable", "witness").
+    // * dissemination - The mode of the protocol, with the same constraints as in `with_dissemination`.
+    //
+    // # Returns:
+    // * The updated configuration.
+    // # Panics:
+    // * If the parameters of the mode are invalid.
+    pub fn with_protocol_dissemination(mut self, protocol_information: &str, dissemination: Dissemination) -> Self {
+        if let Err(error) = dissemination.check() {
+            panic!("Error: {error}");
+        }
+        self.protocol_dissemination.insert(String::from(protocol_information), dissemination);
+        self
+    }
+
+    pub fn with_storage_backend(mut self, storage_backend: StorageBackend) -> Self {
+        self.storage_backend = storage_backend;
+        self
+    }
+
+    pub fn with_journaling(mut self, journaling: bool) -> Self {
+        self.journaling = journaling;
+        self
+    }
+
+    pub fn with_repeated_collect_policy(mut self, repeated_collect_policy: RepeatedCollectPolicy) -> Self {
+        self.repeated_collect_policy = repeated_collect_policy;
+        self
+    }
+
+    // # Method Description:
+    // This method sets whether threads authenticate their signals. Under `Authentication::Ed25519`, every
+    // thread signs its signals with the key of its identity (see `with_identity_file`), and
+    // its reliable handle rejects the signals whose signature does not match their claimed sender.
+    //
+    // # Parameters:
+    // * authentication - `Authentication::Unsigned` or `Authentication::Ed25519`.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_authentication(mut self, authentication: Authentication) -> Self {
+        self.authentication = authentication;
+        self
+    }
+
+    // # Method Description:
+    // This method sets whether a thread delivers its own basic and reliable broadcasts. By default it does,
+    // like every other thread. Without self-delivery, `basic_broadcast` skips the sending thread, and a
+    // reliable broadcast instance still runs the protocol on its sender (whose Echo and Vote count towards
+    // the quorums) but is not delivered to it. The rounds of the witness, aggregated witness, and barycentric
+    // protocols always include the thread's own value, which the protocols need.
+    //
+    // # Parameters:
+    // * deliver_to_self - Whether a thread delivers its own broadcasts.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_deliver_to_self(mut self, deliver_to_self: bool) -> Self {
+        self.deliver_to_self = deliver_to_self;
+        self
+    }
+
+    // # Method Description:
+    // This method appends a layer to the middleware chain of the hub (see `MiddlewareChain`), closest to the
+    // wire: frames sent by a thread go through the layers in the order they were added, and frames received
+    // through them in reverse order.
+    //
+    // # Parameters:
+    // * layer - The layer.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    // # Method Description:
+    // This method sets the size, in bytes, above which a frame reaching the local queues of a thread is
+    // quarantined instead of being stored (see `BasicCommunication::quarantined`). By default, frames of any
+    // size are stored.
+    //
+    // # Parameters:
+    // * max_payload_size - The largest accepted frame, in bytes.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = Some(max_payload_size);
+        self
+    }
+
+    // # Method Description:
+    // This method sets the signed membership of the cluster (see `MembershipCertificate`). Network
+    // transports then refuse the connections of non-members, handles reject the frames naming a non-member,
+    // and under `Authentication::Ed25519`, signals are verified with the public keys of the members.
+    //
+    // # Parameters:
+    // * membership - The verified membership certificate.
+    //
+    // # Returns:
+    // * The updated configuration.
+    //
+    // # Panics:
+    // * If the membership does not have one member per thread.
+    pub fn with_membership(mut self, membership: MembershipCertificate) -> Self {
+        if membership.get_member_count() != self.thread_count {
+            panic!("Error: the membership has {} members for {} threads", membership.get_member_count(), self.thread_count);
+        }
+        self.membership = Some(membership);
+        self
+    }
+
+    // # Method Description:
+    // This method sets the file holding the identity of the node hosted by the process (see
+    // `IdentityStore::load`), for clusters whose nodes run in separate processes. Under
+    // `Authentication::Ed25519`, the node then signs its signals with the secret key of that identity, while
+    // the configuration only holds the public keys of its peers, through the membership. Without it, every
+    // thread signs with the identity `IdentityStore::generate_cluster` derives from the seed, which is only
+    // suited to in-process simulations.
+    //
+    // # Parameters:
+    // * identity_file - The path of the identity file.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_identity_file(mut self, identity_file: PathBuf) -> Self {
+        self.identity_file = Some(identity_file);
+        self
+    }
+
+    // # Method Description:
+    // This method sets how long the handles keep the state of the instances and rounds they completed.
+    // Collected instances can no longer be inspected nor re-sent to recovering threads, and their late
+    // frames are ignored.
+    //
+    // # Parameters:
+    // * retention_policy - The retention policy.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_retention_policy(mut self, retention_policy: RetentionPolicy) -> Self {
+        self.retention_policy = retention_policy;
+        self
+    }
+
+    // # Method Description:
+    // This method sets how the demultiplexer of every thread handles a lane whose queue is full (see `LaneScaling`).
+    //
+    // # Parameters:
+    // * lane_scaling - The lane scaling mode.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_lane_scaling(mut self, lane_scaling: LaneScaling) -> Self {
+        self.lane_scaling = lane_scaling;
+        self
+    }
+
+    // # Method Description:
+    // This method sets which instances the handles trace end-to-end (see `TraceSampling`). Instances that
+    // are not sampled are still delivered, but record no phase latency or round status.
+    //
+    // # Parameters:
+    // * trace_sampling - The trace sampling.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_trace_sampling(mut self, trace_sampling: TraceSampling) -> Self {
+        self.trace_sampling = trace_sampling;
+        self
+    }
+
+    // # Method Description:
+    // This method sets whether the witness handles gossip the digests of their rounds (see `DigestGossip`),
+    // publishing `Event::DivergenceSuspected` for the rounds of peers that keep diverging.
+    //
+    // # Parameters:
+    // * digest_gossip - The digest gossip.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_digest_gossip(mut self, digest_gossip: DigestGossip) -> Self {
+        self.digest_gossip = digest_gossip;
+        self
+    }
+
+    // # Method Description:
+    // This method sets whether the witness handles embed their `FrameStatistics` (values and witnesses
+    // collected, thresholds, creation time) in the reports and aggregated reports they broadcast.
+    //
+    // # Parameters:
+    // * frame_statistics - Whether the statistics are embedded.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_frame_statistics(mut self, frame_statistics: bool) -> Self {
+        self.frame_statistics = frame_statistics;
+        self
+    }
+
+    // # Method Description:
+    // This method runs the cluster in demo mode: every frame a thread sends is held for the delay of its
+    // lane, and the handles narrate the progress of their rounds (see `Metrics::subscribe_narrations`).
+    //
+    // # Parameters:
+    // * demo_pacing - The pacing of the cluster.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_demo_pacing(mut self, demo_pacing: DemoPacing) -> Self {
+        self.demo_pacing = Some(demo_pacing);
+        self
+    }
+
+    // # Method Description:
+    // This method makes the communicators maintain a vector clock per thread: the basic, reliable, and FIFO
+    // messages a thread sends are stamped with it, and the clocks of the messages it receives are merged
+    // into it (see `BasicQueues::get_clock`). Off by default, so that re-sent inputs stay identical.
+    //
+    // # Parameters:
+    // * vector_clocks - Whether the communicators maintain vector clocks.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_vector_clocks(mut self, vector_clocks: bool) -> Self {
+        self.vector_clocks = vector_clocks;
+        self
+    }
+
+    // # Method Description:
+    // This method lets reliable handles deliver an instance on its fast path: once the Echo signals of every
+    // thread of the cluster were received for the same content, within the timeout from the first signal of
+    // the instance, it is delivered without waiting for the Vote quorum, one communication step earlier.
+    // Otherwise, the instance is delivered on the Vote quorum as usual. Handles still vote, so that the
+    // threads that missed an Echo deliver the instance on the slow path.
+    //
+    // # Parameters:
+    // * timeout - How long after the first signal of an instance the Echo signals of every thread must be received.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_fast_path(mut self, timeout: Duration) -> Self {
+        self.fast_path = Some(timeout);
+        self
+    }
+
+    // # Method Description:
+    // This method elects a leader for every round, whose Input is the only one reliable handles accept for
+    // the "reliable" instances of the round (see `LeaderElection`).
+    //
+    // # Parameters:
+    // * leader_election - The election.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_leader_election(mut self, leader_election: LeaderElection) -> Self {
+        self.leader_election = leader_election;
+        self
+    }
+
+    // # Method Description:
+    // This method runs a pacemaker in every reliable handle (see `Pacemaker`): once an instance stayed
+    // undelivered for the timeout, the thread requests a new view of its round, and the round moves to it
+    // with the other threads, rotating its leader under a `LeaderElection`.
+    //
+    // # Parameters:
+    // * timeout - How long an instance may stay undelivered before its round changes views.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_pacemaker(mut self, timeout: Duration) -> Self {
+        self.pacemaker = Some(timeout);
+        self
+    }
+
+    // # Method Description:
+    // This method batches the signals every thread sends (see `SignalBatching`): the Echo and Vote signals
+    // destined to the same peer within the window, or up to the maximum number of signals, are sent to it in
+    // a single frame, instead of one frame per signal.
+    //
+    // # Parameters:
+    // * window - How long a signal may wait for more signals to the same peer before it is sent.
+    // * max_signals - The number of signals that sends a batch before the end of its window.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_signal_batching(mut self, window: Duration, max_signals: usize) -> Self {
+        self.signal_batching = Some(SignalBatching::new(window, max_signals));
+        self
+    }
+
+    // # Method Description:
+    // This method sets how the local queues of every thread evict the messages and collections nobody
+    // receives (see `QueueEviction`).
+    //
+    // # Parameters:
+    // * queue_eviction - The eviction policy.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub fn with_queue_eviction(mut self, queue_eviction: QueueEviction) -> Self {
+        self.queue_eviction = queue_eviction;
+        self
+    }
+
+    pub fn get_thread_count(&self) -> u32 {
+        self.thread_count
+    }
+
+    pub fn get_thresholds(&self) -> &Thresholds {
+        &self.thresholds
+    }
+
+    pub fn get_quorum_config(&self) -> &QuorumConfig {
+        &self.quorum_config
+    }
+
+    // # Method Description:
+    // This method provides the thresholds a thread of the cluster counts its signals with (see `SelfCounting`).
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    //
+    // # Returns:
+    // * The thresholds of the cluster, excluding the thread if it does not count itself.
+    pub fn get_counting_thresholds(&self, id: u32) -> Thresholds {
+        self.quorum_config.get_self_counting().apply(id, &self.thresholds)
+    }
+
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
+
+    pub fn get_buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_duplicate_input_policy(&self) -> DuplicateInputPolicy {
+        self.duplicate_input_policy
+    }
+
+    pub fn get_network_emulation(&self) -> Option<&NetworkEmulation> {
+        self.network_emulation.as_ref()
+    }
+
+    // # Method Description:
+    // This method provides the global stabilization time of the emulated network (see `Stabilization`).
+    //
+    // # Returns:
+    // * The GST, or `None` if the network is not emulated as partially synchronous.
+    pub fn get_gst(&self) -> Option<Duration> {
+        self.network_emulation.as_ref().and_then(|network_emulation| network_emulation.get_stabilization()).map(|stabilization| stabilization.get_gst())
+    }
+
+    pub fn get_fault_script(&self) -> &FaultScript {
+        &self.fault_script
+    }
+
+    pub fn get_value_ordering(&self) -> ValueOrdering {
+        self.value_ordering
+    }
+
+    pub fn get_round_values(&self) -> RoundValues {
+        self.round_values
+    }
+
+    pub fn get_handle_mode(&self) -> HandleMode {
+        self.handle_mode
+    }
+
+    pub fn get_decode_policy(&self) -> DecodePolicy {
+        self.decode_policy
+    }
+
+    pub fn get_aggregation_depth(&self) -> u32 {
+        self.aggregation_depth
+    }
+
+    pub fn get_dissemination(&self) -> Dissemination {
+        self.dissemination
+    }
+
+    pub fn get_protocol_dissemination(&self) -> &BTreeMap<String, Dissemination> {
+        &self.protocol_dissemination
+    }
+
+    pub fn get_storage_backend(&self) -> &StorageBackend {
+        &self.storage_backend
+    }
+
+    pub fn get_journaling(&self) -> bool {
+        self.journaling
+    }
+
+    pub fn get_repeated_collect_policy(&self) -> RepeatedCollectPolicy {
+        self.repeated_collect_policy
+    }
+
+    pub fn get_authentication(&self) -> Authentication {
+        self.authentication
+    }
+
+    pub fn get_deliver_to_self(&self) -> bool {
+        self.deliver_to_self
+    }
+
+    pub fn get_layers(&self) -> &Vec<Layer> {
+        &self.layers
+    }
+
+    pub fn get_max_payload_size(&self) -> Option<usize> {
+        self.max_payload_size
+    }
+
+    pub fn get_membership(&self) -> Option<&MembershipCertificate> {
+        self.membership.as_ref()
+    }
+
+    pub fn get_identity_file(&self) -> Option<&Path> {
+        self.identity_file.as_deref()
+    }
+
+    pub fn get_retention_policy(&self) -> RetentionPolicy {
+        self.retention_policy
+    }
+
+    pub fn get_lane_scaling(&self) -> LaneScaling {
+        self.lane_scaling
+    }
+
+    pub fn get_trace_sampling(&self) -> TraceSampling {
+        self.trace_sampling
+    }
+
+    pub fn get_trace_sampler(&self) -> TraceSampler {
+        TraceSampler::new(self.trace_sampling, self.seed)
+    }
+
+    pub fn get_digest_gossip(&self) -> DigestGossip {
+        self.digest_gossip
+    }
+
+    pub fn get_frame_statistics(&self) -> bool {
+        self.frame_statistics
+    }
+
+    pub fn get_demo_pacing(&self) -> Option<DemoPacing> {
+        self.demo_pacing
+    }
+
+    pub fn get_vector_clocks(&self) -> bool {
+        self.vector_clocks
+    }
+
+    pub fn get_fast_path(&self) -> Option<Duration> {
+        self.fast_path
+    }
+
+    pub fn get_leader_election(&self) -> LeaderElection {
+        self.leader_election
+    }
+
+    pub fn get_pacemaker(&self) -> Option<Duration> {
+        self.pacemaker
+    }
+
+    pub fn get_signal_batching(&self) -> Option<SignalBatching> {
+        self.signal_batching
+    }
+
+    pub fn get_queue_eviction(&self) -> QueueEviction {
+        self.queue_eviction
+    }
+
+    pub fn get_leader_schedule(&self) -> LeaderSchedule {
+        LeaderSchedule::new(self.leader_election, self.thread_count, self.seed)
+    }
+
+    // # Method Description:
+    // This method checks whether a thread may take part in the cluster.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    //
+    // # Returns:
+    // * `true` if the thread is a member of the configured membership, or if no membership is configured.
+    pub fn is_member(&self, id: u32) -> bool {
+        self.membership.as_ref().is_none_or(|membership| membership.is_member(id))
+    }
+
+    // # Method Description:
+    // This method opens the storage of a thread with the configured backend.
+    //
+    // # Parameters:
+    // * id - The ID of the thread owning the storage.
+    //
+    // # Returns:
+    // * The storage, or a description of why it could not be opened.
+    pub fn open_storage(&self, id: u32) -> Result<Arc<dyn Storage>, String> {
+        self.storage_backend.open(id)
+    }
+
+    // # Method Description:
+    // This method checks whether threads may join and leave a cluster of this configuration at runtime
+    // (see `ReliableHub::join`). Membership changes only reach the raw channels of the threads, and epochs
+    // derive unweighted thresholds from the number of members, so they cannot be combined with anything
+    // wrapping or sizing the links or the quorums on the thread count the hub was built with.
+    //
+    // # Returns:
+    // * `Ok(())` if the membership may change, or a description of the setting preventing it.
+    pub fn check_dynamic_membership(&self) -> Result<(), String> {
+        if self.network_emulation.is_some() {
+            return Err(String::from("the network is emulated"))
+        }
+        if !self.layers.is_empty() {
+            return Err(String::from("frames go through middleware layers"))
+        }
+        if (0..self.thread_count).any(|id| self.fault_script.get_outage(id).is_some()) {
+            return Err(String::from("outages are scripted"))
+        }
+        if std::iter::once(&self.dissemination).chain(self.protocol_dissemination.values()).any(|mode| *mode != Dissemination::Direct) {
+            return Err(String::from("signals are disseminated along a spanning tree or by gossip"))
+        }
+        if self.authentication != Authentication::Unsigned {
+            return Err(String::from("signals are signed"))
+        }
+        if self.membership.is_some() {
+            return Err(String::from("the membership is signed"))
+        }
+        if self.thresholds.is_weighted() {
+            return Err(String::from("quorums are weighted"))
+        }
+        Ok(())
+    }
+
+    // # Method Description:
+    // This method resizes the configuration after a membership change, for the communicators created
+    // after it.
+    //
+    // # Parameters:
+    // * thread_count - The number of threads that ever joined the cluster.
+    // * thresholds - The thresholds of the current epoch.
+    //
+    // # Returns:
+    // * The updated configuration.
+    pub(crate) fn with_epoch(mut self, thread_count: u32, thresholds: Thresholds) -> Self {
+        self.thread_count = thread_count;
+        self.thresholds = thresholds;
+        self
+    }
+
+    // # Method Description:
+    // This method provides the transmitters a thread uses to reach every thread of the cluster.
+    // Without network emulation these are the raw transmitters; otherwise, frames to other threads
+    // go through emulated links, paced beforehand in demo mode (see `DemoPacing`). With middleware layers,
+    // every frame goes through the layers first, and with an `Outage` of the thread, through its outage gate
+    // before anything else.
+    //
+    // # Parameters:
+    // * id - The ID of the thread the transmitters are handed to.
+    // * transmitters - The raw transmitters of every thread, indexed by thread ID.
+    //
+    // # Returns:
+    // * A vector of transmitters, indexed by thread ID.
+    pub fn link_transmitters(&self, id: u32, transmitters: &[Sender<Bytes>]) -> Vec<Sender<Bytes>> {
+        let transmitters = match &self.demo_pacing {
+            Some(demo_pacing) => demo_pacing.pace_transmitters(transmitters.to_vec(), self.buffer_size),
+            None => transmitters.to_vec(),
+        };
+        let links = match &self.network_emulation {
+            Some(network_emulation) => network_emulation.emulate_links(id, &transmitters, self.buffer_size, self.seed),
+            None => transmitters,
+        };
+        let links = MiddlewareChain::from_config(self).wrap_transmitters(links, self.buffer_size);
+        match self.fault_script.get_outage(id) {
+            Some(outage) => outage.gate_transmitters(links, self.buffer_size),
+            None => links,
+        }
+    }
+
+    // # Method Description:
+    // This method provides the receiver a thread's frames are received from. Without an `Outage` of the
+    // thread, this is the raw receiver; otherwise, frames go through its outage gate.
+    //
+    // # Parameters:
+    // * id - The ID of the thread the receiver is handed to.
+    // * receiver - The raw receiver of the thread's channel.
+    //
+    // # Returns:
+    // * The receiver of the thread.
+    pub fn link_receiver(&self, id: u32, receiver: Receiver<Bytes>) -> Receiver<Bytes> {
+        match self.fault_script.get_outage(id) {
+            Some(outage) => outage.gate_receiver(id, receiver, self.codec, self.buffer_size),
+            None => receiver,
+        }
+    }
+
+    // # Method Description:
+    // This method creates a seeded RNG for a single randomized component of a single thread.
+    // Streams are derived deterministically from the experiment seed, the thread ID, and the
+    // component name, so that two components never share a stream and re-running an experiment
+    // with the same seed reproduces every random decision.
+    //
+    // # Parameters:
+    // * id - The ID of the thread that owns the component.
+    // * component - A name identifying the randomized component (e.g. "gossip").
+    //
+    // # Returns:
+    // * A `StdRng` seeded for the given thread and component.
+    pub fn create_rng(&self, id: u32, component: &str) -> StdRng {
+        StdRng::seed_from_u64(derive_seed(self.seed, id, component))
+    }
+}
+
+impl JsonConversion<ClusterConfig> for ClusterConfig {}
+
+// # Struct Description:
+// This struct holds the thresholds every handle of the cluster uses for its phase transitions. They are
+// derived from a `QuorumConfig`, by default `QuorumConfig::new()`. With weighted quorums, thresholds are
+// weights rather than thread counts, and every thread counts for its weight.
+//
+// # Fields:
+// * faulty_threads - The maximum number of tolerated faulty threads (or weight) `t`, i.e. `(n - 1) / 3` by default.
+// * validity_threshold - The quorum required to advance a phase, i.e. `n - t + 1` by default.
+// * agreement_threshold - The number of matching reports guaranteeing one is correct, i.e. `t + 1`.
+// * weights - The weight of every thread, indexed by thread ID, or empty if every thread counts once.
+// * excluded - The thread whose own signals count for nothing, in the thresholds a thread counts with under
+//   `SelfCounting::Exclude` (see `excluding`); it is local to the thread, and never serialized.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Thresholds {
+    faulty_threads: u64,
+    validity_threshold: u64,
+    agreement_threshold: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    weights: Vec<u64>,
+    #[serde(skip)]
+    excluded: Option<u32>,
+}
+
+impl Thresholds {
+    pub fn new(thread_count: u32) -> Self {
+        let faulty_threads = thread_count.saturating_sub(1) as u64 / 3;
+        let validity_threshold = thread_count as u64 - faulty_threads + 1;
+        let agreement_threshold = faulty_threads + 1;
+        Self {
+            faulty_threads,
+            validity_threshold,
+            agreement_threshold,
+            weights: vec![],
+            excluded: None,
+        }
+    }
+
+    pub fn get_faulty_threads(&self) -> u64 {
+        self.faulty_threads
+    }
+
+    pub fn get_validity_threshold(&self) -> u64 {
+        self.validity_threshold
+    }
+
+    pub fn get_agreement_threshold(&self) -> u64 {
+        self.agreement_threshold
+    }
+
+    pub fn get_weights(&self) -> &Vec<u64> {
+        &self.weights
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        !self.weights.is_empty()
+    }
+
+    // # Method Description:
+    // This method provides how much a thread counts towards a quorum.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    //
+    // # Returns:
+    // * The weight of the thread, 1 without weighted quorums, and 0 for a thread outside the cluster or
+    //   excluded from the count.
+    pub fn get_weight(&self, id: u32) -> u64 {
+        if self.excluded == Some(id) {
+            return 0
+        }
+        if self.weights.is_empty() {
+            return 1
+        }
+        self.weights.get(id as usize).copied().unwrap_or(0)
+    }
+
+    // # Method Description:
+    // This method provides how much a set of distinct threads counts towards a quorum, e.g. the creators of
+    // the witnesses of a proof.
+    //
+    // # Parameters:
+    // * ids - The IDs of the threads.
+    //
+    // # Returns:
+    // * The sum of their weights, i.e. their number without weighted quorums.
+    pub fn get_combined_weight(&self, ids: &HashSet<u32>) -> u64 {
+        ids.iter().map(|id| self.get_weight(*id)).sum()
+    }
+
+    // # Method Description:
+    // This method provides the thresholds a thread counts with when its own signals are not counted (see
+    // `SelfCounting::Exclude`): the thread weighs nothing, and the validity threshold is lowered by its
+    // weight, so that its quorums need as many other threads as before. The agreement threshold is kept,
+    // since it must be reached by other threads for one of them to be correct.
+    //
+    // # Parameters:
+    // * id - The ID of the counting thread.
+    //
+    // # Returns:
+    // * The thresholds of the thread.
+    pub fn excluding(&self, id: u32) -> Self {
+        let mut thresholds = self.clone();
+        thresholds.validity_threshold = self.validity_threshold.saturating_sub(self.get_weight(id));
+        thresholds.excluded = Some(id);
+        thresholds
+    }
+
+    // # Method Description:
+    // This method provides the number of witnesses of an aggregation level needed to complete the level
+    // (level 1: witnesses, level 2: aggregated witnesses, level `l`: level-`l` aggregated witnesses).
+    // Every level uses the validity threshold, so that the quorums of two threads at the same level
+    // always share a correct thread.
+    //
+    // # Parameters:
+    // * level - The aggregation level, starting at 1.
+    //
+    // # Returns:
+    // * The quorum of the level.
+    pub fn get_level_threshold(&self, _level: u32) -> u64 {
+        self.validity_threshold
+    }
+}
+
+// # Enum Description:
+// This enum represents the formula of the validity threshold, the quorum needed to echo, vote, deliver,
+// report, or trust.
+//
+// # Variants:
+// * NMinusFPlusOne - `n - f + 1`, the historical quorum of this crate, one more than Bracha's.
+// * NMinusF - `n - f`, Bracha's quorum, which a cluster can still reach with `f` threads down.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum QuorumFormula {
+    #[default]
+    NMinusFPlusOne,
+    NMinusF,
+}
+
+// # Enum Description:
+// This enum represents whether a thread counts its own signals towards its quorums. A thread receives its
+// own Input, Echo, and Vote signals, values, and reports like those of any other thread, so by default it
+// counts itself, as the `n - f + 1` quorum of this crate assumes. Specifications counting the other threads
+// only are run with `Exclude`, under which the thread's own signals still run the protocol (e.g. the
+// thread's value is still collected in its rounds) but count for nothing, and its validity threshold is
+// lowered by its weight (see `Thresholds::excluding`). It applies to the reliable, witness, and barycentric
+// handles; consistent broadcast and aggregated witness rounds, whose certificates and proofs are checked by
+// other threads against the thresholds of the cluster, always count the thread.
+//
+// # Variants:
+// * Include - A thread counts its own signals, as received back from the network.
+// * Exclude - A thread counts the signals of the other threads only, against thresholds lowered by its weight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum SelfCounting {
+    #[default]
+    Include,
+    Exclude,
+}
+
+impl SelfCounting {
+    // # Method Description:
+    // This method provides the thresholds a thread counts its signals with.
+    //
+    // # Parameters:
+    // * id - The ID of the counting thread.
+    // * thresholds - The thresholds of the cluster (or of the epoch the signals belong to).
+    //
+    // # Returns:
+    // * The thresholds as given under `Include`, or `Thresholds::excluding` the thread under `Exclude`.
+    pub fn apply(&self, id: u32, thresholds: &Thresholds) -> Thresholds {
+        match self {
+            SelfCounting::Include => thresholds.clone(),
+            SelfCounting::Exclude => thresholds.excluding(id),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct describes how the thresholds of a cluster are derived from its size, so that experiments can
+// override the number of tolerated faults, switch to Bracha's `n - f` quorums, or weigh threads unequally.
+// With weights, `n` is the total weight, `f` a weight, and every quorum a weight (see `Thresholds::get_weight`).
+//
+// # Fields:
+// * faulty_threads - The number of tolerated faulty threads (or weight), if overridden; `(n - 1) / 3` otherwise.
+// * formula - The formula of the validity threshold.
+// * weights - The weight of every thread, indexed by thread ID, if quorums are weighted.
+// * self_counting - Whether a thread counts its own signals towards its quorums.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct QuorumConfig {
+    faulty_threads: Option<u64>,
+    formula: QuorumFormula,
+    weights: Option<Vec<u64>>,
+    #[serde(default)]
+    self_counting: SelfCounting,
+}
+
+impl QuorumConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_faulty_threads(mut self, faulty_threads: u64) -> Self {
+        self.faulty_threads = Some(faulty_threads);
+        self
+    }
+
+    pub fn with_formula(mut self, formula: QuorumFormula) -> Self {
+        self.formula = formula;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: Vec<u64>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    pub fn with_self_counting(mut self, self_counting: SelfCounting) -> Self {
+        self.self_counting = self_counting;
+        self
+    }
+
+    pub fn get_faulty_threads(&self) -> Option<u64> {
+        self.faulty_threads
+    }
+
+    pub fn get_formula(&self) -> QuorumFormula {
+        self.formula
+    }
+
+    pub fn get_weights(&self) -> Option<&Vec<u64>> {
+        self.weights.as_ref()
+    }
+
+    pub fn get_self_counting(&self) -> SelfCounting {
+        self.self_counting
+    }
+
+    // # Method Description:
+    // This method derives the thresholds of a cluster.
+    //
+    // # Parameters:
+    // * thread_count - The number of threads in the cluster.
+    //
+    // # Returns:
+    // * The thresholds, or a description of why the configuration cannot be satisfied: weights that do not
+    //   cover every thread or whose total does not fit a `u64`, no thread or weight at all, or a validity
+    //   threshold above the size (or total weight) of the cluster.
+    pub fn thresholds(&self, thread_count: u32) -> Result<Thresholds, String> {
+        let total = match &self.weights {
+            Some(weights) if weights.len() != thread_count as usize => {
+                return Err(format!("{} weights are given for {} threads", weights.len(), thread_count))
+            },
+            Some(weights) => weights.iter().try_fold(0u64, |total, weight| total.checked_add(*weight))
+                .ok_or(format!("the total weight of {weights:?} exceeds {}", u64::MAX))?,
+            None => thread_count as u64,
+        };
+        let faulty_threads = self.faulty_threads.unwrap_or(total.saturating_sub(1) / 3);
+        if faulty_threads >= total {
+            return Err(format!("{faulty_threads} faulty threads cannot be tolerated out of {total}"))
+        }
+        let validity_threshold = match self.formula {
+            QuorumFormula::NMinusFPlusOne => total - faulty_threads + 1,
+            QuorumFormula::NMinusF => total - faulty_threads,
+        };
+        if validity_threshold > total {
+            return Err(format!("the validity threshold {validity_threshold} exceeds the {total} threads (or weight) of the cluster"))
+        }
+        Ok(Thresholds {
+            faulty_threads,
+            validity_threshold,
+            agreement_threshold: faulty_threads + 1,
+            weights: self.weights.clone().unwrap_or_default(),
+            excluded: None,
+        })
+    }
+}
+
+// # Function Description:
+// This function derives a sub-seed from the experiment seed, a thread ID, and a component name.
+// A fixed FNV-1a hash followed by a SplitMix64 finalizer is used instead of the standard library
+// hasher, so derived seeds remain stable across Rust versions and platforms.
+//
+// # Parameters:
+// * seed - The experiment seed.
+// * id - The ID of the thread that owns the component.
+// * component - A name identifying the randomized component.
+//
+// # Returns:
+// * A `u64` seed for the component's RNG.
+pub(crate) fn derive_seed(seed: u64, id: u32, component: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in component.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    let mut mixed = seed ^ hash ^ ((id as u64) << 32);
+    mixed = mixed.wrapping_add(0x9e3779b97f4a7c15);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d049bb133111eb);
+    mixed ^ (mixed >> 31)
+}
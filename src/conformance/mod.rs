@@ -0,0 +1,277 @@
+use std::time::Duration;
+use bytes::Bytes;
+use tokio::{sync::mpsc::{self, Receiver, Sender}, task::JoinHandle, time};
+
+use crate::basic::BasicCommunication;
+use crate::reliable::{ReliableCommunication, ReliableHub};
+use crate::witness::{WitnessCommunication, WitnessHub};
+use crate::config::ClusterConfig;
+use crate::round::{Instance, Round};
+
+// # Enum Description:
+// This enum represents the protocol implementations covered by the conformance suite.
+//
+// # Variants:
+// * Reliable - Quorum-based reliable broadcast: thread 0 broadcasts, every correct thread must deliver.
+// * Witness - Witness-verified broadcast: every correct thread broadcasts a value, and must collect the round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformanceProtocol {
+    Reliable,
+    Witness,
+}
+
+// # Enum Description:
+// This enum represents the expected outcome of a conformance case.
+//
+// # Variants:
+// * Deliver - Every correct thread delivers before the timeout.
+// * Stall - No correct thread delivers before the timeout, as the remaining threads cannot form a quorum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expectation {
+    Deliver,
+    Stall,
+}
+
+// # Struct Description:
+// This struct describes a single conformance case: a protocol run over a cluster in which the last
+// `faulty_threads` threads are crashed from the start (they never start their handles), together
+// with the outcome the configured thresholds imply.
+//
+// # Fields:
+// * name - A short description of the case.
+// * protocol - The protocol implementation under test.
+// * config - The configuration of the cluster.
+// * faulty_threads - The number of crashed threads.
+// * expectation - The expected outcome.
+#[derive(Debug, Clone)]
+pub struct ConformanceCase {
+    name: String,
+    protocol: ConformanceProtocol,
+    config: ClusterConfig,
+    faulty_threads: u32,
+    expectation: Expectation,
+}
+
+impl ConformanceCase {
+    pub fn new(name: String, protocol: ConformanceProtocol, config: ClusterConfig, faulty_threads: u32, expectation: Expectation) -> Self {
+        Self {
+            name,
+            protocol,
+            config,
+            faulty_threads,
+            expectation
+        }
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_protocol(&self) -> ConformanceProtocol {
+        self.protocol
+    }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    pub fn get_faulty_threads(&self) -> u32 {
+        self.faulty_threads
+    }
+
+    pub fn get_expectation(&self) -> Expectation {
+        self.expectation
+    }
+}
+
+// # Struct Description:
+// This struct holds the outcome of a conformance case.
+//
+// # Fields:
+// * case - The case that was run.
+// * delivered - The number of correct threads that delivered before the timeout.
+// * passed - Whether the outcome matched the expectation.
+#[derive(Debug, Clone)]
+pub struct ConformanceResult {
+    case: ConformanceCase,
+    delivered: u32,
+    passed: bool,
+}
+
+impl ConformanceResult {
+    pub fn get_case(&self) -> &ConformanceCase {
+        &self.case
+    }
+
+    pub fn get_delivered(&self) -> u32 {
+        self.delivered
+    }
+
+    pub fn get_passed(&self) -> bool {
+        self.passed
+    }
+}
+
+// # Function Description:
+// This function computes the number of crashed threads the configured thresholds tolerate: a quorum
+// of `validity_threshold` signals can only be formed while that many threads remain correct. With
+// the textbook quorum of `n - t`, this is exactly `t`.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The largest number of crashed threads with which correct threads still deliver.
+pub fn tolerated_crash_faults(config: &ClusterConfig) -> u32 {
+    u64::from(config.get_thread_count()).saturating_sub(config.get_thresholds().get_validity_threshold()) as u32
+}
+
+// # Function Description:
+// This function builds the conformance cases documenting the fault-tolerance boundary of a protocol
+// implementation on a cluster: a fault-free run, a run with exactly `t` crashed threads, a run with
+// the largest number of crashed threads the thresholds tolerate, and a run with one more crashed
+// thread than tolerated, which must stall. Runs with the same number of crashed threads are only
+// built once. Crash faults only exercise termination; breaking agreement requires Byzantine
+// (equivocating) threads.
+//
+// # Parameters:
+// * protocol - The protocol implementation under test.
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The conformance cases, to be run with `run_case`.
+pub fn conformance_suite(protocol: ConformanceProtocol, config: &ClusterConfig) -> Vec<ConformanceCase> {
+    let thread_count = config.get_thread_count();
+    let faulty_threads = config.get_thresholds().get_faulty_threads() as u32;
+    let tolerated = tolerated_crash_faults(config);
+    let expect = |crashed: u32| if crashed <= tolerated { Expectation::Deliver } else { Expectation::Stall };
+
+    let mut cases = vec![
+        ConformanceCase::new(format!("n = {thread_count}, no faulty threads"), protocol, config.clone(), 0, expect(0)),
+    ];
+    let mut push_case = |name: String, crashed: u32| {
+        if cases.iter().all(|case| case.get_faulty_threads() != crashed) {
+            cases.push(ConformanceCase::new(name, protocol, config.clone(), crashed, expect(crashed)));
+        }
+    };
+    push_case(format!("n = {thread_count}, exactly t = {faulty_threads} faulty threads"), faulty_threads);
+    push_case(format!("n = {thread_count}, {tolerated} faulty threads (tolerated by the quorum)"), tolerated);
+    if tolerated < thread_count {
+        push_case(format!("n = {thread_count}, {} faulty threads (one more than tolerated)", tolerated + 1), tolerated + 1);
+    }
+    cases
+}
+
+// # Function Description:
+// This function runs a conformance case on a fresh cluster. Crashed threads keep their communicators
+// (so frames addressed to them are still accepted) but never start their handles.
+//
+// # Parameters:
+// * case - The case to run.
+// * timeout - How long correct threads wait to deliver before the run is considered stalled.
+//
+// # Returns:
+// * The `ConformanceResult` of the case.
+pub async fn run_case(case: &ConformanceCase, timeout: Duration) -> ConformanceResult {
+    let config = case.get_config().clone();
+    let thread_count = config.get_thread_count();
+    let correct_threads = thread_count.saturating_sub(case.get_faulty_threads());
+    let (transmitters, receivers) = create_channels(&config);
+
+    let mut handles: Vec<JoinHandle<bool>> = vec![];
+    match case.get_protocol() {
+        ConformanceProtocol::Reliable => {
+            let mut hub: ReliableHub<String> = ReliableHub::with_config(transmitters, receivers, config);
+            let mut crashed = vec![];
+            for id in 0..thread_count {
+                let mut communicator = hub.create_reliable_communicator();
+                if id >= correct_threads {
+                    crashed.push(communicator);
+                    continue
+                }
+                handles.push(tokio::spawn(async move {
+                    let reliable_handle = communicator.initialize_reliable_handle();
+                    if *communicator.get_id() == 0 {
+                        communicator.reliable_broadcast(String::from("conformance value"), Instance(0), Round(0)).await;
+                    }
+                    let delivered = time::timeout(timeout, communicator.reliable_recv(Some(0), Instance(0), Round(0))).await.is_ok();
+                    communicator.terminate_reliable_handle(reliable_handle);
+                    delivered
+                }));
+            }
+            return collect_result(case, handles, crashed).await
+        },
+        ConformanceProtocol::Witness => {
+            let mut hub: WitnessHub<String> = WitnessHub::with_config(transmitters, receivers, config);
+            let mut crashed = vec![];
+            for id in 0..thread_count {
+                let mut communicator = hub.create_witness_communicator();
+                if id >= correct_threads {
+                    crashed.push(communicator);
+                    continue
+                }
+                handles.push(tokio::spawn(async move {
+                    let reliable_handle = communicator.initialize_reliable_handle();
+                    let witness_handle = communicator.initialize_witness_handle();
+                    let value = format!("conformance value by {}", communicator.get_id());
+                    communicator.witness_broadcast(value, Round(0)).await;
+                    let delivered = time::timeout(timeout, communicator.witness_collect(Round(0))).await.is_ok();
+                    communicator.terminate_witness_handle(witness_handle);
+                    communicator.terminate_reliable_handle(reliable_handle);
+                    delivered
+                }));
+            }
+            return collect_result(case, handles, crashed).await
+        },
+    }
+}
+
+// # Function Description:
+// This function waits for the correct threads of a case and checks their outcome against the expectation.
+//
+// # Parameters:
+// * case - The case that was run.
+// * handles - The tasks of the correct threads, each resolving to whether the thread delivered.
+// * crashed - The communicators of the crashed threads, kept alive until the correct threads finish.
+//
+// # Returns:
+// * The `ConformanceResult` of the case.
+async fn collect_result<C>(case: &ConformanceCase, handles: Vec<JoinHandle<bool>>, crashed: Vec<C>) -> ConformanceResult {
+    let correct_threads = handles.len() as u32;
+    let mut delivered = 0;
+    for handle in handles {
+        if handle.await.unwrap_or(false) {
+            delivered += 1;
+        }
+    }
+    drop(crashed);
+
+    let passed = match case.get_expectation() {
+        Expectation::Deliver => delivered == correct_threads,
+        Expectation::Stall => delivered == 0,
+    };
+    ConformanceResult {
+        case: case.clone(),
+        delivered,
+        passed
+    }
+}
+
+// # Function Description:
+// This function creates the channel of every thread of a conformance cluster.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The transmitters and receivers of every thread, indexed by thread ID.
+fn create_channels(config: &ClusterConfig) -> (Vec<Sender<Bytes>>, Vec<Receiver<Bytes>>) {
+    let mut transmitters = vec![];
+    let mut receivers = vec![];
+    for _ in 0..config.get_thread_count() {
+        let (tx, rx) = mpsc::channel(config.get_buffer_size());
+        transmitters.push(tx);
+        receivers.push(rx);
+    }
+    (transmitters, receivers)
+}
@@ -0,0 +1,270 @@
+use std::{collections::BTreeSet, fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use async_trait::async_trait;
+
+use crate::basic::{Message, RecvObject};
+use crate::config::Thresholds;
+use crate::extension::{InstanceProcessor, Processing};
+use crate::logging::log;
+use crate::reliable::{ObjectContent, ReliableCommunication, ReliableInstanceCount, ReliableInstanceMonitor, Signal, SignalChannels, SignalFrame, SignalType};
+use crate::round::{Instance, Round};
+use crate::signing::SignalSigner;
+
+// The protocol information of the messages of consistent broadcast.
+pub const CONSISTENT: &str = "consistent";
+
+// # Trait Description:
+// This trait extends `ReliableCommunication` with consistent broadcast, whose instances run on the reliable
+// handle. It is implemented by every reliable communicator.
+#[async_trait]
+pub trait ConsistentCommunication<T>: ReliableCommunication<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // This method broadcasts a message with consistent broadcast (signed echo broadcast), a cheaper
+    // alternative to `reliable_broadcast` for the instances that only need consistency: every thread echoes
+    // the message to this thread only, and this thread sends every thread a Vote carrying the endorsements
+    // of an Echo quorum, i.e. `3n` signals instead of `n + 2n²`. No two threads deliver different messages
+    // for the instance, but threads may not all deliver it if this thread is faulty. Under
+    // `Authentication::Ed25519`, endorsements are the signatures of the Echo signals, so that consistency
+    // also holds against Byzantine threads. The message is received with `consistent_recv`.
+    //
+    // # Parameters:
+    // * message - The content of the message to broadcast.
+    // * instance_number - The consensus instance number associated with the message.
+    // * round_number - The round number within the consensus instance.
+    //
+    // # Returns:
+    // * A future that asynchronously broadcasts the signal to all registered signal receivers.
+    fn consistent_broadcast(&mut self, message: T, instance_number: Instance, round_number: Round) -> impl Future<Output = ()> {
+        let sent_message = Message::new(String::from(CONSISTENT), *self.get_id(), message, None, Some(instance_number), round_number)
+            .with_epoch(self.get_signal_channels().get_epoch());
+        let sent_message = self.get_queues().stamp(sent_message);
+        let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
+        self.get_signal_channels().broadcast_signal(input)
+    }
+
+    // # Method Description:
+    // This method retrieves a message delivered by consistent broadcast, blocking until it is delivered.
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing a specific sender's thread ID.
+    // * instance_number - The consensus instance number associated with the message.
+    // * round_number - The round number within the consensus instance.
+    //
+    // # Returns:
+    // * The `Message`.
+    async fn consistent_recv(&mut self, id: Option<u32>, instance_number: Instance, round_number: Round) -> Message<T> {
+        match self.get_queues().basic_recv(id, String::from(CONSISTENT), Some(instance_number), round_number).await {
+            RecvObject::Message(message) => message,
+            RecvObject::Collection(_) => panic!("Error: retreived Vec<Message> instead of Message"),
+        }
+    }
+}
+
+impl<T, C> ConsistentCommunication<T> for C
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T>,
+{
+}
+
+// # Function Description:
+// This function provides the number of Echo signals (or their weight) the sender of a consistent broadcast
+// must gather before it sends its Vote, i.e. `⌈(n + t + 1) / 2⌉`: any two such quorums share a correct
+// thread, which echoes a single content per instance, so no two threads deliver different contents.
+//
+// # Parameters:
+// * thresholds - The thresholds of the cluster.
+// * cluster_weight - The combined weight of every thread of the cluster, i.e. `n` without weighted quorums.
+//
+// # Returns:
+// * The quorum of Echo signals.
+pub fn consistent_threshold(thresholds: &Thresholds, cluster_weight: u64) -> u64 {
+    (cluster_weight + thresholds.get_faulty_threads() + 2) / 2
+}
+
+// # Struct Description:
+// This struct is the endorsement of a consistent broadcast by a thread: the signature of the Echo signal
+// the thread sent its sender. The sender attaches the endorsements of an Echo quorum to its Vote, so that
+// every thread can check, on the Vote alone, that a quorum echoed its content.
+//
+// # Fields:
+// * echoer - The ID of the thread that sent the Echo signal.
+// * signature - The hex-encoded signature of the Echo signal, under `Authentication::Ed25519`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Endorsement {
+    echoer: u32,
+    signature: Option<String>,
+}
+
+impl Endorsement {
+    // # Function Description:
+    // This function creates the endorsement of an Echo signal received by the sender of a consistent broadcast.
+    //
+    // # Parameters:
+    // * echo - The Echo signal.
+    //
+    // # Returns:
+    // * The endorsement, `None` if the signal does not name the thread that sent it.
+    pub fn from_echo<T>(echo: &Signal<T>) -> Option<Self>
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let echoer = echo.get_origin()?;
+        Some(Self { echoer, signature: echo.get_signature().cloned() })
+    }
+
+    pub fn get_echoer(&self) -> u32 {
+        self.echoer
+    }
+
+    pub fn get_signature(&self) -> Option<&String> {
+        self.signature.as_ref()
+    }
+
+    // # Method Description:
+    // This method rebuilds the Echo signal of the endorsement, as the echoer sent it.
+    //
+    // # Parameters:
+    // * vote - The Vote carrying the endorsement.
+    //
+    // # Returns:
+    // * The Echo signal, signed if the endorsement is.
+    fn rebuild_echo<T>(&self, vote: &Signal<T>) -> Signal<T>
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let echo = vote.answered_with(SignalType::Echo).with_origin(self.echoer);
+        match &self.signature {
+            Some(signature) => echo.with_signature(self.echoer, signature.clone()),
+            None => echo,
+        }
+    }
+}
+
+// # Function Description:
+// This function checks the Vote of a consistent broadcast before it is delivered: it must be sent by the
+// sender of its content, and carry the endorsements of distinct threads weighing at least the Echo quorum.
+// With a signer, every endorsement must be the valid signature of an Echo of the same content; without one,
+// endorsements are taken at face value, which only guards against crashed threads.
+//
+// # Parameters:
+// * vote - The Vote.
+// * thresholds - The thresholds of the cluster, giving the weight of every echoer.
+// * threshold - The Echo quorum (see `consistent_threshold`).
+// * signer - The signer of the receiving thread, holding the public key of every thread, if signals are signed.
+//
+// # Returns:
+// * `Ok(())`, or a description of the first problem found.
+pub fn verify_endorsements<T>(vote: &Signal<T>, thresholds: &Thresholds, threshold: u64, signer: Option<&SignalSigner>) -> Result<(), String>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    let sender = vote.get_content().get_id();
+    if vote.get_origin() != Some(sender) {
+        return Err(format!("the Vote of the broadcast of node {sender} was not sent by its sender"))
+    }
+    let mut echoers = BTreeSet::new();
+    for endorsement in vote.get_endorsements() {
+        if !echoers.insert(endorsement.echoer) {
+            return Err(format!("node {} endorsed the broadcast more than once", endorsement.echoer))
+        }
+        if let Some(signer) = signer && !endorsement.rebuild_echo(vote).is_authentic(signer) {
+            return Err(format!("the endorsement of node {} does not match the broadcast", endorsement.echoer))
+        }
+    }
+    let weight: u64 = echoers.iter().map(|echoer| thresholds.get_weight(*echoer)).sum();
+    if weight < threshold {
+        return Err(format!("the endorsements weigh {weight}, below the Echo quorum of {threshold}"))
+    }
+    Ok(())
+}
+
+// # Struct Description:
+// This struct is the processor of the consistent broadcasts (signed echo broadcast) of a reliable handle (see
+// `InstanceProcessor`). Threads echo the Input of the sender to the sender only; the sender votes once it
+// gathered an Echo quorum (see `consistent_threshold`), attaching the endorsements of the quorum to its Vote;
+// threads deliver the content of the Vote once its endorsements are checked (see `verify_endorsements`). No
+// two threads deliver different contents, but nothing is relayed on behalf of a faulty sender, so some
+// threads may deliver while others never do.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * thresholds - The thresholds of the cluster, giving the weight of every echoer.
+// * consistent_threshold - The number of Echo signals the sender of a consistent broadcast gathers before it votes.
+// * deliver_to_self - Whether the thread's own broadcasts are delivered to it.
+pub(crate) struct ConsistentProcessor {
+    thread_id: u32,
+    thresholds: Thresholds,
+    consistent_threshold: u64,
+    deliver_to_self: bool,
+}
+
+impl ConsistentProcessor {
+    pub(crate) fn new(thread_id: u32, thresholds: Thresholds, cluster_weight: u64, deliver_to_self: bool) -> Self {
+        Self {
+            thread_id,
+            consistent_threshold: consistent_threshold(&thresholds, cluster_weight),
+            thresholds,
+            deliver_to_self,
+        }
+    }
+}
+
+#[async_trait]
+impl<T> InstanceProcessor<T> for ConsistentProcessor
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    async fn process(&mut self, instance: &mut ReliableInstanceMonitor<T>, signal: &Signal<T>, received: Option<&SignalFrame>, signal_channels: &SignalChannels<T>) -> Processing {
+        let sender = signal.get_content().get_id();
+        match signal.get_signal() {
+            SignalType::Input => {
+                if !instance.state.echo {
+                    log!(debug, { node = self.thread_id, transition = "echo" }, "id {}, instance: {}, echoing to id: {sender}...", self.thread_id, signal.get_instance_number());
+                    let echo = signal.answered_with(SignalType::Echo);
+                    instance.sent.push(echo.clone());
+                    instance.state.echo = true;
+                    signal_channels.send_signal_to(&[sender], echo).await;
+                }
+            },
+            SignalType::Echo => {
+                // only the sender gathers the Echo signals of its broadcast, for the content it broadcast
+                let Some(origin) = signal.get_origin() else {
+                    return Processing::Ignore
+                };
+                let digest = signal.get_content().get_quorum_digest();
+                if sender != self.thread_id || instance.input.as_ref() != Some(signal.get_content()) || !instance.senders.insert((SignalType::Echo, origin, digest.clone())) {
+                    return Processing::Ignore
+                }
+                let count = instance.counts.entry(digest).or_insert_with(ReliableInstanceCount::new);
+                count.echo += self.thresholds.get_weight(origin);
+                instance.endorsements.extend(Endorsement::from_echo(signal));
+                if count.echo >= self.consistent_threshold && !instance.state.vote {
+                    log!(debug, { node = self.thread_id, transition = "vote" }, "id {}, instance: {}, voting...", self.thread_id, signal.get_instance_number());
+                    let vote = signal.answered_with(SignalType::Vote).with_endorsements(instance.endorsements.clone());
+                    instance.sent.push(vote.clone());
+                    instance.state.vote = true;
+                    signal_channels.broadcast_signal(vote).await;
+                }
+            },
+            SignalType::Vote => {
+                let digest = signal.get_content().get_quorum_digest();
+                if instance.state.deliver || signal.get_origin() != Some(sender) || !instance.senders.insert((SignalType::Vote, sender, digest.clone())) {
+                    return Processing::Ignore
+                }
+                signal_channels.relay_signal(signal, received).await;
+                if let Err(reason) = verify_endorsements(signal, &self.thresholds, self.consistent_threshold, signal_channels.get_signer()) {
+                    log!(warn, { node = self.thread_id }, "id: {}, instance: {}, discarding consistent broadcast: {reason}", self.thread_id, signal.get_instance_number());
+                    return Processing::Ignore
+                }
+                instance.counts.entry(digest).or_insert_with(ReliableInstanceCount::new).vote += self.thresholds.get_weight(sender);
+                instance.state.deliver = true;
+                return Processing::Deliver(sender != self.thread_id || self.deliver_to_self)
+            },
+        }
+        Processing::Ignore
+    }
+}
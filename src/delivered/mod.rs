@@ -0,0 +1,195 @@
+use std::{fmt::Debug, hash::Hash, time::Instant};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::basic::Message;
+use crate::round::{Instance, Round};
+
+// # Enum Description:
+// This enum represents what a protocol delivered: a single value, as returned by `basic_recv` and
+// `reliable_recv`, or the values collected for a round, as returned by the witness, aggregated witness,
+// and barycentric agreement collects.
+//
+// # Variants:
+// * Value - The delivered value.
+// * Collection - The collected messages, each with its own sender and metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveredPayload<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    Value(T),
+    Collection(Vec<Message<T>>),
+}
+
+// # Struct Description:
+// This struct records how a delivery was made, beyond what identifies it.
+//
+// # Fields:
+// * receiver - The ID of the thread that received the delivery.
+// * dimension - The dimension of the delivered message, if it has one.
+// * received_at - When the delivery was returned to the application, e.g. to measure latencies in a harness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryMetadata {
+    receiver: u32,
+    dimension: Option<u32>,
+    received_at: Instant,
+}
+
+impl DeliveryMetadata {
+    pub fn new(receiver: u32, dimension: Option<u32>) -> Self {
+        Self {
+            receiver,
+            dimension,
+            received_at: Instant::now(),
+        }
+    }
+
+    pub fn get_receiver(&self) -> u32 {
+        self.receiver
+    }
+
+    pub fn get_dimension(&self) -> Option<u32> {
+        self.dimension
+    }
+
+    pub fn get_received_at(&self) -> Instant {
+        self.received_at
+    }
+}
+
+// # Struct Description:
+// This struct is the envelope every protocol returns its deliveries in through the `*_delivered` calls
+// (e.g. `reliable_recv_delivered`, `witness_collect_delivered`), so that generic experiment harnesses treat
+// the deliveries of every protocol the same way instead of matching a `Message` against a `Vec<Message>`.
+// The accessors `get_message`, `get_values`, and `into_messages` give back the shape returned by the
+// protocol's own calls.
+//
+// # Fields:
+// * protocol - The protocol that delivered the payload.
+// * origin - The ID of the thread that sent a single value; `None` for a collection, whose messages come
+//   from several threads.
+// * instance - The instance number of the delivery, if the protocol numbers its instances.
+// * round - The round of the delivery.
+// * payload - The delivered value or collection.
+// * metadata - How the delivery was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivered<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    protocol: String,
+    origin: Option<u32>,
+    instance: Option<Instance>,
+    round: Round,
+    payload: DeliveredPayload<T>,
+    metadata: DeliveryMetadata,
+}
+
+impl<T> Delivered<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Function Description:
+    // This function wraps a single delivered message.
+    //
+    // # Parameters:
+    // * receiver - The ID of the thread that received the message.
+    // * message - The message.
+    //
+    // # Returns:
+    // * The delivery, whose origin is the sender of the message.
+    pub fn from_message(receiver: u32, message: Message<T>) -> Self {
+        let metadata = DeliveryMetadata::new(receiver, message.get_dimension());
+        Self {
+            protocol: message.get_protocol_information().clone(),
+            origin: Some(message.get_id()),
+            instance: message.get_instance_number(),
+            round: message.get_round_number(),
+            payload: DeliveredPayload::Value(message.get_message().clone()),
+            metadata,
+        }
+    }
+
+    // # Function Description:
+    // This function wraps the messages collected for a round.
+    //
+    // # Parameters:
+    // * receiver - The ID of the thread that collected the round.
+    // * protocol - The protocol of the collected round.
+    // * round - The collected round.
+    // * messages - The collected messages.
+    //
+    // # Returns:
+    // * The delivery, without origin or instance.
+    pub fn from_collection(receiver: u32, protocol: String, round: Round, messages: Vec<Message<T>>) -> Self {
+        Self {
+            protocol,
+            origin: None,
+            instance: None,
+            round,
+            payload: DeliveredPayload::Collection(messages),
+            metadata: DeliveryMetadata::new(receiver, None),
+        }
+    }
+
+    pub fn get_protocol(&self) -> &String {
+        &self.protocol
+    }
+
+    pub fn get_origin(&self) -> Option<u32> {
+        self.origin
+    }
+
+    pub fn get_instance(&self) -> Option<Instance> {
+        self.instance
+    }
+
+    pub fn get_round(&self) -> Round {
+        self.round
+    }
+
+    pub fn get_payload(&self) -> &DeliveredPayload<T> {
+        &self.payload
+    }
+
+    pub fn get_metadata(&self) -> &DeliveryMetadata {
+        &self.metadata
+    }
+
+    // # Method Description:
+    // This method returns the delivered value of a single delivery.
+    //
+    // # Returns:
+    // * The value, `None` for a collection.
+    pub fn get_message(&self) -> Option<&T> {
+        match &self.payload {
+            DeliveredPayload::Value(value) => Some(value),
+            DeliveredPayload::Collection(_) => None,
+        }
+    }
+
+    // # Method Description:
+    // This method returns every delivered value, whatever the shape of the delivery.
+    //
+    // # Returns:
+    // * The single value, or the values of the collection in collection order.
+    pub fn get_values(&self) -> Vec<&T> {
+        match &self.payload {
+            DeliveredPayload::Value(value) => vec![value],
+            DeliveredPayload::Collection(messages) => messages.iter().map(|message| message.get_message()).collect(),
+        }
+    }
+
+    // # Method Description:
+    // This method turns the delivery back into the messages the protocol's own calls return.
+    //
+    // # Returns:
+    // * The single delivered `Message`, or the collected `Message`s.
+    pub fn into_messages(self) -> Vec<Message<T>> {
+        match self.payload {
+            DeliveredPayload::Value(value) => vec![Message::new(self.protocol, self.origin.unwrap_or_default(), value,
+                self.metadata.dimension, self.instance, self.round)],
+            DeliveredPayload::Collection(messages) => messages,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::{fmt, time::Duration};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize};
+use tokio::{sync::{broadcast, mpsc::{self, Sender}}, time::Instant};
+
+use crate::json::JsonConversion;
+use crate::metrics::RoundStatus;
+use crate::multiplex::{Lane, untag_frame};
+use crate::round::Round;
+
+// The number of narrations kept for a subscriber that falls behind.
+const NARRATION_CAPACITY: usize = 1024;
+
+// # Struct Description:
+// This struct configures the demo mode of a cluster (see `ClusterConfig::with_demo_pacing`), in which the
+// protocols run slowly enough to be followed step by step, e.g. when teaching reliable broadcast and the
+// witness protocols. Every frame a thread sends is held for the delay of its lane before it leaves the
+// thread, so that every phase of a protocol (Input, Echo, Vote, values, reports) takes at least that long,
+// and the handles narrate the progress of every round (see `Narration`).
+//
+// # Fields:
+// * basic - The delay of the frames of the `Basic` lane (broadcast messages and witness values).
+// * signal - The delay of the frames of the `Signal` lane (Input, Echo, and Vote signals).
+// * report - The delay of the frames of the `Report` lane (witness reports and aggregated reports).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DemoPacing {
+    basic: Duration,
+    signal: Duration,
+    report: Duration,
+}
+
+impl DemoPacing {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            basic: step,
+            signal: step,
+            report: step
+        }
+    }
+
+    pub fn with_lane_delay(mut self, lane: Lane, delay: Duration) -> Self {
+        match lane {
+            Lane::Basic => self.basic = delay,
+            Lane::Signal => self.signal = delay,
+            Lane::Report => self.report = delay,
+        }
+        self
+    }
+
+    pub fn get_lane_delay(&self, lane: Lane) -> Duration {
+        match lane {
+            Lane::Basic => self.basic,
+            Lane::Signal => self.signal,
+            Lane::Report => self.report,
+        }
+    }
+
+    // # Method Description:
+    // This method paces the frames sent over transmitters: each transmitter is replaced by one whose frames
+    // are stamped when sent, and released by a forwarding task once the delay of their lane has elapsed, so
+    // that frames sent together are released together. Frames are released in the order they were sent, so
+    // a frame never overtakes an earlier frame to the same thread.
+    //
+    // # Parameters:
+    // * transmitters - The transmitters of a thread, indexed by thread ID.
+    // * buffer_size - The capacity of the channel in front of every forwarding task.
+    //
+    // # Returns:
+    // * The transmitters to hand to the thread, indexed by thread ID.
+    pub fn pace_transmitters(&self, transmitters: Vec<Sender<Bytes>>, buffer_size: usize) -> Vec<Sender<Bytes>> {
+        transmitters.into_iter().map(|transmitter| {
+            let (tx, mut rx) = mpsc::channel::<Bytes>(buffer_size);
+            let (held_tx, mut held_rx) = mpsc::unbounded_channel::<(Instant, Bytes)>();
+            let pacing = *self;
+            tokio::spawn(async move {
+                let mut last_release = Instant::now();
+                while let Some(frame) = rx.recv().await {
+                    let delay = untag_frame(&frame).map(|(lane, _)| pacing.get_lane_delay(lane)).unwrap_or(Duration::ZERO);
+                    last_release = last_release.max(Instant::now() + delay);
+                    if held_tx.send((last_release, frame)).is_err() {
+                        return
+                    }
+                }
+            });
+            tokio::spawn(async move {
+                while let Some((release, frame)) = held_rx.recv().await {
+                    tokio::time::sleep_until(release).await;
+                    if transmitter.send(frame).await.is_err() {
+                        return
+                    }
+                }
+            });
+            tx
+        }).collect()
+    }
+}
+
+// # Struct Description:
+// This struct is a human-readable account of a step of a round, published by the handles of a cluster in
+// demo mode whenever a round starts, reaches one of its quorums, or is delivered.
+//
+// # Fields:
+// * id - The ID of the thread that took the step.
+// * elapsed - The time since the thread's lanes were opened.
+// * protocol_information - The protocol of the round.
+// * instance - The instance within the round, for protocols with several instances per round.
+// * round_number - The round number.
+// * text - The account of the step, e.g. "reached its echoes quorum (3/3)".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Narration {
+    id: u32,
+    elapsed: Duration,
+    protocol_information: String,
+    instance: Option<String>,
+    round_number: Round,
+    text: String,
+}
+
+impl Narration {
+    pub fn new(id: u32, elapsed: Duration, round_status: &RoundStatus, text: String) -> Self {
+        Self {
+            id,
+            elapsed,
+            protocol_information: round_status.get_protocol_information().clone(),
+            instance: round_status.get_instance().cloned(),
+            round_number: round_status.get_round_number(),
+            text
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_instance(&self) -> Option<&String> {
+        self.instance.as_ref()
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_text(&self) -> &String {
+        &self.text
+    }
+}
+
+impl fmt::Display for Narration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>7.3}s] node {}: {} round {}", self.elapsed.as_secs_f64(), self.id, self.protocol_information, self.round_number)?;
+        if let Some(instance) = &self.instance {
+            write!(f, " (instance {instance})")?;
+        }
+        write!(f, " {}", self.text)
+    }
+}
+
+impl JsonConversion<Narration> for Narration {}
+
+// # Struct Description:
+// This struct narrates the rounds of a thread in demo mode. It is held by the thread's `Metrics`,
+// which hands it every round status its handles record, and publishes a `Narration` for every step between
+// two statuses of a round.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * started - The instant the narrator was created, from which narrations are timed.
+// * sender - The channel on which narrations are published.
+#[derive(Debug, Clone)]
+pub struct Narrator {
+    id: u32,
+    started: Instant,
+    sender: broadcast::Sender<Narration>,
+}
+
+impl Narrator {
+    pub fn new(id: u32) -> Self {
+        let (sender, _) = broadcast::channel(NARRATION_CAPACITY);
+        Self {
+            id,
+            started: Instant::now(),
+            sender
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Narration> {
+        self.sender.subscribe()
+    }
+
+    // # Method Description:
+    // This method narrates the steps a round took between two of its statuses: its start, the quorums it
+    // reached, and its delivery.
+    //
+    // # Parameters:
+    // * previous - The previous status of the round, if it had one.
+    // * round_status - The new status of the round.
+    pub fn narrate(&self, previous: Option<&RoundStatus>, round_status: &RoundStatus) {
+        let elapsed = self.started.elapsed();
+        let mut texts = vec![];
+        if previous.is_none() {
+            texts.push(String::from("started"));
+        }
+        for (index, stage) in round_status.get_stages().iter().enumerate() {
+            let before = previous.and_then(|previous| previous.get_stages().get(index)).map(|stage| stage.get_count()).unwrap_or(0);
+            if before < stage.get_quorum() && stage.get_count() >= stage.get_quorum() {
+                texts.push(format!("reached its {} quorum ({}/{})", stage.get_name(), stage.get_count(), stage.get_quorum()));
+            }
+        }
+        if round_status.get_delivered() && !previous.is_some_and(|previous| previous.get_delivered()) {
+            texts.push(String::from("was delivered"));
+        }
+        for text in texts {
+            let _ = self.sender.send(Narration::new(self.id, elapsed, round_status, text));
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use std::fmt::Write;
+use serde::{Serialize, Deserialize};
+
+use crate::aggregated_witness;
+use crate::barycentric_agreement;
+use crate::capabilities::Protocol;
+use crate::config::{ClusterConfig, QuorumFormula};
+use crate::json::JsonConversion;
+use crate::multiplex::Lane;
+use crate::reliable;
+use crate::witness;
+
+// # Enum Description:
+// This enum represents the quorums of a cluster (see `Thresholds`), which the phases of every protocol wait
+// for.
+//
+// # Variants:
+// * FaultyThreads - The number (or weight) of tolerated faulty threads `f`.
+// * Validity - The quorum needed to advance a phase (echo, vote, deliver, report, or trust).
+// * Agreement - The quorum guaranteeing that a correct thread is among the senders.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Quorum {
+    FaultyThreads,
+    Validity,
+    Agreement,
+}
+
+impl Quorum {
+    // # Method Description:
+    // This method gives the formula of the quorum in a cluster, in terms of its size `n` (or total weight)
+    // and of `f`.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The formula, e.g. "n - f + 1".
+    pub fn get_formula(&self, config: &ClusterConfig) -> String {
+        let quorum_config = config.get_quorum_config();
+        match self {
+            Quorum::FaultyThreads => match quorum_config.get_faulty_threads() {
+                Some(faulty_threads) => format!("{faulty_threads} (configured)"),
+                None => String::from("(n - 1) / 3"),
+            },
+            Quorum::Validity => match quorum_config.get_formula() {
+                QuorumFormula::NMinusFPlusOne => String::from("n - f + 1"),
+                QuorumFormula::NMinusF => String::from("n - f"),
+            },
+            Quorum::Agreement => String::from("f + 1"),
+        }
+    }
+
+    // # Method Description:
+    // This method evaluates the quorum in a cluster.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The number of threads (or weight) of the quorum.
+    pub fn evaluate(&self, config: &ClusterConfig) -> u64 {
+        let thresholds = config.get_thresholds();
+        match self {
+            Quorum::FaultyThreads => thresholds.get_faulty_threads(),
+            Quorum::Validity => thresholds.get_validity_threshold(),
+            Quorum::Agreement => thresholds.get_agreement_threshold(),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct describes a quorum as a protocol uses it in a cluster.
+//
+// # Fields:
+// * quorum - The quorum.
+// * formula - The formula of the quorum in the cluster (see `Quorum::get_formula`).
+// * value - The value of the quorum in the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThresholdDescriptor {
+    quorum: Quorum,
+    formula: String,
+    value: u64,
+}
+
+impl ThresholdDescriptor {
+    pub fn new(quorum: Quorum, config: &ClusterConfig) -> Self {
+        Self {
+            quorum,
+            formula: quorum.get_formula(config),
+            value: quorum.evaluate(config),
+        }
+    }
+
+    pub fn get_quorum(&self) -> Quorum {
+        self.quorum
+    }
+
+    pub fn get_formula(&self) -> &String {
+        &self.formula
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.value
+    }
+}
+
+// # Struct Description:
+// This struct describes a type of message exchanged by a protocol.
+//
+// # Fields:
+// * name - The name of the message type, referred to by the phases.
+// * content - The `ObjectContent` (or `SignalType`) the message type is carried as.
+// * lane - The lane the message type is received on (see `Lane`).
+// * description - What the message type carries.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageTypeDescriptor {
+    name: String,
+    content: String,
+    lane: Lane,
+    description: String,
+}
+
+impl MessageTypeDescriptor {
+    pub fn new(name: &str, content: &str, lane: Lane, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            content: content.to_string(),
+            lane,
+            description: description.to_string(),
+        }
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_content(&self) -> &String {
+        &self.content
+    }
+
+    pub fn get_lane(&self) -> Lane {
+        self.lane
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+}
+
+// # Struct Description:
+// This struct describes a phase of a protocol: the messages it counts, the quorum that completes it, and
+// the messages it sends once completed. Phases counting messages are named after the stages of the
+// `RoundStatus`es the handles record (e.g. "echoes", "witnesses"), so that the progress of a round can be
+// laid over the phases of its protocol.
+//
+// # Fields:
+// * name - The name of the phase.
+// * counts - The message type counted by the phase, if any.
+// * threshold - The quorum of counted messages completing the phase, if any.
+// * join - The quorum of counted messages after which a thread that has not sent the messages of the phase
+//          yet sends them anyway, if any.
+// * sends - The message types sent once the phase completes.
+// * description - What the phase does.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PhaseDescriptor {
+    name: String,
+    counts: Option<String>,
+    threshold: Option<Quorum>,
+    join: Option<Quorum>,
+    sends: Vec<String>,
+    description: String,
+}
+
+impl PhaseDescriptor {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            counts: None,
+            threshold: None,
+            join: None,
+            sends: vec![],
+            description: description.to_string(),
+        }
+    }
+
+    pub fn with_counts(mut self, counts: &str) -> Self {
+        self.counts = Some(counts.to_string());
+        self
+    }
+
+    pub fn with_threshold(mut self, counts: &str, threshold: Quorum) -> Self {
+        self.counts = Some(counts.to_string());
+        self.threshold = Some(threshold);
+        self
+    }
+
+    pub fn with_join(mut self, join: Quorum) -> Self {
+        self.join = Some(join);
+        self
+    }
+
+    pub fn with_sends(mut self, sends: &[&str]) -> Self {
+        self.sends = sends.iter().map(|send| send.to_string()).collect();
+        self
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_counts(&self) -> Option<&String> {
+        self.counts.as_ref()
+    }
+
+    pub fn get_threshold(&self) -> Option<Quorum> {
+        self.threshold
+    }
+
+    pub fn get_join(&self) -> Option<Quorum> {
+        self.join
+    }
+
+    pub fn get_sends(&self) -> &Vec<String> {
+        &self.sends
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+}
+
+// # Struct Description:
+// This struct describes the faults a protocol tolerates in a cluster.
+//
+// # Fields:
+// * model - The faults of the faulty threads.
+// * network - What the protocol assumes of the links between correct threads.
+// * faulty_threads - The number (or weight) of tolerated faulty threads.
+// * resilience - The bound on the size of the cluster the protocol needs.
+// * weighted - Whether quorums are counted in weight (stake) rather than in threads.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FaultAssumptions {
+    model: String,
+    network: String,
+    faulty_threads: ThresholdDescriptor,
+    resilience: String,
+    weighted: bool,
+}
+
+impl FaultAssumptions {
+    // # Function Description:
+    // This function creates the fault assumptions shared by the protocols of this crate: up to `f` Byzantine
+    // threads, out of at least `3f + 1`, over asynchronous reliable links.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The fault assumptions.
+    pub fn new(config: &ClusterConfig) -> Self {
+        Self {
+            model: String::from("Byzantine: faulty threads may send arbitrary messages or none, every other thread follows the protocol"),
+            network: String::from("asynchronous: every message between correct threads is eventually received, after an unbounded delay and in any order"),
+            faulty_threads: ThresholdDescriptor::new(Quorum::FaultyThreads, config),
+            resilience: String::from("n >= 3f + 1"),
+            weighted: config.get_thresholds().is_weighted(),
+        }
+    }
+
+    pub fn get_model(&self) -> &String {
+        &self.model
+    }
+
+    pub fn get_network(&self) -> &String {
+        &self.network
+    }
+
+    pub fn get_faulty_threads(&self) -> &ThresholdDescriptor {
+        &self.faulty_threads
+    }
+
+    pub fn get_resilience(&self) -> &String {
+        &self.resilience
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        self.weighted
+    }
+}
+
+// # Struct Description:
+// This struct is a machine-readable description of a protocol in a cluster: its phases, the types of the
+// messages it exchanges, the quorums its phases wait for, and the faults it tolerates. Every protocol module
+// builds its own (see e.g. `witness::protocol_descriptor`) from the code it runs, so that tooling (the
+// `describe` mode of the CLI, diagram renderers reading the JSON) stays accurate as the protocols change.
+//
+// # Fields:
+// * protocol - The protocol.
+// * name - The protocol information of the protocol's messages (e.g. "aggregated witness").
+// * description - What the protocol provides.
+// * builds_on - The protocols whose deliveries this protocol runs on.
+// * phases - The phases of an instance (or round) of the protocol, in order.
+// * message_types - The types of the messages the protocol exchanges.
+// * thresholds - The quorums the phases wait for, as evaluated in the cluster.
+// * fault_assumptions - The faults the protocol tolerates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProtocolDescriptor {
+    protocol: Protocol,
+    name: String,
+    description: String,
+    builds_on: Vec<Protocol>,
+    phases: Vec<PhaseDescriptor>,
+    message_types: Vec<MessageTypeDescriptor>,
+    thresholds: Vec<ThresholdDescriptor>,
+    fault_assumptions: FaultAssumptions,
+}
+
+impl ProtocolDescriptor {
+    pub fn new(protocol: Protocol, name: &str, description: &str, config: &ClusterConfig) -> Self {
+        Self {
+            protocol,
+            name: name.to_string(),
+            description: description.to_string(),
+            builds_on: vec![],
+            phases: vec![],
+            message_types: vec![],
+            thresholds: vec![],
+            fault_assumptions: FaultAssumptions::new(config),
+        }
+    }
+
+    // # Function Description:
+    // This function gathers the descriptor of a protocol from the module implementing it.
+    //
+    // # Parameters:
+    // * protocol - The protocol.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The descriptor of the protocol.
+    pub fn for_protocol(protocol: Protocol, config: &ClusterConfig) -> Self {
+        match protocol {
+            Protocol::Reliable => reliable::protocol_descriptor(config),
+            Protocol::Witness => witness::protocol_descriptor(config),
+            Protocol::AggregatedWitness => aggregated_witness::protocol_descriptor(config),
+            Protocol::Barycentric => barycentric_agreement::protocol_descriptor(config),
+        }
+    }
+
+    pub fn with_builds_on(mut self, builds_on: &[Protocol]) -> Self {
+        self.builds_on = builds_on.to_vec();
+        self
+    }
+
+    pub fn with_message_type(mut self, message_type: MessageTypeDescriptor) -> Self {
+        self.message_types.push(message_type);
+        self
+    }
+
+    // # Method Description:
+    // This method appends a phase to the protocol, and records the quorums it waits for.
+    //
+    // # Parameters:
+    // * phase - The phase.
+    // * config - The configuration of the cluster, in which the quorums are evaluated.
+    //
+    // # Returns:
+    // * The updated descriptor.
+    pub fn with_phase(mut self, phase: PhaseDescriptor, config: &ClusterConfig) -> Self {
+        for quorum in phase.threshold.iter().chain(phase.join.iter()) {
+            if !self.thresholds.iter().any(|threshold| threshold.quorum == *quorum) {
+                self.thresholds.push(ThresholdDescriptor::new(*quorum, config));
+                self.thresholds.sort_by_key(ThresholdDescriptor::get_quorum);
+            }
+        }
+        self.phases.push(phase);
+        self
+    }
+
+    pub fn get_protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+
+    pub fn get_builds_on(&self) -> &Vec<Protocol> {
+        &self.builds_on
+    }
+
+    pub fn get_phases(&self) -> &Vec<PhaseDescriptor> {
+        &self.phases
+    }
+
+    pub fn get_message_types(&self) -> &Vec<MessageTypeDescriptor> {
+        &self.message_types
+    }
+
+    pub fn get_message_type(&self, name: &str) -> Option<&MessageTypeDescriptor> {
+        self.message_types.iter().find(|message_type| message_type.name == name)
+    }
+
+    pub fn get_thresholds(&self) -> &Vec<ThresholdDescriptor> {
+        &self.thresholds
+    }
+
+    pub fn get_threshold(&self, quorum: Quorum) -> Option<&ThresholdDescriptor> {
+        self.thresholds.iter().find(|threshold| threshold.quorum == quorum)
+    }
+
+    pub fn get_fault_assumptions(&self) -> &FaultAssumptions {
+        &self.fault_assumptions
+    }
+
+    // # Method Description:
+    // This method checks that the descriptor is consistent: every message type a phase counts or sends is
+    // described, and every quorum a phase waits for is evaluated.
+    //
+    // # Returns:
+    // * `Ok(())`, or the first inconsistency found.
+    pub fn validate(&self) -> Result<(), String> {
+        for phase in &self.phases {
+            for message_type in phase.counts.iter().chain(phase.sends.iter()) {
+                if self.get_message_type(message_type).is_none() {
+                    return Err(format!("phase \"{}\" of {} refers to the undescribed message type \"{message_type}\"", phase.name, self.name))
+                }
+            }
+            for quorum in phase.threshold.iter().chain(phase.join.iter()) {
+                if self.get_threshold(*quorum).is_none() {
+                    return Err(format!("phase \"{}\" of {} waits for the unevaluated quorum {quorum:?}", phase.name, self.name))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // # Method Description:
+    // This method renders the descriptor as text, for the `describe` mode of the CLI.
+    //
+    // # Returns:
+    // * The rendered descriptor.
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+        let _ = writeln!(rendered, "{} ({:?}): {}", self.name, self.protocol, self.description);
+        if !self.builds_on.is_empty() {
+            let _ = writeln!(rendered, "  builds on: {:?}", self.builds_on);
+        }
+        let _ = writeln!(rendered, "  phases:");
+        for (index, phase) in self.phases.iter().enumerate() {
+            let _ = writeln!(rendered, "    {}. {}: {}", index + 1, phase.name, phase.description);
+            if let (Some(counts), Some(threshold)) = (&phase.counts, phase.threshold.and_then(|quorum| self.get_threshold(quorum))) {
+                let _ = writeln!(rendered, "       completes at {} {counts} ({:?}: {} = {})", threshold.value, threshold.quorum, threshold.formula, threshold.value);
+            }
+            if let Some(join) = phase.join.and_then(|quorum| self.get_threshold(quorum)) {
+                let _ = writeln!(rendered, "       joins at {} ({:?}: {} = {})", join.value, join.quorum, join.formula, join.value);
+            }
+            if !phase.sends.is_empty() {
+                let _ = writeln!(rendered, "       sends: {}", phase.sends.join(", "));
+            }
+        }
+        let _ = writeln!(rendered, "  message types:");
+        for message_type in &self.message_types {
+            let _ = writeln!(rendered, "    {} ({}, {:?} lane): {}", message_type.name, message_type.content, message_type.lane, message_type.description);
+        }
+        let fault_assumptions = &self.fault_assumptions;
+        let _ = writeln!(rendered, "  fault assumptions:");
+        let _ = writeln!(rendered, "    faults: {}", fault_assumptions.model);
+        let _ = writeln!(rendered, "    network: {}", fault_assumptions.network);
+        let _ = writeln!(rendered, "    tolerated: f = {} = {}{}, with {}", fault_assumptions.faulty_threads.formula, fault_assumptions.faulty_threads.value,
+            if fault_assumptions.weighted { " (in weight)" } else { "" }, fault_assumptions.resilience);
+        rendered
+    }
+}
+
+impl JsonConversion<ProtocolDescriptor> for ProtocolDescriptor {}
+
+// # Function Description:
+// This function gathers the descriptor of every protocol of this crate.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The descriptors, in the order of `Protocol::ALL`.
+pub fn describe_protocols(config: &ClusterConfig) -> Vec<ProtocolDescriptor> {
+    Protocol::ALL.iter().map(|protocol| ProtocolDescriptor::for_protocol(*protocol, config)).collect()
+}
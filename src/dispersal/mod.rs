@@ -0,0 +1,517 @@
+use std::{collections::HashSet, fmt::Debug, hash::Hash};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use async_trait::async_trait;
+
+use crate::extension::{Delivery, InstanceProcessor};
+use crate::json::JsonConversion;
+use crate::logging::log;
+use crate::reliable::{DeliveryWatches, ObjectContent, ReliableCommunication, ReliableInstanceMonitor, Signal, SignalType};
+use crate::round::{Instance, Round};
+
+// The protocol information of the fragments of a dispersed payload.
+pub const DISPERSAL: &str = "dispersal";
+
+// # Trait Description:
+// This trait extends `ReliableCommunication` with the reliable broadcast of large payloads by verifiable
+// information dispersal. It is implemented by every reliable communicator.
+#[async_trait]
+pub trait DispersalCommunication<T>: ReliableCommunication<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // This method reliably broadcasts a large payload with verifiable information dispersal (AVID): instead
+    // of sending the whole payload to every thread, the payload is coded into a Reed-Solomon fragment per
+    // thread, any `n - 2t` of which recover it, and every thread is sent its own fragment with the Merkle
+    // proof that it belongs to the root of the dispersal. Threads echo their fragment to every other thread
+    // and vote on the root as in reliable broadcast, so each step carries `O(|payload| / (n - 2t))` bytes
+    // per thread instead of `O(|payload|)`. The payload is received with `reliable_recv_large`.
+    //
+    // # Parameters:
+    // * payload - The payload.
+    // * instance_number - The instance number of the dispersal.
+    // * round_number - The round number of the dispersal.
+    // # Panics:
+    // * If the cluster has more than 256 threads, or weighted quorums.
+    async fn reliable_broadcast_large(&mut self, payload: Bytes, instance_number: Instance, round_number: Round) {
+        let config = self.get_config();
+        if config.get_thresholds().is_weighted() {
+            panic!("Error: payloads cannot be dispersed with weighted quorums");
+        }
+        let thread_count = config.get_thread_count();
+        let data_fragments = data_fragment_count(thread_count, config.get_thresholds().get_faulty_threads() as u32);
+        let fragments = disperse(*self.get_id(), &payload, round_number, thread_count, data_fragments);
+        let signal_channels = self.get_signal_channels();
+        for fragment in fragments {
+            let recipient = [fragment.get_index()];
+            let input = Signal::new(SignalType::Input, ObjectContent::Fragment(fragment), instance_number, round_number);
+            signal_channels.send_signal_to(&recipient, input).await;
+        }
+    }
+
+    // # Method Description:
+    // This method retrieves a payload reliably broadcast with `reliable_broadcast_large`, blocking until it
+    // is reconstructed from the fragments of the dispersal.
+    //
+    // # Parameters:
+    // * id - The ID of the thread that dispersed the payload.
+    // * instance_number - The instance number of the dispersal.
+    // * round_number - The round number of the dispersal.
+    //
+    // # Returns:
+    // * The payload, or `None` if the sender did not code it consistently, in which case no correct thread
+    //   delivers it.
+    async fn reliable_recv_large(&mut self, id: u32, instance_number: Instance, round_number: Round) -> Option<Bytes> {
+        let mut payload = self.get_delivery_watches().subscribe_payload(id, instance_number, round_number);
+        let payload = payload.wait_for(Option::is_some).await
+            .expect("Error: the reliable handle stopped before delivering a dispersal")
+            .clone();
+        payload.unwrap()
+    }
+}
+
+impl<T, C> DispersalCommunication<T> for C
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T>,
+{
+}
+
+// The exponential (doubled, to skip a modulo) and logarithm tables of GF(2^8) with the polynomial
+// x^8 + x^4 + x^3 + x^2 + 1, in which the fragments are coded.
+const GF_TABLES: ([u8; 512], [u8; 256]) = gf_tables();
+
+const fn gf_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut value: u16 = 1;
+    let mut power = 0;
+    while power < 255 {
+        exp[power] = value as u8;
+        exp[power + 255] = value as u8;
+        log[value as usize] = power as u8;
+        value <<= 1;
+        if value & 0x100 != 0 {
+            value ^= 0x11d;
+        }
+        power += 1;
+    }
+    (exp, log)
+}
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0
+    }
+    GF_TABLES.0[GF_TABLES.1[a as usize] as usize + GF_TABLES.1[b as usize] as usize]
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0
+    }
+    GF_TABLES.0[GF_TABLES.1[a as usize] as usize + 255 - GF_TABLES.1[b as usize] as usize]
+}
+
+// # Function Description:
+// This function computes the Lagrange coefficients that evaluate, at a target point, the polynomial of
+// degree less than `points.len()` going through values at the given points.
+//
+// # Parameters:
+// * points - The distinct points the values are known at.
+// * target - The point to evaluate the polynomial at.
+//
+// # Returns:
+// * The coefficient of the value at every point, in the order of the points.
+fn lagrange_coefficients(points: &[u8], target: u8) -> Vec<u8> {
+    points.iter().enumerate().map(|(i, x_i)| {
+        points.iter().enumerate().filter(|(m, _)| *m != i).fold(1, |coefficient, (_, x_m)| {
+            gf_mul(coefficient, gf_div(target ^ x_m, x_i ^ x_m))
+        })
+    }).collect()
+}
+
+// # Function Description:
+// This function evaluates, at a target point, the polynomials going through shards at the given points,
+// byte by byte.
+//
+// # Parameters:
+// * points - The points of the shards.
+// * shards - The shards, of equal lengths.
+// * target - The point to evaluate the polynomials at.
+//
+// # Returns:
+// * The shard at the target point.
+fn interpolate(points: &[u8], shards: &[&[u8]], target: u8) -> Vec<u8> {
+    let coefficients = lagrange_coefficients(points, target);
+    let mut shard = vec![0u8; shards.first().map_or(0, |shard| shard.len())];
+    for (coefficient, known) in coefficients.iter().zip(shards) {
+        for (byte, known_byte) in shard.iter_mut().zip(known.iter()) {
+            *byte ^= gf_mul(*coefficient, *known_byte);
+        }
+    }
+    shard
+}
+
+// # Function Description:
+// This function codes a payload into `fragment_count` shards with a systematic Reed-Solomon code: the first
+// `data_fragments` shards are the payload itself, padded with zeros, and any `data_fragments` shards are
+// enough to recover it.
+//
+// # Parameters:
+// * payload - The payload.
+// * fragment_count - The number of shards, at most 256.
+// * data_fragments - The number of shards needed to recover the payload.
+//
+// # Returns:
+// * The shards, indexed by fragment index.
+fn encode_shards(payload: &[u8], fragment_count: u32, data_fragments: u32) -> Vec<Vec<u8>> {
+    let shard_length = payload.len().div_ceil(data_fragments as usize);
+    let mut shards: Vec<Vec<u8>> = (0..data_fragments as usize).map(|index| {
+        let mut shard = payload.get(index * shard_length..payload.len().min((index + 1) * shard_length)).unwrap_or(&[]).to_vec();
+        shard.resize(shard_length, 0);
+        shard
+    }).collect();
+    let points: Vec<u8> = (0..data_fragments).map(|index| index as u8).collect();
+    let parity: Vec<Vec<u8>> = {
+        let data: Vec<&[u8]> = shards.iter().map(Vec::as_slice).collect();
+        (data_fragments..fragment_count).map(|index| interpolate(&points, &data, index as u8)).collect()
+    };
+    shards.extend(parity);
+    shards
+}
+
+fn hash_leaf(shard: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update([0u8]).chain_update(shard).finalize().into()
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update([1u8]).chain_update(left).chain_update(right).finalize().into()
+}
+
+// # Function Description:
+// This function computes the root a dispersal is known by: the hash of the Merkle root of its shards, of
+// the length of its payload, and of the number of shards needed to recover it, so that fragments of
+// payloads coded differently never share a root.
+fn hash_root(merkle_root: &[u8; 32], length: u64, data_fragments: u32) -> String {
+    let root: [u8; 32] = Sha256::new().chain_update([2u8]).chain_update(merkle_root)
+        .chain_update(length.to_le_bytes()).chain_update(data_fragments.to_le_bytes()).finalize().into();
+    to_hex(&root)
+}
+
+// The depth of the Merkle tree over the shards of `fragment_count` fragments, padded to a power of two.
+fn merkle_depth(fragment_count: u32) -> usize {
+    fragment_count.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
+// # Function Description:
+// This function builds the Merkle tree over shards, padded to a power of two with zeroed leaves.
+//
+// # Returns:
+// * The levels of the tree, from the leaves to the root.
+fn merkle_levels(shards: &[Vec<u8>]) -> Vec<Vec<[u8; 32]>> {
+    let mut level: Vec<[u8; 32]> = shards.iter().map(|shard| hash_leaf(shard)).collect();
+    level.resize(shards.len().max(1).next_power_of_two(), [0u8; 32]);
+    let mut levels = vec![level];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels.last().unwrap().chunks(2).map(|pair| hash_node(&pair[0], &pair[1])).collect();
+        levels.push(next);
+    }
+    levels
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None
+    }
+    let bytes: Option<Vec<u8>> = (0..hex.len()).step_by(2).map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok()).collect();
+    bytes?.try_into().ok()
+}
+
+// # Function Description:
+// This function provides the number of fragments needed to recover a payload dispersed to a cluster: the
+// `n - 2t` fragments of the AVID protocol, which the correct threads that echoed their fragment always hold.
+//
+// # Parameters:
+// * thread_count - The number of threads `n` of the cluster.
+// * faulty_threads - The number of tolerated faulty threads `t`.
+//
+// # Returns:
+// * The number of fragments, at least 1.
+pub fn data_fragment_count(thread_count: u32, faulty_threads: u32) -> u32 {
+    thread_count.saturating_sub(2 * faulty_threads).max(1)
+}
+
+// # Function Description:
+// This function disperses a payload: it is coded into a fragment per thread, any `data_fragments` of which
+// recover it, each carrying the proof that it belongs to the root of the dispersal.
+//
+// # Parameters:
+// * id - The ID of the thread dispersing the payload.
+// * payload - The payload.
+// * round_number - The round the payload is dispersed in.
+// * thread_count - The number of threads of the cluster, at most 256.
+// * data_fragments - The number of fragments needed to recover the payload (see `data_fragment_count`).
+//
+// # Returns:
+// * The fragments, indexed by the ID of the thread they are sent to.
+pub fn disperse(id: u32, payload: &[u8], round_number: Round, thread_count: u32, data_fragments: u32) -> Vec<Fragment> {
+    if thread_count > 256 || data_fragments == 0 || data_fragments > thread_count {
+        panic!("Error: cannot code {thread_count} fragments, {data_fragments} of which recover the payload");
+    }
+    let shards = encode_shards(payload, thread_count, data_fragments);
+    let levels = merkle_levels(&shards);
+    let root = hash_root(&levels.last().unwrap()[0], payload.len() as u64, data_fragments);
+    shards.iter().enumerate().map(|(index, shard)| {
+        let proof = levels[..levels.len() - 1].iter().enumerate()
+            .map(|(depth, level)| to_hex(&level[(index >> depth) ^ 1]))
+            .collect();
+        Fragment {
+            protocol_information: String::from(DISPERSAL),
+            id,
+            root: root.clone(),
+            length: payload.len() as u64,
+            data_fragments,
+            index: index as u32,
+            data: BASE64.encode(shard),
+            proof,
+            round_number
+        }
+    }).collect()
+}
+
+// # Function Description:
+// This function recovers a dispersed payload from fragments of its root, and checks that the sender coded it
+// consistently: the payload is coded again, and must have the same root. A payload whose fragments were not
+// coded from a single payload is never recovered, whichever fragments are used, so every correct thread
+// agrees on the outcome of a dispersal.
+//
+// # Parameters:
+// * fragments - Verified fragments of the same root, with distinct indices (see `Fragment::verify`).
+// * thread_count - The number of threads of the cluster.
+//
+// # Returns:
+// * The payload, `None` if there are too few fragments or the payload was not coded consistently.
+pub fn reconstruct(fragments: &[&Fragment], thread_count: u32) -> Option<Bytes> {
+    let first = fragments.first()?;
+    let data_fragments = first.data_fragments as usize;
+    if fragments.len() < data_fragments {
+        return None
+    }
+    let used = &fragments[..data_fragments];
+    let shards: Vec<Vec<u8>> = used.iter().map(|fragment| fragment.decode_data()).collect::<Option<_>>()?;
+    let points: Vec<u8> = used.iter().map(|fragment| fragment.index as u8).collect();
+    let known: Vec<&[u8]> = shards.iter().map(Vec::as_slice).collect();
+    let mut payload = Vec::with_capacity(first.length as usize);
+    for index in 0..data_fragments as u8 {
+        match points.iter().position(|point| *point == index) {
+            Some(position) => payload.extend_from_slice(known[position]),
+            None => payload.extend(interpolate(&points, &known, index)),
+        }
+    }
+    if payload.len() < first.length as usize {
+        return None
+    }
+    payload.truncate(first.length as usize);
+    let levels = merkle_levels(&encode_shards(&payload, thread_count, first.data_fragments));
+    if hash_root(&levels.last().unwrap()[0], first.length, first.data_fragments) != first.root {
+        return None
+    }
+    Some(Bytes::from(payload))
+}
+
+// # Struct Description:
+// This struct is a fragment of a payload dispersed with `reliable_broadcast_large`: the shard of a thread in
+// the Reed-Solomon coding of the payload, with the Merkle proof that it belongs to the root of the dispersal.
+//
+// # Fields:
+// * protocol_information - The protocol of the fragment, always "dispersal".
+// * id - The ID of the thread that dispersed the payload.
+// * root - The hex-encoded root of the dispersal, binding the shards, the length of the payload, and the
+//   number of fragments needed to recover it.
+// * length - The length of the payload, in bytes.
+// * data_fragments - The number of fragments needed to recover the payload.
+// * index - The index of the fragment, i.e. the ID of the thread the fragment was coded for.
+// * data - The base64-encoded shard.
+// * proof - The hex-encoded sibling hashes from the leaf of the shard to the Merkle root.
+// * round_number - The round the payload was dispersed in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Fragment {
+    protocol_information: String,
+    id: u32,
+    root: String,
+    length: u64,
+    data_fragments: u32,
+    index: u32,
+    data: String,
+    proof: Vec<String>,
+    round_number: Round,
+}
+
+impl Fragment {
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_root(&self) -> &String {
+        &self.root
+    }
+
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn get_data_fragments(&self) -> u32 {
+        self.data_fragments
+    }
+
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    // # Method Description:
+    // This method decodes the shard carried by the fragment.
+    //
+    // # Returns:
+    // * The shard, `None` if it is not valid base64.
+    pub fn decode_data(&self) -> Option<Vec<u8>> {
+        BASE64.decode(&self.data).ok()
+    }
+
+    // # Method Description:
+    // This method checks that the fragment belongs to its root, for a cluster: its index is a thread of the
+    // cluster, the number of fragments needed is the cluster's, and its Merkle proof leads from its shard to
+    // the root.
+    //
+    // # Parameters:
+    // * thread_count - The number of threads of the cluster.
+    // * data_fragments - The number of fragments needed to recover a payload in the cluster.
+    //
+    // # Returns:
+    // * `true` if the fragment is valid.
+    pub fn verify(&self, thread_count: u32, data_fragments: u32) -> bool {
+        if self.index >= thread_count || self.data_fragments != data_fragments || self.proof.len() != merkle_depth(thread_count) {
+            return false
+        }
+        let Some(shard) = self.decode_data() else {
+            return false
+        };
+        if shard.len() != (self.length as usize).div_ceil(data_fragments as usize) {
+            return false
+        }
+        let mut hash = hash_leaf(&shard);
+        for (depth, sibling) in self.proof.iter().enumerate() {
+            let Some(sibling) = from_hex(sibling) else {
+                return false
+            };
+            hash = match (self.index >> depth) & 1 {
+                0 => hash_node(&hash, &sibling),
+                _ => hash_node(&sibling, &hash),
+            };
+        }
+        hash_root(&hash, self.length, self.data_fragments) == self.root
+    }
+}
+
+impl JsonConversion<Fragment> for Fragment {}
+
+// # Struct Description:
+// This struct is the processor of the dispersals of a reliable handle (see `InstanceProcessor`). Fragments
+// are checked against their root before they are counted, Echo signals do not amplify, since a thread joining
+// a dispersal has no fragment of its own to echo, and a payload is delivered once the Vote quorum is reached
+// and enough fragments are gathered, whichever comes last: the correct threads that echoed their fragment
+// always provide enough of them.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * thread_count - The number of threads of the cluster, each holding a fragment of every dispersed payload.
+// * data_fragments - The number of fragments needed to recover a payload.
+// * dispersals - The dispersals the thread accepted the fragment of, by sender, instance, and round, so that
+//   it echoes a single root per dispersal.
+// * delivery_watches - The watches on which the reconstructed payloads are published.
+pub(crate) struct DispersalProcessor<T> {
+    thread_id: u32,
+    thread_count: u32,
+    data_fragments: u32,
+    dispersals: HashSet<(u32, Instance, Round)>,
+    delivery_watches: DeliveryWatches<T>,
+}
+
+impl<T> DispersalProcessor<T> {
+    pub(crate) fn new(thread_id: u32, thread_count: u32, faulty_threads: u32, delivery_watches: DeliveryWatches<T>) -> Self {
+        Self {
+            thread_id,
+            thread_count,
+            data_fragments: data_fragment_count(thread_count, faulty_threads),
+            dispersals: HashSet::new(),
+            delivery_watches,
+        }
+    }
+}
+
+impl<T> InstanceProcessor<T> for DispersalProcessor<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // The fragment must belong to its root, and be the fragment of the receiving thread in an Input, or of
+    // the thread echoing it in an Echo. A thread accepts the Input of a single root per dispersal, so that a
+    // sender coding different payloads for different threads gathers no Echo quorum.
+    fn accept(&mut self, signal: &Signal<T>) -> bool {
+        let ObjectContent::Fragment(fragment) = signal.get_content() else {
+            return false
+        };
+        let valid = fragment.verify(self.thread_count, self.data_fragments) && match signal.get_signal() {
+            SignalType::Input => fragment.get_index() == self.thread_id,
+            SignalType::Echo => signal.get_origin() == Some(fragment.get_index()),
+            SignalType::Vote => true,
+        };
+        if !valid {
+            log!(warn, { node = self.thread_id }, "id: {}, instance: {}, discarding invalid fragment by id: {}", self.thread_id, signal.get_instance_number(), fragment.get_id());
+            return false
+        }
+        *signal.get_signal() != SignalType::Input || self.dispersals.insert((fragment.get_id(), signal.get_instance_number(), signal.get_round_number()))
+    }
+
+    fn is_amplified(&self) -> bool {
+        false
+    }
+
+    fn record(&self, instance: &mut ReliableInstanceMonitor<T>, signal: &Signal<T>) {
+        if let ObjectContent::Fragment(fragment) = signal.get_content() {
+            instance.fragments.entry(fragment.get_index()).or_insert_with(|| fragment.clone());
+        }
+    }
+
+    fn deliver(&self, instance: &ReliableInstanceMonitor<T>, signal: &Signal<T>) -> Delivery {
+        let ObjectContent::Fragment(fragment) = signal.get_content() else {
+            return Delivery::Pending
+        };
+        if instance.fragments.len() < fragment.get_data_fragments() as usize {
+            return Delivery::Pending
+        }
+        let instance_number = signal.get_instance_number();
+        let gathered: Vec<&Fragment> = instance.fragments.values().collect();
+        let payload = reconstruct(&gathered, self.thread_count);
+        match &payload {
+            Some(_) => log!(info, { node = self.thread_id, transition = "deliver" }, "id {}, instance: {}, delivering dispersed payload...", self.thread_id, instance_number),
+            None => log!(warn, { node = self.thread_id }, "id {}, instance: {}, discarding inconsistently dispersed payload...", self.thread_id, instance_number),
+        }
+        self.delivery_watches.publish_payload(fragment.get_id(), instance_number, signal.get_round_number(), payload);
+        Delivery::Delivered
+    }
+}
@@ -0,0 +1,307 @@
+use std::{collections::BTreeMap, sync::Mutex, time::Duration};
+use serde::{Serialize, Deserialize};
+use rand::{Rng, rngs::StdRng, seq::index::sample};
+
+use crate::config::ClusterConfig;
+use crate::emulation::RegionTopology;
+
+// # Enum Description:
+// This enum represents how reliable handles disseminate their Echo and Vote signals, for every protocol
+// or for a single one (see `ClusterConfig::with_protocol_dissemination`). Input signals and every other
+// lane are always sent directly.
+//
+// # Variants:
+// * Direct - Every Echo and Vote is sent directly to every thread of the cluster.
+// * SpanningTree - Every Echo and Vote is sent along a spanning tree of the cluster (see `BroadcastTree`):
+//   a thread only sends to its tree neighbours, and relays the signals it receives to its other neighbours.
+//   Each thread receives every signal exactly once, so the total number of frames per phase is unchanged,
+//   but the fan-out of a thread is bounded by its tree degree and, with a region topology, every signal
+//   crosses each region boundary of the tree once instead of once per pair of threads. The tree assumes
+//   its inner threads relay faithfully: a crashed or Byzantine relay cuts its subtree off.
+//   The enclosed value is the fan-out of the tree, at least 1.
+// * Gossip - Every Echo and Vote is disseminated epidemically: a thread only sends its own signals to
+//   itself, and forwards every signal the first time it receives it to a random subset of the other
+//   threads, so that the fan-out of a thread is bounded by the fan-out of the gossip whatever the size of
+//   the cluster. Every signal is sent about `fanout * n` times instead of `n` times, and reaches every
+//   thread with high probability once the fan-out exceeds `ln n`; with anti-entropy, the threads also
+//   periodically send their digest of the instances they hold to a random peer, which re-sends them the
+//   signals they miss (see `GossipDigest`), so that every signal eventually reaches every correct thread.
+//   The enclosed values are the fan-out, at least 1, and the interval of the anti-entropy exchanges, if any.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum Dissemination {
+    #[default]
+    Direct,
+    SpanningTree(u32),
+    Gossip(u32, Option<Duration>),
+}
+
+impl Dissemination {
+    // # Method Description:
+    // This method checks that the dissemination mode can be configured.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of the invalid parameter.
+    pub fn check(&self) -> Result<(), String> {
+        match self {
+            Dissemination::SpanningTree(0) => Err(String::from("the fan-out of a spanning tree must be at least 1")),
+            Dissemination::Gossip(0, _) => Err(String::from("the fan-out of a gossip must be at least 1")),
+            Dissemination::Gossip(_, Some(interval)) if interval.is_zero() => Err(String::from("the interval of anti-entropy must be positive")),
+            _ => Ok(()),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct represents the spanning tree along which Echo and Vote signals are disseminated.
+// Without a region topology, threads form a complete tree of the given fan-out ordered by thread ID.
+// With a region topology, the tree is topology-aware: the lowest thread ID of every region is the root
+// of its region, the threads of a region form a tree of the given fan-out under their region root, and
+// the region roots form a tree of the given fan-out under the root of the first region. Hence only
+// `region_count - 1` edges of the tree cross a region boundary.
+//
+// # Fields:
+// * parents - The parent of every thread in the tree, `None` for the root, indexed by thread ID.
+// * neighbours - The tree neighbours (parent and children) of every thread, indexed by thread ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastTree {
+    parents: Vec<Option<u32>>,
+    neighbours: Vec<Vec<u32>>,
+}
+
+impl BroadcastTree {
+    // # Method Description:
+    // This method builds the spanning tree of a cluster.
+    //
+    // # Parameters:
+    // * thread_count - The number of threads in the cluster.
+    // * fanout - The maximum number of children of a thread within a region, and of a region root among region roots.
+    // * regions - The region topology of the cluster, if any.
+    //
+    // # Panics:
+    // * If the fan-out is 0.
+    pub fn new(thread_count: u32, fanout: u32, regions: Option<&RegionTopology>) -> Self {
+        if fanout == 0 {
+            panic!("Error: the fan-out of a broadcast tree must be at least 1");
+        }
+        let groups: Vec<Vec<u32>> = match regions {
+            Some(regions) => (0..regions.get_region_count() as u32)
+                .map(|region| (0..thread_count).filter(|id| regions.get_region(*id) == region).collect::<Vec<u32>>())
+                .filter(|group| !group.is_empty())
+                .collect(),
+            None => vec![(0..thread_count).collect()],
+        };
+
+        let mut parents = vec![None; thread_count as usize];
+        let region_roots: Vec<u32> = groups.iter().map(|group| group[0]).collect();
+        for group in groups.iter().chain(std::iter::once(&region_roots)) {
+            for (index, id) in group.iter().enumerate().skip(1) {
+                parents[*id as usize] = Some(group[(index - 1) / fanout as usize]);
+            }
+        }
+
+        let mut neighbours = vec![vec![]; thread_count as usize];
+        for (id, parent) in parents.iter().enumerate() {
+            if let Some(parent) = parent {
+                neighbours[id].push(*parent);
+                neighbours[*parent as usize].push(id as u32);
+            }
+        }
+
+        Self {
+            parents,
+            neighbours
+        }
+    }
+
+    // # Method Description:
+    // This method builds the spanning tree selected by a configuration, over its region topology if any.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The spanning tree, or `None` when signals are disseminated directly.
+    pub fn from_config(config: &ClusterConfig) -> Option<Self> {
+        match config.get_dissemination() {
+            Dissemination::Direct | Dissemination::Gossip(_, _) => None,
+            Dissemination::SpanningTree(fanout) => {
+                let regions = config.get_network_emulation().and_then(|network_emulation| network_emulation.get_regions());
+                Some(Self::new(config.get_thread_count(), fanout, regions))
+            },
+        }
+    }
+
+    pub fn get_parent(&self, id: u32) -> Option<u32> {
+        self.parents[id as usize]
+    }
+
+    pub fn get_neighbours(&self, id: u32) -> &Vec<u32> {
+        &self.neighbours[id as usize]
+    }
+
+    // # Method Description:
+    // This method computes the depth of the tree, i.e. the number of relays a signal goes through in the worst case
+    // before reaching every thread from the root.
+    //
+    // # Returns:
+    // * The number of edges on the longest path from the root to a thread.
+    pub fn get_depth(&self) -> u32 {
+        (0..self.parents.len() as u32)
+            .map(|id| {
+                let mut depth = 0;
+                let mut current = id;
+                while let Some(parent) = self.get_parent(current) {
+                    depth += 1;
+                    current = parent;
+                }
+                depth
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    // # Method Description:
+    // This method counts the edges of the tree that cross a region boundary, i.e. the number of inter-region
+    // frames needed to disseminate one signal.
+    //
+    // # Parameters:
+    // * regions - The region topology of the cluster.
+    //
+    // # Returns:
+    // * The number of edges between threads of different regions.
+    pub fn get_cross_region_edges(&self, regions: &RegionTopology) -> u32 {
+        self.parents.iter()
+            .enumerate()
+            .filter(|(id, parent)| matches!(parent, Some(parent) if regions.get_region(*parent) != regions.get_region(*id as u32)))
+            .count() as u32
+    }
+}
+
+// # Struct Description:
+// This struct decides, for the Echo and Vote signals of a thread, where they are sent, according to the
+// dissemination mode of their protocol (see `Dissemination`): the spanning trees of the modes in use are
+// built once, and the gossip peers are drawn from the seeded RNG of the thread.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * thread_count - The number of threads of the cluster.
+// * dissemination - The dissemination mode of the protocols without a mode of their own.
+// * protocols - The dissemination mode of every protocol with a mode of its own, by protocol information.
+// * trees - The spanning tree of every fan-out in use, by fan-out.
+// * rng - The RNG drawing the gossip peers.
+#[derive(Debug)]
+pub struct Disseminator {
+    id: u32,
+    thread_count: u32,
+    dissemination: Dissemination,
+    protocols: BTreeMap<String, Dissemination>,
+    trees: BTreeMap<u32, BroadcastTree>,
+    rng: Mutex<StdRng>,
+}
+
+impl Disseminator {
+    // # Function Description:
+    // This function creates the disseminator of a thread from the configuration of its cluster.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The disseminator, or `None` when every protocol sends its signals directly.
+    pub fn from_config(id: u32, config: &ClusterConfig) -> Option<Self> {
+        let dissemination = config.get_dissemination();
+        let protocols = config.get_protocol_dissemination().clone();
+        let modes: Vec<Dissemination> = std::iter::once(dissemination).chain(protocols.values().copied()).collect();
+        if modes.iter().all(|mode| *mode == Dissemination::Direct) {
+            return None
+        }
+        let regions = config.get_network_emulation().and_then(|network_emulation| network_emulation.get_regions());
+        let trees = modes.iter()
+            .filter_map(|mode| match mode {
+                Dissemination::SpanningTree(fanout) => Some((*fanout, BroadcastTree::new(config.get_thread_count(), *fanout, regions))),
+                _ => None,
+            })
+            .collect();
+        Some(Self {
+            id,
+            thread_count: config.get_thread_count(),
+            dissemination,
+            protocols,
+            trees,
+            rng: Mutex::new(config.create_rng(id, "gossip")),
+        })
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    // # Method Description:
+    // This method provides the dissemination mode of a protocol.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol.
+    //
+    // # Returns:
+    // * The mode of the protocol, or the mode of the cluster if the protocol has none of its own.
+    pub fn get_dissemination(&self, protocol_information: &str) -> Dissemination {
+        self.protocols.get(protocol_information).copied().unwrap_or(self.dissemination)
+    }
+
+    // # Method Description:
+    // This method provides the interval at which the thread exchanges digests with its peers, the shortest
+    // of the gossiped protocols.
+    //
+    // # Returns:
+    // * The interval, or `None` if no protocol is gossiped with anti-entropy.
+    pub fn get_anti_entropy_interval(&self) -> Option<Duration> {
+        std::iter::once(&self.dissemination).chain(self.protocols.values())
+            .filter_map(|mode| match mode {
+                Dissemination::Gossip(_, interval) => *interval,
+                _ => None,
+            })
+            .min()
+    }
+
+    // # Method Description:
+    // This method selects the threads to which a signal received by the thread is relayed: its tree
+    // neighbours but the one it was received from, or a random subset of the other threads but the one it
+    // was received from. Signals sent directly are not relayed.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol of the signal.
+    // * relay - The thread the signal was received from, `None` if the thread sent it to itself.
+    //
+    // # Returns:
+    // * The IDs of the threads to relay the signal to.
+    pub fn get_relay_targets(&self, protocol_information: &str, relay: Option<u32>) -> Vec<u32> {
+        match self.get_dissemination(protocol_information) {
+            Dissemination::Direct => vec![],
+            Dissemination::SpanningTree(fanout) => self.trees[&fanout].get_neighbours(self.id).iter()
+                .copied()
+                .filter(|neighbour| Some(*neighbour) != relay)
+                .collect(),
+            Dissemination::Gossip(fanout, _) => {
+                let peers: Vec<u32> = (0..self.thread_count).filter(|peer| *peer != self.id && Some(*peer) != relay).collect();
+                let mut rng = self.rng.lock().unwrap();
+                sample(&mut *rng, peers.len(), (fanout as usize).min(peers.len())).into_iter()
+                    .map(|index| peers[index])
+                    .collect()
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method draws the peer the thread sends its digest to in the next anti-entropy exchange.
+    //
+    // # Returns:
+    // * The ID of the peer, or `None` in a cluster of a single thread.
+    pub fn pick_peer(&self) -> Option<u32> {
+        if self.thread_count < 2 {
+            return None
+        }
+        let peer = self.rng.lock().unwrap().gen_range(0..self.thread_count - 1);
+        Some(if peer >= self.id { peer + 1 } else { peer })
+    }
+}
@@ -0,0 +1,253 @@
+use std::{collections::{HashMap, HashSet}, time::{Duration, Instant}};
+use serde::{Serialize, Deserialize};
+
+use crate::json::{FrameKind, JsonConversion, frame_kinds};
+use crate::round::Round;
+
+// # Enum Description:
+// This enum represents whether the witness handles of a cluster gossip the digests of their rounds, to
+// detect nodes whose rounds diverge (e.g. nodes configured with other thresholds, codecs, or fault scripts)
+// long before the divergence shows in the delivered rounds.
+//
+// # Variants:
+// * Disabled - Rounds are not gossiped.
+// * Periodic - Every handle sends the digests of its active rounds to the other threads at the given
+//   interval, and suspects a peer of diverging on a round once their digests of the round differed in the
+//   given number of consecutive gossips. A round in progress legitimately differs between nodes until its
+//   last values arrive, so the bound should span the time a round takes to settle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum DigestGossip {
+    #[default]
+    Disabled,
+    Periodic(Duration, u32),
+}
+
+// # Struct Description:
+// This struct is the frame a witness handle gossips to the other threads on their `Report` lane: the digest
+// of every round it holds (see `WitnessRoundContent::get_digest`).
+//
+// # Fields:
+// * id - The ID of the thread that gossiped the digests.
+// * round_digests - The digest of every active round of the thread, by round number.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoundDigests {
+    id: u32,
+    round_digests: Vec<(Round, u64)>,
+}
+
+impl RoundDigests {
+    pub fn new(id: u32, round_digests: Vec<(Round, u64)>) -> Self {
+        Self {
+            id,
+            round_digests
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_round_digests(&self) -> &Vec<(Round, u64)> {
+        &self.round_digests
+    }
+}
+
+impl JsonConversion<RoundDigests> for RoundDigests {}
+
+impl FrameKind for RoundDigests {
+    const FRAME_KIND: u64 = frame_kinds::ROUND_DIGESTS_FRAME;
+}
+
+// # Struct Description:
+// This struct describes a round on which a node suspects a peer of diverging, published by witness handles
+// as `Event::DivergenceSuspected`.
+//
+// # Fields:
+// * id - The ID of the node that suspects the divergence.
+// * peer - The ID of the peer whose round diverges.
+// * round_number - The round that diverges.
+// * local_digest - The digest of the round held by the node.
+// * remote_digest - The digest of the round gossiped by the peer.
+// * gossips - The number of consecutive gossips in which the digests differed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DivergenceSuspicion {
+    id: u32,
+    peer: u32,
+    round_number: Round,
+    local_digest: u64,
+    remote_digest: u64,
+    gossips: u32,
+}
+
+impl DivergenceSuspicion {
+    pub fn new(id: u32, peer: u32, round_number: Round, local_digest: u64, remote_digest: u64, gossips: u32) -> Self {
+        Self {
+            id,
+            peer,
+            round_number,
+            local_digest,
+            remote_digest,
+            gossips
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_peer(&self) -> u32 {
+        self.peer
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_local_digest(&self) -> u64 {
+        self.local_digest
+    }
+
+    pub fn get_remote_digest(&self) -> u64 {
+        self.remote_digest
+    }
+
+    pub fn get_gossips(&self) -> u32 {
+        self.gossips
+    }
+}
+
+// # Struct Description:
+// This struct compares the round digests a witness handle receives with its own, following the configured
+// `DigestGossip`. The digests of a peer's round must differ in `bound` consecutive gossips before the peer
+// is suspected, and every round is only suspected once per peer.
+//
+// # Fields:
+// * id - The ID of the thread of the handle.
+// * gossip - The digest gossip of the cluster.
+// * last_gossip - The instant the handle last gossiped its digests, if it did.
+// * streaks - The number of consecutive gossips in which the digests differed, by peer and round.
+// * suspected - The peers and rounds already suspected.
+#[derive(Debug, Clone)]
+pub struct DivergenceDetector {
+    id: u32,
+    gossip: DigestGossip,
+    last_gossip: Option<Instant>,
+    streaks: HashMap<(u32, Round), u32>,
+    suspected: HashSet<(u32, Round)>,
+}
+
+impl DivergenceDetector {
+    pub fn new(id: u32, gossip: DigestGossip) -> Self {
+        Self {
+            id,
+            gossip,
+            last_gossip: None,
+            streaks: HashMap::new(),
+            suspected: HashSet::new(),
+        }
+    }
+
+    pub fn get_gossip(&self) -> DigestGossip {
+        self.gossip
+    }
+
+    // # Method Description:
+    // This method gives the interval at which the handle gossips its digests.
+    //
+    // # Returns:
+    // * The interval, or `None` if the gossip is disabled.
+    pub fn get_interval(&self) -> Option<Duration> {
+        match self.gossip {
+            DigestGossip::Disabled => None,
+            DigestGossip::Periodic(interval, _) => Some(interval),
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether the handle is due to gossip its digests, for handles that are polled
+    // rather than woken up by a timer (see `HandleMode::Polled`), and records the gossip if it is.
+    //
+    // # Returns:
+    // * Whether the handle must gossip its digests now.
+    pub fn is_due(&mut self) -> bool {
+        let Some(interval) = self.get_interval() else {
+            return false
+        };
+        let now = Instant::now();
+        match self.last_gossip {
+            Some(last_gossip) if now.duration_since(last_gossip) < interval => false,
+            _ => {
+                self.last_gossip = Some(now);
+                true
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method compares the digests gossiped by a peer with the handle's own. Rounds the handle does
+    // not hold are skipped, and the streak of a round is reset as soon as its digests match again.
+    //
+    // # Parameters:
+    // * round_digests - The digests gossiped by the peer.
+    // * local_digest - Gives the digest of a round held by the handle, if it holds it.
+    //
+    // # Returns:
+    // * The rounds on which the peer is newly suspected of diverging.
+    pub fn compare<F>(&mut self, round_digests: &RoundDigests, local_digest: F) -> Vec<DivergenceSuspicion>
+    where
+        F: Fn(Round) -> Option<u64>,
+    {
+        let DigestGossip::Periodic(_, bound) = self.gossip else {
+            return vec![]
+        };
+        let peer = round_digests.get_id();
+        let mut suspicions = vec![];
+        for (round_number, remote_digest) in round_digests.get_round_digests() {
+            let Some(digest) = local_digest(*round_number) else {
+                continue
+            };
+            let key = (peer, *round_number);
+            if digest == *remote_digest {
+                self.streaks.remove(&key);
+                continue
+            }
+            let streak = self.streaks.entry(key).or_insert(0);
+            *streak += 1;
+            if *streak >= bound && self.suspected.insert(key) {
+                suspicions.push(DivergenceSuspicion::new(self.id, peer, *round_number, digest, *remote_digest, *streak));
+            }
+        }
+        suspicions
+    }
+
+    // # Method Description:
+    // This method forgets a round collected by the handle.
+    //
+    // # Parameters:
+    // * round_number - The collected round.
+    pub fn forget(&mut self, round_number: Round) {
+        self.streaks.retain(|(_, round), _| *round != round_number);
+        self.suspected.retain(|(_, round)| *round != round_number);
+    }
+}
+
+// # Function Description:
+// This function combines the digests of the values of a round into the digest of the round, whatever the
+// order in which they were collected.
+//
+// # Parameters:
+// * values - The sender ID and digest of every value of the round.
+//
+// # Returns:
+// * The digest of the round.
+pub fn combine_digests(mut values: Vec<(u32, u64)>) -> u64 {
+    values.sort();
+    let mut digest: u64 = 0xcbf29ce484222325;
+    for (id, value_digest) in values {
+        for byte in id.to_le_bytes().iter().chain(value_digest.to_le_bytes().iter()) {
+            digest ^= *byte as u64;
+            digest = digest.wrapping_mul(0x100000001b3);
+        }
+    }
+    digest
+}
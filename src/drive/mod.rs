@@ -0,0 +1,166 @@
+use std::{fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use tokio::{task::JoinHandle, sync::oneshot};
+use async_trait::async_trait;
+
+use crate::basic::BasicCommunication;
+use crate::multiplex::Demultiplexer;
+
+// # Enum Description:
+// This enum represents how the background handles of a communicator (its lane demultiplexer and its
+// reliable, witness, or barycentric handles) are operated.
+//
+// # Variants:
+// * Spawned - Every handle runs in its own tokio task, processing frames as soon as they arrive.
+// * Polling - No handle task is spawned: the handles are parked in the communicator, and frames are only
+//   processed when the application calls `drive(communicator)`. This makes the interleaving of a run fully
+//   determined by the order of the `drive` calls (e.g. for deterministic tests, or to embed the protocols
+//   into a custom event loop). Emulated network links still run as tasks, and the processing delay of
+//   `Fault::Slowdown` is not applied.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum HandleMode {
+    #[default]
+    Spawned,
+    Polling,
+}
+
+// # Struct Description:
+// This struct reports how a handle shut down cooperatively (see `ReliableCommunication::shutdown_reliable_handle`
+// and `WitnessCommunication::shutdown_witness_handle`), in place of being aborted.
+//
+// # Fields:
+// * id - The ID of the thread owning the handle.
+// * handle - The kind of handle (e.g. "reliable" or "witness").
+// * flushed - The number of frames the handle processed after the shutdown was requested, that were
+//   already received when it was.
+// * open - The instances (or rounds) the handle had started but not delivered when it stopped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShutdownReport {
+    id: u32,
+    handle: String,
+    flushed: usize,
+    open: Vec<String>,
+}
+
+impl ShutdownReport {
+    pub fn new(id: u32, handle: String, flushed: usize, open: Vec<String>) -> Self {
+        Self {
+            id,
+            handle,
+            flushed,
+            open
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_handle(&self) -> &String {
+        &self.handle
+    }
+
+    pub fn get_flushed(&self) -> usize {
+        self.flushed
+    }
+
+    pub fn get_open(&self) -> &Vec<String> {
+        &self.open
+    }
+
+    pub fn get_open_count(&self) -> usize {
+        self.open.len()
+    }
+}
+
+pub type ShutdownResponder = oneshot::Sender<ShutdownReport>;
+
+// # Trait Description:
+// A trait implemented by every handle that can be parked in a communicator and driven manually.
+#[async_trait]
+pub trait PolledHandle: Send {
+    // # Method Description:
+    // This method processes every frame (and command) already pending for the handle, without waiting for new ones.
+    //
+    // # Returns:
+    // * The number of frames and commands processed.
+    async fn process_pending(&mut self) -> usize;
+}
+
+// # Struct Description:
+// This struct holds the parts of a communicator that are driven manually in `HandleMode::Polling`.
+// In `HandleMode::Spawned` it stays empty.
+//
+// # Fields:
+// * demultiplexer - The demultiplexer of the thread's channel, if it is not run by a task.
+// * handles - The handles initialized so far, in initialization order.
+pub struct PolledHandles {
+    demultiplexer: Option<Demultiplexer>,
+    handles: Vec<Box<dyn PolledHandle>>,
+}
+
+impl PolledHandles {
+    pub fn new(demultiplexer: Option<Demultiplexer>) -> Self {
+        Self {
+            demultiplexer,
+            handles: vec![],
+        }
+    }
+
+    // # Method Description:
+    // This method parks a handle so that it is processed by `drive`.
+    //
+    // # Parameters:
+    // * handle - The handle to park.
+    //
+    // # Returns:
+    // * An already completed `JoinHandle`, standing in for the task the handle would run in `HandleMode::Spawned`.
+    pub fn park(&mut self, handle: Box<dyn PolledHandle>) -> JoinHandle<()> {
+        self.handles.push(handle);
+        tokio::spawn(async {})
+    }
+
+    // # Method Description:
+    // This method processes every frame pending on the thread's channel and in the parked handles, once.
+    //
+    // # Returns:
+    // * The number of frames and commands processed.
+    async fn process_pending(&mut self) -> usize {
+        let mut processed = 0;
+        if let Some(demultiplexer) = &mut self.demultiplexer {
+            processed += demultiplexer.poll();
+        }
+        for handle in self.handles.iter_mut() {
+            processed += handle.process_pending().await;
+        }
+        processed
+    }
+}
+
+// # Function Description:
+// This function drives a communicator operated in `HandleMode::Polling`: it demultiplexes the frames
+// pending on the thread's channel, lets every parked handle process them, and stores the delivered
+// messages in the local queues, until nothing is left to process. It never waits for frames from other
+// threads, so a cluster is driven by calling it on every communicator until all of them return 0.
+// Frames the handles send to other threads are awaited, so channel buffers must be large enough to hold
+// the frames sent between two calls. In `HandleMode::Spawned`, it only stores the pending deliveries.
+//
+// # Parameters:
+// * communicator - The communicator to drive.
+//
+// # Returns:
+// * The number of frames and commands processed.
+pub async fn drive<T, C>(communicator: &mut C) -> usize
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: BasicCommunication<T>,
+{
+    let mut processed = 0;
+    loop {
+        let pending = communicator.get_polled_handles().process_pending().await + communicator.get_queues().store_pending();
+        if pending == 0 {
+            return processed
+        }
+        processed += pending;
+    }
+}
@@ -0,0 +1,574 @@
+use std::{collections::VecDeque, time::Duration};
+use bytes::Bytes;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Serialize, Deserialize};
+use tokio::{sync::mpsc::{self, Sender}, time::{self, Instant}};
+
+use crate::config::derive_seed;
+use crate::logging::log;
+
+// # Struct Description:
+// This struct describes the emulated network placed between the threads of a cluster. When it is set
+// in the `ClusterConfig`, every hub wires each pair of distinct threads through an emulated link
+// (see `emulate_links`) instead of handing out the raw channel transmitters. Frames a thread sends
+// to itself are never emulated.
+//
+// # Fields:
+// * regions - The optional region topology assigning threads to regions and links to latencies and bandwidths.
+// * conditions - The conditions of every link. With a region topology, the latency and bandwidth of a link
+//   are those of its regions, and only the jitter, reordering, drop rate, and duplication are taken from here.
+// * links - The conditions of individual links, by sending and receiving thread, overriding every other setting.
+// * stabilization - The global stabilization time of the network and the delay bound after it, if the network
+//   is partially synchronous.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct NetworkEmulation {
+    regions: Option<RegionTopology>,
+    #[serde(default)]
+    conditions: LinkConditions,
+    #[serde(default)]
+    links: Vec<(u32, u32, LinkConditions)>,
+    #[serde(default)]
+    stabilization: Option<Stabilization>,
+}
+
+impl NetworkEmulation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_regions(mut self, regions: RegionTopology) -> Self {
+        self.regions = Some(regions);
+        self
+    }
+
+    pub fn with_conditions(mut self, conditions: LinkConditions) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    // # Method Description:
+    // This method sets the conditions of the link from one thread to another, replacing those the link
+    // would get from the region topology and the conditions of every link.
+    //
+    // # Parameters:
+    // * sender - The ID of the sending thread.
+    // * receiver - The ID of the receiving thread.
+    // * conditions - The conditions of the link.
+    //
+    // # Returns:
+    // * The updated emulation.
+    pub fn with_link(mut self, sender: u32, receiver: u32, conditions: LinkConditions) -> Self {
+        self.links.retain(|(link_sender, link_receiver, _)| (*link_sender, *link_receiver) != (sender, receiver));
+        self.links.push((sender, receiver, conditions));
+        self
+    }
+
+    // # Method Description:
+    // This method makes the network partially synchronous (see `Stabilization`).
+    //
+    // # Parameters:
+    // * stabilization - The global stabilization time and the delay bound after it.
+    //
+    // # Returns:
+    // * The updated emulation.
+    pub fn with_stabilization(mut self, stabilization: Stabilization) -> Self {
+        self.stabilization = Some(stabilization);
+        self
+    }
+
+    pub fn get_regions(&self) -> Option<&RegionTopology> {
+        self.regions.as_ref()
+    }
+
+    pub fn get_stabilization(&self) -> Option<Stabilization> {
+        self.stabilization
+    }
+
+    pub fn get_conditions(&self) -> LinkConditions {
+        self.conditions
+    }
+
+    // # Method Description:
+    // This method computes the conditions of the link from one thread to another.
+    //
+    // # Parameters:
+    // * sender - The ID of the sending thread.
+    // * receiver - The ID of the receiving thread.
+    //
+    // # Returns:
+    // * The `LinkConditions` of the link.
+    pub fn get_link_conditions(&self, sender: u32, receiver: u32) -> LinkConditions {
+        if let Some((_, _, conditions)) = self.links.iter().find(|(link_sender, link_receiver, _)| (*link_sender, *link_receiver) == (sender, receiver)) {
+            return *conditions
+        }
+        match &self.regions {
+            Some(regions) => {
+                let region_conditions = regions.get_link_conditions(sender, receiver);
+                LinkConditions { latency: region_conditions.latency, bandwidth: region_conditions.bandwidth, ..self.conditions }
+            },
+            None => self.conditions,
+        }
+    }
+
+    // # Method Description:
+    // This method wires a thread's view of the cluster through emulated links. For every other thread,
+    // a `LinkConditioner` is spawned that receives the frames sent by `sender` and forwards them to the
+    // original transmitter once the link's conditions allow it; the transmitter to `sender` itself is kept
+    // as is. The random decisions of every link are drawn from its own stream of the experiment seed. With a
+    // `Stabilization`, the global stabilization time of every link is measured from its creation.
+    //
+    // # Parameters:
+    // * sender - The ID of the thread the transmitters are handed to.
+    // * transmitters - The raw transmitters of every thread, indexed by thread ID.
+    // * buffer_size - The capacity of the channel feeding each link.
+    // * seed - The experiment seed.
+    //
+    // # Returns:
+    // * A vector of transmitters, indexed by thread ID, to hand to the sending thread.
+    pub fn emulate_links(&self, sender: u32, transmitters: &[Sender<Bytes>], buffer_size: usize, seed: u64) -> Vec<Sender<Bytes>> {
+        let mut links = vec![];
+        for (receiver, transmitter) in transmitters.iter().enumerate() {
+            let receiver = receiver as u32;
+            if receiver == sender {
+                links.push(transmitter.clone());
+            } else {
+                let rng = StdRng::seed_from_u64(derive_seed(seed, sender, &format!("link {receiver}")));
+                let mut conditioner = LinkConditioner::new(self.get_link_conditions(sender, receiver), rng);
+                if let Some(stabilization) = self.stabilization {
+                    conditioner = conditioner.with_stabilization(stabilization);
+                }
+                links.push(conditioner.wrap(transmitter.clone(), buffer_size));
+            }
+        }
+        links
+    }
+}
+
+// # Struct Description:
+// This struct describes the conditions of a single directed link.
+//
+// # Fields:
+// * latency - The one-way propagation delay added to every frame.
+// * bandwidth - The capacity of the link in bytes per second, or `None` for an unlimited link.
+//   Frames are serialized onto the link one after the other, so a burst queues behind earlier frames.
+// * jitter - The bound of the random delay added to the latency of every frame, drawn uniformly.
+// * reordering - The number of frames out of a million that are delivered as soon as their own delay
+//   elapsed, overtaking earlier frames still in flight; the other frames keep their sending order.
+// * drop_rate - The number of frames out of a million that are lost.
+// * duplication - The number of frames out of a million that are delivered several times.
+// * copies - The number of times a duplicated frame is delivered, at least 2.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct LinkConditions {
+    latency: Duration,
+    bandwidth: Option<u64>,
+    #[serde(default)]
+    jitter: Duration,
+    #[serde(default)]
+    reordering: u32,
+    #[serde(default)]
+    drop_rate: u32,
+    #[serde(default)]
+    duplication: u32,
+    #[serde(default)]
+    copies: u32,
+}
+
+impl LinkConditions {
+    pub fn new(latency: Duration, bandwidth: Option<u64>) -> Self {
+        Self {
+            latency,
+            bandwidth,
+            jitter: Duration::ZERO,
+            reordering: 0,
+            drop_rate: 0,
+            duplication: 0,
+            copies: 0
+        }
+    }
+
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    // # Method Description:
+    // This method sets the fraction of the frames that may overtake earlier frames, e.g. `0.05` for 5%.
+    //
+    // # Parameters:
+    // * fraction - The fraction of the frames, clamped to `0.0..=1.0`.
+    //
+    // # Returns:
+    // * The updated conditions.
+    pub fn with_reordering(mut self, fraction: f64) -> Self {
+        self.reordering = per_million(fraction);
+        self
+    }
+
+    // # Method Description:
+    // This method sets the fraction of the frames that are lost, e.g. `0.01` for 1%.
+    //
+    // # Parameters:
+    // * fraction - The fraction of the frames, clamped to `0.0..=1.0`.
+    //
+    // # Returns:
+    // * The updated conditions.
+    pub fn with_drop_rate(mut self, fraction: f64) -> Self {
+        self.drop_rate = per_million(fraction);
+        self
+    }
+
+    // # Method Description:
+    // This method sets the fraction of the frames that are delivered several times, e.g. `0.1` for 10%,
+    // as a network retransmitting frames whose acknowledgement was lost would.
+    //
+    // # Parameters:
+    // * fraction - The fraction of the frames, clamped to `0.0..=1.0`.
+    // * copies - The number of times a duplicated frame is delivered, raised to 2 if lower.
+    //
+    // # Returns:
+    // * The updated conditions.
+    pub fn with_duplication(mut self, fraction: f64, copies: u32) -> Self {
+        self.duplication = per_million(fraction);
+        self.copies = copies.max(2);
+        self
+    }
+
+    pub fn get_latency(&self) -> Duration {
+        self.latency
+    }
+
+    pub fn get_bandwidth(&self) -> Option<u64> {
+        self.bandwidth
+    }
+
+    pub fn get_jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    pub fn get_reordering(&self) -> f64 {
+        self.reordering as f64 / 1_000_000.0
+    }
+
+    pub fn get_drop_rate(&self) -> f64 {
+        self.drop_rate as f64 / 1_000_000.0
+    }
+
+    pub fn get_duplication(&self) -> f64 {
+        self.duplication as f64 / 1_000_000.0
+    }
+
+    pub fn get_copies(&self) -> u32 {
+        self.copies
+    }
+
+    // # Method Description:
+    // This method computes the time needed to serialize a frame onto the link.
+    //
+    // # Parameters:
+    // * size - The size of the frame in bytes.
+    //
+    // # Returns:
+    // * The transmission time, `Duration::ZERO` on an unlimited link.
+    pub fn transmission_time(&self, size: usize) -> Duration {
+        match self.bandwidth {
+            Some(bandwidth) => Duration::from_secs_f64(size as f64 / bandwidth.max(1) as f64),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+// # Struct Description:
+// This struct models a partially synchronous network: until the global stabilization time (GST), frames are
+// delayed and lost by an adversary, whose latency, jitter, and drop rate are scripted by its `LinkConditions`
+// (by default, the conditions of the link); from GST on, no frame is lost, and every frame is delivered
+// within a known bound. As in the model of Dwork, Lynch, and Stockmeyer, a frame sent before GST that is not
+// lost is delivered by GST plus the bound at the latest, so that liveness can be measured from GST.
+//
+// # Fields:
+// * gst - The global stabilization time, measured from the creation of the links.
+// * bound - The bound of the delay of every frame after GST.
+// * adversary - The conditions of the links before GST, if they differ from the conditions of the links.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Stabilization {
+    gst: Duration,
+    bound: Duration,
+    adversary: Option<LinkConditions>,
+}
+
+impl Stabilization {
+    pub fn new(gst: Duration, bound: Duration) -> Self {
+        Self {
+            gst,
+            bound,
+            adversary: None
+        }
+    }
+
+    pub fn with_adversary(mut self, adversary: LinkConditions) -> Self {
+        self.adversary = Some(adversary);
+        self
+    }
+
+    pub fn get_gst(&self) -> Duration {
+        self.gst
+    }
+
+    pub fn get_bound(&self) -> Duration {
+        self.bound
+    }
+
+    pub fn get_adversary(&self) -> Option<LinkConditions> {
+        self.adversary
+    }
+}
+
+// # Struct Description:
+// This struct assigns the threads of a cluster to geographic regions and describes the links between
+// regions, so that experiments can be run over WAN-like topologies. Matrices are indexed by
+// [sending region][receiving region]; the diagonal describes links within a region.
+//
+// # Fields:
+// * assignments - The region of every thread, indexed by thread ID.
+// * latencies - The one-way latency between every pair of regions.
+// * bandwidths - The bandwidth (in bytes per second) between every pair of regions, `None` for unlimited.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RegionTopology {
+    assignments: Vec<u32>,
+    latencies: Vec<Vec<Duration>>,
+    bandwidths: Vec<Vec<Option<u64>>>,
+}
+
+impl RegionTopology {
+    // # Method Description:
+    // This method creates a topology with unlimited bandwidth between every pair of regions.
+    //
+    // # Parameters:
+    // * assignments - The region of every thread, indexed by thread ID.
+    // * latencies - A square matrix of one-way latencies between regions.
+    //
+    // # Panics:
+    // * If the latency matrix is not square, or a thread is assigned to a region outside of it.
+    pub fn new(assignments: Vec<u32>, latencies: Vec<Vec<Duration>>) -> Self {
+        let region_count = latencies.len();
+        if latencies.iter().any(|row| row.len() != region_count) {
+            panic!("Error: the inter-region latency matrix must be square");
+        }
+        if assignments.iter().any(|region| *region as usize >= region_count) {
+            panic!("Error: a thread is assigned to a region outside of the latency matrix");
+        }
+        let bandwidths = vec![vec![None; region_count]; region_count];
+
+        Self {
+            assignments,
+            latencies,
+            bandwidths
+        }
+    }
+
+    // # Method Description:
+    // This method creates a topology assigning threads to regions in a round-robin fashion
+    // (thread `i` is placed in region `i % region_count`).
+    //
+    // # Parameters:
+    // * thread_count - The number of threads in the cluster.
+    // * latencies - A square matrix of one-way latencies between regions.
+    pub fn round_robin(thread_count: u32, latencies: Vec<Vec<Duration>>) -> Self {
+        let region_count = latencies.len().max(1) as u32;
+        let assignments = (0..thread_count).map(|id| id % region_count).collect();
+        Self::new(assignments, latencies)
+    }
+
+    // # Method Description:
+    // This method sets the bandwidth between every pair of regions.
+    //
+    // # Parameters:
+    // * bandwidths - A square matrix of bandwidths in bytes per second, `None` for unlimited links.
+    //
+    // # Panics:
+    // * If the bandwidth matrix does not have the dimensions of the latency matrix.
+    pub fn with_bandwidths(mut self, bandwidths: Vec<Vec<Option<u64>>>) -> Self {
+        let region_count = self.latencies.len();
+        if bandwidths.len() != region_count || bandwidths.iter().any(|row| row.len() != region_count) {
+            panic!("Error: the inter-region bandwidth matrix must match the latency matrix");
+        }
+        self.bandwidths = bandwidths;
+        self
+    }
+
+    pub fn get_region(&self, id: u32) -> u32 {
+        match self.assignments.get(id as usize) {
+            Some(region) => *region,
+            None => panic!("Error: thread {} is not assigned to a region", id),
+        }
+    }
+
+    pub fn get_region_count(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn get_link_conditions(&self, sender: u32, receiver: u32) -> LinkConditions {
+        let sending_region = self.get_region(sender) as usize;
+        let receiving_region = self.get_region(receiver) as usize;
+        LinkConditions::new(self.latencies[sending_region][receiving_region], self.bandwidths[sending_region][receiving_region])
+    }
+}
+
+// # Function Description:
+// This function converts a fraction to a number of occurrences out of a million.
+//
+// # Parameters:
+// * fraction - The fraction, clamped to `0.0..=1.0`.
+//
+// # Returns:
+// * The number of occurrences out of a million.
+fn per_million(fraction: f64) -> u32 {
+    (fraction.clamp(0.0, 1.0) * 1_000_000.0).round() as u32
+}
+
+// # Struct Description:
+// This struct emulates a single directed link: it is wrapped around the transmitter of the receiving thread
+// (see `wrap`), and injects the latency, bandwidth, jitter, reordering, losses, and duplicates of its `LinkConditions`
+// into the frames sent through it before they are delivered.
+//
+// # Fields:
+// * conditions - The conditions of the link.
+// * stabilization - The global stabilization time of the link and the delay bound after it, if any.
+// * rng - The source of the random jitter, reordering, losses, and duplicates of the link.
+pub struct LinkConditioner {
+    conditions: LinkConditions,
+    stabilization: Option<Stabilization>,
+    rng: StdRng,
+}
+
+impl LinkConditioner {
+    pub fn new(conditions: LinkConditions, rng: StdRng) -> Self {
+        Self {
+            conditions,
+            stabilization: None,
+            rng
+        }
+    }
+
+    pub fn with_stabilization(mut self, stabilization: Stabilization) -> Self {
+        self.stabilization = Some(stabilization);
+        self
+    }
+
+    pub fn get_conditions(&self) -> LinkConditions {
+        self.conditions
+    }
+
+    // # Method Description:
+    // This method spawns the task emulating the link. Frames are timestamped on arrival, lost according
+    // to the drop rate, serialized onto the link according to its bandwidth, and forwarded to the
+    // destination once their latency and jitter elapsed. Frames keep their sending order, as on a TCP
+    // connection, unless they are drawn to be reordered: those are delivered as soon as their own delay
+    // elapsed, possibly before earlier frames. A frame drawn to be duplicated is serialized onto the link
+    // again for every extra copy, and every copy is delivered after its own latency and jitter, regardless
+    // of the order of the other frames. With a `Stabilization`, frames sent before GST follow the
+    // conditions of the adversary, and every frame is delivered by GST or its sending time, whichever is
+    // later, plus the bound; frames sent from GST on are never lost.
+    //
+    // # Parameters:
+    // * destination - The transmitter of the receiving thread.
+    // * buffer_size - The capacity of the channel feeding the link.
+    //
+    // # Returns:
+    // * The transmitter feeding the link.
+    pub fn wrap(mut self, destination: Sender<Bytes>, buffer_size: usize) -> Sender<Bytes> {
+        let (link_tx, mut link_rx) = mpsc::channel::<Bytes>(buffer_size);
+        let stabilization = self.stabilization;
+        let gst = stabilization.map(|stabilization| Instant::now() + stabilization.gst);
+
+        tokio::spawn(async move {
+            // frames in flight, by delivery time, frames of equal time in sending order
+            let mut in_flight: VecDeque<(Instant, Bytes)> = VecDeque::new();
+            let mut link_free = Instant::now();
+            let mut last_ordered = Instant::now();
+            let mut closed = false;
+
+            loop {
+                let next_delivery = in_flight.front().map(|(deliver_at, _)| *deliver_at);
+                if closed && next_delivery.is_none() {
+                    break;
+                }
+
+                tokio::select! {
+                    received = link_rx.recv(), if !closed => {
+                        match received {
+                            Some(frame) => {
+                                let now = Instant::now();
+                                let (conditions, deadline) = match (stabilization, gst) {
+                                    (Some(stabilization), Some(gst)) if now < gst => (stabilization.adversary.unwrap_or(self.conditions), Some(gst + stabilization.bound)),
+                                    (Some(stabilization), _) => (LinkConditions { drop_rate: 0, ..self.conditions }, Some(now + stabilization.bound)),
+                                    _ => (self.conditions, None),
+                                };
+                                if self.draw(conditions.drop_rate) {
+                                    log!(trace, "link dropped a frame of {} bytes", frame.len());
+                                    continue;
+                                }
+                                let start = link_free.max(now);
+                                link_free = start + conditions.transmission_time(frame.len());
+                                let mut deliver_at = link_free + conditions.get_latency() + self.draw_jitter(conditions.jitter);
+                                if let Some(deadline) = deadline {
+                                    deliver_at = deliver_at.min(deadline);
+                                }
+                                if !self.draw(conditions.reordering) {
+                                    deliver_at = deliver_at.max(last_ordered);
+                                    last_ordered = deliver_at;
+                                }
+                                let copies = if self.draw(conditions.duplication) { conditions.copies.max(2) } else { 1 };
+                                for _ in 1..copies {
+                                    log!(trace, "link duplicated a frame of {} bytes", frame.len());
+                                    link_free += conditions.transmission_time(frame.len());
+                                    let mut copy_at = link_free + conditions.get_latency() + self.draw_jitter(conditions.jitter);
+                                    if let Some(deadline) = deadline {
+                                        copy_at = copy_at.min(deadline);
+                                    }
+                                    let position = in_flight.partition_point(|(in_flight_at, _)| *in_flight_at <= copy_at);
+                                    in_flight.insert(position, (copy_at, frame.clone()));
+                                }
+                                let position = in_flight.partition_point(|(in_flight_at, _)| *in_flight_at <= deliver_at);
+                                in_flight.insert(position, (deliver_at, frame));
+                            },
+                            None => closed = true,
+                        }
+                    }
+                    _ = time::sleep_until(next_delivery.unwrap_or_else(Instant::now)), if next_delivery.is_some() => {
+                        if let Some((_, frame)) = in_flight.pop_front() {
+                            let _ = destination.send(frame).await;
+                        }
+                    }
+                }
+            }
+        });
+        link_tx
+    }
+
+    // # Method Description:
+    // This method draws whether an event of the given rate occurs for a frame.
+    //
+    // # Parameters:
+    // * rate - The number of occurrences out of a million.
+    //
+    // # Returns:
+    // * Whether the event occurs.
+    fn draw(&mut self, rate: u32) -> bool {
+        rate > 0 && self.rng.gen_range(0..1_000_000) < rate
+    }
+
+    // # Method Description:
+    // This method draws the jitter of a frame.
+    //
+    // # Parameters:
+    // * jitter - The jitter of the link when the frame was sent.
+    //
+    // # Returns:
+    // * A delay drawn uniformly between zero and the jitter.
+    fn draw_jitter(&mut self, jitter: Duration) -> Duration {
+        match jitter.as_nanos() as u64 {
+            0 => Duration::ZERO,
+            jitter => Duration::from_nanos(self.rng.gen_range(0..=jitter)),
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use std::{fmt::Debug, hash::Hash};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+use tokio::sync::broadcast;
+
+use crate::capabilities::CapabilityMismatch;
+use crate::divergence::DivergenceSuspicion;
+use crate::reliable::{ObjectContent, ReliableCommunication, Signal};
+use crate::basic::{Message, MessageChannels};
+use crate::json::{Frame, FrameKind, JsonConversion, frame_kinds};
+use crate::metrics::Metrics;
+use crate::multiplex::Lane;
+use crate::round::{Instance, Round};
+use crate::logging::log;
+
+// The number of leading characters of an undecodable frame kept in its `DecodeFailure`.
+const FRAME_PREFIX_LENGTH: usize = 64;
+
+// # Enum Description:
+// This enum represents a noteworthy occurrence observed by a thread's background handles.
+// Events are published on the communicator's event channel, so that an application (or an
+// experiment script) can react to protocol anomalies without the handles panicking.
+//
+// # Variants:
+// * DuplicateInput - A second Input signal was received for an instance that already had one.
+//   Carries the ID of the observing thread, the instance ID, and the content of the rejected Input.
+// * Equivocation - Two Input signals with differing content were received for the same instance.
+// * RevealMismatch - A value revealed in a commit-reveal witness round did not match the digest its
+//   sender committed to, and was discarded. Carries the ID of the observing thread, the rejected
+//   reveal, and the committed digest.
+// * DecodeFailure - A frame received by a handle could not be decoded, and was discarded.
+// * InvalidSignature - A signal was not signed by the thread it claims to come from, under
+//   `Authentication::Ed25519`, and was discarded. Carries the ID of the observing thread and the signal.
+// * NonMember - A signal named a thread that is not a member of the configured membership, and was
+//   discarded. Carries the ID of the observing thread, the ID of the non-member, and the signal.
+// * CapabilityMismatch - A reliable handle delivered content of a protocol its communicator does not run
+//   (e.g. a report from a witness peer to a reliable communicator), and discarded it.
+// * DivergenceSuspected - The digest of a witness round gossiped by a peer kept differing from the digest
+//   of the round held by the observing thread (see `DigestGossip`).
+// * AbandonedCollect - A caller stopped waiting for the collection of a round before it was delivered (e.g.
+//   `witness_collect_with_timeout` timed out, or the collecting future lost a `select!`). The collection is
+//   kept for the next collect of the round.
+// * InvalidPayload - The payload of an instance failed the external validity predicate of the observing
+//   thread, which neither echoes, votes for, nor delivers it. Carries the ID of the observing thread, the
+//   instance ID, and the rejected message.
+// * RoundCollected - A handle dropped the state of a delivered round following its `RetentionPolicy`, after
+//   which the late frames of the round are ignored. Carries the ID of the observing thread, the protocol of
+//   the handle, and the round.
+// * NonLeaderInput - An Input signal for a "reliable" instance was sent by a thread that is not the leader of
+//   its round under the configured `LeaderElection`, and was rejected. Carries the ID of the observing thread,
+//   the ID of the leader of the round, and the rejected message.
+// * ViewChanged - A round moved to a new view, as a quorum of threads saw an instance of the round stay
+//   undelivered for the timeout of their pacemaker. Carries the ID of the observing thread, the round, and the view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Event<T> {
+    DuplicateInput(u32, String, ObjectContent<T>),
+    Equivocation(EquivocationEvidence<T>),
+    RevealMismatch(u32, Message<T>, u64),
+    DecodeFailure(DecodeFailure),
+    InvalidSignature(u32, Signal<T>),
+    NonMember(u32, u32, Signal<T>),
+    CapabilityMismatch(CapabilityMismatch),
+    DivergenceSuspected(DivergenceSuspicion),
+    AbandonedCollect(AbandonedCollect),
+    InvalidPayload(u32, String, Message<T>),
+    RoundCollected(u32, String, Round),
+    NonLeaderInput(u32, u32, Message<T>),
+    ViewChanged(u32, Round, u32),
+}
+
+impl<T> Event<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Method Description:
+    // This method reads the round an event belongs to, e.g. to follow a single round (see
+    // `ReliableCommunication::subscribe_round`).
+    //
+    // # Returns:
+    // * The round of the event, or `None` for a capability mismatch, which concerns a peer rather than a
+    //   round, and for a decode failure whose round could not be read from the frame.
+    pub fn get_round_number(&self) -> Option<Round> {
+        match self {
+            Event::DuplicateInput(_, _, content) => Some(content.get_round_number()),
+            Event::Equivocation(evidence) => Some(evidence.get_first().get_round_number()),
+            Event::RevealMismatch(_, reveal, _) => Some(reveal.get_round_number()),
+            Event::DecodeFailure(failure) => failure.get_round_number(),
+            Event::InvalidSignature(_, signal) => Some(signal.get_round_number()),
+            Event::NonMember(_, _, signal) => Some(signal.get_round_number()),
+            Event::CapabilityMismatch(_) => None,
+            Event::DivergenceSuspected(suspicion) => Some(suspicion.get_round_number()),
+            Event::AbandonedCollect(abandoned) => Some(abandoned.get_round_number()),
+            Event::InvalidPayload(_, _, message) => Some(message.get_round_number()),
+            Event::RoundCollected(_, _, round_number) => Some(*round_number),
+            Event::NonLeaderInput(_, _, message) => Some(message.get_round_number()),
+            Event::ViewChanged(_, round_number, _) => Some(*round_number),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is the evidence that the creator of an instance equivocated, i.e. sent two
+// Input signals with differing content under the same instance ID. It holds both contents so
+// that the evidence can be forwarded or serialized and checked independently.
+//
+// # Fields:
+// * id - The ID of the thread that observed the equivocation.
+// * instance_id - The instance ID shared by both Inputs.
+// * first - The content of the Input that was accepted.
+// * second - The content of the conflicting Input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivocationEvidence<T> {
+    id: u32,
+    instance_id: String,
+    first: ObjectContent<T>,
+    second: ObjectContent<T>,
+}
+
+impl<T> EquivocationEvidence<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(id: u32, instance_id: String, first: ObjectContent<T>, second: ObjectContent<T>) -> Self {
+        Self {
+            id,
+            instance_id,
+            first,
+            second
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_instance_id(&self) -> &String {
+        &self.instance_id
+    }
+
+    pub fn get_first(&self) -> &ObjectContent<T> {
+        &self.first
+    }
+
+    pub fn get_second(&self) -> &ObjectContent<T> {
+        &self.second
+    }
+}
+
+impl<T> JsonConversion<EquivocationEvidence<T>> for EquivocationEvidence<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{}
+
+// # Enum Description:
+// This enum represents how a thread reacts to frames its handles cannot decode. Whatever the policy,
+// every such frame is published as `Event::DecodeFailure` and counted in the thread's metrics.
+//
+// # Variants:
+// * Discard - The frame is discarded, and the protocols carry on as if it had never been received.
+// * Strict - The frame is discarded, and the round it belongs to is aborted: collecting the round
+//   (e.g. `try_witness_collect`) returns the `DecodeFailure` instead of waiting for a delivery that may
+//   never come. A failure whose round cannot be read from the frame aborts every round not yet collected.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum DecodePolicy {
+    #[default]
+    Discard,
+    Strict,
+}
+
+// # Struct Description:
+// This struct describes a frame a thread's handle could not decode. The sender and round are read from
+// the payload of the frame on a best-effort basis, as they are only known if it is well-formed JSON.
+//
+// # Fields:
+// * id - The ID of the thread that received the frame.
+// * lane - The lane the frame was received on.
+// * source - The ID of the thread that sent the frame, if it could be read.
+// * round_number - The round the frame belongs to, if it could be read.
+// * frame_size - The size of the frame, in bytes.
+// * prefix - The first characters of the payload of the frame.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DecodeFailure {
+    id: u32,
+    lane: Lane,
+    source: Option<u32>,
+    round_number: Option<Round>,
+    frame_size: usize,
+    prefix: String,
+}
+
+impl DecodeFailure {
+    pub fn new(id: u32, lane: Lane, frame: &Bytes) -> Self {
+        let payload = Frame::parse(frame).map(|frame| frame.get_payload().clone()).unwrap_or_else(|_| frame.clone());
+        let value: Option<Value> = serde_json::from_slice(&payload).ok();
+        let source = value.as_ref().and_then(|value| find_number(value, "id"));
+        let round_number = value.as_ref().and_then(|value| find_number(value, "round_number")).map(Round);
+        Self {
+            id,
+            lane,
+            source,
+            round_number,
+            frame_size: frame.len(),
+            prefix: String::from_utf8_lossy(&payload).chars().take(FRAME_PREFIX_LENGTH).collect(),
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_lane(&self) -> Lane {
+        self.lane
+    }
+
+    pub fn get_source(&self) -> Option<u32> {
+        self.source
+    }
+
+    pub fn get_round_number(&self) -> Option<Round> {
+        self.round_number
+    }
+
+    pub fn get_frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    pub fn get_prefix(&self) -> &String {
+        &self.prefix
+    }
+
+    // # Method Description:
+    // This method checks whether the failure aborts a round under `DecodePolicy::Strict`.
+    //
+    // # Parameters:
+    // * round_number - The round to check.
+    //
+    // # Returns:
+    // * `true` if the failure belongs to the round, or if its round is unknown.
+    pub fn aborts_round(&self, round_number: Round) -> bool {
+        self.round_number.is_none_or(|aborted| aborted == round_number)
+    }
+}
+
+impl JsonConversion<DecodeFailure> for DecodeFailure {}
+
+impl FrameKind for DecodeFailure {
+    const FRAME_KIND: u64 = frame_kinds::DECODE_FAILURE_FRAME;
+}
+
+// # Struct Description:
+// This struct describes the collection of a round a caller stopped waiting for, published as
+// `Event::AbandonedCollect` when the collecting future is dropped.
+//
+// # Fields:
+// * id - The ID of the thread whose caller abandoned the collection.
+// * protocol_information - The protocol of the round.
+// * instance_number - The instance of the round, if any.
+// * round_number - The round.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AbandonedCollect {
+    id: u32,
+    protocol_information: String,
+    instance_number: Option<Instance>,
+    round_number: Round,
+}
+
+impl AbandonedCollect {
+    pub fn new(id: u32, protocol_information: String, instance_number: Option<Instance>, round_number: Round) -> Self {
+        Self {
+            id,
+            protocol_information,
+            instance_number,
+            round_number
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_instance_number(&self) -> Option<Instance> {
+        self.instance_number
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+}
+
+impl JsonConversion<AbandonedCollect> for AbandonedCollect {}
+
+// # Function Description:
+// This function looks for a numeric field in a decoded JSON value, breadth-first, so that the field of
+// a frame is preferred over the field of the objects it wraps (e.g. the round of a `Signal` over the
+// round of its content).
+//
+// # Parameters:
+// * value - The decoded JSON value.
+// * key - The name of the field.
+//
+// # Returns:
+// * The value of the shallowest field named `key` that fits in a `u32`, if any.
+fn find_number(value: &Value, key: &str) -> Option<u32> {
+    let mut level = vec![value];
+    while !level.is_empty() {
+        let mut next = vec![];
+        for object in level.iter().filter_map(|value| value.as_object()) {
+            if let Some(number) = object.get(key).and_then(Value::as_u64).and_then(|number| u32::try_from(number).ok()) {
+                return Some(number)
+            }
+            next.extend(object.values());
+        }
+        level = next;
+    }
+    None
+}
+
+// # Struct Description:
+// This struct reports the frames a handle cannot decode: it publishes them as `Event::DecodeFailure`,
+// counts them in the communicator's metrics, and, under `DecodePolicy::Strict`, aborts their round by
+// notifying the thread's own `BasicQueues`, where collect calls are waiting.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * lane - The lane the handle reads.
+// * decode_policy - How the thread reacts to undecodable frames.
+// * thread_channel - The channels on which aborted rounds are notified.
+// * event_channel - The channel on which `Event::DecodeFailure` events are published.
+// * metrics - The metrics of the communicator, in which failures are counted.
+pub(crate) struct DecodeFailureReporter<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    thread_id: u32,
+    lane: Lane,
+    decode_policy: DecodePolicy,
+    thread_channel: MessageChannels<T>,
+    event_channel: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+}
+
+impl<T> DecodeFailureReporter<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    pub(crate) fn new<C: ReliableCommunication<T>>(communicator: &C, lane: Lane) -> Self {
+        Self {
+            thread_id: *communicator.get_id(),
+            lane,
+            decode_policy: communicator.get_config().get_decode_policy(),
+            thread_channel: communicator.get_channels().clone(),
+            event_channel: communicator.get_event_channel().clone(),
+            metrics: communicator.get_metrics().clone(),
+        }
+    }
+
+    // # Method Description:
+    // This method reports a frame the handle could not decode.
+    //
+    // # Parameters:
+    // * frame - The undecodable frame.
+    pub(crate) async fn report(&self, frame: &Bytes) {
+        let failure = DecodeFailure::new(self.thread_id, self.lane, frame);
+        log!(warn, { node = self.thread_id, lane = ?self.lane }, "id: {}, discarding undecodable frame: {:?}", self.thread_id, failure);
+        self.metrics.record_decode_failure(self.lane);
+        if self.decode_policy == DecodePolicy::Strict {
+            self.thread_channel.send_decode_failure(self.thread_id, failure.clone()).await;
+        }
+        let _ = self.event_channel.send(Event::DecodeFailure(failure));
+    }
+}
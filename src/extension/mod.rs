@@ -0,0 +1,389 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash, sync::{Arc, RwLock}};
+use serde::{Serialize, de::DeserializeOwned};
+use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+use crate::capabilities::CapabilityMismatch;
+use crate::events::Event;
+use crate::logging::log;
+use crate::reliable::{ObjectContent, ReliableCommunication, ReliableInstanceMonitor, Signal, SignalChannels, SignalFrame, SignalType};
+use crate::round::{Instance, Round};
+
+// The key of the processor of the extension frames, whatever their type ID (see `ProcessorRegistry`).
+pub(crate) const EXTENSION: &str = "extension";
+
+// # Trait Description:
+// This trait extends `ReliableCommunication` with the broadcast of the frame types that plugins introduce
+// (see `ExtensionFrame`). It is implemented by every reliable communicator.
+pub trait ExtensionCommunication<T>: ReliableCommunication<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // This method reliably broadcasts a frame of a type introduced by a plugin, as an
+    // `ObjectContent::Extension`. Every thread delivering it hands it to the handler registered for its
+    // type (see `register_extension`), or discards it if none is registered.
+    //
+    // # Parameters:
+    // * frame - The frame.
+    // * instance_number - The instance number of the broadcast.
+    // * round_number - The round number of the broadcast.
+    fn reliable_broadcast_extension<E: ExtensionFrame>(&mut self, frame: &E, instance_number: Instance, round_number: Round) -> impl Future<Output = ()> {
+        let input = Signal::new(SignalType::Input, frame.to_content(*self.get_id(), round_number), instance_number, round_number);
+        self.get_signal_channels().broadcast_signal(input)
+    }
+
+    // # Method Description:
+    // This method registers the handler to which the reliable handle hands the frames of a type introduced
+    // by a plugin as it delivers them. Frames delivered before their handler is registered are discarded.
+    //
+    // # Parameters:
+    // * handler - The function every delivered frame of the type is handed to.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why the handler was not registered.
+    fn register_extension<E, F>(&self, handler: F) -> Result<(), String>
+    where
+        E: ExtensionFrame,
+        F: Fn(ExtensionDelivery<E>) + Send + Sync + 'static,
+    {
+        self.get_delivery_watches().get_extensions().register(handler)
+    }
+}
+
+impl<T, C> ExtensionCommunication<T> for C
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T>,
+{
+}
+
+// # Trait Description:
+// A trait implemented by the frame types that plugins introduce without touching the core modules. A
+// frame is reliably broadcast as an `ObjectContent::Extension` holding its type ID and its JSON bytes
+// (see `ExtensionCommunication::reliable_broadcast_extension`), and every reliable handle delivering it
+// hands it, decoded, to the handler registered for its type (see `ExtensionRegistry::register`).
+pub trait ExtensionFrame: Serialize + DeserializeOwned + Send + 'static {
+    // The ID of the frame type, unique across the plugins of a cluster.
+    const TYPE_ID: &'static str;
+
+    // # Method Description:
+    // This method wraps the frame into the content of a signal.
+    //
+    // # Parameters:
+    // * id - The ID of the thread broadcasting the frame.
+    // * round_number - The round the frame is broadcast in.
+    //
+    // # Returns:
+    // * The `ObjectContent::Extension` holding the frame.
+    fn to_content<T>(&self, id: u32, round_number: Round) -> ObjectContent<T> {
+        let bytes = serde_json::to_vec(self).expect("Error: extension frame could not be encoded");
+        ObjectContent::Extension { type_id: String::from(Self::TYPE_ID), id, round_number, bytes }
+    }
+}
+
+// # Struct Description:
+// This struct is an extension frame delivered to the handler of its type.
+//
+// # Fields:
+// * id - The ID of the thread that broadcast the frame.
+// * instance_number - The instance the frame was delivered in.
+// * round_number - The round the frame was delivered in.
+// * frame - The decoded frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionDelivery<E> {
+    id: u32,
+    instance_number: Instance,
+    round_number: Round,
+    frame: E,
+}
+
+impl<E> ExtensionDelivery<E> {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_instance_number(&self) -> Instance {
+        self.instance_number
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_frame(&self) -> &E {
+        &self.frame
+    }
+
+    pub fn into_frame(self) -> E {
+        self.frame
+    }
+}
+
+// The handler of a frame type, with the frame type erased: it decodes the bytes of a frame itself.
+type ErasedHandler = Arc<dyn Fn(u32, Instance, Round, &[u8]) -> Result<(), String> + Send + Sync>;
+
+// # Struct Description:
+// This struct holds the handlers of the extension frame types, by type ID, shared between a communicator
+// and its reliable handle (see `DeliveryWatches::get_extensions`). Handlers erase the type of their
+// frames, so that the handle delivers frame types it was not compiled against. Handlers run on the
+// reliable handle, and should hand the frames over (e.g. to a channel) rather than block.
+//
+// # Fields:
+// * handlers - The handler of every registered frame type, by type ID.
+#[derive(Clone, Default)]
+pub struct ExtensionRegistry {
+    handlers: Arc<RwLock<HashMap<String, ErasedHandler>>>,
+}
+
+impl Debug for ExtensionRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtensionRegistry").field("type_ids", &self.get_type_ids()).finish()
+    }
+}
+
+impl ExtensionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // # Method Description:
+    // This method registers the handler of a frame type.
+    //
+    // # Parameters:
+    // * handler - The function every delivered frame of the type is handed to.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why the handler was not registered: another handler is already
+    //   registered for the type ID.
+    pub fn register<E, F>(&self, handler: F) -> Result<(), String>
+    where
+        E: ExtensionFrame,
+        F: Fn(ExtensionDelivery<E>) + Send + Sync + 'static,
+    {
+        let mut handlers = self.handlers.write().unwrap();
+        if handlers.contains_key(E::TYPE_ID) {
+            return Err(format!("a handler is already registered for the extension \"{}\"", E::TYPE_ID))
+        }
+        let erased: ErasedHandler = Arc::new(move |id, instance_number, round_number, bytes: &[u8]| {
+            let frame = serde_json::from_slice::<E>(bytes)
+                .map_err(|error| format!("extension \"{}\" by id: {id} could not be decoded: {error}", E::TYPE_ID))?;
+            handler(ExtensionDelivery { id, instance_number, round_number, frame });
+            Ok(())
+        });
+        handlers.insert(String::from(E::TYPE_ID), erased);
+        Ok(())
+    }
+
+    pub fn is_registered(&self, type_id: &str) -> bool {
+        self.handlers.read().unwrap().contains_key(type_id)
+    }
+
+    pub fn get_type_ids(&self) -> Vec<String> {
+        let mut type_ids: Vec<String> = self.handlers.read().unwrap().keys().cloned().collect();
+        type_ids.sort();
+        type_ids
+    }
+
+    // # Method Description:
+    // This method hands a delivered extension frame to the handler of its type.
+    //
+    // # Parameters:
+    // * content - The delivered content.
+    // * instance_number - The instance the content was delivered in.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why the frame was not handled: the content is not an extension,
+    //   no handler is registered for its type, or its bytes do not decode as the type.
+    pub fn dispatch<T>(&self, content: &ObjectContent<T>, instance_number: Instance) -> Result<(), String>
+    where
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let ObjectContent::Extension { type_id, id, round_number, bytes } = content else {
+            return Err(format!("{} content by id: {} is not an extension", content.get_protocol_information(), content.get_id()))
+        };
+        // the handler is cloned out of the lock, so that it may register further handlers
+        let handler = self.handlers.read().unwrap().get(type_id).cloned();
+        match handler {
+            Some(handler) => handler(*id, instance_number, *round_number, bytes),
+            None => Err(format!("extension \"{type_id}\" by id: {id}, for which no handler is registered")),
+        }
+    }
+}
+
+// # Enum Description:
+// This enum represents what the reliable handle does with a signal once the processor of its protocol
+// processed it (see `InstanceProcessor::process`).
+//
+// # Variants:
+// * Count - The signal is counted towards the quorums of its instance, as in reliable broadcast.
+// * Ignore - The processor handled the signal, and the handle does nothing more with it.
+// * Deliver - The processor delivered the instance. The handle queues its content for the communicator if
+//   `true`, and records the delivery.
+pub(crate) enum Processing {
+    Count,
+    Ignore,
+    Deliver(bool),
+}
+
+// # Enum Description:
+// This enum represents how the reliable handle delivers an instance whose quorum is reached (see
+// `InstanceProcessor::deliver`).
+//
+// # Variants:
+// * Queue - The content is queued for the communicator, or handed to the protocol running on top, as in
+//   reliable broadcast.
+// * Delivered - The processor delivered the content itself.
+// * Pending - The instance cannot be delivered yet, and is tried again on its next signal.
+pub(crate) enum Delivery {
+    Queue,
+    Delivered,
+    Pending,
+}
+
+// # Trait Description:
+// A trait implemented by the protocols that run on the reliable handle but depart from reliable broadcast,
+// so that the handle checks, counts, and delivers their instances without branching on their protocol:
+// the handle looks the processor of every signal up in its `ProcessorRegistry`, and runs plain reliable
+// broadcast for the protocols without one. Every method behaves as reliable broadcast by default.
+#[async_trait]
+pub(crate) trait InstanceProcessor<T>: Send + Sync
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // This method checks a signal before it opens or joins its instance.
+    //
+    // # Returns:
+    // * `true` if the signal is processed, `false` if it is discarded.
+    fn accept(&mut self, _signal: &Signal<T>) -> bool {
+        true
+    }
+
+    // # Method Description:
+    // This method processes a signal once its Input was recorded and its content passed the validity
+    // predicate of the thread, for the protocols exchanging signals of their own.
+    //
+    // # Parameters:
+    // * instance - The monitor of the signal's instance.
+    // * signal - The signal.
+    // * received - The frame the signal was received in, `None` for a replayed signal.
+    // * signal_channels - The channels on which the processor sends its signals.
+    //
+    // # Returns:
+    // * What the handle does with the signal next.
+    async fn process(&mut self, _instance: &mut ReliableInstanceMonitor<T>, _signal: &Signal<T>, _received: Option<&SignalFrame>, _signal_channels: &SignalChannels<T>) -> Processing {
+        Processing::Count
+    }
+
+    // # Method Description:
+    // This method tells whether a thread joins an instance on an agreement quorum of Echo signals, and may
+    // deliver it on the fast path, as in reliable broadcast.
+    fn is_amplified(&self) -> bool {
+        true
+    }
+
+    // # Method Description:
+    // This method records what the processor needs from an Echo or Vote signal the handle counted.
+    //
+    // # Parameters:
+    // * instance - The monitor of the signal's instance.
+    // * signal - The signal.
+    fn record(&self, _instance: &mut ReliableInstanceMonitor<T>, _signal: &Signal<T>) {}
+
+    // # Method Description:
+    // This method delivers an instance whose quorum is reached.
+    //
+    // # Parameters:
+    // * instance - The monitor of the instance.
+    // * signal - The signal that completed the quorum, or that followed it.
+    //
+    // # Returns:
+    // * How the instance is delivered.
+    fn deliver(&self, _instance: &ReliableInstanceMonitor<T>, _signal: &Signal<T>) -> Delivery {
+        Delivery::Queue
+    }
+}
+
+// # Struct Description:
+// This struct holds the processors of the protocols that depart from reliable broadcast, by protocol
+// information, for a reliable handle. Extension frames are processed by the processor registered for
+// `EXTENSION`, whatever their type ID.
+//
+// # Fields:
+// * processors - The processor of every protocol, by protocol information.
+pub(crate) struct ProcessorRegistry<T> {
+    processors: HashMap<String, Box<dyn InstanceProcessor<T>>>,
+}
+
+impl<T> ProcessorRegistry<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    pub(crate) fn new() -> Self {
+        Self { processors: HashMap::new() }
+    }
+
+    pub(crate) fn with_processor(mut self, protocol_information: &str, processor: impl InstanceProcessor<T> + 'static) -> Self {
+        self.processors.insert(String::from(protocol_information), Box::new(processor));
+        self
+    }
+
+    fn get_key(content: &ObjectContent<T>) -> &str {
+        match content {
+            ObjectContent::Extension { .. } => EXTENSION,
+            content => content.get_protocol_information(),
+        }
+    }
+
+    pub(crate) fn get(&self, content: &ObjectContent<T>) -> Option<&dyn InstanceProcessor<T>> {
+        self.processors.get(Self::get_key(content)).map(|processor| processor.as_ref())
+    }
+
+    pub(crate) fn get_mut(&mut self, content: &ObjectContent<T>) -> Option<&mut (dyn InstanceProcessor<T> + 'static)> {
+        self.processors.get_mut(Self::get_key(content)).map(|processor| processor.as_mut())
+    }
+}
+
+// # Struct Description:
+// This struct is the processor of the extension frames: a delivered frame is handed to the handler of its
+// type instead of being queued. A frame that no handler takes is discarded, and published as
+// `Event::CapabilityMismatch`.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * extensions - The handlers of the frame types.
+// * event_channel - The channel on which events are published.
+pub(crate) struct ExtensionProcessor<T> {
+    thread_id: u32,
+    extensions: ExtensionRegistry,
+    event_channel: broadcast::Sender<Event<T>>,
+}
+
+impl<T> ExtensionProcessor<T> {
+    pub(crate) fn new(thread_id: u32, extensions: ExtensionRegistry, event_channel: broadcast::Sender<Event<T>>) -> Self {
+        Self {
+            thread_id,
+            extensions,
+            event_channel,
+        }
+    }
+}
+
+impl<T> InstanceProcessor<T> for ExtensionProcessor<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    fn deliver(&self, _instance: &ReliableInstanceMonitor<T>, signal: &Signal<T>) -> Delivery {
+        let instance_number = signal.get_instance_number();
+        match self.extensions.dispatch(signal.get_content(), instance_number) {
+            Ok(()) => log!(info, { node = self.thread_id, transition = "deliver" }, "id {}, instance: {}, delivering extension...", self.thread_id, instance_number),
+            Err(reason) => {
+                log!(warn, { node = self.thread_id }, "id: {}, instance: {}, discarding {reason}", self.thread_id, instance_number);
+                let mismatch = CapabilityMismatch::new(self.thread_id, signal.get_content().get_id(), reason, false);
+                let _ = self.event_channel.send(Event::CapabilityMismatch(mismatch));
+            },
+        }
+        Delivery::Delivered
+    }
+}
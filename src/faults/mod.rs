@@ -0,0 +1,317 @@
+use std::{collections::VecDeque, time::Duration};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize};
+use tokio::{sync::mpsc::{self, Receiver, Sender}, time::Instant};
+
+use crate::json::{Codec, WireFormat};
+use crate::multiplex::{Lane, tag_frame};
+use crate::reliable::SyncRequest;
+
+// # Enum Description:
+// This enum represents a fault injected into a single thread of an experiment. Unlike the network
+// emulation, which acts on the links between threads, faults act on the threads themselves.
+//
+// # Variants:
+// * Slowdown - Emulates a slow (e.g. CPU-throttled) replica: every frame processed by any of the
+//   thread's handle loops is delayed by the given duration before it is processed. Frames arriving
+//   meanwhile queue up, so the delay compounds under load as it would on a saturated node.
+// * ByzantineLeader - Makes the thread misbehave whenever it acts as the leader of a leader-based
+//   protocol, following the given `LeaderBehavior`.
+// * Crash - Crashes the thread at the given `CrashPoint`: from then on it takes no part in the protocols,
+//   so the other threads must terminate without it.
+// * Byzantine - Makes the thread take part in reliable broadcast instances following the given
+//   `ByzantineBehavior` instead of the protocol.
+// * Outage - Crashes the thread for the time window of the given `Outage`, then recovers it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Fault {
+    Slowdown(u32, Duration),
+    ByzantineLeader(u32, LeaderBehavior),
+    Crash(u32, CrashPoint),
+    Byzantine(u32, ByzantineBehavior),
+    Outage(u32, Outage),
+}
+
+// # Enum Description:
+// This enum represents when a crashed thread stops. Like leader behaviors, crashes are applied by the
+// code running the thread (e.g. the simulator), which asks the fault script for the crash point of every
+// thread (see `FaultScript::get_crash_point`). A crashed thread keeps its communicator, so frames addressed
+// to it are still accepted, but its handles no longer process them.
+//
+// # Variants:
+// * BeforeStart - The thread never starts its handles.
+// * AfterRound - The thread takes part in every round up to the given one, then terminates its handles.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CrashPoint {
+    BeforeStart,
+    AfterRound(u32),
+}
+
+// # Struct Description:
+// This struct represents a time window during which a thread is down. Unlike `Fault::Crash`, it is applied
+// by the hubs themselves, on the channels of the thread (see `ClusterConfig::link_transmitters` and
+// `ClusterConfig::link_receiver`): during the window, no frame reaches the thread, so its handles stop,
+// and no frame sent by the thread leaves it. The frames of the window are buffered or dropped according to
+// the `OutagePolicy`. On recovery, the thread asks every other thread to catch it up with the instances it
+// missed (see `SyncRequest`). Times are measured from the creation of the hub.
+//
+// # Fields:
+// * crash_at - When the thread goes down.
+// * recover_at - When the thread recovers.
+// * policy - What happens to the frames of the window.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Outage {
+    crash_at: Duration,
+    recover_at: Duration,
+    policy: OutagePolicy,
+}
+
+// # Enum Description:
+// This enum represents what happens to the frames sent to or by a thread while it is down.
+//
+// # Variants:
+// * Buffer - The frames are held, and delivered in order when the thread recovers, as for a paused
+//   (e.g. frozen VM) thread.
+// * Drop - The frames are lost, as for a crashed thread; the thread relies on the catch-up on recovery.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutagePolicy {
+    Buffer,
+    Drop,
+}
+
+impl Outage {
+    pub fn new(crash_at: Duration, recover_at: Duration, policy: OutagePolicy) -> Self {
+        Self {
+            crash_at,
+            recover_at,
+            policy
+        }
+    }
+
+    pub fn get_crash_at(&self) -> Duration {
+        self.crash_at
+    }
+
+    pub fn get_recover_at(&self) -> Duration {
+        self.recover_at
+    }
+
+    pub fn get_policy(&self) -> OutagePolicy {
+        self.policy
+    }
+
+    // # Method Description:
+    // This method routes the frames sent by a thread through a gate that holds or drops them while the
+    // thread is down. The window starts when the method is called.
+    //
+    // # Parameters:
+    // * transmitters - The transmitters of the thread, indexed by thread ID.
+    // * buffer_size - The capacity of the channel in front of every gate.
+    //
+    // # Returns:
+    // * The transmitters to hand to the thread, indexed by thread ID.
+    pub fn gate_transmitters(&self, transmitters: Vec<Sender<Bytes>>, buffer_size: usize) -> Vec<Sender<Bytes>> {
+        let (crash, recovery) = (Instant::now() + self.crash_at, Instant::now() + self.recover_at);
+        let policy = self.policy;
+        transmitters.into_iter().map(|transmitter| {
+            let (tx, mut rx) = mpsc::channel::<Bytes>(buffer_size);
+            tokio::spawn(async move {
+                while let Some(frame) = rx.recv().await {
+                    let now = Instant::now();
+                    if now >= crash && now < recovery {
+                        match policy {
+                            OutagePolicy::Buffer => tokio::time::sleep_until(recovery).await,
+                            OutagePolicy::Drop => continue,
+                        }
+                    }
+                    if transmitter.send(frame).await.is_err() {
+                        return
+                    }
+                }
+            });
+            tx
+        }).collect()
+    }
+
+    // # Method Description:
+    // This method routes the frames received by a thread through a gate that holds or drops them while the
+    // thread is down. On recovery, the gate releases the held frames, followed by the thread's own
+    // `SyncRequest`, which makes its reliable handle ask the other threads to catch it up. The window starts
+    // when the method is called.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * receiver - The receiver of the thread's channel.
+    // * codec - The wire format of the `SyncRequest`.
+    // * buffer_size - The capacity of the channel behind the gate.
+    //
+    // # Returns:
+    // * The receiver to hand to the thread.
+    pub fn gate_receiver(&self, id: u32, mut receiver: Receiver<Bytes>, codec: Codec, buffer_size: usize) -> Receiver<Bytes> {
+        let (crash, recovery) = (Instant::now() + self.crash_at, Instant::now() + self.recover_at);
+        let policy = self.policy;
+        let (tx, rx) = mpsc::channel::<Bytes>(buffer_size);
+        tokio::spawn(async move {
+            let mut held = VecDeque::new();
+            let mut recovered = false;
+            loop {
+                tokio::select! {
+                    received = receiver.recv() => {
+                        let Some(frame) = received else {
+                            return
+                        };
+                        let now = Instant::now();
+                        if now >= crash && now < recovery {
+                            if policy == OutagePolicy::Buffer {
+                                held.push_back(frame);
+                            }
+                            continue
+                        }
+                        if !recovered && now >= recovery {
+                            recovered = true;
+                            held.push_back(tag_frame(Lane::Signal, SyncRequest::new(id).write_frame(codec)));
+                        }
+                        held.push_back(frame);
+                    }
+                    _ = tokio::time::sleep_until(recovery), if !recovered => {
+                        recovered = true;
+                        held.push_back(tag_frame(Lane::Signal, SyncRequest::new(id).write_frame(codec)));
+                    }
+                }
+                while let Some(frame) = held.pop_front() {
+                    if tx.send(frame).await.is_err() {
+                        return
+                    }
+                }
+            }
+        });
+        rx
+    }
+}
+
+// # Enum Description:
+// This enum represents the canned attacks of a faulty leader. Leader-based protocols (e.g. view-change
+// or PBFT-style modules) do not implement them themselves: when proposing, they ask the fault script
+// for the groups of threads to send their proposal to (see `FaultScript::get_proposal_groups`), and
+// send a distinct proposal to each group.
+//
+// # Variants:
+// * WithholdProposal - The leader never sends its proposal (e.g. the pre-prepare), so the followers
+//   must detect the silent leader and change views.
+// * ConflictingProposals - The leader sends conflicting proposals to two disjoint halves of the cluster.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LeaderBehavior {
+    WithholdProposal,
+    ConflictingProposals,
+}
+
+// # Enum Description:
+// This enum represents the attacks of a Byzantine thread on reliable broadcast. Like crashes, they are
+// applied by the code running the thread, which wraps its communicator in a `ByzantineCommunicator`
+// (see `FaultScript::get_byzantine_behavior`).
+//
+// # Variants:
+// * Equivocate - The thread sends conflicting payloads for the same instance to two disjoint halves of
+//   the cluster, in the Inputs it broadcasts and in the Echoes and Votes it sends for every instance.
+// * DropEchoes - The thread never echoes, but still votes once it received a quorum of Echoes.
+// * VoteWithoutEcho - The thread votes for every Input it receives at once, without echoing it or
+//   waiting for a quorum of Echoes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ByzantineBehavior {
+    Equivocate,
+    DropEchoes,
+    VoteWithoutEcho,
+}
+
+// # Struct Description:
+// This struct holds the faults injected into the threads of an experiment.
+//
+// # Fields:
+// * faults - The injected faults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct FaultScript {
+    faults: Vec<Fault>,
+}
+
+impl FaultScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fault(mut self, fault: Fault) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    pub fn get_faults(&self) -> &Vec<Fault> {
+        &self.faults
+    }
+
+    // # Method Description:
+    // This method computes the processing delay a thread's handle loops must add to every frame.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    //
+    // # Returns:
+    // * `Some(Duration)` with the sum of the slowdowns injected into the thread, or `None` if there are none.
+    pub fn get_processing_delay(&self, id: u32) -> Option<Duration> {
+        let delays: Vec<Duration> = self.faults.iter().map(|fault| match fault {
+            Fault::Slowdown(slowed_id, delay) if *slowed_id == id => *delay,
+            _ => Duration::ZERO,
+        }).collect();
+        let delay: Duration = delays.into_iter().sum();
+
+        if delay.is_zero() { None } else { Some(delay) }
+    }
+
+    pub fn get_crash_point(&self, id: u32) -> Option<CrashPoint> {
+        self.faults.iter().find_map(|fault| match fault {
+            Fault::Crash(crashed_id, crash_point) if *crashed_id == id => Some(*crash_point),
+            _ => None,
+        })
+    }
+
+    pub fn get_outage(&self, id: u32) -> Option<Outage> {
+        self.faults.iter().find_map(|fault| match fault {
+            Fault::Outage(down_id, outage) if *down_id == id => Some(*outage),
+            _ => None,
+        })
+    }
+
+    pub fn get_byzantine_behavior(&self, id: u32) -> Option<ByzantineBehavior> {
+        self.faults.iter().find_map(|fault| match fault {
+            Fault::Byzantine(byzantine_id, behavior) if *byzantine_id == id => Some(*behavior),
+            _ => None,
+        })
+    }
+
+    pub fn get_leader_behavior(&self, id: u32) -> Option<LeaderBehavior> {
+        self.faults.iter().find_map(|fault| match fault {
+            Fault::ByzantineLeader(leader_id, behavior) if *leader_id == id => Some(*behavior),
+            _ => None,
+        })
+    }
+
+    // # Method Description:
+    // This method computes the groups of threads a leader sends its proposal to. Every group must receive
+    // a distinct proposal; a correct leader has a single group holding every thread.
+    //
+    // # Parameters:
+    // * id - The ID of the leader.
+    // * thread_count - The number of threads in the cluster.
+    //
+    // # Returns:
+    // * The groups of thread IDs: one group for a correct leader, none for a leader withholding its
+    //   proposal, and two disjoint halves for a leader sending conflicting proposals.
+    pub fn get_proposal_groups(&self, id: u32, thread_count: u32) -> Vec<Vec<u32>> {
+        let threads: Vec<u32> = (0..thread_count).collect();
+        match self.get_leader_behavior(id) {
+            None => vec![threads],
+            Some(LeaderBehavior::WithholdProposal) => vec![],
+            Some(LeaderBehavior::ConflictingProposals) => {
+                let (first, second) = threads.split_at((thread_count / 2) as usize);
+                vec![first.to_vec(), second.to_vec()]
+            },
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::{collections::VecDeque, ptr, slice};
+use bytes::Bytes;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::config::ClusterConfig;
+use crate::replay::{ReliableReplay, ReplayAction};
+use crate::round::{Instance, Round};
+
+// The kind of an `RbAction` carrying a frame sent to a thread.
+const RB_ACTION_SENT: u32 = 0;
+// The kind of an `RbAction` carrying a frame delivered to the core's own thread.
+const RB_ACTION_DELIVERED: u32 = 1;
+
+// # Struct Description:
+// This struct is the reliable broadcast core handed to C callers: the lock-step reliable handle of a single
+// thread (see `ReliableReplay`), with the runtime it is driven on and the actions it emitted that were not
+// polled yet. The core never touches the network: the caller feeds it the frames it receives, and routes
+// the frames it sends, so that simulators written in other languages run this exact implementation.
+//
+// # Fields:
+// * runtime - The single-threaded runtime on which the core is driven.
+// * replay - The lock-step reliable handle of the thread.
+// * actions - The actions emitted by the core and not polled yet, in emission order.
+// * polled - The frame of the action polled last, kept alive until the next poll.
+pub struct ReliableCore {
+    runtime: Runtime,
+    replay: ReliableReplay<String>,
+    actions: VecDeque<ReplayAction>,
+    polled: Option<Bytes>,
+}
+
+// # Struct Description:
+// This struct is an action polled from a core (see `rb_core_poll`), laid out for C.
+//
+// # Fields:
+// * kind - `RB_ACTION_SENT` (0) for a frame to send to a thread, `RB_ACTION_DELIVERED` (1) for a frame
+//   delivered to the core's own thread.
+// * destination - The ID of the thread to send the frame to, or the ID of the core's thread if delivered.
+// * frame - The tagged frame, valid until the next call on the core. It is not NUL-terminated.
+// * length - The length of the frame, in bytes.
+#[repr(C)]
+pub struct RbAction {
+    kind: u32,
+    destination: u32,
+    frame: *const u8,
+    length: usize,
+}
+
+// # Function Description:
+// This function creates the core of a thread of a cluster under the default configuration.
+//
+// # Parameters:
+// * thread_count - The number of threads in the cluster.
+// * id - The ID of the thread.
+//
+// # Returns:
+// * The core, to release with `rb_core_free`, or a null pointer if the thread is not part of the cluster.
+#[unsafe(no_mangle)]
+pub extern "C" fn rb_core_new(thread_count: u32, id: u32) -> *mut ReliableCore {
+    if id >= thread_count {
+        return ptr::null_mut()
+    }
+    let Ok(runtime) = Builder::new_current_thread().enable_all().build() else {
+        return ptr::null_mut()
+    };
+    let replay = {
+        let _guard = runtime.enter();
+        ReliableReplay::new(ClusterConfig::new(thread_count), id)
+    };
+    match replay {
+        Ok(replay) => Box::into_raw(Box::new(ReliableCore {
+            runtime,
+            replay,
+            actions: VecDeque::new(),
+            polled: None,
+        })),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+// # Function Description:
+// This function releases a core.
+//
+// # Parameters:
+// * core - The core, created by `rb_core_new`, or a null pointer.
+//
+// # Safety:
+// * The core must not be used after it is released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rb_core_free(core: *mut ReliableCore) {
+    if !core.is_null() {
+        drop(unsafe { Box::from_raw(core) });
+    }
+}
+
+// # Function Description:
+// This function feeds a frame received from another thread (or sent by the core to itself) to a core,
+// and drives the core until it has nothing left to process.
+//
+// # Parameters:
+// * core - The core.
+// * frame - The tagged frame, as polled from the core that sent it.
+// * length - The length of the frame, in bytes.
+//
+// # Returns:
+// * The number of actions the core emitted, to poll with `rb_core_poll`, or -1 if an argument is null.
+//
+// # Safety:
+// * The core must have been created by `rb_core_new`, and `frame` must point to `length` readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rb_core_feed(core: *mut ReliableCore, frame: *const u8, length: usize) -> i64 {
+    let (Some(core), Some(frame)) = (unsafe { core.as_mut() }, unsafe { read_bytes(frame, length) }) else {
+        return -1
+    };
+    let step = core.runtime.block_on(core.replay.step(frame));
+    core.queue(step.get_actions().clone())
+}
+
+// # Function Description:
+// This function makes a core reliably broadcast a message, and drives it until it has nothing left to process.
+//
+// # Parameters:
+// * core - The core.
+// * message - The message, in UTF-8.
+// * length - The length of the message, in bytes.
+// * instance_number - The instance number of the broadcast.
+// * round_number - The round number of the broadcast.
+//
+// # Returns:
+// * The number of actions the core emitted, to poll with `rb_core_poll`, or -1 if an argument is null or
+//   the message is not UTF-8.
+//
+// # Safety:
+// * The core must have been created by `rb_core_new`, and `message` must point to `length` readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rb_core_broadcast(core: *mut ReliableCore, message: *const u8, length: usize, instance_number: u32, round_number: u32) -> i64 {
+    let (Some(core), Some(message)) = (unsafe { core.as_mut() }, unsafe { read_string(message, length) }) else {
+        return -1
+    };
+    let actions = core.runtime.block_on(core.replay.broadcast(message, Instance(instance_number), Round(round_number)));
+    core.queue(actions)
+}
+
+// # Function Description:
+// This function polls the next action emitted by a core.
+//
+// # Parameters:
+// * core - The core.
+// * action - Where the action is written.
+//
+// # Returns:
+// * 1 if an action was written, 0 if the core has no action left, or -1 if an argument is null.
+//
+// # Safety:
+// * The core must have been created by `rb_core_new`, and `action` must point to a writable `RbAction`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rb_core_poll(core: *mut ReliableCore, action: *mut RbAction) -> i32 {
+    let (Some(core), Some(action)) = (unsafe { core.as_mut() }, unsafe { action.as_mut() }) else {
+        return -1
+    };
+    let Some(next) = core.actions.pop_front() else {
+        core.polled = None;
+        return 0
+    };
+    let (kind, destination, frame) = match next {
+        ReplayAction::Sent(destination, frame) => (RB_ACTION_SENT, destination, frame),
+        ReplayAction::Delivered(frame) => (RB_ACTION_DELIVERED, core.replay.get_id(), frame),
+    };
+    let frame = core.polled.insert(frame);
+    *action = RbAction {
+        kind,
+        destination,
+        frame: frame.as_ptr(),
+        length: frame.len(),
+    };
+    1
+}
+
+impl ReliableCore {
+    // # Method Description:
+    // This method queues the actions emitted by the core until they are polled.
+    //
+    // # Parameters:
+    // * actions - The actions.
+    //
+    // # Returns:
+    // * The number of actions queued.
+    fn queue(&mut self, actions: Vec<ReplayAction>) -> i64 {
+        let queued = actions.len() as i64;
+        self.actions.extend(actions);
+        queued
+    }
+}
+
+// # Function Description:
+// This function reads a UTF-8 string passed by a C caller.
+//
+// # Parameters:
+// * bytes - The bytes of the string.
+// * length - The length of the string, in bytes.
+//
+// # Returns:
+// * The string, or `None` if the pointer is null or the bytes are not UTF-8.
+//
+// # Safety:
+// * `bytes` must point to `length` readable bytes, if it is not null.
+unsafe fn read_string(bytes: *const u8, length: usize) -> Option<String> {
+    String::from_utf8(unsafe { read_bytes(bytes, length) }?.to_vec()).ok()
+}
+
+// # Function Description:
+// This function copies the bytes passed by a C caller, e.g. a frame.
+//
+// # Parameters:
+// * bytes - The bytes.
+// * length - The number of bytes.
+//
+// # Returns:
+// * The bytes, or `None` if the pointer is null.
+//
+// # Safety:
+// * `bytes` must point to `length` readable bytes, if it is not null.
+unsafe fn read_bytes(bytes: *const u8, length: usize) -> Option<Bytes> {
+    if bytes.is_null() {
+        return None
+    }
+    Some(Bytes::copy_from_slice(unsafe { slice::from_raw_parts(bytes, length) }))
+}
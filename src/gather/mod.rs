@@ -0,0 +1,146 @@
+use std::{collections::{BTreeMap, BTreeSet}, fmt::Debug, hash::Hash, time::{Duration, Instant}};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::basic::Message;
+use crate::reliable::ReliableCommunication;
+use crate::round::{Instance, Round};
+use crate::logging::log;
+
+// The reliable broadcast instances of the three phases of a gather round.
+const VALUE_INSTANCE: Instance = Instance(0);
+const SET_INSTANCE: Instance = Instance(1);
+const UNION_INSTANCE: Instance = Instance(2);
+
+// How long a gather waits for a delivery before checking its round again.
+const GATHER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// # Enum Description:
+// This enum represents the payloads gather threads reliably broadcast to each other, one per phase of a
+// round, each in its own reliable broadcast instance of the round. Sets name the senders of the values
+// they hold: reliable broadcast delivers a single value per sender and round, so a sender identifies it.
+//
+// # Variants:
+// * Value - The value of the thread, in instance 0.
+// * Set - The senders of the first `n - f` values the thread delivered, in instance 1.
+// * Union - The union of the first `n - f` sets the thread accepted, in instance 2.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GatherPayload<T> {
+    Value(T),
+    Set(BTreeSet<u32>),
+    Union(BTreeSet<u32>),
+}
+
+// # Struct Description:
+// This struct runs gather over the reliable broadcast of a communicator, so that the threads of a cluster
+// output sets of values sharing a common core: a set of at least `n - f` values included in the output of
+// every correct thread. In a round, a thread reliably broadcasts its value, and once it delivered `n - f`
+// values, the set of their senders. It accepts the set of another thread once it delivered every value of
+// the set, and once it accepted `n - f` sets, broadcasts their union. It accepts a union like a set, and
+// once it accepted `n - f` unions, outputs the values of their union.
+//
+// Rounds use the first three reliable broadcast instances of their round number on the communicator, so the
+// communicator should be dedicated to gather.
+//
+// # Fields:
+// * communicator - The communicator whose reliable handle carries the values and sets.
+pub struct Gather<C> {
+    communicator: C,
+}
+
+impl<C> Gather<C> {
+    pub fn new(communicator: C) -> Self {
+        Self {
+            communicator,
+        }
+    }
+
+    pub fn get_communicator(&mut self) -> &mut C {
+        &mut self.communicator
+    }
+
+    // # Method Description:
+    // This method runs a gather round.
+    //
+    // # Parameters:
+    // * value - The value of the thread.
+    // * round_number - The round, which every thread must run with its own value.
+    //
+    // # Returns:
+    // * The output of the thread: the values of the union it accepted last, by increasing sender ID. Every
+    //   value is delivered by reliable broadcast, so all correct threads output the same value for a sender.
+    pub async fn gather<T>(&mut self, value: T, round_number: Round) -> Vec<Message<T>>
+    where
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+        C: ReliableCommunication<GatherPayload<T>> + Send,
+    {
+        let thread_id = *self.communicator.get_id();
+        let thread_count = self.communicator.get_config().get_thread_count();
+        let quorum = (thread_count - self.communicator.get_config().get_thresholds().get_faulty_threads() as u32) as usize;
+        self.communicator.reliable_broadcast(GatherPayload::Value(value), VALUE_INSTANCE, round_number).await;
+
+        let mut values: BTreeMap<u32, T> = BTreeMap::new();
+        let mut sets: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+        let mut unions: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+        let mut set_sent = false;
+        let mut union_sent = false;
+        loop {
+            let deadline = Instant::now() + GATHER_POLL_INTERVAL;
+            for message in self.communicator.reliable_recv_batch(None, round_number, usize::MAX, deadline).await {
+                let sender = message.get_id();
+                match (message.get_instance_number(), message.get_message().clone()) {
+                    (Some(VALUE_INSTANCE), GatherPayload::Value(value)) => {
+                        values.insert(sender, value);
+                    },
+                    (Some(SET_INSTANCE), GatherPayload::Set(set)) if is_well_formed(&set, quorum, thread_count) => {
+                        sets.insert(sender, set);
+                    },
+                    (Some(UNION_INSTANCE), GatherPayload::Union(union)) if is_well_formed(&union, quorum, thread_count) => {
+                        unions.insert(sender, union);
+                    },
+                    (Some(SET_INSTANCE), GatherPayload::Set(senders)) | (Some(UNION_INSTANCE), GatherPayload::Union(senders)) => {
+                        log!(warn, { node = thread_id, round = %round_number }, "id: {thread_id}, discarding the gather payload of id: {sender} naming the senders {senders:?}, which are fewer than {quorum} or not all in the cluster");
+                    },
+                    (instance_number, _) => {
+                        log!(warn, { node = thread_id, round = %round_number }, "id: {thread_id}, discarding the gather payload of id: {sender} in instance {instance_number:?}, which does not belong to it");
+                    },
+                }
+            }
+
+            if !set_sent && values.len() >= quorum {
+                let set = values.keys().copied().collect();
+                self.communicator.reliable_broadcast(GatherPayload::Set(set), SET_INSTANCE, round_number).await;
+                set_sent = true;
+            }
+            let accepted_sets: Vec<&BTreeSet<u32>> = sets.values().filter(|set| set.iter().all(|sender| values.contains_key(sender))).collect();
+            if set_sent && !union_sent && accepted_sets.len() >= quorum {
+                let union = accepted_sets.into_iter().flatten().copied().collect();
+                self.communicator.reliable_broadcast(GatherPayload::Union(union), UNION_INSTANCE, round_number).await;
+                union_sent = true;
+            }
+            let accepted_unions: Vec<&BTreeSet<u32>> = unions.values().filter(|union| union.iter().all(|sender| values.contains_key(sender))).collect();
+            if union_sent && accepted_unions.len() >= quorum {
+                let output: BTreeSet<u32> = accepted_unions.into_iter().flatten().copied().collect();
+                log!(info, { node = thread_id, round = %round_number }, "id: {thread_id}, gather round {round_number} output the values of {output:?}");
+                return output.into_iter()
+                    .map(|sender| Message::new(String::from("gather"), sender, values[&sender].clone(), None, Some(VALUE_INSTANCE), round_number))
+                    .collect()
+            }
+        }
+    }
+}
+
+// # Function Description:
+// This function checks that a set or union names at least `n - f` senders, all of them threads of the
+// cluster. A correct thread only broadcasts such sets, so any other is sent by a Byzantine thread, and is
+// discarded rather than accepted once its senders are delivered.
+//
+// # Parameters:
+// * senders - The senders named by the set or union.
+// * quorum - The number of values a correct thread waits for, `n - f`.
+// * thread_count - The number of threads of the cluster.
+//
+// # Returns:
+// * Whether the set or union could have been broadcast by a correct thread.
+fn is_well_formed(senders: &BTreeSet<u32>, quorum: usize, thread_count: u32) -> bool {
+    senders.len() >= quorum && senders.iter().all(|sender| *sender < thread_count)
+}
@@ -0,0 +1,376 @@
+use std::{collections::{BTreeMap, BTreeSet}, sync::{Arc, Mutex}, time::{Duration, SystemTime, UNIX_EPOCH}};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{Sender, WeakSender};
+
+use crate::json::JsonConversion;
+use crate::multiplex::Lane;
+
+// The largest HTTP request head read by the health endpoint, in bytes.
+const MAX_REQUEST_SIZE: usize = 8192;
+
+// # Function Description:
+// This function converts a time to the number of milliseconds elapsed since the Unix epoch.
+//
+// # Parameters:
+// * time - The time.
+//
+// # Returns:
+// * The number of milliseconds, 0 for a time before the epoch.
+fn get_unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_millis() as u64).unwrap_or(0)
+}
+
+// # Struct Description:
+// This struct collects the liveness of a thread as its handles run: which handles are running, when a
+// frame was last processed, which peers were suspected, and the lane queues whose depths are reported.
+// It is kept in the thread's `Metrics`, so that it is shared between the communicator, its
+// handles, and its demultiplexer, and can be read while they run.
+//
+// # Fields:
+// * started - The time the probe was created, standing for the last frame until one is processed.
+// * handles - Whether every handle started so far is still running, keyed by handle name (e.g. "reliable").
+// * last_frame - The time a handle last processed a frame, if one did.
+// * suspected_peers - The peers the thread's handles suspected (see `Event::DivergenceSuspected`).
+// * lanes - The transmitters of the thread's lane queues, held weakly so that the queues still close.
+#[derive(Debug, Clone)]
+pub struct HealthProbe {
+    started: SystemTime,
+    handles: Arc<Mutex<BTreeMap<String, bool>>>,
+    last_frame: Arc<Mutex<Option<SystemTime>>>,
+    suspected_peers: Arc<Mutex<BTreeSet<u32>>>,
+    lanes: Arc<Mutex<Vec<WatchedLane>>>,
+}
+
+// A lane queue whose depth is reported: its lane, and its transmitter.
+type WatchedLane = (Lane, WeakSender<Bytes>);
+
+impl Default for HealthProbe {
+    fn default() -> Self {
+        Self {
+            started: SystemTime::now(),
+            handles: Arc::default(),
+            last_frame: Arc::default(),
+            suspected_peers: Arc::default(),
+            lanes: Arc::default(),
+        }
+    }
+}
+
+impl HealthProbe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // # Method Description:
+    // This method records that a handle started running.
+    //
+    // # Parameters:
+    // * name - The name of the handle (e.g. "reliable", "witness").
+    //
+    // # Returns:
+    // * The `RunningHandle` the handle holds, which records that it stopped once dropped.
+    pub fn start_handle(&self, name: &str) -> RunningHandle {
+        self.handles.lock().unwrap().insert(name.to_string(), true);
+        RunningHandle {
+            probe: self.clone(),
+            name: name.to_string(),
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether a handle was started and has not stopped since.
+    //
+    // # Parameters:
+    // * name - The name of the handle.
+    //
+    // # Returns:
+    // * `true` if the handle is running.
+    pub fn is_running(&self, name: &str) -> bool {
+        self.handles.lock().unwrap().get(name).copied().unwrap_or(false)
+    }
+
+    // # Method Description:
+    // This method checks whether any handle of the thread was ever started.
+    //
+    // # Returns:
+    // * `true` if a handle was started, even if it stopped since.
+    pub fn has_started(&self) -> bool {
+        !self.handles.lock().unwrap().is_empty()
+    }
+
+    // # Method Description:
+    // This method records that a handle processed a frame, now.
+    pub fn record_frame(&self) {
+        *self.last_frame.lock().unwrap() = Some(SystemTime::now());
+    }
+
+    // # Method Description:
+    // This method records that a handle suspected a peer.
+    //
+    // # Parameters:
+    // * peer - The ID of the suspected peer.
+    pub fn record_suspicion(&self, peer: u32) {
+        self.suspected_peers.lock().unwrap().insert(peer);
+    }
+
+    // # Method Description:
+    // This method makes the probe report the depth of a lane queue.
+    //
+    // # Parameters:
+    // * lane - The lane.
+    // * queue - The transmitter of the lane queue.
+    pub fn watch_lane(&self, lane: Lane, queue: &Sender<Bytes>) {
+        self.lanes.lock().unwrap().push((lane, queue.downgrade()));
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the liveness of the thread.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * rounds_completed - The number of rounds (or reliable broadcast instances) delivered, by protocol.
+    //
+    // # Returns:
+    // * The `NodeHealth` of the thread. The queues already closed are omitted.
+    pub fn snapshot(&self, id: u32, rounds_completed: BTreeMap<String, u64>) -> NodeHealth {
+        let queue_depths = self.lanes.lock().unwrap().iter()
+            .filter_map(|(lane, queue)| queue.upgrade().map(|queue| (format!("{lane:?}"), queue.max_capacity() - queue.capacity())))
+            .collect();
+        NodeHealth {
+            id,
+            started: get_unix_millis(self.started),
+            handles: self.handles.lock().unwrap().clone(),
+            last_frame: self.last_frame.lock().unwrap().map(get_unix_millis),
+            rounds_completed,
+            suspected_peers: self.suspected_peers.lock().unwrap().clone(),
+            queue_depths,
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is held by a running handle, and records in the thread's `HealthProbe` that the handle
+// stopped once it is stopped explicitly or dropped, e.g. because the task of the handle was aborted.
+//
+// # Fields:
+// * probe - The probe of the thread.
+// * name - The name of the handle.
+#[derive(Debug)]
+pub struct RunningHandle {
+    probe: HealthProbe,
+    name: String,
+}
+
+impl RunningHandle {
+    // # Method Description:
+    // This method records that the handle stopped, e.g. once it was shut down.
+    pub fn stop(&self) {
+        self.probe.handles.lock().unwrap().insert(self.name.clone(), false);
+    }
+}
+
+impl Drop for RunningHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// # Struct Description:
+// This struct is a snapshot of the liveness of a thread, as reported by `ReliableCommunication::health`
+// and the health endpoint of a node.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * started - The time the thread's metrics were created, in milliseconds since the Unix epoch.
+// * handles - Whether every handle started by the thread is still running, keyed by handle name.
+// * last_frame - The time a handle of the thread last processed a frame, in milliseconds since the Unix
+//   epoch, if one did.
+// * rounds_completed - The number of rounds (or reliable broadcast instances) delivered, by protocol.
+// * suspected_peers - The peers suspected by the thread's handles.
+// * queue_depths - The number of frames waiting in every lane queue of the thread, keyed by lane name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeHealth {
+    id: u32,
+    started: u64,
+    handles: BTreeMap<String, bool>,
+    last_frame: Option<u64>,
+    rounds_completed: BTreeMap<String, u64>,
+    suspected_peers: BTreeSet<u32>,
+    queue_depths: BTreeMap<String, usize>,
+}
+
+impl NodeHealth {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_started(&self) -> u64 {
+        self.started
+    }
+
+    pub fn get_handles(&self) -> &BTreeMap<String, bool> {
+        &self.handles
+    }
+
+    pub fn get_last_frame(&self) -> Option<u64> {
+        self.last_frame
+    }
+
+    pub fn get_rounds_completed(&self) -> &BTreeMap<String, u64> {
+        &self.rounds_completed
+    }
+
+    pub fn get_suspected_peers(&self) -> &BTreeSet<u32> {
+        &self.suspected_peers
+    }
+
+    pub fn get_queue_depths(&self) -> &BTreeMap<String, usize> {
+        &self.queue_depths
+    }
+
+    // # Method Description:
+    // This method measures how long the thread has gone without processing a frame.
+    //
+    // # Parameters:
+    // * now - The time of the measure.
+    //
+    // # Returns:
+    // * The time since the last frame was processed, or since the thread started if none was.
+    pub fn get_idle(&self, now: SystemTime) -> Duration {
+        Duration::from_millis(get_unix_millis(now).saturating_sub(self.last_frame.unwrap_or(self.started)))
+    }
+
+    // # Method Description:
+    // This method lists the reasons the thread is not live: a handle that stopped, or no frame processed
+    // for longer than the given stall bound. Suspected peers are reported, but do not make the thread unhealthy.
+    //
+    // # Parameters:
+    // * stall - The longest time the thread may go without processing a frame.
+    //
+    // # Returns:
+    // * The problems of the thread, empty if it is healthy.
+    pub fn get_problems(&self, stall: Duration) -> Vec<String> {
+        let mut problems: Vec<String> = self.handles.iter()
+            .filter(|(_, running)| !**running)
+            .map(|(name, _)| format!("{name} handle stopped"))
+            .collect();
+        let idle = self.get_idle(SystemTime::now());
+        if idle > stall {
+            problems.push(format!("no frame processed for {idle:?}"));
+        }
+        problems
+    }
+
+    pub fn is_healthy(&self, stall: Duration) -> bool {
+        self.get_problems(stall).is_empty()
+    }
+}
+
+impl JsonConversion<NodeHealth> for NodeHealth {}
+
+// # Struct Description:
+// This struct is a snapshot of the liveness of every node of a cluster, so that a script can decide
+// whether a long experiment is still healthy or should be aborted.
+//
+// # Fields:
+// * nodes - The `NodeHealth` of every node, in ID order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterHealth {
+    nodes: Vec<NodeHealth>,
+}
+
+impl ClusterHealth {
+    pub fn new(mut nodes: Vec<NodeHealth>) -> Self {
+        nodes.sort_by_key(NodeHealth::get_id);
+        Self {
+            nodes
+        }
+    }
+
+    pub fn get_nodes(&self) -> &Vec<NodeHealth> {
+        &self.nodes
+    }
+
+    // # Method Description:
+    // This method lists the problems of every node (see `NodeHealth::get_problems`).
+    //
+    // # Parameters:
+    // * stall - The longest time a node may go without processing a frame.
+    //
+    // # Returns:
+    // * The problems, prefixed with the ID of their node, empty if the cluster is healthy.
+    pub fn get_problems(&self, stall: Duration) -> Vec<String> {
+        self.nodes.iter()
+            .flat_map(|node| node.get_problems(stall).into_iter().map(|problem| format!("id: {}, {problem}", node.id)))
+            .collect()
+    }
+
+    pub fn is_healthy(&self, stall: Duration) -> bool {
+        self.get_problems(stall).is_empty()
+    }
+}
+
+impl JsonConversion<ClusterHealth> for ClusterHealth {}
+
+// # Function Description:
+// This function serves the health of a cluster over HTTP until its task is aborted: `GET /health` is
+// answered with the `ClusterHealth` in JSON, with status 200 if the cluster is healthy and 503 otherwise,
+// and any other request with status 404.
+//
+// # Parameters:
+// * listener - The listener on which requests are accepted.
+// * stall - The longest time a node may go without processing a frame and still be healthy.
+// * health - The function taking a snapshot of the health of the cluster.
+pub async fn serve_health<F>(listener: TcpListener, stall: Duration, health: F)
+where
+    F: Fn() -> ClusterHealth + Send + Sync + 'static,
+{
+    let health = Arc::new(health);
+    while let Ok((stream, _)) = listener.accept().await {
+        let health = health.clone();
+        tokio::spawn(async move {
+            let _ = answer_health_request(stream, stall, health.as_ref()).await;
+        });
+    }
+}
+
+// # Function Description:
+// This function answers a single request made to the health endpoint, then closes the connection.
+//
+// # Parameters:
+// * stream - The connection.
+// * stall - The longest time a node may go without processing a frame and still be healthy.
+// * health - The function taking a snapshot of the health of the cluster.
+//
+// # Returns:
+// * An error if the request could not be read or the response could not be written.
+async fn answer_health_request<F>(mut stream: TcpStream, stall: Duration, health: &F) -> std::io::Result<()>
+where
+    F: Fn() -> ClusterHealth,
+{
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < MAX_REQUEST_SIZE {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let (status, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/health")) => {
+            let cluster_health = health();
+            let status = if cluster_health.is_healthy(stall) { "200 OK" } else { "503 Service Unavailable" };
+            (status, cluster_health.write_json())
+        },
+        _ => ("404 Not Found", String::new()),
+    };
+    let response = format!("HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,249 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+use serde::{Serialize, Deserialize};
+use rand::{RngCore, rngs::OsRng};
+use ed25519_dalek::SigningKey;
+
+use crate::config::ClusterConfig;
+use crate::json::JsonConversion;
+use crate::signing::Authentication;
+
+// # Struct Description:
+// This struct holds the key pair of a node. Keys are stored as opaque bytes tagged with the name of
+// their scheme, so that every subsystem using keys (signing, MACs, distributed key generation) reads
+// them from the same store. The secret key is never printed by `Debug`.
+//
+// # Fields:
+// * scheme - The name of the scheme the keys belong to.
+// * public_key - The public key, shared with the peers of the node.
+// * secret_key - The secret key, which never leaves the node.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyPair {
+    scheme: String,
+    public_key: Vec<u8>,
+    secret_key: Vec<u8>,
+}
+
+impl KeyPair {
+    // The scheme of the key pairs used to sign signals (see `Authentication::Ed25519`).
+    pub const ED25519: &str = "ed25519";
+
+    pub fn new(scheme: &str, public_key: Vec<u8>, secret_key: Vec<u8>) -> Self {
+        Self {
+            scheme: scheme.to_string(),
+            public_key,
+            secret_key
+        }
+    }
+
+    // # Method Description:
+    // This method draws random key material for in-process experiments, where no scheme derives
+    // the public key from the secret key yet.
+    //
+    // # Parameters:
+    // * rng - The RNG the key material is drawn from, e.g. created through `ClusterConfig::create_rng`.
+    //
+    // # Returns:
+    // * A key pair of the "random" scheme, with 32-byte keys.
+    pub fn generate<R: RngCore>(rng: &mut R) -> Self {
+        let mut public_key = vec![0; 32];
+        let mut secret_key = vec![0; 32];
+        rng.fill_bytes(&mut public_key);
+        rng.fill_bytes(&mut secret_key);
+        Self::new("random", public_key, secret_key)
+    }
+
+    // # Method Description:
+    // This method draws an Ed25519 key pair, e.g. for a cluster that signs its signals.
+    //
+    // # Parameters:
+    // * rng - The RNG the secret key is drawn from.
+    //
+    // # Returns:
+    // * A key pair of the "ed25519" scheme, with the 32-byte secret key and its 32-byte public key.
+    pub fn generate_ed25519<R: RngCore>(rng: &mut R) -> Self {
+        let mut secret_key = [0; 32];
+        rng.fill_bytes(&mut secret_key);
+        let public_key = SigningKey::from_bytes(&secret_key).verifying_key().to_bytes();
+        Self::new(Self::ED25519, public_key.to_vec(), secret_key.to_vec())
+    }
+
+    pub fn get_scheme(&self) -> &String {
+        &self.scheme
+    }
+
+    pub fn get_public_key(&self) -> &Vec<u8> {
+        &self.public_key
+    }
+
+    pub fn get_secret_key(&self) -> &Vec<u8> {
+        &self.secret_key
+    }
+}
+
+impl fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("scheme", &self.scheme)
+            .field("public_key", &self.public_key)
+            .field("secret_key", &"<redacted>")
+            .finish()
+    }
+}
+
+// # Struct Description:
+// This struct represents the persistent identity of a node: its ID, its key pair, and the public keys
+// of its peers. It is either loaded from a file at startup (`load`) or injected in-process, e.g. from
+// `generate_cluster`, and is the single source of keys for every subsystem of the node.
+//
+// # Fields:
+// * id - The ID of the node.
+// * key_pair - The key pair of the node.
+// * peer_keys - The public key of every known node, by node ID, including the node itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IdentityStore {
+    id: u32,
+    key_pair: KeyPair,
+    peer_keys: BTreeMap<u32, Vec<u8>>,
+}
+
+impl IdentityStore {
+    pub fn new(id: u32, key_pair: KeyPair) -> Self {
+        let peer_keys = BTreeMap::from([(id, key_pair.get_public_key().clone())]);
+        Self {
+            id,
+            key_pair,
+            peer_keys
+        }
+    }
+
+    // # Method Description:
+    // This method records the public key of a peer.
+    //
+    // # Parameters:
+    // * id - The ID of the peer.
+    // * public_key - The public key of the peer.
+    //
+    // # Returns:
+    // * The updated store.
+    //
+    // # Panics:
+    // * If the key of the node itself is replaced by a different one.
+    pub fn with_peer_key(mut self, id: u32, public_key: Vec<u8>) -> Self {
+        if id == self.id && &public_key != self.key_pair.get_public_key() {
+            panic!("Error: the public key of node {} does not match its key pair", id);
+        }
+        self.peer_keys.insert(id, public_key);
+        self
+    }
+
+    // # Function Description:
+    // This function generates the identities of every node of a cluster, each holding the public keys
+    // of all nodes. Keys are drawn from the "identity" RNG of every node, so the same seed always
+    // yields the same identities, and anyone knowing the seed knows the secret keys: they are meant for
+    // in-process simulations, while nodes in network mode use `load_or_create`. Under `Authentication::Ed25519` the keys are Ed25519 keys, otherwise
+    // they are random key material.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The identity of every node, indexed by node ID.
+    pub fn generate_cluster(config: &ClusterConfig) -> Vec<Self> {
+        let key_pairs: Vec<KeyPair> = (0..config.get_thread_count())
+            .map(|id| {
+                let mut rng = config.create_rng(id, "identity");
+                match config.get_authentication() {
+                    Authentication::Unsigned => KeyPair::generate(&mut rng),
+                    Authentication::Ed25519 => KeyPair::generate_ed25519(&mut rng),
+                }
+            })
+            .collect();
+        key_pairs.iter()
+            .enumerate()
+            .map(|(id, key_pair)| {
+                key_pairs.iter()
+                    .enumerate()
+                    .fold(Self::new(id as u32, key_pair.clone()), |store, (peer, peer_key_pair)| store.with_peer_key(peer as u32, peer_key_pair.get_public_key().clone()))
+            })
+            .collect()
+    }
+
+    // # Function Description:
+    // This function loads an identity from its file.
+    //
+    // # Parameters:
+    // * path - The path of the identity file.
+    //
+    // # Returns:
+    // * The identity, or a description of why the file could not be read or is inconsistent.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|error| format!("could not read {}: {error}", path.display()))?;
+        let store = Self::read_json(&data).map_err(|error| format!("could not decode {}: {error}", path.display()))?;
+        match store.peer_keys.get(&store.id) {
+            Some(public_key) if public_key != store.key_pair.get_public_key() => {
+                Err(format!("the public key of node {} in {} does not match its key pair", store.id, path.display()))
+            },
+            _ => Ok(store),
+        }
+    }
+
+    // # Function Description:
+    // This function loads the identity of a node from its file, or, the first time the node starts, creates
+    // it and persists it there. A new identity holds an Ed25519 key pair drawn from the randomness of the
+    // operating system, not from the experiment seed, so its secret key is known to the node alone.
+    //
+    // # Parameters:
+    // * path - The path of the identity file.
+    // * id - The ID of the node.
+    //
+    // # Returns:
+    // * The identity, or a description of why the file could not be read or written, or belongs to another node.
+    pub fn load_or_create(path: &Path, id: u32) -> Result<Self, String> {
+        if !path.exists() {
+            let identity = Self::new(id, KeyPair::generate_ed25519(&mut OsRng));
+            identity.save(path)?;
+            return Ok(identity)
+        }
+        let identity = Self::load(path)?;
+        if identity.id != id {
+            return Err(format!("{} holds the identity of node {}, not of node {id}", path.display(), identity.id))
+        }
+        Ok(identity)
+    }
+
+    // # Method Description:
+    // This method writes the identity to a file, readable by its owner only on unix systems.
+    //
+    // # Parameters:
+    // * path - The path of the identity file.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the file could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.write_json()).map_err(|error| format!("could not write {}: {error}", path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(|error| format!("could not restrict {}: {error}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_key_pair(&self) -> &KeyPair {
+        &self.key_pair
+    }
+
+    pub fn get_peer_key(&self, id: u32) -> Option<&Vec<u8>> {
+        self.peer_keys.get(&id)
+    }
+
+    pub fn get_peer_ids(&self) -> Vec<u32> {
+        self.peer_keys.keys().copied().collect()
+    }
+}
+
+impl JsonConversion<IdentityStore> for IdentityStore {}
@@ -0,0 +1,274 @@
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use tokio::sync::oneshot;
+
+use crate::json::JsonConversion;
+
+pub use crate::reliable::{ReliableInstanceMonitor, ReliableInstanceState, ReliableInstanceCount, ReliableInstanceTiming};
+pub use crate::witness::{WitnessRoundMonitor, WitnessRoundState, WitnessRoundCount, WitnessRoundContent};
+pub use crate::barycentric_agreement::{BarycentricRoundMonitor, BarycentricRoundState, BarycentricRoundCount, BarycentricRoundContent};
+
+pub type InspectResponder<S> = oneshot::Sender<S>;
+
+// # Trait Description:
+// A trait implemented by the monitors the background handles keep per instance or round. Monitors are
+// owned by the handles, so applications never access them directly: they send an inspection query to a
+// handle (e.g. `reliable_inspect`, `witness_inspect`, `barycentric_inspect`), which answers with a
+// read-only snapshot of its monitors, suitable for dashboards and assertions.
+pub trait Inspect {
+    type Snapshot;
+
+    // # Method Description:
+    // This method captures the current state of the monitor.
+    //
+    // # Returns:
+    // * A snapshot that no longer changes as the monitor progresses.
+    fn snapshot(&self) -> Self::Snapshot;
+}
+
+// # Struct Description:
+// This struct is a read-only snapshot of a reliable broadcast instance, as monitored by a reliable handle.
+//
+// # Fields:
+// * echo_count - The number of threads whose Echo signal was received, for the content echoed the most.
+// * vote_count - The number of threads whose Vote signal was received, for the content voted the most.
+// * echoed - Whether the thread has broadcast its Echo.
+// * voted - Whether the thread has broadcast its Vote.
+// * delivered - Whether the instance has been delivered.
+// * input_digest - The digest of the content of the first Input received, if any.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReliableInstanceSnapshot {
+    echo_count: u64,
+    vote_count: u64,
+    echoed: bool,
+    voted: bool,
+    delivered: bool,
+    input_digest: Option<u64>,
+}
+
+impl ReliableInstanceSnapshot {
+    pub fn get_echo_count(&self) -> u64 {
+        self.echo_count
+    }
+
+    pub fn get_vote_count(&self) -> u64 {
+        self.vote_count
+    }
+
+    pub fn get_echoed(&self) -> bool {
+        self.echoed
+    }
+
+    pub fn get_voted(&self) -> bool {
+        self.voted
+    }
+
+    pub fn get_delivered(&self) -> bool {
+        self.delivered
+    }
+
+    pub fn get_input_digest(&self) -> Option<u64> {
+        self.input_digest
+    }
+}
+
+impl JsonConversion<ReliableInstanceSnapshot> for ReliableInstanceSnapshot {}
+
+impl<T> Inspect for ReliableInstanceMonitor<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    type Snapshot = ReliableInstanceSnapshot;
+
+    fn snapshot(&self) -> ReliableInstanceSnapshot {
+        let count = self.get_count();
+        ReliableInstanceSnapshot {
+            echo_count: count.echo,
+            vote_count: count.vote,
+            echoed: self.state.echo,
+            voted: self.state.vote,
+            delivered: self.state.deliver,
+            input_digest: self.input.as_ref().map(|input| input.json_digest()),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is a read-only snapshot of a witness round, as monitored by a witness (or aggregated witness) handle.
+//
+// # Fields:
+// * value_count - The number of values collected.
+// * report_count - The number of reports received.
+// * witness_count - The number of reports validated as witnesses.
+// * aggregated_report_count - The number of aggregated reports received.
+// * aggregated_witness_count - The number of aggregated reports validated as aggregated witnesses.
+// * reported - Whether the thread has reliably broadcast its report.
+// * delivered - Whether the round has been delivered.
+// * aggregated_delivered - Whether the round has been delivered at the aggregated witness level.
+// * value_digests - The digests of the collected values, in the configured `ValueOrdering`.
+// * level_witness_counts - The number of witnesses validated at every aggregation level that has any, by level.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WitnessRoundSnapshot {
+    value_count: u64,
+    report_count: u64,
+    witness_count: u64,
+    aggregated_report_count: u64,
+    aggregated_witness_count: u64,
+    reported: bool,
+    delivered: bool,
+    aggregated_delivered: bool,
+    value_digests: Vec<u64>,
+    level_witness_counts: BTreeMap<u32, u64>,
+}
+
+impl WitnessRoundSnapshot {
+    pub fn get_value_count(&self) -> u64 {
+        self.value_count
+    }
+
+    pub fn get_report_count(&self) -> u64 {
+        self.report_count
+    }
+
+    pub fn get_witness_count(&self) -> u64 {
+        self.witness_count
+    }
+
+    pub fn get_aggregated_report_count(&self) -> u64 {
+        self.aggregated_report_count
+    }
+
+    pub fn get_aggregated_witness_count(&self) -> u64 {
+        self.aggregated_witness_count
+    }
+
+    pub fn get_reported(&self) -> bool {
+        self.reported
+    }
+
+    pub fn get_delivered(&self) -> bool {
+        self.delivered
+    }
+
+    pub fn get_aggregated_delivered(&self) -> bool {
+        self.aggregated_delivered
+    }
+
+    pub fn get_value_digests(&self) -> &Vec<u64> {
+        &self.value_digests
+    }
+
+    pub fn get_level_witness_count(&self, level: u32) -> u64 {
+        self.level_witness_counts.get(&level).copied().unwrap_or(0)
+    }
+}
+
+impl JsonConversion<WitnessRoundSnapshot> for WitnessRoundSnapshot {}
+
+impl<T> Inspect for WitnessRoundMonitor<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    type Snapshot = WitnessRoundSnapshot;
+
+    fn snapshot(&self) -> WitnessRoundSnapshot {
+        WitnessRoundSnapshot {
+            value_count: self.count.values,
+            report_count: self.count.reports,
+            witness_count: self.count.witnesses,
+            aggregated_report_count: self.count.aggregated_reports,
+            aggregated_witness_count: self.count.aggregated_witnesses,
+            reported: self.state.report,
+            delivered: self.state.witnesses,
+            aggregated_delivered: self.state.aggregated_witnesses,
+            value_digests: self.content.values.iter().map(|value| value.get_digest()).collect(),
+            level_witness_counts: (1..=2).map(|level| (level, self.count.get_level_witnesses(level)))
+                .chain(self.count.nested_witnesses.iter().map(|(level, count)| (*level, *count)))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is a read-only snapshot of a barycentric agreement round, as monitored by a barycentric handle.
+//
+// # Fields:
+// * message_count - The number of messages received.
+// * barycentric_report_count - The number of barycentric reports received.
+// * buddy_count - The number of buddies found.
+// * messages_collected - Whether enough messages were received to look for trusted values.
+// * trusted - Whether a trusted value has been found.
+// * delivered - Whether the round has been delivered.
+// * message_digests - The digest of the message received from every thread, by thread ID (`None` if none was received).
+// * buddies - Whether every thread is a buddy, by thread ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BarycentricRoundSnapshot {
+    message_count: u32,
+    barycentric_report_count: u64,
+    buddy_count: u64,
+    messages_collected: bool,
+    trusted: bool,
+    delivered: bool,
+    message_digests: Vec<Option<u64>>,
+    buddies: Vec<bool>,
+}
+
+impl BarycentricRoundSnapshot {
+    pub fn get_message_count(&self) -> u32 {
+        self.message_count
+    }
+
+    pub fn get_barycentric_report_count(&self) -> u64 {
+        self.barycentric_report_count
+    }
+
+    pub fn get_buddy_count(&self) -> u64 {
+        self.buddy_count
+    }
+
+    pub fn get_messages_collected(&self) -> bool {
+        self.messages_collected
+    }
+
+    pub fn get_trusted(&self) -> bool {
+        self.trusted
+    }
+
+    pub fn get_delivered(&self) -> bool {
+        self.delivered
+    }
+
+    pub fn get_message_digests(&self) -> &Vec<Option<u64>> {
+        &self.message_digests
+    }
+
+    pub fn get_buddies(&self) -> &Vec<bool> {
+        &self.buddies
+    }
+}
+
+impl JsonConversion<BarycentricRoundSnapshot> for BarycentricRoundSnapshot {}
+
+impl<T> Inspect for BarycentricRoundMonitor<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
+{
+    type Snapshot = BarycentricRoundSnapshot;
+
+    // Slots of threads that have not sent a message yet hold a placeholder without protocol information.
+    fn snapshot(&self) -> BarycentricRoundSnapshot {
+        BarycentricRoundSnapshot {
+            message_count: self.count.messages,
+            barycentric_report_count: self.count.barycentric_reports,
+            buddy_count: self.count.buddies,
+            messages_collected: self.state.messages,
+            trusted: self.state.trusted,
+            delivered: self.state.buddies,
+            message_digests: self.content.messages.iter()
+                .map(|message| (!message.get_protocol_information().is_empty()).then(|| message.get_digest()))
+                .collect(),
+            buddies: self.content.buddies.clone(),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use serde::{Deserialize, Serialize};
+use std::any::type_name;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
 
 // # Trait Description:
 // This trait provides a unified interface for serializing and deserializing types
@@ -29,4 +32,269 @@ where
     fn write_json(&self) -> String {
         serde_json::to_string(self).expect("Error: JSON object could not be created")
     }
+
+    // # Method Description
+    // Computes a digest of the instance from its JSON representation. A fixed FNV-1a hash is used
+    // instead of the standard library hasher, so every thread computes the same digest for the same instance.
+    // # Returns:
+    // * A `u64` digest of the instance.
+    fn json_digest(&self) -> u64 {
+        let mut digest: u64 = 0xcbf29ce484222325;
+        for byte in self.write_json().as_bytes() {
+            digest ^= *byte as u64;
+            digest = digest.wrapping_mul(0x100000001b3);
+        }
+        digest
+    }
+
+    // # Method Description
+    // Computes a SHA-256 digest of the instance from its JSON representation. Unlike `json_digest`, no
+    // thread can craft two instances with the same digest, so it can stand for the instance where a
+    // Byzantine thread would gain from a collision.
+    // # Returns:
+    // * The hex-encoded digest of the instance.
+    fn content_digest(&self) -> String {
+        Sha256::digest(self.write_json().as_bytes()).iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+// # Enum Description:
+// This enum represents the encoding of the frames exchanged between threads (see `WireFormat` and
+// `Frame`), selected per hub with `ClusterConfig::with_codec`. Frames are `Bytes`, so that every format is
+// carried as is, and a frame sent to several threads is shared between their channels instead of copied.
+//
+// # Variants:
+// * Json - Headers and payloads are encoded as JSON, like `JsonConversion` does.
+// * Bincode - Headers and payloads are bincode encodings, which do not describe their content: the kind
+//   of a frame tells which type its payload is decoded as.
+// * Cbor - Headers and payloads are CBOR encodings, which describe their content like JSON does.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Codec {
+    Json,
+    Bincode,
+    Cbor,
+}
+
+impl Codec {
+    // # Method Description
+    // Encodes a value in this format.
+    // # Parameters:
+    // * value - The value.
+    // # Returns:
+    // * The encoding of the value.
+    fn encode<S: Serialize + ?Sized>(&self, value: &S) -> Vec<u8> {
+        match self {
+            Codec::Json => serde_json::to_vec(value).expect("Error: JSON object could not be created"),
+            Codec::Bincode => bincode::serialize(value).expect("Error: bincode object could not be created"),
+            Codec::Cbor => {
+                let mut bytes = vec![];
+                ciborium::into_writer(value, &mut bytes).expect("Error: CBOR object could not be created");
+                bytes
+            },
+        }
+    }
+
+    // # Method Description
+    // Decodes a value encoded in this format.
+    // # Parameters:
+    // * data - The encoding of the value.
+    // # Returns:
+    // * `Ok(D)` if decoding succeeds, otherwise a description of the decoding error.
+    fn decode<D: DeserializeOwned>(&self, data: &[u8]) -> Result<D, String> {
+        match self {
+            Codec::Json => serde_json::from_slice(data).map_err(|error| error.to_string()),
+            Codec::Bincode => bincode::deserialize(data).map_err(|error| error.to_string()),
+            Codec::Cbor => ciborium::from_reader(data).map_err(|error| error.to_string()),
+        }
+    }
+}
+
+// The size of the fixed part of a frame: the kind of its payload and the length of its header.
+const FRAME_PREFIX_SIZE: usize = 12;
+
+// # Struct Description:
+// This struct is a frame split into its parts, without decoding them. A frame is laid out as the kind of
+// its payload (the `FrameKind` tag of the payload type, 8 bytes), the length of its header (4 bytes),
+// the header, and the payload, all big-endian. Receivers dispatch a frame on its kind and filter it on its
+// header, so that the payload, e.g. a large report, is only decoded once it is needed. The parts are
+// slices of the received frame, and are not copied.
+//
+// # Fields:
+// * kind - The kind tag of the payload type.
+// * header - The encoded header, empty for frames without header.
+// * payload - The encoded payload.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    kind: u64,
+    header: Bytes,
+    payload: Bytes,
+}
+
+impl Frame {
+    // # Method Description
+    // Splits a frame into its parts.
+    // # Parameters:
+    // * frame - The frame.
+    // # Returns:
+    // * `Ok(Frame)` if the frame is laid out as described above, otherwise a description of the error.
+    pub fn parse(frame: &Bytes) -> Result<Self, String> {
+        if frame.len() < FRAME_PREFIX_SIZE {
+            return Err(format!("frame of {} bytes is too short", frame.len()))
+        }
+        let kind = u64::from_be_bytes(frame[..8].try_into().expect("8 bytes"));
+        let header_size = u32::from_be_bytes(frame[8..FRAME_PREFIX_SIZE].try_into().expect("4 bytes")) as usize;
+        if frame.len() - FRAME_PREFIX_SIZE < header_size {
+            return Err(format!("frame header of {header_size} bytes exceeds the frame"))
+        }
+        Ok(Self {
+            kind,
+            header: frame.slice(FRAME_PREFIX_SIZE..FRAME_PREFIX_SIZE + header_size),
+            payload: frame.slice(FRAME_PREFIX_SIZE + header_size..),
+        })
+    }
+
+    // # Method Description
+    // Assembles a frame from an encoded header and payload.
+    // # Parameters:
+    // * kind - The kind tag of the payload type.
+    // * header - The encoded header.
+    // * payload - The encoded payload.
+    // # Returns:
+    // * The frame.
+    fn assemble(kind: u64, header: &[u8], payload: &[u8]) -> Bytes {
+        let mut frame = BytesMut::with_capacity(FRAME_PREFIX_SIZE + header.len() + payload.len());
+        frame.put_u64(kind);
+        frame.put_u32(header.len() as u32);
+        frame.put_slice(header);
+        frame.put_slice(payload);
+        frame.freeze()
+    }
+
+    // # Method Description
+    // Checks whether the payload of the frame is of a type.
+    // # Returns:
+    // * `true` if the payload is a `T`.
+    pub fn is<T: FrameKind>(&self) -> bool {
+        self.kind == T::FRAME_KIND
+    }
+
+    // # Method Description
+    // Decodes the header of the frame.
+    // # Parameters:
+    // * codec - The encoding of the frame.
+    // # Returns:
+    // * `Ok(H)` if decoding succeeds, otherwise a description of the decoding error.
+    pub fn read_header<H: DeserializeOwned>(&self, codec: Codec) -> Result<H, String> {
+        codec.decode(&self.header)
+    }
+
+    // # Method Description
+    // Decodes the payload of the frame.
+    // # Parameters:
+    // * codec - The encoding of the frame.
+    // # Returns:
+    // * `Ok(T)` if the payload is a `T` and decoding succeeds, otherwise a description of the error.
+    pub fn read_payload<T: DeserializeOwned + FrameKind>(&self, codec: Codec) -> Result<T, String> {
+        if !self.is::<T>() {
+            return Err(format!("frame is not a {}", type_name::<T>()))
+        }
+        codec.decode(&self.payload)
+    }
+
+    // # Method Description
+    // Replaces the header of the frame, e.g. to relay a signal: the payload is reused as it was encoded.
+    // # Parameters:
+    // * codec - The encoding of the frame.
+    // * header - The new header.
+    // # Returns:
+    // * The new frame.
+    pub fn with_header<H: Serialize>(&self, codec: Codec, header: &H) -> Bytes {
+        Self::assemble(self.kind, &codec.encode(header), &self.payload)
+    }
+
+    pub fn get_kind(&self) -> u64 {
+        self.kind
+    }
+
+    pub fn get_payload(&self) -> &Bytes {
+        &self.payload
+    }
+}
+
+// # Trait Description:
+// This trait gives every type sent as the payload of a frame the constant tag that is the kind of its
+// frames (see `Frame`). Tags are part of the wire format: unlike type names, they do not change with the
+// compiler, the crate version, or the module a type moves to, so threads built apart still dispatch each
+// other's frames. A generic type has one tag whatever its parameters, which receivers know from their hub.
+// The tags of the crate are listed in `frame_kinds`; they must stay unique, and must not change once released.
+pub trait FrameKind {
+    const FRAME_KIND: u64;
+}
+
+// The kind tags of the frame types of the crate, kept in one place so that they stay unique (see `FrameKind`).
+pub(crate) mod frame_kinds {
+    pub const MESSAGE_FRAME: u64 = 1;
+    pub const SIGNAL_FRAME: u64 = 2;
+    pub const REPORT_FRAME: u64 = 3;
+    pub const AGGREGATED_REPORT_FRAME: u64 = 4;
+    pub const BARYCENTRIC_REPORT_FRAME: u64 = 5;
+    pub const COMMITMENT_FRAME: u64 = 6;
+    pub const ROUND_DIGESTS_FRAME: u64 = 7;
+    pub const PROBE_FRAME: u64 = 8;
+    pub const DECODE_FAILURE_FRAME: u64 = 9;
+    pub const SIGNAL_BATCH_FRAME: u64 = 10;
+    pub const SYNC_REQUEST_FRAME: u64 = 11;
+    pub const GOSSIP_DIGEST_FRAME: u64 = 12;
+    pub const VIEW_CHANGE_FRAME: u64 = 13;
 }
+
+// # Trait Description:
+// This trait generalizes `JsonConversion` to every `Codec`: it encodes the frames a thread sends and
+// decodes the frames it receives in the encoding of its hub (see `Frame`). It is implemented by every type
+// implementing `JsonConversion`. Signatures and digests are always computed on the JSON representation,
+// so they do not depend on the encoding.
+//
+// # Type Parameters:
+// * T - The concrete type that is encoded and decoded.
+pub trait WireFormat<T>: Serialize
+where
+    T: Serialize + DeserializeOwned + FrameKind,
+{
+    // # Method Description
+    // Constructs a new instance of type `T` from the payload of a frame, ignoring its header.
+    // # Parameters:
+    // * codec - The encoding of the frame.
+    // * data - The frame.
+    // # Returns:
+    // * `Ok(T)` if decoding succeeds, otherwise a description of the decoding error.
+    fn read_frame(codec: Codec, data: &Bytes) -> Result<T, String> {
+        Frame::parse(data)?.read_payload(codec)
+    }
+
+    // # Method Description
+    // Converts the instance into a frame without header.
+    // # Parameters:
+    // * codec - The encoding of the frame.
+    // # Returns:
+    // * The frame.
+    fn write_frame(&self, codec: Codec) -> Bytes {
+        Frame::assemble(T::FRAME_KIND, &[], &codec.encode(self))
+    }
+
+    // # Method Description
+    // Converts the instance into a frame with a header, which receivers can decode without the instance.
+    // # Parameters:
+    // * codec - The encoding of the frame.
+    // * header - The header.
+    // # Returns:
+    // * The frame.
+    fn write_frame_with_header<H: Serialize>(&self, codec: Codec, header: &H) -> Bytes {
+        Frame::assemble(T::FRAME_KIND, &codec.encode(header), &codec.encode(self))
+    }
+}
+
+impl<T, U> WireFormat<T> for U
+where
+    T: Serialize + DeserializeOwned + FrameKind,
+    U: JsonConversion<T>,
+{}
@@ -0,0 +1,103 @@
+use serde::{Serialize, Deserialize};
+
+use crate::config::derive_seed;
+use crate::round::Round;
+
+// # Enum Description:
+// This enum represents how a leader is elected for every round, so that leader-based replication (e.g.
+// PBFT or HotStuff-style protocols) can be built on reliable broadcast. Under an election, reliable handles
+// only accept the Input of the round's leader for the "reliable" instances of the round: the Inputs of every
+// other thread are rejected, published as `Event::NonLeaderInput`, and never echoed, so they are never
+// delivered by a correct thread. The other protocols (witness, aggregated witness, barycentric, ...) are
+// not affected.
+//
+// # Variants:
+// * Disabled - No leader is elected, and every thread's Inputs are accepted.
+// * RoundRobin - The leader of round `r` is thread `r mod n`.
+// * Seeded - The leader of every round is drawn from a digest of the experiment seed and the round number,
+//   so that every thread elects the same leader while the schedule cannot be guessed without the seed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum LeaderElection {
+    #[default]
+    Disabled,
+    RoundRobin,
+    Seeded,
+}
+
+// # Struct Description:
+// This struct elects the leader of every round, following the `LeaderElection` of a cluster. The leader of
+// a round only depends on the configuration of the cluster, never on the electing thread, so every thread
+// elects the same leader without exchanging a message.
+//
+// # Fields:
+// * election - The election of the cluster.
+// * thread_count - The number of threads of the cluster, among which the leader is elected.
+// * seed - The experiment seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderSchedule {
+    election: LeaderElection,
+    thread_count: u32,
+    seed: u64,
+}
+
+impl LeaderSchedule {
+    pub fn new(election: LeaderElection, thread_count: u32, seed: u64) -> Self {
+        Self {
+            election,
+            thread_count,
+            seed
+        }
+    }
+
+    pub fn get_election(&self) -> LeaderElection {
+        self.election
+    }
+
+    // # Method Description:
+    // This method elects the leader of a round.
+    //
+    // # Parameters:
+    // * round_number - The round.
+    //
+    // # Returns:
+    // * The ID of the leader of the round, or `None` if no leader is elected.
+    pub fn get_leader(&self, round_number: Round) -> Option<u32> {
+        self.get_leader_in_view(round_number, 0)
+    }
+
+    // # Method Description:
+    // This method elects the leader of a round once it changed views (see `Pacemaker`): every view of a
+    // round moves its leader to the next thread, so that a round whose leader is silent gets a new leader.
+    //
+    // # Parameters:
+    // * round_number - The round.
+    // * view - The view of the round, 0 until it changes views.
+    //
+    // # Returns:
+    // * The ID of the leader of the round in the view, or `None` if no leader is elected.
+    pub fn get_leader_in_view(&self, round_number: Round, view: u32) -> Option<u32> {
+        if self.thread_count == 0 {
+            return None
+        }
+        let first = match self.election {
+            LeaderElection::Disabled => return None,
+            LeaderElection::RoundRobin => round_number.0 as u64,
+            LeaderElection::Seeded => derive_seed(self.seed, round_number.0, "leader election"),
+        };
+        Some((first.wrapping_add(view as u64) % self.thread_count as u64) as u32)
+    }
+
+    // # Method Description:
+    // This method checks whether a thread may broadcast in a round.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * round_number - The round.
+    // * view - The view of the round, 0 until it changes views.
+    //
+    // # Returns:
+    // * `true` if no leader is elected, or if the thread is the leader of the round in the view.
+    pub fn accepts(&self, id: u32, round_number: Round, view: u32) -> bool {
+        self.get_leader_in_view(round_number, view).is_none_or(|leader| leader == id)
+    }
+}
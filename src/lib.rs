@@ -3,4 +3,52 @@ pub mod reliable;
 pub mod witness; 
 pub mod aggregated_witness; 
 pub mod barycentric_agreement;
-pub mod json; 
\ No newline at end of file
+pub mod json; 
+pub mod config;
+pub mod events;
+pub mod metrics;
+pub mod emulation;
+pub mod mock;
+pub mod faults;
+pub mod multiplex;
+pub mod conformance;
+pub mod drive;
+pub mod inspect;
+pub mod payload;
+pub mod dissemination;
+pub mod identity;
+pub mod testkit;
+pub mod storage;
+pub mod transport;
+pub mod signing;
+pub mod trace;
+pub mod middleware;
+pub mod byzantine;
+pub mod delivered;
+pub mod binary_agreement;
+pub mod membership;
+pub mod retention;
+pub mod replay;
+pub mod roster;
+pub mod round;
+pub mod capabilities;
+pub mod divergence;
+pub mod scenario;
+pub mod demo;
+pub mod certificate;
+pub mod dispersal;
+pub mod extension;
+pub mod health;
+pub mod snapshot;
+pub mod descriptor;
+pub mod ordering;
+pub mod consistent;
+pub mod approximate_agreement;
+pub mod gather;
+pub mod leader;
+pub mod chained_consensus;
+pub mod pacemaker;
+pub mod batching;
+mod logging;
+#[cfg(feature = "ffi")]
+mod ffi;
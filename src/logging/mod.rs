@@ -0,0 +1,25 @@
+// # Macro Description:
+// This macro logs a message of the library as a `tracing` event, so that applications collect it with the
+// subscriber of their choice, filtered by level and by the span of the protocol instance it belongs to
+// (see `ReliableHandleLoop::process_signal`). With the `stdout` feature, enabled by default for the
+// simulator, the message is also printed to stdout.
+//
+// # Parameters:
+// * level - The level of the event: `trace`, `debug`, `info`, `warn`, or `error`.
+// * fields - The structured fields of the event, in braces, in the syntax of the `tracing` macros
+//   (e.g. `{ node = id, instance = %instance_number }`), if any.
+// * message - The format string of the message, and its arguments.
+macro_rules! log {
+    ($level:ident, { $($field:tt)+ }, $($message:tt)+) => {{
+        tracing::$level!($($field)+, $($message)+);
+        #[cfg(feature = "stdout")]
+        println!($($message)+);
+    }};
+    ($level:ident, $($message:tt)+) => {{
+        tracing::$level!($($message)+);
+        #[cfg(feature = "stdout")]
+        println!($($message)+);
+    }};
+}
+
+pub(crate) use log;
@@ -3,33 +3,73 @@
 // # Author: Haruta Otaki
 // # Date: June 19th, 2025
 
-use std::{env}; 
+use std::{collections::{BTreeMap, BTreeSet}, env, fmt::Debug, hash::Hash, net::SocketAddr, path::{Path, PathBuf}, sync::{Arc, Mutex}, time::{Duration, Instant}}; 
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use futures::{future::join_all, StreamExt};
 use rust_project::aggregated_witness::{AggregatedWitnessCommunication, AggregatedWitnessCommunicator, AggregatedWitnessHub};
-use rust_project::barycentric_agreement::{BarycentricCommunication, BarycentricCommunicator, BarycentricHub};
-use tokio::sync::mpsc::{self, Receiver, Sender};
-use tokio::{task::JoinHandle};
+use rust_project::barycentric_agreement::{BarycentricCommunication, BarycentricCommunicator, BarycentricHub, BuddyCertificate};
+use tokio::sync::{Barrier, broadcast, mpsc::{self, Receiver, Sender}};
+use tokio::{task::JoinHandle, signal::unix::{signal, SignalKind}};
+use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::{TcpListener, TcpStream}};
 use rust_project::basic::{BasicCommunication, BasicHub, BasicCommunicator};
-use rust_project::reliable::{ReliableCommunication, ReliableHub, ReliableCommunicator};
-use rust_project::witness::{WitnessCommunication, WitnessHub, WitnessCommunicator};
+use rust_project::reliable::{ReliableCommunication, ReliableHub, ReliableCommunicator, Signal, SignalType, ObjectContent};
+use rust_project::witness::{WitnessCommunication, WitnessHub, WitnessCommunicator, RoundValues, Report, ReportType};
+use rust_project::capabilities::{Capabilities, CapabilityMismatch, Negotiation, Protocol};
+use rust_project::divergence::DigestGossip;
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::json::{Codec, JsonConversion, WireFormat};
+use rust_project::events::{Event, DecodePolicy};
+use rust_project::multiplex::{Lane, LaneResize, LaneScaling, tag_frame};
+use rust_project::emulation::{LinkConditioner, LinkConditions, NetworkEmulation, RegionTopology, Stabilization};
+use rust_project::mock::MockReliableCommunicator;
+use rust_project::faults::{ByzantineBehavior, CrashPoint, Fault, Outage, OutagePolicy};
+use rust_project::byzantine::ByzantineCommunicator;
+use rust_project::metrics::{DeliveryPaths, FrameStatistics, LatencyHistogram, MetricsReport, MetricsComparison, Metrics, read_metrics_export, write_metrics_export};
+use rust_project::drive::{drive, HandleMode};
+use rust_project::basic::{CollectError, Message, RepeatedCollectPolicy};
+use rust_project::delivered::Delivered;
+use rust_project::binary_agreement::{BinaryAgreement, BinaryVote};
+use rust_project::inspect::WitnessRoundSnapshot;
+use rust_project::payload::NumericPayload;
+use rust_project::dissemination::{BroadcastTree, Dissemination, Disseminator};
+use rust_project::identity::IdentityStore;
+use rust_project::membership::MembershipCertificate;
+use rust_project::retention::RetentionPolicy;
+use rust_project::demo::{DemoPacing, Narration};
+use rust_project::extension::{ExtensionCommunication, ExtensionDelivery, ExtensionFrame};
+use rust_project::health::{ClusterHealth, serve_health};
+use rust_project::descriptor::{ProtocolDescriptor, describe_protocols};
+use bytes::Bytes;
+use rust_project::scenario::{DeadlineReport, NodeScript, RoundDeadline, Scenario, ScenarioAction, ScriptOutcome};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::{Transport, ChannelTransport, TcpTransport, ConnectionEvent};
+use rust_project::signing::Authentication;
+use rust_project::middleware::{Layer, MiddlewareChain};
+use rust_project::trace::{DeliveryRecord, TraceChecker, TraceSampling, check_trace, write_trace};
 
 // # Function Description: 
 // This function creates a set of asynchronous channels for inter-thread communication.
-// # Parameters:
+// * config - the cluster configuration (thread count, buffer size)
 // * thread_count - total number of threads in the simulation
 // # Returns
 // * a vector of sending handles per thread
 //  * a vector of receiving handles per thread
-fn create_channels(thread_count: u32) -> (Vec<Sender<String>>, Vec<Receiver<String>> ) {
-    let mut receivers: Vec<Receiver<String>> = vec![];
-    let mut transmitters: Vec<Sender<String>> = vec![];
+fn create_channels(config: &ClusterConfig) -> (Vec<Sender<Bytes>>, Vec<Receiver<Bytes>> ) {
+    let channels = ChannelTransport::create_channels(config);
+    (channels.transmitters, channels.receivers)
+}
 
-    for _ in 0..thread_count{
-        // adjust the buffer size according to the number of threads participating 
-        let (tx, rx) = mpsc::channel(256); 
-        transmitters.push(tx);
-        receivers.push(rx);
-    }
-    (transmitters, receivers)
+// # Function Description:
+// This function encodes a signal forged as JSON, with fields a node would never set itself, as a tagged
+// signal frame.
+// # Parameters:
+// * forged - the forged signal.
+// * codec - the codec of the cluster.
+// # Returns
+// * the frame, for the `Lane::Signal` of the injected nodes.
+fn forged_signal_frame(forged: &serde_json::Value, codec: Codec) -> Bytes {
+    let signal = Signal::<String>::read_json(&forged.to_string()).expect("a forged signal is a signal");
+    tag_frame(Lane::Signal, signal.write_signal_frame(codec))
 }
 
 // # Function Description: 
@@ -54,116 +94,121 @@ fn create_witness_thread (id: u32, mut witness_communicator: WitnessCommunicator
             if id == 0 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 0).await; 
+                witness_communicator.witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 0).await; 
+                witness_communicator.witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 0).await; 
+                witness_communicator.witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 3 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 0).await; 
+                witness_communicator.witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 4 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 0).await; 
+                witness_communicator.witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 5 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 0).await; 
+                witness_communicator.witness_broadcast(message, Round(0)).await; 
                 
             }
           
             println!("id: {id}, collecting...");
-            witness_communicator.witness_collect(0).await; 
+            witness_communicator.witness_collect(Round(0)).await; 
 
             println!("Testing... Round 2, witness communication"); 
             if id == 0 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 1).await; 
+                witness_communicator.witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 1).await; 
+                witness_communicator.witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 1).await; 
+                witness_communicator.witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 3 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 1).await; 
+                witness_communicator.witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 4 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 1).await; 
+                witness_communicator.witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 5 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                witness_communicator.witness_broadcast(message, 1).await; 
+                witness_communicator.witness_broadcast(message, Round(1)).await; 
                 
             }
           
             println!("id: {id}, collecting...");
-            witness_communicator.witness_collect(1).await; 
+            witness_communicator.witness_collect(Round(1)).await; 
+
+            //test: subscribing to an already delivered round still succeeds
+            let subscription = witness_communicator.witness_subscribe(Round(0)); 
+            let values = subscription.await.unwrap(); 
+            println!("id: {id}, late subscription to round 1 delivered {} values", values.len());
 
             //test reliable broadcast           
             if id == 0 {
                 println!("Testing... Round 3, reliable communication"); 
                 println!("id: {id}, reliable broadcasting..."); 
                 let message = format!("reliable broadcast message by {id}");
-                witness_communicator.reliable_broadcast(message, 0, 2).await; 
+                witness_communicator.reliable_broadcast(message, Instance(0), Round(2)).await; 
             }
 
             println!("id: {id}, reliable receiving...");
-            witness_communicator.reliable_recv(Some(0), 0, 2).await; 
+            witness_communicator.reliable_recv(Some(0), Instance(0), Round(2)).await; 
 
              //test send() & recv()
              if id == 2 {
                 println!("Testing... Round 3, basic communication"); 
                 println!("id: {id}, sending..."); 
                 let message = format!("message from {} to {}", id, 1);
-                witness_communicator.basic_send(1, message, 2).await; 
+                witness_communicator.basic_send(1, message, Round(2)).await; 
             }
 
             if id == 1 {
                 println!("id: {id}, receiving...");
-                witness_communicator.basic_recv(Some(2), 2).await; 
+                witness_communicator.basic_recv(Some(2), Round(2)).await; 
             }
 
             witness_communicator.terminate_reliable_handle(reliable_handle);
@@ -198,117 +243,117 @@ fn create_barycentric_agreement_thread (id: u32, mut barycentric_communicator: B
             if id == 0 {
                 println!("id: {id}, barycentric agreement..."); 
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 0).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(0)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, barycentric agreement..."); 
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message,  0).await; 
+                barycentric_communicator.barycentric_agreement(message,  Round(0)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 0).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(0)).await; 
                 
             }
 
              if id == 3 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 0).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(0)).await; 
                 
             }
 
              if id == 4 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 0).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(0)).await; 
                 
             }
 
              if id == 5 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 0).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(0)).await; 
                 
             }
           
             println!("id: {id}, collecting...");
-            barycentric_communicator.barycentric_collect(0).await; 
+            barycentric_communicator.barycentric_collect(Round(0)).await; 
 
           
             println!("Testing... Round 2, barycentric agreement"); 
             if id == 0 {
                 println!("id: {id}, barycentric agreement..."); 
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 1).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(1)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, barycentric agreement..."); 
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 1).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(1)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 1).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(1)).await; 
                 
             }
 
              if id == 3 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 1).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(1)).await; 
                 
             }
 
              if id == 4 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 1).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(1)).await; 
                 
             }
 
              if id == 5 {
                 println!("id: {id}, barycentric agreement...");
                 let message = format!("barycentric agreement broadcast message by {id}");
-                barycentric_communicator.barycentric_agreement(message, 1).await; 
+                barycentric_communicator.barycentric_agreement(message, Round(1)).await; 
                 
             }
           
             println!("id: {id}, collecting...");
-            barycentric_communicator.barycentric_collect(1).await; 
+            barycentric_communicator.barycentric_collect(Round(1)).await; 
 
             //test reliable broadcast           
             if id == 0 {
                 println!("Testing... Round 3, reliable communication"); 
                 println!("id: {id}, reliable broadcasting..."); 
                 let message = format!("reliable broadcast message by {id}");
-                barycentric_communicator.reliable_broadcast(message, 0, 2).await; 
+                barycentric_communicator.reliable_broadcast(message, Instance(0), Round(2)).await; 
             }
 
             println!("id: {id}, reliable receiving...");
-            barycentric_communicator.reliable_recv(Some(0), 0, 2).await; 
+            barycentric_communicator.reliable_recv(Some(0), Instance(0), Round(2)).await; 
 
              //test send() & recv()
              if id == 2 {
                 println!("Testing... Round 3, basic communication"); 
                 println!("id: {id}, sending..."); 
                 let message = format!("message from {} to {}", id, 1);
-                barycentric_communicator.basic_send(1, message, 2).await; 
+                barycentric_communicator.basic_send(1, message, Round(2)).await; 
             }
 
             if id == 1 {
                 println!("id: {id}, receiving...");
-                barycentric_communicator.basic_recv(Some(2), 2).await; 
+                barycentric_communicator.basic_recv(Some(2), Round(2)).await; 
             }
 
             barycentric_communicator.terminate_reliable_handle(reliable_handle);
@@ -338,40 +383,64 @@ fn create_reliable_thread (id:u32, mut reliable_communicator: ReliableCommunicat
                 println!("Testing... Round 1, reliable communication"); 
                 println!("id: {id}, reliable broadcasting..."); 
                 let message = format!("reliable broadcast message by {id}");
-                reliable_communicator.reliable_broadcast(message, 0, 0).await; 
+                reliable_communicator.reliable_broadcast(message, Instance(0), Round(0)).await; 
             }
 
-            //test: create f faulty thread
-            // if id == 1 && count == 0{
-            //     println!("id: {id}, shutdown...");
-            //     thread.recv(None).await; 
-            // }
-
             println!("id: {id}, reliable receiving...");
-            reliable_communicator.reliable_recv(Some(0), 0, 0).await; 
+            reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await; 
             
             if id == 1 {
                 println!("Testing... Round 2, reliable communication"); 
                 println!("id: {id}, reliable broadcasting..."); 
                 let message = format!("reliable broadcast message by {id}");
-                reliable_communicator.reliable_broadcast(message, 1, 0).await; 
+                reliable_communicator.reliable_broadcast(message, Instance(1), Round(0)).await; 
             }
 
             // test: multiple reliable_broadcast calls
             println!("id: {id}, reliable receiving...");
-            reliable_communicator.reliable_recv(Some(1),1, 0).await; 
+            reliable_communicator.reliable_recv(Some(1),Instance(1), Round(0)).await; 
             
             //test send() & recv()
             if id == 2 {
                 println!("Testing... Round 3, basic communication"); 
                 println!("id: {id}, sending..."); 
                 let message = format!("message from {} to {}", id, 1);
-                reliable_communicator.basic_send(1, message, 0).await; 
+                reliable_communicator.basic_send(1, message, Round(0)).await; 
             }
 
             if id == 1 {
                 println!("id: {id}, receiving...");
-                reliable_communicator.basic_recv(Some(2), 0).await; 
+                reliable_communicator.basic_recv(Some(2), Round(0)).await; 
+            }
+
+            //test: batched receive of several reliable instances of the same round
+            if id == 0 {
+                println!("Testing... Round 4, batched reliable communication"); 
+                for instance_number in 2..5 {
+                    let message = format!("batched reliable broadcast message {instance_number} by {id}");
+                    reliable_communicator.reliable_broadcast(message, Instance(instance_number), Round(1)).await; 
+                }
+            }
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let mut batched = vec![];
+            while batched.len() < 3 && Instant::now() < deadline {
+                batched.extend(reliable_communicator.reliable_recv_batch(Some(0), Round(1), 3 - batched.len(), deadline).await);
+            }
+            println!("id: {id}, batch received {} of 3 messages", batched.len());
+
+            //test: phase latencies of the reliable instances delivered above
+            let latencies = reliable_communicator.phase_latencies("reliable");
+            println!("id: {id}, p99 latencies: input->echo quorum {:?}, echo quorum->vote quorum {:?}, vote quorum->deliver {:?}", 
+                latencies.get_input_to_echo_quorum().percentile(99.0), 
+                latencies.get_echo_quorum_to_vote_quorum().percentile(99.0), 
+                latencies.get_vote_quorum_to_deliver().percentile(99.0));
+
+            //test: the exported metric report embeds the configuration it was measured under
+            let report = reliable_communicator.metrics_report();
+            let exported = report.write_json();
+            match MetricsReport::read_json(&exported) {
+                Ok(imported) if imported == report => println!("id: {id}, metrics report exported ({} bytes, seed {})", exported.len(), imported.get_config().get_seed()),
+                _ => println!("id: {id}, metrics report could not be read back"),
             }
 
             reliable_communicator.terminate_reliable_handle(reliable_handle);
@@ -380,6 +449,63 @@ fn create_reliable_thread (id:u32, mut reliable_communicator: ReliableCommunicat
         }
     })
 }
+// # Function Description:
+// This function spawns a thread crashed by a `Fault::Crash`, in place of `create_reliable_thread`. A thread
+// crashed after a round receives the reliable instances the scripted threads broadcast up to that round
+// (so that it echoes and votes for them), then terminates its handle; a thread crashed before starting
+// never starts it.
+// # Parameters:
+// * id - the unique identifier for this thread.
+// * reliable_communicator - a `ReliableCommunicator` instance, encapsulating communication logic for this thread.
+// * crash_point - when the thread crashes.
+// # Returns:
+// * a `JoinHandle` resolving to the communicator, which must be kept until the correct threads finish.
+fn create_crashed_reliable_thread(id: u32, mut reliable_communicator: ReliableCommunicator<String>, crash_point: CrashPoint) -> JoinHandle<ReliableCommunicator<String>> {
+    tokio::spawn(async move {
+        if let CrashPoint::AfterRound(round) = crash_point {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+            reliable_communicator.reliable_recv(Some(1), Instance(1), Round(0)).await;
+            if round >= 1 {
+                for instance_number in 2..5 {
+                    reliable_communicator.reliable_recv(Some(0), Instance(instance_number), Round(1)).await;
+                }
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+        }
+        println!("id: {id}, crashed ({crash_point:?})");
+        reliable_communicator
+    })
+}
+
+// # Function Description:
+// This function spawns a thread made Byzantine by a `Fault::Byzantine`, in place of `create_reliable_thread`.
+// The thread answers the signals of every instance the correct threads broadcast following its behavior,
+// and broadcasts an instance of its own (instance 0 of round 2), which nobody waits for. 
+// # Parameters:
+// * id - the unique identifier for this thread.
+// * byzantine_communicator - the thread's `ByzantineCommunicator`.
+// # Returns:
+// * a `JoinHandle` resolving to the communicator, which must be kept until the correct threads finish.
+fn create_byzantine_reliable_thread(id: u32, mut byzantine_communicator: ByzantineCommunicator<String, ReliableCommunicator<String>>) 
+    -> JoinHandle<ByzantineCommunicator<String, ReliableCommunicator<String>>> {
+    tokio::spawn(async move {
+        let _byzantine_handle = byzantine_communicator.initialize_byzantine_handle();
+        byzantine_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(0), Round(2)).await;
+        byzantine_communicator
+    })
+}
+
+// # Function Description:
+// This function derives the conflicting payload a Byzantine thread sends in place of a genuine one.
+// # Parameters:
+// * message - the genuine payload.
+// # Returns
+// * The conflicting payload.
+fn conflicting_message(message: &String) -> String {
+    format!("conflicting {message}")
+}
+
 // # Function Description: 
 // This function spawns a single asynchronous thread simulating a node in a basic message-passing network. 
 // The thread executes a predefined sequence of basic communication actions for testing purposes.
@@ -395,29 +521,29 @@ fn create_basic_thread (id:u32, mut basic_communicator: BasicCommunicator<String
             if id == 0 {
                 let message = format!("message from {} to {}", id, 1);
                 println!("id: {id}, sending..."); 
-                basic_communicator.basic_send(1, message, 0).await;
+                basic_communicator.basic_send(1, message, Round(0)).await;
             }
             if id == 1 {
                 let message = format!("message from {} to {}", id, 2);
                 println!("id: {id}, sending..."); 
-                basic_communicator.basic_send(2, message, 0).await;
+                basic_communicator.basic_send(2, message, Round(0)).await;
             }
             if id == 1 {
                 println!("id: {id}, receiving..."); 
-                basic_communicator.basic_recv(None, 0).await; 
+                basic_communicator.basic_recv(None, Round(0)).await; 
             }
             if id == 2 {
                 println!("id: {id}, receiving..."); 
-                basic_communicator.basic_recv(Some(1), 0).await; 
+                basic_communicator.basic_recv(Some(1), Round(0)).await; 
             }
             if id == 0 {
                 println!("id: {id}, broadcasting..."); 
                 let message = format!("broadcast message from {id}");
-                basic_communicator.basic_broadcast(message, 0).await;
+                basic_communicator.basic_broadcast(message, Round(0)).await;
             }
 
             println!("id: {id}, receiving..."); 
-            basic_communicator.basic_recv(Some(0), 0).await; 
+            basic_communicator.basic_recv(Some(0), Round(0)).await; 
 
             println!("id: {id}, break");
             break; 
@@ -426,6 +552,141 @@ fn create_basic_thread (id:u32, mut basic_communicator: BasicCommunicator<String
     })
 }
 
+// # Function Description:
+// This function spawns a thread crashed by a `Fault::Crash`, in place of `create_witness_thread`. A thread
+// crashed after a round takes part in the witness rounds up to that round, broadcasting its value if the
+// scripted threads would (IDs 0 to 5) and collecting the round, and in the reliable instance of round 2,
+// then terminates its handles; a thread crashed before starting never starts them.
+// # Parameters:
+// * id - the unique identifier for this thread.
+// * witness_communicator - a `WitnessCommunicator` instance, encapsulating communication logic for this thread.
+// * crash_point - when the thread crashes.
+// # Returns:
+// * a `JoinHandle` resolving to the communicator, which must be kept until the correct threads finish.
+fn create_crashed_witness_thread(id: u32, mut witness_communicator: WitnessCommunicator<String>, crash_point: CrashPoint) -> JoinHandle<WitnessCommunicator<String>> {
+    tokio::spawn(async move {
+        if let CrashPoint::AfterRound(round) = crash_point {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            for witness_round in 0..=round.min(1) {
+                if id <= 5 {
+                    witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(witness_round)).await;
+                }
+                witness_communicator.witness_collect(Round(witness_round)).await;
+            }
+            if round >= 2 {
+                witness_communicator.reliable_recv(Some(0), Instance(0), Round(2)).await;
+            }
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+        }
+        println!("id: {id}, crashed ({crash_point:?})");
+        witness_communicator
+    })
+}
+
+// # Function Description:
+// This function spawns a thread following the script of a scenario file, in place of `create_witness_thread`.
+// The thread runs its actions, then keeps its handles running until every scripted thread is done, so that
+// its peers can still complete their rounds; a thread whose script crashes it terminates its handles first.
+// # Parameters:
+// * witness_communicator - a `WitnessCommunicator` instance, encapsulating communication logic for this thread.
+// * script - the script of the thread.
+// * timeout - how long every action waits for a delivery.
+// * barrier - the barrier every scripted thread waits on once its script is done.
+// # Returns:
+// * a `JoinHandle` resolving to the outcome of the script.
+fn create_scripted_witness_thread(mut witness_communicator: WitnessCommunicator<String>, script: NodeScript, timeout: Duration, barrier: Arc<Barrier>) -> JoinHandle<ScriptOutcome> {
+    tokio::spawn(async move {
+        let reliable_handle = witness_communicator.initialize_reliable_handle();
+        let witness_handle = witness_communicator.initialize_witness_handle();
+        let outcome = script.run(&mut witness_communicator, timeout).await;
+        if outcome.is_crashed() {
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            println!("id: {}, crashed after {} actions", outcome.get_id(), outcome.get_performed());
+            barrier.wait().await;
+        } else {
+            barrier.wait().await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+        }
+        outcome
+    })
+}
+
+// # Function Description:
+// This function runs a scenario file on a witness cluster: every thread that is not crashed by the fault
+// script follows its script, and the threads without a script take part in the protocols only.
+// # Parameters:
+// * transmitters - the outgoing message channel of every thread.
+// * receivers - the incoming message channel of every thread.
+// * config - the cluster configuration, configured for the scenario (see `Scenario::configure`).
+// * scenario - the scenario.
+// * nodes - the per-node metrics of the run.
+// # Returns:
+// * the outcome of every scripted thread, by thread ID.
+async fn run_witness_scenario(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>,
+    config: ClusterConfig, scenario: &Scenario, nodes: &NodeMetrics) -> Vec<ScriptOutcome> {
+    let thread_count = config.get_thread_count();
+    let fault_script = config.get_fault_script().clone();
+    let scripted = (0..thread_count).filter(|id| fault_script.get_crash_point(*id).is_none()).count();
+    let barrier = Arc::new(Barrier::new(scripted));
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    let mut crashed = vec![];
+    for i in 0..thread_count {
+        let communicator = witness_hub.create_witness_communicator();
+        register_node(nodes, i, communicator.get_metrics());
+        match fault_script.get_crash_point(i) {
+            Some(crash_point) => crashed.push(create_crashed_witness_thread(i, communicator, crash_point)),
+            None => handles.push(create_scripted_witness_thread(communicator, scenario.get_script(i), scenario.get_timeout(), barrier.clone())),
+        }
+    }
+    let outcomes = join_all(handles).await.into_iter().map(|outcome| outcome.unwrap()).collect();
+    report_faulty_threads(crashed.len(), 0);
+    outcomes
+}
+
+// # Function Description:
+// This function prints the outcome of every scripted thread of a scenario.
+// # Parameters:
+// * outcomes - the outcome of every scripted thread.
+// # Returns:
+// * whether every script ran to completion or to its crash.
+fn report_script_outcomes(outcomes: &[ScriptOutcome]) -> bool {
+    let mut passed = true;
+    for outcome in outcomes {
+        match outcome.get_failure() {
+            Some(failure) => {
+                println!("id: {}, script failed after {} actions: {failure}", outcome.get_id(), outcome.get_performed());
+                passed = false;
+            },
+            None => println!("id: {}, script done ({} actions{})", outcome.get_id(), outcome.get_performed(), if outcome.is_crashed() { ", crashed" } else { "" }),
+        }
+    }
+    passed
+}
+
+// # Function Description:
+// This function checks the round deadlines of a scenario against the delivery times of the nodes of its
+// run, and prints the deadline-miss report.
+// # Parameters:
+// * config - the cluster configuration of the run.
+// * scenario - the scenario.
+// * nodes - the per-node metrics of the run.
+// # Returns:
+// * the deadline-miss report, empty if the scenario declares no deadline.
+fn report_deadlines(config: &ClusterConfig, scenario: &Scenario, nodes: &NodeMetrics) -> DeadlineReport {
+    if scenario.get_deadlines().is_empty() {
+        return DeadlineReport::default()
+    }
+    let reports: Vec<MetricsReport> = nodes.lock().unwrap().iter().map(|(id, metrics)| metrics.report(*id, config.clone())).collect();
+    let report = DeadlineReport::from_reports(scenario.get_deadlines(), &reports);
+    println!("{}", report.render());
+    report
+}
+
 // # Function Description
 // This function spawns an asynchronous task that simulates a node participating in an 
 // aggregated witness-based reliable broadcast network.
@@ -448,163 +709,170 @@ fn create_aggregated_witness_thread (id: u32, mut aggregated_witness_communicato
             if id == 0 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 0).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 0).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 0).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 3 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 0).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 4 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 0).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(0)).await; 
                 
             }
 
             if id == 5 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 0).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(0)).await; 
                 
             }
           
             println!("id: {id}, aggregated collecting...");
-            aggregated_witness_communicator.aggregated_witness_collect(0).await; 
+            aggregated_witness_communicator.aggregated_witness_collect(Round(0)).await; 
+
+            //test: the delivery of round 1 is justified by a verifiable aggregation proof
+            let proof = aggregated_witness_communicator.aggregated_witness_proof(Round(0)).await; 
+            println!("id: {id}, aggregation proof verification: {:?}", proof.verify());
+            if id == 0 {
+                let _ = std::fs::write("aggregation_proof.json", proof.write_json());
+            }
 
 
             println!("Testing... Round 2, aggregated witness communication"); 
             if id == 0 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 1).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 1).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 1).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 3 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 1).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 4 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 1).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(1)).await; 
                 
             }
 
             if id == 5 {
                 println!("id: {id}, aggregated witness broadcasting..."); 
                 let message = format!("aggregated witness broadcast message by {id}");
-                aggregated_witness_communicator.aggregated_witness_broadcast(message, 1).await; 
+                aggregated_witness_communicator.aggregated_witness_broadcast(message, Round(1)).await; 
                 
             }
           
             println!("id: {id}, aggregated collecting...");
-            aggregated_witness_communicator.aggregated_witness_collect(1).await; 
+            aggregated_witness_communicator.aggregated_witness_collect(Round(1)).await; 
 
             println!("Testing... Round 3, aggregated witness communication"); 
             if id == 0 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                aggregated_witness_communicator.witness_broadcast(message, 2).await; 
+                aggregated_witness_communicator.witness_broadcast(message, Round(2)).await; 
                 
             }
 
             if id == 1 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                aggregated_witness_communicator.witness_broadcast(message, 2).await; 
+                aggregated_witness_communicator.witness_broadcast(message, Round(2)).await; 
                 
             }
 
             if id == 2 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                aggregated_witness_communicator.witness_broadcast(message, 2).await; 
+                aggregated_witness_communicator.witness_broadcast(message, Round(2)).await; 
                 
             }
 
             if id == 3 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                aggregated_witness_communicator.witness_broadcast(message, 2).await; 
+                aggregated_witness_communicator.witness_broadcast(message, Round(2)).await; 
                 
             }
 
             if id == 4 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                aggregated_witness_communicator.witness_broadcast(message, 2).await; 
+                aggregated_witness_communicator.witness_broadcast(message, Round(2)).await; 
                 
             }
 
             if id == 5 {
                 println!("id: {id}, witness broadcasting..."); 
                 let message = format!("witness broadcast message by {id}");
-                aggregated_witness_communicator.witness_broadcast(message, 2).await; 
+                aggregated_witness_communicator.witness_broadcast(message, Round(2)).await; 
                 
             }
 
             println!("id: {id}, collecting...");
-            aggregated_witness_communicator.witness_collect(2).await; 
+            aggregated_witness_communicator.witness_collect(Round(2)).await; 
 
             //test reliable broadcast           
             if id == 0 {
                 println!("Testing... Round 4, aggregated reliable communication"); 
                 println!("id: {id}, reliable broadcasting..."); 
                 let message = format!("reliable broadcast message by {id}");
-                aggregated_witness_communicator.reliable_broadcast(message, 0, 3).await; 
+                aggregated_witness_communicator.reliable_broadcast(message, Instance(0), Round(3)).await; 
             }
 
             println!("id: {id}, reliable receiving...");
-            aggregated_witness_communicator.reliable_recv(Some(0), 0, 3).await; 
+            aggregated_witness_communicator.reliable_recv(Some(0), Instance(0), Round(3)).await; 
 
              //test send() & recv()
              if id == 2 {
                 println!("Testing... Round 5, aggregated basic communication"); 
                 println!("id: {id}, sending..."); 
                 let message = format!("message from {} to {}", id, 1);
-                aggregated_witness_communicator.basic_send(1, message, 3).await; 
+                aggregated_witness_communicator.basic_send(1, message, Round(3)).await; 
             }
 
             if id == 1 {
                 println!("id: {id}, receiving...");
-                aggregated_witness_communicator.basic_recv(Some(2), 3).await; 
+                aggregated_witness_communicator.basic_recv(Some(2), Round(3)).await; 
             }
 
             aggregated_witness_communicator.terminate_reliable_handle(reliable_handle);
@@ -617,83 +885,4083 @@ fn create_aggregated_witness_thread (id: u32, mut aggregated_witness_communicato
 }
 
 // # Function Description:
-// This asynchronous function sets up and spawns a collection of simulated threads
-// for testing different message-passing communication models: either a `BasicHub` or a `ReliableHub`.
+// This function creates a WAN-like network emulation with three regions (e.g. US, EU, and Asia), 
+// assigning threads to regions in a round-robin fashion. Links within a region are fast, while links 
+// between regions add tens of milliseconds of latency and are limited to 1 MB/s.
 // # Parameters:
-// * `transmitters` - a vector of `Sender<String>` objects, each representing the outgoing message channel for a thread.
-// * `receivers` - a vector of `Receiver<String>` objects, each representing the incoming message channel for a thread.
-// * `thread_count` - the number of threads to spawn (and thus the number of communicators to create).
-// * `communication_type` - a string reference that specifies the communication mode ("basic" or "reliable").
-async fn simulate_threads(transmitters: Vec<Sender<String>>, receivers: Vec<Receiver<String>>,
-    thread_count: u32, communication_type: &String) {
+// * thread_count - total number of threads in the simulation
+// # Returns
+// * the `NetworkEmulation` of the topology
+fn create_wan_emulation(thread_count: u32) -> NetworkEmulation {
+    let ms = Duration::from_millis;
+    let latencies = vec![
+        vec![ms(1), ms(40), ms(90)],
+        vec![ms(40), ms(1), ms(70)],
+        vec![ms(90), ms(70), ms(1)],
+    ];
+    let bandwidths = vec![
+        vec![None, Some(1_000_000), Some(1_000_000)],
+        vec![Some(1_000_000), None, Some(1_000_000)],
+        vec![Some(1_000_000), Some(1_000_000), None],
+    ];
+    let regions = RegionTopology::round_robin(thread_count, latencies).with_bandwidths(bandwidths);
+    NetworkEmulation::new().with_regions(regions)
+}
+
+// # Function Description:
+// This function waits briefly for the next event published by a communicator's background handles.
+// # Parameters:
+// * events - the event subscription of the communicator.
+// # Returns
+// * `Some(Event)` if an event was published before the timeout, otherwise `None`.
+async fn next_event(events: &mut broadcast::Receiver<Event<String>>) -> Option<Event<String>> {
+    match tokio::time::timeout(Duration::from_millis(500), events.recv()).await {
+        Ok(Ok(event)) => Some(event),
+        _ => None,
+    }
+}
+
+// # Function Description:
+// This function runs a reliable broadcast workload for the metrics comparison scenario: the broadcasting
+// nodes reliably broadcast a few instances each, and every node delivers them.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster, with the faults of the run.
+// * `broadcasters` - the number of nodes that broadcast, starting from node 0.
+// # Returns
+// * the `MetricsReport` of every node.
+async fn run_comparison_workload(config: ClusterConfig, broadcasters: u32) -> Vec<MetricsReport> {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);    
     let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle(); 
+            for instance_number in 0..5 {
+                if id < broadcasters {
+                    let message = format!("reliable broadcast message by {id}");
+                    reliable_communicator.reliable_broadcast(message, Instance(instance_number), Round(0)).await; 
+                }
+                for sender in 0..broadcasters {
+                    reliable_communicator.reliable_recv(Some(sender), Instance(instance_number), Round(0)).await; 
+                }
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            reliable_communicator.metrics_report()
+        }));
+    }
 
-    if communication_type == "basic" {
-        println!("Setting up basic communication..."); 
-        let mut basic_hub = BasicHub::new(transmitters, receivers, thread_count); 
-        for i in 0..thread_count {
-            let handle: JoinHandle<()> = create_basic_thread(i as u32, basic_hub.create_basic_communicator());
-            handles.push(handle);
-        }
+    let mut reports = vec![];
+    for handle in handles {
+        reports.push(handle.await.unwrap());
+    }
+    reports
+}
 
-        for handle in handles {
-            let _ = handle.await.unwrap();
-        }
+// # Function Description:
+// This function runs the metrics comparison scenario: a baseline reliable workload is compared with
+// itself, which must not flag any regression, then with a workload in which every node broadcasts and
+// the last node is slowed down, which must flag the frames, bytes, and latencies of "reliable" as
+// regressions. The baseline must also survive an export round trip.
+// # Parameters:
+// * `config` - the configuration of the baseline run; the candidate run also slows the last node down.
+// # Returns
+// * `true` if the comparisons flagged exactly the expected regressions.
+async fn simulate_metrics_comparison(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let baseline = run_comparison_workload(config.clone(), 1).await;
+    let slowed_config = config.with_fault(Fault::Slowdown(thread_count - 1, Duration::from_millis(10)));
+    let candidate = run_comparison_workload(slowed_config, thread_count).await;
+
+    let exported = read_metrics_export(&write_metrics_export(&baseline)).is_ok_and(|imported| imported == baseline);
+    let unchanged = MetricsComparison::new(&baseline, &baseline, 10.0).get_regressions().is_empty();
+    let comparison = MetricsComparison::new(&baseline, &candidate, 10.0);
+    let regressions: Vec<&String> = comparison.get_regressions().iter()
+        .filter(|delta| delta.get_protocol_information() == "reliable")
+        .map(|delta| delta.get_metric())
+        .collect();
+    for delta in comparison.get_deltas() {
+        println!("{} {}: {} -> {}{}", delta.get_protocol_information(), delta.get_metric(), delta.get_baseline(), delta.get_candidate(), if delta.get_regression() { " (regression)" } else { "" });
     }
-    else if communication_type == "reliable" {
-        println!("Setting up reliable communication...");      
-        let mut reliable_hub = ReliableHub::new(transmitters, receivers, thread_count);    
-        for i in 0..thread_count {
-            let handle: JoinHandle<()> = create_reliable_thread(i as u32, reliable_hub.create_reliable_communicator());
-            handles.push(handle);
-        }
+    let flagged = regressions.iter().any(|metric| *metric == "frames") && regressions.iter().any(|metric| *metric == "bytes")
+        && regressions.iter().any(|metric| metric.ends_with("(us)"));
 
-        for handle in handles {
-            let _ = handle.await.unwrap();
-        }
-    } else if communication_type == "witness" {
-        println!("Setting up witness communication...");      
-        let mut witness_hub = WitnessHub::new(transmitters, receivers, thread_count);    
-        for i in 0..thread_count {
-            let handle: JoinHandle<()> = create_witness_thread(i as u32, witness_hub.create_witness_communicator());
-            handles.push(handle);
-        }
+    println!("metrics comparison scenario: exported {exported}, unchanged run without regressions {unchanged}, regressions flagged {flagged}");
+    exported && unchanged && flagged
+}
 
-        for handle in handles {
-            let _ = handle.await.unwrap();
-        }
-    } else if communication_type == "aggregated_witness" {
-        println!("Setting up aggregated witness communication...");      
-        let mut aggregated_witness_hub = AggregatedWitnessHub::new(transmitters, receivers, thread_count);    
-        for i in 0..thread_count {
-            let handle: JoinHandle<()> = create_aggregated_witness_thread(i as u32, aggregated_witness_hub.create_aggregated_witness_communicator());
-            handles.push(handle);
+// # Function Description:
+// This function sums the frames received by a cluster for a protocol.
+// # Parameters:
+// * `reports` - the metrics reports of every node of the cluster.
+// * `protocol_information` - the protocol whose frames are counted.
+// # Returns
+// * the total number of frames received.
+fn count_frames(reports: &[MetricsReport], protocol_information: &str) -> u64 {
+    reports.iter()
+        .filter_map(|report| report.get_traffic().get(protocol_information))
+        .map(|traffic| traffic.get_frames())
+        .sum()
+}
+
+// # Function Description:
+// This function runs the tree dissemination scenario over the WAN emulation: every node reliably
+// broadcasts with direct dissemination, then along a topology-aware spanning tree. Both runs must deliver
+// every instance without any node receiving a signal twice (duplicate suppression), while the tree bounds
+// the fan-out of every node and crosses region boundaries less often. A witness round driven in
+// polling mode must also deliver along the tree.
+// # Parameters:
+// * `config` - gives the thread count, from which the regions and the broadcast tree are built.
+// # Returns
+// * `true` if both dissemination modes delivered without duplicate frames.
+async fn simulate_tree_dissemination(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let emulation = create_wan_emulation(thread_count);
+    let regions = emulation.get_regions().unwrap().clone();
+    let tree = BroadcastTree::new(thread_count, 2, Some(&regions));
+    let direct_config = config.clone().with_network_emulation(emulation);
+    let tree_config = direct_config.clone().with_dissemination(Dissemination::SpanningTree(2));
+
+    let direct = tokio::time::timeout(Duration::from_secs(20), run_comparison_workload(direct_config, thread_count)).await;
+    let spanning = tokio::time::timeout(Duration::from_secs(20), run_comparison_workload(tree_config, thread_count)).await;
+    let (direct, spanning) = match (direct, spanning) {
+        (Ok(direct), Ok(spanning)) => (direct, spanning),
+        _ => {
+            println!("tree dissemination scenario: a reliable workload did not deliver");
+            return false
+        },
+    };
+    let direct_frames = count_frames(&direct, "reliable");
+    let tree_frames = count_frames(&spanning, "reliable");
+    // 5 instances per broadcaster, each node receiving one Input and at most one Echo and one Vote per origin
+    let n = thread_count as u64;
+    let bound = 5 * n * (n + 2 * n * n);
+
+    let direct_crossings: u32 = (0..thread_count)
+        .map(|origin| (0..thread_count).filter(|id| regions.get_region(*id) != regions.get_region(origin)).count() as u32)
+        .sum();
+    let tree_crossings = tree.get_cross_region_edges(&regions) * thread_count;
+    let tree_fanout = (0..thread_count).map(|id| tree.get_neighbours(id).len()).max().unwrap_or(0);
+    println!("direct: {direct_frames} frames, fan-out {}, {direct_crossings} inter-region frames per phase", thread_count - 1);
+    println!("spanning tree (depth {}): {tree_frames} frames, fan-out {tree_fanout}, {tree_crossings} inter-region frames per phase", tree.get_depth());
+
+    let quorum = thread_count - config.get_thresholds().get_faulty_threads() as u32;
+    let polled_config = config.with_handle_mode(HandleMode::Polling).with_dissemination(Dissemination::SpanningTree(2));
+    let polled = match run_polled_witness_round(polled_config).await {
+        Some(collected) => collected.iter().all(|values| values.len() as u32 >= quorum),
+        None => false,
+    };
+
+    let suppressed = direct_frames <= bound && tree_frames <= bound;
+    println!("tree dissemination scenario: at most {bound} frames {suppressed}, polled witness round delivered {polled}");
+    suppressed && polled
+}
+
+// # Function Description:
+// This function runs the identity scenario: the identities of a cluster are generated from the seed,
+// saved to and reloaded from files, and must round-trip unchanged, agree on every node's public key,
+// and be generated identically from the same seed. A tampered file must be rejected on load.
+// # Parameters:
+// * `config` - gives the seed the identities are generated from.
+// # Returns
+// * `true` if every identity round-tripped consistently and the tampered file was rejected.
+fn simulate_identity(config: ClusterConfig) -> bool {
+    let identities = IdentityStore::generate_cluster(&config);
+    let reproducible = identities == IdentityStore::generate_cluster(&config);
+    let consistent = identities.iter().all(|identity| {
+        identities.iter().all(|peer| identity.get_peer_key(peer.get_id()) == Some(peer.get_key_pair().get_public_key()))
+    });
+
+    let directory = env::temp_dir();
+    let mut reloaded = true;
+    for identity in identities.iter() {
+        let path = directory.join(format!("identity-{}-{}.json", config.get_seed(), identity.get_id()));
+        let loaded = identity.save(&path).and_then(|_| IdentityStore::load(&path));
+        reloaded &= loaded.as_ref() == Ok(identity);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    let path = directory.join(format!("identity-{}-tampered.json", config.get_seed()));
+    let tampered = identities[0].clone().write_json().replacen("\"id\":0", "\"id\":1", 1);
+    let rejected = std::fs::write(&path, tampered).is_ok() && IdentityStore::load(&path).is_err();
+    let _ = std::fs::remove_file(&path);
+
+    println!("identity scenario: reproducible {reproducible}, consistent peer keys {consistent}, reloaded {reloaded}, tampered file rejected {rejected}");
+    reproducible && consistent && reloaded && rejected
+}
+
+// # Function Description:
+// This function runs the delivery watch scenario: node 0 reliably broadcasts one instance, and two tasks
+// of node 1 watch its delivery while node 1 also receives it with `reliable_recv`. Both watchers must
+// observe the delivery without stealing it from the queue, and a watcher created after the delivery must
+// see it at once. The same is checked on a `MockReliableCommunicator` with a scripted delivery.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster.
+// # Returns
+// * `true` if every watcher observed the delivered message and `reliable_recv` still returned it.
+async fn simulate_watch_delivery(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let expected = String::from("reliable broadcast message by 0");
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut watchers = vec![];
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        if id == 1 {
+            for _ in 0..2 {
+                let mut watcher = reliable_communicator.watch_delivery(Instance(0), Round(0));
+                watchers.push(tokio::spawn(async move {
+                    match watcher.wait_for(Option::is_some).await {
+                        Ok(delivered) => delivered.as_ref().map(|message| message.get_message().clone()),
+                        Err(_) => None,
+                    }
+                }));
+            }
         }
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+            }
+            let received = reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+            let late = reliable_communicator.watch_delivery(Instance(0), Round(0)).borrow().clone();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            (received.get_message().clone(), late.map(|message| message.get_message().clone()))
+        }));
+    }
 
-        for handle in handles {
-            let _ = handle.await.unwrap();
+    let mut watched = true;
+    for watcher in watchers {
+        let observed = tokio::time::timeout(Duration::from_secs(5), watcher).await;
+        watched &= matches!(observed, Ok(Ok(Some(message))) if message == expected);
+    }
+    let mut received = true;
+    for handle in handles {
+        let (message, late) = handle.await.unwrap();
+        received &= message == expected && late.as_ref() == Some(&expected);
+    }
+
+    let mut reliable_mock: MockReliableCommunicator<String> = MockReliableCommunicator::with_config(0, config);
+    let mock_watcher = reliable_mock.watch_delivery(Instance(0), Round(0));
+    reliable_mock.deliver_reliable(2, String::from("reliable broadcast message by 2"), Instance(0), Round(0));
+    let mock_watched = mock_watcher.borrow().as_ref().map(|message| message.get_id()) == Some(2);
+    let mock_received = reliable_mock.reliable_recv(Some(2), Instance(0), Round(0)).await.get_id() == 2;
+
+    println!("watch delivery scenario: watchers observed {watched}, received and late watchers {received}, mock {}", mock_watched && mock_received);
+    watched && received && mock_watched && mock_received
+}
+
+// # Function Description:
+// This function sends frames through a `LinkConditioner` and receives them on the other side.
+// # Parameters:
+// * `conditions` - the conditions of the link.
+// * `rng` - the source of the random decisions of the link.
+// * `frames` - the number of frames sent, numbered in sending order.
+// # Returns
+// * the number of every frame received, in receiving order, with the time it spent on the link.
+async fn condition_frames(conditions: LinkConditions, rng: rand::rngs::StdRng, frames: u32) -> Vec<(u32, Duration)> {
+    let (destination, mut received) = mpsc::channel(frames as usize + 1);
+    let link = LinkConditioner::new(conditions, rng).wrap(destination, frames as usize + 1);
+    let sent_at = Instant::now();
+    for frame in 0..frames {
+        let _ = link.send(Bytes::from(frame.to_string())).await;
+    }
+    drop(link);
+    let mut delivered = vec![];
+    while let Some(frame) = received.recv().await {
+        delivered.push((String::from_utf8_lossy(&frame).parse().unwrap(), sent_at.elapsed()));
+    }
+    delivered
+}
+
+// # Function Description:
+// This function runs the partial synchrony scenario. On a link whose GST is 300ms and whose delays are bounded
+// by 50ms after it, frames sent before GST under an adversary delaying them by 10s and losing half of them
+// must either be lost or be delivered by GST plus the bound, and frames sent after GST over a link slower
+// than the bound and losing half of its frames must all be delivered within the bound. A reliable broadcast
+// must then be delivered by every node of a partially synchronous cluster shortly after GST, as reported
+// relative to GST by the metrics of every node.
+// # Parameters:
+// * `config` - seeds the conditioned link, and is extended with the network emulation of the cluster run.
+// # Returns
+// * `true` if the link and the cluster respected the bounds of partial synchrony.
+async fn simulate_gst(config: ClusterConfig) -> bool {
+    let (gst, bound, slack) = (Duration::from_millis(300), Duration::from_millis(50), Duration::from_millis(40));
+    let stabilization = Stabilization::new(gst, bound).with_adversary(LinkConditions::new(Duration::from_secs(10), None).with_drop_rate(0.5));
+    let conditions = LinkConditions::new(Duration::from_millis(200), None).with_drop_rate(0.5);
+    let (destination, mut received) = mpsc::channel(128);
+    let link = LinkConditioner::new(conditions, config.create_rng(0, "gst link")).with_stabilization(stabilization).wrap(destination, 128);
+    let created_at = Instant::now();
+    let collector = tokio::spawn(async move {
+        let mut delivered = vec![];
+        while let Some(frame) = received.recv().await {
+            delivered.push((String::from_utf8_lossy(&frame).parse::<u32>().unwrap(), created_at.elapsed()));
         }
-    }  else {
-        println!("Setting up barycentric agreement communication...");      
-        let mut barycentric_agreement_hub = BarycentricHub::new(transmitters, receivers, thread_count);    
-        for i in 0..thread_count {
-            let handle: JoinHandle<()> = create_barycentric_agreement_thread(i as u32, barycentric_agreement_hub.create_barycentric_communicator());
-            handles.push(handle);
+        delivered
+    });
+    let frames = 40;
+    for frame in 0..frames {
+        let _ = link.send(Bytes::from(frame.to_string())).await;
+    }
+    tokio::time::sleep_until((created_at + gst + bound * 2).into()).await;
+    let sent_after_gst = created_at.elapsed();
+    for frame in frames..frames * 2 {
+        let _ = link.send(Bytes::from(frame.to_string())).await;
+    }
+    drop(link);
+    let delivered = collector.await.unwrap_or_default();
+    let (before, after): (Vec<_>, Vec<_>) = delivered.iter().partition(|(frame, _)| *frame < frames);
+    let link_passed = !before.is_empty() && before.len() < frames as usize
+        && before.iter().all(|(_, at)| *at >= gst && *at <= gst + bound + slack)
+        && after.len() == frames as usize && after.iter().all(|(_, at)| *at <= sent_after_gst + bound + slack);
+    println!("gst link: {} of {frames} frames sent before GST delivered by {:?}, {} of {frames} frames sent after GST delivered within {:?}",
+        before.len(), before.iter().map(|(_, at)| *at).max(), after.len(), after.iter().map(|(_, at)| at.saturating_sub(sent_after_gst)).max());
+
+    let emulation = NetworkEmulation::new().with_conditions(LinkConditions::new(Duration::from_millis(5), None))
+        .with_stabilization(Stabilization::new(gst, bound).with_adversary(LinkConditions::new(Duration::from_secs(10), None)));
+    let config = config.with_network_emulation(emulation);
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let config = config.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+            }
+            let delivered = tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0))).await.is_ok();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            (delivered, reliable_communicator.get_metrics().report(id, config))
+        }));
+    }
+    let reports: Vec<(bool, MetricsReport)> = join_all(handles).await.into_iter().map(|result| result.unwrap()).collect();
+    let after_gst: Vec<Option<Duration>> = reports.iter().map(|(_, report)| report.get_delivery_after_gst()).collect();
+    let cluster_passed = reports.iter().all(|(delivered, report)| *delivered && report.get_gst() == Some(gst))
+        && after_gst.iter().all(|after_gst| after_gst.is_some_and(|after_gst| after_gst > Duration::ZERO && after_gst <= bound * 3 + slack));
+    println!("gst cluster: every node delivered {}, deliveries after GST {after_gst:?} (at most {:?} expected)",
+        reports.iter().all(|(delivered, _)| *delivered), bound * 3 + slack);
+
+    let passed = link_passed && cluster_passed;
+    println!("gst scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the demo scenario, which paces the protocols and narrates them step by step: node 0
+// reliably broadcasts a message, then every node takes part in a witness round, while the narrations of
+// every node are printed as they are published. Unless the cluster already runs in demo mode (e.g. with
+// `--pace=500`), every lane is paced by 200ms. Every node must narrate both deliveries, and the reliable
+// delivery must take at least its three paced phases (Input, Echo, Vote).
+// # Parameters:
+// * `config` - the configuration of the witness cluster, paced at 200 ms per step unless it sets its own pacing.
+// # Returns
+// * `true` if every node narrated both deliveries, no sooner than the pacing allows.
+async fn simulate_demo(config: ClusterConfig) -> bool {
+    let demo_pacing = config.get_demo_pacing().unwrap_or(DemoPacing::new(Duration::from_millis(200)));
+    let config = config.with_demo_pacing(demo_pacing);
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let (narration_tx, mut narration_rx) = tokio::sync::mpsc::unbounded_channel::<Narration>();
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let mut narrations = witness_communicator.get_metrics().subscribe_narrations().expect("Error: the demo cluster must narrate its rounds");
+        let narration_tx = narration_tx.clone();
+        tokio::spawn(async move {
+            while let Ok(narration) = narrations.recv().await {
+                if narration_tx.send(narration).is_err() {
+                    return
+                }
+            }
+        });
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            if id == 0 {
+                witness_communicator.reliable_broadcast(String::from("hello from node 0"), Instance(0), Round(0)).await;
+            }
+            let reliable = tokio::time::timeout(Duration::from_secs(30), witness_communicator.reliable_recv(Some(0), Instance(0), Round(0))).await.is_ok();
+            witness_communicator.witness_broadcast(format!("value of node {id}"), Round(1)).await;
+            let witness = tokio::time::timeout(Duration::from_secs(30), witness_communicator.witness_collect(Round(1))).await.is_ok();
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            reliable && witness
+        }));
+    }
+    drop(narration_tx);
+    let printer = tokio::spawn(async move {
+        let mut narrated = vec![];
+        while let Some(narration) = narration_rx.recv().await {
+            println!("{narration}");
+            narrated.push(narration);
         }
+        narrated
+    });
+    let completed: Vec<bool> = join_all(handles).await.into_iter().map(|result| result.unwrap_or(false)).collect();
+    drop(witness_hub);
+    let narrated = tokio::time::timeout(Duration::from_secs(5), printer).await.ok().and_then(Result::ok).unwrap_or_default();
 
-        for handle in handles {
-            let _ = handle.await.unwrap();
+    let minimum = demo_pacing.get_lane_delay(Lane::Signal) * 3;
+    let delivered = |id: u32, protocol_information: &str| narrated.iter()
+        .find(|narration| narration.get_id() == id && narration.get_protocol_information() == protocol_information && narration.get_text() == "was delivered")
+        .map(|narration| narration.get_elapsed());
+    let reliable: Vec<Option<Duration>> = (0..thread_count).map(|id| delivered(id, "reliable")).collect();
+    let witness: Vec<Option<Duration>> = (0..thread_count).map(|id| delivered(id, "witness")).collect();
+    let passed = completed.iter().all(|completed| *completed)
+        && reliable.iter().all(|elapsed| elapsed.is_some_and(|elapsed| elapsed >= minimum))
+        && witness.iter().all(Option::is_some);
+    println!("demo scenario: {} narrations, reliable deliveries at {reliable:?} (at least {minimum:?} expected), witness deliveries at {witness:?}, {}",
+        narrated.len(), if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the link conditions scenario. A `LinkConditioner` must lose about the configured
+// fraction of the frames, keep the frames in order under jitter alone, let frames overtake each other when
+// they are reordered, and delay every frame by its latency plus at most its jitter. A witness round and a
+// barycentric round must then complete on a cluster whose links all add jitter and reorder frames.
+// # Parameters:
+// * `config` - seeds the conditioned links, and is extended with the lossy links of the witness cluster run.
+// # Returns
+// * `true` if every link and the witness round behaved as expected.
+async fn simulate_link_conditions(config: ClusterConfig) -> bool {
+    let ms = Duration::from_millis;
+    let mut passed = true;
+
+    let delivered = condition_frames(LinkConditions::new(Duration::ZERO, None).with_drop_rate(0.5), config.create_rng(0, "link drops"), 1000).await;
+    let dropped = (400..=600).contains(&delivered.len());
+    println!("link conditions scenario, drop rate 0.5: {} of 1000 frames delivered, {}", delivered.len(), if dropped { "passed" } else { "failed" });
+    passed &= dropped;
+
+    let delivered = condition_frames(LinkConditions::new(ms(30), None).with_jitter(ms(10)), config.create_rng(0, "link jitter"), 200).await;
+    let ordered = delivered.len() == 200 && delivered.windows(2).all(|frames| frames[0].0 < frames[1].0);
+    let bounded = delivered.iter().all(|(_, delay)| *delay >= ms(30) && *delay <= ms(40) + ms(25));
+    println!("link conditions scenario, jitter: ordered {ordered}, delays within latency and jitter {bounded}");
+    passed &= ordered && bounded;
+
+    let delivered = condition_frames(LinkConditions::new(ms(5), None).with_jitter(ms(20)).with_reordering(1.0), config.create_rng(0, "link reordering"), 200).await;
+    let mut numbers: Vec<u32> = delivered.iter().map(|(frame, _)| *frame).collect();
+    let reordered = numbers.windows(2).any(|frames| frames[0] > frames[1]);
+    numbers.sort();
+    let complete = numbers == (0..200).collect::<Vec<u32>>();
+    println!("link conditions scenario, reordering: reordered {reordered}, every frame delivered {complete}");
+    passed &= reordered && complete;
+
+    let conditions = LinkConditions::new(ms(2), None).with_jitter(ms(20)).with_reordering(0.5);
+    let config = config.with_network_emulation(NetworkEmulation::new().with_conditions(conditions));
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let barrier = Arc::new(Barrier::new(config.get_thread_count() as usize));
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let barrier = barrier.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            let collected = witness_communicator.witness_collect_with_timeout(Round(0), Duration::from_secs(10)).await.is_ok();
+            barrier.wait().await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            collected
+        }));
+    }
+    let collected = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("link conditions scenario, witness round over jittered and reordering links: {}", if collected { "passed" } else { "failed" });
+    passed &= collected;
+
+    let (transmitters, receivers) = create_channels(&config);
+    let mut barycentric_hub = BarycentricHub::with_config(transmitters, receivers, config.clone());
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut barycentric_communicator: BarycentricCommunicator<String> = barycentric_hub.create_barycentric_communicator();
+        let barrier = barrier.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = barycentric_communicator.initialize_reliable_handle();
+            let barycentric_handle = barycentric_communicator.initialize_barycentric_handle();
+            barycentric_communicator.barycentric_agreement(format!("barycentric agreement broadcast message by {id}"), Round(0)).await;
+            let collected = barycentric_communicator.barycentric_collect_with_timeout(Round(0), Duration::from_secs(10)).await.is_ok();
+            barrier.wait().await;
+            barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+            barycentric_communicator.terminate_reliable_handle(reliable_handle);
+            collected
+        }));
+    }
+    let agreed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("link conditions scenario, barycentric round over jittered and reordering links: {}", if agreed { "passed" } else { "failed" });
+    passed && agreed
+}
+
+// # Function Description:
+// This function runs the repeated collect scenario: under each `RepeatedCollectPolicy`, every node
+// collects the same witness round twice. The second call must return the first collection under
+// `Cached`, and fail with `CollectError::AlreadyConsumed` under `AlreadyConsumed`, instead of blocking.
+// # Parameters:
+// * `config` - the configuration of the witness clusters, run once per repeated collect policy.
+// # Returns
+// * `true` if every node observed the outcome expected under both policies.
+async fn simulate_repeated_collect(config: ClusterConfig) -> bool {
+    let mut passed = true;
+    for policy in [RepeatedCollectPolicy::Cached, RepeatedCollectPolicy::AlreadyConsumed] {
+        let config = config.clone().with_repeated_collect_policy(policy);
+        let (transmitters, receivers) = create_channels(&config);
+        let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+        let mut handles = vec![];
+        for id in 0..config.get_thread_count() {
+            let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = witness_communicator.initialize_reliable_handle();
+                let witness_handle = witness_communicator.initialize_witness_handle();
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+                let first = witness_communicator.try_witness_collect(Round(0)).await;
+                let second = tokio::time::timeout(Duration::from_secs(1), witness_communicator.try_witness_collect(Round(0))).await;
+                witness_communicator.terminate_witness_handle(witness_handle);
+                witness_communicator.terminate_reliable_handle(reliable_handle);
+                match (policy, first, second) {
+                    (RepeatedCollectPolicy::Cached, Ok(first), Ok(Ok(second))) => first == second,
+                    (RepeatedCollectPolicy::AlreadyConsumed, Ok(_), Ok(Err(CollectError::AlreadyConsumed(protocol_information, Round(0))))) => protocol_information == "witness",
+                    _ => false,
+                }
+            }));
         }
-    } 
+        let observed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+        println!("repeated collect scenario: {policy:?} observed {observed}");
+        passed &= observed;
+    }
+    passed
 }
 
-#[tokio::main] 
-async fn main() {
-    //takes in the number of threads to simulate from the command-line argument
-    let args: Vec<String> = env::args().collect();
-    let thread_count:u32 = args[1].parse().unwrap(); 
-    let communication_type: String = args[2].parse().unwrap(); 
-    
-    let (transmitters, receivers) = create_channels(thread_count);
-    simulate_threads(transmitters, receivers, thread_count, &communication_type).await;
+// # Function Description:
+// This function runs the abandoned collect scenario: before any value is broadcast, every node abandons
+// the collection of round 0 to a timeout and of round 1 to a `select!`. Both abandonments must be
+// published as `Event::AbandonedCollect`, and once the rounds are delivered, collecting them must return
+// their values as a first collection under `RepeatedCollectPolicy::AlreadyConsumed`.
+// # Parameters:
+// * `config` - the configuration of the witness cluster, whose collects are set to fail once consumed.
+// # Returns
+// * `true` if every node observed the expected events and collections.
+async fn simulate_abandoned_collect(config: ClusterConfig) -> bool {
+    let config = config.with_repeated_collect_policy(RepeatedCollectPolicy::AlreadyConsumed);
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let barrier = Arc::new(Barrier::new(thread_count as usize));
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let barrier = barrier.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            let mut events = witness_communicator.subscribe_events();
+            let timed_out = matches!(witness_communicator.witness_collect_with_timeout(Round(0), Duration::from_millis(50)).await, Err(CollectError::Timeout(_, Round(0))));
+            let lost = tokio::select! {
+                _ = witness_communicator.witness_collect(Round(1)) => false,
+                _ = tokio::time::sleep(Duration::from_millis(50)) => true,
+            };
+            barrier.wait().await;
+
+            for round in 0..2 {
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(round)).await;
+            }
+            //round 1 is collected first, so that the collection of round 0 is received while it is abandoned
+            let second = witness_communicator.try_witness_collect(Round(1)).await;
+            let first = witness_communicator.try_witness_collect(Round(0)).await;
+            let repeated = witness_communicator.try_witness_collect(Round(1)).await;
+            barrier.wait().await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+
+            let mut abandoned = vec![];
+            while let Ok(event) = events.try_recv() {
+                if let Event::AbandonedCollect(abandonment) = event {
+                    abandoned.push((abandonment.get_id(), abandonment.get_protocol_information().clone(), abandonment.get_round_number()));
+                }
+            }
+            let expected = vec![(id, String::from("witness"), Round(0)), (id, String::from("witness"), Round(1))];
+            let collected = first.is_ok_and(|values| !values.is_empty()) && second.is_ok_and(|values| !values.is_empty())
+                && matches!(repeated, Err(CollectError::AlreadyConsumed(_, Round(1))));
+            if !(timed_out && lost && collected && abandoned == expected) {
+                println!("node {id}: timed out {timed_out}, lost select {lost}, collected {collected}, abandoned {abandoned:?}");
+                return false
+            }
+            true
+        }));
+    }
+    let observed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("abandoned collect scenario: {}", if observed { "passed" } else { "failed" });
+    observed
+}
+
+// # Function Description:
+// This function runs the workload of a node connected through a transport: every node reliably broadcasts
+// one instance in round 0 and receives every instance, then takes part in witness round 1.
+// # Parameters:
+// * `id` - the ID of the node.
+// * `witness_communicator` - the communicator of the node.
+// # Returns
+// * `true` if the node received every instance and delivered the witness round.
+async fn run_transport_workload(id: u32, mut witness_communicator: WitnessCommunicator<String>) -> bool {
+    let thread_count = witness_communicator.get_config().get_thread_count();
+    let quorum = thread_count - witness_communicator.get_config().get_thresholds().get_faulty_threads() as u32;
+    let reliable_handle = witness_communicator.initialize_reliable_handle();
+    let witness_handle = witness_communicator.initialize_witness_handle();
+    witness_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(id), Round(0)).await;
+    let mut received = 0;
+    for sender in 0..thread_count {
+        let message = witness_communicator.reliable_recv(Some(sender), Instance(sender), Round(0)).await;
+        received += (message.get_message() == &format!("reliable broadcast message by {sender}")) as u32;
+    }
+    witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(1)).await;
+    let values = witness_communicator.witness_collect(Round(1)).await;
+    witness_communicator.terminate_witness_handle(witness_handle);
+    witness_communicator.terminate_reliable_handle(reliable_handle);
+    println!("id: {id}, received {received} reliable broadcasts, witness round delivered {} values", values.len());
+    received == thread_count && values.len() as u32 >= quorum
+}
+
+// # Function Description:
+// This function opens a transport and takes the witness communicators of the threads it hosts.
+// # Parameters:
+// * `transport` - the transport connecting this process to the cluster.
+// * `config` - the cluster configuration.
+// # Returns
+// * the communicators of the local threads, or a description of why the transport could not be opened.
+async fn open_witness_communicators(transport: &mut dyn Transport, config: &ClusterConfig) -> Result<Vec<WitnessCommunicator<String>>, String> {
+    let local_ids = transport.get_local_ids(config);
+    let channels = transport.open(config).await?;
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    Ok((0..config.get_thread_count())
+        .map(|_| witness_hub.create_witness_communicator())
+        .filter(|communicator| local_ids.contains(communicator.get_id()))
+        .collect())
+}
+
+// # Function Description:
+// This function runs one node of a cluster whose nodes run in separate processes, connected over TCP
+// (e.g. `cargo run -- 4 tcp_node 0 2 127.0.0.1:7000,127.0.0.1:7001,127.0.0.1:7002,127.0.0.1:7003` for node 2).
+// Instead of the addresses, the node can be given the membership certificate of the cluster (e.g. written
+// with `create_membership`): the addresses are then read from the certificate, signals are signed with the
+// identity of the node, and frames of non-members are rejected. The identity is read from
+// `--identity=<file>` (by default `node-<id>.identity.json`), and created there the first time the node
+// starts. The node waits for every peer to acknowledge a connection before it runs the workload (see
+// `TcpTransport::with_startup_barrier`), and fails with the missing peers after `--startup-timeout=<seconds>`
+// (30 by default). The node stays up briefly after completing the workload, so that its last frames reach its peers.
+// With `--health=<address>`, the node serves its health on `GET /health` at the given address while it runs.
+// # Parameters:
+// * `config` - the cluster configuration.
+// * `id` - the ID of the node hosted by this process.
+// * `addresses` - the comma-separated address of every node, ordered by ID, or the path of a membership certificate.
+// * `health` - the address of the health endpoint of the node, if any.
+// * `identity_file` - the path of the identity file of the node.
+// * `startup_timeout` - how long the node waits for its peers at startup.
+// # Returns
+// * `true` if the node completed the workload.
+async fn run_tcp_node(config: ClusterConfig, id: u32, addresses: &str, health: Option<SocketAddr>, identity_file: &Path, startup_timeout: Duration) -> bool {
+    let (config, addresses) = if Path::new(addresses).is_file() {
+        match load_membership(config, id, Path::new(addresses), identity_file) {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                println!("tcp node {id}: {error}");
+                return false
+            },
+        }
+    } else {
+        match addresses.split(',').map(str::parse).collect() {
+            Ok(addresses) => (config, addresses),
+            Err(error) => {
+                println!("tcp node {id}: invalid address list: {error}");
+                return false
+            },
+        }
+    };
+    let Some(address) = addresses.get(id as usize).copied() else {
+        println!("tcp node {id}: no address given for this node");
+        return false
+    };
+    let mut transport = match TcpTransport::bind(id, address).await {
+        Ok(transport) => transport.with_peer_addresses(addresses).with_startup_barrier(startup_timeout),
+        Err(error) => {
+            println!("tcp node {id}: {error}");
+            return false
+        },
+    };
+    let mut endpoint = None;
+    let completed = match open_witness_communicators(&mut transport, &config).await {
+        Ok(mut communicators) => match communicators.pop() {
+            Some(communicator) => {
+                if let Some(health) = health {
+                    match serve_node_health(health, id, communicator.get_metrics().clone()).await {
+                        Ok(served) => endpoint = Some(served),
+                        Err(error) => println!("tcp node {id}: {error}"),
+                    }
+                }
+                tokio::time::timeout(Duration::from_secs(30), run_transport_workload(id, communicator)).await.unwrap_or(false)
+            },
+            None => false,
+        },
+        Err(error) => {
+            println!("tcp node {id}: {error}");
+            false
+        },
+    };
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    if let Some(endpoint) = endpoint {
+        endpoint.abort();
+    }
+    println!("tcp node {id}: {}", if completed { "passed" } else { "failed" });
+    completed
+}
+
+// # Function Description:
+// This function serves the health of a node over HTTP (see `serve_health`), reporting it unhealthy once it
+// has not processed a frame for 10 seconds.
+// # Parameters:
+// * `address` - the address of the health endpoint.
+// * `id` - the ID of the node.
+// * `metrics` - the metrics of the node, holding its `HealthProbe`.
+// # Returns
+// * the task serving the endpoint, or a description of why the address could not be listened on.
+async fn serve_node_health(address: SocketAddr, id: u32, metrics: Metrics) -> Result<JoinHandle<()>, String> {
+    let listener = TcpListener::bind(address).await.map_err(|error| format!("could not serve health on {address}: {error}"))?;
+    println!("tcp node {id}: serving health on http://{address}/health");
+    Ok(tokio::spawn(serve_health(listener, Duration::from_secs(10), move || ClusterHealth::new(vec![metrics.health(id)]))))
+}
+
+// # Function Description:
+// This function loads the membership certificate of a node at startup, and the identity of the node (created
+// and persisted the first time it starts), whose public key must be the one the certificate admits. The node
+// then signs its signals with the key of that identity.
+// # Parameters:
+// * `config` - the cluster configuration.
+// * `id` - the ID of the node.
+// * `path` - the path of the membership certificate.
+// * `identity_file` - the path of the identity file of the node.
+// # Returns
+// * the configuration of the signed cluster and the address of every node, or a description of why the
+//   certificate cannot be used.
+fn load_membership(config: ClusterConfig, id: u32, path: &Path, identity_file: &Path) -> Result<(ClusterConfig, Vec<SocketAddr>), String> {
+    let membership = MembershipCertificate::load(path)?;
+    if membership.get_member_count() != config.get_thread_count() {
+        return Err(format!("the membership has {} members for {} threads", membership.get_member_count(), config.get_thread_count()))
+    }
+    let identity = IdentityStore::load_or_create(identity_file, id)?;
+    if membership.get_member(id).is_none_or(|member| member.get_public_key() != identity.get_key_pair().get_public_key()) {
+        return Err(format!("the membership does not admit the identity of node {id} in {}", identity_file.display()))
+    }
+    let addresses = membership.get_addresses();
+    let config = config.with_authentication(Authentication::Ed25519).with_identity_file(identity_file.to_path_buf());
+    Ok((config.with_membership(membership), addresses))
+}
+
+// # Function Description:
+// This function drives every communicator of a cluster operated in `HandleMode::Polling`, in ID order,
+// until none of them has anything left to process.
+// # Parameters:
+// * `communicators` - the communicators of the cluster.
+// # Returns
+// * the number of frames and commands processed.
+async fn drive_cluster<C: BasicCommunication<String>>(communicators: &mut [C]) -> usize {
+    let mut processed = 0;
+    loop {
+        let mut pending = 0;
+        for communicator in communicators.iter_mut() {
+            pending += drive(communicator).await;
+        }
+        if pending == 0 {
+            return processed
+        }
+        processed += pending;
+    }
+}
+
+// # Function Description:
+// This function runs a witness round on a cluster operated in `HandleMode::Polling`, within the current
+// task: every node broadcasts a value, the cluster is driven until it is quiescent, and every node collects
+// the round.
+// # Parameters:
+// * `config` - the cluster configuration, in polling mode.
+// # Returns
+// * the values collected by every node, or `None` if a node did not deliver the round.
+async fn run_polled_witness_round(config: ClusterConfig) -> Option<Vec<Vec<Message<String>>>> {
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let mut communicators: Vec<WitnessCommunicator<String>> = (0..config.get_thread_count()).map(|_| witness_hub.create_witness_communicator()).collect();
+    let mut handles = vec![];
+    for communicator in communicators.iter_mut() {
+        handles.push((communicator.initialize_reliable_handle(), communicator.initialize_witness_handle()));
+    }
+
+    for communicator in communicators.iter_mut() {
+        let value = format!("witness broadcast message by {}", communicator.get_id());
+        communicator.witness_broadcast(value, Round(0)).await;
+    }
+    drive_cluster(&mut communicators).await;
+
+    let mut collected = vec![];
+    for communicator in communicators.iter_mut() {
+        match tokio::time::timeout(Duration::from_millis(100), communicator.witness_collect(Round(0))).await {
+            Ok(values) => collected.push(values),
+            Err(_) => return None,
+        }
+    }
+    for (communicator, (reliable_handle, witness_handle)) in communicators.iter().zip(handles) {
+        communicator.terminate_witness_handle(witness_handle);
+        communicator.terminate_reliable_handle(reliable_handle);
+    }
+    Some(collected)
+}
+
+// # Function Description:
+// This function runs the polling scenario: a reliable broadcast and two identical witness rounds are run
+// on clusters operated in `HandleMode::Polling`, driven manually from the current task. Every node must
+// deliver once the cluster is quiescent, and both witness rounds must collect the same values.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster, whose handles are switched to polling.
+// # Returns
+// * `true` if every scenario passed.
+async fn simulate_polling(config: ClusterConfig) -> bool {
+    let config = config.with_handle_mode(HandleMode::Polling);
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut communicators: Vec<ReliableCommunicator<String>> = (0..config.get_thread_count()).map(|_| reliable_hub.create_reliable_communicator()).collect();
+    let handles: Vec<JoinHandle<()>> = communicators.iter_mut().map(|communicator| communicator.initialize_reliable_handle()).collect();
+
+    communicators[0].reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+    let processed = drive_cluster(&mut communicators).await;
+    let mut delivered = 0;
+    for communicator in communicators.iter_mut() {
+        if tokio::time::timeout(Duration::from_millis(100), communicator.reliable_recv(Some(0), Instance(0), Round(0))).await.is_ok() {
+            delivered += 1;
+        }
+    }
+    for (communicator, handle) in communicators.iter().zip(handles) {
+        communicator.terminate_reliable_handle(handle);
+    }
+    let reliable_passed = delivered == config.get_thread_count();
+    println!("polling scenario, reliable broadcast: {} frames processed, {} nodes delivered, {}", processed, delivered, if reliable_passed { "passed" } else { "failed" });
+
+    let first_round = run_polled_witness_round(config.clone()).await;
+    let second_round = run_polled_witness_round(config).await;
+    let witness_passed = first_round.is_some() && first_round == second_round;
+    println!("polling scenario, witness rounds: {}", if witness_passed { "passed" } else { "failed" });
+
+    reliable_passed && witness_passed
+}
+
+type CommitRevealOutcome = (Option<Vec<Message<String>>>, Option<Event<String>>);
+
+// # Function Description:
+// This function spawns a node for the commit-reveal scenario: every node commits to a value in round 0
+// and reveals it in round 1. If `mismatch` is set, node 0 reveals a different value than it committed to.
+// # Parameters:
+// * id - the ID of the node.
+// * witness_communicator - the node's `WitnessCommunicator`.
+// * mismatch - whether node 0 reveals a value that does not match its commitment.
+// # Returns
+// * `JoinHandle<CommitRevealOutcome>` - resolving to the values collected in round 1 (if the round was delivered), and the first event published on the node, if any.
+fn create_commit_reveal_thread(id: u32, mut witness_communicator: WitnessCommunicator<String>, mismatch: bool) -> JoinHandle<CommitRevealOutcome> {
+    tokio::spawn(async move {
+        let mut events = witness_communicator.subscribe_events();
+        let reliable_handle = witness_communicator.initialize_reliable_handle(); 
+        let witness_handle = witness_communicator.initialize_witness_handle(); 
+
+        let value = format!("committed value by {id}");
+        witness_communicator.witness_commit(value.clone(), Round(0)).await; 
+        if mismatch && id == 0 {
+            witness_communicator.witness_reveal(String::from("revealed value by 0"), Round(0)).await; 
+        } else {
+            witness_communicator.witness_reveal(value, Round(0)).await; 
+        }
+        let collected = tokio::time::timeout(Duration::from_millis(500), witness_communicator.witness_collect(Round(1))).await.ok();
+
+        let event = next_event(&mut events).await;
+        witness_communicator.terminate_reliable_handle(reliable_handle);
+        witness_communicator.terminate_witness_handle(witness_handle);
+        (collected, event)
+    })
+}
+
+// # Function Description:
+// This function runs the commit-reveal scenarios. Without a mismatch, every node must collect the committed
+// values in round 1 and observe no event. When node 0 reveals a value it did not commit to, every node must
+// publish `Event::RevealMismatch` and never collect the revealed value.
+// # Parameters:
+// * `config` - the configuration of the witness clusters, giving the validity threshold the reveals must reach.
+// # Returns
+// * `true` if every scenario passed.
+async fn simulate_commit_reveal(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let validity_threshold = config.get_thresholds().get_validity_threshold() as usize;
+    let mut passed = true;
+
+    for mismatch in [false, true] {
+        let (transmitters, receivers) = create_channels(&config);
+        let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+        let mut handles = vec![];
+        for i in 0..thread_count {
+            handles.push(create_commit_reveal_thread(i, witness_hub.create_witness_communicator(), mismatch));
+        }
+
+        let mut scenario_passed = true;
+        for handle in handles {
+            let (collected, event) = match handle.await {
+                Ok(outcome) => outcome,
+                Err(_) => { scenario_passed = false; continue },
+            };
+            scenario_passed &= if mismatch {
+                let rejected = matches!(&event, Some(Event::RevealMismatch(_, reveal, _)) if reveal.get_id() == 0);
+                let collected_reveal = collected.unwrap_or_default().iter().any(|value| value.get_message() == "revealed value by 0");
+                rejected && !collected_reveal
+            } else {
+                let committed = collected.is_some_and(|values| values.len() >= validity_threshold 
+                    && values.iter().all(|value| value.get_message() == &format!("committed value by {}", value.get_id())));
+                committed && event.is_none()
+            };
+        }
+
+        println!("commit-reveal scenario, {}: {}", if mismatch { "mismatching reveal by 0" } else { "matching reveals" }, if scenario_passed { "passed" } else { "failed" });
+        passed &= scenario_passed;
+    }
+    passed
+}
+
+// # Function Description:
+// This function spawns a node for the decode failure scenario: the node runs witness rounds 0 and 1, and
+// checks the events it observed and the failures counted in its metrics. Node 0 received an undecodable
+// signal and an undecodable report of round 0 before the rounds started; under `DecodePolicy::Strict`,
+// its round 0 must be aborted with the failure, and every other round must be collected.
+// # Parameters:
+// * id - the ID of the node.
+// * witness_communicator - the node's `WitnessCommunicator`.
+// * strict - whether the cluster runs under `DecodePolicy::Strict`.
+// # Returns
+// * `JoinHandle<bool>` - resolving to whether the node observed the expected outcome.
+fn create_decode_failure_thread(id: u32, mut witness_communicator: WitnessCommunicator<String>, strict: bool) -> JoinHandle<bool> {
+    tokio::spawn(async move {
+        let mut events = witness_communicator.subscribe_events();
+        let reliable_handle = witness_communicator.initialize_reliable_handle(); 
+        let witness_handle = witness_communicator.initialize_witness_handle(); 
+
+        let mut rounds = vec![];
+        for round_number in 0..2 {
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(round_number)).await; 
+            rounds.push(tokio::time::timeout(Duration::from_secs(5), witness_communicator.try_witness_collect(Round(round_number))).await);
+        }
+        let mut failures = vec![];
+        while let Some(event) = next_event(&mut events).await {
+            if let Event::DecodeFailure(failure) = event {
+                failures.push(failure);
+            }
+        }
+        let counted = witness_communicator.metrics_report().get_decode_failures().clone();
+        witness_communicator.terminate_witness_handle(witness_handle);
+        witness_communicator.terminate_reliable_handle(reliable_handle);
+
+        let round_0 = match &rounds[0] {
+            Ok(Ok(_)) => id != 0 || !strict,
+            Ok(Err(CollectError::Aborted(failure))) => id == 0 && strict && failure.get_round_number() == Some(Round(0)),
+            Ok(Err(_)) => false,
+            Err(_) => false,
+        };
+        let round_1 = matches!(&rounds[1], Ok(Ok(_)));
+        let observed = if id == 0 {
+            failures.len() == 2
+                && failures.iter().any(|failure| failure.get_lane() == Lane::Report && failure.get_source() == Some(1) && failure.get_frame_size() == UNDECODABLE_REPORT.len())
+                && counted.get("Report") == Some(&1) && counted.get("Signal") == Some(&1)
+        } else {
+            failures.is_empty() && counted.is_empty()
+        };
+        round_0 && round_1 && observed
+    })
+}
+
+// An undecodable report of round 0 by node 1, and an undecodable signal of round 0.
+const UNDECODABLE_REPORT: &str = r#"{"report_type":"Witness","id":1,"messages":"undecodable","round_number":0}"#;
+const UNDECODABLE_SIGNAL: &str = r#"{"signal":"Input","content":{"Unknown":{"id":1}},"instance_number":0,"round_number":0}"#;
+
+// # Function Description:
+// This function runs the decode failure scenarios, under `DecodePolicy::Discard` and `DecodePolicy::Strict`:
+// an undecodable signal and an undecodable report are sent to node 0 before the witness rounds start.
+// # Parameters:
+// * `config` - the configuration of the witness clusters, run once per decode policy.
+// # Returns
+// * `true` if every scenario passed.
+async fn simulate_decode_failure(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let mut passed = true;
+
+    for decode_policy in [DecodePolicy::Discard, DecodePolicy::Strict] {
+        let config = config.clone().with_decode_policy(decode_policy);
+        let (transmitters, receivers) = create_channels(&config);
+        let _ = transmitters[0].send(tag_frame(Lane::Signal, Bytes::from(UNDECODABLE_SIGNAL))).await;
+        let _ = transmitters[0].send(tag_frame(Lane::Report, Bytes::from(UNDECODABLE_REPORT))).await;
+        let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+        let mut handles = vec![];
+        for i in 0..thread_count {
+            handles.push(create_decode_failure_thread(i, witness_hub.create_witness_communicator(), decode_policy == DecodePolicy::Strict));
+        }
+
+        let mut scenario_passed = true;
+        for handle in handles {
+            scenario_passed &= handle.await.unwrap_or(false);
+        }
+        println!("decode failure scenario, {:?}: {}", decode_policy, if scenario_passed { "passed" } else { "failed" });
+        passed &= scenario_passed;
+    }
+    passed
+}
+
+// # Function Description:
+// This function spawns a node for the inspection scenario: the node runs a witness round and a barycentric
+// agreement round, then queries its handles for snapshots of the rounds and of its reliable broadcast
+// instances, and checks them against what it collected.
+// # Parameters:
+// * id - the ID of the node.
+// * witness_communicator - the node's `WitnessCommunicator`.
+// * barycentric_communicator - the node's `BarycentricCommunicator`.
+// * validity_threshold - the number of values needed to deliver a round.
+// # Returns
+// * `JoinHandle<bool>` - resolving to whether every snapshot matched.
+fn create_inspect_thread(id: u32, mut witness_communicator: WitnessCommunicator<String>, mut barycentric_communicator: BarycentricCommunicator<String>, validity_threshold: u64) -> JoinHandle<bool> {
+    tokio::spawn(async move {
+        let reliable_handle = witness_communicator.initialize_reliable_handle(); 
+        let witness_handle = witness_communicator.initialize_witness_handle(); 
+        witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await; 
+        let collected = witness_communicator.witness_collect(Round(0)).await; 
+
+        let round = witness_communicator.witness_inspect(Round(0)).await.ok().flatten();
+        let unknown_round = witness_communicator.witness_inspect(Round(1)).await.ok().flatten();
+        let instances = witness_communicator.reliable_inspect().await.unwrap_or_default();
+        let witness_passed = match round {
+            Some(round) => {
+                let exported = round.write_json();
+                round.get_delivered() && round.get_reported() && round.get_witness_count() >= validity_threshold
+                    && round.get_value_digests().len() == round.get_value_count() as usize
+                    && collected.iter().all(|value| round.get_value_digests().contains(&value.get_digest()))
+                    && WitnessRoundSnapshot::read_json(&exported).is_ok_and(|imported| imported == round)
+            },
+            None => false,
+        };
+        let delivered_instances = instances.values().filter(|instance| instance.get_delivered()).count() as u64;
+        let reliable_passed = unknown_round.is_none() && delivered_instances >= validity_threshold;
+        witness_communicator.terminate_witness_handle(witness_handle);
+        witness_communicator.terminate_reliable_handle(reliable_handle);
+
+        let reliable_handle = barycentric_communicator.initialize_reliable_handle(); 
+        let barycentric_handle = barycentric_communicator.initialize_barycentric_handle(); 
+        barycentric_communicator.barycentric_agreement(format!("barycentric agreement message by {id}"), Round(0)).await; 
+        barycentric_communicator.barycentric_collect(Round(0)).await; 
+        let round = barycentric_communicator.barycentric_inspect(Round(0)).await.ok().flatten();
+        let barycentric_passed = round.is_some_and(|round| round.get_delivered() && round.get_buddy_count() >= validity_threshold
+            && round.get_message_digests().iter().flatten().count() as u32 == round.get_message_count());
+        barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+        barycentric_communicator.terminate_reliable_handle(reliable_handle);
+
+        println!("id: {id}, inspection: witness {witness_passed}, reliable {reliable_passed}, barycentric {barycentric_passed}");
+        witness_passed && reliable_passed && barycentric_passed
+    })
+}
+
+// # Function Description:
+// This function runs the inspection scenario: every node runs a witness round and a barycentric agreement
+// round, and the snapshots returned by its handles must match what it collected.
+// # Parameters:
+// * `config` - the configuration of the witness and barycentric clusters.
+// # Returns
+// * `true` if every node passed.
+async fn simulate_inspection(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let validity_threshold = config.get_thresholds().get_validity_threshold();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let (transmitters, receivers) = create_channels(&config);
+    let mut barycentric_hub = BarycentricHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for i in 0..thread_count {
+        handles.push(create_inspect_thread(i, witness_hub.create_witness_communicator(), barycentric_hub.create_barycentric_communicator(), validity_threshold));
+    }
+
+    let mut passed = true;
+    for handle in handles {
+        passed &= handle.await.unwrap_or(false);
+    }
+    println!("inspection scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function spawns a node for the aggregation depth scenario: the node runs an aggregated witness round,
+// collects it, then collects every aggregation level and checks that the levels are nested, that each holds
+// at least `validity_threshold` values, that the last one is what the round delivered, and that its handle
+// counted enough witnesses at every level.
+// # Parameters:
+// * id - the ID of the node.
+// * aggregated_witness_communicator - the node's `AggregatedWitnessCommunicator`.
+// * aggregation_depth - the number of aggregation levels of the round.
+// * validity_threshold - the number of values (and witnesses) needed to complete a level.
+// # Returns
+// * `JoinHandle<bool>` - resolving to whether every level matched.
+fn create_aggregation_depth_thread(id: u32, mut aggregated_witness_communicator: AggregatedWitnessCommunicator<String>, aggregation_depth: u32, validity_threshold: u64) -> JoinHandle<bool> {
+    tokio::spawn(async move {
+        let reliable_handle = aggregated_witness_communicator.initialize_reliable_handle(); 
+        let witness_handle = aggregated_witness_communicator.initialize_witness_handle(); 
+        aggregated_witness_communicator.aggregated_witness_broadcast(format!("aggregated witness broadcast message by {id}"), Round(0)).await; 
+        let collected = aggregated_witness_communicator.aggregated_witness_collect(Round(0)).await; 
+
+        let mut levels: Vec<Vec<Message<String>>> = vec![];
+        for level in 1..=aggregation_depth {
+            levels.push(aggregated_witness_communicator.aggregated_witness_collect_level(Round(0), level).await);
+        }
+        let round = aggregated_witness_communicator.witness_inspect(Round(0)).await.ok().flatten();
+        aggregated_witness_communicator.terminate_witness_handle(witness_handle);
+        aggregated_witness_communicator.terminate_reliable_handle(reliable_handle);
+
+        let nested = levels.windows(2).all(|pair| pair[0].iter().all(|value| pair[1].contains(value)));
+        let quorums = levels.iter().all(|level| level.len() as u64 >= validity_threshold);
+        let delivered = levels.last() == Some(&collected);
+        let counted = round.is_some_and(|round| (1..=aggregation_depth).all(|level| round.get_level_witness_count(level) >= validity_threshold));
+        println!("id: {id}, aggregation depth {aggregation_depth}: nested {nested}, quorums {quorums}, delivered {delivered}, counted {counted}");
+        nested && quorums && delivered && counted
+    })
+}
+
+// # Function Description:
+// This function runs the aggregation depth scenario: every node takes part in an aggregated witness round
+// with 1 to 4 aggregation levels, and every level must be consistent with the levels around it. The time
+// the cluster took is printed for every depth.
+// # Parameters:
+// * `config` - the configuration of the aggregated witness clusters, run once per aggregation depth.
+// # Returns
+// * `true` if every node passed at every depth.
+async fn simulate_aggregation_depth(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let validity_threshold = config.get_thresholds().get_validity_threshold();
+    let mut passed = true;
+
+    for aggregation_depth in 1..=4 {
+        let config = config.clone().with_aggregation_depth(aggregation_depth);
+        let (transmitters, receivers) = create_channels(&config);
+        let mut aggregated_witness_hub = AggregatedWitnessHub::with_config(transmitters, receivers, config);
+        let start = Instant::now();
+        let mut handles = vec![];
+        for i in 0..thread_count {
+            handles.push(create_aggregation_depth_thread(i, aggregated_witness_hub.create_aggregated_witness_communicator(), aggregation_depth, validity_threshold));
+        }
+
+        let mut depth_passed = true;
+        for handle in handles {
+            depth_passed &= handle.await.unwrap_or(false);
+        }
+        println!("aggregation depth {aggregation_depth}: {:?}, {}", start.elapsed(), if depth_passed { "passed" } else { "failed" });
+        passed &= depth_passed;
+    }
+    passed
+}
+
+// # Function Description:
+// This function runs the barycentric certificate scenario: every node runs a barycentric agreement round and
+// collects it with its `BuddyCertificate`. Every certificate must attest enough distinct buddies of the
+// round, survive a JSON round trip, and fail verification without recorded reports; and certificates of
+// different nodes attesting the same barycentric report (same buddy and instance) must agree on its digest.
+// # Parameters:
+// * `config` - the configuration of the barycentric cluster.
+// # Returns
+// * `true` if every certificate passed.
+async fn simulate_barycentric_certificate(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let validity_threshold = config.get_thresholds().get_validity_threshold();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut barycentric_hub = BarycentricHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for i in 0..thread_count {
+        let mut barycentric_communicator = barycentric_hub.create_barycentric_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = barycentric_communicator.initialize_reliable_handle(); 
+            let barycentric_handle = barycentric_communicator.initialize_barycentric_handle(); 
+            barycentric_communicator.barycentric_agreement(format!("barycentric agreement message by {i}"), Round(0)).await; 
+            let (_, certificate) = barycentric_communicator.barycentric_collect_certified(Round(0)).await; 
+            barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+            barycentric_communicator.terminate_reliable_handle(reliable_handle);
+            certificate
+        }));
+    }
+
+    let mut certificates: Vec<BuddyCertificate> = vec![];
+    for handle in handles {
+        match handle.await {
+            Ok(certificate) => certificates.push(certificate),
+            Err(_) => return false,
+        }
+    }
+
+    let mut passed = true;
+    for (id, certificate) in certificates.iter().enumerate() {
+        let buddy_ids = certificate.get_buddy_ids();
+        let well_formed = certificate.get_id() == id as u32 && certificate.get_round_number() == Round(0)
+            && buddy_ids.len() as u64 >= validity_threshold && buddy_ids.windows(2).all(|pair| pair[0] < pair[1])
+            && buddy_ids.iter().all(|buddy_id| *buddy_id < thread_count);
+        let exported = BuddyCertificate::read_json(&certificate.write_json()).is_ok_and(|imported| &imported == certificate);
+        let unverified = !certificate.verify::<String>(&[]);
+        let consistent = certificates.iter().all(|other| certificate.get_attestations().iter().all(|attestation| other.get_attestations().iter()
+            .filter(|other_attestation| other_attestation.get_id() == attestation.get_id() && other_attestation.get_instance_number() == attestation.get_instance_number())
+            .all(|other_attestation| other_attestation.get_report_digest() == attestation.get_report_digest())));
+        println!("id: {id}, buddies: {buddy_ids:?}, well formed {well_formed}, exported {exported}, unverified without reports {unverified}, consistent {consistent}");
+        passed &= well_formed && exported && unverified && consistent;
+    }
+    println!("barycentric certificate scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs one protocol of the payload benchmark on a fresh cluster whose payload type is `T`.
+// For "reliable", every node reliably broadcasts `instances` instances and delivers those of every node;
+// for "witness", "aggregated_witness", and "barycentric", every node takes part in one round.
+// # Parameters:
+// * `protocol` - the protocol to run.
+// * `config` - the configuration of the benchmarked cluster.
+// * `payload` - builds the payload of a node (first argument) for an instance (second argument).
+// * `instances` - the number of instances every node reliably broadcasts.
+// # Returns
+// * the time the whole cluster took, or `None` if a node did not finish within the timeout.
+async fn run_payload_benchmark<T>(protocol: &str, config: ClusterConfig, payload: fn(u32, u32) -> T, instances: u32) -> Option<Duration>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Default + Send + Sync + 'static,
+{
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let start = Instant::now();
+    let mut handles: Vec<JoinHandle<()>> = vec![];
+
+    if protocol == "reliable" {
+        let mut hub: ReliableHub<T> = ReliableHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_reliable_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                for instance_number in 0..instances {
+                    communicator.reliable_broadcast(payload(id, instance_number), Instance(id * instances + instance_number), Round(0)).await;
+                }
+                for sender in 0..thread_count {
+                    for instance_number in 0..instances {
+                        communicator.reliable_recv(Some(sender), Instance(sender * instances + instance_number), Round(0)).await;
+                    }
+                }
+                communicator.terminate_reliable_handle(reliable_handle);
+            }));
+        }
+    } else if protocol == "witness" {
+        let mut hub: WitnessHub<T> = WitnessHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let witness_handle = communicator.initialize_witness_handle();
+                communicator.witness_broadcast(payload(id, 0), Round(0)).await;
+                communicator.witness_collect(Round(0)).await;
+                communicator.terminate_witness_handle(witness_handle);
+                communicator.terminate_reliable_handle(reliable_handle);
+            }));
+        }
+    } else if protocol == "aggregated_witness" {
+        let mut hub: AggregatedWitnessHub<T> = AggregatedWitnessHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_aggregated_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let witness_handle = communicator.initialize_witness_handle();
+                communicator.aggregated_witness_broadcast(payload(id, 0), Round(0)).await;
+                communicator.aggregated_witness_collect(Round(0)).await;
+                communicator.terminate_witness_handle(witness_handle);
+                communicator.terminate_reliable_handle(reliable_handle);
+            }));
+        }
+    } else {
+        let mut hub: BarycentricHub<T> = BarycentricHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_barycentric_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let barycentric_handle = communicator.initialize_barycentric_handle();
+                communicator.barycentric_agreement(payload(id, 0), Round(0)).await;
+                communicator.barycentric_collect(Round(0)).await;
+                communicator.terminate_barycentric_handle(barycentric_handle);
+                communicator.terminate_reliable_handle(reliable_handle);
+            }));
+        }
+    }
+
+    let finished = tokio::time::timeout(Duration::from_secs(30), join_all(handles)).await;
+    match finished {
+        Ok(results) if results.iter().all(Result::is_ok) => Some(start.elapsed()),
+        _ => None,
+    }
+}
+
+// # Function Description:
+// This function runs the payload benchmark: the same workload of every protocol is run with `String`
+// payloads, as in the other experiments, and with `NumericPayload`s, and the elapsed times and the
+// encoded size of a message are compared. Running every protocol with a non-`String` payload also
+// checks that none of them depends on `String` payloads.
+// # Parameters:
+// * `config` - the configuration of every benchmarked cluster.
+// # Returns
+// * `true` if every protocol finished with both payload types.
+async fn simulate_payload_benchmark(config: ClusterConfig) -> bool {
+    let instances = 20;
+    let string_payload: fn(u32, u32) -> String = |id, instance_number| format!("reliable broadcast message {instance_number} by {id}");
+    let numeric_payload: fn(u32, u32) -> NumericPayload = |id, instance_number| NumericPayload::new(((id as u64) << 32) | instance_number as u64);
+
+    let string_size = Message::new(String::from("reliable"), 0, string_payload(0, 0), None, Some(Instance(0)), Round(0)).write_json().len();
+    let numeric_size = Message::new(String::from("reliable"), 0, numeric_payload(0, 0), None, Some(Instance(0)), Round(0)).write_json().len();
+    println!("payload benchmark, encoded message: {string_size} bytes with a String payload, {numeric_size} bytes with a NumericPayload");
+
+    let mut passed = true;
+    for protocol in ["reliable", "witness", "aggregated_witness", "barycentric"] {
+        let string_elapsed = run_payload_benchmark(protocol, config.clone(), string_payload, instances).await;
+        let numeric_elapsed = run_payload_benchmark(protocol, config.clone(), numeric_payload, instances).await;
+        match (string_elapsed, numeric_elapsed) {
+            (Some(string_elapsed), Some(numeric_elapsed)) => {
+                let delta = 100.0 * (numeric_elapsed.as_secs_f64() / string_elapsed.as_secs_f64() - 1.0);
+                println!("payload benchmark, {protocol}: {:?} with String payloads, {:?} with NumericPayloads ({:+.1}%)", string_elapsed, numeric_elapsed, delta);
+            },
+            _ => {
+                println!("payload benchmark, {protocol}: failed (String: {:?}, NumericPayload: {:?})", string_elapsed, numeric_elapsed);
+                passed = false;
+            },
+        }
+    }
+    passed
+}
+
+// # Function Description:
+// This function runs the signing scenario under `Authentication::Ed25519`: every protocol first runs one
+// round on a signed cluster, then a forger injects two Inputs claiming to come from node 0 into a reliable
+// cluster, one unsigned and one with a made-up signature. Every node must reject both, publishing
+// `Event::InvalidSignature`, and never deliver the forged instance, while it still delivers node 0's own.
+// # Parameters:
+// * `config` - the configuration of the benchmarked clusters, which sign their signals with Ed25519.
+// # Returns
+// * `true` if every signed round completed and every node rejected the forgeries.
+async fn simulate_signing(config: ClusterConfig) -> bool {
+    let config = config.with_authentication(Authentication::Ed25519);
+    let payload: fn(u32, u32) -> String = |id, instance_number| format!("reliable broadcast message {instance_number} by {id}");
+    let mut passed = true;
+    for protocol in ["reliable", "witness", "aggregated_witness", "barycentric"] {
+        let elapsed = run_payload_benchmark(protocol, config.clone(), payload, 1).await;
+        println!("signing scenario, {protocol}: {}", if elapsed.is_some() { "signed round completed" } else { "failed" });
+        passed &= elapsed.is_some();
+    }
+
+    let (transmitters, receivers) = create_channels(&config);
+    let forger = transmitters.clone();
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        handles.push(tokio::spawn(async move {
+            let mut events = reliable_communicator.subscribe_events();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+            }
+            let genuine = reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await.get_message() == "reliable broadcast message by 0";
+            let forged = tokio::time::timeout(Duration::from_millis(500), reliable_communicator.reliable_recv(Some(0), Instance(1), Round(0))).await.is_ok();
+            let mut rejected = 0;
+            while let Some(event) = next_event(&mut events).await {
+                rejected += matches!(event, Event::InvalidSignature(observer, signal) if observer == id && signal.get_instance_number() == Instance(1)) as u32;
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            println!("id: {id}, delivered the genuine instance {genuine}, delivered the forged instance {forged}, rejected {rejected} forged signals");
+            genuine && !forged && rejected == 2
+        }));
+    }
+
+    let message = Message::new(String::from("reliable"), 0, String::from("forged message"), None, Some(Instance(1)), Round(0));
+    let unsigned = Signal::new(SignalType::Input, ObjectContent::Message(message), Instance(1), Round(0));
+    let mut forged: serde_json::Value = serde_json::from_str(&unsigned.write_json()).unwrap();
+    forged["signer"] = 0.into();
+    forged["signature"] = "00".repeat(64).into();
+    let forged = Signal::<String>::read_json(&forged.to_string()).unwrap();
+    for frame in [unsigned.write_signal_frame(config.get_codec()), forged.write_signal_frame(config.get_codec())] {
+        for transmitter in &forger {
+            let _ = transmitter.send(tag_frame(Lane::Signal, frame.clone())).await;
+        }
+    }
+
+    let rejected = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("signing scenario: forgeries {}", if rejected { "rejected" } else { "accepted" });
+    passed && rejected
+}
+
+// # Function Description:
+// This function runs the cumulative scenario: every node broadcasts a value in each of three witness rounds
+// and collects them, once with `RoundValues::PerRound` and once with `RoundValues::Cumulative`. Per round,
+// every round must deliver only its own values; cumulatively, every round must deliver all the values the
+// node delivered in the previous round, without the nodes re-broadcasting them.
+// # Parameters:
+// * `config` - the configuration of the witness clusters, run once per `RoundValues` mode.
+// # Returns
+// * `true` if every node delivered the expected values under both modes.
+async fn simulate_cumulative(config: ClusterConfig) -> bool {
+    let rounds = 3;
+    let mut passed = true;
+    for round_values in [RoundValues::PerRound, RoundValues::Cumulative] {
+        let config = config.clone().with_round_values(round_values);
+        let (transmitters, receivers) = create_channels(&config);
+        let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+        let mut handles = vec![];
+        for id in 0..config.get_thread_count() {
+            let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = witness_communicator.initialize_reliable_handle();
+                let witness_handle = witness_communicator.initialize_witness_handle();
+                let mut delivered: Vec<Vec<Message<String>>> = vec![];
+                for round in 0..rounds {
+                    witness_communicator.witness_broadcast(format!("value of round {round} by {id}"), Round(round)).await;
+                    delivered.push(witness_communicator.witness_collect(Round(round)).await);
+                }
+                witness_communicator.terminate_witness_handle(witness_handle);
+                witness_communicator.terminate_reliable_handle(reliable_handle);
+                let expected = (0..rounds as usize).all(|round| {
+                    let own = delivered[round].iter().all(|value| value.get_round_number() == Round(round as u32));
+                    match round_values {
+                        RoundValues::PerRound => own,
+                        RoundValues::Cumulative => round == 0 || delivered[round - 1].iter().all(|value| delivered[round].contains(value)),
+                    }
+                });
+                let sizes: Vec<usize> = delivered.iter().map(|values| values.len()).collect();
+                println!("id: {id}, {round_values:?} values delivered per round {sizes:?}, expected {expected}");
+                expected
+            }));
+        }
+        passed &= join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    }
+    println!("cumulative scenario: {}", if passed { "rounds delivered as expected" } else { "unexpected values delivered" });
+    passed
+}
+
+// A frame type introduced by the extension scenario, as a plugin would, without touching the core modules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Heartbeat {
+    sequence: u64,
+    load: f64,
+}
+
+impl ExtensionFrame for Heartbeat {
+    const TYPE_ID: &'static str = "heartbeat";
+}
+
+// A frame type no node registers a handler for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Unregistered {
+    note: String,
+}
+
+impl ExtensionFrame for Unregistered {
+    const TYPE_ID: &'static str = "unregistered";
+}
+
+// # Function Description:
+// This function runs the extension scenario: every node registers a handler for the `Heartbeat` frames,
+// node 0 reliably broadcasts a heartbeat, and node 1 a frame of a type no node registered. Every node must
+// hand the heartbeat, decoded, to its handler, refuse a second handler for the same type, and publish
+// `Event::CapabilityMismatch` for the unregistered frame instead of delivering it.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster carrying the extension frames.
+// # Returns
+// * `true` if every node handled both frames as expected.
+async fn simulate_extension(config: ClusterConfig) -> bool {
+    let heartbeat = Heartbeat { sequence: 7, load: 0.25 };
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let mut events = reliable_communicator.subscribe_events();
+        let heartbeat = heartbeat.clone();
+        handles.push(tokio::spawn(async move {
+            let (heartbeat_tx, mut heartbeat_rx) = mpsc::unbounded_channel();
+            let registered = reliable_communicator.register_extension(move |delivery: ExtensionDelivery<Heartbeat>| {
+                let _ = heartbeat_tx.send(delivery);
+            }).is_ok();
+            let refused = reliable_communicator.register_extension(|_: ExtensionDelivery<Heartbeat>| {}).is_err();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            match id {
+                0 => reliable_communicator.reliable_broadcast_extension(&heartbeat, Instance(0), Round(0)).await,
+                1 => reliable_communicator.reliable_broadcast_extension(&Unregistered { note: String::from("no handler") }, Instance(1), Round(0)).await,
+                _ => {},
+            }
+            let delivered = tokio::time::timeout(Duration::from_secs(5), heartbeat_rx.recv()).await.ok().flatten();
+            let mismatch = tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    match events.recv().await {
+                        Ok(Event::CapabilityMismatch(mismatch)) => return Some(mismatch),
+                        Ok(_) => {},
+                        Err(_) => return None,
+                    }
+                }
+            }).await.ok().flatten();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            let handled = delivered.as_ref().is_some_and(|delivery| delivery.get_id() == 0 && delivery.get_instance_number() == Instance(0) && *delivery.get_frame() == heartbeat);
+            let discarded = mismatch.as_ref().is_some_and(|mismatch| mismatch.get_peer() == 1 && mismatch.get_reason().contains(Unregistered::TYPE_ID));
+            println!("id: {id}, registered {registered}, second handler refused {refused}, heartbeat handled {handled}, unregistered frame discarded {discarded}");
+            registered && refused && handled && discarded
+        }));
+    }
+    let passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("extension scenario: {}", if passed { "frames handled as expected" } else { "frames mishandled" });
+    passed
+}
+
+// # Function Description:
+// This function runs the workload of the gossip scenario: every node reliably broadcasts 5 instances and
+// receives those of every node. Handles are only terminated once every node received everything, so
+// that the nodes that are done still answer the digests of the others.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster, with its dissemination mode.
+// # Returns
+// * The metrics report of every node.
+async fn run_gossip_workload(config: ClusterConfig) -> Vec<MetricsReport> {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);
+    let barrier = Arc::new(Barrier::new(thread_count as usize));
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for instance_number in 0..5 {
+                let message = format!("reliable broadcast message by {id}");
+                reliable_communicator.reliable_broadcast(message, Instance(instance_number), Round(0)).await;
+                for sender in 0..thread_count {
+                    reliable_communicator.reliable_recv(Some(sender), Instance(instance_number), Round(0)).await;
+                }
+            }
+            barrier.wait().await;
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            reliable_communicator.metrics_report()
+        }));
+    }
+    join_all(handles).await.into_iter().map(|report| report.unwrap()).collect()
+}
+
+// # Function Description:
+// This function runs the gossip scenario: every node reliably broadcasts a few instances with direct
+// dissemination, then by gossip with a fan-out of `ln n + 1`, then by gossip with a fan-out of 1, which
+// misses threads without the anti-entropy exchanges. Every run must deliver every instance. A witness
+// round must also deliver when only the witness protocol is gossiped, the reliable protocol being sent
+// directly.
+// # Parameters:
+// * `config` - the configuration of the reliable clusters, run once per dissemination mode.
+// # Returns
+// * `true` if every run delivered.
+async fn simulate_gossip(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let fanout = (thread_count as f64).ln().ceil() as u32 + 1;
+    let anti_entropy = Some(Duration::from_millis(100));
+    let modes = [Dissemination::Direct, Dissemination::Gossip(fanout, anti_entropy), Dissemination::Gossip(1, anti_entropy)];
+    let mut passed = true;
+    for dissemination in modes {
+        let workload = run_gossip_workload(config.clone().with_dissemination(dissemination));
+        match tokio::time::timeout(Duration::from_secs(60), workload).await {
+            Ok(reports) => println!("{dissemination:?}: every instance delivered, {} frames received", count_frames(&reports, "reliable")),
+            Err(_) => {
+                println!("{dissemination:?}: an instance was not delivered");
+                passed = false;
+            },
+        }
+    }
+
+    let witness_config = config.with_protocol_dissemination("witness", Dissemination::Gossip(1, anti_entropy));
+    let selected = Disseminator::from_config(0, &witness_config).is_some_and(|disseminator| disseminator.get_dissemination("reliable") == Dissemination::Direct
+        && disseminator.get_dissemination("witness") == Dissemination::Gossip(1, anti_entropy));
+    let (transmitters, receivers) = create_channels(&witness_config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, witness_config.clone());
+    let quorum = thread_count - witness_config.get_thresholds().get_faulty_threads() as u32;
+    let barrier = Arc::new(Barrier::new(thread_count as usize));
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let barrier = barrier.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            let collected = tokio::time::timeout(Duration::from_secs(10), witness_communicator.witness_collect(Round(0))).await;
+            // the handles keep answering the digests of the other nodes until every node collected
+            barrier.wait().await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            collected.is_ok_and(|values| values.len() as u32 >= quorum)
+        }));
+    }
+    let witnessed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("witness protocol gossiped alone: dissemination selected per protocol {selected}, round delivered {witnessed}");
+    passed &= selected && witnessed;
+
+    println!("gossip scenario: {}", if passed { "every run delivered" } else { "a run did not deliver" });
+    passed
+}
+
+// # Function Description:
+// This function sends a GET request to an HTTP endpoint and reads the whole response.
+// # Parameters:
+// * `address` - the address of the endpoint.
+// * `path` - the path requested.
+// # Returns
+// * the status code and the body of the response, or `None` if the endpoint could not be reached.
+async fn http_get(address: SocketAddr, path: &str) -> Option<(u16, String)> {
+    let mut stream = TcpStream::connect(address).await.ok()?;
+    stream.write_all(format!("GET {path} HTTP/1.1\r\nHost: {address}\r\nConnection: close\r\n\r\n").as_bytes()).await.ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.ok()?;
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.split_whitespace().nth(1)?.parse().ok()?;
+    Some((status, body.to_string()))
+}
+
+// # Function Description:
+// This function runs the health scenario: every thread of a witness cluster takes part in a witness round,
+// then the health of the cluster is checked in-process (`health()`) and through the HTTP endpoint
+// (`serve_health`). The healthy cluster must report every handle running, a frame processed, the round
+// completed, and the messages left in a basic queue; once the witness handle of thread 0 is aborted, and
+// once the cluster went idle for longer than the stall bound, the cluster must be reported unhealthy.
+// # Parameters:
+// * `config` - the configuration of the monitored witness cluster.
+// # Returns
+// * `true` if the health of the cluster was reported as expected.
+async fn simulate_health(config: ClusterConfig) -> bool {
+    let stall = Duration::from_secs(5);
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let mut tasks = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        tasks.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("value of node {id}"), Round(1)).await;
+            let collected = tokio::time::timeout(Duration::from_secs(30), witness_communicator.witness_collect(Round(1))).await.is_ok();
+            (witness_communicator, reliable_handle, witness_handle, collected)
+        }));
+    }
+    let mut threads = vec![];
+    for task in join_all(tasks).await {
+        match task {
+            Ok(thread) => threads.push(thread),
+            Err(error) => {
+                println!("health scenario: a thread panicked: {error}");
+                return false
+            },
+        }
+    }
+    // messages nobody receives wait in the basic queue of thread 1
+    for message_number in 0..3 {
+        threads[0].0.basic_send(1, format!("unread message {message_number}"), Round(2)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let metrics: Vec<(u32, Metrics)> = threads.iter().map(|(communicator, ..)| (*communicator.get_id(), communicator.get_metrics().clone())).collect();
+    let cluster_health = move || ClusterHealth::new(metrics.iter().map(|(id, metrics)| metrics.health(*id)).collect());
+    let listener = match TcpListener::bind("127.0.0.1:0").await {
+        Ok(listener) => listener,
+        Err(error) => {
+            println!("health scenario: could not listen: {error}");
+            return false
+        },
+    };
+    let Ok(address) = listener.local_addr() else {
+        return false
+    };
+    let endpoint = tokio::spawn(serve_health(listener, stall, cluster_health));
+
+    let mut passed = threads.iter().all(|(.., collected)| *collected);
+    for (communicator, ..) in &threads {
+        let health = communicator.health();
+        let running = health.get_handles().len() == 2 && health.get_handles().values().all(|running| *running);
+        let completed = health.get_rounds_completed().get("witness").copied().unwrap_or(0) >= 1;
+        let queued = *communicator.get_id() != 1 || health.get_queue_depths().get("Basic").copied().unwrap_or(0) == 3;
+        println!("id: {}, handles: {:?}, rounds completed: {:?}, queue depths: {:?}", health.get_id(), health.get_handles(), health.get_rounds_completed(), health.get_queue_depths());
+        passed &= running && completed && queued && health.get_last_frame().is_some() && health.get_suspected_peers().is_empty() && health.is_healthy(stall);
+    }
+    let healthy = http_get(address, "/health").await;
+    let reported = healthy.as_ref().and_then(|(_, body)| ClusterHealth::read_json(body).ok());
+    println!("healthy cluster: status {:?}", healthy.as_ref().map(|(status, _)| *status));
+    passed &= healthy.as_ref().is_some_and(|(status, _)| *status == 200) && reported.is_some_and(|reported| reported.get_nodes().len() as u32 == thread_count);
+    passed &= http_get(address, "/metrics").await.is_some_and(|(status, _)| status == 404);
+
+    let (communicator, _, witness_handle, _) = &threads[0];
+    witness_handle.abort();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let unhealthy = http_get(address, "/health").await;
+    let problems = unhealthy.as_ref().and_then(|(_, body)| ClusterHealth::read_json(body).ok()).map(|reported| reported.get_problems(stall)).unwrap_or_default();
+    println!("after aborting the witness handle of thread 0: status {:?}, problems {:?}", unhealthy.as_ref().map(|(status, _)| *status), problems);
+    passed &= unhealthy.is_some_and(|(status, _)| status == 503) && problems == vec![String::from("id: 0, witness handle stopped")];
+
+    let idle = communicator.health();
+    passed &= idle.get_problems(Duration::from_millis(50)).iter().any(|problem| problem.starts_with("no frame processed"));
+    endpoint.abort();
+    for (communicator, reliable_handle, witness_handle, _) in threads {
+        communicator.terminate_witness_handle(witness_handle);
+        communicator.terminate_reliable_handle(reliable_handle);
+    }
+    println!("health scenario: {}", if passed { "the health of the cluster was reported as expected" } else { "the health of the cluster was misreported" });
+    passed
+}
+
+// # Function Description:
+// This function prints the `ProtocolDescriptor` of every protocol (or of a single one) in a cluster, after
+// checking that each is consistent.
+//
+// # Parameters:
+// * config - The configuration of the cluster, in which the quorums are evaluated.
+// * protocol - The name of the protocol to describe, with underscores for spaces (e.g. "aggregated_witness"), if any.
+// * json - Whether to print the descriptors as JSON lines instead of text.
+//
+// # Returns:
+// * `true` if the protocol exists and every printed descriptor is consistent.
+fn describe(config: &ClusterConfig, protocol: Option<&str>, json: bool) -> bool {
+    let descriptors: Vec<ProtocolDescriptor> = describe_protocols(config).into_iter()
+        .filter(|descriptor| protocol.is_none_or(|protocol| descriptor.get_name().replace(' ', "_") == protocol))
+        .collect();
+    if descriptors.is_empty() {
+        println!("describe: unknown protocol {protocol:?}, expected one of reliable, witness, aggregated_witness, barycentric");
+        return false
+    }
+    let mut consistent = true;
+    for descriptor in descriptors {
+        if let Err(error) = descriptor.validate() {
+            println!("describe: inconsistent descriptor: {error}");
+            consistent = false;
+        }
+        match json {
+            true => println!("{}", descriptor.write_json()),
+            false => println!("{}", descriptor.render()),
+        }
+    }
+    consistent
+}
+
+// # Function Description:
+// This function runs the vector clock scenario with vector clocks maintained by the communicators: thread 0
+// sends a message to thread 1, which forwards it to thread 2 once received, while thread 3 sends thread 2 a
+// message of its own. The forwarded message must happen after the first one, the message of thread 3 must
+// be concurrent with both, and the clock of thread 2 must count every one of them. Thread 0 then reliably
+// broadcasts a message, which every thread must receive with the clock of its broadcast merged in.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * `true` if the clocks of the messages and of the threads order the sends and receipts.
+async fn simulate_vector_clock(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    if thread_count < 4 {
+        println!("vector clock scenario: requires at least 4 threads");
+        return false
+    }
+    let config = config.with_vector_clocks(true);
+    let (transmitters, receivers) = create_channels(&config);
+    let mut basic_hub: BasicHub<String> = BasicHub::with_config(transmitters, receivers, config.clone());
+    let mut basic_communicators: Vec<BasicCommunicator<String>> = (0..thread_count).map(|_| basic_hub.create_basic_communicator()).collect();
+
+    basic_communicators[0].basic_send(1, String::from("first"), Round(0)).await;
+    basic_communicators[3].basic_send(2, String::from("concurrent"), Round(0)).await;
+    let first = basic_communicators[1].basic_recv(Some(0), Round(0)).await;
+    basic_communicators[1].basic_send(2, String::from("forwarded"), Round(0)).await;
+    let forwarded = basic_communicators[2].basic_recv(Some(1), Round(0)).await;
+    let concurrent = basic_communicators[2].basic_recv(Some(3), Round(0)).await;
+    let (Some(first), Some(forwarded), Some(concurrent)) = (first.get_clock(), forwarded.get_clock(), concurrent.get_clock()) else {
+        println!("vector clock scenario: a message was sent without a clock");
+        return false
+    };
+    let Some(receiver_clock) = basic_communicators[2].get_queues().get_clock().cloned() else {
+        println!("vector clock scenario: thread 2 keeps no clock");
+        return false
+    };
+    println!("first: {first:?}, forwarded: {forwarded:?}, concurrent: {concurrent:?}, thread 2: {receiver_clock:?}");
+    let mut passed = first.happened_before(forwarded)
+        && !concurrent.happened_before(first) && !first.happened_before(concurrent)
+        && !concurrent.happened_before(forwarded) && !forwarded.happened_before(concurrent)
+        && forwarded.happened_before(&receiver_clock) && concurrent.happened_before(&receiver_clock);
+
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut tasks = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        tasks.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable"), Instance(0), Round(0)).await;
+            }
+            let message = reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+            let broadcast_clock = message.get_clock().cloned();
+            let thread_clock = reliable_communicator.get_queues().get_clock().cloned();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            matches!((broadcast_clock, thread_clock), (Some(broadcast_clock), Some(thread_clock))
+                if broadcast_clock.get(0) == 1 && broadcast_clock.happened_before(&thread_clock))
+        }));
+    }
+    for task in join_all(tasks).await {
+        passed &= task.unwrap_or(false);
+    }
+
+    // without vector clocks, messages carry no clock
+    let config = config.with_vector_clocks(false);
+    let (transmitters, receivers) = create_channels(&config);
+    let mut basic_hub: BasicHub<String> = BasicHub::with_config(transmitters, receivers, config);
+    let mut sender = basic_hub.create_basic_communicator();
+    let mut receiver = basic_hub.create_basic_communicator();
+    sender.basic_send(1, String::from("unstamped"), Round(0)).await;
+    let unstamped = receiver.basic_recv(Some(0), Round(0)).await;
+    passed &= unstamped.get_clock().is_none() && receiver.get_queues().get_clock().is_none();
+
+    println!("vector clock scenario: {}", if passed { "the clocks order every send and receipt" } else { "a clock does not order the sends and receipts" });
+    passed
+}
+
+// # Function Description:
+// This function runs a few reliable broadcast instances and a witness round over links with a fixed latency,
+// and gathers how the reliable handles of the threads delivered their instances.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The delivery paths of every thread, summed by protocol information, and how long the run took, or `None`
+//   if a thread did not complete it.
+async fn run_fast_path(config: ClusterConfig) -> Option<(BTreeMap<String, DeliveryPaths>, Duration)> {
+    const INSTANCES: u32 = 3;
+    let thread_count = config.get_thread_count();
+    let config = config.with_network_emulation(NetworkEmulation::new().with_conditions(LinkConditions::new(Duration::from_millis(5), None)));
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let barrier = Arc::new(Barrier::new(thread_count as usize));
+    let start = Instant::now();
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let barrier = barrier.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            for instance_number in 0..INSTANCES {
+                witness_communicator.reliable_broadcast(format!("reliable broadcast message {instance_number} by {id}"), Instance(instance_number), Round(0)).await;
+            }
+            for sender in 0..thread_count {
+                for instance_number in 0..INSTANCES {
+                    witness_communicator.reliable_recv(Some(sender), Instance(instance_number), Round(0)).await;
+                }
+            }
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            let collected = witness_communicator.witness_collect_with_timeout(Round(0), Duration::from_secs(10)).await.is_ok();
+            barrier.wait().await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            collected.then(|| witness_communicator.get_metrics().get_delivery_paths())
+        }));
+    }
+    let completed = tokio::time::timeout(Duration::from_secs(30), join_all(handles)).await.ok()?;
+    let elapsed = start.elapsed();
+    let mut delivery_paths: BTreeMap<String, DeliveryPaths> = BTreeMap::new();
+    for task in completed {
+        for (protocol_information, paths) in task.ok()?? {
+            delivery_paths.entry(protocol_information).or_default().merge(&paths);
+        }
+    }
+    Some((delivery_paths, elapsed))
+}
+
+// # Function Description:
+// This function runs the fast path scenario: reliable broadcast instances and a witness round are run without
+// the fast path, with it, and with a fast path timeout that always expires. With the fast path, every
+// instance of a fault-free cluster must be delivered on the Echo signals of every thread; otherwise, every
+// instance must be delivered on its Vote quorum. The time of every run is printed, to show the benefit.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * `true` if every instance was delivered on the expected path.
+async fn simulate_fast_path(config: ClusterConfig) -> bool {
+    let runs = [
+        ("without the fast path", config.clone(), false),
+        ("with the fast path", config.clone().with_fast_path(Duration::from_secs(1)), true),
+        ("with an expired fast path", config.clone().with_fast_path(Duration::ZERO), false),
+    ];
+    let mut passed = true;
+    for (name, config, fast) in runs {
+        let Some((delivery_paths, elapsed)) = run_fast_path(config).await else {
+            println!("fast path scenario, {name}: a thread did not complete the run");
+            passed = false;
+            continue
+        };
+        let paths: Vec<String> = delivery_paths.iter()
+            .map(|(protocol_information, paths)| format!("{protocol_information}: {} fast, {} slow", paths.get_fast(), paths.get_slow()))
+            .collect();
+        println!("fast path scenario, {name}: {elapsed:?}, {}", paths.join(", "));
+        let expected = ["reliable", "witness"].iter().all(|protocol_information| delivery_paths.contains_key(*protocol_information))
+            && delivery_paths.values().all(|paths| match fast {
+                true => paths.get_fast() > 0 && paths.get_slow() == 0,
+                false => paths.get_fast() == 0 && paths.get_slow() > 0,
+            });
+        passed &= expected;
+    }
+    println!("fast path scenario: {}", if passed { "every instance was delivered on the expected path" } else { "an instance was delivered on the wrong path" });
+    passed
+}
+
+// # Function Description:
+// This function runs the validated broadcast scenario: every node but the last rejects the payloads
+// containing "garbage" with its validity predicate, and node 0 reliably broadcasts a valid payload and a
+// garbage one. Every node must deliver the valid payload; no node, not even the last one, which echoes
+// anything, may deliver the garbage payload, which the other nodes report as `Event::InvalidPayload`.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster.
+// # Returns
+// * `true` if only the valid payload was delivered, and every validating node reported the garbage one.
+async fn simulate_validity(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        handles.push(tokio::spawn(async move {
+            let validating = id != thread_count - 1;
+            if validating {
+                reliable_communicator.set_validity_predicate(|payload: &String| !payload.contains("garbage"));
+            }
+            let mut events = reliable_communicator.subscribe_events();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("valid payload by 0"), Instance(0), Round(0)).await;
+                reliable_communicator.reliable_broadcast(String::from("garbage payload by 0"), Instance(1), Round(0)).await;
+            }
+            let valid = reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await.get_message() == "valid payload by 0";
+            let refused = reliable_communicator.reliable_recv_with_timeout(Some(0), Instance(1), Round(0), Duration::from_millis(500)).await.is_err();
+            let mut reported = false;
+            while let Some(event) = next_event(&mut events).await {
+                reported |= matches!(event, Event::InvalidPayload(observer, _, message) if observer == id && message.get_message() == "garbage payload by 0");
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            println!("id: {id}, validating {validating}, valid payload delivered {valid}, garbage payload refused {refused}, reported {reported}");
+            valid && refused && reported == validating
+        }));
+    }
+    let passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("validity scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the round subscription scenario. Every node of a witness cluster dropping delivered
+// rounds after a grace period (`RetentionPolicy::GracePeriod`) subscribes to rounds 0 and 1, abandons a
+// collection of each of them to a timeout, and runs round 0, then round 1 once the grace period elapsed.
+// The subscription to round 0 must yield the abandonment of round 0 only, and end with the
+// `Event::RoundCollected` of the round; the subscription to round 1 must yield the abandonment of round 1,
+// and stay open since round 1 is not collected yet.
+// # Parameters:
+// * `config` - the configuration of the witness cluster, whose delivered rounds are dropped after a grace period.
+// # Returns
+// * `true` if every subscription of every node yielded the events of its round and ended as expected.
+async fn simulate_subscribe_round(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let grace_period = Duration::from_millis(100);
+    let config = config.with_retention_policy(RetentionPolicy::GracePeriod(grace_period));
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            let mut first_round = Box::pin(witness_communicator.subscribe_round(Round(0)));
+            let mut second_round = Box::pin(witness_communicator.subscribe_round(Round(1)));
+
+            let _ = witness_communicator.witness_collect_with_timeout(Round(0), Duration::ZERO).await;
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            witness_communicator.witness_collect(Round(0)).await;
+            tokio::time::sleep(grace_period * 2).await;
+            let _ = witness_communicator.witness_collect_with_timeout(Round(1), Duration::ZERO).await;
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(1)).await;
+            witness_communicator.witness_collect(Round(1)).await;
+
+            let first_events = tokio::time::timeout(Duration::from_secs(5), (&mut first_round).collect::<Vec<_>>()).await;
+            let mut second_events = vec![];
+            let mut second_open = false;
+            loop {
+                match tokio::time::timeout(Duration::from_millis(200), second_round.next()).await {
+                    Ok(Some(event)) => second_events.push(event),
+                    Ok(None) => break,
+                    Err(_) => {
+                        second_open = true;
+                        break
+                    },
+                }
+            }
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+
+            let first_ended = first_events.is_ok();
+            let first_events = first_events.unwrap_or_default();
+            let first_passed = first_ended && matches!(first_events.as_slice(), [Event::AbandonedCollect(abandonment), Event::RoundCollected(observer, protocol, Round(0))]
+                if abandonment.get_round_number() == Round(0) && *observer == id && protocol == "witness");
+            let second_passed = second_open && matches!(second_events.as_slice(), [Event::AbandonedCollect(abandonment)] if abandonment.get_round_number() == Round(1));
+            println!("id: {id}, round 0: {} events, ended {first_ended}, round 1: {} events, open {second_open}", first_events.len(), second_events.len());
+            if !(first_passed && second_passed) {
+                println!("id: {id}, round 0 events {first_events:?}, round 1 events {second_events:?}");
+            }
+            first_passed && second_passed
+        }));
+    }
+    let passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("round subscription scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the wiring validation scenario: `validate` must accept the channels of correctly wired
+// hubs (spawned and polled), reject a hub whose receivers of nodes 1 and 2 were swapped, and reject a hub
+// that already handed out a communicator. A witness round then runs on the validated hub, to show the
+// probes left nothing behind in the queues.
+// # Parameters:
+// * `config` - the configuration of the witness, reliable, and basic hubs validated.
+// # Returns
+// * `true` if every validation had the expected outcome and the witness round completed.
+async fn simulate_validation(config: ClusterConfig) -> bool {
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub: WitnessHub<String> = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let wired = witness_hub.validate().await;
+
+    let polled_config = config.clone().with_handle_mode(HandleMode::Polling);
+    let (transmitters, receivers) = create_channels(&polled_config);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(transmitters, receivers, polled_config);
+    let polled = reliable_hub.validate().await;
+
+    let (transmitters, mut receivers) = create_channels(&config);
+    receivers.swap(1, 2);
+    let mut basic_hub: BasicHub<String> = BasicHub::with_config(transmitters, receivers, config.clone());
+    let swapped = basic_hub.validate().await;
+
+    let (transmitters, receivers) = create_channels(&config);
+    let mut basic_hub: BasicHub<String> = BasicHub::with_config(transmitters, receivers, config.clone());
+    let _ = basic_hub.create_basic_communicator();
+    let partial = basic_hub.validate().await;
+
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut witness_communicator = witness_hub.create_witness_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            let collected = witness_communicator.witness_collect(Round(0)).await.len();
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            collected
+        }));
+    }
+    let round = tokio::time::timeout(Duration::from_secs(10), join_all(handles)).await
+        .map(|results| results.into_iter().all(|result| result.is_ok_and(|collected| collected > 0)))
+        .unwrap_or(false);
+
+    let passed = wired.is_ok() && polled.is_ok() && round
+        && matches!(&swapped, Err(error) if error.contains("thread 1 received the probes sent to thread 2"))
+        && partial.is_err();
+    println!("validation scenario: wired {wired:?}, polled {polled:?}, swapped {swapped:?}, partial {partial:?}, witness round after validation {}", 
+        if round { "completed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function reports that the correct threads of a run terminated, if threads were crashed by the
+// `--crash` flag or made Byzantine by the `--byzantine` flag. The faulty threads are never awaited: those
+// still waiting for a round are dropped.
+// # Parameters:
+// * `crashed` - the number of crashed threads.
+// * `byzantine` - the number of Byzantine threads.
+fn report_faulty_threads(crashed: usize, byzantine: usize) {
+    if crashed > 0 || byzantine > 0 {
+        println!("every correct thread terminated, with {crashed} crashed threads and {byzantine} byzantine threads");
+    }
+}
+
+// # Function Description:
+// This function parses the value of the `--crash` flag: `<f>` crashes f threads before they start, and
+// `<f>@<round>` crashes them after the given round.
+// # Parameters:
+// * `value` - the value of the flag.
+// # Returns
+// * The number of threads to crash and their crash point.
+fn parse_crash_flag(value: &str) -> (u32, CrashPoint) {
+    match value.split_once('@') {
+        Some((count, round)) => (count.parse().expect("Error: the crash count must be a number"), 
+            CrashPoint::AfterRound(round.parse().expect("Error: the crash round must be a number"))),
+        None => (value.parse().expect("Error: the crash count must be a number"), CrashPoint::BeforeStart),
+    }
+}
+
+// # Function Description:
+// This function parses the value of the `--byzantine` flag: `<ids>` makes the threads with the given
+// comma-separated IDs equivocate, and `<ids>@<behavior>` makes them follow the given behavior
+// (`equivocate`, `drop_echoes`, or `vote_without_echo`).
+// # Parameters:
+// * `value` - the value of the flag.
+// # Returns
+// * The IDs of the Byzantine threads and their behavior.
+fn parse_byzantine_flag(value: &str) -> (Vec<u32>, ByzantineBehavior) {
+    let (ids, behavior) = value.split_once('@').unwrap_or((value, "equivocate"));
+    let ids = ids.split(',').map(|id| id.parse().expect("Error: the byzantine IDs must be numbers")).collect();
+    let behavior = match behavior {
+        "equivocate" => ByzantineBehavior::Equivocate,
+        "drop_echoes" => ByzantineBehavior::DropEchoes,
+        "vote_without_echo" => ByzantineBehavior::VoteWithoutEcho,
+        _ => panic!("Error: unknown byzantine behavior {behavior}"),
+    };
+    (ids, behavior)
+}
+
+// # Function Description:
+// This function parses the value of the `--link` flag: `<latency>:<jitter>:<drop rate>:<reordering>:<duplication>`,
+// with the latency and jitter in milliseconds and the rates as fractions (e.g. `20:10:0.01:0.05`); duplicated
+// frames are delivered twice. Trailing fields can be omitted, and default to zero.
+// # Parameters:
+// * `value` - the value of the flag.
+// # Returns
+// * The conditions of every link.
+fn parse_link_flag(value: &str) -> LinkConditions {
+    let mut fields = value.split(':');
+    let mut next = |name: &str| -> f64 {
+        fields.next().map(|field| field.parse().unwrap_or_else(|_| panic!("Error: the link {name} must be a number"))).unwrap_or(0.0)
+    };
+    let latency = Duration::from_secs_f64(next("latency") / 1000.0);
+    let jitter = Duration::from_secs_f64(next("jitter") / 1000.0);
+    let drop_rate = next("drop rate");
+    let reordering = next("reordering");
+    let duplication = next("duplication");
+    let conditions = LinkConditions::new(latency, None).with_jitter(jitter).with_drop_rate(drop_rate).with_reordering(reordering);
+    if duplication > 0.0 { conditions.with_duplication(duplication, 2) } else { conditions }
+}
+
+// # Function Description:
+// This function parses the value of the `--gst` flag: `<GST>:<bound>`, in milliseconds (e.g. `1000:50`).
+// Before GST, the links follow their own conditions (e.g. those of `--link`).
+// # Parameters:
+// * `value` - the value of the flag.
+// # Returns
+// * The stabilization of the network.
+fn parse_gst_flag(value: &str) -> Stabilization {
+    let Some((gst, bound)) = value.split_once(':') else {
+        panic!("Error: --gst expects <GST ms>:<bound ms>");
+    };
+    let gst: u64 = gst.parse().expect("Error: the GST must be a number of milliseconds");
+    let bound: u64 = bound.parse().expect("Error: the bound must be a number of milliseconds");
+    Stabilization::new(Duration::from_millis(gst), Duration::from_millis(bound))
+}
+
+// # Function Description:
+// This function runs the middleware scenario: a reliable broadcast by node 0 on a cluster whose frames
+// carry a checksum (`Layer::Checksum`) must be delivered by every node, while an Input frame injected
+// without checksum, as a corrupted or foreign frame would be, must be discarded by every node. The
+// checksum layer is also checked on its own: a payload it sent must be restored as sent, and a tampered
+// payload rejected.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster, to which the checksum layer is added.
+// # Returns
+// * `true` if the layer restored and rejected the payloads as expected, and every node delivered the
+//   checksummed broadcast but not the injected one.
+async fn simulate_middleware(config: ClusterConfig) -> bool {
+    let chain = MiddlewareChain::new().with_layer(Layer::Checksum.create());
+    let frame = tag_frame(Lane::Signal, Bytes::from_static(b"middleware payload"));
+    let sent = chain.outbound(frame.clone());
+    let mut tampered = sent.to_vec();
+    tampered[2] ^= 1;
+    let layered = chain.inbound(sent.clone()) == Ok(frame) && chain.inbound(Bytes::from(tampered)).is_err();
+
+    let config = config.with_layer(Layer::Checksum);
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let injector = transmitters.clone();
+    let message = Message::new(String::from("reliable"), 0, String::from("injected message"), None, Some(Instance(2)), Round(0));
+    let injected = tag_frame(Lane::Signal, Signal::new(SignalType::Input, ObjectContent::Message(message), Instance(2), Round(0)).write_signal_frame(config.get_codec()));
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);
+
+    for transmitter in &injector {
+        let _ = transmitter.send(injected.clone()).await;
+    }
+
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+            }
+            let delivered = tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(1), Round(0))).await.is_ok();
+            let injected = tokio::time::timeout(Duration::from_millis(500), reliable_communicator.reliable_recv(Some(0), Instance(2), Round(0))).await.is_ok();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            println!("id: {id}, delivered the checksummed broadcast {delivered}, delivered the injected one {injected}");
+            delivered && !injected
+        }));
+    }
+    let delivered = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("middleware scenario: checksum layer {}, cluster {}", if layered { "passed" } else { "failed" }, if delivered { "passed" } else { "failed" });
+    layered && delivered
+}
+
+// # Function Description:
+// This function runs the crash-and-recover scenario, once per `OutagePolicy`: the thread with the highest ID
+// is down from the start of the run until its recovery, 300 ms later, while node 0 reliably broadcasts
+// instances 0 and 1 and the thread broadcasts instance 2, while it is down. On recovery, the thread must
+// deliver instances 0 and 1, from the frames its gate buffered or, when they were dropped, from the
+// Echoes and Votes the other nodes re-send it to catch it up. Its own broadcast must reach the other nodes
+// once it recovers if it was buffered, and never if it was dropped.
+// # Parameters:
+// * `config` - the configuration of the reliable clusters, run once per outage policy; it must tolerate one crashed thread.
+// # Returns
+// * `true` if, under both policies, every node delivered instances 0 and 1, the recovering thread only
+//   after its recovery, and the other nodes delivered instance 2 exactly when it was buffered.
+async fn simulate_crash_recover(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let recovering = thread_count - 1;
+    let recover_at = Duration::from_millis(300);
+    let mut passed = true;
+    for policy in [OutagePolicy::Buffer, OutagePolicy::Drop] {
+        let config = config.clone().with_fault(Fault::Outage(recovering, Outage::new(Duration::ZERO, recover_at, policy)));
+        let (transmitters, receivers) = create_channels(&config);
+        let start = Instant::now();
+        let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(transmitters, receivers, config);
+
+        let mut handles = vec![];
+        for id in 0..thread_count {
+            let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = reliable_communicator.initialize_reliable_handle();
+                if id == 0 {
+                    reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+                    reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+                }
+                if id == recovering {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(2), Round(0)).await;
+                }
+                let mut delivered = true;
+                for instance_number in 0..2 {
+                    delivered &= tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(instance_number), Round(0))).await.is_ok();
+                }
+                let delivered_at = start.elapsed();
+                let recovered_broadcast = if id == recovering {
+                    None
+                } else {
+                    Some(tokio::time::timeout(Duration::from_secs(1), reliable_communicator.reliable_recv(Some(recovering), Instance(2), Round(0))).await.is_ok())
+                };
+                reliable_communicator.terminate_reliable_handle(reliable_handle);
+                println!("id: {id}, delivered instances 0 and 1 {delivered} after {delivered_at:?}, delivered instance 2 {recovered_broadcast:?}");
+                (delivered, delivered_at, recovered_broadcast)
+            }));
+        }
+
+        let mut scenario_passed = true;
+        for (id, result) in join_all(handles).await.into_iter().enumerate() {
+            let Ok((delivered, delivered_at, recovered_broadcast)) = result else {
+                scenario_passed = false;
+                continue
+            };
+            scenario_passed &= delivered && match recovered_broadcast {
+                None => delivered_at >= recover_at,
+                Some(recovered_broadcast) => recovered_broadcast == (policy == OutagePolicy::Buffer),
+            };
+            if id as u32 == recovering {
+                println!("crash-and-recover scenario, {policy:?}: thread {id} caught up {:?} after its recovery", delivered_at.saturating_sub(recover_at));
+            }
+        }
+        println!("crash-and-recover scenario, {policy:?}: {}", if scenario_passed { "passed" } else { "failed" });
+        passed &= scenario_passed;
+    }
+    passed
+}
+
+// # Function Description:
+// This function runs the collect window scenario: every node broadcasts a value in each of three witness
+// rounds, then collects the rounds as one window. The window must hold the values of every round, in
+// round order, and a reducer counting the values of every round over the same window must find a quorum
+// in each of them.
+// # Parameters:
+// * `config` - the configuration of the witness cluster, giving the validity threshold every round must reach.
+// # Returns
+// * `true` if every node collected the window it expected.
+async fn simulate_collect_window(config: ClusterConfig) -> bool {
+    let window_len = 3;
+    let validity_threshold = config.get_thresholds().get_validity_threshold() as usize;
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let mut handles = vec![];
+    for id in 0..config.get_thread_count() {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            for round_number in 0..window_len {
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id} in round {round_number}"), Round(round_number)).await;
+            }
+            let window = tokio::time::timeout(Duration::from_secs(5), witness_communicator.witness_collect_window(Round(0), window_len)).await;
+            let counts = witness_communicator.witness_reduce_window(Round(0), window_len, vec![0; window_len as usize], |mut counts, value| {
+                counts[value.get_round_number().0 as usize] += 1;
+                counts
+            }).await;
+            let mut rounds = vec![];
+            for round_number in 0..window_len {
+                rounds.extend(witness_communicator.witness_collect(Round(round_number)).await);
+            }
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+
+            let Ok(window) = window else {
+                println!("id: {id}, the window was never collected");
+                return false
+            };
+            println!("id: {id}, collected {} values over {window_len} rounds, {counts:?} per round", window.len());
+            window == rounds && counts.iter().all(|count| *count >= validity_threshold)
+        }));
+    }
+    let passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("collect window scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function summarizes deliveries of any protocol, as a generic experiment harness would.
+// # Parameters:
+// * `deliveries` - The deliveries of a node.
+// # Returns
+// * The number of delivered values, by protocol.
+fn summarize_deliveries(deliveries: &[Delivered<String>]) -> BTreeMap<String, usize> {
+    let mut summary = BTreeMap::new();
+    for delivered in deliveries {
+        *summary.entry(delivered.get_protocol().clone()).or_insert(0) += delivered.get_values().len();
+    }
+    summary
+}
+
+// # Function Description:
+// This function runs the delivered scenario: every node receives a basic message, a reliable instance, a
+// witness round, and an aggregated witness round as `Delivered` envelopes, summarizes them with the same
+// code whatever their protocol, and checks that each envelope converts back to the shape the protocol's
+// own call returns.
+// # Parameters:
+// * `config` - the configuration of the witness cluster.
+// # Returns
+// * `true` if every node received the expected envelopes.
+async fn simulate_delivered(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let validity_threshold = config.get_thresholds().get_validity_threshold() as usize;
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+    let (transmitters, receivers) = create_channels(&config);
+    let mut aggregated_witness_hub = AggregatedWitnessHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let mut aggregated_witness_communicator: AggregatedWitnessCommunicator<String> = aggregated_witness_hub.create_aggregated_witness_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            let aggregated_reliable_handle = aggregated_witness_communicator.initialize_reliable_handle();
+            let aggregated_witness_handle = aggregated_witness_communicator.initialize_witness_handle();
+            if id == 0 {
+                witness_communicator.basic_broadcast(String::from("basic broadcast message by 0"), Round(0)).await;
+                witness_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+            }
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            aggregated_witness_communicator.aggregated_witness_broadcast(format!("aggregated witness broadcast message by {id}"), Round(0)).await;
+
+            let deliveries = vec![
+                witness_communicator.basic_recv_delivered(Some(0), Round(0)).await,
+                witness_communicator.reliable_recv_delivered(Some(0), Instance(1), Round(0)).await,
+                witness_communicator.witness_collect_delivered(Round(0)).await,
+                aggregated_witness_communicator.aggregated_witness_collect_delivered(Round(0)).await,
+            ];
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            aggregated_witness_communicator.terminate_witness_handle(aggregated_witness_handle);
+            aggregated_witness_communicator.terminate_reliable_handle(aggregated_reliable_handle);
+
+            let summary = summarize_deliveries(&deliveries);
+            println!("id: {id}, delivered values by protocol: {summary:?}");
+            let reliable = deliveries[1].clone();
+            let single = deliveries[..2].iter().all(|delivered| delivered.get_origin() == Some(0) && delivered.get_message().is_some());
+            let collections = deliveries[2..].iter().all(|delivered| delivered.get_origin().is_none() && delivered.get_values().len() >= validity_threshold);
+            let receivers = deliveries.iter().all(|delivered| delivered.get_metadata().get_receiver() == id && delivered.get_round() == Round(0));
+            let converted = reliable.get_instance() == Some(Instance(1)) && reliable.into_messages()
+                == vec![Message::new(String::from("reliable"), 0, String::from("reliable broadcast message by 0"), None, Some(Instance(1)), Round(0))];
+            single && collections && receivers && converted
+        }));
+    }
+    let passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    println!("delivered scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the binary agreement scenario: every node proposes the same bit for a first instance,
+// which must be decided, and a bit depending on its ID for a second instance, on which the nodes must agree.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster carrying the votes.
+// # Returns
+// * `true` if every node decided the unanimous bit of the first instance and all nodes decided the same
+//   bit for the second one.
+async fn simulate_binary_agreement(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub: ReliableHub<BinaryVote> = ReliableHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut binary_agreement = BinaryAgreement::new(reliable_hub.create_reliable_communicator());
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = binary_agreement.get_communicator().initialize_reliable_handle();
+            binary_agreement.propose(true, Instance(1)).await;
+            binary_agreement.propose(id % 2 == 0, Instance(2)).await;
+            let unanimous = binary_agreement.decide(Instance(1)).await;
+            let split = binary_agreement.decide(Instance(2)).await;
+            binary_agreement.get_communicator().terminate_reliable_handle(reliable_handle);
+            println!("id: {id}, binary agreement decisions: {:?}", binary_agreement.get_decisions());
+            (unanimous, split)
+        }));
+    }
+    let decisions: Vec<(bool, bool)> = join_all(handles).await.into_iter().map(|result| result.unwrap()).collect();
+    let passed = decisions.iter().all(|decision| decision.0) && decisions.iter().all(|decision| decision.1 == decisions[0].1);
+    println!("binary agreement scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the workload of the protocol study over one protocol: in every round, every node
+// broadcasts a payload of the same size and waits until it received the round, by receiving the message
+// of every node (basic and reliable broadcast) or by collecting the round (the other protocols).
+// # Parameters:
+// * `protocol` - the protocol to run: "basic", "reliable", "witness", "aggregated_witness", or "barycentric".
+// * `config` - the configuration of the studied cluster.
+// * `rounds` - the number of rounds of the workload.
+// # Returns
+// * the elapsed time of the workload, the latencies of the rounds at every node, the number of values
+//   delivered, and the frames and bytes received by the cluster, or `None` if the workload did not finish.
+async fn run_study_workload(protocol: &str, config: ClusterConfig, rounds: u32) -> Option<(Duration, LatencyHistogram, u64, u64, u64)> {
+    let thread_count = config.get_thread_count();
+    let payload = |id: u32, round_number: u32| format!("study payload {id:04} {round_number:04}");
+    let (transmitters, receivers) = create_channels(&config);
+    let start = Instant::now();
+    let mut handles: Vec<JoinHandle<(LatencyHistogram, u64, u64, u64)>> = vec![];
+
+    if protocol == "basic" {
+        let mut hub: BasicHub<String> = BasicHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_basic_communicator();
+            handles.push(tokio::spawn(async move {
+                let (mut latencies, mut delivered, mut bytes) = (LatencyHistogram::new(), 0, 0);
+                for round_number in 0..rounds {
+                    let round_start = Instant::now();
+                    communicator.basic_broadcast(payload(id, round_number), Round(round_number)).await;
+                    for sender in 0..thread_count {
+                        bytes += communicator.basic_recv(Some(sender), Round(round_number)).await.write_json().len() as u64;
+                        delivered += 1;
+                    }
+                    latencies.record(round_start.elapsed());
+                }
+                (latencies, delivered, delivered, bytes)
+            }));
+        }
+    } else if protocol == "reliable" {
+        let mut hub: ReliableHub<String> = ReliableHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_reliable_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let (mut latencies, mut delivered) = (LatencyHistogram::new(), 0);
+                for round_number in 0..rounds {
+                    let round_start = Instant::now();
+                    communicator.reliable_broadcast(payload(id, round_number), Instance(id), Round(round_number)).await;
+                    for sender in 0..thread_count {
+                        communicator.reliable_recv(Some(sender), Instance(sender), Round(round_number)).await;
+                        delivered += 1;
+                    }
+                    latencies.record(round_start.elapsed());
+                }
+                communicator.terminate_reliable_handle(reliable_handle);
+                let (frames, bytes) = count_traffic(&communicator.metrics_report());
+                (latencies, delivered, frames, bytes)
+            }));
+        }
+    } else if protocol == "witness" {
+        let mut hub: WitnessHub<String> = WitnessHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let witness_handle = communicator.initialize_witness_handle();
+                let (mut latencies, mut delivered) = (LatencyHistogram::new(), 0);
+                for round_number in 0..rounds {
+                    let round_start = Instant::now();
+                    communicator.witness_broadcast(payload(id, round_number), Round(round_number)).await;
+                    delivered += communicator.witness_collect(Round(round_number)).await.len() as u64;
+                    latencies.record(round_start.elapsed());
+                }
+                communicator.terminate_witness_handle(witness_handle);
+                communicator.terminate_reliable_handle(reliable_handle);
+                let (frames, bytes) = count_traffic(&communicator.metrics_report());
+                (latencies, delivered, frames, bytes)
+            }));
+        }
+    } else if protocol == "aggregated_witness" {
+        let mut hub: AggregatedWitnessHub<String> = AggregatedWitnessHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_aggregated_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let witness_handle = communicator.initialize_witness_handle();
+                let (mut latencies, mut delivered) = (LatencyHistogram::new(), 0);
+                for round_number in 0..rounds {
+                    let round_start = Instant::now();
+                    communicator.aggregated_witness_broadcast(payload(id, round_number), Round(round_number)).await;
+                    delivered += communicator.aggregated_witness_collect(Round(round_number)).await.len() as u64;
+                    latencies.record(round_start.elapsed());
+                }
+                communicator.terminate_witness_handle(witness_handle);
+                communicator.terminate_reliable_handle(reliable_handle);
+                let (frames, bytes) = count_traffic(&communicator.metrics_report());
+                (latencies, delivered, frames, bytes)
+            }));
+        }
+    } else {
+        let mut hub: BarycentricHub<String> = BarycentricHub::with_config(transmitters, receivers, config);
+        for id in 0..thread_count {
+            let mut communicator = hub.create_barycentric_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = communicator.initialize_reliable_handle();
+                let barycentric_handle = communicator.initialize_barycentric_handle();
+                let (mut latencies, mut delivered) = (LatencyHistogram::new(), 0);
+                for round_number in 0..rounds {
+                    let round_start = Instant::now();
+                    communicator.barycentric_agreement(payload(id, round_number), Round(round_number)).await;
+                    delivered += communicator.barycentric_collect(Round(round_number)).await.len() as u64;
+                    latencies.record(round_start.elapsed());
+                }
+                communicator.terminate_barycentric_handle(barycentric_handle);
+                communicator.terminate_reliable_handle(reliable_handle);
+                let (frames, bytes) = count_traffic(&communicator.metrics_report());
+                (latencies, delivered, frames, bytes)
+            }));
+        }
+    }
+
+    let results = tokio::time::timeout(Duration::from_secs(60), join_all(handles)).await.ok()?;
+    let elapsed = start.elapsed();
+    let mut latencies = LatencyHistogram::new();
+    let (mut delivered, mut frames, mut bytes) = (0, 0, 0);
+    for result in results {
+        let (node_latencies, node_delivered, node_frames, node_bytes) = result.ok()?;
+        latencies.merge(&node_latencies);
+        delivered += node_delivered;
+        frames += node_frames;
+        bytes += node_bytes;
+    }
+    Some((elapsed, latencies, delivered, frames, bytes))
+}
+
+// # Function Description:
+// This function sums the frames and bytes received by a node, over every protocol.
+// # Parameters:
+// * `report` - the metrics report of the node.
+// # Returns
+// * the number of frames and bytes received.
+fn count_traffic(report: &MetricsReport) -> (u64, u64) {
+    report.get_traffic().values().fold((0, 0), |(frames, bytes), traffic| (frames + traffic.get_frames(), bytes + traffic.get_bytes()))
+}
+
+// # Function Description:
+// This function runs the protocol study: the same workload is run over basic, reliable, witness,
+// aggregated witness, and barycentric clusters of the same size, and their latencies, throughput, and
+// traffic are printed side by side, so that the cost of every guarantee can be compared before choosing
+// the protocol to build on. The traffic of basic communication, which has no handle to count it, is
+// counted from the messages it received.
+// # Parameters:
+// * `config` - the configuration of the cluster run for every protocol.
+// # Returns
+// * `true` if the workload finished over every protocol.
+async fn simulate_protocol_study(config: ClusterConfig) -> bool {
+    let rounds = 5;
+    let mut rows = vec![];
+    for protocol in ["basic", "reliable", "witness", "aggregated_witness", "barycentric"] {
+        rows.push(match run_study_workload(protocol, config.clone(), rounds).await {
+            Some((elapsed, latencies, delivered, frames, bytes)) => {
+                let throughput = delivered as f64 / elapsed.as_secs_f64();
+                format!("{:<20} {:>12} {:>12} {:>12} {:>12.0} {:>10} {:>12}", protocol, format!("{elapsed:.2?}"),
+                    format!("{:.2?}", latencies.percentile(50.0)), format!("{:.2?}", latencies.percentile(99.0)), throughput, frames, bytes)
+            },
+            None => format!("{protocol:<20} did not finish"),
+        });
+    }
+
+    println!("protocol study, {} nodes, {rounds} rounds:", config.get_thread_count());
+    println!("{:<20} {:>12} {:>12} {:>12} {:>12} {:>10} {:>12}", "protocol", "elapsed", "p50 round", "p99 round", "values/s", "frames", "bytes");
+    for row in &rows {
+        println!("{row}");
+    }
+    rows.iter().all(|row| !row.ends_with("did not finish"))
+}
+
+// # Function Description:
+// This function runs the codec scenario: a signal carrying a message is encoded and decoded in every wire
+// format, a bincode message frame must not decode as a signal, and the witness workload of the protocol
+// study is run over clusters using each format, whose elapsed time, throughput, and traffic are printed
+// side by side.
+// # Parameters:
+// * `config` - the configuration of the witness clusters, run once per codec.
+// # Returns
+// * `true` if every frame round-trips, the mistyped frame is rejected, and the workload finished in every format.
+async fn simulate_codecs(config: ClusterConfig) -> bool {
+    let rounds = 5;
+    let codecs = [Codec::Json, Codec::Bincode, Codec::Cbor];
+    let message = Message::new(String::from("reliable"), 0, String::from("codec payload"), None, Some(Instance(0)), Round(0));
+    let signal = Signal::new(SignalType::Input, ObjectContent::Message(message.clone()), Instance(0), Round(0));
+    let mut passed = true;
+    for codec in codecs {
+        let frame = signal.write_frame(codec);
+        let round_trip = Signal::<String>::read_frame(codec, &frame).is_ok_and(|decoded| decoded.write_json() == signal.write_json());
+        println!("{codec:?}: signal frame of {} bytes, round trip {}", frame.len(), if round_trip { "ok" } else { "failed" });
+        passed &= round_trip;
+    }
+    let mistyped = Signal::<String>::read_frame(Codec::Bincode, &message.write_frame(Codec::Bincode)).is_err();
+    let mismatched = Message::<String>::read_frame(Codec::Bincode, &message.write_frame(Codec::Json)).is_err();
+    println!("bincode message frame decoded as a signal: {}", if mistyped { "rejected" } else { "accepted" });
+    println!("JSON message frame decoded as bincode: {}", if mismatched { "rejected" } else { "accepted" });
+    passed &= mistyped && mismatched;
+    // rounds and instances are newtypes, but frames must keep carrying them as bare numbers
+    let numbered = Message::new(String::from("reliable"), 0, String::from("codec payload"), None, Some(Instance(7)), Round(3)).write_json();
+    let bare_numbers = numbered.contains("\"instance_number\":7") && numbered.contains("\"round_number\":3");
+    println!("instance and round encoded as bare numbers: {bare_numbers}");
+    passed &= bare_numbers;
+
+    let mut rows = vec![];
+    for codec in codecs {
+        rows.push(match run_study_workload("witness", config.clone().with_codec(codec), rounds).await {
+            Some((elapsed, _, delivered, frames, bytes)) => {
+                let throughput = delivered as f64 / elapsed.as_secs_f64();
+                format!("{:<10} {:>12} {:>12.0} {:>10} {:>12}", format!("{codec:?}"), format!("{elapsed:.2?}"), throughput, frames, bytes)
+            },
+            None => format!("{:<10} did not finish", format!("{codec:?}")),
+        });
+    }
+
+    println!("witness workload, {} nodes, {rounds} rounds:", config.get_thread_count());
+    println!("{:<10} {:>12} {:>12} {:>10} {:>12}", "codec", "elapsed", "values/s", "frames", "bytes");
+    for row in &rows {
+        println!("{row}");
+    }
+    passed &= rows.iter().all(|row| !row.ends_with("did not finish"));
+    println!("codec scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the retention scenario. A reliable cluster keeping at most `capacity` delivered
+// instances per handle (`RetentionPolicy::Capacity`) delivers a stream of broadcasts by node 0, then every
+// node is sent a late Echo of the first instance: its handles must hold no more than `capacity` delivered
+// instances, and must ignore the late Echo instead of opening the collected instance again. A witness
+// cluster dropping delivered rounds after a grace period (`RetentionPolicy::GracePeriod`) then runs a few
+// rounds, waits past the grace period, and runs one more: the first rounds must no longer be inspectable
+// nor subscribable, while the last one still is.
+// # Parameters:
+// * `config` - the configuration of the reliable and witness clusters, each run with its own retention policy.
+// # Returns
+// * `true` if every node delivered every instance and round, and collected the ones it completed first.
+async fn simulate_retention(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let capacity = 4;
+    let instances = 16;
+    let config = config.with_retention_policy(RetentionPolicy::Capacity(capacity));
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters.clone(), receivers, config.clone());
+    let barrier = Arc::new(Barrier::new(thread_count as usize));
+    let message = Message::new(String::from("reliable"), 0, String::from("late message"), None, Some(Instance(0)), Round(0));
+    let mut late_echo: serde_json::Value = serde_json::from_str(&Signal::new(SignalType::Echo, ObjectContent::Message(message), Instance(0), Round(0)).write_json()).unwrap();
+    late_echo["origin"] = 1.into();
+    let late_echo = forged_signal_frame(&late_echo, config.get_codec());
+
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let transmitter = transmitters[id as usize].clone();
+        let barrier = barrier.clone();
+        let late_echo = late_echo.clone();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            let mut delivered = 0;
+            for instance_number in 0..instances {
+                if id == 0 {
+                    reliable_communicator.reliable_broadcast(format!("reliable broadcast message {instance_number} by 0"), Instance(instance_number), Round(0)).await;
+                }
+                if tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(instance_number), Round(0))).await.is_ok() {
+                    delivered += 1;
+                }
+            }
+            // the late Echo precedes the frames of the last instance on the node's lane, so it is processed first
+            let _ = transmitter.send(late_echo).await;
+            barrier.wait().await;
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(format!("reliable broadcast message {instances} by 0"), Instance(instances), Round(0)).await;
+            }
+            if tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(instances), Round(0))).await.is_ok() {
+                delivered += 1;
+            }
+            let snapshots = reliable_communicator.reliable_inspect().await.unwrap_or_default();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            let kept = snapshots.values().filter(|snapshot| snapshot.get_delivered()).count();
+            let reopened = snapshots.contains_key(&format!("{id}::reliable::0::message::0::0"));
+            let last_kept = snapshots.contains_key(&format!("{id}::reliable::0::message::{instances}::0"));
+            println!("id: {id}, reliable: {delivered} instances delivered, {kept} kept, first instance reopened {reopened}, last instance kept {last_kept}");
+            delivered == instances + 1 && kept <= capacity + 1 && !reopened && last_kept
+        }));
+    }
+    let reliable_passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+
+    let rounds = 4;
+    let grace_period = Duration::from_millis(100);
+    let config = config.with_retention_policy(RetentionPolicy::GracePeriod(grace_period));
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            for round_number in 0..rounds {
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(round_number)).await;
+                witness_communicator.witness_collect(Round(round_number)).await;
+            }
+            tokio::time::sleep(grace_period * 2).await;
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(rounds)).await;
+            let collected = witness_communicator.witness_collect(Round(rounds)).await;
+            let first_round = witness_communicator.witness_inspect(Round(0)).await.ok().flatten();
+            let last_round = witness_communicator.witness_inspect(Round(rounds)).await.ok().flatten();
+            let subscription = witness_communicator.witness_subscribe(Round(0)).await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            let last_kept = last_round.is_some_and(|round| round.get_delivered());
+            println!("id: {id}, witness: first round kept {}, subscribable {}, last round kept {last_kept}", first_round.is_some(), subscription.is_ok());
+            collected.len() as u32 >= thread_count - 1 && first_round.is_none() && subscription.is_err() && last_kept
+        }));
+    }
+    let witness_passed = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    let passed = reliable_passed && witness_passed;
+    println!("retention scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the lane autoscaling scenario: a burst of signals reaches node 0 of a reliable cluster
+// with small buffers before its reliable handle reads its `Signal` lane, then the cluster runs a reliable
+// broadcast. Under `LaneScaling::Overflow`, the burst is absorbed silently; under `LaneScaling::Autoscale`,
+// node 0 must record the doubling of its `Signal` lane up to a capacity holding the whole burst. Every
+// node must deliver the broadcast in both modes.
+// # Parameters:
+// * `config` - the configuration of the reliable clusters, run with buffers of 8 frames once per lane scaling setting.
+// # Returns
+// * `true` if the broadcast was delivered in both modes, and the resizes were recorded only when autoscaling.
+async fn simulate_autoscale(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let buffer_size = 8;
+    let burst = 100;
+    let message = Message::new(String::from("reliable"), 1, String::from("burst message"), None, Some(Instance(0)), Round(0));
+    let mut echo: serde_json::Value = serde_json::from_str(&Signal::new(SignalType::Echo, ObjectContent::Message(message), Instance(7), Round(0)).write_json()).unwrap();
+    echo["origin"] = 1.into();
+    let echo = forged_signal_frame(&echo, config.get_codec());
+    let mut passed = true;
+
+    for lane_scaling in [LaneScaling::Overflow, LaneScaling::Autoscale] {
+        let config = config.clone().with_buffer_size(buffer_size).with_lane_scaling(lane_scaling);
+        let (transmitters, receivers) = create_channels(&config);
+        let injector = transmitters[0].clone();
+        let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);
+        for _ in 0..burst {
+            let _ = injector.send(echo.clone()).await;
+        }
+
+        let mut handles = vec![];
+        for id in 0..thread_count {
+            let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = reliable_communicator.initialize_reliable_handle();
+                if id == 0 {
+                    reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+                }
+                let delivered = tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0))).await.is_ok();
+                reliable_communicator.terminate_reliable_handle(reliable_handle);
+                (delivered, reliable_communicator.metrics_report().get_lane_resizes().clone())
+            }));
+        }
+        let results: Vec<(bool, Vec<LaneResize>)> = join_all(handles).await.into_iter().map(|result| result.unwrap_or((false, vec![]))).collect();
+        let delivered = results.iter().all(|(delivered, _)| *delivered);
+        let resizes = &results[0].1;
+        for resize in resizes {
+            println!("{lane_scaling:?}: node 0 grew its {:?} lane from {} to {} frames, with {} frames waiting after {:.2?}",
+                resize.get_lane(), resize.get_from(), resize.get_to(), resize.get_backlog(), resize.get_elapsed());
+        }
+        let recorded = match lane_scaling {
+            LaneScaling::Overflow => results.iter().all(|(_, resizes)| resizes.is_empty()),
+            LaneScaling::Autoscale => !resizes.is_empty()
+                && resizes.iter().all(|resize| resize.get_lane() == Lane::Signal && resize.get_to() >= resize.get_backlog() && resize.get_to() > resize.get_from())
+                && resizes.last().is_some_and(|resize| resize.get_to() >= burst),
+        };
+        println!("{lane_scaling:?}: {} resizes recorded by node 0, broadcast {}", resizes.len(), if delivered { "delivered by every node" } else { "not delivered" });
+        passed &= delivered && recorded;
+    }
+    println!("autoscale scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs the quorum scenario. The thresholds of the cluster are printed under every quorum
+// formula, then reliable broadcast runs with one node never started: with `n - f` quorums the others must
+// deliver, which the default `n - f + 1` quorums cannot guarantee. With weighted `n - f` quorums where node
+// 0 weighs as much as every other node together, the others must deliver without one light node, and must
+// not without node 0. An unsatisfiable quorum configuration must be rejected.
+async fn simulate_quorum(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    for (name, formula) in [("n - f + 1", QuorumFormula::NMinusFPlusOne), ("n - f", QuorumFormula::NMinusF)] {
+        if let Ok(thresholds) = QuorumConfig::new().with_formula(formula).thresholds(thread_count) {
+            println!("{name}: f = {}, validity threshold {}, agreement threshold {}", thresholds.get_faulty_threads(), thresholds.get_validity_threshold(), thresholds.get_agreement_threshold());
+        }
+    }
+
+    let bracha = QuorumConfig::new().with_formula(QuorumFormula::NMinusF);
+    let bracha_passed = quorum_delivers(config.clone().with_quorum_config(bracha).unwrap_or_else(|error| panic!("Error: {error}")), thread_count - 1).await;
+    println!("n - f quorums, node {} down: delivered {bracha_passed}", thread_count - 1);
+
+    let mut weights = vec![1; thread_count as usize];
+    weights[0] = thread_count as u64 - 1;
+    let weighted = QuorumConfig::new().with_formula(QuorumFormula::NMinusF).with_weights(weights.clone());
+    let light_passed = quorum_delivers(config.clone().with_quorum_config(weighted.clone()).unwrap_or_else(|error| panic!("Error: {error}")), thread_count - 1).await;
+    let heavy_passed = !quorum_delivers(config.clone().with_quorum_config(weighted).unwrap_or_else(|error| panic!("Error: {error}")), 0).await;
+    println!("weights {weights:?}: delivered without node {} {light_passed}, without node 0 {}", thread_count - 1, !heavy_passed);
+
+    let invalid = config.clone().with_quorum_config(QuorumConfig::new().with_weights(vec![1; thread_count as usize + 1]));
+    let invalid_passed = invalid.is_err();
+    println!("{} weights for {thread_count} threads: {:?}", thread_count + 1, invalid.err());
+
+    let passed = bracha_passed && light_passed && heavy_passed && invalid_passed;
+    println!("quorum scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs one reliable broadcast instance in a cluster where one node is never started.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * absent - The ID of the node that is never started.
+//
+// # Returns:
+// * `true` if every started node delivered the instance within a second.
+async fn quorum_delivers(config: ClusterConfig, absent: u32) -> bool {
+    let thread_count = config.get_thread_count();
+    let origin = if absent == 1 { 2 } else { 1 };
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    let mut absent_communicators = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        if id == absent {
+            absent_communicators.push(reliable_communicator);
+            continue
+        }
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == origin {
+                reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(0), Round(0)).await;
+            }
+            let delivered = tokio::time::timeout(Duration::from_secs(1), reliable_communicator.reliable_recv(Some(origin), Instance(0), Round(0))).await.is_ok();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            delivered
+        }));
+    }
+    let delivered = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    drop(absent_communicators);
+    delivered
+}
+
+// # Function Description:
+// This function runs the trace sampling scenario. Node 0 reliably broadcasts a batch of instances, once
+// with full tracing and once tracing 10% of the instances. With full tracing, every node must record the
+// round status of every instance; with sampling, every node must record the same, strict subset of the
+// instances, with one phase latency per phase and sampled instance, and the delivery records of the
+// sampled instances must still form a clean trace.
+async fn simulate_trace_sampling(config: ClusterConfig) -> bool {
+    let instances = 200;
+    let (full, _) = trace_sampling_run(config.clone().with_trace_sampling(TraceSampling::Full), instances).await;
+    let full_passed = full.iter().all(|(traced, latencies)| traced.len() as u32 == instances && *latencies == instances as u64);
+    println!("full tracing: traced {:?} instances", full.iter().map(|(traced, _)| traced.len()).collect::<Vec<_>>());
+
+    let config = config.with_trace_sampling(TraceSampling::fraction(0.1));
+    let thread_count = config.get_thread_count();
+    let (sampled, records) = trace_sampling_run(config, instances).await;
+    let traced = sampled[0].0.len() as u32;
+    let consistent = sampled.iter().all(|(other, latencies)| *other == sampled[0].0 && *latencies == traced as u64);
+    let trace = check_trace(write_trace(&records).as_bytes(), TraceChecker::new(thread_count));
+    let trace_passed = matches!(&trace, Ok((checked, violations)) if *checked == (traced * thread_count) as u64 && violations.is_empty());
+    println!("10% tracing: traced {traced} of {instances} instances on every node {consistent}, {} delivery records checked {:?}",
+        records.len(), trace.map(|(_, violations)| violations.len()));
+
+    let passed = full_passed && consistent && traced > 0 && traced < instances / 4 && trace_passed;
+    println!("trace sampling scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs a batch of reliable broadcast instances from node 0 for the trace sampling scenario.
+//
+// # Parameters:
+// * config - The configuration of the cluster, with its trace sampling.
+// * instances - The number of instances.
+//
+// # Returns:
+// * For every node, the instances whose round status it recorded (without its own ID) and the number of
+//   input→echo-quorum latencies it recorded, and the sampled delivery records of every node.
+async fn trace_sampling_run(config: ClusterConfig, instances: u32) -> (Vec<(BTreeSet<String>, u64)>, Vec<DeliveryRecord>) {
+    let thread_count = config.get_thread_count();
+    let trace_sampler = config.get_trace_sampler();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            let mut records = vec![];
+            for instance_number in 0..instances {
+                if id == 0 {
+                    reliable_communicator.reliable_broadcast(format!("reliable broadcast message {instance_number} by 0"), Instance(instance_number), Round(0)).await;
+                }
+                let record = DeliveryRecord::from_message(id, &reliable_communicator.reliable_recv(Some(0), Instance(instance_number), Round(0)).await);
+                if trace_sampler.is_sampled_record(&record) {
+                    records.push(record);
+                }
+            }
+            let report = reliable_communicator.metrics_report();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            let traced: BTreeSet<String> = report.get_round_statuses().iter()
+                .filter_map(|round_status| round_status.get_instance()?.split_once("::").map(|(_, key)| key.to_string()))
+                .collect();
+            let latencies = report.get_phase_latencies().get("reliable").map_or(0, |histogram| histogram.get_input_to_echo_quorum().get_count());
+            ((traced, latencies), records)
+        }));
+    }
+    let mut traced = vec![];
+    let mut records = vec![];
+    for handle in handles {
+        let (node_traced, node_records) = handle.await.unwrap();
+        traced.push(node_traced);
+        records.extend(node_records);
+    }
+    (traced, records)
+}
+
+// # Function Description:
+// This function runs the stake scenario: hubs are built with weighted `n - f` quorums in which node 0
+// weighs as much as every other node together. Reliable broadcast, a witness round, and an aggregated
+// witness round must complete without one light node, whose aggregation proof must verify against the
+// weights, and must not complete without node 0, although a majority of the nodes is up.
+async fn simulate_stake(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let light = thread_count - 1;
+    let mut weights = vec![1; thread_count as usize];
+    weights[0] = thread_count as u64 - 1;
+    let config = config.with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap_or_else(|error| panic!("Error: {error}"));
+    let weighted = config.clone().with_quorum_config(config.get_quorum_config().clone().with_weights(weights.clone())).unwrap_or_else(|error| panic!("Error: {error}"));
+    println!("weights {weights:?}: validity threshold {} of {}", weighted.get_thresholds().get_validity_threshold(), weights.iter().sum::<u64>());
+
+    let reliable_passed = quorum_delivers(weighted.clone(), light).await && !quorum_delivers(weighted, 0).await;
+    println!("reliable: delivered without node {light}, not without node 0: {reliable_passed}");
+    let witness_passed = stake_witness_delivers(config.clone(), weights.clone(), light).await && !stake_witness_delivers(config.clone(), weights.clone(), 0).await;
+    println!("witness: delivered without node {light}, not without node 0: {witness_passed}");
+    let aggregated_passed = stake_aggregated_delivers(config.clone(), weights.clone(), light).await && !stake_aggregated_delivers(config, weights, 0).await;
+    println!("aggregated witness: delivered with a valid proof without node {light}, not without node 0: {aggregated_passed}");
+
+    let passed = reliable_passed && witness_passed && aggregated_passed;
+    println!("stake scenario: {}", if passed { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function runs one witness round over a hub with weighted quorums, where one node is never started.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * weights - The weight of every node.
+// * absent - The ID of the node that is never started.
+//
+// # Returns:
+// * `true` if every started node delivered the round within a second.
+async fn stake_witness_delivers(config: ClusterConfig, weights: Vec<u64>, absent: u32) -> bool {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = match WitnessHub::with_weights(transmitters, receivers, config, weights) {
+        Ok(witness_hub) => witness_hub,
+        Err(error) => {
+            println!("witness hub refused the weights: {error}");
+            return false
+        },
+    };
+    let mut handles = vec![];
+    let mut absent_communicators = vec![];
+    for id in 0..thread_count {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        if id == absent {
+            absent_communicators.push(witness_communicator);
+            continue
+        }
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            let delivered = tokio::time::timeout(Duration::from_secs(1), witness_communicator.witness_collect(Round(0))).await.is_ok();
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            delivered
+        }));
+    }
+    let delivered = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    drop(absent_communicators);
+    delivered
+}
+
+// # Function Description:
+// This function runs one aggregated witness round over a hub with weighted quorums, where one node is
+// never started.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * weights - The weight of every node.
+// * absent - The ID of the node that is never started.
+//
+// # Returns:
+// * `true` if every started node delivered the round within a second, with an aggregation proof that verifies.
+async fn stake_aggregated_delivers(config: ClusterConfig, weights: Vec<u64>, absent: u32) -> bool {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut aggregated_witness_hub = match AggregatedWitnessHub::with_weights(transmitters, receivers, config, weights) {
+        Ok(aggregated_witness_hub) => aggregated_witness_hub,
+        Err(error) => {
+            println!("aggregated witness hub refused the weights: {error}");
+            return false
+        },
+    };
+    let mut handles = vec![];
+    let mut absent_communicators = vec![];
+    for id in 0..thread_count {
+        let mut aggregated_witness_communicator: AggregatedWitnessCommunicator<String> = aggregated_witness_hub.create_aggregated_witness_communicator();
+        if id == absent {
+            absent_communicators.push(aggregated_witness_communicator);
+            continue
+        }
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = aggregated_witness_communicator.initialize_reliable_handle();
+            let witness_handle = aggregated_witness_communicator.initialize_witness_handle();
+            aggregated_witness_communicator.aggregated_witness_broadcast(format!("aggregated witness broadcast message by {id}"), Round(0)).await;
+            let delivered = tokio::time::timeout(Duration::from_secs(1), async {
+                aggregated_witness_communicator.aggregated_witness_collect(Round(0)).await;
+                aggregated_witness_communicator.aggregated_witness_proof(Round(0)).await
+            }).await;
+            aggregated_witness_communicator.terminate_witness_handle(witness_handle);
+            aggregated_witness_communicator.terminate_reliable_handle(reliable_handle);
+            delivered.is_ok_and(|proof| proof.verify().is_ok())
+        }));
+    }
+    let delivered = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+    drop(absent_communicators);
+    delivered
+}
+
+// # Function Description:
+// This function runs the capability negotiation scenario. Capabilities running other protocols must be
+// degraded to the protocols in common, and capabilities encoding frames differently refused. In process, a
+// witness report is injected as the Input of node 0 into a cluster of reliable communicators, which run no
+// protocol delivering reports: every node must discard it with `Event::CapabilityMismatch` instead of
+// panicking, and still deliver a reliable broadcast afterwards. Over TCP, a node whose peer encodes frames
+// with another codec must refuse the connection, and a node whose peer runs fewer protocols must degrade
+// it, both ends publishing `ConnectionEvent::CapabilityMismatch`.
+// # Parameters:
+// * `config` - the configuration the announced capabilities are derived from, and of the reliable cluster.
+// # Returns
+// * `true` if the negotiations, the in-process cluster, and both TCP connections behaved as expected.
+async fn simulate_capabilities(config: ClusterConfig) -> bool {
+    let witness = Capabilities::from_config(&config, &[Protocol::Reliable, Protocol::Witness]);
+    let reliable = Capabilities::from_config(&config, &[Protocol::Reliable]);
+    let bincode = Capabilities::from_config(&config.clone().with_codec(Codec::Bincode), &[Protocol::Reliable]);
+    let negotiated = matches!(witness.negotiate(&reliable), Negotiation::Degraded(agreed, _) if agreed == reliable)
+        && matches!(reliable.negotiate(&bincode), Negotiation::Refused(_))
+        && reliable.negotiate(&reliable) == Negotiation::Agreed(reliable.clone());
+    println!("witness against reliable: {:?}, reliable against bincode: {:?}", witness.negotiate(&reliable), reliable.negotiate(&bincode));
+
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let injector = transmitters.clone();
+    let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config.clone());
+    let mut handles = vec![];
+    for id in 0..thread_count {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let mut events = reliable_communicator.subscribe_events();
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            let mismatch = tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    match events.recv().await {
+                        Ok(Event::CapabilityMismatch(mismatch)) => return Some(mismatch),
+                        Ok(_) => {},
+                        Err(_) => return None,
+                    }
+                }
+            }).await.ok().flatten();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+            }
+            let delivered = tokio::time::timeout(Duration::from_secs(5), reliable_communicator.reliable_recv(Some(0), Instance(1), Round(0))).await.is_ok();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            println!("id: {id}, {mismatch:?}, delivered the reliable broadcast {delivered}");
+            mismatch.is_some_and(|mismatch| mismatch.get_peer() == 0 && !mismatch.is_refused()) && delivered
+        }));
+    }
+    let value = Message::new(String::from("witness"), 0, String::from("witness value by 0"), None, None, Round(1));
+    let report = Report::new(ReportType::Report, String::from("witness"), 0, vec![value], None, Instance(0), Round(1));
+    let injected = tag_frame(Lane::Signal, Signal::new(SignalType::Input, ObjectContent::Report(report), Instance(0), Round(1)).write_signal_frame(config.get_codec()));
+    for transmitter in &injector {
+        let _ = transmitter.send(injected.clone()).await;
+    }
+    let discarded = join_all(handles).await.into_iter().all(|result| result.unwrap_or(false));
+
+    let two_nodes = ClusterConfig::new(2);
+    let refused = match connect_capabilities(&two_nodes, &two_nodes.clone().with_codec(Codec::Bincode), &Protocol::ALL).await {
+        Ok((first, second, _)) => first.is_some_and(|mismatch| mismatch.is_refused()) && second.is_some_and(|mismatch| mismatch.is_refused()),
+        Err(error) => {
+            println!("capabilities scenario: {error}");
+            false
+        },
+    };
+    let degraded = match connect_capabilities(&two_nodes, &two_nodes, &[Protocol::Reliable]).await {
+        Ok((first, second, received)) => first.is_some_and(|mismatch| !mismatch.is_refused()) && second.is_some_and(|mismatch| !mismatch.is_refused()) && received,
+        Err(error) => {
+            println!("capabilities scenario: {error}");
+            false
+        },
+    };
+
+    let passed = negotiated && discarded && refused && degraded;
+    println!("capabilities scenario: negotiation {}, in-process mismatch {}, refused connection {}, degraded connection {}",
+        if negotiated { "passed" } else { "failed" }, if discarded { "passed" } else { "failed" }, if refused { "passed" } else { "failed" }, if degraded { "passed" } else { "failed" });
+    passed
+}
+
+// # Function Description:
+// This function connects two nodes over TCP, the second running only some protocols, and sends a frame
+// from the first to the second.
+// # Parameters:
+// * `first_config` - the cluster configuration of the first node, a cluster of two nodes.
+// * `second_config` - the cluster configuration of the second node.
+// * `second_protocols` - the protocols the second node announces.
+// # Returns
+// * the capability mismatch published by each node, if any, and whether the frame was received, or a description of the failure.
+async fn connect_capabilities(first_config: &ClusterConfig, second_config: &ClusterConfig, second_protocols: &[Protocol]) -> Result<(Option<CapabilityMismatch>, Option<CapabilityMismatch>, bool), String> {
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let first = TcpTransport::bind(0, loopback).await?;
+    let second = TcpTransport::bind(1, loopback).await?.with_protocols(second_protocols);
+    let addresses = vec![first.get_local_address(), second.get_local_address()];
+    let mut first = first.with_peer_addresses(addresses.clone());
+    let mut second = second.with_peer_addresses(addresses);
+    let mut first_events = first.subscribe_connection_events();
+    let mut second_events = second.subscribe_connection_events();
+    let first_channels = first.open(first_config).await?;
+    let mut second_channels = second.open(second_config).await?;
+    let _ = first_channels.transmitters[1].send(tag_frame(Lane::Basic, Bytes::from_static(b"frame"))).await;
+    let received = tokio::time::timeout(Duration::from_secs(1), second_channels.receivers[1].recv()).await.is_ok_and(|frame| frame.is_some());
+    let mismatch = |events: &mut broadcast::Receiver<ConnectionEvent>| std::iter::from_fn(|| events.try_recv().ok()).find_map(|event| match event {
+        ConnectionEvent::CapabilityMismatch(mismatch) => Some(mismatch),
+        _ => None,
+    });
+    let (first_mismatch, second_mismatch) = (mismatch(&mut first_events), mismatch(&mut second_events));
+    println!("first node: {first_mismatch:?}, second node: {second_mismatch:?}, frame received {received}");
+    Ok((first_mismatch, second_mismatch, received))
+}
+
+// # Function Description:
+// This function spawns a node for the divergence scenario: the node runs a witness round, waits for a few
+// digest gossips, and returns the peers it suspected of diverging.
+// # Parameters:
+// * id - the ID of the node.
+// * witness_communicator - the node's `WitnessCommunicator`.
+// * forged - the node's own `Report` lane and a forged value, slipped to the node once it collected its round, if any.
+// * settle - how long the node waits for the gossips after collecting its round.
+// # Returns
+// * `JoinHandle<Vec<(u32, Round)>>` - resolving to the peers and rounds the node suspected.
+fn create_divergence_thread(id: u32, mut witness_communicator: WitnessCommunicator<String>, forged: Option<(Sender<Bytes>, Bytes)>, settle: Duration) -> JoinHandle<Vec<(u32, Round)>> {
+    tokio::spawn(async move {
+        let mut events = witness_communicator.subscribe_events();
+        let reliable_handle = witness_communicator.initialize_reliable_handle(); 
+        let witness_handle = witness_communicator.initialize_witness_handle(); 
+        witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await; 
+        let _ = tokio::time::timeout(Duration::from_secs(5), witness_communicator.witness_collect(Round(0))).await;
+        if let Some((transmitter, forged_value)) = forged {
+            let _ = transmitter.send(forged_value).await;
+        }
+        tokio::time::sleep(settle).await;
+
+        let mut suspected = vec![];
+        while let Ok(event) = events.try_recv() {
+            if let Event::DivergenceSuspected(suspicion) = event {
+                suspected.push((suspicion.get_peer(), suspicion.get_round_number()));
+            }
+        }
+        suspected.sort();
+        witness_communicator.terminate_witness_handle(witness_handle);
+        witness_communicator.terminate_reliable_handle(reliable_handle);
+        suspected
+    })
+}
+
+// # Function Description:
+// This function runs the divergence scenarios under digest gossip: in a healthy cluster no node is
+// suspected, and once a forged value of round 0 is slipped to node 0 only, node 0 suspects every peer of
+// diverging on round 0, and every peer suspects node 0 alone. The forged value is slipped once node 0
+// collected the round, as a value lost or corrupted after delivery would be.
+// # Parameters:
+// * `config` - the configuration of the reliable cluster, which gossips its digests every 50 ms.
+// # Returns
+// * `true` if every scenario passed.
+async fn simulate_divergence(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let interval = Duration::from_millis(50);
+    let bound = 3;
+    let config = config.with_digest_gossip(DigestGossip::Periodic(interval, bound));
+    let mut passed = true;
+
+    for forged in [false, true] {
+        let (transmitters, receivers) = create_channels(&config);
+        let forged_value = Message::new(String::from("witness"), 1, String::from("forged value"), None, Some(Instance(0)), Round(0));
+        let forged_frame = (transmitters[0].clone(), tag_frame(Lane::Report, forged_value.write_frame(config.get_codec())));
+        let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config.clone());
+        let mut handles = vec![];
+        for i in 0..thread_count {
+            let forged_frame = if forged && i == 0 { Some(forged_frame.clone()) } else { None };
+            handles.push(create_divergence_thread(i, witness_hub.create_witness_communicator(), forged_frame, interval * (bound + 4)));
+        }
+
+        let mut scenario_passed = true;
+        for (id, handle) in handles.into_iter().enumerate() {
+            let suspected = handle.await.unwrap_or_default();
+            let expected: Vec<(u32, Round)> = match (forged, id) {
+                (false, _) => vec![],
+                (true, 0) => (1..thread_count).map(|peer| (peer, Round(0))).collect(),
+                (true, _) => vec![(0, Round(0))],
+            };
+            if suspected != expected {
+                println!("node {id} suspected {suspected:?}, expected {expected:?}");
+                scenario_passed = false;
+            }
+        }
+        println!("divergence scenario, {}: {}", if forged { "forged value" } else { "healthy" }, if scenario_passed { "passed" } else { "failed" });
+        passed &= scenario_passed;
+    }
+    passed
+}
+
+// The C ABI of the reliable broadcast core (see include/reliable_broadcast.h), called as a foreign caller would.
+#[cfg(feature = "ffi")]
+#[repr(C)]
+struct RbAction {
+    kind: u32,
+    destination: u32,
+    frame: *const u8,
+    length: usize,
+}
+
+#[cfg(feature = "ffi")]
+unsafe extern "C" {
+    fn rb_core_new(thread_count: u32, id: u32) -> *mut std::ffi::c_void;
+    fn rb_core_free(core: *mut std::ffi::c_void);
+    fn rb_core_feed(core: *mut std::ffi::c_void, frame: *const u8, length: usize) -> i64;
+    fn rb_core_broadcast(core: *mut std::ffi::c_void, message: *const u8, length: usize, instance_number: u32, round_number: u32) -> i64;
+    fn rb_core_poll(core: *mut std::ffi::c_void, action: *mut RbAction) -> i32;
+}
+
+// # Function Description:
+// This function takes the actions a core emitted through the C ABI, queuing the frames it sent for their
+// destinations and recording the frames it delivered.
+// # Parameters:
+// * id - the ID of the core's thread.
+// * core - the core.
+// * pending - the frames to feed, with the ID of their destination.
+// * delivered - the frames delivered by every core, by thread ID.
+#[cfg(feature = "ffi")]
+fn poll_core_actions(id: u32, core: *mut std::ffi::c_void, pending: &mut std::collections::VecDeque<(u32, Vec<u8>)>, delivered: &mut [Vec<Vec<u8>>]) {
+    let mut action = RbAction { kind: 0, destination: 0, frame: std::ptr::null(), length: 0 };
+    while unsafe { rb_core_poll(core, &mut action) } == 1 {
+        let frame = unsafe { std::slice::from_raw_parts(action.frame, action.length) }.to_vec();
+        match action.kind {
+            0 => pending.push_back((action.destination, frame)),
+            _ => delivered[id as usize].push(frame),
+        }
+    }
+}
+
+// # Function Description:
+// This function runs the C ABI scenario: a core is created for every thread, every core reliably broadcasts
+// a message, and the frames the cores send are routed between them until none is left, as a simulator
+// embedding the cores would. Every core must deliver every message, and no core is created for a thread
+// outside the cluster.
+// # Parameters:
+// * `config` - gives the thread count of the cluster of cores.
+// # Returns
+// * `true` if every core delivered every message.
+#[cfg(feature = "ffi")]
+async fn simulate_ffi(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    // the cores block on their own runtimes, which cannot run inside the simulator's
+    tokio::task::spawn_blocking(move || {
+        let cores: Vec<*mut std::ffi::c_void> = (0..thread_count).map(|id| unsafe { rb_core_new(thread_count, id) }).collect();
+        let outsider = unsafe { rb_core_new(thread_count, thread_count) };
+        let mut pending = std::collections::VecDeque::new();
+        let mut delivered = vec![vec![]; thread_count as usize];
+        for (id, core) in cores.iter().enumerate() {
+            let message = format!("ffi message by {id}");
+            unsafe { rb_core_broadcast(*core, message.as_ptr(), message.len(), 0, 0) };
+            poll_core_actions(id as u32, *core, &mut pending, &mut delivered);
+        }
+        while let Some((destination, frame)) = pending.pop_front() {
+            let core = cores[destination as usize];
+            unsafe { rb_core_feed(core, frame.as_ptr(), frame.len()) };
+            poll_core_actions(destination, core, &mut pending, &mut delivered);
+        }
+        for core in cores {
+            unsafe { rb_core_free(core) };
+        }
+
+        let mut passed = outsider.is_null();
+        for (id, frames) in delivered.iter().enumerate() {
+            let complete = frames.len() == thread_count as usize
+                && (0..thread_count).all(|sender| frames.iter().any(|frame| String::from_utf8_lossy(frame).contains(&format!("ffi message by {sender}"))));
+            if !complete {
+                println!("core {id} delivered {} frames", frames.len());
+                passed = false;
+            }
+        }
+        println!("ffi scenario: {}", if passed { "passed" } else { "failed" });
+        passed
+    }).await.unwrap_or(false)
+}
+
+#[cfg(not(feature = "ffi"))]
+async fn simulate_ffi(_config: ClusterConfig) -> bool {
+    println!("ffi scenario: requires the `ffi` feature (cargo run --features ffi -- 4 ffi)");
+    false
+}
+
+// # Function Description:
+// This function runs the frame statistics scenario: an aggregated witness round is run with and without
+// `ClusterConfig::with_frame_statistics`, and the reports and aggregated reports carried by the aggregation
+// proof of every node are checked to embed statistics consistent with the thresholds of the cluster only
+// when enabled.
+// # Parameters:
+// * `config` - the configuration of the aggregated witness clusters, run with and without frame statistics.
+// # Returns
+// * `true` if every node observed the expected statistics.
+async fn simulate_frame_statistics(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let thresholds = config.get_thresholds().clone();
+    let mut passed = true;
+
+    for frame_statistics in [false, true] {
+        let config = config.clone().with_frame_statistics(frame_statistics);
+        let (transmitters, receivers) = create_channels(&config);
+        let mut aggregated_witness_hub = AggregatedWitnessHub::with_config(transmitters, receivers, config);
+        let started_at = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|elapsed| elapsed.as_millis() as u64).unwrap_or(0);
+        let mut handles = vec![];
+        for id in 0..thread_count {
+            let mut aggregated_witness_communicator: AggregatedWitnessCommunicator<String> = aggregated_witness_hub.create_aggregated_witness_communicator();
+            handles.push(tokio::spawn(async move {
+                let reliable_handle = aggregated_witness_communicator.initialize_reliable_handle();
+                let witness_handle = aggregated_witness_communicator.initialize_witness_handle();
+                aggregated_witness_communicator.aggregated_witness_broadcast(format!("aggregated witness broadcast message by {id}"), Round(0)).await;
+                aggregated_witness_communicator.aggregated_witness_collect(Round(0)).await;
+                let proof = aggregated_witness_communicator.aggregated_witness_proof(Round(0)).await;
+                aggregated_witness_communicator.terminate_witness_handle(witness_handle);
+                aggregated_witness_communicator.terminate_reliable_handle(reliable_handle);
+                proof
+            }));
+        }
+        let proofs = join_all(handles).await;
+        let finished_at = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|elapsed| elapsed.as_millis() as u64).unwrap_or(0);
+
+        let mut scenario_passed = true;
+        for (id, proof) in proofs.into_iter().enumerate() {
+            let Ok(proof) = proof else {
+                scenario_passed = false;
+                continue
+            };
+            let reports: Vec<_> = proof.get_aggregated_witnesses().iter().flat_map(|aggregated_witness| aggregated_witness.get_reports().iter()).collect();
+            let observed = match frame_statistics {
+                false => proof.get_aggregated_witnesses().iter().all(|aggregated_witness| aggregated_witness.get_statistics().is_none())
+                    && reports.iter().all(|report| report.get_statistics().is_none()),
+                true => {
+                    let consistent = |statistics: &FrameStatistics| statistics.get_validity_threshold() == thresholds.get_validity_threshold()
+                        && statistics.get_agreement_threshold() == thresholds.get_agreement_threshold()
+                        && (started_at..=finished_at).contains(&statistics.get_created_at());
+                    proof.get_aggregated_witnesses().iter().all(|aggregated_witness| aggregated_witness.get_statistics().is_some_and(|statistics| {
+                        consistent(statistics) && statistics.get_witnesses() as usize == aggregated_witness.get_reports().len()
+                    })) && reports.iter().all(|report| report.get_statistics().is_some_and(|statistics| {
+                        consistent(statistics) && statistics.get_values() as usize == report.get_messages().len() && u64::from(statistics.get_values()) >= thresholds.get_validity_threshold()
+                    }))
+                },
+            };
+            if !observed {
+                println!("node {id} carried unexpected frame statistics");
+                scenario_passed = false;
+            }
+        }
+        println!("frame statistics scenario, {}: {}", if frame_statistics { "embedded" } else { "disabled" }, if scenario_passed { "passed" } else { "failed" });
+        passed &= scenario_passed;
+    }
+    passed
+}
+
+// # Function Description:
+// This function runs the scenario file scenario: the bundled `scenarios/witness.toml` is loaded and run on a
+// cluster of the size it declares, and must pass with its last node crashed, which alone misses the deadline of the reliable round; a scenario
+// expecting a reliable message that is never sent, and more values than the cluster has threads, must report
+// the failing nodes only, and every node must miss a deadline of no time at all; and a script of a node
+// outside the cluster must be rejected.
+// # Parameters:
+// * `config` - the configuration used for the scenarios declaring its thread count.
+// # Returns
+// * `true` if every scenario ran as expected.
+async fn simulate_scenario_file(config: ClusterConfig) -> bool {
+    let thread_count = config.get_thread_count();
+    let mut passed = true;
+
+    let bundled = Scenario::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/witness.toml")))
+        .and_then(|scenario| {
+            // the scripts of the bundled scenario only hold for the cluster size it declares
+            let declared = scenario.get_thread_count().unwrap_or(thread_count);
+            let cluster = if declared == thread_count { config.clone() } else { ClusterConfig::new(declared) };
+            scenario.configure(cluster).map(|config| (scenario, config))
+        });
+    let bundled_passed = match bundled {
+        Ok((scenario, config)) => {
+            let (transmitters, receivers) = create_channels(&config);
+            let nodes = NodeMetrics::default();
+            let outcomes = run_witness_scenario(transmitters, receivers, config.clone(), &scenario, &nodes).await;
+            // the crashed node misses the deadline of the reliable round it no longer takes part in, and only it
+            let crashed: Vec<u32> = outcomes.iter().filter(|outcome| outcome.is_crashed()).map(|outcome| outcome.get_id()).collect();
+            let deadlines = report_deadlines(&config, &scenario, &nodes);
+            let missed: Vec<u32> = deadlines.get_misses().iter().map(|outcome| outcome.get_id()).collect();
+            report_script_outcomes(&outcomes) && !crashed.is_empty() && !deadlines.get_outcomes().is_empty() && missed == crashed
+        },
+        Err(error) => {
+            println!("could not load the bundled scenario: {error}");
+            false
+        },
+    };
+    println!("scenario file scenario, bundled: {}", if bundled_passed { "passed" } else { "failed" });
+    passed &= bundled_passed;
+
+    let scripts = (0..thread_count).map(|id| {
+        let mut actions = vec![
+            ScenarioAction::Broadcast { round: 0, message: format!("witness broadcast message by {id}") },
+            ScenarioAction::Collect { round: 0, min_values: if id == 2 { Some(thread_count as usize + 1) } else { None } },
+        ];
+        match id {
+            0 => actions.push(ScenarioAction::ReliableReceive { from: 1, instance: 0, round: 1, expect: Some(String::from("expected message")) }),
+            1 => actions.push(ScenarioAction::ReliableBroadcast { instance: 0, round: 1, message: String::from("actual message") }),
+            _ => {},
+        }
+        NodeScript::new(id, actions)
+    }).collect();
+    let failing = Scenario::new(scripts).with_timeout(Duration::from_secs(2))
+        .with_deadline(RoundDeadline::new("witness", Round(0), Duration::ZERO))
+        .with_deadline(RoundDeadline::new("reliable", Round(1), Duration::from_secs(5)));
+    let (transmitters, receivers) = create_channels(&config);
+    let nodes = NodeMetrics::default();
+    let outcomes = run_witness_scenario(transmitters, receivers, config.clone(), &failing, &nodes).await;
+    report_script_outcomes(&outcomes);
+    let failed: Vec<u32> = outcomes.iter().filter(|outcome| outcome.get_failure().is_some()).map(|outcome| outcome.get_id()).collect();
+    let failing_passed = failed == vec![0, 2];
+    println!("scenario file scenario, failing: {} (failed nodes {failed:?})", if failing_passed { "passed" } else { "failed" });
+    passed &= failing_passed;
+
+    // nobody delivers a round within no time at all, while the reliable round is delivered in time by every node
+    let deadlines = report_deadlines(&config, &failing, &nodes);
+    let missed: Vec<(String, u32)> = deadlines.get_misses().iter().map(|outcome| (outcome.get_protocol().clone(), outcome.get_id())).collect();
+    let deadlines_passed = missed == (0..thread_count).map(|id| (String::from("witness"), id)).collect::<Vec<_>>();
+    println!("scenario file scenario, deadlines: {} (missed {missed:?})", if deadlines_passed { "passed" } else { "failed" });
+    passed &= deadlines_passed;
+
+    let outside = Scenario::new(vec![NodeScript::new(thread_count, vec![ScenarioAction::Crash])]);
+    let rejected = outside.configure(config).is_err();
+    println!("scenario file scenario, node outside the cluster: {}", if rejected { "passed" } else { "failed" });
+    passed & rejected
+}
+
+// The metrics of the nodes of a run, by node ID, kept outside of the node tasks so that they can still be
+// dumped when the run is interrupted.
+type NodeMetrics = Arc<Mutex<Vec<(u32, Metrics)>>>;
+
+// # Function Description:
+// This function records the metrics of a node of the run, so that they are dumped if the run is interrupted.
+// # Parameters:
+// * nodes - the metrics of the nodes of the run.
+// * id - the ID of the node.
+// * metrics - the node's metrics, shared with its handles.
+fn register_node(nodes: &NodeMetrics, id: u32, metrics: &Metrics) {
+    nodes.lock().unwrap().push((id, metrics.clone()));
+}
+
+// # Function Description:
+// This function writes the metric reports of the registered nodes to `metrics.jsonl`, one report per line,
+// so that runs can be compared with the `compare_metrics` tool.
+// # Parameters:
+// * config - the cluster configuration of the run.
+// * nodes - the metrics of the nodes of the run.
+fn export_node_metrics(config: &ClusterConfig, nodes: &NodeMetrics) {
+    let reports: Vec<MetricsReport> = nodes.lock().unwrap().iter().map(|(id, metrics)| metrics.report(*id, config.clone())).collect();
+    if !reports.is_empty() {
+        let _ = std::fs::write("metrics.jsonl", write_metrics_export(&reports));
+        println!("metrics of {} nodes exported to metrics.jsonl", reports.len());
+    }
+}
+
+// # Function Description:
+// This function waits for the process to be asked to stop, with Ctrl-C (SIGINT) or SIGTERM. The signal
+// handlers are installed when the function is called, so signals received before its future is polled
+// are not missed.
+// # Returns
+// * a future resolving to the name of the signal received.
+fn shutdown_signal() -> impl Future<Output = &'static str> {
+    let mut interrupt = signal(SignalKind::interrupt()).expect("Error: SIGINT handler could not be installed");
+    let mut terminate = signal(SignalKind::terminate()).expect("Error: SIGTERM handler could not be installed");
+    async move {
+        tokio::select! {
+            _ = interrupt.recv() => "SIGINT",
+            _ = terminate.recv() => "SIGTERM",
+        }
+    }
+}
+
+// # Function Description:
+// This function dumps the partial results of an interrupted run: the metric report of every registered
+// node, as JSON, then the status of every round the node took part in, with the reason of every stall.
+// # Parameters:
+// * config - the cluster configuration of the run.
+// * nodes - the metrics of the nodes of the run.
+// * signal - the name of the signal that interrupted the run.
+// # Returns
+// * the number of completed and stalled rounds, over every node.
+fn dump_partial_results(config: &ClusterConfig, nodes: &NodeMetrics, signal: &str) -> (usize, usize) {
+    println!("interrupted by {signal}, partial results:");
+    let (mut completed, mut stalled) = (0, 0);
+    for (id, metrics) in nodes.lock().unwrap().iter() {
+        let report = metrics.report(*id, config.clone());
+        println!("id: {id}, metrics: {}", report.write_json());
+        for round_status in report.get_round_statuses() {
+            let instance = round_status.get_instance().map(|instance| format!(" ({instance})")).unwrap_or_default();
+            let round = format!("{} round {}{instance}", round_status.get_protocol_information(), round_status.get_round_number());
+            match round_status.get_stall_reason() {
+                None => {
+                    println!("id: {id}, {round}: completed");
+                    completed += 1;
+                },
+                Some(reason) => {
+                    println!("id: {id}, {round}: stalled, {reason}");
+                    stalled += 1;
+                },
+            }
+        }
+    }
+    println!("completed rounds: {completed}, stalled rounds: {stalled}");
+    export_node_metrics(config, nodes);
+    (completed, stalled)
+}
+
+// # Function Description:
+// This function runs the interrupt scenario: every node completes witness round 0, then only node 0
+// broadcasts in round 1, which therefore stalls, and the process sends itself SIGINT. The run is then
+// interrupted by the handler installed in `main`, which dumps the partial results and exits with status 130.
+// # Parameters:
+// * `config` - the configuration of the witness cluster left running until the interrupt.
+// * `nodes` - the metrics of the nodes of the run.
+// # Returns
+// * `false` if the process was not interrupted within 10 seconds.
+async fn simulate_interrupt(config: ClusterConfig, nodes: &NodeMetrics) -> bool {
+    let thread_count = config.get_thread_count();
+    let (transmitters, receivers) = create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);
+    let (ready_tx, mut ready_rx) = mpsc::channel(thread_count as usize);
+    for i in 0..thread_count {
+        let mut witness_communicator = witness_hub.create_witness_communicator();
+        register_node(nodes, i, witness_communicator.get_metrics());
+        let ready_tx = ready_tx.clone();
+        tokio::spawn(async move {
+            let _reliable_handle = witness_communicator.initialize_reliable_handle(); 
+            let _witness_handle = witness_communicator.initialize_witness_handle(); 
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {i}"), Round(0)).await; 
+            witness_communicator.witness_collect(Round(0)).await; 
+            if i == 0 {
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {i}"), Round(1)).await; 
+            }
+            let _ = ready_tx.send(()).await;
+            // the node keeps its handles running until the process is interrupted
+            std::future::pending::<()>().await;
+        });
+    }
+
+    for _ in 0..thread_count {
+        ready_rx.recv().await;
+    }
+    //lets the value of round 1 reach every node before the interruption
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let interrupted = std::process::Command::new("kill").args(["-INT", &std::process::id().to_string()]).status();
+    println!("sent SIGINT: {interrupted:?}");
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    false
+}
+
+// # Function Description:
+// This asynchronous function sets up and spawns a collection of simulated threads
+// for testing different message-passing communication models: either a `BasicHub` or a `ReliableHub`.
+// # Parameters:
+// * `transmitters` - a vector of `Sender<Bytes>` objects, each representing the outgoing message channel for a thread.
+// * `receivers` - a vector of `Receiver<Bytes>` objects, each representing the incoming message channel for a thread.
+// * `config` - the cluster configuration (thread count, experiment seed) shared by every communicator.
+// * `communication_type` - a string reference that specifies the communication mode ("basic" or "reliable").
+// * `scenario` - the scenario file followed by the threads of witness runs, in place of the built-in scripts.
+async fn simulate_threads(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>,
+    config: ClusterConfig, communication_type: &String, nodes: &NodeMetrics, scenario: Option<&Scenario>) {
+    let mut handles = vec![];
+    let thread_count = config.get_thread_count();
+    println!("experiment seed: {}", config.get_seed());
+
+    if communication_type == "basic" {
+        println!("Setting up basic communication..."); 
+        let mut basic_hub = BasicHub::with_config(transmitters, receivers, config); 
+        for i in 0..thread_count {
+            let handle: JoinHandle<()> = create_basic_thread(i as u32, basic_hub.create_basic_communicator());
+            handles.push(handle);
+        }
+
+        for handle in handles {
+            let _ = handle.await.unwrap();
+        }
+    }
+    else if communication_type == "reliable" {
+        println!("Setting up reliable communication...");      
+        let fault_script = config.get_fault_script().clone();
+        let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);    
+        let mut crashed = vec![];
+        let mut byzantine = vec![];
+        for i in 0..thread_count {
+            let communicator = reliable_hub.create_reliable_communicator();
+            register_node(nodes, i, communicator.get_metrics());
+            match (fault_script.get_crash_point(i), fault_script.get_byzantine_behavior(i)) {
+                (Some(crash_point), _) => crashed.push(create_crashed_reliable_thread(i, communicator, crash_point)),
+                (None, Some(behavior)) => byzantine.push(create_byzantine_reliable_thread(i, ByzantineCommunicator::new(communicator, behavior, conflicting_message))),
+                (None, None) => handles.push(create_reliable_thread(i as u32, communicator)),
+            }
+        }
+
+        for handle in handles {
+            let _ = handle.await.unwrap();
+        }
+        report_faulty_threads(crashed.len(), byzantine.len());
+    } else if let (true, Some(scenario)) = (communication_type == "witness", scenario) {
+        println!("Running witness scenario file...");
+        let outcomes = run_witness_scenario(transmitters, receivers, config.clone(), scenario, nodes).await;
+        report_deadlines(&config, scenario, nodes);
+        if !report_script_outcomes(&outcomes) {
+            std::process::exit(1);
+        }
+    } else if communication_type == "witness" {
+        println!("Setting up witness communication...");      
+        let fault_script = config.get_fault_script().clone();
+        let mut witness_hub = WitnessHub::with_config(transmitters, receivers, config);    
+        let mut crashed = vec![];
+        for i in 0..thread_count {
+            let communicator = witness_hub.create_witness_communicator();
+            register_node(nodes, i, communicator.get_metrics());
+            match fault_script.get_crash_point(i) {
+                Some(crash_point) => crashed.push(create_crashed_witness_thread(i, communicator, crash_point)),
+                None => handles.push(create_witness_thread(i as u32, communicator)),
+            }
+        }
+
+        for handle in handles {
+            let _ = handle.await.unwrap();
+        }
+        report_faulty_threads(crashed.len(), 0);
+    } else if communication_type == "aggregated_witness" {
+        println!("Setting up aggregated witness communication...");      
+        let mut aggregated_witness_hub = AggregatedWitnessHub::with_config(transmitters, receivers, config);    
+        for i in 0..thread_count {
+            let communicator = aggregated_witness_hub.create_aggregated_witness_communicator();
+            register_node(nodes, i, communicator.get_metrics());
+            let handle: JoinHandle<()> = create_aggregated_witness_thread(i as u32, communicator);
+            handles.push(handle);
+        }
+
+        for handle in handles {
+            let _ = handle.await.unwrap();
+        }
+    } else if communication_type == "interrupt" {
+        println!("Running interrupt scenario...");      
+        if !simulate_interrupt(config, nodes).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "metrics_comparison" {
+        println!("Running metrics comparison scenario...");      
+        if !simulate_metrics_comparison(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "tree_dissemination" {
+        println!("Running tree dissemination scenario...");      
+        if !simulate_tree_dissemination(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "identity" {
+        println!("Running identity scenario...");      
+        if !simulate_identity(config) {
+            std::process::exit(1);
+        }
+    } else if communication_type == "watch_delivery" {
+        println!("Running delivery watch scenario...");      
+        if !simulate_watch_delivery(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "repeated_collect" {
+        println!("Running repeated collect scenario...");      
+        if !simulate_repeated_collect(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "signing" {
+        println!("Running signing scenario...");      
+        if !simulate_signing(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "cumulative" {
+        println!("Running cumulative scenario...");      
+        if !simulate_cumulative(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "extension" {
+        println!("Running extension scenario...");      
+        if !simulate_extension(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "gossip" {
+        println!("Running gossip scenario...");      
+        if !simulate_gossip(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "health" {
+        println!("Running health scenario...");
+        if !simulate_health(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "vector_clock" {
+        println!("Running vector clock scenario...");
+        if !simulate_vector_clock(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "fast_path" {
+        println!("Running fast path scenario...");
+        if !simulate_fast_path(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "validity" {
+        println!("Running validated broadcast scenario...");
+        if !simulate_validity(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "subscribe_round" {
+        println!("Running round subscription scenario...");
+        if !simulate_subscribe_round(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "validate" {
+        println!("Running validation scenario...");      
+        if !simulate_validation(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "middleware" {
+        println!("Running middleware scenario...");
+        if !simulate_middleware(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "crash_recover" {
+        println!("Running crash-and-recover scenario...");
+        if !simulate_crash_recover(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "collect_window" {
+        println!("Running collect window scenario...");
+        if !simulate_collect_window(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "delivered" {
+        println!("Running delivered scenario...");
+        if !simulate_delivered(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "binary_agreement" {
+        println!("Running binary agreement scenario...");
+        if !simulate_binary_agreement(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "study" {
+        println!("Running protocol study...");
+        if !simulate_protocol_study(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "codec" {
+        println!("Running codec scenario...");
+        if !simulate_codecs(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "retention" {
+        println!("Running retention scenario...");
+        if !simulate_retention(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "autoscale" {
+        println!("Running autoscale scenario...");
+        if !simulate_autoscale(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "quorum" {
+        println!("Running quorum scenario...");
+        if !simulate_quorum(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "trace_sampling" {
+        println!("Running trace sampling scenario...");
+        if !simulate_trace_sampling(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "stake" {
+        println!("Running stake scenario...");
+        if !simulate_stake(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "capabilities" {
+        println!("Running capabilities scenario...");
+        if !simulate_capabilities(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "divergence" {
+        println!("Running divergence scenario...");
+        if !simulate_divergence(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "ffi" {
+        println!("Running C ABI scenario...");
+        if !simulate_ffi(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "frame_statistics" {
+        println!("Running frame statistics scenario...");
+        if !simulate_frame_statistics(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "scenario_file" {
+        println!("Running scenario file scenario...");
+        if !simulate_scenario_file(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "abandoned_collect" {
+        println!("Running abandoned collect scenario...");
+        if !simulate_abandoned_collect(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "link_conditions" {
+        println!("Running link conditions scenario...");
+        if !simulate_link_conditions(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "demo" {
+        println!("Running demo scenario...");
+        if !simulate_demo(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "gst" {
+        println!("Running GST scenario...");
+        if !simulate_gst(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "polling" {
+        println!("Running polling scenarios...");      
+        if !simulate_polling(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "commit_reveal" {
+        println!("Running commit-reveal scenarios...");      
+        if !simulate_commit_reveal(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "decode_failure" {
+        println!("Running decode failure scenarios...");      
+        if !simulate_decode_failure(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "inspect" {
+        println!("Running inspection scenario...");      
+        if !simulate_inspection(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "aggregation_depth" {
+        println!("Running aggregation depth scenarios...");      
+        if !simulate_aggregation_depth(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "barycentric_certificate" {
+        println!("Running barycentric certificate scenario...");      
+        if !simulate_barycentric_certificate(config).await {
+            std::process::exit(1);
+        }
+    } else if communication_type == "payload_benchmark" {
+        println!("Running payload benchmark...");      
+        if !simulate_payload_benchmark(config).await {
+            std::process::exit(1);
+        }
+    } else {
+        println!("Setting up barycentric agreement communication...");      
+        let mut barycentric_agreement_hub = BarycentricHub::with_config(transmitters, receivers, config);    
+        for i in 0..thread_count {
+            let communicator = barycentric_agreement_hub.create_barycentric_communicator();
+            register_node(nodes, i, communicator.get_metrics());
+            let handle: JoinHandle<()> = create_barycentric_agreement_thread(i as u32, communicator);
+            handles.push(handle);
+        }
+
+        for handle in handles {
+            let _ = handle.await.unwrap();
+        }
+    } 
+}
+
+#[tokio::main] 
+async fn main() {
+    //takes in the number of threads to simulate from the command-line argument
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().partition(|arg| arg.starts_with("--"));
+    let thread_count:u32 = args[1].parse().unwrap(); 
+    let communication_type: String = args[2].parse().unwrap(); 
+    //optionally takes in the experiment seed, so that randomized runs can be reproduced
+    let seed: u64 = match args.get(3) {
+        Some(seed) => seed.parse().unwrap(),
+        None => 0,
+    };
+    let mut config = ClusterConfig::new(thread_count).with_seed(seed);
+    //optionally runs the experiment over an emulated WAN topology
+    if let Some("wan") = args.get(4).map(String::as_str) {
+        config = config.with_network_emulation(create_wan_emulation(thread_count));
+    }
+    //optionally conditions every link with latency, jitter, losses, reordering, and duplicates (e.g. --link=20:10:0.01:0.05:0.1)
+    if let Some(flag) = flags.iter().rev().find_map(|flag| flag.strip_prefix("--link=")) {
+        let network_emulation = config.get_network_emulation().cloned().unwrap_or_default();
+        config = config.with_network_emulation(network_emulation.with_conditions(parse_link_flag(flag)));
+    }
+    //optionally makes the network partially synchronous, with its GST and its delay bound after GST in milliseconds (e.g. --gst=1000:50)
+    if let Some(flag) = flags.iter().rev().find_map(|flag| flag.strip_prefix("--gst=")) {
+        let network_emulation = config.get_network_emulation().cloned().unwrap_or_default();
+        config = config.with_network_emulation(network_emulation.with_stabilization(parse_gst_flag(flag)));
+    }
+    //optionally crashes the threads with the highest IDs in reliable and witness runs (e.g. --crash=1 or --crash=1@0)
+    if let Some(flag) = flags.iter().rev().find_map(|flag| flag.strip_prefix("--crash=")) {
+        let (crashed, crash_point) = parse_crash_flag(flag);
+        for id in thread_count.saturating_sub(crashed)..thread_count {
+            config = config.with_fault(Fault::Crash(id, crash_point));
+        }
+    }
+    //optionally makes threads of reliable runs Byzantine (e.g. --byzantine=6 or --byzantine=5,6@drop_echoes)
+    if let Some(flag) = flags.iter().rev().find_map(|flag| flag.strip_prefix("--byzantine=")) {
+        let (ids, behavior) = parse_byzantine_flag(flag);
+        for id in ids {
+            config = config.with_fault(Fault::Byzantine(id, behavior));
+        }
+    }
+    //optionally traces a fraction of the instances only, to bound the tracing overhead of large runs (e.g. --trace-sample=0.01)
+    if let Some(flag) = flags.iter().rev().find_map(|flag| flag.strip_prefix("--trace-sample=")) {
+        let fraction: f64 = flag.parse().expect("Error: --trace-sample expects a fraction between 0 and 1");
+        config = config.with_trace_sampling(TraceSampling::fraction(fraction));
+    }
+    //optionally paces every frame and narrates the rounds, to follow the protocols step by step (e.g. --pace=500)
+    if let Some(flag) = flags.iter().rev().find_map(|flag| flag.strip_prefix("--pace=")) {
+        let step: u64 = flag.parse().expect("Error: --pace expects a delay in milliseconds");
+        config = config.with_demo_pacing(DemoPacing::new(Duration::from_millis(step)));
+    }
+    //optionally drives the threads of witness runs from a scenario file (e.g. --scenario=scenarios/witness.toml)
+    let scenario = flags.iter().rev().find_map(|flag| flag.strip_prefix("--scenario=")).map(|path| {
+        let scenario = Scenario::load(Path::new(path)).unwrap_or_else(|error| panic!("Error: {error}"));
+        config = scenario.configure(config.clone()).unwrap_or_else(|error| panic!("Error: invalid scenario: {error}"));
+        scenario
+    });
+    //prints the descriptors of the protocols, as text or JSON (e.g. --protocol=aggregated_witness --json)
+    if communication_type == "describe" {
+        let protocol = flags.iter().rev().find_map(|flag| flag.strip_prefix("--protocol="));
+        if !describe(&config, protocol, flags.iter().any(|flag| flag == "--json")) {
+            std::process::exit(1);
+        }
+        return
+    }
+    //runs a single node of a cluster spread over several processes, connected over TCP
+    if communication_type == "tcp_node" {
+        let id: u32 = args[4].parse().unwrap();
+        let health = flags.iter().rev().find_map(|flag| flag.strip_prefix("--health=")).map(|address| {
+            address.parse::<SocketAddr>().expect("Error: --health expects the address to serve the health endpoint on")
+        });
+        let identity_file = flags.iter().rev().find_map(|flag| flag.strip_prefix("--identity=")).map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(format!("node-{id}.identity.json")));
+        let startup_timeout = flags.iter().rev().find_map(|flag| flag.strip_prefix("--startup-timeout=")).map(|seconds| {
+            Duration::from_secs(seconds.parse().expect("Error: --startup-timeout expects a number of seconds"))
+        }).unwrap_or(Duration::from_secs(30));
+        if !run_tcp_node(config, id, &args[5], health, &identity_file, startup_timeout).await {
+            std::process::exit(1);
+        }
+        return
+    }
+    
+    let (transmitters, receivers) = create_channels(&config);
+    let start = Instant::now();
+    //dumps the partial results of the run if it is interrupted
+    let nodes = NodeMetrics::default();
+    tokio::select! {
+        _ = simulate_threads(transmitters, receivers, config.clone(), &communication_type, &nodes, scenario.as_ref()) => {
+            export_node_metrics(&config, &nodes);
+            println!("elapsed: {:?}", start.elapsed());
+        }
+        signal = shutdown_signal() => {
+            dump_partial_results(&config, &nodes, signal);
+            println!("elapsed: {:?}", start.elapsed());
+            std::process::exit(130);
+        }
+    }
 }
\ No newline at end of file
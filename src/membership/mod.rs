@@ -0,0 +1,274 @@
+use std::{collections::BTreeMap, fs, net::SocketAddr, path::Path};
+use serde::{Serialize, Deserialize};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::config::Thresholds;
+use crate::identity::{IdentityStore, KeyPair};
+use crate::json::JsonConversion;
+
+// # Struct Description:
+// This struct represents a node admitted to a cluster by its membership document.
+//
+// # Fields:
+// * id - The ID of the node.
+// * public_key - The Ed25519 public key of the node.
+// * address - The address the node listens on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Member {
+    id: u32,
+    public_key: Vec<u8>,
+    address: SocketAddr,
+}
+
+impl Member {
+    pub fn new(id: u32, public_key: Vec<u8>, address: SocketAddr) -> Self {
+        Self {
+            id,
+            public_key,
+            address
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_public_key(&self) -> &Vec<u8> {
+        &self.public_key
+    }
+
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+// # Struct Description:
+// This struct is the genesis document of a cluster: the nodes admitted to it and the thresholds its
+// quorums use. Every node of a cluster running in network mode loads the same document at startup, so
+// that nodes agree on who takes part in quorums before exchanging any frame.
+//
+// # Fields:
+// * members - The admitted nodes, ordered by ID, whose IDs are the thread IDs of the cluster.
+// * thresholds - The thresholds of the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MembershipDocument {
+    members: Vec<Member>,
+    thresholds: Thresholds,
+}
+
+impl MembershipDocument {
+    // # Function Description:
+    // This function creates the document of a cluster, whose thresholds are derived from its size.
+    //
+    // # Parameters:
+    // * members - The admitted nodes, in any order.
+    //
+    // # Returns:
+    // * The document.
+    pub fn new(mut members: Vec<Member>) -> Self {
+        members.sort_by_key(|member| member.id);
+        let thresholds = Thresholds::new(members.len() as u32);
+        Self {
+            members,
+            thresholds
+        }
+    }
+
+    // # Function Description:
+    // This function creates the document of a cluster from the identities of its nodes.
+    //
+    // # Parameters:
+    // * identities - The identity of every node.
+    // * addresses - The address of every node, indexed by node ID.
+    //
+    // # Returns:
+    // * The document, or a description of the node whose address is missing.
+    pub fn from_identities(identities: &[IdentityStore], addresses: &[SocketAddr]) -> Result<Self, String> {
+        let mut members = vec![];
+        for identity in identities {
+            let Some(address) = addresses.get(identity.get_id() as usize) else {
+                return Err(format!("no address given for node {}", identity.get_id()))
+            };
+            members.push(Member::new(identity.get_id(), identity.get_key_pair().get_public_key().clone(), *address));
+        }
+        Ok(Self::new(members))
+    }
+
+    pub fn get_members(&self) -> &Vec<Member> {
+        &self.members
+    }
+
+    pub fn get_thresholds(&self) -> &Thresholds {
+        &self.thresholds
+    }
+}
+
+impl JsonConversion<MembershipDocument> for MembershipDocument {}
+
+// # Struct Description:
+// This struct is a membership document signed by every node it admits, so that no node can be added,
+// removed, or moved without the consent of every member. Signatures are computed on the JSON
+// representation of the document.
+//
+// # Fields:
+// * document - The membership document.
+// * signatures - The hex-encoded signature of the document by every member that signed it, by node ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MembershipCertificate {
+    document: MembershipDocument,
+    signatures: BTreeMap<u32, String>,
+}
+
+impl MembershipCertificate {
+    pub fn new(document: MembershipDocument) -> Self {
+        Self {
+            document,
+            signatures: BTreeMap::new()
+        }
+    }
+
+    // # Method Description:
+    // This method adds the signature of a member to the certificate.
+    //
+    // # Parameters:
+    // * identity - The identity of the member, holding its Ed25519 key pair.
+    //
+    // # Returns:
+    // * The updated certificate, or a description of why the identity cannot sign it.
+    pub fn signed_by(mut self, identity: &IdentityStore) -> Result<Self, String> {
+        let key_pair = identity.get_key_pair();
+        if key_pair.get_scheme() != KeyPair::ED25519 {
+            return Err(format!("the key pair of node {} is a {} key pair, not an {} one", identity.get_id(), key_pair.get_scheme(), KeyPair::ED25519))
+        }
+        if self.get_member(identity.get_id()).is_none_or(|member| member.get_public_key() != key_pair.get_public_key()) {
+            return Err(format!("node {} is not a member with this key pair", identity.get_id()))
+        }
+        let secret_key: [u8; 32] = key_pair.get_secret_key().as_slice().try_into()
+            .map_err(|_| format!("the secret key of node {} is not 32 bytes long", identity.get_id()))?;
+        let signature = SigningKey::from_bytes(&secret_key).sign(self.document.write_json().as_bytes());
+        let signature = signature.to_bytes().iter().map(|byte| format!("{byte:02x}")).collect();
+        self.signatures.insert(identity.get_id(), signature);
+        Ok(self)
+    }
+
+    // # Method Description:
+    // This method checks that the certificate can be trusted: the members are the threads `0..n` of a
+    // cluster of `n` threads, its thresholds are those of `n` threads, and every member signed the document.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of the first problem found.
+    pub fn verify(&self) -> Result<(), String> {
+        let members = self.document.get_members();
+        if let Some((index, member)) = members.iter().enumerate().find(|(index, member)| member.id != *index as u32) {
+            return Err(format!("member {} is listed where node {index} is expected", member.id))
+        }
+        if self.document.thresholds != Thresholds::new(members.len() as u32) {
+            return Err(format!("the thresholds do not match a cluster of {} members", members.len()))
+        }
+        let document = self.document.write_json();
+        for member in members {
+            let Some(signature) = self.signatures.get(&member.id) else {
+                return Err(format!("member {} did not sign the document", member.id))
+            };
+            let public_key: [u8; 32] = member.public_key.as_slice().try_into()
+                .map_err(|_| format!("the public key of member {} is not 32 bytes long", member.id))?;
+            let verifying_key = VerifyingKey::from_bytes(&public_key).map_err(|error| format!("the public key of member {} is invalid: {error}", member.id))?;
+            let signature: Option<Vec<u8>> = (0..signature.len()).step_by(2).map(|index| u8::from_str_radix(signature.get(index..index + 2)?, 16).ok()).collect();
+            let authentic = signature.and_then(|signature| Signature::from_slice(&signature).ok())
+                .is_some_and(|signature| verifying_key.verify(document.as_bytes(), &signature).is_ok());
+            if !authentic {
+                return Err(format!("the signature of member {} does not match the document", member.id))
+            }
+        }
+        Ok(())
+    }
+
+    // # Function Description:
+    // This function loads a certificate from its file, and verifies it.
+    //
+    // # Parameters:
+    // * path - The path of the certificate file.
+    //
+    // # Returns:
+    // * The certificate, or a description of why the file could not be read or cannot be trusted.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|error| format!("could not read {}: {error}", path.display()))?;
+        let certificate = Self::read_json(&data).map_err(|error| format!("could not decode {}: {error}", path.display()))?;
+        certificate.verify().map_err(|error| format!("{} cannot be trusted: {error}", path.display()))?;
+        Ok(certificate)
+    }
+
+    // # Function Description:
+    // This function loads a certificate that is still being signed, without verifying it, so that a member
+    // can add its own signature (see `create_membership sign`).
+    //
+    // # Parameters:
+    // * path - The path of the certificate file.
+    //
+    // # Returns:
+    // * The certificate, or a description of why the file could not be read.
+    pub fn load_unverified(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|error| format!("could not read {}: {error}", path.display()))?;
+        Self::read_json(&data).map_err(|error| format!("could not decode {}: {error}", path.display()))
+    }
+
+    // # Method Description:
+    // This method writes the certificate to a file.
+    //
+    // # Parameters:
+    // * path - The path of the certificate file.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the file could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.write_json()).map_err(|error| format!("could not write {}: {error}", path.display()))
+    }
+
+    pub fn get_document(&self) -> &MembershipDocument {
+        &self.document
+    }
+
+    pub fn get_member(&self, id: u32) -> Option<&Member> {
+        self.document.members.iter().find(|member| member.id == id)
+    }
+
+    pub fn get_signature_count(&self) -> u32 {
+        self.signatures.len() as u32
+    }
+
+    pub fn get_member_count(&self) -> u32 {
+        self.document.members.len() as u32
+    }
+
+    pub fn is_member(&self, id: u32) -> bool {
+        self.get_member(id).is_some()
+    }
+
+    // # Method Description:
+    // This method provides the address of every member, e.g. for the address book of a network transport.
+    //
+    // # Returns:
+    // * The addresses, indexed by node ID.
+    pub fn get_addresses(&self) -> Vec<SocketAddr> {
+        self.document.members.iter().map(|member| member.address).collect()
+    }
+
+    // # Method Description:
+    // This method checks whether a connection comes from a member: the node it announces must be a
+    // member, and the connection must come from the host of the member's address.
+    //
+    // # Parameters:
+    // * id - The ID announced by the connection, if the transport announces one.
+    // * remote - The address the connection comes from.
+    //
+    // # Returns:
+    // * `true` if the connection is admitted.
+    pub fn admits(&self, id: Option<u32>, remote: SocketAddr) -> bool {
+        match id {
+            Some(id) => self.get_member(id).is_some_and(|member| member.address.ip() == remote.ip()),
+            None => self.document.members.iter().any(|member| member.address == remote),
+        }
+    }
+}
+
+impl JsonConversion<MembershipCertificate> for MembershipCertificate {}
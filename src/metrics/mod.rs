@@ -0,0 +1,1139 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, sync::{Arc, Mutex}, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+use serde::{Serialize, Deserialize};
+use tokio::sync::broadcast;
+
+use crate::batching::BatchCount;
+use crate::config::{ClusterConfig, Thresholds};
+use crate::demo::{Narration, Narrator};
+use crate::health::{HealthProbe, NodeHealth};
+use crate::json::JsonConversion;
+use crate::multiplex::{Lane, LaneResize};
+use crate::round::Round;
+
+// The number of significant bits kept per recorded value. Values are bucketed HDR-style: exactly
+// below 2^SIGNIFICANT_BITS microseconds, and with a relative error below 2^-(SIGNIFICANT_BITS - 1) above.
+const SIGNIFICANT_BITS: u32 = 7;
+
+// # Struct Description:
+// This struct is an HDR-style latency histogram. Recorded durations are kept in microseconds and
+// bucketed log-linearly, so that percentiles keep a bounded relative error over any range of
+// latencies while memory only grows with the number of distinct buckets used.
+//
+// # Fields:
+// * buckets - The number of recorded values per bucket, keyed by the bucket's lowest value (in microseconds).
+// * count - The number of recorded values.
+// * min - The smallest recorded value (in microseconds).
+// * max - The largest recorded value (in microseconds).
+// * total - The sum of the recorded values (in microseconds).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    buckets: BTreeMap<u64, u64>,
+    count: u64,
+    min: u64,
+    max: u64,
+    total: u128,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // # Method Description:
+    // This method records a single latency.
+    //
+    // # Parameters:
+    // * latency - The latency to record.
+    pub fn record(&mut self, latency: Duration) {
+        let value = latency.as_micros().min(u64::MAX as u128) as u64;
+        *self.buckets.entry(bucket_of(value)).or_insert(0) += 1;
+
+        if self.count == 0 || value < self.min {
+            self.min = value;
+        }
+        if value > self.max {
+            self.max = value;
+        }
+        self.count += 1;
+        self.total += value as u128;
+    }
+
+    // # Method Description:
+    // This method adds every value recorded by another histogram to this one, e.g. to build a
+    // cluster-wide histogram from the histograms of every thread.
+    //
+    // # Parameters:
+    // * other - The histogram to merge into this one.
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.count == 0 {
+            return;
+        }
+        for (bucket, count) in &other.buckets {
+            *self.buckets.entry(*bucket).or_insert(0) += count;
+        }
+        if self.count == 0 || other.min < self.min {
+            self.min = other.min;
+        }
+        if other.max > self.max {
+            self.max = other.max;
+        }
+        self.count += other.count;
+        self.total += other.total;
+    }
+
+    // # Method Description:
+    // This method computes a percentile of the recorded latencies. The result is the lowest value
+    // of the bucket holding the percentile, clamped to the recorded minimum and maximum.
+    //
+    // # Parameters:
+    // * percentile - The percentile to compute, between 0.0 and 100.0 (e.g. 99.0 for the p99).
+    //
+    // # Returns:
+    // * The latency at the given percentile, or `Duration::ZERO` if nothing was recorded.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let percentile = percentile.clamp(0.0, 100.0);
+        let rank = ((percentile / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+
+        let mut seen = 0;
+        for (bucket, count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_micros((*bucket).clamp(self.min, self.max));
+            }
+        }
+        Duration::from_micros(self.max)
+    }
+
+    pub fn get_count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn get_min(&self) -> Duration {
+        Duration::from_micros(self.min)
+    }
+
+    pub fn get_max(&self) -> Duration {
+        Duration::from_micros(self.max)
+    }
+
+    pub fn get_mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_micros((self.total / self.count as u128) as u64)
+    }
+}
+
+// # Function Description:
+// This function maps a value to the lowest value of its bucket by keeping only its
+// `SIGNIFICANT_BITS` most significant bits.
+//
+// # Parameters:
+// * value - The value to bucket (in microseconds).
+//
+// # Returns:
+// * The lowest value of the bucket holding `value`.
+fn bucket_of(value: u64) -> u64 {
+    let bits = u64::BITS - value.leading_zeros();
+    if bits <= SIGNIFICANT_BITS {
+        return value;
+    }
+    let shift = bits - SIGNIFICANT_BITS;
+    (value >> shift) << shift
+}
+
+// # Enum Description:
+// This enum represents the intervals of a reliable broadcast instance measured by a reliable handle.
+//
+// # Variants:
+// * InputToEchoQuorum - From the first signal of the instance (normally its Input) to the echo quorum.
+// * EchoQuorumToVoteQuorum - From the echo quorum to the vote quorum.
+// * VoteQuorumToDeliver - From the vote quorum to the hand-off of the delivered content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Phase {
+    InputToEchoQuorum,
+    EchoQuorumToVoteQuorum,
+    VoteQuorumToDeliver,
+}
+
+// # Struct Description:
+// This struct groups the latency histograms of every phase of the reliable broadcast instances
+// run for one protocol.
+//
+// # Fields:
+// * input_to_echo_quorum - The latencies from the first signal of an instance to its echo quorum.
+// * echo_quorum_to_vote_quorum - The latencies from the echo quorum to the vote quorum.
+// * vote_quorum_to_deliver - The latencies from the vote quorum to the delivery.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PhaseHistogram {
+    input_to_echo_quorum: LatencyHistogram,
+    echo_quorum_to_vote_quorum: LatencyHistogram,
+    vote_quorum_to_deliver: LatencyHistogram,
+}
+
+impl PhaseHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, phase: Phase, latency: Duration) {
+        self.get_phase_mut(phase).record(latency);
+    }
+
+    pub fn merge(&mut self, other: &PhaseHistogram) {
+        self.input_to_echo_quorum.merge(&other.input_to_echo_quorum);
+        self.echo_quorum_to_vote_quorum.merge(&other.echo_quorum_to_vote_quorum);
+        self.vote_quorum_to_deliver.merge(&other.vote_quorum_to_deliver);
+    }
+
+    pub fn get_phase(&self, phase: Phase) -> &LatencyHistogram {
+        match phase {
+            Phase::InputToEchoQuorum => &self.input_to_echo_quorum,
+            Phase::EchoQuorumToVoteQuorum => &self.echo_quorum_to_vote_quorum,
+            Phase::VoteQuorumToDeliver => &self.vote_quorum_to_deliver,
+        }
+    }
+
+    fn get_phase_mut(&mut self, phase: Phase) -> &mut LatencyHistogram {
+        match phase {
+            Phase::InputToEchoQuorum => &mut self.input_to_echo_quorum,
+            Phase::EchoQuorumToVoteQuorum => &mut self.echo_quorum_to_vote_quorum,
+            Phase::VoteQuorumToDeliver => &mut self.vote_quorum_to_deliver,
+        }
+    }
+
+    pub fn get_input_to_echo_quorum(&self) -> &LatencyHistogram {
+        &self.input_to_echo_quorum
+    }
+
+    pub fn get_echo_quorum_to_vote_quorum(&self) -> &LatencyHistogram {
+        &self.echo_quorum_to_vote_quorum
+    }
+
+    pub fn get_vote_quorum_to_deliver(&self) -> &LatencyHistogram {
+        &self.vote_quorum_to_deliver
+    }
+}
+
+// # Struct Description:
+// This struct collects the phase latencies measured by a thread's reliable handle, per protocol
+// (e.g. "reliable", "witness"). It is shared by the handles of the thread through its `Metrics`.
+//
+// # Fields:
+// * histograms - The phase histograms, keyed by protocol information.
+#[derive(Debug, Clone, Default)]
+pub struct PhaseLatencies {
+    histograms: Arc<Mutex<HashMap<String, PhaseHistogram>>>,
+}
+
+impl PhaseLatencies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // # Method Description:
+    // This method records the latency of one phase of an instance of the given protocol.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol the instance belongs to.
+    // * phase - The measured phase.
+    // * latency - The measured latency.
+    pub fn record(&self, protocol_information: &str, phase: Phase, latency: Duration) {
+        let mut histograms = self.histograms.lock().unwrap();
+        histograms.entry(protocol_information.to_string()).or_default().record(phase, latency);
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the phase histograms of the given protocol.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol whose histograms are requested.
+    //
+    // # Returns:
+    // * A `PhaseHistogram`, empty if no instance of the protocol completed a phase yet.
+    pub fn get(&self, protocol_information: &str) -> PhaseHistogram {
+        let histograms = self.histograms.lock().unwrap();
+        histograms.get(protocol_information).cloned().unwrap_or_default()
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the phase histograms of every protocol.
+    //
+    // # Returns:
+    // * The `PhaseHistogram`s keyed by protocol information.
+    pub fn get_all(&self) -> BTreeMap<String, PhaseHistogram> {
+        let histograms = self.histograms.lock().unwrap();
+        histograms.iter().map(|(protocol_information, histogram)| (protocol_information.clone(), histogram.clone())).collect()
+    }
+}
+
+// # Struct Description:
+// This struct holds the metrics of a thread: the phase latencies of its instances, the frames its handles
+// received, sent, or discarded, the counters of its local queues and lanes, and the latest progress of
+// every round (or reliable broadcast instance) the handles took part in, so that a run interrupted before
+// completion can still report which rounds completed and which stalled. It is shared between the
+// communicator and its handles, so that the metrics can be read in-process while the handles are running,
+// and also carries the liveness probe of the handles and, in demo mode, the narrator of the rounds.
+//
+// # Fields:
+// * phase_latencies - The phase latencies of the thread's instances.
+// * decode_failures - The number of undecodable frames, keyed by lane.
+// * traffic - The number of signal frames (and bytes) received, keyed by protocol information.
+// * sent_signals - The number of signal frames sent, keyed by protocol information and round number.
+// * signal_batches - The number of frames the signal batcher sent, and of signals they carried (see `SignalBatching`).
+// * round_statuses - The latest status of every round, keyed by protocol information, instance, and round number.
+// * quarantined - The number of frames quarantined by the local queues, keyed by reason.
+// * quarantined_senders - The number of frames quarantined by the local queues, keyed by the ID of their sender.
+// * duplicates - The number of duplicate messages the local queues did not store again, keyed by the ID of their sender.
+// * evicted - The number of objects the local queues evicted before they were received, keyed by the ID of their sender.
+// * lane_resizes - The resizes of the thread's saturated lanes, in order (see `LaneScaling::Autoscale`).
+// * delivery_paths - The number of instances delivered on the fast and the slow path, keyed by protocol information.
+// * health_probe - The liveness of the thread's handles and lanes (see `HealthProbe`).
+// * narrator - The narrator of the thread's rounds, in demo mode (see `ClusterConfig::with_demo_pacing`).
+// * started - The instant the metrics were created, from which the delivery times of rounds are measured.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    phase_latencies: PhaseLatencies,
+    decode_failures: Arc<Mutex<HashMap<Lane, u64>>>,
+    traffic: Arc<Mutex<HashMap<String, TrafficCount>>>,
+    sent_signals: Arc<Mutex<BTreeMap<(String, Round), u64>>>,
+    signal_batches: Arc<Mutex<BatchCount>>,
+    round_statuses: Arc<Mutex<BTreeMap<RoundKey, RoundStatus>>>,
+    quarantined: Arc<Mutex<BTreeMap<String, u64>>>,
+    quarantined_senders: Arc<Mutex<BTreeMap<u32, u64>>>,
+    duplicates: Arc<Mutex<BTreeMap<u32, u64>>>,
+    evicted: Arc<Mutex<BTreeMap<u32, u64>>>,
+    lane_resizes: Arc<Mutex<Vec<LaneResize>>>,
+    delivery_paths: Arc<Mutex<BTreeMap<String, DeliveryPaths>>>,
+    health_probe: HealthProbe,
+    narrator: Option<Narrator>,
+    started: Option<Instant>,
+}
+
+// The key of a round status: its protocol information, instance, and round number.
+type RoundKey = (String, Option<String>, Round);
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            started: Some(Instant::now()),
+            ..Self::default()
+        }
+    }
+
+    // # Method Description:
+    // This method makes the metrics narrate every round status recorded (see `Narrator`).
+    //
+    // # Parameters:
+    // * narrator - The narrator of the thread.
+    //
+    // # Returns:
+    // * The updated metrics.
+    pub fn with_narrator(mut self, narrator: Narrator) -> Self {
+        self.narrator = Some(narrator);
+        self
+    }
+
+    // # Method Description:
+    // This method subscribes to the narrations of the thread's rounds.
+    //
+    // # Returns:
+    // * The receiver of the narrations, or `None` if the cluster does not run in demo mode.
+    pub fn subscribe_narrations(&self) -> Option<broadcast::Receiver<Narration>> {
+        self.narrator.as_ref().map(|narrator| narrator.subscribe())
+    }
+
+    pub fn get_phase_latencies(&self) -> &PhaseLatencies {
+        &self.phase_latencies
+    }
+
+    // # Method Description:
+    // This method counts a frame discarded because it could not be decoded.
+    //
+    // # Parameters:
+    // * lane - The lane the frame was received on.
+    pub fn record_decode_failure(&self, lane: Lane) {
+        let mut decode_failures = self.decode_failures.lock().unwrap();
+        *decode_failures.entry(lane).or_default() += 1;
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the number of undecodable frames of every lane.
+    //
+    // # Returns:
+    // * The number of undecodable frames keyed by lane name (e.g. "Signal"); lanes without failures are omitted.
+    pub fn get_decode_failures(&self) -> BTreeMap<String, u64> {
+        let decode_failures = self.decode_failures.lock().unwrap();
+        decode_failures.iter().map(|(lane, count)| (format!("{lane:?}"), *count)).collect()
+    }
+
+    // # Method Description:
+    // This method counts a frame quarantined by the local queues of the thread, by reason and by sender.
+    //
+    // # Parameters:
+    // * reason - The kind of validation the frame failed (e.g. "size").
+    // * sender - The ID of the thread that sent the frame, if known (see `QuarantinedFrame::get_sender`).
+    pub fn record_quarantined(&self, reason: &str, sender: Option<u32>) {
+        let mut quarantined = self.quarantined.lock().unwrap();
+        *quarantined.entry(reason.to_string()).or_default() += 1;
+        if let Some(sender) = sender {
+            *self.quarantined_senders.lock().unwrap().entry(sender).or_default() += 1;
+        }
+    }
+
+    pub fn get_quarantined(&self) -> BTreeMap<String, u64> {
+        self.quarantined.lock().unwrap().clone()
+    }
+
+    pub fn get_quarantined_senders(&self) -> BTreeMap<u32, u64> {
+        self.quarantined_senders.lock().unwrap().clone()
+    }
+
+    // # Method Description:
+    // This method counts a duplicate message the local queues of the thread did not store again.
+    //
+    // # Parameters:
+    // * sender - The ID of the thread that sent the message.
+    pub fn record_duplicate(&self, sender: u32) {
+        *self.duplicates.lock().unwrap().entry(sender).or_default() += 1;
+    }
+
+    pub fn get_duplicates(&self) -> BTreeMap<u32, u64> {
+        self.duplicates.lock().unwrap().clone()
+    }
+
+    // # Method Description:
+    // This method counts an object the local queues of the thread evicted before it was received (see `QueueEviction`).
+    //
+    // # Parameters:
+    // * sender - The ID of the thread that sent the object.
+    pub fn record_evicted(&self, sender: u32) {
+        *self.evicted.lock().unwrap().entry(sender).or_default() += 1;
+    }
+
+    pub fn get_evicted(&self) -> BTreeMap<u32, u64> {
+        self.evicted.lock().unwrap().clone()
+    }
+
+    // # Method Description:
+    // This method counts an instance delivered by the reliable handle of the thread, on the fast path (see
+    // `ClusterConfig::with_fast_path`) or on the Vote quorum.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol the instance belongs to.
+    // * fast - Whether the instance was delivered on the fast path.
+    pub fn record_delivery_path(&self, protocol_information: &str, fast: bool) {
+        let mut delivery_paths = self.delivery_paths.lock().unwrap();
+        delivery_paths.entry(protocol_information.to_string()).or_default().record(fast);
+    }
+
+    pub fn get_delivery_paths(&self) -> BTreeMap<String, DeliveryPaths> {
+        self.delivery_paths.lock().unwrap().clone()
+    }
+
+    // # Method Description:
+    // This method records that the demultiplexer of the thread resized a saturated lane.
+    //
+    // # Parameters:
+    // * lane_resize - The resize.
+    pub fn record_lane_resize(&self, lane_resize: LaneResize) {
+        self.lane_resizes.lock().unwrap().push(lane_resize);
+    }
+
+    pub fn get_lane_resizes(&self) -> Vec<LaneResize> {
+        self.lane_resizes.lock().unwrap().clone()
+    }
+
+    // # Method Description:
+    // This method counts a signal frame received for an instance of the given protocol.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol the instance belongs to.
+    // * size - The size of the frame, in bytes.
+    pub fn record_traffic(&self, protocol_information: &str, size: usize) {
+        let mut traffic = self.traffic.lock().unwrap();
+        traffic.entry(protocol_information.to_string()).or_default().record(size);
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the signal frames received for every protocol.
+    //
+    // # Returns:
+    // * The `TrafficCount`s keyed by protocol information.
+    pub fn get_traffic(&self) -> BTreeMap<String, TrafficCount> {
+        let traffic = self.traffic.lock().unwrap();
+        traffic.iter().map(|(protocol_information, count)| (protocol_information.clone(), *count)).collect()
+    }
+
+    // # Method Description:
+    // This method counts the signal frames sent (broadcast or relayed) in a round of the given protocol.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol the round belongs to.
+    // * round_number - The round the frames belong to.
+    // * frames - The number of frames sent.
+    pub fn record_sent_signals(&self, protocol_information: &str, round_number: Round, frames: u64) {
+        let mut sent_signals = self.sent_signals.lock().unwrap();
+        *sent_signals.entry((protocol_information.to_string(), round_number)).or_default() += frames;
+    }
+
+    // # Method Description:
+    // This method returns the number of signal frames sent in a round of the given protocol so far.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol the round belongs to.
+    // * round_number - The round whose frames are counted.
+    //
+    // # Returns:
+    // * The number of frames sent, 0 if none was.
+    pub fn get_sent_signals(&self, protocol_information: &str, round_number: Round) -> u64 {
+        let sent_signals = self.sent_signals.lock().unwrap();
+        sent_signals.get(&(protocol_information.to_string(), round_number)).copied().unwrap_or(0)
+    }
+
+    // # Method Description:
+    // This method counts a frame sent by the signal batcher of the thread.
+    //
+    // # Parameters:
+    // * signals - The number of signals the frame carried.
+    pub fn record_signal_batch(&self, signals: usize) {
+        self.signal_batches.lock().unwrap().record(signals);
+    }
+
+    pub fn get_signal_batches(&self) -> BatchCount {
+        *self.signal_batches.lock().unwrap()
+    }
+
+    // # Method Description:
+    // This method records the latest status of a round, replacing the previous one, and narrates the steps
+    // the round took in demo mode. The time a round is first recorded as delivered is kept as its delivery time.
+    //
+    // # Parameters:
+    // * round_status - The status of the round.
+    pub fn record_round_status(&self, mut round_status: RoundStatus) {
+        let mut round_statuses = self.round_statuses.lock().unwrap();
+        let key = (round_status.protocol_information.clone(), round_status.instance.clone(), round_status.round_number);
+        if round_status.delivered {
+            round_status.delivered_at = round_statuses.get(&key).and_then(|previous| previous.delivered_at)
+                .or(self.started.map(|started| started.elapsed()));
+        }
+        match &self.narrator {
+            Some(narrator) => {
+                let previous = round_statuses.insert(key, round_status.clone());
+                narrator.narrate(previous.as_ref(), &round_status);
+            },
+            None => {
+                round_statuses.insert(key, round_status);
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the status of every round recorded so far.
+    //
+    // # Returns:
+    // * The `RoundStatus`es, ordered by protocol information, instance, and round number.
+    pub fn get_round_statuses(&self) -> Vec<RoundStatus> {
+        let round_statuses = self.round_statuses.lock().unwrap();
+        round_statuses.values().cloned().collect()
+    }
+
+    pub fn get_health_probe(&self) -> &HealthProbe {
+        &self.health_probe
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the liveness of a thread, counting its completed rounds from the
+    // round statuses recorded so far.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    //
+    // # Returns:
+    // * The `NodeHealth` of the thread.
+    pub fn health(&self, id: u32) -> NodeHealth {
+        let mut rounds_completed: BTreeMap<String, u64> = BTreeMap::new();
+        for round_status in self.round_statuses.lock().unwrap().values().filter(|round_status| round_status.delivered) {
+            *rounds_completed.entry(round_status.protocol_information.clone()).or_default() += 1;
+        }
+        self.health_probe.snapshot(id, rounds_completed)
+    }
+
+    // # Method Description:
+    // This method exports the metrics collected so far as the report of a thread.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster the thread belongs to.
+    //
+    // # Returns:
+    // * The `MetricsReport` of the thread.
+    pub fn report(&self, id: u32, config: ClusterConfig) -> MetricsReport {
+        MetricsReport::new(id, config, self.phase_latencies.get_all(), self.get_decode_failures(), self.get_traffic(), self.get_round_statuses())
+            .with_quarantined(self.get_quarantined())
+            .with_quarantined_senders(self.get_quarantined_senders())
+            .with_duplicates(self.get_duplicates())
+            .with_lane_resizes(self.get_lane_resizes())
+            .with_delivery_paths(self.get_delivery_paths())
+    }
+}
+
+// # Struct Description:
+// This struct counts the instances of one protocol a thread's reliable handle delivered on each path, so
+// that the benefit of the fast path can be quantified (see `ClusterConfig::with_fast_path`).
+//
+// # Fields:
+// * fast - The number of instances delivered on the Echo signals of every thread.
+// * slow - The number of instances delivered on the Vote quorum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DeliveryPaths {
+    fast: u64,
+    slow: u64,
+}
+
+impl DeliveryPaths {
+    pub fn record(&mut self, fast: bool) {
+        match fast {
+            true => self.fast += 1,
+            false => self.slow += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &DeliveryPaths) {
+        self.fast += other.fast;
+        self.slow += other.slow;
+    }
+
+    pub fn get_fast(&self) -> u64 {
+        self.fast
+    }
+
+    pub fn get_slow(&self) -> u64 {
+        self.slow
+    }
+}
+
+// # Struct Description:
+// This struct counts the signal frames received by a thread's reliable handle for one protocol, i.e. the
+// message complexity (and volume) of the protocol as seen by the thread.
+//
+// # Fields:
+// * frames - The number of frames received.
+// * bytes - The total size of the frames received, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TrafficCount {
+    frames: u64,
+    bytes: u64,
+}
+
+impl TrafficCount {
+    pub fn record(&mut self, size: usize) {
+        self.frames += 1;
+        self.bytes += size as u64;
+    }
+
+    pub fn merge(&mut self, other: &TrafficCount) {
+        self.frames += other.frames;
+        self.bytes += other.bytes;
+    }
+
+    pub fn get_frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn get_bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+// # Struct Description:
+// This struct is the progress of a round towards one of its quorums.
+//
+// # Fields:
+// * name - What is counted (e.g. "values", "witnesses").
+// * count - The number counted so far.
+// * quorum - The number needed to complete the stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundStage {
+    name: String,
+    count: u64,
+    quorum: u64,
+}
+
+impl RoundStage {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn get_quorum(&self) -> u64 {
+        self.quorum
+    }
+}
+
+// # Struct Description:
+// This struct is the status of a round (or reliable broadcast instance) as last seen by a handle: its
+// stages, in protocol order, and whether it was delivered.
+//
+// # Fields:
+// * protocol_information - The protocol the round belongs to.
+// * instance - The instance within the round, for protocols with several instances per round (e.g. reliable broadcast).
+// * round_number - The round number.
+// * stages - The progress of the round towards each of its quorums, in protocol order.
+// * delivered - Whether the round was delivered.
+// * delivered_at - When the round was delivered, since the creation of the thread's metrics, if it was.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundStatus {
+    protocol_information: String,
+    instance: Option<String>,
+    round_number: Round,
+    stages: Vec<RoundStage>,
+    delivered: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delivered_at: Option<Duration>,
+}
+
+impl RoundStatus {
+    pub fn new(protocol_information: String, instance: Option<String>, round_number: Round, delivered: bool) -> Self {
+        Self {
+            protocol_information,
+            instance,
+            round_number,
+            stages: vec![],
+            delivered,
+            delivered_at: None,
+        }
+    }
+
+    pub fn with_stage(mut self, name: &str, count: u64, quorum: u64) -> Self {
+        self.stages.push(RoundStage { name: name.to_string(), count, quorum });
+        self
+    }
+
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_instance(&self) -> Option<&String> {
+        self.instance.as_ref()
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_stages(&self) -> &Vec<RoundStage> {
+        &self.stages
+    }
+
+    pub fn get_delivered(&self) -> bool {
+        self.delivered
+    }
+
+    pub fn get_delivered_at(&self) -> Option<Duration> {
+        self.delivered_at
+    }
+
+    // # Method Description:
+    // This method explains why an undelivered round has not completed.
+    //
+    // # Returns:
+    // * `None` if the round was delivered, otherwise the first stage whose quorum was not reached.
+    pub fn get_stall_reason(&self) -> Option<String> {
+        if self.delivered {
+            return None
+        }
+        match self.stages.iter().find(|stage| stage.count < stage.quorum) {
+            Some(stage) => Some(format!("waiting for {} ({}/{})", stage.name, stage.count, stage.quorum)),
+            None => Some(String::from("every quorum reached, waiting for delivery")),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct holds the statistics a thread embeds in the reports and aggregated reports it broadcasts,
+// with `ClusterConfig::with_frame_statistics`, so that exported frames can be analyzed on their own,
+// without joining them against the metric reports of their creators.
+//
+// # Fields:
+// * values - The number of values the creator had collected in the round.
+// * witnesses - The number of witnesses the report combines (the witnesses the creator had collected for a
+//   report, the witnesses of the level below for an aggregated report).
+// * validity_threshold - The validity threshold of the cluster when the report was created.
+// * agreement_threshold - The agreement threshold of the cluster when the report was created.
+// * created_at - When the report was created, in milliseconds since the UNIX epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FrameStatistics {
+    values: u32,
+    witnesses: u32,
+    validity_threshold: u64,
+    agreement_threshold: u64,
+    created_at: u64,
+}
+
+impl FrameStatistics {
+    // # Function Description:
+    // This function records the statistics of a report created now.
+    //
+    // # Parameters:
+    // * values - The number of values the creator had collected in the round.
+    // * witnesses - The number of witnesses the report combines.
+    // * thresholds - The thresholds of the cluster.
+    //
+    // # Returns:
+    // * The statistics.
+    pub fn new(values: usize, witnesses: usize, thresholds: &Thresholds) -> Self {
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_millis() as u64).unwrap_or(0);
+        Self {
+            values: values as u32,
+            witnesses: witnesses as u32,
+            validity_threshold: thresholds.get_validity_threshold(),
+            agreement_threshold: thresholds.get_agreement_threshold(),
+            created_at
+        }
+    }
+
+    pub fn get_values(&self) -> u32 {
+        self.values
+    }
+
+    pub fn get_witnesses(&self) -> u32 {
+        self.witnesses
+    }
+
+    pub fn get_validity_threshold(&self) -> u64 {
+        self.validity_threshold
+    }
+
+    pub fn get_agreement_threshold(&self) -> u64 {
+        self.agreement_threshold
+    }
+
+    pub fn get_created_at(&self) -> u64 {
+        self.created_at
+    }
+}
+
+// # Struct Description:
+// This struct is the exported metric report of a single thread. It embeds the `ClusterConfig` the 
+// thread ran with, so that the report records exactly how its measurements were produced.
+//
+// # Fields:
+// * id - The ID of the thread that measured the latencies.
+// * config - The configuration of the cluster the thread belonged to.
+// * phase_latencies - The phase histograms measured by the thread, keyed by protocol information.
+// * decode_failures - The number of frames the thread could not decode, keyed by lane name.
+// * traffic - The signal frames received by the thread, keyed by protocol information.
+// * round_statuses - The status of every round the thread's handles took part in.
+// * quarantined - The number of frames the thread quarantined, keyed by reason.
+// * quarantined_senders - The number of frames the thread quarantined, keyed by the ID of their sender, when known.
+// * duplicates - The number of duplicate messages the thread did not store again, keyed by the ID of their sender.
+// * lane_resizes - The resizes of the thread's saturated lanes, in order.
+// * delivery_paths - The number of instances the thread delivered on the fast and the slow path, keyed by protocol information.
+// * gst - The global stabilization time of the emulated network, if it is partially synchronous, so that the
+//   delivery times of the rounds can be reported relative to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsReport {
+    id: u32,
+    config: ClusterConfig,
+    phase_latencies: BTreeMap<String, PhaseHistogram>,
+    decode_failures: BTreeMap<String, u64>,
+    #[serde(default)]
+    traffic: BTreeMap<String, TrafficCount>,
+    #[serde(default)]
+    round_statuses: Vec<RoundStatus>,
+    #[serde(default)]
+    quarantined: BTreeMap<String, u64>,
+    #[serde(default)]
+    quarantined_senders: BTreeMap<u32, u64>,
+    #[serde(default)]
+    duplicates: BTreeMap<u32, u64>,
+    #[serde(default)]
+    lane_resizes: Vec<LaneResize>,
+    #[serde(default)]
+    delivery_paths: BTreeMap<String, DeliveryPaths>,
+    #[serde(default)]
+    gst: Option<Duration>,
+}
+
+impl MetricsReport {
+    pub fn new(id: u32, config: ClusterConfig, phase_latencies: BTreeMap<String, PhaseHistogram>, decode_failures: BTreeMap<String, u64>, traffic: BTreeMap<String, TrafficCount>, round_statuses: Vec<RoundStatus>) -> Self {
+        let gst = config.get_gst();
+        Self {
+            id,
+            config,
+            phase_latencies,
+            decode_failures,
+            traffic,
+            round_statuses,
+            quarantined: BTreeMap::new(),
+            quarantined_senders: BTreeMap::new(),
+            duplicates: BTreeMap::new(),
+            lane_resizes: vec![],
+            delivery_paths: BTreeMap::new(),
+            gst,
+        }
+    }
+
+    pub fn with_quarantined(mut self, quarantined: BTreeMap<String, u64>) -> Self {
+        self.quarantined = quarantined;
+        self
+    }
+
+    pub fn with_quarantined_senders(mut self, quarantined_senders: BTreeMap<u32, u64>) -> Self {
+        self.quarantined_senders = quarantined_senders;
+        self
+    }
+
+    pub fn with_duplicates(mut self, duplicates: BTreeMap<u32, u64>) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    pub fn with_lane_resizes(mut self, lane_resizes: Vec<LaneResize>) -> Self {
+        self.lane_resizes = lane_resizes;
+        self
+    }
+
+    pub fn with_delivery_paths(mut self, delivery_paths: BTreeMap<String, DeliveryPaths>) -> Self {
+        self.delivery_paths = delivery_paths;
+        self
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    pub fn get_phase_latencies(&self) -> &BTreeMap<String, PhaseHistogram> {
+        &self.phase_latencies
+    }
+
+    pub fn get_decode_failures(&self) -> &BTreeMap<String, u64> {
+        &self.decode_failures
+    }
+
+    pub fn get_traffic(&self) -> &BTreeMap<String, TrafficCount> {
+        &self.traffic
+    }
+
+    pub fn get_round_statuses(&self) -> &Vec<RoundStatus> {
+        &self.round_statuses
+    }
+
+    pub fn get_quarantined(&self) -> &BTreeMap<String, u64> {
+        &self.quarantined
+    }
+
+    pub fn get_quarantined_senders(&self) -> &BTreeMap<u32, u64> {
+        &self.quarantined_senders
+    }
+
+    pub fn get_duplicates(&self) -> &BTreeMap<u32, u64> {
+        &self.duplicates
+    }
+
+    pub fn get_lane_resizes(&self) -> &Vec<LaneResize> {
+        &self.lane_resizes
+    }
+
+    pub fn get_delivery_paths(&self) -> &BTreeMap<String, DeliveryPaths> {
+        &self.delivery_paths
+    }
+
+    pub fn get_gst(&self) -> Option<Duration> {
+        self.gst
+    }
+
+    // # Method Description:
+    // This method measures the liveness of the thread relative to GST: how long after GST its last round
+    // was delivered.
+    //
+    // # Returns:
+    // * The time from GST to the last delivery, zero if every round was delivered before GST, or `None` if the
+    //   network has no GST, or a round was not delivered (or has no delivery time).
+    pub fn get_delivery_after_gst(&self) -> Option<Duration> {
+        let gst = self.gst?;
+        let mut last_delivery = Duration::ZERO;
+        for round_status in &self.round_statuses {
+            last_delivery = last_delivery.max(round_status.delivered_at?);
+        }
+        Some(last_delivery.saturating_sub(gst))
+    }
+}
+
+impl JsonConversion<MetricsReport> for MetricsReport {}
+
+// # Function Description:
+// This function reads an export of the metric reports of a run: one `MetricsReport` in JSON per line, as
+// written by `write_metrics_export`. Empty lines are skipped.
+//
+// # Parameters:
+// * data - The content of the export.
+//
+// # Returns:
+// * The `MetricsReport`s of the run, or the error of the first line that could not be parsed, with its line number.
+pub fn read_metrics_export(data: &str) -> Result<Vec<MetricsReport>, String> {
+    data.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| MetricsReport::read_json(&line.to_string()).map_err(|error| format!("line {}: {error}", index + 1)))
+        .collect()
+}
+
+// # Function Description:
+// This function writes the metric reports of a run as an export readable by `read_metrics_export`.
+//
+// # Parameters:
+// * reports - The `MetricsReport`s of the run, e.g. one per thread.
+//
+// # Returns:
+// * The export, one report per line.
+pub fn write_metrics_export(reports: &[MetricsReport]) -> String {
+    reports.iter().map(|report| report.write_json() + "\n").collect()
+}
+
+// The percentiles of every phase compared across runs.
+const COMPARED_PERCENTILES: [f64; 3] = [50.0, 90.0, 99.0];
+
+// # Struct Description:
+// This struct compares one metric of one protocol across two runs. Every compared metric (latency
+// percentiles, frame counts, and bytes) is better when lower.
+//
+// # Fields:
+// * protocol_information - The protocol the metric belongs to.
+// * metric - The name of the metric (e.g. "input_to_echo_quorum p99 (us)", "frames").
+// * baseline - The value of the metric in the baseline run.
+// * candidate - The value of the metric in the candidate run.
+// * regression - Whether the candidate exceeds the baseline by more than the comparison threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    protocol_information: String,
+    metric: String,
+    baseline: u64,
+    candidate: u64,
+    regression: bool,
+}
+
+impl MetricDelta {
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_metric(&self) -> &String {
+        &self.metric
+    }
+
+    pub fn get_baseline(&self) -> u64 {
+        self.baseline
+    }
+
+    pub fn get_candidate(&self) -> u64 {
+        self.candidate
+    }
+
+    pub fn get_regression(&self) -> bool {
+        self.regression
+    }
+
+    // # Method Description:
+    // This method computes the relative change of the metric.
+    //
+    // # Returns:
+    // * The change in percent of the baseline, or `None` if the baseline is zero.
+    pub fn get_change(&self) -> Option<f64> {
+        (self.baseline > 0).then(|| (self.candidate as f64 - self.baseline as f64) / self.baseline as f64 * 100.0)
+    }
+}
+
+// # Struct Description:
+// This struct compares the metric exports of two runs, e.g. before and after a protocol change. The
+// reports of every thread of a run are merged per protocol, then the p50, p90, and p99 of every phase,
+// the number of frames, and the number of bytes are compared, and increases beyond the threshold are
+// flagged as regressions. Protocols found in only one run are compared against zero.
+//
+// # Fields:
+// * threshold - The relative increase tolerated before a metric is flagged, in percent.
+// * deltas - The compared metrics, by protocol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsComparison {
+    threshold: f64,
+    deltas: Vec<MetricDelta>,
+}
+
+impl MetricsComparison {
+    pub fn new(baseline: &[MetricsReport], candidate: &[MetricsReport], threshold: f64) -> Self {
+        let baseline = merge_reports(baseline);
+        let candidate = merge_reports(candidate);
+        let protocols: BTreeSet<&String> = baseline.keys().chain(candidate.keys()).collect();
+        let empty = (PhaseHistogram::default(), TrafficCount::default());
+
+        let mut deltas = vec![];
+        for protocol_information in protocols {
+            let (baseline_histogram, baseline_traffic) = baseline.get(protocol_information).unwrap_or(&empty);
+            let (candidate_histogram, candidate_traffic) = candidate.get(protocol_information).unwrap_or(&empty);
+            let mut compare = |metric: String, baseline: u64, candidate: u64| {
+                let regression = candidate as f64 > baseline as f64 * (1.0 + threshold / 100.0);
+                deltas.push(MetricDelta { protocol_information: protocol_information.clone(), metric, baseline, candidate, regression });
+            };
+
+            for (phase, name) in [(Phase::InputToEchoQuorum, "input_to_echo_quorum"), (Phase::EchoQuorumToVoteQuorum, "echo_quorum_to_vote_quorum"), (Phase::VoteQuorumToDeliver, "vote_quorum_to_deliver")] {
+                for percentile in COMPARED_PERCENTILES {
+                    let baseline_latency = baseline_histogram.get_phase(phase).percentile(percentile).as_micros() as u64;
+                    let candidate_latency = candidate_histogram.get_phase(phase).percentile(percentile).as_micros() as u64;
+                    compare(format!("{name} p{percentile} (us)"), baseline_latency, candidate_latency);
+                }
+            }
+            compare(String::from("frames"), baseline_traffic.get_frames(), candidate_traffic.get_frames());
+            compare(String::from("bytes"), baseline_traffic.get_bytes(), candidate_traffic.get_bytes());
+        }
+
+        Self {
+            threshold,
+            deltas,
+        }
+    }
+
+    pub fn get_threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn get_deltas(&self) -> &Vec<MetricDelta> {
+        &self.deltas
+    }
+
+    pub fn get_regressions(&self) -> Vec<&MetricDelta> {
+        self.deltas.iter().filter(|delta| delta.regression).collect()
+    }
+}
+
+impl JsonConversion<MetricsComparison> for MetricsComparison {}
+
+// # Function Description:
+// This function merges the reports of every thread of a run, per protocol.
+//
+// # Parameters:
+// * reports - The `MetricsReport`s of the run.
+//
+// # Returns:
+// * The merged phase histograms and traffic counts, keyed by protocol information.
+fn merge_reports(reports: &[MetricsReport]) -> BTreeMap<String, (PhaseHistogram, TrafficCount)> {
+    let mut merged: BTreeMap<String, (PhaseHistogram, TrafficCount)> = BTreeMap::new();
+    for report in reports {
+        for (protocol_information, histogram) in report.get_phase_latencies() {
+            merged.entry(protocol_information.clone()).or_default().0.merge(histogram);
+        }
+        for (protocol_information, traffic) in report.get_traffic() {
+            merged.entry(protocol_information.clone()).or_default().1.merge(traffic);
+        }
+    }
+    merged
+}
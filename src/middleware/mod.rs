@@ -0,0 +1,235 @@
+use std::{fmt::Debug, sync::Arc};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Serialize, Deserialize};
+use tokio::sync::mpsc::{self, Sender};
+
+use crate::config::ClusterConfig;
+use crate::multiplex::{Lane, tag_frame, untag_frame};
+use crate::logging::log;
+
+// # Trait Description:
+// A trait implemented by every layer of a `MiddlewareChain`. A layer transforms the payload of every
+// frame a thread sends, and reverses the transformation on every frame it receives, so that cross-cutting
+// features (compression, encryption, integrity checks, tracing) are written once, for every protocol and
+// lane, instead of each hard-coding its place in `MessageChannels`, `SignalChannels`, or `ReportChannels`.
+// Layers only see the payload of a frame: its lane tag stays readable, so transports can still route
+// frames by lane.
+pub trait FrameLayer: Debug + Send + Sync {
+    // # Method Description:
+    // This method transforms the payload of a frame sent by the thread.
+    //
+    // # Parameters:
+    // * lane - The lane of the frame.
+    // * payload - The payload, as produced by the previous layer of the chain.
+    //
+    // # Returns:
+    // * The transformed payload.
+    fn outbound(&self, lane: Lane, payload: Bytes) -> Bytes;
+
+    // # Method Description:
+    // This method reverses `outbound` on the payload of a frame received by the thread.
+    //
+    // # Parameters:
+    // * lane - The lane of the frame.
+    // * payload - The payload, as restored by the next layer of the chain.
+    //
+    // # Returns:
+    // * The restored payload, or a description of why the payload was rejected.
+    fn inbound(&self, lane: Lane, payload: Bytes) -> Result<Bytes, String>;
+}
+
+// # Enum Description:
+// This enum represents the built-in layers a hub can be configured with (see `ClusterConfig::with_layer`).
+//
+// # Variants:
+// * Trace - Prints the lane and size of every frame sent and received, without changing it.
+// * Checksum - Appends the FNV-1a digest of every payload sent, and rejects received payloads whose digest
+//   does not match, e.g. to catch frames corrupted by a custom transport.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Layer {
+    Trace,
+    Checksum,
+}
+
+impl Layer {
+    // # Method Description:
+    // This method creates the layer.
+    //
+    // # Returns:
+    // * The layer, ready to be added to a `MiddlewareChain`.
+    pub fn create(&self) -> Arc<dyn FrameLayer> {
+        match self {
+            Layer::Trace => Arc::new(TraceLayer),
+            Layer::Checksum => Arc::new(ChecksumLayer),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct holds the ordered layers applied to the frames of a thread. Sent frames go through the
+// layers in order, and received frames through the same layers in reverse order, so the first layer is
+// the closest to the protocols and the last one the closest to the wire.
+//
+// # Fields:
+// * layers - The layers, in outbound order.
+#[derive(Debug, Clone, Default)]
+pub struct MiddlewareChain {
+    layers: Vec<Arc<dyn FrameLayer>>,
+}
+
+impl MiddlewareChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // # Function Description:
+    // This function creates the chain of the layers configured for a hub.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The chain, empty if no layer is configured.
+    pub fn from_config(config: &ClusterConfig) -> Self {
+        config.get_layers().iter().fold(Self::new(), |chain, layer| chain.with_layer(layer.create()))
+    }
+
+    // # Method Description:
+    // This method appends a layer to the chain, closest to the wire.
+    //
+    // # Returns:
+    // * The updated chain.
+    pub fn with_layer(mut self, layer: Arc<dyn FrameLayer>) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    // # Method Description:
+    // This method applies the layers to a frame sent by the thread. Untagged frames are sent unchanged.
+    //
+    // # Parameters:
+    // * frame - The tagged frame.
+    //
+    // # Returns:
+    // * The tagged frame, as sent over the wire.
+    pub fn outbound(&self, frame: Bytes) -> Bytes {
+        if self.is_empty() {
+            return frame
+        }
+        let Some((lane, payload)) = untag_frame(&frame) else {
+            return frame
+        };
+        let payload = self.layers.iter().fold(payload, |payload, layer| layer.outbound(lane, payload));
+        tag_frame(lane, payload)
+    }
+
+    // # Method Description:
+    // This method reverses the layers on a frame received by the thread. Untagged frames are received unchanged.
+    //
+    // # Parameters:
+    // * frame - The tagged frame, as received from the wire.
+    //
+    // # Returns:
+    // * The restored tagged frame, or a description of the layer that rejected it.
+    pub fn inbound(&self, frame: Bytes) -> Result<Bytes, String> {
+        if self.is_empty() {
+            return Ok(frame)
+        }
+        let Some((lane, mut payload)) = untag_frame(&frame) else {
+            return Ok(frame)
+        };
+        for layer in self.layers.iter().rev() {
+            payload = layer.inbound(lane, payload)?;
+        }
+        Ok(tag_frame(lane, payload))
+    }
+
+    // # Method Description:
+    // This method routes the frames sent over transmitters through the chain: each transmitter is replaced
+    // by one whose frames are transformed by a forwarding task before reaching the original transmitter.
+    // Without layers, the transmitters are returned as is.
+    //
+    // # Parameters:
+    // * transmitters - The transmitters of a thread, indexed by thread ID.
+    // * buffer_size - The capacity of the channel in front of every forwarding task.
+    //
+    // # Returns:
+    // * The transmitters to hand to the thread, indexed by thread ID.
+    pub fn wrap_transmitters(&self, transmitters: Vec<Sender<Bytes>>, buffer_size: usize) -> Vec<Sender<Bytes>> {
+        if self.is_empty() {
+            return transmitters
+        }
+        transmitters.into_iter().map(|transmitter| {
+            let (tx, mut rx) = mpsc::channel::<Bytes>(buffer_size);
+            let chain = self.clone();
+            tokio::spawn(async move {
+                while let Some(frame) = rx.recv().await {
+                    if transmitter.send(chain.outbound(frame)).await.is_err() {
+                        return
+                    }
+                }
+            });
+            tx
+        }).collect()
+    }
+}
+
+// # Struct Description:
+// This struct is the `Layer::Trace` layer.
+#[derive(Debug, Clone, Copy)]
+struct TraceLayer;
+
+impl FrameLayer for TraceLayer {
+    fn outbound(&self, lane: Lane, payload: Bytes) -> Bytes {
+        log!(debug, "middleware trace: sending {:?} frame of {} bytes", lane, payload.len());
+        payload
+    }
+
+    fn inbound(&self, lane: Lane, payload: Bytes) -> Result<Bytes, String> {
+        log!(debug, "middleware trace: receiving {:?} frame of {} bytes", lane, payload.len());
+        Ok(payload)
+    }
+}
+
+// # Struct Description:
+// This struct is the `Layer::Checksum` layer. The digest is appended to the payload as 8 big-endian bytes.
+#[derive(Debug, Clone, Copy)]
+struct ChecksumLayer;
+
+// The size of the digest appended by the `Layer::Checksum` layer.
+const CHECKSUM_SIZE: usize = 8;
+
+impl ChecksumLayer {
+    fn digest(payload: &[u8]) -> u64 {
+        let mut digest: u64 = 0xcbf29ce484222325;
+        for byte in payload {
+            digest ^= *byte as u64;
+            digest = digest.wrapping_mul(0x100000001b3);
+        }
+        digest
+    }
+}
+
+impl FrameLayer for ChecksumLayer {
+    fn outbound(&self, _lane: Lane, payload: Bytes) -> Bytes {
+        let mut checked = BytesMut::with_capacity(payload.len() + CHECKSUM_SIZE);
+        checked.put_slice(&payload);
+        checked.put_u64(Self::digest(&payload));
+        checked.freeze()
+    }
+
+    fn inbound(&self, lane: Lane, payload: Bytes) -> Result<Bytes, String> {
+        let Some(content_size) = payload.len().checked_sub(CHECKSUM_SIZE) else {
+            return Err(format!("{lane:?} frame without checksum"))
+        };
+        let digest = u64::from_be_bytes(payload[content_size..].try_into().expect("8 bytes"));
+        match digest == Self::digest(&payload[..content_size]) {
+            true => Ok(payload.slice(..content_size)),
+            false => Err(format!("{lane:?} frame with a mismatched checksum")),
+        }
+    }
+}
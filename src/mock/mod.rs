@@ -0,0 +1,643 @@
+use std::{fmt::Debug, hash::Hash, collections::BTreeMap, sync::atomic::{AtomicU64, Ordering}};
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::{task::JoinHandle, sync::{broadcast, mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender}}};
+use async_trait::async_trait;
+
+use crate::basic::{BasicCommunication, BasicQueues, Message, MessageChannels};
+use crate::reliable::{ReliableCommunication, Signal, ChannelType, ObjectContent, SignalChannels, ReliableHandleCommand, DeliveryWatches};
+use crate::witness::{WitnessCommunication, WitnessRoundContent, Report, ReportType, ReportChannels, WitnessHandleCommand, RoundSubscriptions};
+use crate::json::{Codec, WireFormat};
+use crate::multiplex::{Lane, untag_frame};
+use crate::config::ClusterConfig;
+use crate::events::Event;
+use crate::metrics::{FrameStatistics, Metrics};
+use crate::drive::{PolledHandles, ShutdownReport};
+use crate::round::{Instance, Round};
+
+// The capacity of the channels recording the mock's outgoing traffic and buffering its scripted deliveries.
+const MOCK_BUFFER_SIZE: usize = 1024;
+
+// # Function Description:
+// This function spawns the reliable handle of a mock communicator, which runs no protocol: it only
+// answers inspection queries, with no instances, and stops when shut down, with no instance open.
+//
+// # Parameters:
+// * id - The ID of the mock.
+// * command_receiver - The receiver of the commands sent to the handle.
+//
+// # Returns:
+// * A `JoinHandle` to the spawned task, that runs until explicitly terminated or shut down.
+fn spawn_mock_reliable_handle(id: u32, mut command_receiver: UnboundedReceiver<ReliableHandleCommand>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(command) = command_receiver.recv().await {
+            match command {
+                ReliableHandleCommand::Inspect(responder) => {
+                    let _ = responder.send(BTreeMap::new());
+                },
+                ReliableHandleCommand::Snapshot(responder) => {
+                    let _ = responder.send(String::from("{}"));
+                },
+                ReliableHandleCommand::Shutdown(responder) => {
+                    let _ = responder.send(ShutdownReport::new(id, String::from("reliable"), 0, vec![]));
+                    return
+                },
+            }
+        }
+    })
+}
+
+// # Struct Description:
+// This struct records the traffic sent by a mock communicator. Every transmitter handed to the
+// mock's channels leads to one of its receivers, so that sends and broadcasts are captured
+// instead of reaching other threads.
+//
+// # Fields:
+// * signal_rx - A receiver capturing the signals broadcast through the mock's `SignalChannels`.
+// * outbox_rxs - One receiver per thread, capturing the messages sent to that thread through the mock's `MessageChannels`.
+// * codec - The wire format of the captured frames.
+// * broadcasts - The messages broadcast so far, in order.
+// * sends - The messages sent so far with `basic_send`/`basic_broadcast`, paired with their destination.
+struct MockRecorder<T> {
+    signal_rx: Receiver<Bytes>,
+    outbox_rxs: Vec<Receiver<Bytes>>,
+    codec: Codec,
+    broadcasts: Vec<Message<T>>,
+    sends: Vec<(u32, Message<T>)>,
+}
+
+impl<T> MockRecorder<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Method Description:
+    // This method creates a recorder and the channels whose traffic it captures.
+    //
+    // # Parameters:
+    // * thread_count - The number of threads the mock can send to.
+    // * codec - The wire format of the mock's frames.
+    //
+    // # Returns:
+    // * The recorder, the `MessageChannels` and the `SignalChannels` to hand to the mock.
+    fn new(thread_count: u32, codec: Codec) -> (Self, MessageChannels<T>, SignalChannels<T>) {
+        let (signal_tx, signal_rx) = mpsc::channel(MOCK_BUFFER_SIZE);
+        let mut outbox_txs = vec![];
+        let mut outbox_rxs = vec![];
+        for _ in 0..thread_count {
+            let (outbox_tx, outbox_rx) = mpsc::channel(MOCK_BUFFER_SIZE);
+            outbox_txs.push(outbox_tx);
+            outbox_rxs.push(outbox_rx);
+        }
+
+        let recorder = Self {
+            signal_rx,
+            outbox_rxs,
+            codec,
+            broadcasts: vec![],
+            sends: vec![],
+        };
+        (recorder, MessageChannels::new(outbox_txs).with_codec(codec), SignalChannels::new(vec![signal_tx]).with_codec(codec))
+    }
+
+    // # Method Description:
+    // This method moves the traffic captured since the last call into the recorded lists.
+    // Captured frames are tagged with their lane, as on a thread's channel.
+    fn drain(&mut self) {
+        while let Ok(received_signal) = self.signal_rx.try_recv() {
+            if let Some((Lane::Signal, received_signal)) = untag_frame(&received_signal)
+                && let Ok(signal) = Signal::<T>::read_frame(self.codec, &received_signal)
+                && let ObjectContent::Message(message) = signal.get_content() {
+                self.broadcasts.push(message.clone());
+            }
+        }
+        for (id, outbox_rx) in self.outbox_rxs.iter_mut().enumerate() {
+            while let Ok(received_message) = outbox_rx.try_recv() {
+                if let Some((Lane::Basic, received_message)) = untag_frame(&received_message)
+                    && let Ok(message) = Message::<T>::read_frame(self.codec, &received_message) {
+                    self.sends.push((id as u32, message));
+                }
+            }
+        }
+    }
+}
+
+// # Function Description:
+// This function queues a scripted delivery on a mock's local queue.
+//
+// # Parameters:
+// * delivery_tx - The transmitter feeding the mock's `BasicQueues`.
+// * frame - The serialized `Message` or `Report` to deliver.
+//
+// # Panics:
+// * If more than `MOCK_BUFFER_SIZE` deliveries are pending.
+fn script_delivery(delivery_tx: &Sender<Bytes>, frame: Bytes) {
+    if delivery_tx.try_send(frame).is_err() {
+        panic!("Error: too many pending scripted deliveries on the mock communicator");
+    }
+}
+
+// # Struct Description:
+// This struct is a drop-in stand-in for a `ReliableCommunicator`, meant for the unit tests of
+// applications built on this crate. It implements `BasicCommunication` and `ReliableCommunication`
+// without running any protocol: broadcasts and sends are recorded, and the messages returned by
+// `basic_recv`/`reliable_recv` are scripted by the test with `deliver_basic`/`deliver_reliable`.
+//
+// # Fields:
+// * id - The ID of the simulated thread.
+// * basic_channels - `MessageChannels` whose traffic is recorded.
+// * signal_channels - `SignalChannels` whose traffic is recorded.
+// * queues - The `BasicQueues` serving the scripted deliveries.
+// * delivery_tx - A transmitter feeding `queues` with scripted deliveries.
+// * scripted_sends - The number of basic messages scripted so far, with which the next one is numbered.
+// * recorder - The `MockRecorder` capturing the outgoing traffic.
+// * handle_rx - A receiver handed out by `take_reliable_handle_rx`; nothing is ever sent on it.
+// * command_tx - A transmitter for sending commands (e.g. inspection queries) to the mock reliable handle.
+// * command_rx - A receiver for commands, taken by the mock reliable handle.
+// * event_tx - A transmitter for `Event`s; the mock never publishes any on its own.
+// * metrics - Phase latency histograms; the mock never records any on its own.
+// * delivery_watches - Watch channels of scripted reliable deliveries.
+// * polled_handles - Always empty: the mock runs no handle to drive.
+// * config - The configuration of the simulated cluster.
+pub struct MockReliableCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    id: u32,
+    basic_channels: MessageChannels<T>,
+    signal_channels: SignalChannels<T>,
+    queues: BasicQueues<T>,
+    delivery_tx: Sender<Bytes>,
+    scripted_sends: AtomicU64,
+    recorder: MockRecorder<T>,
+    handle_rx: Option<Receiver<Bytes>>,
+    command_tx: UnboundedSender<ReliableHandleCommand>,
+    command_rx: Option<UnboundedReceiver<ReliableHandleCommand>>,
+    event_tx: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
+}
+
+impl<T> MockReliableCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(id: u32, thread_count: u32) -> Self {
+        Self::with_config(id, ClusterConfig::new(thread_count))
+    }
+
+    pub fn with_config(id: u32, config: ClusterConfig) -> Self {
+        let (recorder, basic_channels, signal_channels) = MockRecorder::new(config.get_thread_count(), config.get_codec());
+        let (delivery_tx, delivery_rx) = mpsc::channel(MOCK_BUFFER_SIZE);
+        let queues = BasicQueues::new(delivery_rx, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy()).with_validation(id, &config);
+        let (_, handle_rx) = mpsc::channel(1);
+        let handle_rx = Some(handle_rx);
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        let command_rx = Some(command_rx);
+        let (event_tx, _) = broadcast::channel(config.get_buffer_size());
+        let metrics = Metrics::new();
+        let delivery_watches = DeliveryWatches::new();
+        let polled_handles = PolledHandles::new(None);
+
+        Self {
+            id,
+            basic_channels,
+            signal_channels,
+            queues,
+            delivery_tx,
+            scripted_sends: AtomicU64::new(0),
+            recorder,
+            handle_rx,
+            command_tx,
+            command_rx,
+            event_tx,
+            metrics,
+            delivery_watches,
+            polled_handles,
+            config,
+        }
+    }
+
+    // # Method Description:
+    // This method scripts a message sent with `basic_send`/`basic_broadcast` by another thread,
+    // to be returned by a later `basic_recv`. Every scripted message is numbered like a send, so a message
+    // scripted twice is returned twice.
+    //
+    // # Parameters:
+    // * sender - The ID of the thread the message is from.
+    // * message - The content of the message.
+    // * round_number - The round the message belongs to.
+    pub fn deliver_basic(&self, sender: u32, message: T, round_number: Round) {
+        let sequence = self.scripted_sends.fetch_add(1, Ordering::Relaxed) + 1;
+        let delivered_message = Message::new(String::from("basic"), sender, message, None, None, round_number).with_sequence(sequence);
+        script_delivery(&self.delivery_tx, delivered_message.write_frame(self.config.get_codec()));
+    }
+
+    // # Method Description:
+    // This method scripts a message reliably broadcast by another thread, to be returned by a later `reliable_recv`
+    // and published to the watchers of its instance (see `watch_delivery`).
+    //
+    // # Parameters:
+    // * sender - The ID of the thread the message is from.
+    // * message - The content of the message.
+    // * instance_number - The instance the message belongs to.
+    // * round_number - The round the message belongs to.
+    pub fn deliver_reliable(&self, sender: u32, message: T, instance_number: Instance, round_number: Round) {
+        let delivered_message = Message::new(String::from("reliable"), sender, message, None, Some(instance_number), round_number);
+        script_delivery(&self.delivery_tx, delivered_message.write_frame(self.config.get_codec()));
+        self.delivery_watches.publish(instance_number, round_number, delivered_message);
+    }
+
+    // # Method Description:
+    // This method returns every message broadcast through the reliable broadcast protocol so far, in order.
+    pub fn get_recorded_broadcasts(&mut self) -> &Vec<Message<T>> {
+        self.recorder.drain();
+        &self.recorder.broadcasts
+    }
+
+    // # Method Description:
+    // This method returns every message sent with `basic_send`/`basic_broadcast` so far, paired with its destination.
+    pub fn get_recorded_sends(&mut self) -> &Vec<(u32, Message<T>)> {
+        self.recorder.drain();
+        &self.recorder.sends
+    }
+}
+
+#[async_trait]
+impl<T> ReliableCommunication<T> for MockReliableCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // The mock runs no protocol, so the reliable handle only answers inspection queries, with no instances.
+    fn initialize_reliable_handle(&mut self) -> JoinHandle<()> {
+        spawn_mock_reliable_handle(*self.get_id(), self.take_reliable_command_rx())
+    }
+
+    async fn upon_input(_thread_id: u32, _thread_signal_channel: &SignalChannels<T>, _signal: Signal<T>) {}
+    async fn upon_echo(_thread_id: u32, _thread_signal_channel: &SignalChannels<T>, _signal: Signal<T>) {}
+    async fn upon_vote(_thread_id: u32, _channel: ChannelType<T>, _signal: Signal<T>) {}
+
+    fn get_signal_channels(&self) -> &SignalChannels<T> {
+        &self.signal_channels
+    }
+
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>> {
+        &self.event_tx
+    }
+
+    fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T> {
+        &self.delivery_watches
+    }
+
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes> {
+        self.handle_rx.take().unwrap()
+    }
+
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand> {
+        self.command_rx.take().unwrap()
+    }
+
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand> {
+        &self.command_tx
+    }
+}
+
+impl<T> BasicCommunication<T> for MockReliableCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    fn get_channels(&self) -> &MessageChannels<T> {
+        &self.basic_channels
+    }
+
+    fn get_queues(&mut self) -> &mut BasicQueues<T> {
+        &mut self.queues
+    }
+
+    fn get_id(&self) -> &u32 {
+        &self.id
+    }
+
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
+}
+
+// # Struct Description:
+// This struct is a drop-in stand-in for a `WitnessCommunicator`, meant for the unit tests of
+// applications built on this crate. On top of everything `MockReliableCommunicator` offers, the
+// outcome of witness rounds is scripted with `deliver_witness_round`, and is then returned by
+// `witness_collect` and, once the mock witness handle is initialized, by `witness_subscribe`.
+//
+// # Fields:
+// * id - The ID of the simulated thread.
+// * basic_channels - `MessageChannels` whose traffic is recorded.
+// * signal_channels - `SignalChannels` whose traffic is recorded.
+// * report_channels - `ReportChannels` leading to the mock witness handle.
+// * queues - The `BasicQueues` serving the scripted deliveries.
+// * delivery_tx - A transmitter feeding `queues` with scripted deliveries.
+// * scripted_sends - The number of basic messages scripted so far, with which the next one is numbered.
+// * recorder - The `MockRecorder` capturing the outgoing traffic.
+// * reliable_handle_rx - A receiver handed out by `take_reliable_handle_rx`; nothing is ever sent on it.
+// * witness_handle_rx - A receiver of the scripted witness rounds, taken by the mock witness handle.
+// * witness_command_tx - A transmitter for sending commands (e.g. round subscriptions) to the mock witness handle.
+// * witness_command_rx - A receiver for commands, taken by the mock witness handle.
+// * reliable_command_tx - A transmitter for sending commands (e.g. inspection queries) to the mock reliable handle.
+// * reliable_command_rx - A receiver for commands, taken by the mock reliable handle.
+// * event_tx - A transmitter for `Event`s; the mock never publishes any on its own.
+// * metrics - Phase latency histograms; the mock never records any on its own.
+// * delivery_watches - Watch channels of scripted reliable deliveries.
+// * polled_handles - Always empty: the mock runs no handle to drive.
+// * config - The configuration of the simulated cluster.
+pub struct MockWitnessCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    id: u32,
+    basic_channels: MessageChannels<T>,
+    signal_channels: SignalChannels<T>,
+    report_channels: ReportChannels<T>,
+    queues: BasicQueues<T>,
+    delivery_tx: Sender<Bytes>,
+    scripted_sends: AtomicU64,
+    recorder: MockRecorder<T>,
+    reliable_handle_rx: Option<Receiver<Bytes>>,
+    witness_handle_rx: Option<Receiver<Bytes>>,
+    witness_command_tx: UnboundedSender<WitnessHandleCommand<T>>,
+    witness_command_rx: Option<UnboundedReceiver<WitnessHandleCommand<T>>>,
+    reliable_command_tx: UnboundedSender<ReliableHandleCommand>,
+    reliable_command_rx: Option<UnboundedReceiver<ReliableHandleCommand>>,
+    event_tx: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
+}
+
+impl<T> MockWitnessCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(id: u32, thread_count: u32) -> Self {
+        Self::with_config(id, ClusterConfig::new(thread_count))
+    }
+
+    pub fn with_config(id: u32, config: ClusterConfig) -> Self {
+        let (recorder, basic_channels, signal_channels) = MockRecorder::new(config.get_thread_count(), config.get_codec());
+        let (delivery_tx, delivery_rx) = mpsc::channel(MOCK_BUFFER_SIZE);
+        let queues = BasicQueues::new(delivery_rx, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy()).with_validation(id, &config);
+        let (_, reliable_handle_rx) = mpsc::channel(1);
+        let reliable_handle_rx = Some(reliable_handle_rx);
+        let (witness_handle_tx, witness_handle_rx) = mpsc::channel(MOCK_BUFFER_SIZE);
+        let report_channels = ReportChannels::new(vec![witness_handle_tx]).with_codec(config.get_codec());
+        let witness_handle_rx = Some(witness_handle_rx);
+        let (witness_command_tx, witness_command_rx) = mpsc::unbounded_channel();
+        let witness_command_rx = Some(witness_command_rx);
+        let (reliable_command_tx, reliable_command_rx) = mpsc::unbounded_channel();
+        let reliable_command_rx = Some(reliable_command_rx);
+        let (event_tx, _) = broadcast::channel(config.get_buffer_size());
+        let metrics = Metrics::new();
+        let delivery_watches = DeliveryWatches::new();
+        let polled_handles = PolledHandles::new(None);
+
+        Self {
+            id,
+            basic_channels,
+            signal_channels,
+            report_channels,
+            queues,
+            delivery_tx,
+            scripted_sends: AtomicU64::new(0),
+            recorder,
+            reliable_handle_rx,
+            witness_handle_rx,
+            witness_command_tx,
+            witness_command_rx,
+            reliable_command_tx,
+            reliable_command_rx,
+            event_tx,
+            metrics,
+            delivery_watches,
+            polled_handles,
+            config,
+        }
+    }
+
+    // # Method Description:
+    // This method scripts a message sent with `basic_send`/`basic_broadcast` by another thread,
+    // to be returned by a later `basic_recv`. Every scripted message is numbered like a send, so a message
+    // scripted twice is returned twice.
+    //
+    // # Parameters:
+    // * sender - The ID of the thread the message is from.
+    // * message - The content of the message.
+    // * round_number - The round the message belongs to.
+    pub fn deliver_basic(&self, sender: u32, message: T, round_number: Round) {
+        let sequence = self.scripted_sends.fetch_add(1, Ordering::Relaxed) + 1;
+        let delivered_message = Message::new(String::from("basic"), sender, message, None, None, round_number).with_sequence(sequence);
+        script_delivery(&self.delivery_tx, delivered_message.write_frame(self.config.get_codec()));
+    }
+
+    // # Method Description:
+    // This method scripts a message reliably broadcast by another thread, to be returned by a later `reliable_recv`
+    // and published to the watchers of its instance (see `watch_delivery`).
+    //
+    // # Parameters:
+    // * sender - The ID of the thread the message is from.
+    // * message - The content of the message.
+    // * instance_number - The instance the message belongs to.
+    // * round_number - The round the message belongs to.
+    pub fn deliver_reliable(&self, sender: u32, message: T, instance_number: Instance, round_number: Round) {
+        let delivered_message = Message::new(String::from("reliable"), sender, message, None, Some(instance_number), round_number);
+        script_delivery(&self.delivery_tx, delivered_message.write_frame(self.config.get_codec()));
+        self.delivery_watches.publish(instance_number, round_number, delivered_message);
+    }
+
+    // # Method Description:
+    // This method scripts the outcome of a witness round, to be returned by a later `witness_collect`
+    // and by `witness_subscribe`.
+    //
+    // # Parameters:
+    // * round_number - The round whose outcome is scripted.
+    // * values - The values delivered in the round, each paired with the ID of the thread that broadcast it.
+    //   They are delivered in the configured `ValueOrdering`, as a witness handle would.
+    pub fn deliver_witness_round(&self, round_number: Round, values: Vec<(u32, T)>) {
+        let protocol_information = String::from("witness");
+        let instance_number = Instance(0);
+        let value_ordering = self.config.get_value_ordering();
+        let mut messages = vec![];
+        for (sender, value) in values {
+            let message = Message::new(protocol_information.clone(), sender, value, None, Some(instance_number), round_number);
+            value_ordering.insert(&mut messages, message);
+        }
+        let report = Report::new(ReportType::Witness, protocol_information, self.id, messages, None, instance_number, round_number);
+        script_delivery(&self.delivery_tx, report.write_frame(self.config.get_codec()));
+        if self.report_channels.get_handle_channels()[0].try_send(report.write_frame(self.config.get_codec())).is_err() {
+            panic!("Error: too many pending scripted witness rounds on the mock communicator");
+        }
+    }
+
+    // # Method Description:
+    // This method returns every message broadcast through the reliable broadcast protocol so far
+    // (including `witness_broadcast`), in order.
+    pub fn get_recorded_broadcasts(&mut self) -> &Vec<Message<T>> {
+        self.recorder.drain();
+        &self.recorder.broadcasts
+    }
+
+    // # Method Description:
+    // This method returns every message sent with `basic_send`/`basic_broadcast` so far, paired with its destination.
+    pub fn get_recorded_sends(&mut self) -> &Vec<(u32, Message<T>)> {
+        self.recorder.drain();
+        &self.recorder.sends
+    }
+}
+
+#[async_trait]
+impl<T> WitnessCommunication<T> for MockWitnessCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // This method spawns the mock witness handle, which runs no protocol: it only records the scripted
+    // witness rounds and answers round subscriptions with them. Inspection queries are answered with `None`,
+    // and a shutdown with no round open.
+    //
+    // # Returns:
+    // * A `JoinHandle` to the spawned task, that runs until explicitly terminated or shut down.
+    fn initialize_witness_handle(&mut self) -> JoinHandle<()> {
+        let id = *self.get_id();
+        let codec = self.get_config().get_codec();
+        let mut receiver = self.take_witness_handle_rx();
+        let mut command_receiver = self.take_witness_command_rx();
+        let mut subscriptions: RoundSubscriptions<T> = RoundSubscriptions::new();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    Some(command) = command_receiver.recv() => {
+                        match command {
+                            WitnessHandleCommand::Subscribe(protocol_information, round_number, responder) => {
+                                subscriptions.subscribe(protocol_information, round_number, responder);
+                            },
+                            WitnessHandleCommand::Inspect(_, responder) => {
+                                let _ = responder.send(None);
+                            },
+                            WitnessHandleCommand::Snapshot(responder) => {
+                                let _ = responder.send(BTreeMap::new());
+                            },
+                            WitnessHandleCommand::Shutdown(responder) => {
+                                let _ = responder.send(ShutdownReport::new(id, String::from("witness"), 0, vec![]));
+                                return
+                            },
+                        }
+                    }
+                    Some(received_object) = receiver.recv() => {
+                        if let Ok(report) = Report::<T>::read_frame(codec, &received_object) {
+                            subscriptions.complete(report.get_protocol_information().clone(), report.get_round_number(), report.get_messages());
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    // # Method Description:
+    // The mock witness handle never broadcasts reports, so this method does nothing.
+    async fn reliable_broadcast_report(_thread_id: u32, _thread_signal_channel: &SignalChannels<T>, _content: &mut WitnessRoundContent<T>, _dimension: Option<u32>, _round_number: Round, _protocol_information: String, _statistics: Option<FrameStatistics>) {}
+
+    fn take_witness_handle_rx(&mut self) -> Receiver<Bytes> {
+        self.witness_handle_rx.take().unwrap()
+    }
+
+    fn take_witness_command_rx(&mut self) -> UnboundedReceiver<WitnessHandleCommand<T>> {
+        self.witness_command_rx.take().unwrap()
+    }
+
+    fn get_witness_command_channel(&self) -> &UnboundedSender<WitnessHandleCommand<T>> {
+        &self.witness_command_tx
+    }
+
+    fn get_report_channels(&self) -> &ReportChannels<T> {
+        &self.report_channels
+    }
+}
+
+#[async_trait]
+impl<T> ReliableCommunication<T> for MockWitnessCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // The mock runs no protocol, so the reliable handle only answers inspection queries, with no instances.
+    fn initialize_reliable_handle(&mut self) -> JoinHandle<()> {
+        spawn_mock_reliable_handle(*self.get_id(), self.take_reliable_command_rx())
+    }
+
+    async fn upon_input(_thread_id: u32, _thread_signal_channel: &SignalChannels<T>, _signal: Signal<T>) {}
+    async fn upon_echo(_thread_id: u32, _thread_signal_channel: &SignalChannels<T>, _signal: Signal<T>) {}
+    async fn upon_vote(_thread_id: u32, _channel: ChannelType<T>, _signal: Signal<T>) {}
+
+    fn get_signal_channels(&self) -> &SignalChannels<T> {
+        &self.signal_channels
+    }
+
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>> {
+        &self.event_tx
+    }
+
+    fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T> {
+        &self.delivery_watches
+    }
+
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes> {
+        self.reliable_handle_rx.take().unwrap()
+    }
+
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand> {
+        self.reliable_command_rx.take().unwrap()
+    }
+
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand> {
+        &self.reliable_command_tx
+    }
+}
+
+impl<T> BasicCommunication<T> for MockWitnessCommunicator<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    fn get_channels(&self) -> &MessageChannels<T> {
+        &self.basic_channels
+    }
+
+    fn get_queues(&mut self) -> &mut BasicQueues<T> {
+        &mut self.queues
+    }
+
+    fn get_id(&self) -> &u32 {
+        &self.id
+    }
+
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
+}
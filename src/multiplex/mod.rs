@@ -0,0 +1,464 @@
+use std::{collections::VecDeque, time::{Duration, Instant}};
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Serialize, Deserialize};
+use tokio::sync::mpsc::{self, Receiver, Sender, error::TrySendError};
+
+use crate::config::ClusterConfig;
+use crate::drive::HandleMode;
+use crate::middleware::MiddlewareChain;
+use crate::metrics::Metrics;
+use crate::demo::Narrator;
+use crate::logging::log;
+
+// The number of lanes multiplexed over the channel of a thread.
+const LANE_COUNT: usize = 3;
+
+// # Enum Description:
+// This enum represents the logical lanes multiplexed over the single channel of every thread. Every
+// frame is tagged with its lane by the channels that send it (`MessageChannels`, `SignalChannels`,
+// `ReportChannels`), and the receiving side demultiplexes it to the queue of that lane.
+//
+// # Variants:
+// * Basic - Messages sent or delivered to the thread's `BasicQueues`.
+// * Signal - Reliable broadcast signals processed by the thread's reliable handle.
+// * Report - Values and reports delivered to the thread's witness (or barycentric) handle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Lane {
+    Basic,
+    Signal,
+    Report,
+}
+
+impl Lane {
+    fn get_tag(&self) -> &'static str {
+        match self {
+            Lane::Basic => "B",
+            Lane::Signal => "S",
+            Lane::Report => "R",
+        }
+    }
+
+    fn get_index(&self) -> usize {
+        match self {
+            Lane::Basic => 0,
+            Lane::Signal => 1,
+            Lane::Report => 2,
+        }
+    }
+}
+
+// # Enum Description:
+// This enum represents how the demultiplexer of a thread handles a lane whose queue is full.
+//
+// # Variants:
+// * Overflow - The frames of a saturated lane wait in an overflow buffer that grows as needed, silently.
+// * Autoscale - The capacity of a saturated lane is doubled as many times as its backlog requires: the
+//   frames beyond its queue wait in a spill buffer reserved to the new capacity, and every resize is
+//   announced and recorded in the thread's metrics (see `LaneResize`), so that long runs survive bursts
+//   while the undersized buffers are still flagged.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum LaneScaling {
+    #[default]
+    Overflow,
+    Autoscale,
+}
+
+// # Struct Description:
+// This struct records that the demultiplexer of a thread grew the capacity of a saturated lane.
+//
+// # Fields:
+// * lane - The saturated lane.
+// * from - The capacity of the lane before the resize, in frames.
+// * to - The capacity of the lane after the resize, in frames.
+// * backlog - The number of frames waiting in the lane when it was resized.
+// * elapsed - The time elapsed between the creation of the demultiplexer and the resize.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LaneResize {
+    lane: Lane,
+    from: usize,
+    to: usize,
+    backlog: usize,
+    elapsed: Duration,
+}
+
+impl LaneResize {
+    pub fn get_lane(&self) -> Lane {
+        self.lane
+    }
+
+    pub fn get_from(&self) -> usize {
+        self.from
+    }
+
+    pub fn get_to(&self) -> usize {
+        self.to
+    }
+
+    pub fn get_backlog(&self) -> usize {
+        self.backlog
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+// # Struct Description:
+// This struct tracks the capacity of every lane of a thread under the configured `LaneScaling`.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * lane_scaling - How saturated lanes are handled.
+// * buffer_size - The capacity of every lane queue.
+// * capacities - The current capacity of every lane, indexed by lane.
+// * started - The creation instant of the demultiplexer.
+// * metrics - The thread's metrics, in which resizes are recorded.
+struct LaneScaler {
+    id: u32,
+    lane_scaling: LaneScaling,
+    buffer_size: usize,
+    capacities: [usize; LANE_COUNT],
+    started: Instant,
+    metrics: Metrics,
+}
+
+impl LaneScaler {
+    // # Method Description:
+    // This method grows the capacity of a lane once its backlog exceeds it, under `LaneScaling::Autoscale`.
+    //
+    // # Parameters:
+    // * lane - The lane.
+    // * queue - The transmitter of the lane queue.
+    // * overflow - The spill buffer of the lane.
+    fn check(&mut self, lane: Lane, queue: &Sender<Bytes>, overflow: &mut VecDeque<Bytes>) {
+        let index = lane.get_index();
+        let backlog = self.buffer_size - queue.capacity() + overflow.len();
+        if self.lane_scaling != LaneScaling::Autoscale || backlog <= self.capacities[index] {
+            return
+        }
+        let from = self.capacities[index];
+        let mut to = from;
+        while to < backlog {
+            to *= 2;
+        }
+        overflow.reserve(to.saturating_sub(self.buffer_size + overflow.len()));
+        self.capacities[index] = to;
+        log!(warn, "id: {}, lane {:?} saturated with {} frames waiting, growing its capacity from {} to {} frames", self.id, lane, backlog, from, to);
+        self.metrics.record_lane_resize(LaneResize { lane, from, to, backlog, elapsed: self.started.elapsed() });
+    }
+}
+
+// # Function Description:
+// This function tags a serialized frame with the lane it travels on.
+//
+// # Parameters:
+// * lane - The lane of the frame.
+// * frame - The serialized frame.
+//
+// # Returns:
+// * The tagged frame, as sent over a thread's channel.
+pub fn tag_frame(lane: Lane, frame: Bytes) -> Bytes {
+    let mut tagged = BytesMut::with_capacity(frame.len() + 2);
+    tagged.put_slice(lane.get_tag().as_bytes());
+    tagged.put_u8(b'|');
+    tagged.put_slice(&frame);
+    tagged.freeze()
+}
+
+// # Function Description:
+// This function splits a tagged frame into its lane and the serialized frame.
+//
+// # Parameters:
+// * frame - The tagged frame, as received from a thread's channel.
+//
+// # Returns:
+// * `Some((Lane, Bytes))` with the lane and the serialized frame, a slice of the tagged frame, or `None`
+//   if the frame is not tagged.
+pub fn untag_frame(frame: &Bytes) -> Option<(Lane, Bytes)> {
+    let lane = match frame.get(..2)? {
+        b"B|" => Lane::Basic,
+        b"S|" => Lane::Signal,
+        b"R|" => Lane::Report,
+        _ => return None,
+    };
+    Some((lane, frame.slice(2..)))
+}
+
+// # Function Description:
+// This function attributes a frame received from a network connection to the peer at the other end of the
+// connection, as authenticated by its handshake, so that the receiving thread knows who sent it whatever
+// the frame claims. A frame that already carries an attribution is attributed again, and the inner
+// attribution makes it unreadable, so a peer cannot pass its frames off as another's.
+//
+// # Parameters:
+// * peer - The ID of the peer.
+// * frame - The frame, as received.
+//
+// # Returns:
+// * The attributed frame.
+pub fn attribute_frame(peer: u32, frame: Bytes) -> Bytes {
+    let attribution = format!("{peer}@");
+    let mut attributed = BytesMut::with_capacity(attribution.len() + frame.len());
+    attributed.put_slice(attribution.as_bytes());
+    attributed.put_slice(&frame);
+    attributed.freeze()
+}
+
+// # Function Description:
+// This function splits an attributed frame into the peer it was received from and the frame.
+//
+// # Parameters:
+// * frame - The frame, attributed or not.
+//
+// # Returns:
+// * The peer, `None` if the frame is not attributed (e.g. on the in-memory transport), and the frame, a
+//   slice of the attributed frame.
+pub fn read_attribution(frame: &Bytes) -> (Option<u32>, Bytes) {
+    // a peer ID has at most 10 digits
+    let peer = frame.iter().take(11).position(|byte| *byte == b'@')
+        .and_then(|at| Some((std::str::from_utf8(&frame[..at]).ok()?.parse::<u32>().ok()?, at)));
+    match peer {
+        Some((peer, at)) => (Some(peer), frame.slice(at + 1..)),
+        None => (None, frame.clone()),
+    }
+}
+
+// # Struct Description:
+// This struct holds the per-lane queues a thread's channel is demultiplexed into.
+//
+// # Fields:
+// * basic - The queue of the `Basic` lane, read by the thread's `BasicQueues`.
+// * signal - The queue of the `Signal` lane, read by the thread's reliable handle.
+// * report - The queue of the `Report` lane, read by the thread's witness or barycentric handle.
+// * demultiplexer - The demultiplexer filling the queues, if it must be polled (see `HandleMode::Polling`).
+// * metrics - The thread's metrics, in which the demultiplexer records the lanes it resized.
+pub struct LaneReceivers {
+    pub basic: Receiver<Bytes>,
+    pub signal: Receiver<Bytes>,
+    pub report: Receiver<Bytes>,
+    pub demultiplexer: Option<Demultiplexer>,
+    pub metrics: Metrics,
+}
+
+// # Struct Description:
+// This struct demultiplexes a thread's channel into one queue per lane. A lane whose queue is full
+// does not hold back the other lanes: its frames wait in an overflow buffer, in order, until its queue
+// has room again. Frames of a lane nobody reads (e.g. the `Report` lane of a reliable communicator) and
+// untagged frames are discarded. Received frames first go through the middleware layers of the hub, and
+// the frames a layer rejects are discarded as well. The frames of the `Basic` lane keep the peer a network
+// transport attributed them to (see `attribute_frame`), so that the local queues can name the sender of the
+// frames they quarantine. Under `LaneScaling::Autoscale`, the overflow of a lane
+// is a spill buffer whose growth is announced and recorded (see `LaneScaler`).
+//
+// # Fields:
+// * receiver - The receiver of the thread's channel.
+// * middleware - The middleware layers of the hub.
+// * lanes - The transmitters of the lane queues, indexed by lane.
+// * overflow - The frames waiting for room in each lane queue, indexed by lane.
+// * scaler - The capacity tracker of the lanes.
+pub struct Demultiplexer {
+    receiver: Receiver<Bytes>,
+    middleware: MiddlewareChain,
+    lanes: [Sender<Bytes>; LANE_COUNT],
+    overflow: [VecDeque<Bytes>; LANE_COUNT],
+    scaler: LaneScaler,
+}
+
+impl Demultiplexer {
+    // # Method Description:
+    // This method creates a demultiplexer and the lane queues it fills.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * receiver - The receiver of the thread's channel.
+    // * config - The configuration of the cluster, giving the middleware layers, the capacity of every lane
+    //   queue, and the `LaneScaling`.
+    //
+    // # Returns:
+    // * The demultiplexer, and the `LaneReceivers` of the thread (without demultiplexer).
+    fn new(id: u32, receiver: Receiver<Bytes>, config: &ClusterConfig) -> (Self, LaneReceivers) {
+        let buffer_size = config.get_buffer_size();
+        let (basic_tx, basic) = mpsc::channel(buffer_size);
+        let (signal_tx, signal) = mpsc::channel(buffer_size);
+        let (report_tx, report) = mpsc::channel(buffer_size);
+        let metrics = match config.get_demo_pacing() {
+            Some(_) => Metrics::new().with_narrator(Narrator::new(id)),
+            None => Metrics::new(),
+        };
+        let health_probe = metrics.get_health_probe();
+        health_probe.watch_lane(Lane::Basic, &basic_tx);
+        health_probe.watch_lane(Lane::Signal, &signal_tx);
+        health_probe.watch_lane(Lane::Report, &report_tx);
+
+        let demultiplexer = Self {
+            receiver,
+            middleware: MiddlewareChain::from_config(config),
+            lanes: [basic_tx, signal_tx, report_tx],
+            overflow: Default::default(),
+            scaler: LaneScaler {
+                id,
+                lane_scaling: config.get_lane_scaling(),
+                buffer_size,
+                capacities: [buffer_size; LANE_COUNT],
+                started: Instant::now(),
+                metrics: metrics.clone(),
+            },
+        };
+        let lanes = LaneReceivers {
+            basic,
+            signal,
+            report,
+            demultiplexer: None,
+            metrics
+        };
+        (demultiplexer, lanes)
+    }
+
+    // # Method Description:
+    // This method routes a frame received from the thread's channel to the queue of its lane.
+    //
+    // # Parameters:
+    // * middleware - The middleware layers of the hub.
+    // * lanes - The transmitters of the lane queues.
+    // * overflow - The overflow buffers of the lanes.
+    // * scaler - The capacity tracker of the lanes.
+    // * frame - The tagged frame, attributed to its peer by network transports.
+    fn route(middleware: &MiddlewareChain, lanes: &[Sender<Bytes>; LANE_COUNT], overflow: &mut [VecDeque<Bytes>; LANE_COUNT], scaler: &mut LaneScaler, frame: Bytes) {
+        let (peer, frame) = read_attribution(&frame);
+        let frame = match middleware.inbound(frame) {
+            Ok(frame) => frame,
+            Err(error) => {
+                log!(warn, "frame rejected by middleware: {error}");
+                return
+            },
+        };
+        let Some((lane, payload)) = untag_frame(&frame) else {
+            return
+        };
+        let index = lane.get_index();
+        if lanes[index].is_closed() {
+            return
+        }
+        let payload = match (lane, peer) {
+            (Lane::Basic, Some(peer)) => attribute_frame(peer, payload),
+            _ => payload,
+        };
+        if !overflow[index].is_empty() {
+            overflow[index].push_back(payload);
+        } else if let Err(TrySendError::Full(payload)) = lanes[index].try_send(payload) {
+            overflow[index].push_back(payload);
+        }
+        scaler.check(lane, &lanes[index], &mut overflow[index]);
+    }
+
+    // # Method Description:
+    // This method moves as many overflowing frames as fit into the lane queues, then routes every frame
+    // pending on the thread's channel, without waiting for new ones.
+    //
+    // # Returns:
+    // * The number of frames received from the thread's channel.
+    pub fn poll(&mut self) -> usize {
+        for index in 0..LANE_COUNT {
+            while let Some(frame) = self.overflow[index].pop_front() {
+                match self.lanes[index].try_send(frame) {
+                    Ok(()) => {},
+                    Err(TrySendError::Full(frame)) => {
+                        self.overflow[index].push_front(frame);
+                        break
+                    },
+                    Err(TrySendError::Closed(_)) => self.overflow[index].clear(),
+                }
+            }
+        }
+
+        let mut received = 0;
+        while let Ok(frame) = self.receiver.try_recv() {
+            Self::route(&self.middleware, &self.lanes, &mut self.overflow, &mut self.scaler, frame);
+            received += 1;
+        }
+        received
+    }
+
+    // # Method Description:
+    // This method runs the demultiplexer until the thread's channel is closed and every overflowing frame
+    // was delivered or discarded.
+    async fn run(mut self) {
+        let mut open = true;
+        while open || self.overflow.iter().any(|frames| !frames.is_empty()) {
+            tokio::select! {
+                received = self.receiver.recv(), if open => {
+                    match received {
+                        Some(frame) => Self::route(&self.middleware, &self.lanes, &mut self.overflow, &mut self.scaler, frame),
+                        None => open = false,
+                    }
+                }
+                reserved = self.lanes[0].reserve(), if !self.overflow[0].is_empty() => {
+                    flush_overflow(reserved, &mut self.overflow[0]);
+                }
+                reserved = self.lanes[1].reserve(), if !self.overflow[1].is_empty() => {
+                    flush_overflow(reserved, &mut self.overflow[1]);
+                }
+                reserved = self.lanes[2].reserve(), if !self.overflow[2].is_empty() => {
+                    flush_overflow(reserved, &mut self.overflow[2]);
+                }
+            }
+        }
+    }
+}
+
+// # Function Description:
+// This function spawns the task demultiplexing a thread's channel into one queue per lane.
+//
+// # Parameters:
+// * id - The ID of the thread.
+// * receiver - The receiver of the thread's channel.
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The `LaneReceivers` of the thread.
+pub fn demultiplex(id: u32, receiver: Receiver<Bytes>, config: &ClusterConfig) -> LaneReceivers {
+    let (demultiplexer, lanes) = Demultiplexer::new(id, receiver, config);
+    tokio::spawn(demultiplexer.run());
+    lanes
+}
+
+// # Function Description:
+// This function opens the lanes of a thread's channel according to the configured `HandleMode`: the
+// demultiplexer runs in its own task in `HandleMode::Spawned`, and is handed out with the lanes, to be
+// polled by `drive`, in `HandleMode::Polling`.
+//
+// # Parameters:
+// * id - The ID of the thread.
+// * receiver - The receiver of the thread's channel.
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The `LaneReceivers` of the thread.
+pub fn open_lanes(id: u32, receiver: Receiver<Bytes>, config: &ClusterConfig) -> LaneReceivers {
+    match config.get_handle_mode() {
+        HandleMode::Spawned => demultiplex(id, receiver, config),
+        HandleMode::Polling => {
+            let (demultiplexer, mut lanes) = Demultiplexer::new(id, receiver, config);
+            lanes.demultiplexer = Some(demultiplexer);
+            lanes
+        },
+    }
+}
+
+// # Function Description:
+// This function moves the oldest overflowing frame of a lane into the lane's queue, or discards the
+// overflow if nobody reads the lane anymore.
+//
+// # Parameters:
+// * reserved - The result of reserving a slot in the lane's queue.
+// * overflow - The overflow buffer of the lane.
+fn flush_overflow(reserved: Result<mpsc::Permit<'_, Bytes>, mpsc::error::SendError<()>>, overflow: &mut VecDeque<Bytes>) {
+    match reserved {
+        Ok(permit) => {
+            if let Some(frame) = overflow.pop_front() {
+                permit.send(frame);
+            }
+        },
+        Err(_) => overflow.clear(),
+    }
+}
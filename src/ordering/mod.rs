@@ -0,0 +1,308 @@
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use async_trait::async_trait;
+
+use crate::basic::{Message, RecvObject};
+use crate::reliable::{ObjectContent, ReliableCommunication, Signal, SignalType};
+use crate::round::{Instance, Round};
+
+// The protocol information of the messages of FIFO broadcast.
+pub const FIFO: &str = "fifo";
+
+// The protocol information of the messages of causal broadcast.
+pub const CAUSAL: &str = "causal";
+
+// # Trait Description:
+// This trait extends `ReliableCommunication` with FIFO and causal broadcast, which run reliable broadcast
+// instances and order their deliveries. It is implemented by every reliable communicator.
+#[async_trait]
+pub trait OrderedCommunication<T>: ReliableCommunication<T>
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Method Description:
+    // This method reliably broadcasts a message with FIFO order: the broadcasts of a thread are numbered in
+    // the order they are made, from 0, and every thread receives them in that order with `fifo_recv`, even
+    // if their reliable broadcast instances are delivered in another order. FIFO broadcasts form a stream
+    // per sender, apart from the rounds: their instance number is their sequence number and their round is 0.
+    //
+    // # Parameters:
+    // * message - The content of the message to broadcast.
+    //
+    // # Returns:
+    // * A future that asynchronously broadcasts the signal to all registered signal receivers.
+    fn fifo_broadcast(&mut self, message: T) -> impl Future<Output = ()> {
+        let instance_number = self.get_queues().get_ordered_delivery().next_fifo_instance();
+        let sent_message = Message::new(String::from(FIFO), *self.get_id(), message, None, Some(instance_number), Round(0))
+            .with_epoch(self.get_signal_channels().get_epoch());
+        let sent_message = self.get_queues().stamp(sent_message);
+        let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, Round(0));
+        self.get_signal_channels().broadcast_signal(input)
+    }
+
+    // # Method Description:
+    // This method receives the next FIFO broadcast of a sender, or of any sender, blocking until it is
+    // delivered. A broadcast delivered before the ones its sender made earlier waits in the local queue.
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing a specific sender's thread ID. If `None`, the next broadcast of
+    //        whichever sender is received, searching the senders in turn.
+    //
+    // # Returns:
+    // * The `Message`, whose instance number is its sequence number.
+    async fn fifo_recv(&mut self, id: Option<u32>) -> Message<T> {
+        let message = match id {
+            Some(id) => {
+                let instance_number = self.get_queues().get_ordered_delivery().get_next_fifo_instance(id);
+                match self.get_queues().basic_recv(Some(id), String::from(FIFO), Some(instance_number), Round(0)).await {
+                    RecvObject::Message(message) => message,
+                    RecvObject::Collection(_) => panic!("Error: retreived Vec<Message> instead of Message"),
+                }
+            },
+            None => self.get_queues().recv_ordered(FIFO, OrderedDelivery::is_fifo_deliverable).await,
+        };
+        self.get_queues().get_ordered_delivery().fifo_received(message.get_id());
+        message
+    }
+
+    // # Method Description:
+    // This method reliably broadcasts a message with causal order: the message carries the vector clock of
+    // the thread, counting the causal broadcasts it received with `causal_recv` and its own, and every
+    // thread receives it with `causal_recv` only after every causal broadcast it depends on. Like FIFO
+    // broadcasts, causal broadcasts are numbered per sender with their instance number, in round 0.
+    //
+    // # Parameters:
+    // * message - The content of the message to broadcast.
+    //
+    // # Returns:
+    // * A future that asynchronously broadcasts the signal to all registered signal receivers.
+    fn causal_broadcast(&mut self, message: T) -> impl Future<Output = ()> {
+        let id = *self.get_id();
+        let deliver_to_self = self.get_config().get_deliver_to_self();
+        let (instance_number, clock) = self.get_queues().get_ordered_delivery().next_causal_clock(id, deliver_to_self);
+        let sent_message = Message::new(String::from(CAUSAL), id, message, None, Some(instance_number), Round(0))
+            .with_epoch(self.get_signal_channels().get_epoch())
+            .with_clock(clock);
+        let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, Round(0));
+        self.get_signal_channels().broadcast_signal(input)
+    }
+
+    // # Method Description:
+    // This method receives the next causal broadcast whose dependencies were all received, from any sender,
+    // blocking until one is delivered. Broadcasts delivered before their dependencies wait in the local queue.
+    //
+    // # Returns:
+    // * The `Message`, with its vector clock.
+    async fn causal_recv(&mut self) -> Message<T> {
+        let message = self.get_queues().recv_ordered(CAUSAL, OrderedDelivery::is_causally_deliverable).await;
+        self.get_queues().get_ordered_delivery().causal_received(message.get_id());
+        message
+    }
+}
+
+impl<T, C> OrderedCommunication<T> for C
+where
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T>,
+{
+}
+
+// # Struct Description:
+// This struct is a vector clock, attached to the messages of causal broadcast (see `Message::with_clock`):
+// the number of causal broadcasts of every thread the sender had delivered when it broadcast the message,
+// its own broadcasts included. When the communicators maintain vector clocks, it is also the event clock
+// of a thread (see `BasicQueues::get_clock`), counting its sends and receipts. Entries are kept by thread ID, and absent entries count as 0, so that clocks
+// stay small and threads joining a roster need no resizing.
+//
+// # Fields:
+// * entries - The number of causal broadcasts of every thread, by thread ID, without the zero entries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct VectorClock {
+    entries: BTreeMap<u32, u32>,
+}
+
+impl VectorClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: u32) -> u32 {
+        self.entries.get(&id).copied().unwrap_or(0)
+    }
+
+    pub fn get_entries(&self) -> &BTreeMap<u32, u32> {
+        &self.entries
+    }
+
+    // # Method Description:
+    // This method counts one more causal broadcast of a thread.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    pub fn increment(&mut self, id: u32) {
+        *self.entries.entry(id).or_insert(0) += 1;
+    }
+
+    // # Method Description:
+    // This method merges another clock into this one, keeping the highest count of every thread.
+    //
+    // # Parameters:
+    // * other - The other clock.
+    pub fn merge(&mut self, other: &VectorClock) {
+        for (id, count) in &other.entries {
+            let entry = self.entries.entry(*id).or_insert(0);
+            *entry = (*entry).max(*count);
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether the broadcasts counted by this clock happened before those counted by
+    // another one, i.e. every entry is lower or equal and the clocks differ.
+    //
+    // # Parameters:
+    // * other - The other clock.
+    //
+    // # Returns:
+    // * `true` if this clock happened before the other one.
+    pub fn happened_before(&self, other: &VectorClock) -> bool {
+        self != other && self.entries.iter().all(|(id, count)| *count <= other.get(*id))
+    }
+
+    // # Method Description:
+    // This method checks whether a message stamped with this clock by a sender can be delivered by a thread
+    // that delivered the broadcasts counted by `delivered`: it must be the next broadcast of its sender, and
+    // every broadcast it depends on must have been delivered.
+    //
+    // # Parameters:
+    // * sender - The ID of the sender of the message.
+    // * delivered - The broadcasts delivered by the thread.
+    //
+    // # Returns:
+    // * `true` if the message can be delivered.
+    pub fn is_deliverable(&self, sender: u32, delivered: &VectorClock) -> bool {
+        self.get(sender) == delivered.get(sender) + 1
+            && self.entries.iter().all(|(id, count)| *id == sender || *count <= delivered.get(*id))
+    }
+}
+
+// # Struct Description:
+// This struct holds the ordering state of a thread for FIFO and causal broadcast, kept with its queues
+// (see `BasicQueues::get_ordered_delivery`). FIFO broadcasts are numbered per sender, from 0, with their
+// instance number; causal broadcasts carry the vector clock of their sender. Messages are queued as the
+// reliable handle delivers them, in any order, and only received once their predecessors were.
+//
+// # Fields:
+// * fifo_sent - The number of FIFO broadcasts of the thread.
+// * fifo_received - The number of FIFO broadcasts received from every sender, i.e. the instance number of the
+//                   next one to receive, by sender.
+// * causal_sent - The number of causal broadcasts of the thread.
+// * causal_received - The causal broadcasts received from every sender, the thread's own included.
+#[derive(Debug, Clone, Default)]
+pub struct OrderedDelivery {
+    fifo_sent: u32,
+    fifo_received: BTreeMap<u32, u32>,
+    causal_sent: u32,
+    causal_received: VectorClock,
+}
+
+impl OrderedDelivery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // # Method Description:
+    // This method numbers the next FIFO broadcast of the thread.
+    //
+    // # Returns:
+    // * The instance number of the broadcast.
+    pub fn next_fifo_instance(&mut self) -> Instance {
+        self.fifo_sent += 1;
+        Instance(self.fifo_sent - 1)
+    }
+
+    // # Method Description:
+    // This method gives the instance number of the next FIFO broadcast to receive from a sender.
+    //
+    // # Parameters:
+    // * sender - The ID of the sender.
+    //
+    // # Returns:
+    // * The instance number.
+    pub fn get_next_fifo_instance(&self, sender: u32) -> Instance {
+        Instance(self.fifo_received.get(&sender).copied().unwrap_or(0))
+    }
+
+    // # Method Description:
+    // This method checks whether a message is the next FIFO broadcast of its sender.
+    //
+    // # Parameters:
+    // * message - The message.
+    //
+    // # Returns:
+    // * `true` if the message can be received.
+    pub fn is_fifo_deliverable<T>(&self, message: &Message<T>) -> bool
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        message.get_instance_number() == Some(self.get_next_fifo_instance(message.get_id()))
+    }
+
+    // # Method Description:
+    // This method records that the next FIFO broadcast of a sender was received.
+    //
+    // # Parameters:
+    // * sender - The ID of the sender.
+    pub fn fifo_received(&mut self, sender: u32) {
+        *self.fifo_received.entry(sender).or_insert(0) += 1;
+    }
+
+    // # Method Description:
+    // This method stamps the next causal broadcast of the thread: its clock counts the broadcasts the thread
+    // received so far, and the broadcast itself.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * deliver_to_self - Whether the broadcast will be delivered to the thread. If not, it is counted as
+    //                     received right away, so that the broadcasts depending on it can be received.
+    //
+    // # Returns:
+    // * The instance number and the clock of the broadcast.
+    pub fn next_causal_clock(&mut self, id: u32, deliver_to_self: bool) -> (Instance, VectorClock) {
+        self.causal_sent += 1;
+        let mut clock = self.causal_received.clone();
+        clock.entries.insert(id, self.causal_sent);
+        if !deliver_to_self {
+            self.causal_received.increment(id);
+        }
+        (Instance(self.causal_sent - 1), clock)
+    }
+
+    pub fn get_causal_received(&self) -> &VectorClock {
+        &self.causal_received
+    }
+
+    // # Method Description:
+    // This method checks whether every causal broadcast a message depends on was received.
+    //
+    // # Parameters:
+    // * message - The message.
+    //
+    // # Returns:
+    // * `true` if the message can be received; messages without a clock never can.
+    pub fn is_causally_deliverable<T>(&self, message: &Message<T>) -> bool
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        message.get_clock().is_some_and(|clock| clock.is_deliverable(message.get_id(), &self.causal_received))
+    }
+
+    // # Method Description:
+    // This method records that the next causal broadcast of a sender was received.
+    //
+    // # Parameters:
+    // * sender - The ID of the sender.
+    pub fn causal_received(&mut self, sender: u32) {
+        self.causal_received.increment(sender);
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::{BTreeMap, HashMap, HashSet}, time::{Duration, Instant}};
+use serde::{Serialize, Deserialize};
+
+use crate::json::{FrameKind, JsonConversion, frame_kinds};
+use crate::round::Round;
+
+// # Struct Description:
+// This struct is the frame a reliable handle sends on the `Signal` lane, to every thread, to move a round to
+// a new view: once its pacemaker saw an instance of the round stay undelivered for the timeout, or once it
+// received the view change of enough threads to know that a correct thread did.
+//
+// # Fields:
+// * sender - The ID of the thread changing views.
+// * round_number - The round.
+// * view - The view the thread moves the round to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct ViewChange {
+    sender: u32,
+    round_number: Round,
+    view: u32,
+}
+
+impl ViewChange {
+    pub(crate) fn new(sender: u32, round_number: Round, view: u32) -> Self {
+        Self {
+            sender,
+            round_number,
+            view,
+        }
+    }
+
+    pub(crate) fn get_sender(&self) -> u32 {
+        self.sender
+    }
+
+    pub(crate) fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub(crate) fn get_view(&self) -> u32 {
+        self.view
+    }
+}
+
+impl JsonConversion<ViewChange> for ViewChange {}
+
+impl FrameKind for ViewChange {
+    const FRAME_KIND: u64 = frame_kinds::VIEW_CHANGE_FRAME;
+}
+
+// # Struct Description:
+// This struct is the outcome of a view change counted by a pacemaker.
+//
+// # Fields:
+// * join - Whether the thread must send the view change too, as threads weighing more than the agreement
+//   threshold, among which a correct one, sent it.
+// * advance - Whether the round moved to the view, as threads weighing the validity threshold sent it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct ViewChangeStep {
+    pub join: bool,
+    pub advance: bool,
+}
+
+// # Struct Description:
+// This struct is the pacemaker of a reliable handle (see `ClusterConfig::with_pacemaker`): it keeps a timer
+// per instance, started with the first signal of the instance and stopped once it is delivered, and the
+// current view of every round, 0 until the round changes views. Once a timer expires, the thread requests
+// the next view of the round; a round moves to a view once the view changes of a validity quorum are counted,
+// and a thread joins a view change requested by an agreement quorum, so that the correct threads move
+// together. When a round moves, the timers of its undelivered instances start again, so that a round still
+// stuck in the new view changes views again. Under a `LeaderElection`, the view rotates the leader of the round.
+//
+// # Fields:
+// * timeout - How long an instance may stay undelivered before the thread changes views, if the pacemaker is enabled.
+// * timers - When the timer of every undelivered instance started, with the round of the instance, by key.
+// * settled - The keys of the delivered instances, whose timers are never started again.
+// * views - The current view of every round that changed views.
+// * requested - The highest view the thread requested for every round.
+// * view_changes - The weight of the threads that requested every view of every round, by sender.
+#[derive(Debug, Default)]
+pub(crate) struct Pacemaker {
+    timeout: Option<Duration>,
+    timers: HashMap<String, (Round, Instant)>,
+    settled: HashSet<String>,
+    views: HashMap<Round, u32>,
+    requested: HashMap<Round, u32>,
+    view_changes: HashMap<(Round, u32), BTreeMap<u32, u64>>,
+}
+
+impl Pacemaker {
+    pub(crate) fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn get_view(&self, round_number: Round) -> u32 {
+        self.views.get(&round_number).copied().unwrap_or_default()
+    }
+
+    // # Method Description:
+    // This method starts the timer of an instance, unless the pacemaker is disabled, the timer already
+    // runs, or the instance was delivered.
+    //
+    // # Parameters:
+    // * key - The key of the instance.
+    // * round_number - The round of the instance.
+    pub(crate) fn start(&mut self, key: &str, round_number: Round) {
+        if self.timeout.is_none() || self.settled.contains(key) {
+            return
+        }
+        self.timers.entry(key.to_string()).or_insert((round_number, Instant::now()));
+    }
+
+    // # Method Description:
+    // This method stops the timer of a delivered instance for good.
+    //
+    // # Parameters:
+    // * key - The key of the instance.
+    pub(crate) fn stop(&mut self, key: &str) {
+        if self.timeout.is_none() {
+            return
+        }
+        self.timers.remove(key);
+        self.settled.insert(key.to_string());
+    }
+
+    // # Method Description:
+    // This method forgets a collected instance (see `RetentionPolicy`).
+    //
+    // # Parameters:
+    // * key - The key of the instance.
+    pub(crate) fn forget(&mut self, key: &str) {
+        self.timers.remove(key);
+        self.settled.remove(key);
+    }
+
+    // # Method Description:
+    // This method collects the rounds whose timers expired, and records the view the thread requests for each.
+    //
+    // # Returns:
+    // * The rounds that must change views, each with the view the thread requests, once per view.
+    pub(crate) fn expired(&mut self) -> Vec<(Round, u32)> {
+        let Some(timeout) = self.timeout else {
+            return vec![]
+        };
+        let mut rounds: Vec<Round> = self.timers.values()
+            .filter(|(_, started)| started.elapsed() >= timeout)
+            .map(|(round_number, _)| *round_number)
+            .collect();
+        rounds.sort();
+        rounds.dedup();
+
+        let mut expired = vec![];
+        for round_number in rounds {
+            let view = self.get_view(round_number) + 1;
+            if self.request(round_number, view) {
+                expired.push((round_number, view));
+            }
+        }
+        expired
+    }
+
+    // # Method Description:
+    // This method records that the thread requests a view of a round.
+    //
+    // # Returns:
+    // * `true` if the thread did not request the view, nor a higher one, yet.
+    pub(crate) fn request(&mut self, round_number: Round, view: u32) -> bool {
+        let requested = self.requested.entry(round_number).or_default();
+        if *requested >= view {
+            return false
+        }
+        *requested = view;
+        true
+    }
+
+    // # Method Description:
+    // This method counts the view change of a thread, once per sender and view.
+    //
+    // # Parameters:
+    // * view_change - The view change.
+    // * weight - The weight the sender counts for.
+    // * agreement_threshold - The weight of view changes the thread joins.
+    // * validity_threshold - The weight of view changes that moves the round.
+    //
+    // # Returns:
+    // * Whether the thread must join the view change, and whether the round moved to its view.
+    pub(crate) fn record(&mut self, view_change: &ViewChange, weight: u64, agreement_threshold: u64, validity_threshold: u64) -> ViewChangeStep {
+        let round_number = view_change.get_round_number();
+        let view = view_change.get_view();
+        if view <= self.get_view(round_number) {
+            return ViewChangeStep::default()
+        }
+        let senders = self.view_changes.entry((round_number, view)).or_default();
+        senders.insert(view_change.get_sender(), weight);
+        let collected: u64 = senders.values().sum();
+
+        let join = collected >= agreement_threshold && self.requested.get(&round_number).is_none_or(|requested| *requested < view);
+        let advance = collected >= validity_threshold;
+        if advance {
+            self.views.insert(round_number, view);
+            self.view_changes.retain(|(changed_round, changed_view), _| *changed_round != round_number || *changed_view > view);
+            for (timer_round, started) in self.timers.values_mut() {
+                if *timer_round == round_number {
+                    *started = Instant::now();
+                }
+            }
+        }
+        ViewChangeStep { join, advance }
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+
+// # Struct Description:
+// This struct is a compact numeric payload for the communication layers, e.g. for benchmarks that should
+// not measure the cost of formatting, escaping, and copying `String`s. It is serialized as a bare JSON
+// number (`#[serde(transparent)]`), so a frame carrying it is smaller and faster to encode and decode than
+// one carrying a `String`, and it is `Copy`. It satisfies every bound the protocols place on payloads,
+// including the `Default` required by barycentric agreement.
+//
+// # Fields:
+// * 0 - The numeric value.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct NumericPayload(u64);
+
+impl NumericPayload {
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for NumericPayload {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for NumericPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,15 +1,48 @@
 use core::panic;
-use std::{vec, fmt::Debug, hash::Hash, collections::HashMap, marker::PhantomData};
+use std::{vec, fmt::Debug, hash::Hash, collections::{BTreeMap, HashMap, HashSet}, marker::PhantomData, path::Path, sync::{Arc, Mutex}, time::{Duration, Instant}};
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
 
-use tokio::{task::JoinHandle, sync::mpsc::{self, Receiver, Sender}};
-use futures::future::join_all;
+use tokio::{task::JoinHandle, sync::{broadcast, oneshot, watch, mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender}}};
+use futures::{future::{Either, join_all}, stream::{self, Stream}};
+use tracing::Instrument;
 use async_trait::async_trait; 
 
-use crate::{aggregated_witness::AggregatedReport, barycentric_agreement::BarycentricReport, basic::{BasicCommunication, BasicQueues, Message, MessageChannels, RecvObject}}; 
-use crate::witness::{Report, ReportChannels};
-use crate::json::{JsonConversion};
+use crate::delivered::Delivered;
+use crate::certificate::DeliveryCertificate;
+use crate::dispersal::{DISPERSAL, DispersalProcessor, Fragment};
+use crate::extension::{EXTENSION, Delivery, ExtensionProcessor, ExtensionRegistry, Processing, ProcessorRegistry};
+use bytes::Bytes;
+use crate::{aggregated_witness::AggregatedReport, barycentric_agreement::BarycentricReport, basic::{BasicCommunication, BasicQueues, Message, MessageChannels, RecvError, RecvObject, validate_wiring}}; 
+use crate::witness::{Commitment, Report, ReportChannels, WitnessHandleCommand, WitnessRoundMonitor, next_gossip};
+use crate::json::{Codec, Frame, FrameKind, JsonConversion, WireFormat, frame_kinds};
+use crate::config::{ClusterConfig, SelfCounting, Thresholds};
+use crate::multiplex::{Lane, LaneReceivers, tag_frame, open_lanes};
+use crate::drive::{HandleMode, PolledHandle, PolledHandles, ShutdownReport, ShutdownResponder, drive};
+use crate::events::{Event, EquivocationEvidence, DecodeFailureReporter};
+use crate::metrics::{Phase, PhaseHistogram, Metrics, MetricsReport, RoundStatus};
+use crate::health::{NodeHealth, RunningHandle};
+use crate::inspect::{Inspect, InspectResponder, ReliableInstanceSnapshot};
+use crate::dissemination::{Dissemination, Disseminator};
+use crate::signing::{SignalSigner, verify_with_key};
+use crate::membership::{MembershipCertificate, MembershipDocument};
+use crate::retention::RetentionTracker;
+use crate::trace::TraceSampler;
+use crate::leader::LeaderSchedule;
+use crate::pacemaker::{Pacemaker, ViewChange};
+use crate::batching::{SignalBatch, SignalBatcher};
+use crate::roster::{Roster, send_frame};
+use crate::round::{Instance, Round};
+use crate::capabilities::{Capabilities, CapabilityMismatch, Protocol};
+use crate::logging::log;
+use crate::storage::SignalJournal;
+use crate::snapshot::{ClusterSnapshot, SnapshotTap, ThreadSnapshot};
+use crate::descriptor::{MessageTypeDescriptor, PhaseDescriptor, ProtocolDescriptor, Quorum};
+use crate::ordering::{CAUSAL, FIFO};
+use crate::consistent::{CONSISTENT, ConsistentProcessor, Endorsement};
 
+// The protocols whose deliveries are queued for the communicator, instead of being handed to the protocol
+// running on top of reliable broadcast.
+const QUEUED_PROTOCOLS: [&str; 4] = ["reliable", FIFO, CAUSAL, CONSISTENT];
 
 
 // # Trait Description:
@@ -24,7 +57,8 @@ where
 {
     // # Method Description:
     // This method initiates a reliable broadcast by wrapping a message with protocol metadata
-    // and broadcasting it to all participants via signal channels.
+    // and broadcasting it to all participants via signal channels. The instance is delivered to
+    // every thread, including this one unless self-delivery is disabled (see `ClusterConfig::with_deliver_to_self`).
     //
     // # Parameters:
     // * message - The content of the message to broadcast as a `String`.
@@ -33,9 +67,11 @@ where
     //
     // # Returns:
     // * A future that asynchronously broadcasts the signal to all registered signal receivers.
-    fn reliable_broadcast(&mut self, message: T, instance_number: u32, round_number: u32) -> impl Future<Output = ()>  {
+    fn reliable_broadcast(&mut self, message: T, instance_number: Instance, round_number: Round) -> impl Future<Output = ()>  {
         let protocol_information = String::from("reliable");
-        let sent_message = Message::new(protocol_information, *self.get_id(), message, None, Some(instance_number), round_number);
+        let sent_message = Message::new(protocol_information, *self.get_id(), message, None, Some(instance_number), round_number)
+            .with_epoch(self.get_signal_channels().get_epoch());
+        let sent_message = self.get_queues().stamp(sent_message);
         let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
         self.get_signal_channels().broadcast_signal(input)
     }
@@ -54,7 +90,7 @@ where
     // * A `Message` instance retrieved from the queue.
     // # Panics:
     // * If the retrieved object is a `Collection` instead of a `Message`.
-    async fn reliable_recv(&mut self, id: Option<u32>, instance_number: u32, round_number: u32) -> Message<T> {
+    async fn reliable_recv(&mut self, id: Option<u32>, instance_number: Instance, round_number: Round) -> Message<T> {
         let protocol_information = String::from("reliable");
         match 
         self.get_queues().basic_recv(id, protocol_information, Some(instance_number), round_number).await {
@@ -64,8 +100,107 @@ where
             RecvObject::Collection(_) => {panic!("Error: retreived Vec<Message> instead of Message")},
         }
     }
+
+    // # Method Description:
+    // This method retrieves a reliably delivered message like `reliable_recv`, in the `Delivered` envelope
+    // shared by every protocol.
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing a specific sender's thread ID.
+    // * instance_number - The consensus instance number associated with the message.
+    // * round_number - The round number within the consensus instance.
+    //
+    // # Returns:
+    // * The `Delivered` value, with the instance number and sender of the message.
+    async fn reliable_recv_delivered(&mut self, id: Option<u32>, instance_number: Instance, round_number: Round) -> Delivered<T> {
+        let message = self.reliable_recv(id, instance_number, round_number).await;
+        Delivered::from_message(*self.get_id(), message)
+    }
+
+    // # Method Description:
+    // This method retrieves a reliably delivered message like `reliable_recv`, with the certificate proving
+    // that a validity quorum of threads voted for it, which third parties can verify offline with the
+    // membership document of the cluster (see `DeliveryCertificate::verify`).
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing a specific sender's thread ID.
+    // * instance_number - The consensus instance number associated with the message.
+    // * round_number - The round number within the consensus instance.
+    //
+    // # Returns:
+    // * The `Message` and its `DeliveryCertificate`.
+    // # Panics:
+    // * If signals are not signed, i.e. the cluster does not run under `Authentication::Ed25519`.
+    async fn reliable_recv_with_proof(&mut self, id: Option<u32>, instance_number: Instance, round_number: Round) -> (Message<T>, DeliveryCertificate<T>) {
+        if self.get_signal_channels().get_signer().is_none() {
+            panic!("Error: delivery certificates require signed signals (see Authentication::Ed25519)")
+        }
+        let message = self.reliable_recv(id, instance_number, round_number).await;
+        let mut certificate = self.get_delivery_watches().subscribe_certificate(message.get_id(), instance_number, round_number);
+        let certificate = certificate.wait_for(Option::is_some).await
+            .expect("Error: the reliable handle stopped before certifying a delivery")
+            .clone().unwrap();
+        (message, certificate)
+    }
+
+    // # Method Description:
+    // This method retrieves a reliably delivered message like `reliable_recv`, but stops waiting after a
+    // timeout, e.g. so that an application can retry the instance or change views on top of it. A message
+    // delivered after the timeout stays in the local queue.
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing a specific sender's thread ID.
+    // * instance_number - The consensus instance number associated with the message.
+    // * round_number - The round number within the consensus instance.
+    // * timeout - How long to wait for the message.
+    //
+    // # Returns:
+    // * The `Message`, or `RecvError::Timeout` if the instance was not delivered in time.
+    async fn reliable_recv_with_timeout(&mut self, id: Option<u32>, instance_number: Instance, round_number: Round, timeout: Duration) -> Result<Message<T>, RecvError> {
+        tokio::time::timeout(timeout, self.reliable_recv(id, instance_number, round_number)).await
+            .map_err(|_| RecvError::Timeout(String::from("reliable"), Some(instance_number), round_number))
+    }
+
+    // # Method Description:
+    // This method retrieves up to `max` reliably delivered messages of a round, across instances, from
+    // the local queue in one call, e.g. so that a replicated state machine can apply commands in batches.
+    // It waits until the deadline only if no matching message has been delivered yet.
+    //
+    // # Parameters:
+    // * id - Optional `u32` representing a specific sender's thread ID. If provided,
+    //        the method will only retrieve from that sender’s queue.
+    // * round_number - The round number within the consensus instances.
+    // * max - The maximum number of messages to return.
+    // * deadline - The instant after which the method returns even if nothing was delivered.
+    //
+    // # Returns:
+    // * A vector of up to `max` `Message`s, in delivery order per sender; empty if the deadline passed first.
+    async fn reliable_recv_batch(&mut self, id: Option<u32>, round_number: Round, max: usize, deadline: Instant) -> Vec<Message<T>> {
+        let protocol_information = String::from("reliable");
+        self.get_queues().recv_batch(id, protocol_information, round_number, max, deadline).await
+    }
  
-    fn initialize_reliable_handle(&mut self) -> JoinHandle<()>;
+    // # Method Description:
+    // Starts the thread's reliable handle, which listens for incoming signal messages (Input, Echo, Vote).
+    // The handle tracks instance states, applies threshold-based transitions, 
+    // and ensures messages are delivered once protocol conditions are met (see `ReliableHandleLoop`).
+    // In `HandleMode::Spawned` the handle runs in its own task; in `HandleMode::Polling` it is parked
+    // in the communicator and only processes signals when the communicator is driven.
+    //
+    // # Returns:
+    // * A `JoinHandle` to the spawned task, that runs until explicitly terminated (already completed in polling mode).
+    fn initialize_reliable_handle(&mut self) -> JoinHandle<()> 
+    where 
+        Self: Sized + 'static,
+    {
+        log!(debug, "initializing reliable handle...");
+
+        let reliable_handle: ReliableHandleLoop<T, Self> = ReliableHandleLoop::new(self);
+        match self.get_config().get_handle_mode() {
+            HandleMode::Spawned => tokio::spawn(reliable_handle.run()),
+            HandleMode::Polling => self.get_polled_handles().park(Box::new(reliable_handle)),
+        }
+    }
 
     // # Method Description:
     // This method terminates the asynchronous thread associated with the thread's reliable broadcast mechanics. 
@@ -73,10 +208,36 @@ where
     // # Parameters:
     // * reliable_handle - A `JoinHandle<()>` representing the spawned handle responsible for the designated thread's reliable broadcast mechanics.
     fn terminate_reliable_handle(&self, reliable_handle: JoinHandle<()>) {
-        println!("id: {}, terminating reliable_handle...", self.get_id());
+        log!(debug, { node = *self.get_id() }, "id: {}, terminating reliable_handle...", self.get_id());
         reliable_handle.abort();
     }
 
+    // # Method Description:
+    // This method stops the thread's reliable handle cooperatively, unlike `terminate_reliable_handle`: the
+    // handle first processes every signal it already received, so that in-flight deliveries complete and
+    // queued Echoes and Votes are still sent, then reports the instances it had not delivered and exits.
+    // In `HandleMode::Polling`, the communicator is driven until the handle stopped.
+    //
+    // # Parameters:
+    // * reliable_handle - The `JoinHandle` returned by `initialize_reliable_handle`.
+    //
+    // # Returns:
+    // * The `ShutdownReport` of the handle, `None` if the handle was no longer running.
+    async fn shutdown_reliable_handle(&mut self, reliable_handle: JoinHandle<()>) -> Option<ShutdownReport>
+    where
+        Self: Sized,
+    {
+        log!(debug, { node = *self.get_id() }, "id: {}, shutting down reliable_handle...", self.get_id());
+        let (responder, report) = oneshot::channel();
+        self.get_reliable_command_channel().send(ReliableHandleCommand::Shutdown(responder)).ok()?;
+        if let HandleMode::Polling = self.get_config().get_handle_mode() {
+            drive(self).await;
+        }
+        let report = report.await.ok();
+        let _ = reliable_handle.await;
+        report
+    }
+
     // # Method Description:
     // This function constructs a unique string identifier for a signal instance by combining 
     // protocol metadata, sender ID, content type, instance number, and round number. 
@@ -89,73 +250,399 @@ where
     // # Returns:
     // * A String identifier in the format: "<protocol>::<sender_id>::<content_type>::<instance_number>::<round_number>"
     fn get_instance_id(thread_id:u32, signal: Signal<T>) -> String {
-        let instance_number = signal.get_instance_number();
-        let round_number = signal.get_round_number();
-        match signal.get_content() {
-            ObjectContent::Message(message) => {
-                return format!("{}::{}::{}::{}::{}::{}", 
-                thread_id, message.get_protocol_information(), message.get_id(), "message", instance_number, round_number);
-            },
-            ObjectContent::Report(report) => {
-                return format!("{}::{}::{}::{}::{}::{}", 
-                thread_id, report.get_protocol_information(), report.get_id(), "report", instance_number, round_number);
-            },
-            ObjectContent::AggregatedReport(aggregated_report) => {
-                return format!("{}::{}::{}::{}::{}::{}", 
-                thread_id, aggregated_report.get_protocol_information(), aggregated_report.get_id(), "aggregated report", instance_number, round_number);
+        format!("{}::{}", thread_id, signal.get_instance_key())
+    }
+
+    // # Method Description:
+    // This method handles a second Input signal received for an instance that already has one,
+    // according to the configured `DuplicateInputPolicy`. The duplicate never re-enters the protocol.
+    //
+    // # Parameters:
+    // * thread_id - The ID of the current thread processing the signal.
+    // * event_channel - The channel on which events are published.
+    // * duplicate_input_policy - The policy to apply.
+    // * instance_id - The ID of the instance the duplicate belongs to.
+    // * first - The content of the Input accepted for the instance.
+    // * second - The content of the duplicate Input.
+    fn upon_duplicate_input(thread_id: u32, event_channel: &broadcast::Sender<Event<T>>, duplicate_input_policy: DuplicateInputPolicy, 
+            instance_id: String, first: &ObjectContent<T>, second: &ObjectContent<T>) {
+        match duplicate_input_policy {
+            DuplicateInputPolicy::FirstWins => {},
+            DuplicateInputPolicy::Reject => {
+                log!(warn, { node = thread_id, instance_id = %instance_id }, "id {}, instance: {}, rejecting duplicate input...", thread_id, instance_id);
+                let _ = event_channel.send(Event::DuplicateInput(thread_id, instance_id, second.clone()));
             },
-            ObjectContent::BarycentricReport(barycentric_report) => {
-                return format!("{}::{}::{}::{}::{}::{}", 
-                thread_id, barycentric_report.get_protocol_information(), barycentric_report.get_id(), "barycentric report", instance_number, round_number);
+            DuplicateInputPolicy::Evidence => {
+                if first != second {
+                    log!(warn, { node = thread_id, instance_id = %instance_id }, "id {}, instance: {}, recording equivocation...", thread_id, instance_id);
+                    let evidence = EquivocationEvidence::new(thread_id, instance_id, first.clone(), second.clone());
+                    let _ = event_channel.send(Event::Equivocation(evidence));
+                }
             },
         }
     }
 
+    // # Method Description:
+    // This method watches the delivery of a reliable broadcast instance. Unlike `reliable_recv`, it does
+    // not consume the delivered message from the local queue, so several tasks of the thread can observe
+    // the same delivery (and `reliable_recv` still returns it). Watching after the delivery yields it at once.
+    //
+    // # Parameters:
+    // * instance_number - The instance to watch.
+    // * round_number - The round to watch.
+    //
+    // # Returns:
+    // * A `watch::Receiver` holding `None` until a message is delivered for the instance and round, then
+    //   the latest message delivered for them (by any sender).
+    fn watch_delivery(&self, instance_number: Instance, round_number: Round) -> watch::Receiver<Option<Message<T>>> {
+        self.get_delivery_watches().subscribe(instance_number, round_number)
+    }
+
+    // # Method Description:
+    // This method subscribes to the events published by the communicator's background handles.
+    // Only events published after the subscription are received.
+    //
+    // # Returns:
+    // * A `broadcast::Receiver` yielding the communicator's events.
+    fn subscribe_events(&self) -> broadcast::Receiver<Event<T>> {
+        self.get_event_channel().subscribe()
+    }
+
+    // # Method Description:
+    // This method subscribes to the events of a single round, published by any handle of the communicator,
+    // e.g. so that a test or a dashboard can follow one round without filtering every event. The stream
+    // ends with the `Event::RoundCollected` of the round, once a witness, aggregated witness, or barycentric
+    // handle collected it (see `RetentionPolicy`), or when the event channel closes. Reliable handles
+    // collect instances rather than rounds, so on a reliable communicator the stream only ends with the
+    // communicator. Only events published after the subscription are received, and events the stream
+    // lagged behind on are skipped.
+    //
+    // # Parameters:
+    // * round_number - The round to follow.
+    //
+    // # Returns:
+    // * A `Stream` yielding the events of the round.
+    fn subscribe_round(&self, round_number: Round) -> impl Stream<Item = Event<T>> + Send + 'static {
+        let thread_id = *self.get_id();
+        stream::unfold(Some(self.subscribe_events()), move |events| async move {
+            let mut events = events?;
+            loop {
+                match events.recv().await {
+                    Ok(event) if event.get_round_number() == Some(round_number) => {
+                        let collected = matches!(event, Event::RoundCollected(..));
+                        return Some((event, (!collected).then_some(events)))
+                    },
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log!(warn, { node = thread_id }, "id: {thread_id}, the subscription to round {round_number} skipped {skipped} events");
+                    },
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    // # Method Description:
+    // This method returns a snapshot of the latency histograms measured by the reliable handle for 
+    // every phase of the instances of a protocol, e.g. to assert a p99 bound within a benchmark.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol whose instances are measured (e.g. "reliable", "witness").
+    //
+    // # Returns:
+    // * A `PhaseHistogram` with the input→echo-quorum, echo-quorum→vote-quorum, and vote-quorum→deliver latencies.
+    fn phase_latencies(&self, protocol_information: &str) -> PhaseHistogram {
+        self.get_metrics().get_phase_latencies().get(protocol_information)
+    }
+
+    // # Method Description:
+    // This method returns the number of frames the thread's handles discarded because they could not
+    // be decoded, e.g. to detect a serialization bug before it shows up as a stalled round.
+    //
+    // # Returns:
+    // * The number of undecodable frames keyed by lane name (e.g. "Signal", "Report").
+    fn decode_failures(&self) -> BTreeMap<String, u64> {
+        self.get_metrics().get_decode_failures()
+    }
+
+    // # Method Description:
+    // This method returns the number of signal frames (Input, Echo, and Vote, including relayed ones) this
+    // thread sent in a round of a protocol, e.g. to check the message complexity of a round (see `testkit`).
+    //
+    // # Parameters:
+    // * protocol_information - The protocol the round belongs to (e.g. "reliable", "witness").
+    // * round_number - The round whose frames are counted.
+    //
+    // # Returns:
+    // * The number of signal frames sent.
+    fn sent_signals(&self, protocol_information: &str, round_number: Round) -> u64 {
+        self.get_metrics().get_sent_signals(protocol_information, round_number)
+    }
+
+    // # Method Description:
+    // This method exports the metric report of this thread: the phase histograms of every protocol, the
+    // number of undecodable frames, and the status of every round, together with the cluster configuration
+    // they were measured under.
+    //
+    // # Returns:
+    // * A `MetricsReport`, serializable through `JsonConversion`.
+    fn metrics_report(&self) -> MetricsReport {
+        self.get_metrics().report(*self.get_id(), self.get_config().clone())
+    }
+
+    // # Method Description:
+    // This method reports the liveness of this thread: whether each of its handles is running, when a frame
+    // was last processed, the rounds completed per protocol, the peers suspected, and the depth of every
+    // lane queue (see `ClusterHealth` to check a whole cluster).
+    //
+    // # Returns:
+    // * A `NodeHealth`, serializable through `JsonConversion`.
+    fn health(&self) -> NodeHealth {
+        self.get_metrics().health(*self.get_id())
+    }
+
+    // # Method Description:
+    // This method takes a snapshot of the state of this thread: the monitors of its running reliable and
+    // witness handles, and the messages waiting in its basic queues (see `ThreadSnapshot`). The handles
+    // answer once they processed the frames received before the request; in `HandleMode::Polling`, the
+    // communicator is driven until they did.
+    //
+    // # Returns:
+    // * The `ThreadSnapshot` of the thread, serializable through `JsonConversion`.
+    async fn snapshot(&mut self) -> ThreadSnapshot<T>
+    where
+        Self: Sized,
+    {
+        let pending = SnapshotTap::new(self).request();
+        if let HandleMode::Polling = self.get_config().get_handle_mode() {
+            drive(self).await;
+        }
+        let snapshot = pending.receive().await;
+        self.get_queues().store_pending();
+        let queues = self.get_queues().get_queues().iter()
+            .filter(|(_, queue)| !queue.is_empty())
+            .map(|(sender, queue)| (*sender, queue.iter().cloned().collect()))
+            .collect();
+        snapshot.with_queues(queues)
+    }
+
+    // # Method Description:
+    // This method restores a snapshot of this thread, taken from a communicator of the same thread, e.g.
+    // before it crashed: the messages of the snapshot are queued again ahead of the messages already
+    // received, and the handles start from the monitors of the snapshot once initialized.
+    //
+    // # Parameters:
+    // * snapshot - The `ThreadSnapshot` to restore.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why the snapshot cannot be restored: it belongs to another thread, or
+    //   a handle of this thread was already started.
+    fn restore(&mut self, mut snapshot: ThreadSnapshot<T>) -> Result<(), String> {
+        if snapshot.get_id() != *self.get_id() {
+            return Err(format!("the snapshot of id: {} cannot be restored into id: {}", snapshot.get_id(), self.get_id()))
+        }
+        if self.get_metrics().get_health_probe().has_started() {
+            return Err(format!("the handles of id: {} were already started", self.get_id()))
+        }
+        for (sender, messages) in snapshot.take_queues() {
+            let queue = self.get_queues().get_queues().entry(sender).or_default();
+            for message in messages.into_iter().rev() {
+                queue.push_front(message);
+            }
+        }
+        self.get_signal_channels().restore(snapshot);
+        Ok(())
+    }
+
+    // # Method Description:
+    // This method queries the reliable handle for a snapshot of every instance it monitors. The query is
+    // answered by the handle itself, once it has processed the signals received before it (in
+    // `HandleMode::Polling`, when the communicator is next driven).
+    //
+    // # Returns:
+    // * A `oneshot::Receiver` that yields a `ReliableInstanceSnapshot` per instance, by instance ID.
+    fn reliable_inspect(&self) -> oneshot::Receiver<BTreeMap<String, ReliableInstanceSnapshot>> {
+        let (responder, snapshots) = oneshot::channel();
+        let _ = self.get_reliable_command_channel().send(ReliableHandleCommand::Inspect(responder));
+        snapshots
+    }
+
+    // # Method Description:
+    // This method provides the report channels the reliable handle delivers non-"reliable" content to.
+    // Communicators that only run the reliable protocol have none.
+    //
+    // # Returns:
+    // * `Some(ReportChannels)` if delivered reports should be routed to a witness-style handle, otherwise `None`.
+    fn get_delivery_report_channels(&self) -> Option<&ReportChannels<T>> {
+        None
+    }
+
+    // # Method Description:
+    // This method provides the command channel of the handle holding the rounds of the thread, queried for
+    // their monitors by `snapshot`. Communicators that only run the reliable protocol have none.
+    //
+    // # Returns:
+    // * `Some(UnboundedSender)` if the thread runs a witness-style handle, otherwise `None`.
+    fn get_round_command_channel(&self) -> Option<&UnboundedSender<WitnessHandleCommand<T>>> {
+        None
+    }
+
+    // # Method Description:
+    // This method provides the capabilities of the communicator: the protocols it runs, and the codec and
+    // authentication of its hub. The reliable handle discards the delivered content none of its protocols
+    // delivers, and publishes it as `Event::CapabilityMismatch`.
+    //
+    // # Returns:
+    // * The capabilities of the communicator, running the reliable protocol only unless overridden.
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::from_config(self.get_config(), &[Protocol::Reliable])
+    }
+
+    // # Method Description:
+    // This method sets the external validity predicate of the payloads this thread reliably broadcasts and
+    // delivers, for validated broadcast: the reliable handle neither echoes, votes for, nor delivers a
+    // payload failing it, and publishes `Event::InvalidPayload` instead. As long as the correct threads
+    // share a deterministic predicate, only valid payloads gather a quorum, so no correct thread delivers
+    // an invalid one. The predicate applies to the payloads of every protocol relying on the reliable
+    // handle (e.g. the values of witness rounds), and to the instances received once it is set.
+    //
+    // # Parameters:
+    // * predicate - A function returning whether a payload is valid.
+    fn set_validity_predicate(&self, predicate: impl Fn(&T) -> bool + Send + Sync + 'static) {
+        self.get_signal_channels().set_validity_predicate(Arc::new(predicate));
+    }
+
     async fn upon_input(thread_id: u32, thread_signal_channel: &SignalChannels<T>, signal: Signal<T>);
     async fn upon_echo(thread_id: u32, thread_signal_channel: &SignalChannels<T>, signal: Signal<T>);
     async fn upon_vote(thread_id: u32, thread_channel: ChannelType<T>, signal: Signal<T>); 
     
     fn get_signal_channels(&self) -> &SignalChannels<T>;
-    fn take_reliable_handle_rx(&mut self) -> Receiver<String>;
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>>;
+    fn get_metrics(&self) -> &Metrics;
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T>;
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes>;
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand>;
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand>;
 }
 
 // # Struct Description:
 // This struct manages a collection of ReliableCommunicator instances to enable reliable broadcast communication
 // among asynchronous threads. Each communicator is initialized with both standard and signal-based communication
-// channels to support protocols like reliable broadcast.
+// channels to support protocols like reliable broadcast. Both are multiplexed as lanes over the single channel of
+// every thread. Unlike the hubs of the other protocols, a reliable hub lets threads join and leave the
+// cluster at runtime (see `join` and `leave`), when its configuration allows it.
 //
 // # Fields:
 // * reliable_communicators - A vector of ReliableCommunicator instances.
+// * config - The configuration shared by every communicator created from this hub.
+// * roster - The live membership of the cluster, `None` if the configuration does not allow it to change (see `ClusterConfig::check_dynamic_membership`).
+// * snapshot_taps - The command channels of the handles of every thread created, snapshot by `snapshot`.
 pub struct ReliableHub<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    reliable_communicators: Vec<ReliableCommunicator<T>>
+    reliable_communicators: Vec<ReliableCommunicator<T>>,
+    config: ClusterConfig,
+    roster: Option<Roster>,
+    snapshot_taps: Vec<SnapshotTap<T>>,
 }
  
 impl<T> ReliableHub<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    pub fn new(transmitters: Vec<Sender<String>>, mut receivers: Vec<Receiver<String>>, thread_count: u32) -> Self {  
-        let mut reliable_communicators = vec![];
-        let mut handle_transmitters = vec![];
-        let mut handle_receivers = vec![];
+    pub fn new(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, thread_count: u32) -> Self {  
+        Self::with_config(transmitters, receivers, ClusterConfig::new(thread_count))
+    }
 
-        for _ in 0..(thread_count) {
-            let (handle_tx, handle_rx) = mpsc::channel(256); 
-            handle_transmitters.push(handle_tx);
-            handle_receivers.push(handle_rx);
-        }
+    // # Function Description:
+    // This function creates a hub whose quorums are weighted: every Echo, Vote, value, and witness counts
+    // for the weight (stake) of its sender, and the thresholds are derived from the total weight following
+    // the quorum configuration of `config` (see `QuorumConfig::with_weights`).
+    //
+    // # Parameters:
+    // * transmitters - The transmitter of every thread.
+    // * receivers - The receiver of every thread.
+    // * config - The configuration of the cluster.
+    // * weights - The weight of every thread, indexed by thread ID.
+    //
+    // # Returns:
+    // * The hub, or a description of why the weights cannot be used (see `QuorumConfig::thresholds`): weights
+    //   that do not cover every thread, a total weight of zero, or a total that does not fit a `u64`.
+    pub fn with_weights(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, config: ClusterConfig, weights: Vec<u64>) -> Result<Self, String> {
+        let quorum_config = config.get_quorum_config().clone().with_weights(weights);
+        Ok(Self::with_config(transmitters, receivers, config.with_quorum_config(quorum_config)?))
+    }
+
+    pub fn with_config(transmitters: Vec<Sender<Bytes>>, mut receivers: Vec<Receiver<Bytes>>, config: ClusterConfig) -> Self {  
+        let thread_count = config.get_thread_count();
+        let mut reliable_communicators = vec![];
+        let roster = config.check_dynamic_membership().ok().map(|_| Roster::new(transmitters.clone(), &config));
         
         for i in 0..(thread_count) {
-            let handle_rx = handle_receivers.remove(0);
-            let rx = receivers.remove(0);
-            reliable_communicators.push(ReliableCommunicator::new(transmitters.clone(), rx, thread_count, i as u32, handle_transmitters.clone(), handle_rx));
+            let lanes = open_lanes(i, config.link_receiver(i, receivers.remove(0)), &config);
+            reliable_communicators.push(ReliableCommunicator::new(config.link_transmitters(i, &transmitters), lanes, i, config.clone(), roster.as_ref()));
         }
+        let snapshot_taps = reliable_communicators.iter().map(ReliableCommunicator::get_snapshot_tap).collect();
         
         Self {
-            reliable_communicators
+            reliable_communicators,
+            config,
+            roster,
+            snapshot_taps,
+        }
+    }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    pub fn get_roster(&self) -> Option<&Roster> {
+        self.roster.as_ref()
+    }
+
+    // # Method Description:
+    // This method admits a new thread into the cluster. The thread gets the next unused ID and a channel
+    // of its own, every communicator of the hub starts sending to it, and a new epoch begins, whose
+    // thresholds are derived from the new number of members. Instances broadcast in earlier epochs keep
+    // being counted with the members and thresholds of their epoch.
+    //
+    // # Returns:
+    // * The communicator of the new thread, configured with the current configuration of the cluster, or a
+    //   description of why the membership of the cluster cannot change.
+    pub fn join(&mut self) -> Result<ReliableCommunicator<T>, String> {
+        let roster = self.get_dynamic_roster()?;
+        let (transmitter, receiver) = mpsc::channel::<Bytes>(self.config.get_buffer_size());
+        let epoch = roster.join(transmitter, &self.config)?;
+        let transmitters = roster.get_transmitters();
+        self.config = self.config.clone().with_epoch(transmitters.len() as u32, epoch.get_thresholds().clone());
+        let id = *epoch.get_members().last().unwrap();
+        let lanes = open_lanes(id, receiver, &self.config);
+        let communicator = ReliableCommunicator::new(transmitters.to_vec(), lanes, id, self.config.clone(), Some(&roster));
+        self.snapshot_taps.push(communicator.get_snapshot_tap());
+        Ok(communicator)
+    }
+
+    // # Method Description:
+    // This method removes a thread from the cluster: a new epoch begins without it, the communicators stop
+    // sending to it, and reliable handles ignore its signals in the new epoch. Its ID is never reused.
+    //
+    // # Parameters:
+    // * id - The ID of the leaving thread.
+    //
+    // # Returns:
+    // * The new epoch number, or a description of why the thread cannot leave.
+    pub fn leave(&mut self, id: u32) -> Result<u32, String> {
+        let roster = self.get_dynamic_roster()?;
+        let epoch = roster.leave(id, &self.config)?;
+        self.config = self.config.clone().with_epoch(self.config.get_thread_count(), epoch.get_thresholds().clone());
+        Ok(epoch.get_epoch())
+    }
+
+    fn get_dynamic_roster(&self) -> Result<Roster, String> {
+        match &self.roster {
+            Some(roster) => Ok(roster.clone()),
+            None => Err(format!("the membership of the cluster cannot change: {}", self.config.check_dynamic_membership().unwrap_err())),
         }
     }
  
@@ -166,6 +653,53 @@ where
     pub fn create_reliable_communicator(&mut self) -> ReliableCommunicator<T>{
         self.reliable_communicators.remove(0)
     }
+
+    // # Method Description:
+    // This method checks that every channel of the hub reaches the thread it is meant for (see `validate_wiring`).
+    // It must be called before any communicator is created from the hub.
+    // # Returns:
+    // * `Ok(())` if the wiring is correct, or a description of every mis-wired channel.
+    pub async fn validate(&mut self) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        validate_wiring(&mut self.reliable_communicators).await
+    }
+
+    // # Method Description:
+    // This method snapshots the running handles of every thread created from the hub (see `SnapshotTap`),
+    // including the threads whose communicator was handed out. The basic queues of a thread are only
+    // captured by the `snapshot` of its communicator. In `HandleMode::Polling`, the handles answer once
+    // their communicators are driven.
+    //
+    // # Returns:
+    // * The `ClusterSnapshot` of the cluster, serializable through `JsonConversion`.
+    pub async fn snapshot(&self) -> ClusterSnapshot<T> {
+        ClusterSnapshot::new(join_all(self.snapshot_taps.iter().map(SnapshotTap::snapshot)).await)
+    }
+
+    // # Method Description:
+    // This method restores a snapshot of the cluster into the communicators the hub still holds (see
+    // `ReliableCommunication::restore`). It must be called before the communicators are created.
+    //
+    // # Parameters:
+    // * snapshot - The `ClusterSnapshot` to restore.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why a thread of the snapshot cannot be restored.
+    pub fn restore(&mut self, snapshot: ClusterSnapshot<T>) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        for thread in snapshot.into_threads() {
+            let id = thread.get_id();
+            let Some(communicator) = self.reliable_communicators.iter_mut().find(|communicator| *communicator.get_id() == id) else {
+                return Err(format!("the communicator of id: {id} is not held by the hub"))
+            };
+            communicator.restore(thread)?;
+        }
+        Ok(())
+    }
  }
 
  
@@ -181,6 +715,13 @@ where
 // * queues - A `BasicQueues` instance that stores incoming messages for this thread.
 // * handle_rx - An receiver for signal-related messages, used by the async task that 
 //               processes protocol-level coordination messages.
+// * command_tx - A transmitter for sending commands (e.g. inspection queries) to the reliable handle.
+// * command_rx - A receiver for commands, taken by the reliable handle.
+// * event_tx - A transmitter on which the background handles publish `Event`s.
+// * metrics - The metrics of the thread, in which the reliable handle records its phase latencies.
+// * delivery_watches - The watch channels on which the reliable handle publishes deliveries.
+// * polled_handles - The parts of the thread driven manually in `HandleMode::Polling`.
+// * config - The configuration shared by every communicator created from the same hub.
 pub struct ReliableCommunicator<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
@@ -189,18 +730,41 @@ where
     basic_channels: MessageChannels<T>, 
     signal_channels: SignalChannels<T>, 
     queues: BasicQueues<T>,
-    handle_rx: Option<Receiver<String>>, 
+    handle_rx: Option<Receiver<Bytes>>, 
+    command_tx: UnboundedSender<ReliableHandleCommand>,
+    command_rx: Option<UnboundedReceiver<ReliableHandleCommand>>,
+    event_tx: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
 }
 
 impl<T> ReliableCommunicator<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    fn new(transmitters: Vec<Sender<String>>, receiver: Receiver<String>, thread_count: u32, id: u32, handle_transmitters: Vec<Sender<String>>, handle_rx: Receiver<String>) -> Self {
-        let basic_channels = MessageChannels::new(transmitters.clone());
-        let signal_channels = SignalChannels::<T>::new(handle_transmitters.clone());
-        let queues = BasicQueues::new(receiver, thread_count);
-        let handle_rx = Some(handle_rx);
+    fn get_snapshot_tap(&self) -> SnapshotTap<T> {
+        SnapshotTap::with_channels(self.id, self.command_tx.clone(), None, self.metrics.get_health_probe().clone())
+    }
+
+    fn new(transmitters: Vec<Sender<Bytes>>, lanes: LaneReceivers, id: u32, config: ClusterConfig, roster: Option<&Roster>) -> Self {
+        let mut basic_channels = MessageChannels::new(transmitters.clone()).with_codec(config.get_codec());
+        let metrics = lanes.metrics;
+        let mut signal_channels = SignalChannels::<T>::new(transmitters).with_dissemination(id, &config).with_metrics(&metrics).with_signing(id, &config).with_journal(id, &config).with_codec(config.get_codec()).with_batching(&config);
+        let mut queues = BasicQueues::new(lanes.basic, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy())
+            .with_validation(id, &config).with_metrics(metrics.clone());
+        if let Some(roster) = roster {
+            basic_channels = basic_channels.with_roster(roster);
+            signal_channels = signal_channels.with_roster(roster);
+            queues = queues.with_roster(roster);
+        }
+        let handle_rx = Some(lanes.signal);
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        let command_rx = Some(command_rx);
+        let (event_tx, _) = broadcast::channel(config.get_buffer_size());
+        let delivery_watches = DeliveryWatches::new();
+        let polled_handles = PolledHandles::new(lanes.demultiplexer);
 
         Self {
             id, 
@@ -208,8 +772,71 @@ where
             signal_channels,
             queues,
             handle_rx, 
+            command_tx,
+            command_rx,
+            event_tx,
+            metrics,
+            delivery_watches,
+            polled_handles,
+            config,
         }
     }
+
+    // # Function Description:
+    // This function creates the communicator of a thread restarted after a crash from the journal it kept
+    // under a path. Unlike a hub, a restarted thread cannot create the channels of the cluster, so it is
+    // also handed the transmitters of its peers and its own new receiver. The journal is read from the
+    // directory of the thread (`node-<id>`) under the path, with the durable backend of the configuration,
+    // and the reliable handle of the communicator replays the signals it received before the crash when it
+    // starts, restoring its instances and delivering them again, before it processes new signals. Signals
+    // conflicting with the ones it sent before the crash are not sent. The thread keeps journaling under
+    // the same path.
+    //
+    // # Parameters:
+    // * path - The root of the storage the thread journaled to before the crash.
+    // * transmitters - The transmitter of every thread.
+    // * receiver - The new receiver of the thread.
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster, enabling journaling.
+    //
+    // # Returns:
+    // * The communicator, or a description of why the thread cannot be recovered: journaling is disabled,
+    //   no journal of the thread is under the path, or the journal cannot be read.
+    pub fn recover(path: &Path, transmitters: Vec<Sender<Bytes>>, receiver: Receiver<Bytes>, id: u32, config: ClusterConfig) -> Result<Self, String> {
+        let config = recovery_config::<T>(path, id, config)?;
+        let lanes = open_lanes(id, config.link_receiver(id, receiver), &config);
+        Ok(Self::new(config.link_transmitters(id, &transmitters), lanes, id, config, None))
+    }
+}
+
+// # Function Description:
+// This function checks that a thread can be recovered from the journal it kept under a path (see
+// `ReliableCommunicator::recover`).
+//
+// # Parameters:
+// * path - The root of the storage the thread journaled to.
+// * id - The ID of the thread.
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The configuration of the thread, storing under the path, or a description of why the thread cannot be
+//   recovered.
+pub(crate) fn recovery_config<T>(path: &Path, id: u32, config: ClusterConfig) -> Result<ClusterConfig, String>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    if !path.join(format!("node-{id}")).exists() {
+        return Err(format!("no journal of thread {id} under {}", path.display()))
+    }
+    let backend = config.get_storage_backend().with_root(path);
+    let config = config.with_storage_backend(backend);
+    match SignalJournal::<T>::from_config(id, &config)? {
+        Some(journal) => {
+            log!(info, { node = id }, "id: {id}, recovering from a journal of {} records...", journal.get_length());
+            Ok(config)
+        },
+        None => Err(String::from("journaling is disabled (see `ClusterConfig::with_journaling`)")),
+    }
 }
 
 #[async_trait]
@@ -217,93 +844,6 @@ impl<T> ReliableCommunication<T> for ReliableCommunicator<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
 {
-    // # Method Description:
-    // Spawns an asynchronous background task that listens for incoming signal messages 
-    // (Input, Echo, Vote).
-    // The task tracks instance states, applies threshold-based transitions, 
-    // and ensures messages are delivered once protocol conditions are met.
-    //
-    // # Returns:
-    // * A `JoinHandle` to the spawned task, that runs until explicitly terminated.
-    fn initialize_reliable_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing reliable handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let thread_count = thread_channel.get_channels().len() as u32; 
-        let mut receiver = self.take_reliable_handle_rx(); 
-
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let agreement_threshold = faulty_threads + 1;
-        let mut reliable_broadcast_monitor: HashMap<String, ReliableInstanceMonitor> = HashMap::new();
-
-        
-        let handle = tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    Some(received_signal) = receiver.recv() => {
-                        let signal = match Signal::read_json(&received_signal) {
-                            Ok(correct_signal) => correct_signal,
-                            Err(_)=> { continue },
-                        };
-
-                        let instance_id = Self::get_instance_id(thread_id, signal.clone()); 
-                        if let SignalType::Input = signal.get_signal() {
-                            match reliable_broadcast_monitor.get(&instance_id) {
-                                Some(_) => {
-                                    panic!("Error: instance id already used")
-                                },
-                                None => {
-                                    reliable_broadcast_monitor.insert(instance_id.clone(), ReliableInstanceMonitor::new());
-                                },
-                            }
-                        }
-
-                        let instance = reliable_broadcast_monitor.get_mut(&instance_id).unwrap(); 
-                        let state = &mut instance.state; 
-                        let count = &mut instance.count; 
-
-                        match signal.get_signal()
-                        {
-                            SignalType::Input => {
-                                if state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Echo => {
-                                count.echo += 1;
-
-                                if count.echo >= validity_threshold && state.vote == false{
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else if count.echo >= agreement_threshold && state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Vote => {
-                                count.vote += 1;
-    
-                                if count.vote >= validity_threshold && state.deliver == false {
-                                    let channel = ChannelType::MessageChannels(thread_channel.clone());
-                                    Self::upon_vote(thread_id, channel, signal).await;
-                                    state.deliver = true;
-                                } else if count.vote >= agreement_threshold && state.vote == false {
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else { continue }
-                            }
-                        }
-                    }
-                }
-            }
-        });
-        handle
-    }
-
     // # Method Description:
     // As the first acknowledgment step in the reliable broadcast protocol,
     // handles an `Input` signal by wrapping and broadcasting the original content as an `Echo` signal to all participants.
@@ -314,7 +854,7 @@ where
     // * signal - The received `Input` signal.
 
     async fn upon_input(thread_id: u32, thread_signal_channel: &SignalChannels<T>, signal: Signal<T>){
-        println!("id {}, instance: {}, echoing...", thread_id, signal.get_instance_number());
+        log!(debug, { node = thread_id, transition = "echo" }, "id {}, instance: {}, echoing...", thread_id, signal.get_instance_number());
 
         let echo = Signal::new(SignalType::Echo, signal.get_content().clone(), signal.get_instance_number(), signal.get_round_number());
         thread_signal_channel.broadcast_signal(echo).await;
@@ -329,7 +869,7 @@ where
     // * thread_signal_channel - The channel used to broadcast the `Vote` signal.
     // * signal - The received `Echo` signal.
     async fn upon_echo(thread_id: u32, thread_signal_channel: &SignalChannels<T>, signal: Signal<T>) {
-        println!("id {}, instance: {}, voting...", thread_id, signal.get_instance_number());
+        log!(debug, { node = thread_id, transition = "vote" }, "id {}, instance: {}, voting...", thread_id, signal.get_instance_number());
 
         let vote = Signal::new(SignalType::Vote, signal.get_content().clone(), signal.get_instance_number(), signal.get_round_number());
         thread_signal_channel.broadcast_signal(vote).await; 
@@ -339,15 +879,16 @@ where
     // # Method Description:
     // As the completion step in the reliable broadcast protocol,
     // handles a `Vote` signal by delivering the final message to the application layer via `MessageChannels`.
-    // Panics if the channel or content type does not match expectations.
+    // The handle discards the content the communicator does not deliver beforehand (see `get_capabilities`),
+    // so any other channel or content type panics.
     //
     // # Parameters:
     // * thread_id - The ID of the current thread processing the signal.
     // * channel - The channel used to deliver the final message (`MessageChannels` expected).
     // * signal - The received `Vote` signal.
     async fn upon_vote(thread_id: u32, channel: ChannelType<T>, signal: Signal<T>)  {
-        println!("id {}, instance: {}, delivering...",thread_id,  signal.get_instance_number());
-        let object = signal.get_content().clone(); 
+        log!(info, { node = thread_id, transition = "deliver" }, "id {}, instance: {}, delivering...",thread_id,  signal.get_instance_number());
+        let object = signal.into_content();
         
         if let (ChannelType::MessageChannels(thread_channel), ObjectContent::Message(message)) = (channel, object) {
             thread_channel.send_message(thread_id, message).await;
@@ -360,9 +901,29 @@ where
         &self.signal_channels
     }
 
-    fn take_reliable_handle_rx(&mut self) -> Receiver<String> {
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>> {
+        &self.event_tx
+    }
+
+    fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T> {
+        &self.delivery_watches
+    }
+
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes> {
         self.handle_rx.take().unwrap()
     }
+
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand> {
+        self.command_rx.take().unwrap()
+    }
+
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand> {
+        &self.command_tx
+    }
 }
 
 impl<T> BasicCommunication<T> for ReliableCommunicator<T>
@@ -380,69 +941,1540 @@ where
     fn get_id(& self) -> &u32 {
         &self.id
     }
+
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
 }
 
 
 // # Struct Description:
-// This struct manages a collection of channel transmitters used to broadcast serialized `Signal` messages.
-// It enables reliable and parallel signal transmission to multiple asynchronous threads.
+// This struct holds the state of a thread's reliable handle and processes the signals it receives.
+// The signals are processed by the task spawned by `initialize_reliable_handle` in `HandleMode::Spawned`,
+// and by `drive` in `HandleMode::Polling`.
+// Delivered content is routed to `ReportChannels` when the communicator provides them and the
+// content does not belong to the "reliable" protocol, otherwise to `MessageChannels`.
+// A second Input for an existing instance is handled according to the configured `DuplicateInputPolicy`,
+// and signals that cannot be decoded are reported according to the configured `DecodePolicy`. Under
+// `Authentication::Ed25519`, signals that are not signed by their claimed sender are rejected, and with a
+// membership (see `ClusterConfig::with_membership`), signals naming a thread that is not a member are rejected.
+// With a roster (see `ReliableHub::join`), every instance is counted with the members and thresholds of the
+// epoch its message is stamped with.
+// Delivered content that none of the communicator's protocols delivers (see `Capabilities::supports`) is
+// discarded and published as `Event::CapabilityMismatch`; the thread still echoes and votes for it, so that
+// the peers running the protocol keep their quorums.
+// Under a `LeaderElection`, the Inputs of the "reliable" instances of a round are only accepted from the leader
+// of the round; the others are published as `Event::NonLeaderInput` and never echoed.
+// With a pacemaker (see `ClusterConfig::with_pacemaker`), a round with an instance left undelivered for the
+// timeout changes views together with the other threads (see `Pacemaker`), published as `Event::ViewChanged`;
+// under a `LeaderElection`, the new view elects a new leader, and the rejected Inputs of the round are queued
+// until their sender leads it.
+// Delivered instances are collected according to the configured `RetentionPolicy`: collected instances are no
+// longer inspected nor re-sent to recovering threads, and their late signals are ignored.
+// The latency of every phase of every instance is recorded in the communicator's `Metrics`,
+// including the processing delay of any `Fault::Slowdown` injected into the thread.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * thread_channel - The channels on which "reliable" content is delivered.
+// * thread_signal_channel - The channels on which Echo and Vote signals are broadcast.
+// * report_channel - The channels on which other content is delivered, if any.
+// * event_channel - The channel on which events are published.
+// * capabilities - The capabilities of the communicator, giving the content it delivers.
+// * metrics - The phase latency histograms of the communicator.
+// * delivery_watches - The watch channels on which "reliable" deliveries are published.
+// * duplicate_input_policy - How a second Input for an existing instance is handled.
+// * leader_schedule - The leader of every round, whose "reliable" Inputs are the only ones accepted, if one is elected.
+// * pacemaker - The timers of the undelivered instances and the views of the rounds.
+// * queued_inputs - The Inputs rejected because their sender did not lead their round, by round, processed
+//   again once the round changes views, with a pacemaker.
+// * deliver_to_self - Whether the thread's own "reliable" instances are delivered to it.
+// * decode_failures - The reporter of the signals that cannot be decoded.
+// * membership - The signed membership of the cluster, if any.
+// * receiver - The receiver of the thread's `Signal` lane.
+// * command_receiver - The receiver of the commands sent to the handle.
+// * processing_delay - The processing delay of the thread's `Fault::Slowdown`, if any.
+// * validity_threshold - The number of signals needed to echo, vote, or deliver, without a roster.
+// * agreement_threshold - The number of signals needed to join an instance, without a roster.
+// * thresholds - The thresholds of the cluster.
+// * self_counting - Whether the thread counts its own Echo and Vote signals towards its quorums.
+// * counting_thresholds - The thresholds the thread counts with (see `SelfCounting`), giving the weight every
+//   Echo and Vote signal counts for.
+// * fast_path - How long after the first signal of an instance it can be delivered on the Echo signals of every thread, if it can.
+// * cluster_weight - The combined weight the Echo signals of every thread count for, which the fast path must reach.
+// * processors - The processors of the protocols departing from reliable broadcast (see `InstanceProcessor`).
+// * last_anti_entropy - The instant the handle last sent its `GossipDigest`, if it did.
+// * replayed - Whether the handle replayed the journal of the thread, which it does before processing any signal.
+// * stopped - Whether the handle was shut down (see `ReliableCommunication::shutdown_reliable_handle`).
+// * running - The record of the handle running in the thread's `HealthProbe`, marked stopped once the handle stops.
+// * reliable_broadcast_monitor - The monitor of every instance, by instance ID.
+// * retention - The tracker of the delivered instances, which decides when they are collected.
+// * trace_sampler - The sampler deciding which instances record phase latencies and round statuses.
+pub struct ReliableHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    thread_id: u32,
+    thread_channel: MessageChannels<T>,
+    thread_signal_channel: SignalChannels<T>,
+    report_channel: Option<ReportChannels<T>>,
+    event_channel: broadcast::Sender<Event<T>>,
+    capabilities: Capabilities,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    duplicate_input_policy: DuplicateInputPolicy,
+    leader_schedule: LeaderSchedule,
+    pacemaker: Pacemaker,
+    queued_inputs: HashMap<Round, Vec<Signal<T>>>,
+    deliver_to_self: bool,
+    decode_failures: DecodeFailureReporter<T>,
+    membership: Option<MembershipCertificate>,
+    receiver: Receiver<Bytes>,
+    command_receiver: UnboundedReceiver<ReliableHandleCommand>,
+    processing_delay: Option<Duration>,
+    validity_threshold: u64,
+    agreement_threshold: u64,
+    thresholds: Thresholds,
+    self_counting: SelfCounting,
+    counting_thresholds: Thresholds,
+    fast_path: Option<Duration>,
+    cluster_weight: u64,
+    processors: ProcessorRegistry<T>,
+    reliable_broadcast_monitor: HashMap<String, ReliableInstanceMonitor<T>>,
+    retention: RetentionTracker<String>,
+    trace_sampler: TraceSampler,
+    last_anti_entropy: Option<Instant>,
+    replayed: bool,
+    stopped: bool,
+    running: RunningHandle,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<T, C> ReliableHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T> + 'static,
+{
+    fn new(communicator: &mut C) -> Self {
+        let thread_id = *communicator.get_id();
+        let thresholds = communicator.get_config().get_thresholds().clone();
+        let counting_thresholds = communicator.get_config().get_counting_thresholds(thread_id);
+        let thread_count = communicator.get_config().get_thread_count();
+        let cluster_weight = (0..thread_count).map(|id| thresholds.get_weight(id)).sum();
+        let deliver_to_self = communicator.get_config().get_deliver_to_self();
+        let processors = ProcessorRegistry::new()
+            .with_processor(DISPERSAL, DispersalProcessor::new(thread_id, thread_count, thresholds.get_faulty_threads() as u32, communicator.get_delivery_watches().clone()))
+            .with_processor(CONSISTENT, ConsistentProcessor::new(thread_id, thresholds.clone(), cluster_weight, deliver_to_self))
+            .with_processor(EXTENSION, ExtensionProcessor::new(thread_id, communicator.get_delivery_watches().get_extensions().clone(), communicator.get_event_channel().clone()));
+        let restored = communicator.get_signal_channels().take_restored_instances();
+        let mut retention = RetentionTracker::new(communicator.get_config().get_retention_policy());
+        for (instance_id, instance) in &restored {
+            if instance.state.deliver {
+                retention.complete(instance_id.clone());
+            }
+        }
+
+        Self {
+            thread_id,
+            thread_channel: communicator.get_channels().clone(),
+            thread_signal_channel: communicator.get_signal_channels().clone(),
+            report_channel: communicator.get_delivery_report_channels().cloned(),
+            event_channel: communicator.get_event_channel().clone(),
+            capabilities: communicator.get_capabilities(),
+            metrics: communicator.get_metrics().clone(),
+            delivery_watches: communicator.get_delivery_watches().clone(),
+            duplicate_input_policy: communicator.get_config().get_duplicate_input_policy(),
+            leader_schedule: communicator.get_config().get_leader_schedule(),
+            pacemaker: Pacemaker::new(communicator.get_config().get_pacemaker()),
+            queued_inputs: HashMap::new(),
+            deliver_to_self,
+            decode_failures: DecodeFailureReporter::new(communicator, Lane::Signal),
+            membership: communicator.get_config().get_membership().cloned(),
+            receiver: communicator.take_reliable_handle_rx(),
+            command_receiver: communicator.take_reliable_command_rx(),
+            processing_delay: communicator.get_config().get_fault_script().get_processing_delay(thread_id),
+            validity_threshold: counting_thresholds.get_validity_threshold(),
+            agreement_threshold: counting_thresholds.get_agreement_threshold(),
+            thresholds,
+            self_counting: communicator.get_config().get_quorum_config().get_self_counting(),
+            cluster_weight: (0..communicator.get_config().get_thread_count()).map(|id| counting_thresholds.get_weight(id)).sum(),
+            counting_thresholds,
+            fast_path: communicator.get_config().get_fast_path(),
+            processors,
+            reliable_broadcast_monitor: restored.into_iter().collect(),
+            retention,
+            trace_sampler: communicator.get_config().get_trace_sampler(),
+            last_anti_entropy: None,
+            replayed: false,
+            stopped: false,
+            running: communicator.get_metrics().get_health_probe().start_handle("reliable"),
+            _marker: PhantomData,
+        }
+    }
+
+    // # Method Description:
+    // This method processes commands and signals as they arrive, exchanges digests with a random peer at
+    // the anti-entropy interval of the gossiped protocols, and checks the timers of the pacemaker, until the
+    // handle's task is aborted or the handle is shut down.
+    async fn run(mut self) {
+        self.replay_journal().await;
+        let mut anti_entropy = self.thread_signal_channel.get_disseminator().and_then(|disseminator| disseminator.get_anti_entropy_interval()).map(|interval| {
+            let mut anti_entropy = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            anti_entropy.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            anti_entropy
+        });
+        let mut pacemaker = self.pacemaker.get_timeout().map(|timeout| {
+            let period = (timeout / 4).max(Duration::from_millis(1));
+            let mut pacemaker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            pacemaker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            pacemaker
+        });
+        loop {
+            tokio::select! {
+                _ = next_gossip(&mut anti_entropy) => {
+                    self.exchange_digests().await;
+                }
+                _ = next_gossip(&mut pacemaker) => {
+                    self.check_timers().await;
+                }
+                Some(command) = self.command_receiver.recv() => {
+                    if let Some(responder) = self.process_command(command) {
+                        self.shutdown(responder).await;
+                        return
+                    }
+                }
+                Some(received_signal) = self.receiver.recv() => {
+                    if let Some(delay) = self.processing_delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    self.process_signal(received_signal).await;
+                }
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method processes a single command sent to the handle.
+    //
+    // # Parameters:
+    // * command - The command.
+    //
+    // # Returns:
+    // * The responder of a `Shutdown` command, which the caller must answer by shutting the handle down.
+    fn process_command(&mut self, command: ReliableHandleCommand) -> Option<ShutdownResponder> {
+        match command {
+            ReliableHandleCommand::Inspect(responder) => {
+                let snapshots = self.reliable_broadcast_monitor.iter()
+                    .map(|(instance_id, instance)| (instance_id.clone(), instance.snapshot()))
+                    .collect();
+                let _ = responder.send(snapshots);
+                None
+            },
+            ReliableHandleCommand::Snapshot(responder) => {
+                let monitors: BTreeMap<&String, &ReliableInstanceMonitor<T>> = self.reliable_broadcast_monitor.iter().collect();
+                let _ = responder.send(serde_json::to_string(&monitors).expect("Error: the snapshot of the reliable handle could not be written"));
+                None
+            },
+            ReliableHandleCommand::Shutdown(responder) => Some(responder),
+        }
+    }
+
+    // # Method Description:
+    // This method shuts the handle down: it processes the signals already received, then reports the
+    // instances that were not delivered.
+    //
+    // # Parameters:
+    // * responder - The sender on which the `ShutdownReport` is answered.
+    async fn shutdown(&mut self, responder: ShutdownResponder) {
+        let mut flushed = 0;
+        while let Ok(received_signal) = self.receiver.try_recv() {
+            self.process_signal(received_signal).await;
+            flushed += 1;
+        }
+        let mut open: Vec<String> = self.reliable_broadcast_monitor.iter()
+            .filter(|(_, instance)| !instance.state.deliver)
+            .map(|(instance_id, _)| instance_id.clone())
+            .collect();
+        open.sort();
+        log!(debug, { node = self.thread_id }, "id: {}, reliable handle stopped, {} signals flushed, {} instances open", self.thread_id, flushed, open.len());
+        self.stopped = true;
+        self.running.stop();
+        let _ = responder.send(ShutdownReport::new(self.thread_id, String::from("reliable"), flushed, open));
+    }
+
+    // # Method Description:
+    // This method processes a single frame received on the thread's `Signal` lane. The header of a signal is
+    // decoded first, and the signal is discarded without decoding its content if it comes from a thread that
+    // is not a member (of its epoch, with a roster), belongs to a collected instance, or was already counted.
+    //
+    // # Parameters:
+    // * received_signal - The frame.
+    async fn process_signal(&mut self, received_signal: Bytes) {
+        self.metrics.get_health_probe().record_frame();
+        let codec = self.thread_channel.get_codec();
+        let frame = match Frame::parse(&received_signal) {
+            Ok(frame) => frame,
+            Err(_) => {
+                self.decode_failures.report(&received_signal).await;
+                return
+            },
+        };
+        if frame.is::<SignalBatch>() && let Ok(signal_batch) = frame.read_payload(codec) {
+            self.process_signal_batch(signal_batch).await;
+            return
+        }
+        if frame.is::<GossipDigest>() && let Ok(gossip_digest) = frame.read_payload(codec) {
+            self.process_gossip_digest(gossip_digest).await;
+            return
+        }
+        if frame.is::<ViewChange>() && let Ok(view_change) = frame.read_payload(codec) {
+            self.process_view_change(view_change).await;
+            return
+        }
+        if frame.is::<SyncRequest>() && let Ok(sync_request) = frame.read_payload(codec) {
+            self.process_sync_request(sync_request).await;
+            return
+        }
+        let Ok(header) = frame.read_header::<SignalHeader>(codec) else {
+            self.decode_failures.report(&received_signal).await;
+            return
+        };
+
+        if let Some(membership) = &self.membership
+            && let Some(non_member) = header.get_claimed_ids().into_iter().find(|id| !membership.is_member(*id)) {
+            if let Ok(signal) = self.read_signal(&frame, &header) {
+                let _ = self.event_channel.send(Event::NonMember(self.thread_id, non_member, signal));
+            }
+            return
+        }
+        let Some((validity_threshold, agreement_threshold)) = self.get_signal_thresholds(&header) else {
+            return
+        };
+        let instance_id = format!("{}::{}", self.thread_id, header.instance_key);
+        if self.retention.is_collected(&instance_id) || self.is_counted(&instance_id, &header) {
+            return
+        }
+
+        let signal = match self.read_signal(&frame, &header) {
+            Ok(signal) => signal,
+            Err(_) => {
+                self.decode_failures.report(&received_signal).await;
+                return
+            },
+        };
+        if let Some(signer) = self.thread_signal_channel.get_signer() && !signal.is_authentic(signer) {
+            let _ = self.event_channel.send(Event::InvalidSignature(self.thread_id, signal));
+            return
+        }
+        self.metrics.record_traffic(signal.get_content().get_protocol_information(), received_signal.len());
+        if let Some(journal) = self.thread_signal_channel.get_journal() && let Err(reason) = journal.record_received(&signal) {
+            log!(warn, { node = self.thread_id }, "id: {}, journaling a received signal failed: {reason}", self.thread_id);
+        }
+        self.process_accepted_signal(signal, Some(SignalFrame { frame, header }), validity_threshold, agreement_threshold).await;
+    }
+
+    // # Method Description:
+    // This method decodes the content of a signal frame, and checks that the header of the frame describes it.
+    //
+    // # Parameters:
+    // * frame - The frame.
+    // * header - The decoded header of the frame.
+    //
+    // # Returns:
+    // * The signal, or a description of why it could not be decoded.
+    fn read_signal(&self, frame: &Frame, header: &SignalHeader) -> Result<Signal<T>, String> {
+        let mut signal: Signal<T> = frame.read_payload(self.thread_channel.get_codec())?;
+        signal.relay = header.relay;
+        if signal.get_header() != *header {
+            return Err(String::from("the header of the frame does not describe its signal"))
+        }
+        Ok(signal)
+    }
+
+    // # Method Description:
+    // This method checks whether an Echo or Vote was already counted towards its instance, from its header.
+    // Counting it again would change nothing, so it is discarded before its content is decoded.
+    //
+    // # Parameters:
+    // * instance_id - The ID of the signal's instance.
+    // * header - The header of the signal.
+    //
+    // # Returns:
+    // * `true` if the same sender's signal of the same type was counted for the same content.
+    fn is_counted(&self, instance_id: &String, header: &SignalHeader) -> bool {
+        let (SignalType::Echo | SignalType::Vote, Some(origin)) = (header.signal, header.origin) else {
+            return false
+        };
+        self.reliable_broadcast_monitor.get(instance_id)
+            .is_some_and(|instance| instance.senders.contains(&(header.signal, origin, header.quorum_digest.clone())))
+    }
+
+    // # Method Description:
+    // This method provides the thresholds a signal is counted with. With a roster, an instance is counted
+    // with the members and thresholds of the epoch it was broadcast in, and the signals of threads that
+    // were not members of that epoch are ignored.
+    //
+    // # Parameters:
+    // * header - The header of the signal.
+    //
+    // # Returns:
+    // * The validity and agreement thresholds, `None` if the signal must be ignored.
+    fn get_signal_thresholds(&self, header: &SignalHeader) -> Option<(u64, u64)> {
+        match self.thread_signal_channel.get_roster() {
+            Some(roster) => {
+                let epoch = roster.get_epoch(header.epoch)?;
+                if header.get_sender().is_some_and(|id| !epoch.is_member(id)) {
+                    return None
+                }
+                let thresholds = self.self_counting.apply(self.thread_id, epoch.get_thresholds());
+                Some((thresholds.get_validity_threshold(), thresholds.get_agreement_threshold()))
+            },
+            None => Some((self.validity_threshold, self.agreement_threshold)),
+        }
+    }
+
+    // # Method Description:
+    // This method processes a signal accepted by `process_signal`, or replayed from the journal of the thread.
+    //
+    // # Parameters:
+    // * signal - The signal.
+    // * received - The frame the signal was received in, `None` for a replayed signal.
+    // * validity_threshold - The number of signals needed to echo, vote, or deliver the instance.
+    // * agreement_threshold - The number of signals needed to join the instance.
+    async fn process_accepted_signal(&mut self, signal: Signal<T>, received: Option<SignalFrame>, validity_threshold: u64, agreement_threshold: u64) {
+        let instance_id = C::get_instance_id(self.thread_id, signal.clone()); 
+        // everything logged while the signal is processed is emitted in the span of its instance
+        let span = tracing::debug_span!("instance", instance_id = %instance_id, round = %signal.get_round_number(), node = self.thread_id);
+        self.process_instance_signal(signal, received, instance_id, validity_threshold, agreement_threshold).instrument(span).await;
+    }
+
+    // # Method Description:
+    // This method replays the signals the thread received before it restarted, from its journal, once,
+    // before the handle processes any new signal (see `ReliableCommunicator::recover`). Replayed signals
+    // are processed as they were the first time, so the handle echoes and votes the same contents and
+    // delivers the same instances again; they are not journaled again.
+    //
+    // # Returns:
+    // * The number of signals replayed.
+    async fn replay_journal(&mut self) -> usize {
+        if self.replayed {
+            return 0
+        }
+        self.replayed = true;
+        let Some(journal) = self.thread_signal_channel.get_journal().cloned() else {
+            return 0
+        };
+        let signals = match journal.get_received() {
+            Ok(signals) => signals,
+            Err(reason) => {
+                log!(warn, { node = self.thread_id }, "id: {}, the journal could not be replayed: {reason}", self.thread_id);
+                return 0
+            },
+        };
+        if !signals.is_empty() {
+            log!(info, { node = self.thread_id }, "id: {}, replaying {} journaled signals...", self.thread_id, signals.len());
+        }
+        let replayed = signals.len();
+        for signal in signals {
+            if let Some((validity_threshold, agreement_threshold)) = self.get_signal_thresholds(&signal.get_header()) {
+                self.process_accepted_signal(signal, None, validity_threshold, agreement_threshold).await;
+            }
+        }
+        replayed
+    }
+
+    // # Method Description:
+    // This method checks the Input of a "reliable" instance under a leader election, before the instance is
+    // created: it is only accepted from the leader of its round in its current view, so that a rejected Input
+    // is neither echoed nor delivered. With a pacemaker, a rejected Input is queued for the views its sender
+    // leads, and the round changes views unless the leader's own instance is delivered in time.
+    //
+    // # Parameters:
+    // * signal - The signal.
+    //
+    // # Returns:
+    // * `true` if the signal can be processed.
+    fn admit_input(&mut self, signal: &Signal<T>) -> bool {
+        let round_number = signal.get_round_number();
+        let view = self.pacemaker.get_view(round_number);
+        if let SignalType::Input = signal.get_signal()
+            && let ObjectContent::Message(message) = signal.get_content()
+            && message.get_protocol_information() == "reliable"
+            && !self.leader_schedule.accepts(message.get_id(), round_number, view) {
+            if let Some(leader) = self.leader_schedule.get_leader_in_view(round_number, view) {
+                log!(warn, { node = self.thread_id }, "id: {}, instance: {}, rejecting the input of id: {}, which is not the leader id: {leader} of round {round_number}", self.thread_id, signal.get_instance_number(), message.get_id());
+                let _ = self.event_channel.send(Event::NonLeaderInput(self.thread_id, leader, message.clone()));
+            }
+            if self.pacemaker.get_timeout().is_some() {
+                self.pacemaker.start(&leader_key(round_number), round_number);
+                self.queued_inputs.entry(round_number).or_default().push(signal.clone());
+            }
+            return false
+        }
+        true
+    }
+
+    // # Method Description:
+    // This method counts a signal towards its instance, and echoes, votes, or delivers the instance once
+    // the thresholds are met. The protocols departing from reliable broadcast check, count, and deliver their
+    // instances through their processor (see `InstanceProcessor`).
+    //
+    // # Parameters:
+    // * signal - The signal, accepted by `process_signal`.
+    // * received - The frame the signal was received in, relayed as is, `None` for a replayed signal.
+    // * instance_id - The ID of the signal's instance.
+    // * validity_threshold - The number of signals needed to echo, vote, or deliver the instance.
+    // * agreement_threshold - The number of signals needed to join the instance.
+    async fn process_instance_signal(&mut self, signal: Signal<T>, received: Option<SignalFrame>, instance_id: String, validity_threshold: u64, agreement_threshold: u64) {
+        for collected in self.retention.collect() {
+            self.reliable_broadcast_monitor.remove(&collected);
+            self.pacemaker.forget(&collected);
+        }
+        if self.retention.is_collected(&instance_id) {
+            return
+        }
+        if let Some(processor) = self.processors.get_mut(signal.get_content()) && !processor.accept(&signal) {
+            return
+        }
+        if !self.admit_input(&signal) {
+            return
+        }
+        let round_number = signal.get_round_number();
+        if !self.reliable_broadcast_monitor.contains_key(&instance_id) {
+            self.pacemaker.start(&instance_id, round_number);
+        }
+        let instance = self.reliable_broadcast_monitor.entry(instance_id.clone()).or_insert_with(ReliableInstanceMonitor::new); 
+
+        if let SignalType::Input = signal.get_signal() {
+            match &instance.input {
+                Some(first) => {
+                    C::upon_duplicate_input(self.thread_id, &self.event_channel, self.duplicate_input_policy, instance_id, first, signal.get_content());
+                    return
+                },
+                None => {
+                    tracing::debug!(transition = "input", "id: {}, instance: {}, received input", self.thread_id, signal.get_instance_number());
+                    instance.input = Some(signal.get_content().clone());
+                },
+            }
+        }
+
+        // a payload failing the external validity predicate is neither echoed, voted for, nor delivered, so
+        // that no correct thread helps an invalid payload to a quorum
+        if !self.thread_signal_channel.is_valid(signal.get_content()) {
+            if !instance.invalid && let ObjectContent::Message(message) = signal.get_content() {
+                log!(warn, { node = self.thread_id }, "id: {}, instance: {}, refusing the invalid payload of id: {}", self.thread_id, signal.get_instance_number(), message.get_id());
+                let _ = self.event_channel.send(Event::InvalidPayload(self.thread_id, instance_id, message.clone()));
+            }
+            instance.invalid = true;
+            return
+        }
+
+        if let Some(processor) = self.processors.get_mut(signal.get_content()) {
+            match processor.process(instance, &signal, received.as_ref(), &self.thread_signal_channel).await {
+                Processing::Count => {},
+                Processing::Ignore => return,
+                Processing::Deliver(queued) => {
+                    if queued {
+                        C::upon_vote(self.thread_id, ChannelType::MessageChannels(self.thread_channel.clone()), signal).await;
+                    }
+                    self.retention.complete(instance_id.clone());
+                    if let Some(journal) = self.thread_signal_channel.get_journal() && let Err(reason) = journal.record_delivered(&instance_id) {
+                        log!(warn, { node = self.thread_id }, "id: {}, journaling a delivery failed: {reason}", self.thread_id);
+                    }
+                    return
+                },
+            }
+        }
+        let processor = self.processors.get(signal.get_content());
+        let amplified = processor.is_none_or(|processor| processor.is_amplified());
+
+        // Echo and Vote signals are counted once per sender and content, for the sender's weight (nothing for
+        // the thread's own signals under `SelfCounting::Exclude`), towards the quorums of the content they carry
+        // only, and relayed along the spanning tree the first time they are received; a signal that does not
+        // name its sender cannot be attributed, and is discarded
+        let mut weight = 0;
+        let digest = signal.get_content().get_quorum_digest();
+        if let SignalType::Echo | SignalType::Vote = signal.get_signal() {
+            let Some(origin) = signal.get_origin() else {
+                return
+            };
+            if !instance.senders.insert((*signal.get_signal(), origin, digest.clone())) {
+                return
+            }
+            weight = self.counting_thresholds.get_weight(origin);
+            self.thread_signal_channel.relay_signal(&signal, received.as_ref()).await;
+            if self.thread_signal_channel.is_gossiped(signal.get_content().get_protocol_information()) {
+                instance.received.push(signal.clone());
+            }
+            if *signal.get_signal() == SignalType::Vote && signal.get_signature().is_some() && signal.get_content().get_protocol_information() == "reliable" {
+                instance.votes.push(signal.clone());
+            }
+            if let Some(processor) = processor {
+                processor.record(instance, &signal);
+            }
+            if *signal.get_signal() == SignalType::Echo && self.fast_path.is_some() {
+                let digest = signal.get_content().json_digest();
+                instance.echoes_diverged |= *instance.echo_digest.get_or_insert(digest) != digest;
+            }
+        }
+
+        // instances are sampled by their ID without the local thread ID, so every thread traces the same ones
+        let traced = self.trace_sampler.is_sampled(instance_id.split_once("::").map_or(instance_id.as_str(), |(_, key)| key));
+        let state = &mut instance.state; 
+        let delivered = state.deliver;
+        let count = instance.counts.entry(digest.clone()).or_insert_with(ReliableInstanceCount::new);
+        let timing = &mut instance.timing; 
+        let sent = &mut instance.sent;
+        let protocol_information = signal.get_content().get_protocol_information().clone();
+        let instance_number = signal.get_instance_number();
+        let sender = signal.get_content().get_id();
+
+        let echoes_diverged = instance.echoes_diverged;
+
+        match signal.get_signal()
+        {
+            SignalType::Input => {
+                if state.echo == false {
+                    sent.push(signal.answered_with(SignalType::Echo));
+                    C::upon_input(self.thread_id, &self.thread_signal_channel, signal.clone()).await;
+                    state.echo = true;
+                }
+            },
+            SignalType::Echo => {
+                count.echo += weight;
+                if count.echo >= validity_threshold && count.echo - weight < validity_threshold {
+                    let now = Instant::now();
+                    if traced {
+                        self.metrics.get_phase_latencies().record(&protocol_information, Phase::InputToEchoQuorum, now - timing.started);
+                    }
+                    timing.echo_quorum = Some(now);
+                }
+
+                if count.echo >= validity_threshold && state.vote == false{
+                    sent.push(signal.answered_with(SignalType::Vote));
+                    C::upon_echo(self.thread_id, &self.thread_signal_channel, signal.clone()).await;
+                    state.vote = true;
+                } else if count.echo >= agreement_threshold && state.echo == false && amplified {
+                    sent.push(signal.answered_with(SignalType::Echo));
+                    C::upon_input(self.thread_id, &self.thread_signal_channel, signal.clone()).await;
+                    state.echo = true;
+                }
+            },
+            SignalType::Vote => {
+                count.vote += weight;
+                if count.vote >= validity_threshold && count.vote - weight < validity_threshold {
+                    let now = Instant::now();
+                    if let Some(echo_quorum) = timing.echo_quorum && traced {
+                        self.metrics.get_phase_latencies().record(&protocol_information, Phase::EchoQuorumToVoteQuorum, now - echo_quorum);
+                    }
+                    timing.vote_quorum = Some(now);
+                }
+
+                if count.vote >= agreement_threshold && state.vote == false {
+                    sent.push(signal.answered_with(SignalType::Vote));
+                    C::upon_echo(self.thread_id, &self.thread_signal_channel, signal.clone()).await;
+                    state.vote = true;
+                }
+            }
+        }
+
+        // the fast path delivers an instance on the Echo signals of every thread, if they carry the same content
+        // and arrive in time: no correct thread can then gather an Echo quorum for another content, and every
+        // thread still gathers the Vote quorum of the instance, since this thread voted
+        let fast = *signal.get_signal() == SignalType::Echo && !state.deliver && amplified
+            && count.echo >= self.cluster_weight && !echoes_diverged
+            && self.fast_path.is_some_and(|timeout| timing.started.elapsed() <= timeout);
+        if fast {
+            log!(debug, { node = self.thread_id, transition = "fast path" }, "id: {}, instance: {}, every thread echoed the same content", self.thread_id, instance_number);
+        }
+        let (echoes, votes) = (count.echo, count.vote);
+        let quorum = (votes >= validity_threshold || fast) && !state.deliver;
+        let vote_quorum = timing.vote_quorum;
+
+        // a delivery certificate needs the Votes of the full validity threshold, including the thread's own
+        // Vote, which is not counted under `SelfCounting::Exclude` and may come back after the delivery
+        let own_weight = self.thresholds.get_weight(self.thread_id) - self.counting_thresholds.get_weight(self.thread_id);
+        let certified_threshold = validity_threshold + own_weight;
+        let voted = self.thresholds.get_combined_weight(&instance.votes.iter()
+            .filter(|vote| vote.get_content() == signal.get_content())
+            .filter_map(|vote| vote.get_origin())
+            .collect());
+        let voter_weight = signal.get_origin().map_or(0, |origin| self.thresholds.get_weight(origin));
+
+        // an instance delivered on the fast path, or before the thread's own Vote came back, is certified once
+        // its Vote quorum is reached
+        if delivered && *signal.get_signal() == SignalType::Vote && voted >= certified_threshold && voted.saturating_sub(voter_weight) < certified_threshold
+            && signal.get_signature().is_some() && protocol_information == "reliable" && let ObjectContent::Message(message) = signal.get_content() {
+            let certificate = DeliveryCertificate::assemble(self.thread_id, message.clone(), instance_number, round_number, &instance.votes);
+            self.delivery_watches.publish_certificate(certificate);
+        }
+
+        let own = protocol_information == "reliable" && sender == self.thread_id;
+        let delivery = match processor {
+            Some(processor) if quorum && !own => processor.deliver(instance, &signal),
+            _ => Delivery::Queue,
+        };
+        if quorum && own && !self.deliver_to_self {
+            instance.state.deliver = true;
+        } else if quorum && let Delivery::Pending = delivery {
+            // the processor delivers the instance on one of its next signals
+        } else if quorum && let Delivery::Delivered = delivery {
+            if let Some(vote_quorum) = vote_quorum && traced {
+                self.metrics.get_phase_latencies().record(&protocol_information, Phase::VoteQuorumToDeliver, vote_quorum.elapsed());
+            }
+            instance.state.deliver = true;
+        } else if quorum && let Err(reason) = self.capabilities.supports(signal.get_content()) {
+            log!(warn, { node = self.thread_id }, "id: {}, instance: {}, discarding {reason}", self.thread_id, signal.get_instance_number());
+            let mismatch = CapabilityMismatch::new(self.thread_id, signal.get_content().get_id(), reason, false);
+            let _ = self.event_channel.send(Event::CapabilityMismatch(mismatch));
+            instance.state.deliver = true;
+        } else if quorum {
+            let channel = match &self.report_channel {
+                Some(report_channel) if !QUEUED_PROTOCOLS.contains(&signal.get_content().get_protocol_information().as_str()) => {
+                    ChannelType::ReportChannels(report_channel.clone())
+                },
+                _ => ChannelType::MessageChannels(self.thread_channel.clone()),
+            };
+            if let ObjectContent::Message(message) = signal.get_content() && protocol_information == "reliable" {
+                if signal.get_signature().is_some() && !fast && voted >= certified_threshold {
+                    let certificate = DeliveryCertificate::assemble(self.thread_id, message.clone(), signal.get_instance_number(), round_number, &instance.votes);
+                    self.delivery_watches.publish_certificate(certificate);
+                }
+                self.delivery_watches.publish(signal.get_instance_number(), round_number, message.clone());
+            }
+            C::upon_vote(self.thread_id, channel, signal).await;
+            if let Some(vote_quorum) = vote_quorum && traced {
+                self.metrics.get_phase_latencies().record(&protocol_information, Phase::VoteQuorumToDeliver, vote_quorum.elapsed());
+            }
+            instance.state.deliver = true;
+        }
+
+        let deliver = instance.state.deliver;
+        if deliver && !delivered {
+            self.metrics.record_delivery_path(&protocol_information, fast);
+            self.pacemaker.stop(&instance_id);
+            if protocol_information == "reliable" && self.leader_schedule.accepts(sender, round_number, self.pacemaker.get_view(round_number)) {
+                self.pacemaker.stop(&leader_key(round_number));
+            }
+        }
+        if deliver {
+            self.retention.complete(instance_id.clone());
+        }
+        if deliver && !delivered && let Some(journal) = self.thread_signal_channel.get_journal() 
+            && let Err(reason) = journal.record_delivered(&instance_id) {
+            log!(warn, { node = self.thread_id }, "id: {}, journaling a delivery failed: {reason}", self.thread_id);
+        }
+
+        // instances of the other protocols are reported by their own handles, as part of their rounds
+        if protocol_information == "reliable" && traced {
+            let round_status = RoundStatus::new(protocol_information, Some(instance_id), round_number, deliver)
+                .with_stage("echoes", echoes, validity_threshold)
+                .with_stage("votes", votes, validity_threshold);
+            self.metrics.record_round_status(round_status);
+        }
+    }
+
+    // # Method Description:
+    // This method requests the next view of every round whose pacemaker timer expired, by sending a
+    // `ViewChange` to every thread.
+    async fn check_timers(&mut self) {
+        for (round_number, view) in self.pacemaker.expired() {
+            log!(info, { node = self.thread_id, round = %round_number }, "id: {}, round {round_number} timed out, requesting view {view}", self.thread_id);
+            self.thread_signal_channel.send_view_change(&ViewChange::new(self.thread_id, round_number, view)).await;
+        }
+    }
+
+    // # Method Description:
+    // This method counts the `ViewChange` of a thread. The thread joins a view change requested by an
+    // agreement quorum, and moves the round to the view once a validity quorum requested it: it publishes
+    // `Event::ViewChanged`, and processes again the queued Inputs of the new leader of the round, if any.
+    //
+    // # Parameters:
+    // * view_change - The view change.
+    async fn process_view_change(&mut self, view_change: ViewChange) {
+        let sender = view_change.get_sender();
+        if self.pacemaker.get_timeout().is_none() || sender >= self.thread_signal_channel.get_handle_channels().len() as u32 {
+            return
+        }
+        let round_number = view_change.get_round_number();
+        let view = view_change.get_view();
+        let weight = self.counting_thresholds.get_weight(sender);
+        let step = self.pacemaker.record(&view_change, weight, self.agreement_threshold, self.validity_threshold);
+        if step.join && self.pacemaker.request(round_number, view) {
+            self.thread_signal_channel.send_view_change(&ViewChange::new(self.thread_id, round_number, view)).await;
+        }
+        if !step.advance {
+            return
+        }
+
+        log!(info, { node = self.thread_id, round = %round_number }, "id: {}, round {round_number} moved to view {view}", self.thread_id);
+        let _ = self.event_channel.send(Event::ViewChanged(self.thread_id, round_number, view));
+        let queued = self.queued_inputs.remove(&round_number).unwrap_or_default();
+        let (accepted, queued): (Vec<Signal<T>>, Vec<Signal<T>>) = queued.into_iter()
+            .partition(|signal| self.leader_schedule.accepts(signal.get_content().get_id(), round_number, view));
+        if !queued.is_empty() {
+            self.queued_inputs.insert(round_number, queued);
+        }
+        for signal in accepted {
+            if let Some((validity_threshold, agreement_threshold)) = self.get_signal_thresholds(&signal.get_header()) {
+                self.process_accepted_signal(signal, None, validity_threshold, agreement_threshold).await;
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether the handle is due to exchange digests, for handles that are polled rather
+    // than woken up by a timer (see `HandleMode::Polling`), and records the exchange if it is.
+    //
+    // # Returns:
+    // * Whether the handle must send its digest now.
+    fn is_anti_entropy_due(&mut self) -> bool {
+        let Some(interval) = self.thread_signal_channel.get_disseminator().and_then(|disseminator| disseminator.get_anti_entropy_interval()) else {
+            return false
+        };
+        let now = Instant::now();
+        match self.last_anti_entropy {
+            Some(last_anti_entropy) if now.duration_since(last_anti_entropy) < interval => false,
+            _ => {
+                self.last_anti_entropy = Some(now);
+                true
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method sends the digest of the instances of the gossiped protocols the handle holds to a random
+    // peer (see `GossipDigest`), which answers with the signals the handle misses.
+    async fn exchange_digests(&mut self) {
+        let Some(peer) = self.thread_signal_channel.get_disseminator().and_then(|disseminator| disseminator.pick_peer()) else {
+            return
+        };
+        let mut instances = BTreeMap::new();
+        let mut delivered = vec![];
+        for (instance_id, instance) in &self.reliable_broadcast_monitor {
+            let key = instance_id.split_once("::").map_or(instance_id.as_str(), |(_, key)| key);
+            if !self.thread_signal_channel.is_gossiped(key.split("::").next().unwrap_or_default()) {
+                continue
+            }
+            match instance.state.deliver {
+                true => delivered.push(key.to_string()),
+                false => {
+                    let mut senders: Vec<(SignalType, u32, String)> = instance.senders.iter().cloned().collect();
+                    senders.sort_by_key(|(signal_type, origin, digest)| (*signal_type == SignalType::Vote, *origin, digest.clone()));
+                    instances.insert(key.to_string(), senders);
+                },
+            }
+        }
+        log!(trace, { node = self.thread_id, peer = peer }, "id: {}, sending the digest of {} instances to id: {peer}", self.thread_id, instances.len() + delivered.len());
+        let gossip_digest = GossipDigest { gossiper: self.thread_id, instances, delivered };
+        self.thread_signal_channel.send_gossip_digest(peer, &gossip_digest).await;
+    }
+
+    // # Method Description:
+    // This method answers the `GossipDigest` of a peer by re-sending it the Echo and Vote signals of the
+    // gossiped instances it did not deliver that its digest lacks.
+    //
+    // # Parameters:
+    // * gossip_digest - The digest.
+    async fn process_gossip_digest(&mut self, gossip_digest: GossipDigest) {
+        let gossiper = gossip_digest.get_gossiper();
+        if gossiper == self.thread_id || gossiper >= self.thread_signal_channel.get_handle_channels().len() as u32 {
+            return
+        }
+        let delivered: HashSet<&String> = gossip_digest.delivered.iter().collect();
+        let mut missing = vec![];
+        for (instance_id, instance) in &self.reliable_broadcast_monitor {
+            let key = instance_id.split_once("::").map_or(instance_id.as_str(), |(_, key)| key).to_string();
+            if delivered.contains(&key) {
+                continue
+            }
+            let held: HashSet<&(SignalType, u32, String)> = gossip_digest.instances.get(&key).into_iter().flatten().collect();
+            missing.extend(instance.received.iter()
+                .filter(|signal| signal.get_origin().is_some_and(|origin| !held.contains(&(*signal.get_signal(), origin, signal.get_content().get_quorum_digest()))))
+                .cloned());
+        }
+        if !missing.is_empty() {
+            log!(trace, { node = self.thread_id, peer = gossiper }, "id: {}, re-sending {} signals to id: {gossiper}", self.thread_id, missing.len());
+            self.thread_signal_channel.repair_signals(gossiper, missing).await;
+        }
+    }
+
+    // # Method Description:
+    // This method processes a `SignalBatch` received on the thread's `Signal` lane (see `SignalBatching`), by
+    // processing every frame it carries in order, as if it had been received on its own.
+    //
+    // # Parameters:
+    // * signal_batch - The batch.
+    async fn process_signal_batch(&mut self, signal_batch: SignalBatch) {
+        for received_signal in signal_batch.into_frames() {
+            Box::pin(self.process_signal(received_signal)).await;
+        }
+    }
+
+    // # Method Description:
+    // This method processes a `SyncRequest` received on the thread's `Signal` lane. The thread's own request,
+    // released by its outage gate on recovery, is forwarded to every other thread; the request of another
+    // thread is answered by re-sending it every Echo and Vote this thread sent, so that it catches up with the
+    // instances it missed while it was down.
+    //
+    // # Parameters:
+    // * sync_request - The request.
+    async fn process_sync_request(&mut self, sync_request: SyncRequest) {
+        let requester = sync_request.get_requester();
+        if requester == self.thread_id {
+            log!(info, { node = self.thread_id }, "id: {}, recovered, requesting catch-up...", self.thread_id);
+            self.thread_signal_channel.request_catch_up(sync_request).await;
+            return
+        }
+        for instance in self.reliable_broadcast_monitor.values() {
+            for signal in &instance.sent {
+                self.thread_signal_channel.send_signal_to(&[requester], signal.clone()).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T, C> PolledHandle for ReliableHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: ReliableCommunication<T> + 'static,
+{
+    async fn process_pending(&mut self) -> usize {
+        if self.stopped {
+            // commands sent to a stopped handle are dropped, so that their senders are not left waiting
+            while self.command_receiver.try_recv().is_ok() {}
+            return 0
+        }
+        let mut processed = self.replay_journal().await;
+        while let Ok(command) = self.command_receiver.try_recv() {
+            processed += 1;
+            if let Some(responder) = self.process_command(command) {
+                self.shutdown(responder).await;
+                return processed
+            }
+        }
+        while let Ok(received_signal) = self.receiver.try_recv() {
+            self.process_signal(received_signal).await;
+            processed += 1;
+        }
+        if self.is_anti_entropy_due() {
+            self.exchange_digests().await;
+        }
+        self.check_timers().await;
+        processed
+    }
+}
+
+// # Struct Description:
+// This struct manages a collection of channel transmitters used to broadcast serialized `Signal` messages.
+// It enables reliable and parallel signal transmission to multiple asynchronous threads.
 // # Fields:
 // * handle_transmitters - A vector of senders used to send serialized signal messages to each thread.
+// * id - The ID of the owning thread, stamped as the origin of every Echo and Vote it broadcasts, once known.
+// * disseminator - The disseminator deciding where the Echo and Vote signals of every protocol are sent,
+//   `None` when they are sent directly to every thread.
+// * metrics - The metrics of the owning thread, in which the signals sent per round are counted, if any.
+// * signer - The signer of the owning thread, which signs every broadcast signal, `None` if signals are unsigned.
+// * codec - The wire format of the signals sent.
+// * roster - The live membership of the cluster, whose current members are the only recipients of signals, if threads join and leave at runtime.
+// * journal - The journal of the owning thread, in which every broadcast signal is journaled before it is sent, if it journals.
+// * batcher - The batcher coalescing the signals sent to the same thread into a single frame, if signals are batched.
+// * restored - The snapshot the owning thread was restored from, whose monitors its handles take when they start, if any.
+// * validity - The external validity predicate of the payloads the owning thread echoes and votes for, if any
+//   (see `ReliableCommunication::set_validity_predicate`).
 #[derive(Clone)]
 pub struct SignalChannels<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    handle_transmitters: Vec<Sender<String>>,
+    handle_transmitters: Arc<Vec<Sender<Bytes>>>,
+    roster: Option<Roster>,
+    id: Option<u32>,
+    disseminator: Option<Arc<Disseminator>>,
+    metrics: Option<Metrics>,
+    signer: Option<Arc<SignalSigner>>,
+    codec: Codec,
+    journal: Option<Arc<SignalJournal<T>>>,
+    batcher: Option<Arc<SignalBatcher>>,
+    restored: Arc<Mutex<Option<ThreadSnapshot<T>>>>,
+    validity: Arc<Mutex<Option<ValidityPredicate<T>>>>,
     _marker: PhantomData<T>,
 }
 
-impl<T> SignalChannels<T> 
-where 
-    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
-{
+// A predicate the payloads of reliable broadcasts must satisfy to be echoed and voted for (see `ReliableCommunication::set_validity_predicate`).
+pub type ValidityPredicate<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+impl<T> SignalChannels<T> 
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Method Description:
+    // Asynchronously broadcasts a given Signal to all threads by encoding it in the codec of the channels
+    // and sending it through all registered transmitters. Echo and Vote signals are tagged with the owning
+    // thread as their origin, so that receivers count them once per thread. With a spanning tree or gossip,
+    // they are only sent to the owning thread, whose reliable handle relays them (see `relay_signal`).
+    // With a signer, the signal is signed before it is sent. With a journal, the signal is journaled first,
+    // and is not sent if it conflicts with a signal the thread sent before (see `SignalJournal::record_sent`).
+    // The signal is encoded once, and its frame is shared by every recipient.
+    // # Parameters:
+    // * signal - The Signal to broadcast to all receivers.
+    pub(crate) fn broadcast_signal(&self, signal: Signal<T>) -> impl Future<Output = ()> {
+        let mut send_fns= vec![];
+        let signal = self.stamp_signal(signal);
+        let handle_transmitters = self.get_handle_channels();
+        let refused = self.journal.as_ref().and_then(|journal| journal.record_sent(&signal).err());
+        match (&self.disseminator, signal.get_signal()) {
+            _ if let Some(reason) = &refused => {
+                let id = self.id.unwrap_or_default();
+                log!(warn, { node = id }, "id: {id}, refusing to send {reason}");
+            },
+            (Some(disseminator), SignalType::Echo | SignalType::Vote) if disseminator.get_dissemination(signal.get_content().get_protocol_information()) != Dissemination::Direct => {
+                send_fns.push(send_signal_frame(self.batcher.as_ref(), &handle_transmitters, disseminator.get_id(), signal.write_signal_frame(self.codec)));
+            },
+            _ => {
+                let frame = signal.write_signal_frame(self.codec);
+                for id in 0..handle_transmitters.len() {
+                    if !self.is_member(id as u32) {
+                        continue
+                    }
+                    send_fns.push(send_signal_frame(self.batcher.as_ref(), &handle_transmitters, id as u32, frame.clone()));
+                }; 
+            },
+        }
+        if refused.is_none() {
+            self.record_sent_signals(&signal, send_fns.len());
+        }
+        async move {
+            join_all(send_fns).await; 
+        }
+    }  
+
+    // # Method Description:
+    // Asynchronously sends a given Signal to some threads only, directly, whatever the dissemination mode.
+    // Like `broadcast_signal`, Echo and Vote signals are tagged with the owning thread as their origin, and
+    // signals are signed with a signer. Besides the Echo signals of consistent broadcast, which are sent to
+    // the sender only, only adversaries (see `ByzantineCommunicator`) send a signal to part of the cluster.
+    // # Parameters:
+    // * recipients - The IDs of the threads to send the signal to.
+    // * signal - The Signal to send.
+    pub(crate) fn send_signal_to(&self, recipients: &[u32], signal: Signal<T>) -> impl Future<Output = ()> {
+        let signal = self.stamp_signal(signal);
+        let handle_transmitters = self.get_handle_channels();
+        let frame = signal.write_signal_frame(self.codec);
+        let send_fns: Vec<_> = recipients.iter()
+            .map(|recipient| send_signal_frame(self.batcher.as_ref(), &handle_transmitters, *recipient, frame.clone()))
+            .collect();
+        self.record_sent_signals(&signal, send_fns.len());
+        async move {
+            join_all(send_fns).await; 
+        }
+    }
+
+    // # Method Description:
+    // Asynchronously sends the catch-up request of the owning thread to every other thread.
+    // # Parameters:
+    // * sync_request - The request of the owning thread.
+    pub(crate) fn request_catch_up(&self, sync_request: SyncRequest) -> impl Future<Output = ()> {
+        let frame = tag_frame(Lane::Signal, sync_request.write_frame(self.codec));
+        let send_fns: Vec<_> = self.get_handle_channels().iter().enumerate()
+            .filter(|(id, _)| *id as u32 != sync_request.get_requester() && self.is_member(*id as u32))
+            .map(|(_, handle_tx)| send_frame(handle_tx, frame.clone()))
+            .collect();
+        async move {
+            join_all(send_fns).await; 
+        }
+    }
+
+    // # Method Description:
+    // Tags an Echo or Vote signal with the owning thread as its origin, and signs the signal with a signer.
+    // # Parameters:
+    // * signal - The Signal to send.
+    // # Returns:
+    // * The Signal, ready to be sent.
+    fn stamp_signal(&self, signal: Signal<T>) -> Signal<T> {
+        let signal = match (self.id, signal.get_signal()) {
+            (Some(id), SignalType::Echo | SignalType::Vote) => signal.with_origin(id),
+            _ => signal,
+        };
+        match &self.signer {
+            Some(signer) => signal.signed_by(signer),
+            None => signal,
+        }
+    }
+
+    // # Method Description:
+    // Asynchronously relays a signal received along the spanning tree to every tree neighbour of the owning
+    // thread, or by gossip to a random subset of the threads, except the one it was received from. Does
+    // nothing when the signals of its protocol are sent directly. The frame the signal was received in is
+    // relayed with a new header, so that its content is not encoded again.
+    // # Parameters:
+    // * signal - The Signal to relay, carrying its origin.
+    // * received - The frame the signal was received in, if any.
+    pub(crate) fn relay_signal(&self, signal: &Signal<T>, received: Option<&SignalFrame>) -> impl Future<Output = ()> {
+        let mut send_fns= vec![];
+        if let Some(disseminator) = &self.disseminator {
+            let frame = match received {
+                Some(received) => received.frame.with_header(self.codec, &received.header.relayed_by(disseminator.get_id())),
+                None => signal.relayed_by(disseminator.get_id()).write_signal_frame(self.codec),
+            };
+            let handle_transmitters = self.get_handle_channels();
+            for target in disseminator.get_relay_targets(signal.get_content().get_protocol_information(), signal.get_relay()) {
+                send_fns.push(send_signal_frame(self.batcher.as_ref(), &handle_transmitters, target, frame.clone()));
+            }
+            self.record_sent_signals(signal, send_fns.len());
+        }
+        async move {
+            join_all(send_fns).await; 
+        }
+    }
+
+    // # Method Description:
+    // Asynchronously re-sends signals received by the owning thread to a thread missing them, as the answer
+    // to its `GossipDigest`. The signals keep their origin and signature, and are relayed by the owning thread.
+    // # Parameters:
+    // * recipient - The ID of the thread missing the signals.
+    // * signals - The signals.
+    pub(crate) fn repair_signals(&self, recipient: u32, signals: Vec<Signal<T>>) -> impl Future<Output = ()> {
+        let mut send_fns= vec![];
+        if let Some(disseminator) = &self.disseminator {
+            let handle_transmitters = self.get_handle_channels();
+            for signal in signals {
+                send_fns.push(send_signal_frame(self.batcher.as_ref(), &handle_transmitters, recipient, signal.relayed_by(disseminator.get_id()).write_signal_frame(self.codec)));
+                self.record_sent_signals(&signal, 1);
+            }
+        }
+        async move {
+            join_all(send_fns).await; 
+        }
+    }
+
+    // # Method Description:
+    // Asynchronously sends a view change of the owning thread to every member, itself included.
+    // # Parameters:
+    // * view_change - The view change.
+    pub(crate) fn send_view_change(&self, view_change: &ViewChange) -> impl Future<Output = ()> {
+        let frame = tag_frame(Lane::Signal, view_change.write_frame(self.codec));
+        let send_fns: Vec<_> = self.get_handle_channels().iter().enumerate()
+            .filter(|(id, _)| self.is_member(*id as u32))
+            .map(|(_, handle_tx)| send_frame(handle_tx, frame.clone()))
+            .collect();
+        async move {
+            join_all(send_fns).await; 
+        }
+    }
+
+    // # Method Description:
+    // Asynchronously sends the digest of the owning thread to a peer, in an anti-entropy exchange.
+    // # Parameters:
+    // * peer - The ID of the peer.
+    // * gossip_digest - The digest.
+    pub(crate) fn send_gossip_digest(&self, peer: u32, gossip_digest: &GossipDigest) -> impl Future<Output = ()> {
+        let handle_transmitters = self.get_handle_channels();
+        send_frame(&handle_transmitters[peer as usize], tag_frame(Lane::Signal, gossip_digest.write_frame(self.codec)))
+    }
+
+    // # Method Description:
+    // This method checks whether the Echo and Vote signals of a protocol are gossiped.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol.
+    pub fn is_gossiped(&self, protocol_information: &str) -> bool {
+        self.disseminator.as_ref().is_some_and(|disseminator| matches!(disseminator.get_dissemination(protocol_information), Dissemination::Gossip(_, _)))
+    }
+
+    pub fn get_disseminator(&self) -> Option<&Disseminator> {
+        self.disseminator.as_deref()
+    }
+
+    // # Method Description:
+    // This method provides the transmitters of the channels: those of every thread that ever joined the
+    // cluster with a roster, the transmitters the channels were built with otherwise.
+    pub fn get_handle_channels(&self) -> Arc<Vec<Sender<Bytes>>> {
+        match &self.roster {
+            Some(roster) => roster.get_transmitters(),
+            None => self.handle_transmitters.clone(),
+        }
+    }
+
+    fn is_member(&self, id: u32) -> bool {
+        self.roster.as_ref().is_none_or(|roster| roster.is_member(id))
+    }
+
+    pub fn get_roster(&self) -> Option<&Roster> {
+        self.roster.as_ref()
+    }
+
+    // # Method Description:
+    // This method provides the epoch in which the owning thread broadcasts, 0 without a roster.
+    pub fn get_epoch(&self) -> u32 {
+        self.roster.as_ref().map_or(0, |roster| roster.get_current_epoch().get_epoch())
+    }
+
+    pub fn new(handle_transmitters: Vec<Sender<Bytes>>) -> Self {
+        Self {
+            handle_transmitters: Arc::new(handle_transmitters),
+            roster: None,
+            id: None,
+            disseminator: None,
+            metrics: None,
+            signer: None,
+            codec: Codec::Json,
+            journal: None,
+            batcher: None,
+            restored: Arc::default(),
+            validity: Arc::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    // # Method Description:
+    // This method selects how Echo and Vote signals are disseminated, according to the configuration, and
+    // sets the owning thread as the origin of the Echo and Vote signals it broadcasts.
+    //
+    // # Parameters:
+    // * id - The ID of the thread owning the channels.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_dissemination(mut self, id: u32, config: &ClusterConfig) -> Self {
+        self.id = Some(id);
+        self.disseminator = Disseminator::from_config(id, config).map(Arc::new);
+        self
+    }
+
+    // # Method Description:
+    // This method makes the channels count every signal frame they send in the given metrics.
+    //
+    // # Parameters:
+    // * metrics - The metrics of the thread owning the channels.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_metrics(mut self, metrics: &Metrics) -> Self {
+        self.metrics = Some(metrics.clone());
+        self
+    }
+
+    // # Method Description:
+    // This method makes the channels sign every signal they broadcast, according to the configured
+    // `Authentication`.
+    //
+    // # Parameters:
+    // * id - The ID of the thread owning the channels.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_signing(mut self, id: u32, config: &ClusterConfig) -> Self {
+        self.signer = SignalSigner::from_config(id, config).map(Arc::new);
+        self
+    }
+
+    pub fn get_signer(&self) -> Option<&SignalSigner> {
+        self.signer.as_deref()
+    }
+
+    // # Method Description:
+    // This method makes the channels journal every signal they broadcast before sending it, when the
+    // configuration enables journaling (see `SignalJournal`).
+    //
+    // # Parameters:
+    // * id - The ID of the thread owning the channels.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The updated channels.
+    //
+    // # Panics:
+    // * If the journal of the thread cannot be opened.
+    pub fn with_journal(mut self, id: u32, config: &ClusterConfig) -> Self {
+        self.journal = match SignalJournal::from_config(id, config) {
+            Ok(journal) => journal.map(Arc::new),
+            Err(reason) => panic!("Error: the journal of id: {id} could not be opened: {reason}"),
+        };
+        self
+    }
+
+    pub fn get_journal(&self) -> Option<&Arc<SignalJournal<T>>> {
+        self.journal.as_ref()
+    }
+
+    // # Method Description:
+    // This method makes the channels batch the signals they send to the same thread, according to the
+    // configured `SignalBatching`, in their codec and counting the batches in their metrics.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_batching(mut self, config: &ClusterConfig) -> Self {
+        self.batcher = config.get_signal_batching().map(|batching| Arc::new(SignalBatcher::new(batching, self.codec, self.metrics.clone())));
+        self
+    }
+
+    // # Method Description:
+    // This method keeps the monitors of a snapshot until the handles of the owning thread start.
+    //
+    // # Parameters:
+    // * snapshot - The snapshot of the thread, without its queues.
+    pub(crate) fn restore(&self, snapshot: ThreadSnapshot<T>) {
+        *self.restored.lock().unwrap() = Some(snapshot);
+    }
+
+    pub(crate) fn take_restored_instances(&self) -> BTreeMap<String, ReliableInstanceMonitor<T>> {
+        self.restored.lock().unwrap().as_mut().map(ThreadSnapshot::take_instances).unwrap_or_default()
+    }
+
+    pub(crate) fn take_restored_rounds(&self) -> BTreeMap<Round, WitnessRoundMonitor<T>> {
+        self.restored.lock().unwrap().as_mut().map(ThreadSnapshot::take_rounds).unwrap_or_default()
+    }
+
+    pub(crate) fn set_validity_predicate(&self, predicate: ValidityPredicate<T>) {
+        *self.validity.lock().unwrap() = Some(predicate);
+    }
+
+    // # Method Description:
+    // This method checks the content of a signal against the external validity predicate of the owning
+    // thread. Only messages carry a payload the predicate applies to; any other content is valid.
+    //
+    // # Parameters:
+    // * content - The content of the signal.
+    //
+    // # Returns:
+    // * `true` if the content is valid, or if the thread has no validity predicate.
+    pub(crate) fn is_valid(&self, content: &ObjectContent<T>) -> bool {
+        match (content, self.validity.lock().unwrap().as_ref()) {
+            (ObjectContent::Message(message), Some(predicate)) => predicate(message.get_message()),
+            _ => true,
+        }
+    }
+
+    // # Method Description:
+    // This method makes the channels send to the current members of a roster, and stamp broadcasts with
+    // its current epoch, instead of sending to the transmitters they were built with.
+    //
+    // # Parameters:
+    // * roster - The live membership of the cluster.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_roster(mut self, roster: &Roster) -> Self {
+        self.roster = Some(roster.clone());
+        self
+    }
+
+    // # Method Description:
+    // This method sets the wire format of the signals sent over the channels.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
+
+    fn record_sent_signals(&self, signal: &Signal<T>, frames: usize) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_sent_signals(signal.get_content().get_protocol_information(), signal.get_round_number(), frames as u64);
+        }
+    }
+
+}
+
+// # Enum Description:
+// This enum represents the type of channel through which messages or reports are delivered
+// in the communication framework.
+//
+// # Variants:
+// * MessageChannels - A channel used for sending and receiving standard messages between threads.
+// * ReportChannels - A channel used for sending and receiving reports, from witness or aggregated witness communication protocols.
+pub enum ChannelType<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    MessageChannels(MessageChannels<T>), 
+    ReportChannels(ReportChannels<T>),
+}
+
+// # Struct Description:
+// This struct holds the watch channels on which a reliable handle publishes the messages it delivers,
+// one per instance and round, shared between the communicator and its handle. Channels are created by
+// whichever comes first, the delivery or the first watcher, and keep the latest delivered message. Under
+// `Authentication::Ed25519`, the handle also publishes the `DeliveryCertificate` of every delivered message.
+//
+// # Fields:
+// * watches - The sender of every watched or delivered instance, by instance and round number.
+// * certificates - The sender of the certificate of every watched or delivered instance, by sender of the
+//   message, instance, and round number.
+// * payloads - The sender of the payload of every watched or delivered dispersal (see
+//   `DispersalCommunication::reliable_broadcast_large`), by sender, instance, and round number.
+// * extensions - The handlers to which the handle hands the extension frames it delivers.
+pub struct DeliveryWatches<T> {
+    watches: Arc<Mutex<DeliveryWatchMap<T>>>,
+    certificates: Arc<Mutex<CertificateWatchMap<T>>>,
+    payloads: Arc<Mutex<PayloadWatchMap>>,
+    extensions: ExtensionRegistry,
+}
+
+type DeliveryWatchMap<T> = HashMap<(Instance, Round), watch::Sender<Option<Message<T>>>>;
+type CertificateWatchMap<T> = HashMap<(u32, Instance, Round), watch::Sender<Option<DeliveryCertificate<T>>>>;
+// a delivered dispersal holds `Some(None)` when its payload was not coded consistently
+type PayloadWatchMap = HashMap<(u32, Instance, Round), watch::Sender<Option<Option<Bytes>>>>;
+
+impl<T> DeliveryWatches<T> {
+    pub fn new() -> Self {
+        Self {
+            watches: Arc::new(Mutex::new(HashMap::new())),
+            certificates: Arc::new(Mutex::new(HashMap::new())),
+            payloads: Arc::new(Mutex::new(HashMap::new())),
+            extensions: ExtensionRegistry::new(),
+        }
+    }
+
+    pub fn get_extensions(&self) -> &ExtensionRegistry {
+        &self.extensions
+    }
+
+    // # Method Description:
+    // This method subscribes to the deliveries of an instance.
+    //
+    // # Parameters:
+    // * instance_number - The instance to watch.
+    // * round_number - The round to watch.
+    //
+    // # Returns:
+    // * A `watch::Receiver` holding the latest message delivered for the instance and round, if any.
+    pub fn subscribe(&self, instance_number: Instance, round_number: Round) -> watch::Receiver<Option<Message<T>>> {
+        let mut watches = self.watches.lock().unwrap();
+        watches.entry((instance_number, round_number))
+            .or_insert_with(|| watch::channel(None).0)
+            .subscribe()
+    }
+
+    // # Method Description:
+    // This method publishes a delivered message to the watchers of its instance, current and future.
+    //
+    // # Parameters:
+    // * instance_number - The instance the message was delivered in.
+    // * round_number - The round the message was delivered in.
+    // * message - The delivered message.
+    pub fn publish(&self, instance_number: Instance, round_number: Round, message: Message<T>) {
+        let mut watches = self.watches.lock().unwrap();
+        watches.entry((instance_number, round_number))
+            .or_insert_with(|| watch::channel(None).0)
+            .send_replace(Some(message));
+    }
+
     // # Method Description:
-    // Asynchronously broadcasts a given Signal to all threads by serializing it into a JSON string
-    // and sending it through all registered transmitters.
+    // This method subscribes to the certificate of the message a thread broadcast in an instance.
+    //
     // # Parameters:
-    // * signal - The Signal to broadcast to all receivers.
-    pub(crate) fn broadcast_signal(&self, signal: Signal<T>) -> impl Future<Output = ()> {
-        let mut send_fns= vec![];
-        for handle_tx in self.get_handle_channels() {
-            let new_signal = signal.clone(); 
-            send_fns.push(handle_tx.send(new_signal.write_json()));
-        }; 
-        async move {
-            join_all(send_fns).await; 
-        }
-    }  
+    // * id - The ID of the thread that broadcast the message.
+    // * instance_number - The instance to watch.
+    // * round_number - The round to watch.
+    //
+    // # Returns:
+    // * A `watch::Receiver` holding the certificate of the message, once it is delivered.
+    pub fn subscribe_certificate(&self, id: u32, instance_number: Instance, round_number: Round) -> watch::Receiver<Option<DeliveryCertificate<T>>> {
+        let mut certificates = self.certificates.lock().unwrap();
+        certificates.entry((id, instance_number, round_number))
+            .or_insert_with(|| watch::channel(None).0)
+            .subscribe()
+    }
+
+    // # Method Description:
+    // This method publishes the certificate of a delivered message to its watchers, current and future.
+    //
+    // # Parameters:
+    // * certificate - The certificate.
+    pub fn publish_certificate(&self, certificate: DeliveryCertificate<T>)
+    where
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        let key = (certificate.get_message().get_id(), certificate.get_instance_number(), certificate.get_round_number());
+        let mut certificates = self.certificates.lock().unwrap();
+        certificates.entry(key)
+            .or_insert_with(|| watch::channel(None).0)
+            .send_replace(Some(certificate));
+    }
+
+    // # Method Description:
+    // This method subscribes to the payload a thread dispersed in an instance.
+    //
+    // # Parameters:
+    // * id - The ID of the thread that dispersed the payload.
+    // * instance_number - The instance to watch.
+    // * round_number - The round to watch.
+    //
+    // # Returns:
+    // * A `watch::Receiver` holding the outcome of the dispersal once it is delivered: the payload, or
+    //   `None` if it was not coded consistently.
+    pub fn subscribe_payload(&self, id: u32, instance_number: Instance, round_number: Round) -> watch::Receiver<Option<Option<Bytes>>> {
+        let mut payloads = self.payloads.lock().unwrap();
+        payloads.entry((id, instance_number, round_number))
+            .or_insert_with(|| watch::channel(None).0)
+            .subscribe()
+    }
 
-    pub fn get_handle_channels(&self) -> &Vec<Sender<String>> {
-        &self.handle_transmitters
+    // # Method Description:
+    // This method publishes the outcome of a dispersal to its watchers, current and future.
+    //
+    // # Parameters:
+    // * id - The ID of the thread that dispersed the payload.
+    // * instance_number - The instance the payload was dispersed in.
+    // * round_number - The round the payload was dispersed in.
+    // * payload - The reconstructed payload, or `None` if it was not coded consistently.
+    pub fn publish_payload(&self, id: u32, instance_number: Instance, round_number: Round, payload: Option<Bytes>) {
+        let mut payloads = self.payloads.lock().unwrap();
+        payloads.entry((id, instance_number, round_number))
+            .or_insert_with(|| watch::channel(None).0)
+            .send_replace(Some(payload));
     }
+}
 
-    pub fn new(handle_transmitters: Vec<Sender<String>>) -> Self {
+impl<T> Clone for DeliveryWatches<T> {
+    fn clone(&self) -> Self {
         Self {
-            handle_transmitters,
-            _marker: PhantomData,
+            watches: self.watches.clone(),
+            certificates: self.certificates.clone(),
+            payloads: self.payloads.clone(),
+            extensions: self.extensions.clone(),
         }
     }
+}
 
+impl<T> Default for DeliveryWatches<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // # Enum Description:
-// This enum represents the type of channel through which messages or reports are delivered
-// in the communication framework.
+// This enum represents a command sent by a communicator to its reliable handle.
 //
 // # Variants:
-// * MessageChannels - A channel used for sending and receiving standard messages between threads.
-// * ReportChannels - A channel used for sending and receiving reports, from witness or aggregated witness communication protocols.
-pub enum ChannelType<T>
-where 
-    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
-{
-    MessageChannels(MessageChannels<T>), 
-    ReportChannels(ReportChannels<T>),
+// * Inspect - Requests a snapshot of every instance monitored by the handle, by instance ID, answered on
+//   the enclosed `oneshot::Sender`.
+// * Snapshot - Requests the monitor of every instance held by the handle, by instance ID, answered in JSON
+//   on the enclosed `oneshot::Sender`, since the commands of the handle do not depend on the payload type
+//   (see `ThreadSnapshot`).
+// * Shutdown - Requests the handle to flush the signals it received and stop, answered with its
+//   `ShutdownReport` on the enclosed `oneshot::Sender`.
+pub enum ReliableHandleCommand {
+    Inspect(InspectResponder<BTreeMap<String, ReliableInstanceSnapshot>>),
+    Snapshot(InspectResponder<String>),
+    Shutdown(ShutdownResponder),
 }
 
 // # Enum Description:
@@ -451,13 +2483,30 @@ where
 // * Input - The initial signal sent by the origin thread.
 // * Echo - The signal echoed by threads to confirm receipt.
 // * Vote - The final decision signal cast by threads.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SignalType {
     Input, 
     Echo,
     Vote,
 }
 
+// # Enum Description:
+// This enum represents how a reliable handle reacts to a second Input signal for an instance 
+// that already received one, i.e. the same creator, protocol, content type, instance, and round.
+// In every case the first Input wins and the duplicate does not re-enter the protocol.
+//
+// # Variants:
+// * FirstWins - Duplicates are silently ignored.
+// * Reject - Every duplicate is published as an `Event::DuplicateInput`.
+// * Evidence - Duplicates with differing content are published as `Event::Equivocation` evidence;
+//   identical duplicates are silently ignored.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DuplicateInputPolicy {
+    FirstWins,
+    Reject,
+    Evidence,
+}
+
 // # Enum Description:
 // This enum represents the content of a signal exchanged between threads in the communication framework.
 // It is used to encapsulate different types of payloads, including standard messages, individual reports, 
@@ -467,24 +2516,36 @@ pub enum SignalType {
 // * Message - A standard message sent between threads.
 // * Report - A collection of messages represented as a report generated by a thread.
 // * AggregatedReport - A collection of reports combined into a single aggregated report.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// * BarycentricReport - A report of the vector values collected in a barycentric agreement round.
+// * Commitment - The digest a thread commits to in a commit-reveal witness round.
+// * Fragment - The fragment of a payload dispersed with `reliable_broadcast_large`.
+// * Extension - A frame of a type introduced by a plugin (see `ExtensionFrame`), with the ID of the thread
+//   that broadcast it, the round it was broadcast in, and its encoded bytes. Its type ID stands for its
+//   protocol information.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ObjectContent<T>{
     Message(Message<T>), 
     Report(Report<T>),
     AggregatedReport(AggregatedReport<T>),
-    BarycentricReport(BarycentricReport<T>)
+    BarycentricReport(BarycentricReport<T>),
+    Commitment(Commitment),
+    Fragment(Fragment),
+    Extension { type_id: String, id: u32, round_number: Round, bytes: Vec<u8> },
 }
 
 impl<T> ObjectContent<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    pub fn get_round_number(&self) -> u32{
+    pub fn get_round_number(&self) -> Round {
         match self {
             ObjectContent::Message(message) => message.get_round_number(),
             ObjectContent::Report(report) => report.get_round_number(),
             ObjectContent::AggregatedReport(aggregated_report) => aggregated_report.get_round_number(),
             ObjectContent::BarycentricReport(barycentric_report) => barycentric_report.get_round_number(),
+            ObjectContent::Commitment(commitment) => commitment.get_round_number(),
+            ObjectContent::Fragment(fragment) => fragment.get_round_number(),
+            ObjectContent::Extension { round_number, .. } => *round_number,
         }
     }
 
@@ -494,8 +2555,109 @@ where
             ObjectContent::Report(report) => report.get_protocol_information(),
             ObjectContent::AggregatedReport(aggregated_report) => aggregated_report.get_protocol_information(),
             ObjectContent::BarycentricReport(barycentric_report) => barycentric_report.get_protocol_information(),
+            ObjectContent::Commitment(commitment) => commitment.get_protocol_information(),
+            ObjectContent::Fragment(fragment) => fragment.get_protocol_information(),
+            ObjectContent::Extension { type_id, .. } => type_id,
         }
     } 
+
+    pub fn get_id(&self) -> u32 {
+        match self {
+            ObjectContent::Message(message) => message.get_id(),
+            ObjectContent::Report(report) => report.get_id(),
+            ObjectContent::AggregatedReport(aggregated_report) => aggregated_report.get_id(),
+            ObjectContent::BarycentricReport(barycentric_report) => barycentric_report.get_id(),
+            ObjectContent::Commitment(commitment) => commitment.get_id(),
+            ObjectContent::Fragment(fragment) => fragment.get_id(),
+            ObjectContent::Extension { id, .. } => *id,
+        }
+    }
+
+    // # Method Description:
+    // This method provides the membership epoch of the content. Only messages are stamped with an epoch;
+    // reports and commitments are only exchanged by hubs whose membership never changes.
+    pub fn get_epoch(&self) -> u32 {
+        match self {
+            ObjectContent::Message(message) => message.get_epoch(),
+            _ => 0,
+        }
+    }
+
+    // # Method Description:
+    // This method provides the digest the Echo and Vote signals carrying the content are counted by, so that
+    // the signals of an instance only form a quorum with the signals carrying the same content. The fragments
+    // of a dispersal are counted by root, since every thread echoes its own fragment of the same payload.
+    pub fn get_quorum_digest(&self) -> String {
+        match self {
+            ObjectContent::Fragment(fragment) => fragment.get_root().to_string(),
+            content => content.content_digest(),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is the header of the frame of a signal (see `Frame`): what a reliable handle needs to know
+// to discard the signal (a non-member sender, a collected instance, a signal it already counted) without
+// decoding its content, e.g. a large report. The content is decoded once the signal is accepted, and a
+// signal whose header does not describe it is discarded as undecodable. Relaying a signal only replaces
+// the relay of its header, and reuses its encoded content.
+//
+// # Fields:
+// * signal - The type of the signal.
+// * instance_key - The key of the signal's instance (see `Signal::get_instance_key`).
+// * quorum_digest - The digest the signal is counted by (see `ObjectContent::get_quorum_digest`).
+// * content_id - The ID of the thread the content comes from.
+// * epoch - The epoch the content was broadcast in.
+// * origin - The thread that emitted the signal, on every Echo and Vote.
+// * relay - The thread the signal was last relayed by.
+// * signer - The thread that signed the signal.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SignalHeader {
+    signal: SignalType,
+    instance_key: String,
+    quorum_digest: String,
+    content_id: u32,
+    epoch: u32,
+    origin: Option<u32>,
+    relay: Option<u32>,
+    signer: Option<u32>,
+}
+
+impl SignalHeader {
+    pub(crate) fn get_claimed_ids(&self) -> Vec<u32> {
+        [Some(self.content_id), self.origin, self.relay, self.signer].into_iter().flatten().collect()
+    }
+
+    // # Method Description:
+    // This method provides the thread the signal is counted for: the sender of the content for an Input,
+    // the origin of an Echo or Vote.
+    pub(crate) fn get_sender(&self) -> Option<u32> {
+        match self.signal {
+            SignalType::Input => Some(self.content_id),
+            SignalType::Echo | SignalType::Vote => self.origin,
+        }
+    }
+
+    // # Method Description:
+    // This method provides the header of the signal relayed by a thread.
+    pub(crate) fn relayed_by(&self, relay: u32) -> Self {
+        Self {
+            relay: Some(relay),
+            ..self.clone()
+        }
+    }
+}
+
+// # Struct Description:
+// This struct is the frame a signal was received in, kept while the signal is processed so that relaying
+// the signal reuses its encoded content.
+//
+// # Fields:
+// * frame - The frame.
+// * header - The decoded header of the frame.
+pub(crate) struct SignalFrame {
+    frame: Frame,
+    header: SignalHeader,
 }
 
 // # Struct Description: 
@@ -508,12 +2670,27 @@ where
 // * content - The payload of the signal.
 // * instance_number - The identifier of the consensus instance.
 // * round_number - The round number associated with this signal.
+// * origin - The thread that emitted this signal, set on every Echo and Vote.
+// * relay - The thread this signal was last relayed by, when it is disseminated along a spanning tree.
+// * signer - The thread that signed this signal, under `Authentication::Ed25519`.
+// * signature - The hex-encoded signature of this signal by its signer, under `Authentication::Ed25519`.
+// * endorsements - The endorsements of the Echo quorum a consistent broadcast gathered, on the Vote of its sender.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Signal<T> {
     signal: SignalType,
     content: ObjectContent<T>, 
-    instance_number: u32,
-    round_number: u32
+    instance_number: Instance,
+    round_number: Round,
+    #[serde(default)]
+    origin: Option<u32>,
+    #[serde(default)]
+    relay: Option<u32>,
+    #[serde(default)]
+    signer: Option<u32>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    endorsements: Vec<Endorsement>,
 }
 
 impl<T> Signal<T>
@@ -528,21 +2705,267 @@ where
         &self.content
     }
 
-    pub fn get_instance_number(&self) -> u32 {
+    // # Method Description:
+    // This method takes the content out of a signal, so that delivering a large report does not clone it.
+    pub fn into_content(self) -> ObjectContent<T> {
+        self.content
+    }
+
+    pub fn get_instance_number(&self) -> Instance {
         self.instance_number
     }
 
-    pub fn get_round_number(&self) -> u32 {
+    pub fn get_round_number(&self) -> Round {
         self.round_number
     }
 
-    pub fn new(signal: SignalType, content: ObjectContent<T>, instance_number: u32, round_number: u32) -> Self {
+    pub fn get_origin(&self) -> Option<u32> {
+        self.origin
+    }
+
+    pub fn get_relay(&self) -> Option<u32> {
+        self.relay
+    }
+
+    pub fn get_signer(&self) -> Option<u32> {
+        self.signer
+    }
+
+    pub fn get_signature(&self) -> Option<&String> {
+        self.signature.as_ref()
+    }
+
+    pub fn get_endorsements(&self) -> &Vec<Endorsement> {
+        &self.endorsements
+    }
+
+    // # Method Description:
+    // This method provides every thread the signal names: the sender of its content, its origin, its
+    // relay, and its signer.
+    //
+    // # Returns:
+    // * The IDs of the threads, in that order, without the fields the signal does not set.
+    pub fn get_claimed_ids(&self) -> Vec<u32> {
+        [Some(self.content.get_id()), self.origin, self.relay, self.signer].into_iter().flatten().collect()
+    }
+
+    // # Method Description:
+    // This method provides the key of the signal's instance, which `ReliableCommunication::get_instance_id`
+    // prefixes with the ID of the thread counting it: the protocol, sender, and kind of the content, and the
+    // instance and round numbers of the signal.
+    //
+    // # Returns:
+    // * The key, in the format "<protocol>::<sender_id>::<content_type>::<instance_number>::<round_number>".
+    pub fn get_instance_key(&self) -> String {
+        let instance_number = self.instance_number;
+        let round_number = self.round_number;
+        match &self.content {
+            ObjectContent::Message(message) => {
+                return format!("{}::{}::{}::{}::{}", 
+                message.get_protocol_information(), message.get_id(), "message", instance_number, round_number);
+            },
+            ObjectContent::Report(report) => {
+                return format!("{}::{}::{}::{}::{}", 
+                report.get_protocol_information(), report.get_id(), "report", instance_number, round_number);
+            },
+            ObjectContent::AggregatedReport(aggregated_report) => {
+                // aggregated reports above level 2 are distinct instances from the level 2 report of the same thread
+                let level = match aggregated_report.get_level() {
+                    2 => String::new(),
+                    level => format!("level {level} "),
+                };
+                return format!("{}::{}::{}aggregated report::{}::{}", 
+                aggregated_report.get_protocol_information(), aggregated_report.get_id(), level, instance_number, round_number);
+            },
+            ObjectContent::BarycentricReport(barycentric_report) => {
+                // the reports of every dimension of a multi-dimensional round are distinct instances
+                let dimension = match barycentric_report.get_dimension() {
+                    Some(dimension) => format!("dimension {dimension} "),
+                    None => String::new(),
+                };
+                return format!("{}::{}::{}barycentric report::{}::{}", 
+                barycentric_report.get_protocol_information(), barycentric_report.get_id(), dimension, instance_number, round_number);
+            },
+            ObjectContent::Commitment(commitment) => {
+                format!("{}::{}::{}::{}::{}", 
+                commitment.get_protocol_information(), commitment.get_id(), "commitment", instance_number, round_number)
+            },
+            ObjectContent::Fragment(fragment) => {
+                // the fragments of a dispersal are counted by root, so that fragments coded from different payloads never form a quorum together
+                format!("{}::{}::fragment {}::{}::{}", 
+                fragment.get_protocol_information(), fragment.get_id(), fragment.get_root(), instance_number, round_number)
+            },
+            ObjectContent::Extension { type_id, id, .. } => {
+                format!("{}::{}::{}::{}::{}", 
+                type_id, id, "extension", instance_number, round_number)
+            },
+        }
+    }
+
+
+    // # Method Description:
+    // This method provides the header of the signal's frames, describing the signal without its content.
+    pub(crate) fn get_header(&self) -> SignalHeader {
+        SignalHeader {
+            signal: self.signal,
+            instance_key: self.get_instance_key(),
+            quorum_digest: self.content.get_quorum_digest(),
+            content_id: self.content.get_id(),
+            epoch: self.content.get_epoch(),
+            origin: self.origin,
+            relay: self.relay,
+            signer: self.signer,
+        }
+    }
+
+    // # Method Description:
+    // This method converts the signal into a frame whose header describes it (see `SignalHeader`).
+    //
+    // # Parameters:
+    // * codec - The wire format of the frame.
+    //
+    // # Returns:
+    // * The frame, untagged.
+    pub fn write_signal_frame(&self, codec: Codec) -> Bytes {
+        self.write_frame_with_header(codec, &self.get_header())
+    }
+
+    pub fn new(signal: SignalType, content: ObjectContent<T>, instance_number: Instance, round_number: Round) -> Self {
         Self {
             signal,
             content,
             instance_number,
-            round_number
+            round_number,
+            origin: None,
+            relay: None,
+            signer: None,
+            signature: None,
+            endorsements: vec![],
+        }
+    }
+
+    pub(crate) fn with_endorsements(mut self, endorsements: Vec<Endorsement>) -> Self {
+        self.endorsements = endorsements;
+        self
+    }
+
+    pub(crate) fn with_origin(mut self, origin: u32) -> Self {
+        self.origin = Some(origin);
+        self.relay = None;
+        self
+    }
+
+    pub(crate) fn relayed_by(&self, relay: u32) -> Self {
+        let mut signal = self.clone();
+        signal.relay = Some(relay);
+        signal
+    }
+
+    // # Method Description:
+    // This method creates the signal a thread answers this signal with (e.g. the Echo of an Input).
+    //
+    // # Parameters:
+    // * signal - The type of the answer.
+    //
+    // # Returns:
+    // * A new signal of the given type, carrying the content, instance number, and round number of this one.
+    pub(crate) fn answered_with(&self, signal: SignalType) -> Self {
+        Signal::new(signal, self.content.clone(), self.instance_number, self.round_number)
+    }
+
+    // # Method Description:
+    // This method returns the bytes a signature of the signal covers: the signal without its signature and
+    // its relay, which changes at every hop along a spanning tree.
+    fn get_signed_bytes(&self) -> Vec<u8> {
+        let mut signal = self.clone();
+        signal.relay = None;
+        signal.signature = None;
+        signal.write_json().into_bytes()
+    }
+
+    // # Method Description:
+    // This method attaches a signature computed elsewhere to the signal, e.g. to rebuild a signed Vote
+    // from a `DeliveryCertificate`.
+    //
+    // # Parameters:
+    // * signer - The ID of the thread that signed the signal.
+    // * signature - The hex-encoded signature.
+    //
+    // # Returns:
+    // * The signed signal.
+    pub(crate) fn with_signature(mut self, signer: u32, signature: String) -> Self {
+        self.signer = Some(signer);
+        self.signature = Some(signature);
+        self
+    }
+
+    // # Method Description:
+    // This method signs the signal on behalf of a thread.
+    //
+    // # Parameters:
+    // * signer - The signer of the thread sending the signal.
+    //
+    // # Returns:
+    // * The signed signal.
+    pub(crate) fn signed_by(mut self, signer: &SignalSigner) -> Self {
+        self.signer = Some(signer.get_id());
+        let signature = signer.sign(&self.get_signed_bytes());
+        self.signature = Some(signature.iter().map(|byte| format!("{byte:02x}")).collect());
+        self
+    }
+
+    // # Method Description:
+    // This method checks that the signal was signed by the thread it claims to come from: its signer, which
+    // must also be the sender of the content of an Input and the origin of a signal disseminated along a
+    // spanning tree.
+    //
+    // # Parameters:
+    // * signer - The signer of the receiving thread, holding the public key of every thread.
+    //
+    // # Returns:
+    // * `true` if the signal carries a valid signature by its claimed sender.
+    pub fn is_authentic(&self, signer: &SignalSigner) -> bool {
+        self.is_signed(|claimed, bytes, signature| signer.verify(claimed, bytes, signature))
+    }
+
+    // # Method Description:
+    // This method checks that the signal was signed by the thread it claims to come from, like
+    // `is_authentic`, with the public keys of the members of a cluster, e.g. to verify the signals of a
+    // `DeliveryCertificate` without being part of the cluster.
+    //
+    // # Parameters:
+    // * document - The membership document of the cluster, holding the public key of every member.
+    //
+    // # Returns:
+    // * `true` if the signal carries a valid signature by its claimed sender, who is a member.
+    pub fn is_authentic_under(&self, document: &MembershipDocument) -> bool {
+        self.is_signed(|claimed, bytes, signature| {
+            document.get_members().iter()
+                .find(|member| member.get_id() == claimed)
+                .is_some_and(|member| verify_with_key(member.get_public_key(), bytes, signature))
+        })
+    }
+
+    // # Method Description:
+    // This method checks the claims of the signal, and its signature with a verification function.
+    //
+    // # Parameters:
+    // * verify - Verifies the signature of bytes by a thread, given the ID of the thread, the bytes, and the signature.
+    //
+    // # Returns:
+    // * `true` if the signal carries a valid signature by its claimed sender.
+    fn is_signed(&self, verify: impl Fn(u32, &[u8], &[u8]) -> bool) -> bool {
+        let (Some(claimed), Some(signature)) = (self.signer, &self.signature) else {
+            return false
+        };
+        if (self.signal == SignalType::Input && self.content.get_id() != claimed) || self.origin.is_some_and(|origin| origin != claimed) {
+            return false
+        }
+        if signature.len() % 2 != 0 {
+            return false
         }
+        let signature: Option<Vec<u8>> = (0..signature.len()).step_by(2).map(|index| u8::from_str_radix(signature.get(index..index + 2)?, 16).ok()).collect();
+        signature.is_some_and(|signature| verify(claimed, &self.get_signed_bytes(), &signature))
     }
 }
 
@@ -551,37 +2974,235 @@ where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {}
 
+impl<T> FrameKind for Signal<T> {
+    const FRAME_KIND: u64 = frame_kinds::SIGNAL_FRAME;
+}
+
+impl<T> JsonConversion<ObjectContent<T>> for ObjectContent<T> 
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{}
+
+// # Function Description:
+// This function provides the key of the pacemaker timer started for a round by an Input rejected because its
+// sender did not lead the round, stopped once the leader's "reliable" instance of the round is delivered.
+//
+// # Parameters:
+// * round_number - The round.
+fn leader_key(round_number: Round) -> String {
+    format!("leader::{round_number}")
+}
+
+// # Function Description:
+// This function sends a signal frame to a thread, through the batcher of the sending channels if they batch
+// their signals.
+//
+// # Parameters:
+// * batcher - The batcher of the channels, if any.
+// * handle_transmitters - The transmitters of the threads.
+// * recipient - The ID of the recipient.
+// * frame - The encoded signal, untagged.
+fn send_signal_frame(batcher: Option<&Arc<SignalBatcher>>, handle_transmitters: &[Sender<Bytes>], recipient: u32, frame: Bytes) -> impl Future<Output = ()> + use<> {
+    let transmitter = &handle_transmitters[recipient as usize];
+    match batcher {
+        Some(batcher) => Either::Left(batcher.send(recipient, transmitter, frame)),
+        None => Either::Right(send_frame(transmitter, tag_frame(Lane::Signal, frame))),
+    }
+}
+
+// # Struct Description:
+// This struct is the frame a thread recovering from an outage (see `Fault::Outage`) sends on the `Signal`
+// lane to catch up with the instances it missed: every reliable handle receiving it re-sends the requester
+// the Echoes and Votes it sent, which the requester counts as usual, once per sender. It is released to the
+// recovering thread's own reliable handle by its outage gate, which then sends it to every other thread.
+//
+// # Fields:
+// * requester - The ID of the recovering thread.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SyncRequest {
+    requester: u32,
+}
+
+impl SyncRequest {
+    pub(crate) fn new(requester: u32) -> Self {
+        Self {
+            requester
+        }
+    }
+
+    pub(crate) fn get_requester(&self) -> u32 {
+        self.requester
+    }
+}
+
+impl JsonConversion<SyncRequest> for SyncRequest {}
+
+impl FrameKind for SyncRequest {
+    const FRAME_KIND: u64 = frame_kinds::SYNC_REQUEST_FRAME;
+}
+
+// # Struct Description:
+// This struct is the frame a reliable handle sends on the `Signal` lane, at every anti-entropy exchange of
+// a gossiped protocol (see `Dissemination::Gossip`), to a random peer: the digest of the instances of the
+// gossiped protocols it holds. The peer re-sends it the Echo and Vote signals it received and the digest
+// lacks, for every instance the sender did not deliver, including the instances it never heard of.
+//
+// # Fields:
+// * gossiper - The ID of the thread sending the digest.
+// * instances - The signal types, origins, and quorum digests of the Echo and Vote signals counted in every
+//   undelivered instance, by instance ID without the local thread ID.
+// * delivered - The IDs of the delivered instances, without the local thread ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct GossipDigest {
+    gossiper: u32,
+    instances: BTreeMap<String, Vec<(SignalType, u32, String)>>,
+    delivered: Vec<String>,
+}
+
+impl GossipDigest {
+    pub(crate) fn get_gossiper(&self) -> u32 {
+        self.gossiper
+    }
+}
+
+impl JsonConversion<GossipDigest> for GossipDigest {}
+
+impl FrameKind for GossipDigest {
+    const FRAME_KIND: u64 = frame_kinds::GOSSIP_DIGEST_FRAME;
+}
+
 // # Struct Description:
 // This struct tracks the progress of a single consensus instance in the reliable broadcast protocol.
 //
 // # Fields:
 // * state - A `ReliableInstanceState` struct representing whether echo, vote, or delivery has occurred.
-// * count - A `ReliableInstanceCount` struct counting the number of Echo and Vote signals received.
-pub struct ReliableInstanceMonitor {
+// * counts - The `ReliableInstanceCount` of every content the Echo and Vote signals received for this instance
+//   carried, by quorum digest (see `ObjectContent::get_quorum_digest`), so that signals carrying different
+//   contents never form a quorum together.
+// * input - The content of the first Input signal received for this instance, if any.
+// * timing - A `ReliableInstanceTiming` struct recording when each quorum of this instance was reached.
+// * senders - The signal types, origins, and quorum digests of the Echo and Vote signals already counted, so
+//   that every thread is counted once per signal type and content, however many copies of its signal are received.
+// * sent - The Echo and Vote signals the thread sent for this instance, re-sent to the threads catching up
+//   after an outage (see `SyncRequest`).
+// * votes - The signed Votes counted for this instance, from which its `DeliveryCertificate` is assembled.
+// * fragments - The fragments carried by the Echo and Vote signals of a dispersal, by fragment index.
+// * received - The Echo and Vote signals counted for this instance when its protocol is gossiped, re-sent
+//   to the peers missing them (see `GossipDigest`).
+// * echo_digest - The digest of the content of the first Echo signal counted for this instance, if any.
+// * echoes_diverged - Whether an Echo signal counted for this instance carried another content than the first
+//   one, which rules out its fast path (see `ClusterConfig::with_fast_path`).
+// * endorsements - The endorsements of the Echo signals gathered by the sender of a consistent broadcast.
+// * invalid - Whether the content of the instance failed the external validity predicate of the thread, which
+//   then neither echoes, votes for, nor delivers it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReliableInstanceMonitor<T> {
     pub state: ReliableInstanceState,
-    pub count: ReliableInstanceCount, 
+    pub counts: HashMap<String, ReliableInstanceCount>,
+    pub input: Option<ObjectContent<T>>,
+    #[serde(skip)]
+    pub timing: ReliableInstanceTiming,
+    pub senders: HashSet<(SignalType, u32, String)>,
+    pub sent: Vec<Signal<T>>,
+    pub votes: Vec<Signal<T>>,
+    pub fragments: BTreeMap<u32, Fragment>,
+    pub received: Vec<Signal<T>>,
+    #[serde(default)]
+    pub echo_digest: Option<u64>,
+    #[serde(default)]
+    pub echoes_diverged: bool,
+    #[serde(default)]
+    pub endorsements: Vec<Endorsement>,
+    #[serde(default)]
+    pub invalid: bool,
 }
 
-impl ReliableInstanceMonitor {
+impl<T> ReliableInstanceMonitor<T> {
     pub fn new() -> Self {
         let state = ReliableInstanceState::new();
-        let count = ReliableInstanceCount::new();
+        let counts = HashMap::new();
+        let input = None;
+        let timing = ReliableInstanceTiming::new();
+        let senders = HashSet::new();
+        let sent = vec![];
+        let votes = vec![];
+        let fragments = BTreeMap::new();
+        let received = vec![];
         Self {
             state,
-            count
+            counts,
+            input,
+            timing,
+            senders,
+            sent,
+            votes,
+            fragments,
+            received,
+            echo_digest: None,
+            echoes_diverged: false,
+            endorsements: vec![],
+            invalid: false,
+        }
+    }
+
+    // # Method Description:
+    // This method provides the counts of the content closest to its quorums.
+    //
+    // # Returns:
+    // * The largest Echo count and the largest Vote count over the contents of the instance.
+    pub fn get_count(&self) -> ReliableInstanceCount {
+        let mut count = ReliableInstanceCount::new();
+        for content_count in self.counts.values() {
+            count.echo = count.echo.max(content_count.echo);
+            count.vote = count.vote.max(content_count.vote);
+        }
+        count
+    }
+}
+
+// # Struct Description:
+// This struct records the instants at which a single consensus instance progressed, used to measure phase latencies.
+//
+// # Fields:
+// * started - The instant the first signal of this instance was received.
+// * echo_quorum - The instant the Echo quorum was reached, if it was.
+// * vote_quorum - The instant the Vote quorum was reached, if it was.
+#[derive(Debug, Clone)]
+pub struct ReliableInstanceTiming {
+    pub started: Instant,
+    pub echo_quorum: Option<Instant>,
+    pub vote_quorum: Option<Instant>,
+}
+
+impl ReliableInstanceTiming {
+    pub fn new() -> Self {
+        let started = Instant::now();
+        let echo_quorum = None;
+        let vote_quorum = None;
+        Self {
+            started,
+            echo_quorum,
+            vote_quorum
         }
     }
 }
 
+impl Default for ReliableInstanceTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // # Struct Description:
-// This struct counts the number of signals received in a single consensus instance.
+// This struct counts the number of signals received in a single consensus instance, one per sending thread.
 //
 // # Fields:
-// * echo - The number of Echo signals received for this instance.
-// * vote - The number of Vote signals received for this instance.
+// * echo - The number of threads (or their combined weight) whose Echo signal was received for this instance.
+// * vote - The number of threads (or their combined weight) whose Vote signal was received for this instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReliableInstanceCount {
-    pub echo: u32,
-    pub vote: u32,
+    pub echo: u64,
+    pub vote: u64,
 }
 
 impl ReliableInstanceCount {
@@ -603,6 +3224,7 @@ impl ReliableInstanceCount {
 // * echo - Boolean state of whether the Input signal has been echoed by this thread.
 // * vote - Boolean state of whether the Echo signals have triggered a vote by this thread.
 // * deliver - Boolean state of whether the message has been delivered by this thread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReliableInstanceState {
     pub echo: bool,
     pub vote: bool,
@@ -620,4 +3242,33 @@ impl ReliableInstanceState {
             deliver
         }
     }
-}
\ No newline at end of file
+}
+// # Function Description:
+// This function describes reliable broadcast as the reliable handle runs it (see `ReliableHandleLoop`), for
+// the tooling reading `ProtocolDescriptor`s.
+//
+// # Parameters:
+// * config - The configuration of the cluster, in which the quorums are evaluated.
+//
+// # Returns:
+// * The descriptor of reliable broadcast.
+pub fn protocol_descriptor(config: &ClusterConfig) -> ProtocolDescriptor {
+    ProtocolDescriptor::new(Protocol::Reliable, "reliable", "Bracha reliable broadcast: every correct thread delivers the same content for an instance, or none does", config)
+        .with_message_type(MessageTypeDescriptor::new("Input", "SignalType::Input", Lane::Signal, "the content of the instance, sent by its creator"))
+        .with_message_type(MessageTypeDescriptor::new("Echo", "SignalType::Echo", Lane::Signal, "the content a thread received in the Input of the instance"))
+        .with_message_type(MessageTypeDescriptor::new("Vote", "SignalType::Vote", Lane::Signal, "the content a thread is ready to deliver"))
+        .with_message_type(MessageTypeDescriptor::new("Delivery", "ObjectContent::Message", Lane::Basic, "the delivered content, queued for reliable_recv (or handed to the protocol running on top)"))
+        .with_phase(PhaseDescriptor::new("input", "the creator broadcasts the content of the instance")
+            .with_sends(&["Input"]), config)
+        .with_phase(PhaseDescriptor::new("echo", "every thread echoes the first Input it receives for the instance")
+            .with_counts("Input")
+            .with_sends(&["Echo"]), config)
+        .with_phase(PhaseDescriptor::new("echoes", "a thread votes once the echoes reach the validity quorum, and echoes once they reach the agreement quorum if it has not echoed yet")
+            .with_threshold("Echo", Quorum::Validity)
+            .with_join(Quorum::Agreement)
+            .with_sends(&["Vote"]), config)
+        .with_phase(PhaseDescriptor::new("votes", "a thread delivers once the votes reach the validity quorum, and votes once they reach the agreement quorum if it has not voted yet")
+            .with_threshold("Vote", Quorum::Validity)
+            .with_join(Quorum::Agreement)
+            .with_sends(&["Delivery"]), config)
+}
@@ -0,0 +1,397 @@
+use std::{fmt, fmt::Debug, fs, hash::Hash, path::Path, sync::{Arc, Mutex}};
+use bytes::Bytes;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+
+use crate::config::ClusterConfig;
+use crate::drive::{HandleMode, drive};
+use crate::json::JsonConversion;
+use crate::multiplex::{Lane, untag_frame};
+use crate::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use crate::round::{Instance, Round};
+
+// The number of steps preceding a divergence that are reported with it.
+const CONTEXT_STEPS: usize = 3;
+
+// # Struct Description:
+// This struct records the frames a thread receives during a live run, in the order its channel delivers
+// them, so that the run of the thread can be replayed later (see `ReplayRecording::record`).
+//
+// # Fields:
+// * inputs - The frames received so far, as tagged by their lane.
+#[derive(Debug, Clone)]
+pub struct ReplayTap {
+    inputs: Arc<Mutex<Vec<Bytes>>>,
+}
+
+impl ReplayTap {
+    // # Function Description:
+    // This function taps the receiver of a thread's channel, before it is handed to a hub.
+    //
+    // # Parameters:
+    // * receiver - The raw receiver of the thread's channel.
+    // * buffer_size - The capacity of the channel the frames are forwarded to.
+    //
+    // # Returns:
+    // * The receiver to hand to the hub in place of the raw one, and the tap recording its frames.
+    pub fn tap(mut receiver: Receiver<Bytes>, buffer_size: usize) -> (Receiver<Bytes>, Self) {
+        let (tx, rx) = mpsc::channel(buffer_size);
+        let inputs = Arc::new(Mutex::new(vec![]));
+        let recorded = inputs.clone();
+        tokio::spawn(async move {
+            while let Some(frame) = receiver.recv().await {
+                recorded.lock().unwrap().push(frame.clone());
+                if tx.send(frame).await.is_err() {
+                    return
+                }
+            }
+        });
+        (rx, Self { inputs })
+    }
+
+    pub fn get_inputs(&self) -> Vec<Bytes> {
+        self.inputs.lock().unwrap().clone()
+    }
+}
+
+// # Enum Description:
+// This enum represents an action a replayed thread emits while processing a frame.
+//
+// # Variants:
+// * Sent - A frame sent to a thread, with the ID of the thread and the tagged frame.
+// * Delivered - A frame delivered to the thread's own local queues (a basic or report lane frame sent to itself).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReplayAction {
+    Sent(u32, Bytes),
+    Delivered(Bytes),
+}
+
+impl fmt::Display for ReplayAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayAction::Sent(destination, frame) => write!(f, "sent to {destination}: {frame:?}"),
+            ReplayAction::Delivered(frame) => write!(f, "delivered: {frame:?}"),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct represents a step of a replay: a frame fed to the replayed thread, and the actions the
+// thread emitted until it had nothing left to process.
+//
+// # Fields:
+// * input - The tagged frame.
+// * actions - The actions, ordered by destination thread, and in emission order for every destination.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplayStep {
+    input: Bytes,
+    actions: Vec<ReplayAction>,
+}
+
+impl ReplayStep {
+    pub fn get_input(&self) -> &Bytes {
+        &self.input
+    }
+
+    pub fn get_actions(&self) -> &Vec<ReplayAction> {
+        &self.actions
+    }
+}
+
+// # Struct Description:
+// This struct reports the first step at which a replay diverged from its recording.
+//
+// # Fields:
+// * id - The ID of the replayed thread.
+// * step - The index of the step, from 0.
+// * input - The frame fed at the step.
+// * expected - The actions recorded at the step.
+// * actual - The actions emitted by the replay at the step.
+// * context - The steps preceding the step, at most `CONTEXT_STEPS`, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Divergence {
+    id: u32,
+    step: usize,
+    input: Bytes,
+    expected: Vec<ReplayAction>,
+    actual: Vec<ReplayAction>,
+    context: Vec<ReplayStep>,
+}
+
+impl Divergence {
+    pub fn get_step(&self) -> usize {
+        self.step
+    }
+
+    pub fn get_input(&self) -> &Bytes {
+        &self.input
+    }
+
+    pub fn get_expected(&self) -> &Vec<ReplayAction> {
+        &self.expected
+    }
+
+    pub fn get_actual(&self) -> &Vec<ReplayAction> {
+        &self.actual
+    }
+
+    pub fn get_context(&self) -> &Vec<ReplayStep> {
+        &self.context
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "thread {} diverged at step {}", self.id, self.step)?;
+        for (offset, step) in self.context.iter().enumerate() {
+            writeln!(f, "  step {}: received {:?}", self.step - self.context.len() + offset, step.input)?;
+            for action in &step.actions {
+                writeln!(f, "    {action}")?;
+            }
+        }
+        writeln!(f, "  step {}: received {:?}", self.step, self.input)?;
+        for (label, actions) in [("expected", &self.expected), ("actual", &self.actual)] {
+            writeln!(f, "    {label}:")?;
+            if actions.is_empty() {
+                writeln!(f, "      no action")?;
+            }
+            for action in actions {
+                writeln!(f, "      {action}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// # Struct Description:
+// This struct replays the reliable handle of a single thread in lock step: frames are fed to the thread
+// one at a time, the thread is driven (`HandleMode::Polling`) until it has nothing left to process, and
+// every frame it sent in the meantime is captured instead of reaching its destination. The replayed
+// thread therefore only ever sees the fed frames, making its actions a function of them.
+//
+// # Fields:
+// * id - The ID of the replayed thread.
+// * communicator - The communicator of the replayed thread.
+// * input - The transmitter of the replayed thread's channel.
+// * captures - The receivers of the frames sent by the thread, indexed by destination thread ID.
+pub struct ReliableReplay<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    id: u32,
+    communicator: ReliableCommunicator<T>,
+    input: Sender<Bytes>,
+    captures: Vec<Receiver<Bytes>>,
+}
+
+impl<T> ReliableReplay<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+{
+    // # Function Description:
+    // This function sets up the replay of a thread, whose reliable handle is initialized in `HandleMode::Polling`.
+    //
+    // # Parameters:
+    // * config - The configuration of the replayed cluster.
+    // * id - The ID of the replayed thread.
+    //
+    // # Returns:
+    // * The replay, or a description of why the configuration cannot be replayed in lock step: emulated
+    //   links, middleware layers, and outages forward frames from tasks, whose frames could not be attributed
+    //   to the step that sent them.
+    pub fn new(config: ClusterConfig, id: u32) -> Result<Self, String> {
+        if id >= config.get_thread_count() {
+            return Err(format!("thread {id} is not part of a cluster of {} threads", config.get_thread_count()))
+        }
+        if config.get_network_emulation().is_some() || !config.get_layers().is_empty() || config.get_fault_script().get_outage(id).is_some() {
+            return Err(String::from("emulated links, middleware layers, and outages cannot be replayed in lock step"))
+        }
+        let config = config.with_handle_mode(HandleMode::Polling);
+        let (transmitters, captures): (Vec<_>, Vec<_>) = (0..config.get_thread_count()).map(|_| mpsc::channel(config.get_buffer_size())).unzip();
+        let (inputs, receivers): (Vec<_>, Vec<_>) = (0..config.get_thread_count()).map(|_| mpsc::channel(config.get_buffer_size())).unzip();
+        let input = inputs[id as usize].clone();
+        let mut reliable_hub = ReliableHub::with_config(transmitters, receivers, config);
+        // the hub hands its communicators out in ID order, and the other threads are never driven
+        let mut communicator = reliable_hub.create_reliable_communicator();
+        for _ in 0..id {
+            communicator = reliable_hub.create_reliable_communicator();
+        }
+        communicator.initialize_reliable_handle();
+        Ok(Self {
+            id,
+            communicator,
+            input,
+            captures
+        })
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    // # Method Description:
+    // This method feeds a frame to the replayed thread, and drives it until it has nothing left to process.
+    //
+    // # Parameters:
+    // * frame - The tagged frame.
+    //
+    // # Returns:
+    // * The step, with the actions the thread emitted.
+    pub async fn step(&mut self, frame: Bytes) -> ReplayStep {
+        let _ = self.input.send(frame.clone()).await;
+        drive(&mut self.communicator).await;
+        ReplayStep {
+            input: frame,
+            actions: self.capture()
+        }
+    }
+
+    // # Method Description:
+    // This method makes the replayed thread reliably broadcast a message, and drives it until it has
+    // nothing left to process. The Input signals it sends, including the one to itself, are captured like
+    // any other frame, so they must be fed back to the threads they are sent to.
+    //
+    // # Parameters:
+    // * message - The message to broadcast.
+    // * instance_number - The instance number of the broadcast.
+    // * round_number - The round number of the broadcast.
+    //
+    // # Returns:
+    // * The actions the thread emitted.
+    pub async fn broadcast(&mut self, message: T, instance_number: Instance, round_number: Round) -> Vec<ReplayAction> {
+        self.communicator.reliable_broadcast(message, instance_number, round_number).await;
+        drive(&mut self.communicator).await;
+        self.capture()
+    }
+
+    // # Method Description:
+    // This method takes the frames the replayed thread sent since the last step.
+    //
+    // # Returns:
+    // * The actions, ordered by destination thread, and in emission order for every destination.
+    fn capture(&mut self) -> Vec<ReplayAction> {
+        let mut actions = vec![];
+        for (destination, capture) in self.captures.iter_mut().enumerate() {
+            while let Ok(sent) = capture.try_recv() {
+                let delivered = destination as u32 == self.id && untag_frame(&sent).is_some_and(|(lane, _)| lane != Lane::Signal);
+                actions.push(if delivered { ReplayAction::Delivered(sent) } else { ReplayAction::Sent(destination as u32, sent) });
+            }
+        }
+        actions
+    }
+}
+
+// # Struct Description:
+// This struct is the recorded log of a thread: the frames it received during a live run, with the actions
+// its reliable handle emitted for each of them when the log was recorded. Replaying the log on a modified
+// version of the code (see `ReplayRecording::verify`) checks that the handle still behaves the same, step by
+// step, and reports the first step at which it does not.
+//
+// # Fields:
+// * config - The configuration of the recorded cluster.
+// * id - The ID of the recorded thread.
+// * steps - The recorded steps, in reception order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplayRecording {
+    config: ClusterConfig,
+    id: u32,
+    steps: Vec<ReplayStep>,
+}
+
+impl ReplayRecording {
+    // # Function Description:
+    // This function records the log of a thread, by replaying the frames it received with the current code.
+    //
+    // # Parameters:
+    // * config - The configuration of the recorded cluster.
+    // * id - The ID of the recorded thread.
+    // * inputs - The frames the thread received (see `ReplayTap`).
+    //
+    // # Returns:
+    // * The recording, or a description of why the configuration cannot be replayed in lock step.
+    pub async fn record<T>(config: ClusterConfig, id: u32, inputs: Vec<Bytes>) -> Result<Self, String>
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    {
+        let mut replay = ReliableReplay::<T>::new(config.clone(), id)?;
+        let mut steps = vec![];
+        for input in inputs {
+            steps.push(replay.step(input).await);
+        }
+        Ok(Self {
+            config,
+            id,
+            steps
+        })
+    }
+
+    // # Method Description:
+    // This method replays the recording with the current code, in lock step with the recorded steps.
+    //
+    // # Parameters:
+    // * config - The configuration to replay with, e.g. the recorded one (see `get_config`), or a modified
+    //   one to evaluate the effect of a change.
+    //
+    // # Returns:
+    // * `None` if every step emitted the recorded actions, the first `Divergence` otherwise, or a description
+    //   of why the configuration cannot be replayed in lock step.
+    pub async fn verify<T>(&self, config: ClusterConfig) -> Result<Option<Divergence>, String>
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    {
+        let mut replay = ReliableReplay::<T>::new(config, self.id)?;
+        for (index, recorded) in self.steps.iter().enumerate() {
+            let step = replay.step(recorded.input.clone()).await;
+            if step.actions != recorded.actions {
+                return Ok(Some(Divergence {
+                    id: self.id,
+                    step: index,
+                    input: step.input,
+                    expected: recorded.actions.clone(),
+                    actual: step.actions,
+                    context: self.steps[index.saturating_sub(CONTEXT_STEPS)..index].to_vec(),
+                }))
+            }
+        }
+        Ok(None)
+    }
+
+    // # Function Description:
+    // This function loads a recording from its file.
+    //
+    // # Parameters:
+    // * path - The path of the recording file.
+    //
+    // # Returns:
+    // * The recording, or a description of why the file could not be read.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|error| format!("could not read {}: {error}", path.display()))?;
+        Self::read_json(&data).map_err(|error| format!("could not decode {}: {error}", path.display()))
+    }
+
+    // # Method Description:
+    // This method writes the recording to a file.
+    //
+    // # Parameters:
+    // * path - The path of the recording file.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the file could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.write_json()).map_err(|error| format!("could not write {}: {error}", path.display()))
+    }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_steps(&self) -> &Vec<ReplayStep> {
+        &self.steps
+    }
+}
+
+impl JsonConversion<ReplayRecording> for ReplayRecording {}
@@ -0,0 +1,137 @@
+use std::{collections::{HashSet, VecDeque}, hash::Hash, time::{Duration, Instant}};
+use serde::{Serialize, Deserialize};
+
+// The number of collected instances or rounds a handle remembers, so that the late signals and reports
+// of a collected instance are ignored instead of opening it again.
+const TOMBSTONE_LIMIT: usize = 4096;
+
+// # Enum Description:
+// This enum represents how long the handles of a thread keep the state of the instances and rounds they
+// completed (delivered). Open instances and rounds are never collected, whatever the policy. The local
+// queues of the thread (`BasicQueues`) follow the same policy for the collections they keep to answer
+// repeated collects, and for the messages they remember to store every message once.
+//
+// # Variants:
+// * KeepAll - Completed instances and rounds are kept for the lifetime of the handle, so that they can
+//   always be inspected and re-sent to recovering threads, at the cost of memory growing with every instance.
+// * GracePeriod - Completed instances and rounds are dropped once the given duration has passed since
+//   their completion, leaving time for the late signals of slower threads to be absorbed.
+// * Capacity - At most the given number of completed instances or rounds are kept per handle; the ones
+//   completed first are dropped first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum RetentionPolicy {
+    #[default]
+    KeepAll,
+    GracePeriod(Duration),
+    Capacity(usize),
+}
+
+// # Struct Description:
+// This struct records the instances or rounds a handle completed, and decides which ones to drop
+// following the configured `RetentionPolicy`. Handles call `complete` when an instance or round is
+// delivered, and `collect` before processing every frame, removing the returned keys from their monitors.
+// The keys collected last are remembered, so that `is_collected` lets the handle ignore late frames of a
+// collected instance instead of opening it again.
+//
+// # Fields:
+// * policy - The retention policy of the handle.
+// * completed - The completed instances or rounds that were not collected yet, with their completion instant,
+//   in completion order.
+// * completed_keys - The keys of `completed`.
+// * tombstones - The keys collected last, oldest first, at most `TOMBSTONE_LIMIT`.
+// * tombstone_keys - The keys of `tombstones`.
+// * collected - The number of instances or rounds collected so far.
+#[derive(Debug, Clone)]
+pub struct RetentionTracker<K> {
+    policy: RetentionPolicy,
+    completed: VecDeque<(K, Instant)>,
+    completed_keys: HashSet<K>,
+    tombstones: VecDeque<K>,
+    tombstone_keys: HashSet<K>,
+    collected: u64,
+}
+
+impl<K> RetentionTracker<K>
+where
+    K: Clone + Eq + Hash,
+{
+    pub fn new(policy: RetentionPolicy) -> Self {
+        Self {
+            policy,
+            completed: VecDeque::new(),
+            completed_keys: HashSet::new(),
+            tombstones: VecDeque::new(),
+            tombstone_keys: HashSet::new(),
+            collected: 0,
+        }
+    }
+
+    pub fn get_policy(&self) -> RetentionPolicy {
+        self.policy
+    }
+
+    pub fn get_collected(&self) -> u64 {
+        self.collected
+    }
+
+    // # Method Description:
+    // This method records that an instance or round completed. Completing it again has no effect.
+    //
+    // # Parameters:
+    // * key - The instance ID or round number.
+    pub fn complete(&mut self, key: K) {
+        if self.policy == RetentionPolicy::KeepAll || self.completed_keys.contains(&key) {
+            return
+        }
+        self.completed_keys.insert(key.clone());
+        self.completed.push_back((key, Instant::now()));
+    }
+
+    // # Method Description:
+    // This method checks whether an instance or round was collected, so that its late frames are ignored.
+    //
+    // # Parameters:
+    // * key - The instance ID or round number.
+    //
+    // # Returns:
+    // * `true` if the key is among the keys collected last.
+    pub fn is_collected(&self, key: &K) -> bool {
+        self.tombstone_keys.contains(key)
+    }
+
+    // # Method Description:
+    // This method selects the completed instances or rounds to drop now, and remembers them as collected.
+    //
+    // # Returns:
+    // * The keys to remove from the monitors of the handle, in completion order.
+    pub fn collect(&mut self) -> Vec<K> {
+        let mut collected = vec![];
+        loop {
+            let expired = match (self.policy, self.completed.front()) {
+                (RetentionPolicy::GracePeriod(grace_period), Some((_, completed_at))) => completed_at.elapsed() >= grace_period,
+                (RetentionPolicy::Capacity(capacity), Some(_)) => self.completed.len() > capacity,
+                _ => false,
+            };
+            if !expired {
+                break
+            }
+            let (key, _) = self.completed.pop_front().unwrap();
+            self.completed_keys.remove(&key);
+            self.bury(key.clone());
+            collected.push(key);
+        }
+        self.collected += collected.len() as u64;
+        collected
+    }
+
+    // # Method Description:
+    // This method remembers a collected key, forgetting the oldest one beyond `TOMBSTONE_LIMIT`.
+    fn bury(&mut self, key: K) {
+        if self.tombstones.len() == TOMBSTONE_LIMIT
+            && let Some(oldest) = self.tombstones.pop_front() {
+            self.tombstone_keys.remove(&oldest);
+        }
+        self.tombstone_keys.insert(key.clone());
+        self.tombstones.push_back(key);
+    }
+}
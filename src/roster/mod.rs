@@ -0,0 +1,188 @@
+use std::{collections::BTreeSet, sync::{Arc, RwLock}};
+use bytes::Bytes;
+use tokio::sync::mpsc::Sender;
+
+use crate::config::{ClusterConfig, Thresholds};
+
+// # Struct Description:
+// This struct describes the membership of a cluster during one epoch: the threads taking part in its
+// quorums, and the thresholds derived from their number.
+//
+// # Fields:
+// * epoch - The epoch number, 0 for the membership the hub was built with.
+// * members - The IDs of the threads taking part in the epoch.
+// * thresholds - The thresholds of the epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Epoch {
+    epoch: u32,
+    members: BTreeSet<u32>,
+    thresholds: Thresholds,
+}
+
+impl Epoch {
+    pub fn get_epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    pub fn get_members(&self) -> &BTreeSet<u32> {
+        &self.members
+    }
+
+    pub fn is_member(&self, id: u32) -> bool {
+        self.members.contains(&id)
+    }
+
+    pub fn get_thresholds(&self) -> &Thresholds {
+        &self.thresholds
+    }
+}
+
+// # Struct Description:
+// This struct holds the state shared by every handle of a roster.
+//
+// # Fields:
+// * transmitters - The transmitter of every thread that ever joined the cluster, indexed by thread ID.
+// * epochs - Every epoch of the cluster, indexed by epoch number.
+#[derive(Debug)]
+struct RosterState {
+    transmitters: Arc<Vec<Sender<Bytes>>>,
+    epochs: Vec<Arc<Epoch>>,
+}
+
+// # Struct Description:
+// This struct is the live membership of a cluster whose threads join and leave at runtime (see
+// `ReliableHub::join` and `ReliableHub::leave`). Every communicator of the hub shares it: channels send to
+// the current members through it, and every change starts a new epoch. Messages are stamped with the
+// epoch they are broadcast in, and reliable handles count every instance with the members and thresholds
+// of its epoch, so that instances in flight when the membership changes are not corrupted by the change.
+// Threads never reuse the ID of a thread that left, so IDs keep indexing the transmitters.
+//
+// # Fields:
+// * state - The transmitters and epochs, shared by every handle of the roster.
+#[derive(Debug, Clone)]
+pub struct Roster {
+    state: Arc<RwLock<RosterState>>,
+}
+
+impl Roster {
+    // # Function Description:
+    // This function creates the roster of a cluster, whose first epoch admits every thread of the configuration.
+    //
+    // # Parameters:
+    // * transmitters - The transmitter of every thread, indexed by thread ID.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The roster.
+    pub fn new(transmitters: Vec<Sender<Bytes>>, config: &ClusterConfig) -> Self {
+        let epoch = Epoch {
+            epoch: 0,
+            members: (0..config.get_thread_count()).collect(),
+            thresholds: config.get_thresholds().clone(),
+        };
+        Self {
+            state: Arc::new(RwLock::new(RosterState {
+                transmitters: Arc::new(transmitters),
+                epochs: vec![Arc::new(epoch)],
+            })),
+        }
+    }
+
+    pub fn get_transmitters(&self) -> Arc<Vec<Sender<Bytes>>> {
+        self.state.read().unwrap().transmitters.clone()
+    }
+
+    pub fn get_current_epoch(&self) -> Arc<Epoch> {
+        self.state.read().unwrap().epochs.last().unwrap().clone()
+    }
+
+    pub fn get_epoch(&self, epoch: u32) -> Option<Arc<Epoch>> {
+        self.state.read().unwrap().epochs.get(epoch as usize).cloned()
+    }
+
+    pub fn is_member(&self, id: u32) -> bool {
+        self.get_current_epoch().is_member(id)
+    }
+
+    // # Method Description:
+    // This method checks whether a thread was ever admitted, i.e. whether frames may come from it.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    //
+    // # Returns:
+    // * `true` if the thread joined the cluster at some epoch, even if it left since.
+    pub fn is_known(&self, id: u32) -> bool {
+        (id as usize) < self.state.read().unwrap().transmitters.len()
+    }
+
+    // # Method Description:
+    // This method admits a new thread, starting a new epoch whose thresholds are derived from the new
+    // number of members.
+    //
+    // # Parameters:
+    // * transmitter - The transmitter of the new thread.
+    // * config - The configuration of the cluster, whose quorum configuration derives the thresholds.
+    //
+    // # Returns:
+    // * The new epoch, whose last member is the new thread, or a description of why its thresholds cannot be derived.
+    pub(crate) fn join(&self, transmitter: Sender<Bytes>, config: &ClusterConfig) -> Result<Arc<Epoch>, String> {
+        let mut state = self.state.write().unwrap();
+        let id = state.transmitters.len() as u32;
+        let mut members = state.epochs.last().unwrap().members.clone();
+        members.insert(id);
+        let epoch = Self::next_epoch(&state, members, config)?;
+        let mut transmitters = state.transmitters.as_ref().clone();
+        transmitters.push(transmitter);
+        state.transmitters = Arc::new(transmitters);
+        state.epochs.push(epoch.clone());
+        Ok(epoch)
+    }
+
+    // # Method Description:
+    // This method removes a thread from the members, starting a new epoch whose thresholds are derived
+    // from the new number of members. The thread no longer receives frames.
+    //
+    // # Parameters:
+    // * id - The ID of the leaving thread.
+    // * config - The configuration of the cluster, whose quorum configuration derives the thresholds.
+    //
+    // # Returns:
+    // * The new epoch, or a description of why the thread cannot leave.
+    pub(crate) fn leave(&self, id: u32, config: &ClusterConfig) -> Result<Arc<Epoch>, String> {
+        let mut state = self.state.write().unwrap();
+        let mut members = state.epochs.last().unwrap().members.clone();
+        if !members.remove(&id) {
+            return Err(format!("thread {id} is not a member"))
+        }
+        if members.is_empty() {
+            return Err(format!("thread {id} is the last member"))
+        }
+        let epoch = Self::next_epoch(&state, members, config)?;
+        state.epochs.push(epoch.clone());
+        Ok(epoch)
+    }
+
+    fn next_epoch(state: &RosterState, members: BTreeSet<u32>, config: &ClusterConfig) -> Result<Arc<Epoch>, String> {
+        let thresholds = config.get_quorum_config().thresholds(members.len() as u32)?;
+        Ok(Arc::new(Epoch {
+            epoch: state.epochs.len() as u32,
+            members,
+            thresholds,
+        }))
+    }
+}
+
+// # Function Description:
+// This function sends a frame over a transmitter. The returned future owns a clone of the transmitter, so
+// that it outlives the snapshot of the roster's transmitters it was taken from.
+//
+// # Parameters:
+// * transmitter - The transmitter of the recipient.
+// * frame - The tagged frame to send.
+pub(crate) fn send_frame(transmitter: &Sender<Bytes>, frame: Bytes) -> impl Future<Output = ()> + use<> {
+    let transmitter = transmitter.clone();
+    async move {
+        let _ = transmitter.send(frame).await;
+    }
+}
@@ -0,0 +1,67 @@
+use std::{fmt, ops::Add};
+use serde::{Serialize, Deserialize};
+
+// # Struct Description:
+// This struct is the number of a protocol round. Rounds and instances are distinct types, so that they
+// cannot be swapped in the many signatures taking both. Rounds are encoded as bare numbers, so frames
+// keep their wire format.
+//
+// # Fields:
+// * 0 - The round number.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Round(pub u32);
+
+impl Round {
+    // # Method Description:
+    // This method provides the round following this one.
+    pub fn next(self) -> Self {
+        Round(self.0 + 1)
+    }
+}
+
+// Rounds are offset by a number of rounds, e.g. to walk a window of rounds.
+impl Add<u32> for Round {
+    type Output = Round;
+
+    fn add(self, rounds: u32) -> Round {
+        Round(self.0 + rounds)
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// # Struct Description:
+// This struct is the number of a reliable broadcast instance, encoded as a bare number like `Round`.
+//
+// # Fields:
+// * 0 - The instance number.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Instance(pub u32);
+
+impl Instance {
+    // # Method Description:
+    // This method provides the instance following this one.
+    pub fn next(self) -> Self {
+        Instance(self.0 + 1)
+    }
+}
+
+impl Add<u32> for Instance {
+    type Output = Instance;
+
+    fn add(self, instances: u32) -> Instance {
+        Instance(self.0 + instances)
+    }
+}
+
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,564 @@
+use std::{collections::BTreeSet, fs, path::Path, time::Duration};
+use serde::{Serialize, Deserialize};
+
+use crate::config::ClusterConfig;
+use crate::emulation::{NetworkEmulation, RegionTopology};
+use crate::json::JsonConversion;
+use crate::metrics::MetricsReport;
+use crate::round::{Instance, Round};
+use crate::witness::WitnessCommunication;
+
+// How long an action waits for a delivery when the scenario does not say otherwise.
+const DEFAULT_TIMEOUT_MILLIS: u64 = 10000;
+
+// # Struct Description:
+// This struct is a scenario file: the script every node of a witness run follows, in place of the scripts
+// hard-coded in the simulator. Scenario files are TOML, e.g. `[[nodes]]` tables with an `id`, `delays =
+// [{to = 3, millis = 200}]`, and `[[nodes.actions]]` tables such as `action = "broadcast"`, `round = 0`,
+// `message = "hello"`; files ending in `.json` are read as JSON, like the other artifacts of the crate.
+// Nodes without a script take part in the protocols without broadcasting anything. A scenario written for
+// a given cluster size declares it, e.g. `thread_count = 4`, and is refused by clusters of any other size,
+// since its scripts and expected values only hold for that size. A scenario may also
+// declare the deadlines of its rounds, e.g. `deadlines = [{protocol = "witness", round = 0, millis = 500}]`,
+// against which the delivery times of every node are checked (see `DeadlineReport`).
+//
+// # Fields:
+// * thread_count - The number of threads of the cluster the scenario is written for, if it depends on it.
+// * timeout_millis - How long every action waits for a delivery before the node fails, in milliseconds.
+// * nodes - The script of every scripted node.
+// * deadlines - The deadlines of the rounds of the scenario.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Scenario {
+    #[serde(default)]
+    thread_count: Option<u32>,
+    #[serde(default = "default_timeout_millis")]
+    timeout_millis: u64,
+    nodes: Vec<NodeScript>,
+    #[serde(default)]
+    deadlines: Vec<RoundDeadline>,
+}
+
+fn default_timeout_millis() -> u64 {
+    DEFAULT_TIMEOUT_MILLIS
+}
+
+impl Scenario {
+    pub fn new(nodes: Vec<NodeScript>) -> Self {
+        Self {
+            thread_count: None,
+            timeout_millis: DEFAULT_TIMEOUT_MILLIS,
+            nodes,
+            deadlines: vec![]
+        }
+    }
+
+    pub fn with_thread_count(mut self, thread_count: u32) -> Self {
+        self.thread_count = Some(thread_count);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_millis = timeout.as_millis() as u64;
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: RoundDeadline) -> Self {
+        self.deadlines.push(deadline);
+        self
+    }
+
+    pub fn get_thread_count(&self) -> Option<u32> {
+        self.thread_count
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_millis)
+    }
+
+    pub fn get_nodes(&self) -> &Vec<NodeScript> {
+        &self.nodes
+    }
+
+    pub fn get_deadlines(&self) -> &Vec<RoundDeadline> {
+        &self.deadlines
+    }
+
+    // # Method Description:
+    // This method provides the script of a node.
+    //
+    // # Parameters:
+    // * id - The ID of the node.
+    //
+    // # Returns:
+    // * The script, or an empty script if the node is not scripted.
+    pub fn get_script(&self, id: u32) -> NodeScript {
+        match self.nodes.iter().find(|script| script.id == id) {
+            Some(script) => script.clone(),
+            None => NodeScript::new(id, vec![]),
+        }
+    }
+
+    // # Function Description:
+    // This function reads a scenario file, as JSON if its extension is `.json` and as TOML otherwise.
+    //
+    // # Parameters:
+    // * path - The path of the scenario file.
+    //
+    // # Returns:
+    // * The scenario, or a description of why the file could not be read.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|error| format!("could not read {}: {error}", path.display()))?;
+        let scenario = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::read_json(&data).map_err(|error| error.to_string()),
+            _ => Self::read_toml(&data),
+        };
+        scenario.map_err(|error| format!("could not decode {}: {error}", path.display()))
+    }
+
+    // # Function Description:
+    // This function decodes a scenario written in TOML.
+    //
+    // # Parameters:
+    // * data - The TOML document.
+    //
+    // # Returns:
+    // * The scenario, or a description of why the document could not be decoded.
+    pub fn read_toml(data: &str) -> Result<Self, String> {
+        toml::from_str(data).map_err(|error| error.to_string())
+    }
+
+    // # Method Description:
+    // This method checks that the scenario can be run by a cluster, and configures the cluster for it: the
+    // delays of the scripts are emulated by a `NetworkEmulation` placing every node in its own region.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The configuration of the cluster running the scenario, or a description of why the scenario cannot
+    //   run: a cluster of another size than the one the scenario declares, a script of a node outside the
+    //   cluster, two scripts for the same node, a delay to a node outside the cluster, or delays in a cluster
+    //   that already emulates its network.
+    pub fn configure(&self, config: ClusterConfig) -> Result<ClusterConfig, String> {
+        let thread_count = config.get_thread_count();
+        if let Some(declared) = self.thread_count.filter(|declared| *declared != thread_count) {
+            return Err(format!("the scenario is written for a cluster of {declared} threads, not {thread_count}"))
+        }
+        let mut scripted = BTreeSet::new();
+        for script in &self.nodes {
+            if script.id >= thread_count {
+                return Err(format!("node {} is not part of a cluster of {thread_count} threads", script.id))
+            }
+            if !scripted.insert(script.id) {
+                return Err(format!("node {} has more than one script", script.id))
+            }
+            if let Some(delay) = script.delays.iter().find(|delay| delay.to >= thread_count) {
+                return Err(format!("node {} delays its messages to node {}, outside of the cluster", script.id, delay.to))
+            }
+        }
+        if self.nodes.iter().all(|script| script.delays.is_empty()) {
+            return Ok(config)
+        }
+        if config.get_network_emulation().is_some() {
+            return Err(String::from("the delays of a scenario cannot be combined with another network emulation"))
+        }
+        let mut latencies = vec![vec![Duration::ZERO; thread_count as usize]; thread_count as usize];
+        for script in &self.nodes {
+            for delay in &script.delays {
+                latencies[script.id as usize][delay.to as usize] = Duration::from_millis(delay.millis);
+            }
+        }
+        let regions = RegionTopology::new((0..thread_count).collect(), latencies);
+        Ok(config.with_network_emulation(NetworkEmulation::new().with_regions(regions)))
+    }
+}
+
+impl JsonConversion<Scenario> for Scenario {}
+
+// # Struct Description:
+// This struct is the script of a single node of a scenario.
+//
+// # Fields:
+// * id - The ID of the node.
+// * delays - The delays of the messages the node sends to other nodes, for the whole run.
+// * actions - The actions the node performs, in order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeScript {
+    id: u32,
+    #[serde(default)]
+    delays: Vec<LinkDelay>,
+    actions: Vec<ScenarioAction>,
+}
+
+impl NodeScript {
+    pub fn new(id: u32, actions: Vec<ScenarioAction>) -> Self {
+        Self {
+            id,
+            delays: vec![],
+            actions
+        }
+    }
+
+    pub fn with_delay(mut self, to: u32, delay: Duration) -> Self {
+        self.delays.push(LinkDelay { to, millis: delay.as_millis() as u64 });
+        self
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_delays(&self) -> &Vec<LinkDelay> {
+        &self.delays
+    }
+
+    pub fn get_actions(&self) -> &Vec<ScenarioAction> {
+        &self.actions
+    }
+
+    // # Method Description:
+    // This method runs the script on the communicator of its node, whose handles must be running. The
+    // script stops at the first action that fails, or at a `Crash` action, after which the caller must
+    // terminate the handles of the node.
+    //
+    // # Parameters:
+    // * communicator - The communicator of the node.
+    // * timeout - How long every action waits for a delivery.
+    //
+    // # Returns:
+    // * The outcome of the script.
+    pub async fn run<C>(&self, communicator: &mut C, timeout: Duration) -> ScriptOutcome
+    where
+        C: WitnessCommunication<String> + Send,
+    {
+        let mut outcome = ScriptOutcome::new(self.id);
+        for action in &self.actions {
+            let result = match action {
+                ScenarioAction::Broadcast { round, message } => {
+                    communicator.witness_broadcast(message.clone(), Round(*round)).await;
+                    Ok(())
+                },
+                ScenarioAction::Collect { round, min_values } => {
+                    match communicator.witness_collect_with_timeout(Round(*round), timeout).await {
+                        Ok(values) if values.len() >= min_values.unwrap_or(0) => Ok(()),
+                        Ok(values) => Err(format!("collected {} values in round {round}, expected at least {}", values.len(), min_values.unwrap_or(0))),
+                        Err(error) => Err(format!("could not collect round {round}: {error:?}")),
+                    }
+                },
+                ScenarioAction::ReliableBroadcast { instance, round, message } => {
+                    communicator.reliable_broadcast(message.clone(), Instance(*instance), Round(*round)).await;
+                    Ok(())
+                },
+                ScenarioAction::ReliableReceive { from, instance, round, expect } => {
+                    match communicator.reliable_recv_with_timeout(Some(*from), Instance(*instance), Round(*round), timeout).await {
+                        Ok(message) if expect.as_ref().is_none_or(|expected| message.get_message() == expected) => Ok(()),
+                        Ok(message) => Err(format!("received {:?} from node {from} in instance {instance} of round {round}, expected {:?}", message.get_message(), expect)),
+                        Err(error) => Err(format!("could not receive instance {instance} of round {round} from node {from}: {error:?}")),
+                    }
+                },
+                ScenarioAction::Sleep { millis } => {
+                    tokio::time::sleep(Duration::from_millis(*millis)).await;
+                    Ok(())
+                },
+                ScenarioAction::Crash => {
+                    outcome.crashed = true;
+                    return outcome
+                },
+            };
+            match result {
+                Ok(()) => outcome.performed += 1,
+                Err(failure) => {
+                    outcome.failure = Some(failure);
+                    return outcome
+                },
+            }
+        }
+        outcome
+    }
+}
+
+// # Struct Description:
+// This struct delays the messages a node sends to another node.
+//
+// # Fields:
+// * to - The ID of the node the messages are sent to.
+// * millis - The delay, in milliseconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LinkDelay {
+    to: u32,
+    millis: u64,
+}
+
+impl LinkDelay {
+    pub fn get_to(&self) -> u32 {
+        self.to
+    }
+
+    pub fn get_delay(&self) -> Duration {
+        Duration::from_millis(self.millis)
+    }
+}
+
+// # Enum Description:
+// This enum represents an action of a node script, written `{"action": "<name>", ...}` in scenario files.
+//
+// # Variants:
+// * Broadcast - Broadcasts a value in a witness round.
+// * Collect - Collects a witness round, failing if fewer than `min_values` values are delivered.
+// * ReliableBroadcast - Reliably broadcasts a message in an instance of a round.
+// * ReliableReceive - Receives the message reliably broadcast by a node in an instance of a round, failing
+//   if it differs from `expect`.
+// * Sleep - Waits for the given number of milliseconds.
+// * Crash - Crashes the node: its handles are terminated, and it performs no further action.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScenarioAction {
+    Broadcast { round: u32, message: String },
+    Collect { round: u32, #[serde(default)] min_values: Option<usize> },
+    ReliableBroadcast { instance: u32, round: u32, message: String },
+    ReliableReceive { from: u32, instance: u32, round: u32, #[serde(default)] expect: Option<String> },
+    Sleep { millis: u64 },
+    Crash,
+}
+
+// # Struct Description:
+// This struct is the outcome of a node script.
+//
+// # Fields:
+// * id - The ID of the node.
+// * performed - The number of actions performed.
+// * crashed - Whether the node crashed.
+// * failure - A description of the action that failed, if any.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScriptOutcome {
+    id: u32,
+    performed: usize,
+    crashed: bool,
+    failure: Option<String>,
+}
+
+impl ScriptOutcome {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            performed: 0,
+            crashed: false,
+            failure: None
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_performed(&self) -> usize {
+        self.performed
+    }
+
+    pub fn is_crashed(&self) -> bool {
+        self.crashed
+    }
+
+    pub fn get_failure(&self) -> Option<&String> {
+        self.failure.as_ref()
+    }
+}
+
+// # Struct Description:
+// This struct is the deadline of a round of a scenario: every node is expected to deliver the round within
+// its time budget, measured from the creation of the node's communicator, like the delivery times of the
+// round statuses.
+//
+// # Fields:
+// * protocol - The protocol information of the round, e.g. `witness` or `reliable`.
+// * round - The round number.
+// * millis - The time budget of the round, in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoundDeadline {
+    #[serde(default = "default_deadline_protocol")]
+    protocol: String,
+    round: u32,
+    millis: u64,
+}
+
+fn default_deadline_protocol() -> String {
+    String::from("witness")
+}
+
+impl RoundDeadline {
+    pub fn new(protocol: &str, round: Round, budget: Duration) -> Self {
+        Self {
+            protocol: protocol.to_string(),
+            round: round.0,
+            millis: budget.as_millis() as u64
+        }
+    }
+
+    pub fn get_protocol(&self) -> &String {
+        &self.protocol
+    }
+
+    pub fn get_round(&self) -> Round {
+        Round(self.round)
+    }
+
+    pub fn get_budget(&self) -> Duration {
+        Duration::from_millis(self.millis)
+    }
+}
+
+// # Struct Description:
+// This struct is the outcome of a round deadline on a single node.
+//
+// # Fields:
+// * id - The ID of the node.
+// * protocol - The protocol information of the round.
+// * round - The round number.
+// * budget - The time budget of the round.
+// * delivered_at - When the node delivered the round (the last of its instances, for reliable broadcast),
+//   `None` if it did not deliver every instance it took part in, or took part in none.
+// * met - Whether the node delivered the round within its budget.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeadlineOutcome {
+    id: u32,
+    protocol: String,
+    round: Round,
+    budget: Duration,
+    delivered_at: Option<Duration>,
+    met: bool,
+}
+
+impl DeadlineOutcome {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_protocol(&self) -> &String {
+        &self.protocol
+    }
+
+    pub fn get_round(&self) -> Round {
+        self.round
+    }
+
+    pub fn get_budget(&self) -> Duration {
+        self.budget
+    }
+
+    pub fn get_delivered_at(&self) -> Option<Duration> {
+        self.delivered_at
+    }
+
+    pub fn get_met(&self) -> bool {
+        self.met
+    }
+}
+
+// # Struct Description:
+// This struct is the deadline-miss report of a run: the outcome of every round deadline of a scenario on
+// every node, so that protocol variants can be evaluated against latency targets rather than averages.
+//
+// # Fields:
+// * outcomes - The outcome of every deadline on every node, by deadline then node ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeadlineReport {
+    outcomes: Vec<DeadlineOutcome>,
+}
+
+impl DeadlineReport {
+    // # Function Description:
+    // This function checks the deadlines of a scenario against the round statuses of the metric reports of
+    // a run. A node misses a deadline if it delivered the round late, left one of its instances undelivered,
+    // or never took part in the round (e.g. because it crashed before).
+    //
+    // # Parameters:
+    // * deadlines - The deadlines of the rounds.
+    // * reports - The metric report of every node of the run.
+    //
+    // # Returns:
+    // * The deadline-miss report.
+    pub fn from_reports(deadlines: &[RoundDeadline], reports: &[MetricsReport]) -> Self {
+        let mut outcomes = vec![];
+        for deadline in deadlines {
+            let mut reports: Vec<&MetricsReport> = reports.iter().collect();
+            reports.sort_by_key(|report| report.get_id());
+            for report in reports {
+                let statuses: Vec<_> = report.get_round_statuses().iter()
+                    .filter(|status| status.get_protocol_information() == &deadline.protocol && status.get_round_number() == deadline.get_round())
+                    .collect();
+                let delivered_at = match statuses.iter().all(|status| status.get_delivered()) {
+                    true => statuses.iter().map(|status| status.get_delivered_at()).max().flatten(),
+                    false => None,
+                };
+                outcomes.push(DeadlineOutcome {
+                    id: report.get_id(),
+                    protocol: deadline.protocol.clone(),
+                    round: deadline.get_round(),
+                    budget: deadline.get_budget(),
+                    delivered_at,
+                    met: delivered_at.is_some_and(|delivered_at| delivered_at <= deadline.get_budget()),
+                });
+            }
+        }
+        Self { outcomes }
+    }
+
+    pub fn get_outcomes(&self) -> &Vec<DeadlineOutcome> {
+        &self.outcomes
+    }
+
+    // # Method Description:
+    // This method provides the outcomes of the deadlines that were missed.
+    //
+    // # Returns:
+    // * The missed outcomes, by deadline then node ID.
+    pub fn get_misses(&self) -> Vec<&DeadlineOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.met).collect()
+    }
+
+    // # Method Description:
+    // This method provides the fraction of the deadlines missed, over every node.
+    //
+    // # Returns:
+    // * The miss rate, between 0 and 1, 0 without deadlines.
+    pub fn get_miss_rate(&self) -> f64 {
+        match self.outcomes.len() {
+            0 => 0.0,
+            total => self.get_misses().len() as f64 / total as f64,
+        }
+    }
+
+    // # Method Description:
+    // This method renders the report as text, one line per deadline, naming the nodes that missed it.
+    //
+    // # Returns:
+    // * The text of the report.
+    pub fn render(&self) -> String {
+        let mut lines = vec![];
+        let mut deadlines: Vec<(&String, Round, Duration)> = vec![];
+        for outcome in &self.outcomes {
+            if !deadlines.contains(&(&outcome.protocol, outcome.round, outcome.budget)) {
+                deadlines.push((&outcome.protocol, outcome.round, outcome.budget));
+            }
+        }
+        for (protocol, round, budget) in deadlines {
+            let outcomes: Vec<&DeadlineOutcome> = self.outcomes.iter()
+                .filter(|outcome| &outcome.protocol == protocol && outcome.round == round && outcome.budget == budget)
+                .collect();
+            let misses: Vec<String> = outcomes.iter().filter(|outcome| !outcome.met).map(|outcome| match outcome.delivered_at {
+                Some(delivered_at) => format!("{} ({delivered_at:?})", outcome.id),
+                None => format!("{} (undelivered)", outcome.id),
+            }).collect();
+            let slowest = match outcomes.iter().filter_map(|outcome| outcome.delivered_at).max() {
+                Some(slowest) => format!("slowest {slowest:?}"),
+                None => String::from("undelivered"),
+            };
+            lines.push(format!("{protocol} round {}, budget {budget:?}: {}/{} nodes met, {slowest}{}", round.0,
+                outcomes.len() - misses.len(), outcomes.len(), if misses.is_empty() { String::new() } else { format!(", missed by {}", misses.join(", ")) }));
+        }
+        lines.push(format!("deadline miss rate: {:.1}%", self.get_miss_rate() * 100.0));
+        lines.join("\n")
+    }
+}
+
+impl JsonConversion<DeadlineReport> for DeadlineReport {}
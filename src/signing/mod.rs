@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::config::ClusterConfig;
+use crate::identity::{IdentityStore, KeyPair};
+
+// # Enum Description:
+// This enum represents whether the threads of a cluster authenticate the signals they exchange.
+// Without authentication, signals are accepted at face value, so any thread can claim to be any other.
+//
+// # Variants:
+// * Unsigned - Signals are neither signed nor verified.
+// * Ed25519 - Every signal is signed by the thread broadcasting it with its Ed25519 key, and reliable
+//   handles reject the signals whose signature does not match their claimed sender.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum Authentication {
+    #[default]
+    Unsigned,
+    Ed25519,
+}
+
+// # Struct Description:
+// This struct signs the signals broadcast by a thread and verifies the signals it receives, with the
+// Ed25519 keys of its `IdentityStore`.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * signing_key - The secret key of the thread.
+// * verifying_keys - The public key of every known thread, by thread ID, including the thread itself.
+#[derive(Debug, Clone)]
+pub struct SignalSigner {
+    id: u32,
+    signing_key: SigningKey,
+    verifying_keys: HashMap<u32, VerifyingKey>,
+}
+
+impl SignalSigner {
+    // # Function Description:
+    // This function creates the signer of a thread from its identity, whose keys must be Ed25519 keys.
+    //
+    // # Parameters:
+    // * identity - The identity of the thread.
+    //
+    // # Returns:
+    // * The signer, or a description of why the keys of the identity are not usable.
+    pub fn from_identity(identity: &IdentityStore) -> Result<Self, String> {
+        let key_pair = identity.get_key_pair();
+        if key_pair.get_scheme() != KeyPair::ED25519 {
+            return Err(format!("the key pair of node {} is a {} key pair, not an {} one", identity.get_id(), key_pair.get_scheme(), KeyPair::ED25519))
+        }
+        let secret_key: [u8; 32] = key_pair.get_secret_key().as_slice().try_into()
+            .map_err(|_| format!("the secret key of node {} is not 32 bytes long", identity.get_id()))?;
+        let mut verifying_keys = HashMap::new();
+        for id in identity.get_peer_ids() {
+            let public_key: [u8; 32] = identity.get_peer_key(id).unwrap().as_slice().try_into()
+                .map_err(|_| format!("the public key of node {id} is not 32 bytes long"))?;
+            let verifying_key = VerifyingKey::from_bytes(&public_key).map_err(|error| format!("the public key of node {id} is invalid: {error}"))?;
+            verifying_keys.insert(id, verifying_key);
+        }
+        Ok(Self {
+            id: identity.get_id(),
+            signing_key: SigningKey::from_bytes(&secret_key),
+            verifying_keys
+        })
+    }
+
+    // # Function Description:
+    // This function creates the signer of a thread according to the configured `Authentication`. In network
+    // mode, the signer of the node hosted by the process is built from the identity it persisted (see
+    // `ClusterConfig::with_identity_file`), and the threads hosted by other processes get no signer, as they
+    // sign their own signals. Otherwise, every thread uses the identity `IdentityStore::generate_cluster`
+    // derives from the seed, for in-process simulations. With a membership, signals are verified with the
+    // public keys of the members.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The signer, `None` if signals are unsigned or if the thread is hosted by another process.
+    //
+    // # Panics:
+    // * If the identity file cannot be loaded, or its keys are not Ed25519 keys.
+    pub fn from_config(id: u32, config: &ClusterConfig) -> Option<Self> {
+        match config.get_authentication() {
+            Authentication::Unsigned => None,
+            Authentication::Ed25519 => {
+                let mut identity = match config.get_identity_file() {
+                    Some(path) => match IdentityStore::load(path) {
+                        Ok(identity) if identity.get_id() == id => identity,
+                        Ok(_) => return None,
+                        Err(error) => panic!("Error: {error}"),
+                    },
+                    None => IdentityStore::generate_cluster(config).swap_remove(id as usize),
+                };
+                if let Some(membership) = config.get_membership() {
+                    for member in membership.get_document().get_members() {
+                        identity = identity.with_peer_key(member.get_id(), member.get_public_key().clone());
+                    }
+                }
+                match Self::from_identity(&identity) {
+                    Ok(signer) => Some(signer),
+                    Err(error) => panic!("Error: {error}"),
+                }
+            },
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    // # Method Description:
+    // This method signs bytes with the secret key of the thread.
+    //
+    // # Returns:
+    // * The 64-byte signature.
+    pub fn sign(&self, bytes: &[u8]) -> Vec<u8> {
+        self.signing_key.sign(bytes).to_bytes().to_vec()
+    }
+
+    // # Method Description:
+    // This method verifies the signature of bytes by a thread.
+    //
+    // # Parameters:
+    // * signer - The ID of the thread that claims to have signed the bytes.
+    // * bytes - The signed bytes.
+    // * signature - The signature.
+    //
+    // # Returns:
+    // * `true` if the signer is known and the signature is its signature of the bytes.
+    pub fn verify(&self, signer: u32, bytes: &[u8], signature: &[u8]) -> bool {
+        let (Some(verifying_key), Ok(signature)) = (self.verifying_keys.get(&signer), Signature::from_slice(signature)) else {
+            return false
+        };
+        verifying_key.verify(bytes, &signature).is_ok()
+    }
+}
+
+// # Function Description:
+// This function verifies an Ed25519 signature with a public key alone, e.g. for a party outside the cluster
+// that only knows the public keys of its members.
+//
+// # Parameters:
+// * public_key - The 32-byte public key of the signer.
+// * bytes - The signed bytes.
+// * signature - The signature.
+//
+// # Returns:
+// * `true` if the public key is valid and the signature is its signature of the bytes.
+pub fn verify_with_key(public_key: &[u8], bytes: &[u8], signature: &[u8]) -> bool {
+    let (Ok(public_key), Ok(signature)) = (<[u8; 32]>::try_from(public_key), Signature::from_slice(signature)) else {
+        return false
+    };
+    VerifyingKey::from_bytes(&public_key).is_ok_and(|verifying_key| verifying_key.verify(bytes, &signature).is_ok())
+}
@@ -0,0 +1,280 @@
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use tokio::sync::{oneshot, mpsc::UnboundedSender};
+
+use crate::basic::RecvObject;
+use crate::health::HealthProbe;
+use crate::json::JsonConversion;
+use crate::reliable::{ReliableCommunication, ReliableHandleCommand, ReliableInstanceMonitor};
+use crate::round::Round;
+use crate::witness::{WitnessHandleCommand, WitnessRoundMonitor};
+
+// The names of the handles answering a snapshot with the monitors of their rounds (see `HealthProbe`).
+const ROUND_HANDLES: [&str; 2] = ["witness", "aggregated witness"];
+
+// # Struct Description:
+// This struct is a serializable snapshot of the state of a thread: the monitor of every reliable broadcast
+// instance held by its reliable handle, the monitor of every round held by its witness handle, and the
+// messages waiting in its basic queues. It is taken with `ReliableCommunication::snapshot` (or, without the
+// queues, with `SnapshotTap::snapshot`) and restored into a new communicator of the same thread with
+// `ReliableCommunication::restore`, e.g. to resume a crashed thread or to replay a stuck round in a test.
+// Live state (subscriptions, timers, and the start instants of the phases) is not captured, and the rounds of
+// an aggregated witness handle are captured but only restored by a witness handle.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * instances - The monitor of every instance held by the reliable handle, by instance ID.
+// * rounds - The monitor of every round held by the witness handle, by round number.
+// * queues - The messages waiting in the basic queues, by sender, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ThreadSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    id: u32,
+    instances: BTreeMap<String, ReliableInstanceMonitor<T>>,
+    rounds: BTreeMap<Round, WitnessRoundMonitor<T>>,
+    queues: BTreeMap<u32, Vec<RecvObject<T>>>,
+}
+
+impl<T> ThreadSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            instances: BTreeMap::new(),
+            rounds: BTreeMap::new(),
+            queues: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_queues(mut self, queues: BTreeMap<u32, Vec<RecvObject<T>>>) -> Self {
+        self.queues = queues;
+        self
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_instances(&self) -> &BTreeMap<String, ReliableInstanceMonitor<T>> {
+        &self.instances
+    }
+
+    pub fn get_rounds(&self) -> &BTreeMap<Round, WitnessRoundMonitor<T>> {
+        &self.rounds
+    }
+
+    pub fn get_queues(&self) -> &BTreeMap<u32, Vec<RecvObject<T>>> {
+        &self.queues
+    }
+
+    // # Method Description:
+    // This method lists the instances the thread has not delivered yet.
+    //
+    // # Returns:
+    // * The IDs of the open instances, in order.
+    pub fn get_open_instances(&self) -> Vec<&String> {
+        self.instances.iter()
+            .filter(|(_, instance)| !instance.state.deliver)
+            .map(|(instance_id, _)| instance_id)
+            .collect()
+    }
+
+    // # Method Description:
+    // This method lists the rounds the thread has not delivered yet.
+    //
+    // # Returns:
+    // * The numbers of the open rounds, in order.
+    pub fn get_open_rounds(&self) -> Vec<Round> {
+        self.rounds.iter()
+            .filter(|(_, monitor)| !monitor.state.witnesses)
+            .map(|(round_number, _)| *round_number)
+            .collect()
+    }
+
+    pub(crate) fn take_instances(&mut self) -> BTreeMap<String, ReliableInstanceMonitor<T>> {
+        std::mem::take(&mut self.instances)
+    }
+
+    pub(crate) fn take_rounds(&mut self) -> BTreeMap<Round, WitnessRoundMonitor<T>> {
+        std::mem::take(&mut self.rounds)
+    }
+
+    pub(crate) fn take_queues(&mut self) -> BTreeMap<u32, Vec<RecvObject<T>>> {
+        std::mem::take(&mut self.queues)
+    }
+}
+
+impl<T> JsonConversion<ThreadSnapshot<T>> for ThreadSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{}
+
+// # Struct Description:
+// This struct is a serializable snapshot of every thread of a cluster, as taken by `ReliableHub::snapshot`
+// and `WitnessHub::snapshot`, and restored with their `restore`.
+//
+// # Fields:
+// * threads - The `ThreadSnapshot` of every thread, in ID order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ClusterSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    threads: Vec<ThreadSnapshot<T>>,
+}
+
+impl<T> ClusterSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new(mut threads: Vec<ThreadSnapshot<T>>) -> Self {
+        threads.sort_by_key(ThreadSnapshot::get_id);
+        Self {
+            threads
+        }
+    }
+
+    pub fn get_threads(&self) -> &Vec<ThreadSnapshot<T>> {
+        &self.threads
+    }
+
+    pub fn get_thread(&self, id: u32) -> Option<&ThreadSnapshot<T>> {
+        self.threads.iter().find(|thread| thread.id == id)
+    }
+
+    pub(crate) fn into_threads(self) -> Vec<ThreadSnapshot<T>> {
+        self.threads
+    }
+}
+
+impl<T> JsonConversion<ClusterSnapshot<T>> for ClusterSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{}
+
+// # Struct Description:
+// This struct is a snapshot request sent to the running handles of a thread, whose answers are awaited
+// with `receive`.
+//
+// # Fields:
+// * id - The ID of the thread.
+// * instances - The receiver of the monitors of the reliable handle in JSON, if it is running.
+// * rounds - The receiver of the monitors of the witness handle, if it is running.
+pub struct PendingSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    id: u32,
+    instances: Option<oneshot::Receiver<String>>,
+    rounds: Option<oneshot::Receiver<BTreeMap<Round, WitnessRoundMonitor<T>>>>,
+}
+
+impl<T> PendingSnapshot<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Method Description:
+    // This method waits for the answers of the handles. A handle that stops before answering contributes
+    // no monitor.
+    //
+    // # Returns:
+    // * The `ThreadSnapshot` of the thread, without its queues.
+    pub async fn receive(self) -> ThreadSnapshot<T> {
+        let mut snapshot = ThreadSnapshot::new(self.id);
+        if let Some(instances) = self.instances
+            && let Ok(instances) = instances.await {
+            snapshot.instances = serde_json::from_str(&instances).expect("Error: the snapshot of the reliable handle could not be read");
+        }
+        if let Some(rounds) = self.rounds
+            && let Ok(rounds) = rounds.await {
+            snapshot.rounds = rounds;
+        }
+        snapshot
+    }
+}
+
+// # Struct Description:
+// This struct holds the command channels of the handles of a thread, so that their monitors can be
+// snapshot by the hub the thread was created from, after its communicator was handed out. Only the
+// handles that are running are queried (see `HealthProbe`).
+//
+// # Fields:
+// * id - The ID of the thread.
+// * reliable_commands - The channel of the commands of the reliable handle.
+// * round_commands - The channel of the commands of the witness handle, if the thread runs one.
+// * health_probe - The liveness of the handles of the thread.
+#[derive(Debug, Clone)]
+pub struct SnapshotTap<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    id: u32,
+    reliable_commands: UnboundedSender<ReliableHandleCommand>,
+    round_commands: Option<UnboundedSender<WitnessHandleCommand<T>>>,
+    health_probe: HealthProbe,
+}
+
+impl<T> SnapshotTap<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn with_channels(id: u32, reliable_commands: UnboundedSender<ReliableHandleCommand>, round_commands: Option<UnboundedSender<WitnessHandleCommand<T>>>, health_probe: HealthProbe) -> Self {
+        Self {
+            id,
+            reliable_commands,
+            round_commands,
+            health_probe,
+        }
+    }
+
+    pub fn new<C>(communicator: &C) -> Self
+    where
+        T: Send + Sync + 'static,
+        C: ReliableCommunication<T>,
+    {
+        Self::with_channels(*communicator.get_id(), communicator.get_reliable_command_channel().clone(), 
+            communicator.get_round_command_channel().cloned(), communicator.get_metrics().get_health_probe().clone())
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    // # Method Description:
+    // This method sends a snapshot command to every running handle of the thread.
+    //
+    // # Returns:
+    // * The `PendingSnapshot` answered by the handles (in `HandleMode::Polling`, once the thread is driven).
+    pub fn request(&self) -> PendingSnapshot<T> {
+        let instances = self.health_probe.is_running("reliable").then(|| {
+            let (responder, instances) = oneshot::channel();
+            self.reliable_commands.send(ReliableHandleCommand::Snapshot(responder)).ok().map(|_| instances)
+        }).flatten();
+        let rounds = self.round_commands.as_ref()
+            .filter(|_| ROUND_HANDLES.iter().any(|name| self.health_probe.is_running(name)))
+            .and_then(|round_commands| {
+                let (responder, rounds) = oneshot::channel();
+                round_commands.send(WitnessHandleCommand::Snapshot(responder)).ok().map(|_| rounds)
+            });
+        PendingSnapshot {
+            id: self.id,
+            instances,
+            rounds,
+        }
+    }
+
+    // # Method Description:
+    // This method snapshots the monitors of the running handles of the thread.
+    //
+    // # Returns:
+    // * The `ThreadSnapshot` of the thread, without its queues, which only its communicator can read.
+    pub async fn snapshot(&self) -> ThreadSnapshot<T> {
+        self.request().receive().await
+    }
+}
@@ -0,0 +1,526 @@
+use std::{collections::{BTreeMap, HashMap, HashSet}, fmt::Debug, fs, hash::Hash, io::Write, mem::{self, Discriminant}, path::{Path, PathBuf}, sync::{Arc, Mutex}};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::config::ClusterConfig;
+use crate::reliable::{ObjectContent, Signal, SignalType};
+use crate::round::{Instance, Round};
+
+// # Trait Description:
+// A trait implemented by every persistence backend: a key-value store of byte values, used by the features
+// that persist state across restarts (write-ahead logs, checkpoints, audit trails). Keys are strings and
+// are ordered, so a feature can keep its records under a prefix and scan them in order. Downstream users
+// can implement the trait to plug their own store.
+pub trait Storage: Send + Sync {
+    // # Method Description:
+    // This method stores a value under a key, replacing the previous one.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the value could not be stored.
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), String>;
+
+    // # Method Description:
+    // This method reads the value stored under a key.
+    //
+    // # Returns:
+    // * The value, `None` if the key is absent, or a description of why it could not be read.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+
+    // # Method Description:
+    // This method removes the value stored under a key, if any.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the value could not be removed.
+    fn delete(&self, key: &str) -> Result<(), String>;
+
+    // # Method Description:
+    // This method reads every value whose key starts with a prefix.
+    //
+    // # Returns:
+    // * The keys and values, ordered by key, or a description of why they could not be read.
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, String>;
+
+    // # Method Description:
+    // This method makes every value stored so far durable.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the values could not be made durable.
+    fn flush(&self) -> Result<(), String>;
+}
+
+// # Enum Description:
+// This enum represents the persistence backend selected in the `ClusterConfig` of a hub: tests keep state in
+// memory, while long experiments persist it durably. Durable backends keep every thread in its own directory
+// (`node-<id>`) under the configured root.
+//
+// # Variants:
+// * Memory - State is kept in memory and lost when the process exits.
+// * File - Every value is a file under the root directory, written atomically.
+// * Sled - State is kept in a sled database under the root directory (requires the `sled` feature).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    #[default]
+    Memory,
+    File(PathBuf),
+    Sled(PathBuf),
+}
+
+impl StorageBackend {
+    // # Method Description:
+    // This method opens the storage of a thread with this backend.
+    //
+    // # Parameters:
+    // * id - The ID of the thread owning the storage.
+    //
+    // # Returns:
+    // * The storage, or a description of why it could not be opened.
+    pub fn open(&self, id: u32) -> Result<Arc<dyn Storage>, String> {
+        match self {
+            StorageBackend::Memory => Ok(Arc::new(MemoryStorage::new())),
+            StorageBackend::File(root) => Ok(Arc::new(FileStorage::open(root.join(format!("node-{id}")))?)),
+            StorageBackend::Sled(root) => open_sled(root.join(format!("node-{id}"))),
+        }
+    }
+
+    // # Method Description:
+    // This method selects the same kind of durable backend under another root directory. State kept in
+    // memory cannot be found again, so the files of a `File` backend are selected in place of it.
+    //
+    // # Parameters:
+    // * root - The root directory.
+    //
+    // # Returns:
+    // * The backend under the root.
+    pub fn with_root(&self, root: &Path) -> Self {
+        match self {
+            StorageBackend::Sled(_) => StorageBackend::Sled(root.to_path_buf()),
+            StorageBackend::Memory | StorageBackend::File(_) => StorageBackend::File(root.to_path_buf()),
+        }
+    }
+}
+
+// The prefix under which a `WriteAheadLog` keeps its records.
+const WAL_PREFIX: &str = "wal/";
+
+// # Struct Description:
+// This struct is an append-only log of records kept in a storage, each under the key of its sequence number
+// (zero-padded, so that keys are ordered like the records). Every record is flushed before `append` returns,
+// so a record is durable before the action it journals takes place.
+//
+// # Fields:
+// * storage - The storage holding the records.
+// * next - The sequence number of the next record, locked while a record is appended.
+pub struct WriteAheadLog {
+    storage: Arc<dyn Storage>,
+    next: Mutex<u64>,
+}
+
+impl WriteAheadLog {
+    // # Method Description:
+    // This method opens the log kept in a storage, after the records it already holds.
+    //
+    // # Parameters:
+    // * storage - The storage holding the records.
+    //
+    // # Returns:
+    // * The log, or a description of why its records could not be read.
+    pub fn open(storage: Arc<dyn Storage>) -> Result<Self, String> {
+        let next = storage.scan_prefix(WAL_PREFIX)?.last()
+            .and_then(|(key, _)| key[WAL_PREFIX.len()..].parse::<u64>().ok())
+            .map_or(0, |last| last + 1);
+        Ok(Self {
+            storage,
+            next: Mutex::new(next),
+        })
+    }
+
+    pub fn get_length(&self) -> u64 {
+        *self.next.lock().unwrap()
+    }
+
+    // # Method Description:
+    // This method appends a record to the log, and makes it durable.
+    //
+    // # Parameters:
+    // * record - The record.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the record could not be stored.
+    pub fn append<R: Serialize>(&self, record: &R) -> Result<(), String> {
+        let value = serde_json::to_vec(record).map_err(|error| format!("could not encode the record: {error}"))?;
+        let mut next = self.next.lock().unwrap();
+        self.storage.put(&format!("{WAL_PREFIX}{:020}", *next), &value)?;
+        self.storage.flush()?;
+        *next += 1;
+        Ok(())
+    }
+
+    // # Method Description:
+    // This method reads every record of the log.
+    //
+    // # Returns:
+    // * The records, in the order they were appended, or a description of why they could not be read.
+    pub fn read<R: DeserializeOwned>(&self) -> Result<Vec<R>, String> {
+        self.storage.scan_prefix(WAL_PREFIX)?.into_iter()
+            .map(|(key, value)| serde_json::from_slice(&value).map_err(|error| format!("could not decode the record {key}: {error}")))
+            .collect()
+    }
+}
+
+// # Enum Description:
+// This enum represents a record of the journal of a thread (see `SignalJournal`).
+//
+// # Variants:
+// * Received - A signal received by the reliable handle of the thread, journaled before it is processed.
+// * Sent - A signal broadcast by the thread, journaled before it is sent.
+// * Delivered - The ID of an instance the reliable handle of the thread delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalEntry<T> {
+    Received(Signal<T>),
+    Sent(Signal<T>),
+    Delivered(String),
+}
+
+// The signal type, content type, protocol, creator, instance, and round a thread broadcasts a single content for.
+type SentKey<T> = (SignalType, Discriminant<ObjectContent<T>>, String, u32, Instance, Round);
+
+// # Struct Description:
+// This struct is the write-ahead log of a thread, enabled with `ClusterConfig::with_journaling`: the thread
+// journals every signal its reliable handle receives and every signal it broadcasts, before processing or
+// sending it, and every instance it delivers. A thread restarted after a crash replays the signals it
+// received (see `ReliableCommunicator::recover`), which restores its instances and, through their
+// deliveries, its witness rounds. The signals it sent are kept so that it never sends a content conflicting
+// with one it sent before it crashed, e.g. an Echo of another value in an instance it already echoed.
+// The journal is kept in the storage of the thread (see `ClusterConfig::open_storage`), and grows with
+// every signal: it is meant for experiments of bounded length.
+//
+// # Fields:
+// * log - The log of `JournalEntry` records.
+// * sent - The content of every signal broadcast so far, by signal type, content type, protocol, creator, instance, and round.
+// * delivered - The IDs of the instances delivered so far.
+pub struct SignalJournal<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    log: WriteAheadLog,
+    sent: Mutex<HashMap<SentKey<T>, ObjectContent<T>>>,
+    delivered: Mutex<HashSet<String>>,
+}
+
+impl<T> SignalJournal<T>
+where
+    T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+{
+    // # Method Description:
+    // This method opens the journal of a thread in its storage, restoring the signals it sent and the
+    // instances it delivered before.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The journal, `None` if journaling is disabled, or a description of why it could not be read.
+    pub fn from_config(id: u32, config: &ClusterConfig) -> Result<Option<Self>, String> {
+        if !config.get_journaling() {
+            return Ok(None)
+        }
+        let log = WriteAheadLog::open(config.open_storage(id)?)?;
+        let mut sent = HashMap::new();
+        let mut delivered = HashSet::new();
+        for entry in log.read::<JournalEntry<T>>()? {
+            match entry {
+                JournalEntry::Received(_) => {},
+                JournalEntry::Sent(signal) => {
+                    sent.insert(Self::sent_key(&signal), signal.get_content().clone());
+                },
+                JournalEntry::Delivered(instance_id) => {
+                    delivered.insert(instance_id);
+                },
+            }
+        }
+        Ok(Some(Self {
+            log,
+            sent: Mutex::new(sent),
+            delivered: Mutex::new(delivered),
+        }))
+    }
+
+    fn sent_key(signal: &Signal<T>) -> SentKey<T> {
+        let content = signal.get_content();
+        (*signal.get_signal(), mem::discriminant(content), content.get_protocol_information().clone(), content.get_id(), signal.get_instance_number(), signal.get_round_number())
+    }
+
+    pub fn get_length(&self) -> u64 {
+        self.log.get_length()
+    }
+
+    pub fn get_delivered(&self) -> Vec<String> {
+        let mut delivered: Vec<String> = self.delivered.lock().unwrap().iter().cloned().collect();
+        delivered.sort();
+        delivered
+    }
+
+    // # Method Description:
+    // This method reads the signals the reliable handle of the thread received, to replay them.
+    //
+    // # Returns:
+    // * The signals, in the order they were received, or a description of why they could not be read.
+    pub fn get_received(&self) -> Result<Vec<Signal<T>>, String> {
+        Ok(self.log.read::<JournalEntry<T>>()?.into_iter()
+            .filter_map(|entry| match entry {
+                JournalEntry::Received(signal) => Some(signal),
+                _ => None,
+            })
+            .collect())
+    }
+
+    // # Method Description:
+    // This method journals a signal received by the reliable handle of the thread.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the signal could not be journaled.
+    pub fn record_received(&self, signal: &Signal<T>) -> Result<(), String> {
+        self.log.append(&JournalEntry::Received(signal.clone()))
+    }
+
+    // # Method Description:
+    // This method journals a signal the thread is about to broadcast. A signal with the same content as one
+    // sent before is not journaled again, and may be sent again.
+    //
+    // # Returns:
+    // * Nothing if the signal may be sent, or a description of why it may not: its content conflicts with the
+    //   one sent before for the same signal type, content type, protocol, creator, instance, and round, or it
+    //   could not be journaled.
+    pub fn record_sent(&self, signal: &Signal<T>) -> Result<(), String> {
+        let key = Self::sent_key(signal);
+        let mut sent = self.sent.lock().unwrap();
+        match sent.get(&key) {
+            Some(content) if content == signal.get_content() => Ok(()),
+            Some(_) => Err(format!("{:?} of {} content by id: {}, instance: {}, round: {} conflicting with the one sent before", 
+                key.0, key.2, key.3, key.4, key.5)),
+            None => {
+                self.log.append(&JournalEntry::Sent(signal.clone()))?;
+                sent.insert(key, signal.get_content().clone());
+                Ok(())
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method journals the delivery of an instance, unless it was journaled before.
+    //
+    // # Returns:
+    // * Nothing, or a description of why the delivery could not be journaled.
+    pub fn record_delivered(&self, instance_id: &str) -> Result<(), String> {
+        let mut delivered = self.delivered.lock().unwrap();
+        if delivered.contains(instance_id) {
+            return Ok(())
+        }
+        self.log.append(&JournalEntry::<T>::Delivered(instance_id.to_string()))?;
+        delivered.insert(instance_id.to_string());
+        Ok(())
+    }
+}
+
+// # Struct Description:
+// This struct is a storage kept in memory, e.g. for tests.
+//
+// # Fields:
+// * values - The stored values, by key.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    values: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.values.lock().unwrap().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.values.lock().unwrap().get(key).cloned())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        self.values.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let values = self.values.lock().unwrap();
+        Ok(values.range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// # Struct Description:
+// This struct is a storage keeping every value in its own file under a directory. Keys are hex-encoded into
+// file names, so any key is valid, and values are synced to a temporary file renamed over the previous one,
+// so a value is durable once `put` returns and a crash never leaves a partially written value.
+//
+// # Fields:
+// * directory - The directory holding the values.
+#[derive(Debug)]
+pub struct FileStorage {
+    directory: PathBuf,
+}
+
+impl FileStorage {
+    // # Method Description:
+    // This method opens the storage in a directory, creating it if needed.
+    //
+    // # Parameters:
+    // * directory - The directory holding the values.
+    //
+    // # Returns:
+    // * The storage, or a description of why the directory could not be created.
+    pub fn open(directory: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&directory).map_err(|error| format!("could not create {}: {error}", directory.display()))?;
+        Ok(Self {
+            directory
+        })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key.bytes().map(|byte| format!("{byte:02x}")).collect();
+        self.directory.join(format!("{name}.value"))
+    }
+}
+
+// # Function Description:
+// This function decodes a file name of a `FileStorage` back into its key.
+//
+// # Returns:
+// * The key, `None` if the file name is not the one of a value.
+fn decode_key(name: &str) -> Option<String> {
+    let name = name.strip_suffix(".value")?;
+    if name.len() % 2 != 0 {
+        return None
+    }
+    let bytes: Option<Vec<u8>> = (0..name.len()).step_by(2).map(|index| u8::from_str_radix(&name[index..index + 2], 16).ok()).collect();
+    String::from_utf8(bytes?).ok()
+}
+
+impl Storage for FileStorage {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        let path = self.path(key);
+        let temporary = path.with_extension("tmp");
+        let mut file = fs::File::create(&temporary).map_err(|error| format!("could not create {}: {error}", temporary.display()))?;
+        file.write_all(value).and_then(|_| file.sync_all()).map_err(|error| format!("could not write {}: {error}", temporary.display()))?;
+        fs::rename(&temporary, &path).map_err(|error| format!("could not replace {}: {error}", path.display()))
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let path = self.path(key);
+        match fs::read(&path) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(format!("could not read {}: {error}", path.display())),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        let path = self.path(key);
+        match fs::remove_file(&path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(format!("could not remove {}: {error}", path.display())),
+            _ => Ok(()),
+        }
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let entries = fs::read_dir(&self.directory).map_err(|error| format!("could not list {}: {error}", self.directory.display()))?;
+        let mut keys: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| decode_key(&entry.file_name().to_string_lossy()))
+            .filter(|key| key.starts_with(prefix))
+            .collect();
+        keys.sort();
+        let mut values = vec![];
+        for key in keys {
+            if let Some(value) = self.get(&key)? {
+                values.push((key, value));
+            }
+        }
+        Ok(values)
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// # Struct Description:
+// This struct is a storage kept in a sled database.
+//
+// # Fields:
+// * database - The sled database.
+#[cfg(feature = "sled")]
+pub struct SledStorage {
+    database: sled::Db,
+}
+
+#[cfg(feature = "sled")]
+impl SledStorage {
+    // # Method Description:
+    // This method opens the sled database in a directory, creating it if needed.
+    //
+    // # Returns:
+    // * The storage, or a description of why the database could not be opened.
+    pub fn open(directory: PathBuf) -> Result<Self, String> {
+        let database = sled::open(&directory).map_err(|error| format!("could not open {}: {error}", directory.display()))?;
+        Ok(Self {
+            database
+        })
+    }
+}
+
+#[cfg(feature = "sled")]
+impl Storage for SledStorage {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.database.insert(key, value).map(|_| ()).map_err(|error| error.to_string())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        self.database.get(key).map(|value| value.map(|value| value.to_vec())).map_err(|error| error.to_string())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        self.database.remove(key).map(|_| ()).map_err(|error| error.to_string())
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+        self.database.scan_prefix(prefix)
+            .map(|entry| {
+                let (key, value) = entry.map_err(|error| error.to_string())?;
+                Ok((String::from_utf8_lossy(&key).into_owned(), value.to_vec()))
+            })
+            .collect()
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        self.database.flush().map(|_| ()).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(feature = "sled")]
+fn open_sled(directory: PathBuf) -> Result<Arc<dyn Storage>, String> {
+    Ok(Arc::new(SledStorage::open(directory)?))
+}
+
+#[cfg(not(feature = "sled"))]
+fn open_sled(_directory: PathBuf) -> Result<Arc<dyn Storage>, String> {
+    Err(String::from("the sled storage backend requires the `sled` feature"))
+}
@@ -0,0 +1,70 @@
+use crate::metrics::Metrics;
+use crate::round::Round;
+
+// # Function Description:
+// This function computes the number of signal frames a single reliable broadcast instance sends in a
+// cluster: the origin sends its Input to every thread, then every thread sends its Echo and its Vote to
+// every thread (or along a spanning tree, which takes as many frames). Rounds of the other protocols run
+// a known number of reliable broadcasts per thread, so their bound is a multiple of this one (e.g. two
+// per thread for a witness round: the value and the report).
+//
+// # Parameters:
+// * thread_count - The number of threads in the cluster.
+//
+// # Returns:
+// * `n + 2n²` frames, the O(n²) cost of one instance.
+pub fn reliable_broadcast_bound(thread_count: u32) -> u64 {
+    let n = thread_count as u64;
+    n + 2 * n * n
+}
+
+// # Function Description:
+// This function counts the signal frames sent by every thread of a cluster in a round of a protocol.
+//
+// # Parameters:
+// * nodes - The metrics of every thread (`get_metrics()` of each communicator).
+// * protocol_information - The protocol the round belongs to (e.g. "witness").
+// * round_number - The round whose frames are counted.
+//
+// # Returns:
+// * The total number of signal frames sent in the round.
+pub fn count_sent_signals(nodes: &[Metrics], protocol_information: &str, round_number: Round) -> u64 {
+    nodes.iter().map(|node| node.get_sent_signals(protocol_information, round_number)).sum()
+}
+
+// # Function Description:
+// This function checks the message complexity of a round against a bound.
+//
+// # Parameters:
+// * nodes - The metrics of every thread of the cluster.
+// * protocol_information - The protocol the round belongs to.
+// * round_number - The round whose frames are counted.
+// * bound - The maximum number of signal frames the round may send.
+//
+// # Returns:
+// * The number of frames sent, or a description of the violation if it exceeds the bound.
+pub fn check_message_complexity(nodes: &[Metrics], protocol_information: &str, round_number: Round, bound: u64) -> Result<u64, String> {
+    let sent = count_sent_signals(nodes, protocol_information, round_number);
+    if sent > bound {
+        return Err(format!("{protocol_information} round {round_number} sent {sent} signal frames, more than the bound of {bound}"))
+    }
+    Ok(sent)
+}
+
+// # Function Description:
+// This function asserts that a round did not send more signal frames than a bound, so that algorithmic
+// regressions (e.g. a rebroadcast loop) fail a test instead of only slowing it down.
+//
+// # Parameters:
+// * nodes - The metrics of every thread of the cluster.
+// * protocol_information - The protocol the round belongs to.
+// * round_number - The round whose frames are counted.
+// * bound - The maximum number of signal frames the round may send.
+//
+// # Panics:
+// * If the round sent more frames than the bound.
+pub fn assert_message_complexity_at_most(nodes: &[Metrics], protocol_information: &str, round_number: Round, bound: u64) {
+    if let Err(violation) = check_message_complexity(nodes, protocol_information, round_number, bound) {
+        panic!("Error: {}", violation);
+    }
+}
@@ -0,0 +1,448 @@
+use std::{collections::{BTreeMap, BTreeSet}, fmt::Debug, hash::Hash, io::BufRead, time::Duration};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::basic::Message;
+use crate::config::derive_seed;
+use crate::json::JsonConversion;
+use crate::round::{Instance, Round};
+
+// # Struct Description:
+// This struct records that a node delivered a broadcast instance, identified by its protocol, sender,
+// instance, and round, with the digest of the delivered message. A trace is a sequence of such records,
+// one per line (see `write_trace`), that can be checked offline by a `TraceChecker` without re-running
+// the simulation.
+//
+// # Fields:
+// * id - The ID of the node that delivered the message.
+// * protocol_information - The protocol of the delivered instance.
+// * sender - The ID of the node that broadcast the instance.
+// * instance_number - The instance number, if the protocol numbers its instances.
+// * round_number - The round of the instance.
+// * digest - The digest of the delivered message (see `Message::get_digest`).
+// * delivered_at - The time of the delivery since the start of the run, if it was recorded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveryRecord {
+    id: u32,
+    protocol_information: String,
+    sender: u32,
+    instance_number: Option<Instance>,
+    round_number: Round,
+    digest: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delivered_at: Option<Duration>,
+}
+
+impl DeliveryRecord {
+    pub fn new(id: u32, protocol_information: String, sender: u32, instance_number: Option<Instance>, round_number: Round, digest: u64) -> Self {
+        Self {
+            id,
+            protocol_information,
+            sender,
+            instance_number,
+            round_number,
+            digest,
+            delivered_at: None
+        }
+    }
+
+    // # Method Description:
+    // This method records the time of the delivery, so that the trace can be queried by time range (see `TraceQuery`).
+    //
+    // # Parameters:
+    // * delivered_at - The time of the delivery since the start of the run.
+    //
+    // # Returns:
+    // * The updated record.
+    pub fn with_delivered_at(mut self, delivered_at: Duration) -> Self {
+        self.delivered_at = Some(delivered_at);
+        self
+    }
+
+    // # Function Description:
+    // This function records the delivery of a message by a node.
+    //
+    // # Parameters:
+    // * id - The ID of the node that delivered the message.
+    // * message - The delivered message.
+    //
+    // # Returns:
+    // * The record of the delivery.
+    pub fn from_message<T>(id: u32, message: &Message<T>) -> Self
+    where
+        T: Debug + Clone + Serialize + DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        Self::new(id, message.get_protocol_information().clone(), message.get_id(), message.get_instance_number(), message.get_round_number(), message.get_digest())
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_sender(&self) -> u32 {
+        self.sender
+    }
+
+    pub fn get_instance_number(&self) -> Option<Instance> {
+        self.instance_number
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn get_digest(&self) -> u64 {
+        self.digest
+    }
+
+    pub fn get_delivered_at(&self) -> Option<Duration> {
+        self.delivered_at
+    }
+
+    fn get_instance_key(&self) -> InstanceKey {
+        (self.protocol_information.clone(), self.sender, self.instance_number, self.round_number)
+    }
+}
+
+impl JsonConversion<DeliveryRecord> for DeliveryRecord {}
+
+// The protocol, sender, instance number, and round number identifying a broadcast instance in a trace.
+type InstanceKey = (String, u32, Option<Instance>, Round);
+
+// # Enum Description:
+// This enum represents a violation of the delivery properties of reliable broadcast found in a trace.
+// Only the deliveries of correct nodes are checked: nodes declared faulty may deliver anything.
+//
+// # Variants:
+// * Agreement - Two correct nodes delivered different messages for the same instance. Carries the
+//   instance (protocol, sender, instance number, round number) and both nodes with their digests.
+// * Totality - Some correct nodes delivered an instance that others never delivered. Carries the
+//   instance and the correct nodes missing it.
+// * Duplicate - A correct node delivered the same instance more than once. Carries the instance and the node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Violation {
+    Agreement(String, u32, Option<Instance>, Round, (u32, u64), (u32, u64)),
+    Totality(String, u32, Option<Instance>, Round, Vec<u32>),
+    Duplicate(String, u32, Option<Instance>, Round, u32),
+}
+
+// # Struct Description:
+// This struct checks the agreement and totality of the deliveries of a trace, one record at a time, so
+// that traces of millions of deliveries are checked in memory proportional to the number of instances
+// rather than to the number of records. Agreement and duplicate violations are found as records are
+// checked; totality can only be decided once the whole trace was checked (see `finish`).
+//
+// # Fields:
+// * thread_count - The number of nodes in the recorded cluster.
+// * faulty - The IDs of the nodes whose deliveries are not checked.
+// * deliveries - The digest delivered by every correct node, by instance.
+// * violations - The violations found so far.
+// * records - The number of records checked so far.
+#[derive(Debug, Clone)]
+pub struct TraceChecker {
+    thread_count: u32,
+    faulty: BTreeSet<u32>,
+    deliveries: BTreeMap<InstanceKey, BTreeMap<u32, u64>>,
+    violations: Vec<Violation>,
+    records: u64,
+}
+
+impl TraceChecker {
+    pub fn new(thread_count: u32) -> Self {
+        Self {
+            thread_count,
+            faulty: BTreeSet::new(),
+            deliveries: BTreeMap::new(),
+            violations: vec![],
+            records: 0,
+        }
+    }
+
+    // # Method Description:
+    // This method declares a node faulty: its deliveries are ignored, and it is never missing an instance.
+    //
+    // # Returns:
+    // * The updated checker.
+    pub fn with_faulty(mut self, id: u32) -> Self {
+        self.faulty.insert(id);
+        self
+    }
+
+    // # Method Description:
+    // This method checks one record of a trace against the records checked before it.
+    //
+    // # Parameters:
+    // * record - The record.
+    pub fn check(&mut self, record: &DeliveryRecord) {
+        self.records += 1;
+        if self.faulty.contains(&record.id) {
+            return
+        }
+        let key = record.get_instance_key();
+        let delivered = self.deliveries.entry(key.clone()).or_default();
+        if delivered.contains_key(&record.id) {
+            self.violations.push(Violation::Duplicate(key.0, key.1, key.2, key.3, record.id));
+            return
+        }
+        if let Some((&other, &digest)) = delivered.iter().find(|(_, digest)| **digest != record.digest) {
+            self.violations.push(Violation::Agreement(key.0, key.1, key.2, key.3, (other, digest), (record.id, record.digest)));
+        }
+        delivered.insert(record.id, record.digest);
+    }
+
+    pub fn get_records(&self) -> u64 {
+        self.records
+    }
+
+    // # Method Description:
+    // This method ends the check once every record of the trace was checked.
+    //
+    // # Returns:
+    // * Every violation found, totality violations last.
+    pub fn finish(mut self) -> Vec<Violation> {
+        let correct: Vec<u32> = (0..self.thread_count).filter(|id| !self.faulty.contains(id)).collect();
+        for ((protocol_information, sender, instance_number, round_number), delivered) in self.deliveries {
+            let missing: Vec<u32> = correct.iter().copied().filter(|id| !delivered.contains_key(id)).collect();
+            if !missing.is_empty() {
+                self.violations.push(Violation::Totality(protocol_information, sender, instance_number, round_number, missing));
+            }
+        }
+        self.violations
+    }
+}
+
+// # Enum Description:
+// This enum represents which broadcast instances are traced end-to-end: their phase latencies, round
+// statuses, and delivery records. Full tracing records every instance, which is too expensive in large
+// clusters; sampled tracing records a fixed fraction of the instances, selected by a digest of the instance
+// so that every node traces the same instances.
+//
+// # Variants:
+// * Full - Every instance is traced.
+// * PerMillion - The given number of instances out of a million are traced.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TraceSampling {
+    #[default]
+    Full,
+    PerMillion(u32),
+}
+
+impl TraceSampling {
+    // # Function Description:
+    // This function samples a fraction of the instances, e.g. `TraceSampling::fraction(0.01)` for 1%.
+    //
+    // # Parameters:
+    // * fraction - The fraction of the instances to trace, clamped to `0.0..=1.0`.
+    //
+    // # Returns:
+    // * The sampling.
+    pub fn fraction(fraction: f64) -> Self {
+        match fraction {
+            fraction if fraction >= 1.0 => Self::Full,
+            fraction if fraction > 0.0 => Self::PerMillion((fraction * 1_000_000.0).round() as u32),
+            _ => Self::PerMillion(0),
+        }
+    }
+}
+
+// # Struct Description:
+// This struct decides which instances are traced, following the `TraceSampling` of a cluster. The digest
+// of an instance is derived from the experiment seed and the instance's protocol, sender, kind, instance
+// number, and round number, never from the receiving node, so the same instances are traced by every node
+// and each sampled instance can be followed end-to-end.
+//
+// # Fields:
+// * sampling - The sampling of the cluster.
+// * seed - The experiment seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceSampler {
+    sampling: TraceSampling,
+    seed: u64,
+}
+
+impl TraceSampler {
+    pub fn new(sampling: TraceSampling, seed: u64) -> Self {
+        Self {
+            sampling,
+            seed
+        }
+    }
+
+    pub fn get_sampling(&self) -> TraceSampling {
+        self.sampling
+    }
+
+    // # Method Description:
+    // This method checks whether an instance is traced.
+    //
+    // # Parameters:
+    // * key - The instance, as "<protocol>::<sender_id>::<content_type>::<instance_number>::<round_number>".
+    //
+    // # Returns:
+    // * `true` if the instance is traced.
+    pub fn is_sampled(&self, key: &str) -> bool {
+        match self.sampling {
+            TraceSampling::Full => true,
+            TraceSampling::PerMillion(per_million) => derive_seed(self.seed, 0, key) % 1_000_000 < per_million as u64,
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether the instance of a delivery record is traced, so that sampled runs only
+    // write the records of traced instances. Every node selects the same instances, so a sampled trace
+    // can still be checked for agreement and totality.
+    //
+    // # Parameters:
+    // * record - The delivery record.
+    //
+    // # Returns:
+    // * `true` if the instance of the record is traced.
+    pub fn is_sampled_record(&self, record: &DeliveryRecord) -> bool {
+        self.is_sampled(&format!("{}::{}::message::{}::{}", record.protocol_information, record.sender, record.instance_number.unwrap_or_default(), record.round_number))
+    }
+}
+
+// # Struct Description:
+// This struct is a query over the records of a trace, e.g. every delivery of an instance, or what a node had
+// delivered by a given time of the run. Every criterion left unset matches every record; a time range only
+// matches the records whose delivery time was recorded.
+//
+// # Fields:
+// * id - The node that delivered, if set.
+// * protocol_information - The protocol of the delivered instance, if set.
+// * sender - The node that broadcast the delivered instance, if set.
+// * instance_number - The instance number of the delivered instance, if set.
+// * round_number - The round of the delivered instance, if set.
+// * from - The earliest delivery time matched, inclusive, if set.
+// * until - The latest delivery time matched, inclusive, if set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct TraceQuery {
+    id: Option<u32>,
+    protocol_information: Option<String>,
+    sender: Option<u32>,
+    instance_number: Option<Instance>,
+    round_number: Option<Round>,
+    from: Option<Duration>,
+    until: Option<Duration>,
+}
+
+impl TraceQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_node(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn with_protocol(mut self, protocol_information: String) -> Self {
+        self.protocol_information = Some(protocol_information);
+        self
+    }
+
+    pub fn with_sender(mut self, sender: u32) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    pub fn with_instance(mut self, instance_number: Instance) -> Self {
+        self.instance_number = Some(instance_number);
+        self
+    }
+
+    pub fn with_round(mut self, round_number: Round) -> Self {
+        self.round_number = Some(round_number);
+        self
+    }
+
+    pub fn with_from(mut self, from: Duration) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn with_until(mut self, until: Duration) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    // # Method Description:
+    // This method checks whether a record matches every criterion of the query.
+    //
+    // # Parameters:
+    // * record - The record.
+    //
+    // # Returns:
+    // * `true` if the record matches.
+    pub fn matches(&self, record: &DeliveryRecord) -> bool {
+        let timed = self.from.is_none() && self.until.is_none() || record.delivered_at.is_some_and(|delivered_at| {
+            self.from.is_none_or(|from| delivered_at >= from) && self.until.is_none_or(|until| delivered_at <= until)
+        });
+        timed && self.id.is_none_or(|id| record.id == id)
+            && self.protocol_information.as_ref().is_none_or(|protocol_information| &record.protocol_information == protocol_information)
+            && self.sender.is_none_or(|sender| record.sender == sender)
+            && self.instance_number.is_none_or(|instance_number| record.instance_number == Some(instance_number))
+            && self.round_number.is_none_or(|round_number| record.round_number == round_number)
+    }
+}
+
+// # Function Description:
+// This function writes records as a trace readable by `check_trace`.
+//
+// # Parameters:
+// * records - The records of the run.
+//
+// # Returns:
+// * The trace, one record per line.
+pub fn write_trace(records: &[DeliveryRecord]) -> String {
+    records.iter().map(|record| record.write_json() + "\n").collect()
+}
+
+// # Function Description:
+// This function checks a trace line by line, without loading it into memory.
+//
+// # Parameters:
+// * reader - The reader of the trace (e.g. a `BufReader` over the trace file).
+// * checker - The checker, configured with the recorded cluster.
+//
+// # Returns:
+// * The number of records checked and every violation found, or a description of the first line that
+//   could not be read or parsed.
+pub fn check_trace<R: BufRead>(reader: R, mut checker: TraceChecker) -> Result<(u64, Vec<Violation>), String> {
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| format!("line {}: {error}", index + 1))?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let record = DeliveryRecord::read_json(&line).map_err(|error| format!("line {}: {error}", index + 1))?;
+        checker.check(&record);
+    }
+    Ok((checker.get_records(), checker.finish()))
+}
+
+// # Function Description:
+// This function queries a trace line by line, without loading it into memory.
+//
+// # Parameters:
+// * reader - The reader of the trace (e.g. a `BufReader` over the trace file).
+// * query - The query.
+//
+// # Returns:
+// * The matching records, in trace order, or a description of the first line that could not be read or parsed.
+pub fn query_trace<R: BufRead>(reader: R, query: &TraceQuery) -> Result<Vec<DeliveryRecord>, String> {
+    let mut records = vec![];
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| format!("line {}: {error}", index + 1))?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let record = DeliveryRecord::read_json(&line).map_err(|error| format!("line {}: {error}", index + 1))?;
+        if query.matches(&record) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
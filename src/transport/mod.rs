@@ -0,0 +1,874 @@
+use std::{collections::{BTreeSet, VecDeque}, future::Future, net::SocketAddr, sync::{Arc, RwLock}, time::Duration};
+use bytes::Bytes;
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader, BufWriter};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc::{self, Receiver, Sender}};
+
+use crate::config::ClusterConfig;
+use crate::membership::MembershipCertificate;
+use crate::capabilities::{Capabilities, CapabilityMismatch, Negotiation, Protocol};
+use crate::json::JsonConversion;
+use crate::logging::log;
+use crate::multiplex::attribute_frame;
+
+#[cfg(feature = "quic")]
+mod quic;
+#[cfg(feature = "quic")]
+pub use quic::QuicTransport;
+
+// The largest frame a TCP connection accepts, in bytes; a larger length prefix closes the connection.
+const MAX_FRAME_SIZE: u32 = 64 * 1024 * 1024;
+
+// The capacity of the channel on which a network transport publishes its `ConnectionEvent`s.
+const CONNECTION_EVENT_BUFFER_SIZE: usize = 1024;
+
+// How long a connection waits for the capabilities of the peer before it is given up.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// # Struct Description:
+// This struct holds the channels a transport hands to a hub: a transmitter to every thread of the cluster,
+// and the inbound receiver of every thread. Receivers of threads hosted by another process are closed,
+// and the communicators the hub creates for them must not be used.
+//
+// # Fields:
+// * transmitters - The transmitter to every thread, indexed by thread ID.
+// * receivers - The inbound receiver of every thread, indexed by thread ID.
+pub struct TransportChannels {
+    pub transmitters: Vec<Sender<Bytes>>,
+    pub receivers: Vec<Receiver<Bytes>>,
+}
+
+// # Struct Description:
+// This struct sets how a network transport reconnects to a peer whose connection could not be opened or
+// dropped. Attempts are spaced by an exponential backoff, and the frames sent to the peer meanwhile are
+// buffered up to a limit, so that a transient network blip delays the frames of a peer instead of
+// excluding it from quorums. Frames sent while the buffer is full are dropped.
+//
+// # Fields:
+// * initial_backoff - The delay after the first failed attempt, doubled after every further failed attempt.
+// * max_backoff - The longest delay between two attempts.
+// * buffer_limit - The number of frames buffered while the peer is unreachable.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    buffer_limit: usize,
+}
+
+impl ReconnectPolicy {
+    pub fn new(initial_backoff: Duration, max_backoff: Duration, buffer_limit: usize) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff,
+            buffer_limit
+        }
+    }
+
+    pub fn get_initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    pub fn get_max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    pub fn get_buffer_limit(&self) -> usize {
+        self.buffer_limit
+    }
+
+    // # Method Description:
+    // This method computes the delay before the next attempt to connect to a peer.
+    //
+    // # Parameters:
+    // * failed_attempts - The number of consecutive attempts that failed so far, at least 1.
+    //
+    // # Returns:
+    // * The delay, capped at `max_backoff`.
+    pub fn get_backoff(&self, failed_attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failed_attempts.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(50), Duration::from_secs(2), 4096)
+    }
+}
+
+// # Enum Description:
+// This enum represents a change in the connection of a thread to one of its peers, published by network
+// transports (see `TcpTransport::subscribe_connection_events`).
+//
+// # Variants:
+// * Connected - The first connection to a peer opened. Carries the ID of the local thread, the ID of the
+//   peer, the number of attempts it took, and the number of frames dropped while waiting for it.
+// * Disconnected - The connection to a peer dropped; it is detected when the next frame is written.
+//   Carries the ID of the local thread, the ID of the peer, and the error.
+// * Reconnected - The connection to a peer opened again after it dropped, with the same fields as `Connected`.
+// * CapabilityMismatch - The capabilities exchanged when a connection opened do not match: the connection
+//   was closed if the peer was refused, and carries on with the protocols in common otherwise. Published by
+//   both ends of the connection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Connected(u32, u32, u32, usize),
+    Disconnected(u32, u32, String),
+    Reconnected(u32, u32, u32, usize),
+    CapabilityMismatch(CapabilityMismatch),
+}
+
+// # Struct Description:
+// This struct holds the address every thread of the cluster listens on, shared by a network transport and
+// every connection it opens. An address updated after the transport was opened (e.g. for a peer that
+// restarted on another machine) is used by the next attempt to connect to the peer.
+//
+// # Fields:
+// * addresses - The address of every thread, indexed by thread ID.
+#[derive(Debug, Clone, Default)]
+pub struct PeerAddressBook {
+    addresses: Arc<RwLock<Vec<SocketAddr>>>,
+}
+
+impl PeerAddressBook {
+    pub fn new(addresses: Vec<SocketAddr>) -> Self {
+        Self {
+            addresses: Arc::new(RwLock::new(addresses))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_address(&self, id: u32) -> Option<SocketAddr> {
+        self.addresses.read().unwrap().get(id as usize).copied()
+    }
+
+    // # Method Description:
+    // This method updates the address of a thread.
+    //
+    // # Parameters:
+    // * id - The ID of the thread.
+    // * address - Its new address.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why the thread is not in the address book.
+    pub fn set_address(&self, id: u32, address: SocketAddr) -> Result<(), String> {
+        match self.addresses.write().unwrap().get_mut(id as usize) {
+            Some(entry) => {
+                *entry = address;
+                Ok(())
+            },
+            None => Err(format!("thread {id} is not in the address book")),
+        }
+    }
+}
+
+// # Trait Description:
+// A trait implemented by every way of connecting the threads of a cluster. A transport turns the
+// configuration of the cluster into the channels handed to a hub (`ReliableHub::with_config`,
+// `WitnessHub::with_config`, ...); the hubs and protocols only ever see channels of frames, so they
+// run unchanged over any transport. Network emulation (see `ClusterConfig::link_transmitters`) applies
+// on top of any transport.
+#[async_trait]
+pub trait Transport: Send {
+    // # Method Description:
+    // This method returns the IDs of the threads hosted by this process, whose communicators are the
+    // ones to use among those created by the hub.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    fn get_local_ids(&self, config: &ClusterConfig) -> Vec<u32>;
+
+    // # Method Description:
+    // This method connects the threads hosted by this process to the cluster.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The channels to hand to a hub, or a description of why the transport could not be opened.
+    async fn open(&mut self, config: &ClusterConfig) -> Result<TransportChannels, String>;
+}
+
+// # Struct Description:
+// This struct is the in-memory transport: every thread of the cluster runs in this process and threads
+// are connected by tokio channels.
+#[derive(Debug, Clone, Default)]
+pub struct ChannelTransport;
+
+impl ChannelTransport {
+    pub fn new() -> Self {
+        Self
+    }
+
+    // # Function Description:
+    // This function creates one bounded channel per thread of the cluster.
+    //
+    // # Parameters:
+    // * config - The configuration of the cluster.
+    //
+    // # Returns:
+    // * The transmitter and receiver of every thread.
+    pub fn create_channels(config: &ClusterConfig) -> TransportChannels {
+        let (transmitters, receivers) = (0..config.get_thread_count())
+            .map(|_| mpsc::channel(config.get_buffer_size()))
+            .unzip();
+        TransportChannels {
+            transmitters,
+            receivers
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ChannelTransport {
+    fn get_local_ids(&self, config: &ClusterConfig) -> Vec<u32> {
+        (0..config.get_thread_count()).collect()
+    }
+
+    async fn open(&mut self, config: &ClusterConfig) -> Result<TransportChannels, String> {
+        Ok(Self::create_channels(config))
+    }
+}
+
+// # Struct Description:
+// This struct is a transport over TCP, hosting a single thread of the cluster in this process, so that the
+// threads of a cluster can run in separate processes on different machines. Every thread listens on its
+// own address. Frames to another thread are written to a TCP connection to its address, opened on the first
+// frame and retried until the peer listens, so frames sent before a peer started wait for it instead of being
+// lost. A connection that drops is reopened the same way, following the transport's `ReconnectPolicy`; the
+// frames that were not flushed when it dropped are written again, so a peer may receive them twice. A
+// connection starts with the ID of the sending thread and its `Capabilities`, which the receiving thread answers
+// with its own; every frame is prefixed with its length. With a membership (see `ClusterConfig::with_membership`),
+// connections announcing a non-member, or coming from another host than the member they announce, are closed
+// before any frame is read, and so are connections whose capabilities are refused (see `Capabilities::negotiate`).
+// Behind a startup barrier (see `with_startup_barrier`), the transport opens only once every peer acknowledged
+// a connection, or fails with the peers that did not.
+//
+// # Fields:
+// * local_id - The ID of the thread hosted by this process.
+// * listener - The listener accepting the connections of the other threads, until the transport is opened.
+// * local_address - The address the listener is bound to.
+// * address_book - The address of every thread of the cluster.
+// * reconnect_policy - How connections to the peers are retried.
+// * protocols - The protocols the thread announces to its peers.
+// * connection_events - The channel on which `ConnectionEvent`s are published.
+// * startup_timeout - How long opening the transport waits for every peer to acknowledge a connection, if
+//   it waits (see `with_startup_barrier`).
+#[derive(Debug)]
+pub struct TcpTransport {
+    local_id: u32,
+    listener: Option<TcpListener>,
+    local_address: SocketAddr,
+    address_book: PeerAddressBook,
+    reconnect_policy: ReconnectPolicy,
+    protocols: BTreeSet<Protocol>,
+    connection_events: broadcast::Sender<ConnectionEvent>,
+    startup_timeout: Option<Duration>,
+}
+
+impl TcpTransport {
+    // # Function Description:
+    // This function binds the listener of the thread hosted by this process.
+    //
+    // # Parameters:
+    // * local_id - The ID of the thread hosted by this process.
+    // * address - The address to listen on; port 0 binds an ephemeral port (see `get_local_address`).
+    //
+    // # Returns:
+    // * The transport, or a description of why the address could not be bound.
+    pub async fn bind(local_id: u32, address: SocketAddr) -> Result<Self, String> {
+        let listener = TcpListener::bind(address).await.map_err(|error| format!("could not listen on {address}: {error}"))?;
+        let local_address = listener.local_addr().map_err(|error| error.to_string())?;
+        Ok(Self {
+            local_id,
+            listener: Some(listener),
+            local_address,
+            address_book: PeerAddressBook::default(),
+            reconnect_policy: ReconnectPolicy::default(),
+            protocols: Protocol::ALL.into(),
+            connection_events: broadcast::channel(CONNECTION_EVENT_BUFFER_SIZE).0,
+            startup_timeout: None,
+        })
+    }
+
+    // # Method Description:
+    // This method sets the address every thread of the cluster listens on.
+    //
+    // # Parameters:
+    // * peer_addresses - The address of every thread, indexed by thread ID, including this one.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_peer_addresses(mut self, peer_addresses: Vec<SocketAddr>) -> Self {
+        self.address_book = PeerAddressBook::new(peer_addresses);
+        self
+    }
+
+    // # Method Description:
+    // This method sets how connections to the peers are retried.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
+    }
+
+    // # Method Description:
+    // This method sets the protocols the thread announces to its peers, every protocol by default. The codec
+    // and authentication it announces are those of the configuration the transport is opened with.
+    //
+    // # Parameters:
+    // * protocols - The protocols run by the communicators of the thread.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_protocols(mut self, protocols: &[Protocol]) -> Self {
+        self.protocols = protocols.iter().copied().collect();
+        self
+    }
+
+    // # Method Description:
+    // This method makes opening the transport a startup barrier: the thread connects to every peer right away
+    // instead of on the first frame sent to it, and `open` only returns once every peer acknowledged the
+    // connection by answering the handshake, so that no frame is sent before a peer listens.
+    //
+    // # Parameters:
+    // * timeout - How long `open` waits for the peers before failing with the list of the missing ones.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_startup_barrier(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = Some(timeout);
+        self
+    }
+
+    pub fn get_local_address(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    pub fn get_address_book(&self) -> &PeerAddressBook {
+        &self.address_book
+    }
+
+    // # Method Description:
+    // This method subscribes to the changes in the connections of the local thread to its peers. Only
+    // changes that happen after the subscription are received.
+    //
+    // # Returns:
+    // * A `broadcast::Receiver` of `ConnectionEvent`s.
+    pub fn subscribe_connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection_events.subscribe()
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    fn get_local_ids(&self, _config: &ClusterConfig) -> Vec<u32> {
+        vec![self.local_id]
+    }
+
+    async fn open(&mut self, config: &ClusterConfig) -> Result<TransportChannels, String> {
+        let thread_count = config.get_thread_count();
+        if self.address_book.len() != thread_count as usize {
+            return Err(format!("{} peer addresses given for {} threads", self.address_book.len(), thread_count))
+        }
+        if self.local_id >= thread_count {
+            return Err(format!("thread {} is not part of a cluster of {} threads", self.local_id, thread_count))
+        }
+        let listener = match self.listener.take() {
+            Some(listener) => listener,
+            None => return Err(String::from("the transport is already open")),
+        };
+
+        let capabilities = Capabilities::new(self.protocols.clone(), config.get_codec(), config.get_authentication());
+        let admission = Admission::new(self.local_id, config.get_membership().cloned(), capabilities.clone(), self.connection_events.clone());
+        let (local_tx, local_rx) = mpsc::channel(config.get_buffer_size());
+        tokio::spawn(accept_connections(listener, local_tx.clone(), admission));
+        let startup_events = self.connection_events.subscribe();
+
+        let mut transmitters = vec![];
+        let mut receivers = vec![];
+        let mut local_rx = Some(local_rx);
+        for id in 0..thread_count {
+            if id == self.local_id {
+                transmitters.push(local_tx.clone());
+                receivers.push(local_rx.take().unwrap());
+            } else {
+                let (tx, rx) = mpsc::channel(config.get_buffer_size());
+                let link = PeerLink::new(self.local_id, id, self.address_book.clone(), self.reconnect_policy, capabilities.clone(), self.connection_events.clone(), rx)
+                    .with_eager_connect(self.startup_timeout.is_some());
+                tokio::spawn(write_connection(link));
+                transmitters.push(tx);
+                receivers.push(mpsc::channel(1).1);
+            }
+        }
+        if let Some(timeout) = self.startup_timeout {
+            await_startup_barrier(self.local_id, thread_count, startup_events, timeout).await?;
+        }
+
+        Ok(TransportChannels {
+            transmitters,
+            receivers
+        })
+    }
+}
+
+// # Function Description:
+// This function waits until the local thread connected to every peer and every peer acknowledged its
+// connection, i.e. answered the handshake with capabilities the local thread does not refuse.
+//
+// # Parameters:
+// * local_id - The ID of the local thread.
+// * thread_count - The number of threads of the cluster.
+// * events - The `ConnectionEvent`s of the transport, subscribed to before its links were spawned.
+// * timeout - How long to wait for the peers.
+//
+// # Returns:
+// * Nothing, or a description of the barrier failing, listing the peers that did not acknowledge a
+//   connection in time, or the peer whose capabilities were refused.
+pub(crate) async fn await_startup_barrier(local_id: u32, thread_count: u32, mut events: broadcast::Receiver<ConnectionEvent>, timeout: Duration) -> Result<(), String> {
+    let mut missing: BTreeSet<u32> = (0..thread_count).filter(|id| *id != local_id).collect();
+    let wait = async {
+        while !missing.is_empty() {
+            match events.recv().await {
+                Ok(ConnectionEvent::Connected(id, peer, _, _)) if id == local_id => {
+                    missing.remove(&peer);
+                },
+                Ok(ConnectionEvent::CapabilityMismatch(mismatch)) if mismatch.get_id() == local_id && mismatch.is_refused() => {
+                    return Err(format!("startup barrier of node {local_id} failed: node {} was refused: {}", mismatch.get_peer(), mismatch.get_reason()))
+                },
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {},
+                Err(broadcast::error::RecvError::Closed) => return Err(format!("startup barrier of node {local_id} failed: the transport closed")),
+            }
+        }
+        Ok(())
+    };
+    match tokio::time::timeout(timeout, wait).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("startup barrier of node {local_id} timed out after {timeout:?}, missing the acknowledgement of nodes {:?}", missing)),
+    }
+}
+
+// # Function Description:
+// This function accepts the connections of the other threads and forwards the frames received on each
+// of them to the local thread.
+//
+// # Parameters:
+// * listener - The listener of the local thread.
+// * local_tx - The transmitter to the local thread.
+// * admission - What the connections are checked against.
+async fn accept_connections(listener: TcpListener, local_tx: Sender<Bytes>, admission: Admission) {
+    while let Ok((stream, remote)) = listener.accept().await {
+        let _ = stream.set_nodelay(true);
+        tokio::spawn(read_connection(stream, remote, local_tx.clone(), admission.clone()));
+    }
+}
+
+// # Function Description:
+// This function answers the handshake of one connection, then reads its frames until it closes or sends a
+// malformed frame.
+//
+// # Parameters:
+// * stream - The connection.
+// * remote - The address the connection comes from.
+// * local_tx - The transmitter to the local thread.
+// * admission - What the connection is checked against.
+async fn read_connection(stream: TcpStream, remote: SocketAddr, local_tx: Sender<Bytes>, admission: Admission) {
+    let mut stream = BufReader::new(stream);
+    let Ok(id) = stream.read_u32().await else {
+        return
+    };
+    if admission.get_membership().is_some_and(|membership| !membership.admits(Some(id), remote)) {
+        log!(warn, { node = admission.local_id, peer = id }, "refused the connection of {remote}, announcing node {id}, which is not a member");
+        return
+    }
+    let Ok(capabilities) = exchange_capabilities(&mut stream, admission.get_capabilities()).await else {
+        return
+    };
+    if !admission.negotiate(id, &capabilities) {
+        return
+    }
+    while let Ok(size) = stream.read_u32().await {
+        if size > MAX_FRAME_SIZE {
+            return
+        }
+        let mut frame = vec![0; size as usize];
+        if stream.read_exact(&mut frame).await.is_err() {
+            return
+        }
+        // frames are forwarded as is, attributed to the peer: a frame that cannot be decoded is reported by the receiving handle
+        if local_tx.send(attribute_frame(id, Bytes::from(frame))).await.is_err() {
+            return
+        }
+    }
+}
+
+// # Function Description:
+// This function writes every frame sent to a peer over TCP, connecting to the peer on the first frame (or
+// right away behind a startup barrier) and reconnecting whenever the connection drops. It stops when every transmitter to the peer is dropped.
+//
+// # Parameters:
+// * link - The link to the peer.
+async fn write_connection(mut link: PeerLink) {
+    if !link.wait_first_frame().await {
+        return
+    }
+    let capabilities = link.get_capabilities().clone();
+    loop {
+        let local_id = link.local_id;
+        let Some((stream, remote)) = link.connect(|address| connect_tcp(local_id, &capabilities, address)).await else { return };
+        if !link.negotiate(&remote) {
+            return
+        }
+        match write_frames(&mut link, stream).await {
+            Ok(()) => return,
+            Err(error) => link.disconnected(error),
+        }
+    }
+}
+
+// # Function Description:
+// This function opens a connection to a peer, announces the ID of the local thread on it, and exchanges
+// capabilities with the peer.
+//
+// # Parameters:
+// * local_id - The ID of the local thread.
+// * capabilities - The capabilities of the local thread.
+// * address - The address of the peer.
+//
+// # Returns:
+// * The buffered connection and the capabilities of the peer, or a description of why it could not be opened.
+async fn connect_tcp(local_id: u32, capabilities: &Capabilities, address: SocketAddr) -> Result<(BufWriter<TcpStream>, Capabilities), String> {
+    let stream = TcpStream::connect(address).await.map_err(|error| error.to_string())?;
+    let _ = stream.set_nodelay(true);
+    let mut stream = BufWriter::new(stream);
+    stream.write_u32(local_id).await.map_err(|error| error.to_string())?;
+    let remote = exchange_capabilities(&mut stream, capabilities).await?;
+    Ok((stream, remote))
+}
+
+// # Function Description:
+// This function sends the capabilities of the local thread on a connection, and reads those of the peer.
+// Capabilities are always written as length-prefixed JSON, whatever the codec of the frames.
+//
+// # Parameters:
+// * stream - The connection.
+// * capabilities - The capabilities of the local thread.
+//
+// # Returns:
+// * The capabilities of the peer, or a description of why they could not be exchanged.
+pub(crate) async fn exchange_capabilities<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S, capabilities: &Capabilities) -> Result<Capabilities, String> {
+    let json = capabilities.write_json();
+    stream.write_u32(json.len() as u32).await.map_err(|error| error.to_string())?;
+    stream.write_all(json.as_bytes()).await.map_err(|error| error.to_string())?;
+    stream.flush().await.map_err(|error| error.to_string())?;
+    let read = async {
+        let size = stream.read_u32().await.map_err(|error| error.to_string())?;
+        if size > MAX_FRAME_SIZE {
+            return Err(format!("capabilities of {size} bytes"))
+        }
+        let mut json = vec![0; size as usize];
+        stream.read_exact(&mut json).await.map_err(|error| error.to_string())?;
+        Capabilities::read_json(&String::from_utf8_lossy(&json).into_owned()).map_err(|error| error.to_string())
+    };
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, read).await.map_err(|_| String::from("the peer did not send its capabilities"))?
+}
+
+// # Function Description:
+// This function negotiates the capabilities of the local thread with those of a peer, announcing and
+// publishing any mismatch as `ConnectionEvent::CapabilityMismatch`.
+//
+// # Parameters:
+// * local_id - The ID of the local thread.
+// * peer_id - The ID of the peer.
+// * local - The capabilities of the local thread.
+// * remote - The capabilities of the peer.
+// * events - The channel on which `ConnectionEvent`s are published.
+//
+// # Returns:
+// * `false` if the peer is refused, and its connection must be closed.
+fn negotiate_capabilities(local_id: u32, peer_id: u32, local: &Capabilities, remote: &Capabilities, events: &broadcast::Sender<ConnectionEvent>) -> bool {
+    let (reason, refused) = match local.negotiate(remote) {
+        Negotiation::Agreed(_) => return true,
+        Negotiation::Degraded(agreed, reason) => (format!("{reason}, carrying on with {:?}", agreed.get_protocols()), false),
+        Negotiation::Refused(reason) => (reason, true),
+    };
+    log!(warn, { node = local_id, peer = peer_id, refused }, "id: {local_id}, {} the connection with {peer_id}: {reason}", if refused { "refused" } else { "degraded" });
+    let _ = events.send(ConnectionEvent::CapabilityMismatch(CapabilityMismatch::new(local_id, peer_id, reason, refused)));
+    !refused
+}
+
+// # Struct Description:
+// This struct holds what the accepting side of a network transport checks the connections of its peers
+// against.
+//
+// # Fields:
+// * local_id - The ID of the local thread.
+// * membership - The membership of the cluster, whose members only are admitted, if any.
+// * capabilities - The capabilities of the local thread, which peers must be compatible with.
+// * events - The channel on which `ConnectionEvent`s are published.
+#[derive(Debug, Clone)]
+pub(crate) struct Admission {
+    local_id: u32,
+    membership: Option<MembershipCertificate>,
+    capabilities: Capabilities,
+    events: broadcast::Sender<ConnectionEvent>,
+}
+
+impl Admission {
+    pub(crate) fn new(local_id: u32, membership: Option<MembershipCertificate>, capabilities: Capabilities, events: broadcast::Sender<ConnectionEvent>) -> Self {
+        Self {
+            local_id,
+            membership,
+            capabilities,
+            events
+        }
+    }
+
+    pub(crate) fn get_membership(&self) -> Option<&MembershipCertificate> {
+        self.membership.as_ref()
+    }
+
+    pub(crate) fn get_capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    // # Method Description:
+    // This method negotiates the capabilities of the local thread with those a peer announced on its connection.
+    //
+    // # Parameters:
+    // * peer_id - The ID of the peer.
+    // * remote - The capabilities of the peer.
+    //
+    // # Returns:
+    // * `false` if the peer is refused.
+    pub(crate) fn negotiate(&self, peer_id: u32, remote: &Capabilities) -> bool {
+        negotiate_capabilities(self.local_id, peer_id, &self.capabilities, remote, &self.events)
+    }
+}
+
+// # Function Description:
+// This function writes the frames of a link on a connection, flushing whenever no frame is pending. When
+// the connection fails, the frames written since the last flush are requeued on the link.
+//
+// # Parameters:
+// * link - The link to the peer.
+// * stream - The connection to the peer.
+//
+// # Returns:
+// * `Ok(())` once every transmitter to the peer is dropped, or the error that broke the connection.
+async fn write_frames(link: &mut PeerLink, mut stream: BufWriter<TcpStream>) -> Result<(), String> {
+    let mut unflushed = vec![];
+    let mut next = link.next_frame().await;
+    while let Some(frame) = next {
+        let written = stream.write_u32(frame.len() as u32).await.and(stream.write_all(&frame).await);
+        unflushed.push(frame);
+        if let Err(error) = written {
+            link.requeue(unflushed);
+            return Err(error.to_string())
+        }
+        next = match link.try_next_frame() {
+            Some(frame) => Some(frame),
+            None => {
+                if let Err(error) = stream.flush().await {
+                    link.requeue(unflushed);
+                    return Err(error.to_string())
+                }
+                unflushed.clear();
+                link.next_frame().await
+            },
+        };
+    }
+    Ok(())
+}
+
+// # Struct Description:
+// This struct holds the outbound side of the connection of the local thread to a peer, shared by every
+// network transport: the frames sent to the peer, those buffered while it is unreachable, and how it is
+// reconnected.
+//
+// # Fields:
+// * local_id - The ID of the local thread.
+// * peer_id - The ID of the peer.
+// * address_book - The address of every thread of the cluster.
+// * policy - How connections to the peer are retried.
+// * capabilities - The capabilities the local thread announces to the peer.
+// * events - The channel on which `ConnectionEvent`s are published.
+// * frames - The receiver of the frames sent to the peer.
+// * pending - The frames to write before the next frame of `frames`.
+// * dropped - The number of frames dropped since the last connection opened.
+// * connected - Whether a connection to the peer opened before.
+// * eager - Whether the peer is connected to before the first frame is sent to it.
+pub(crate) struct PeerLink {
+    local_id: u32,
+    peer_id: u32,
+    address_book: PeerAddressBook,
+    policy: ReconnectPolicy,
+    capabilities: Capabilities,
+    events: broadcast::Sender<ConnectionEvent>,
+    frames: Receiver<Bytes>,
+    pending: VecDeque<Bytes>,
+    dropped: usize,
+    connected: bool,
+    eager: bool,
+}
+
+impl PeerLink {
+    pub(crate) fn new(local_id: u32, peer_id: u32, address_book: PeerAddressBook, policy: ReconnectPolicy, capabilities: Capabilities, events: broadcast::Sender<ConnectionEvent>, frames: Receiver<Bytes>) -> Self {
+        Self {
+            local_id,
+            peer_id,
+            address_book,
+            policy,
+            capabilities,
+            events,
+            frames,
+            pending: VecDeque::new(),
+            dropped: 0,
+            connected: false,
+            eager: false,
+        }
+    }
+
+    pub(crate) fn with_eager_connect(mut self, eager: bool) -> Self {
+        self.eager = eager;
+        self
+    }
+
+    // # Method Description:
+    // This method waits for the first frame sent to the peer, unless the link connects eagerly.
+    //
+    // # Returns:
+    // * `false` if every transmitter to the peer was dropped first.
+    pub(crate) async fn wait_first_frame(&mut self) -> bool {
+        if self.eager {
+            return true
+        }
+        match self.frames.recv().await {
+            Some(frame) => {
+                self.requeue(vec![frame]);
+                true
+            },
+            None => false,
+        }
+    }
+
+    // # Method Description:
+    // This method connects to the peer, at its current address in the address book, until an attempt
+    // succeeds. Attempts are spaced following the policy, and the frames sent to the peer meanwhile are
+    // buffered up to its limit.
+    //
+    // # Parameters:
+    // * connect - The function making one attempt to connect to an address.
+    //
+    // # Returns:
+    // * The connection, or `None` if every transmitter to the peer was dropped first.
+    pub(crate) async fn connect<S, F, Fut>(&mut self, mut connect: F) -> Option<S>
+    where
+        F: FnMut(SocketAddr) -> Fut,
+        Fut: Future<Output = Result<S, String>>,
+    {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            if let Some(address) = self.address_book.get_address(self.peer_id)
+                && let Ok(stream) = self.buffer_until(connect(address)).await? {
+                let event = match self.connected {
+                    false => ConnectionEvent::Connected(self.local_id, self.peer_id, attempts, self.dropped),
+                    true => ConnectionEvent::Reconnected(self.local_id, self.peer_id, attempts, self.dropped),
+                };
+                let _ = self.events.send(event);
+                self.connected = true;
+                self.dropped = 0;
+                return Some(stream)
+            }
+            self.buffer_until(tokio::time::sleep(self.policy.get_backoff(attempts))).await?;
+        }
+    }
+
+    pub(crate) fn get_capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    // # Method Description:
+    // This method negotiates the capabilities of the local thread with those the peer answered on a new connection.
+    //
+    // # Parameters:
+    // * remote - The capabilities of the peer.
+    //
+    // # Returns:
+    // * `false` if the peer is refused: the link must stop, and the frames sent to the peer are dropped.
+    pub(crate) fn negotiate(&self, remote: &Capabilities) -> bool {
+        negotiate_capabilities(self.local_id, self.peer_id, &self.capabilities, remote, &self.events)
+    }
+
+    // # Method Description:
+    // This method records that the connection to the peer dropped.
+    //
+    // # Parameters:
+    // * error - The error that broke the connection.
+    pub(crate) fn disconnected(&mut self, error: String) {
+        log!(warn, { node = self.local_id, peer = self.peer_id }, "id: {}, connection to {} dropped: {error}", self.local_id, self.peer_id);
+        let _ = self.events.send(ConnectionEvent::Disconnected(self.local_id, self.peer_id, error));
+    }
+
+    // # Method Description:
+    // This method waits for a future while buffering the frames sent to the peer, up to the limit of the policy.
+    //
+    // # Returns:
+    // * The output of the future, or `None` if every transmitter to the peer was dropped first.
+    async fn buffer_until<F: Future>(&mut self, future: F) -> Option<F::Output> {
+        tokio::pin!(future);
+        loop {
+            tokio::select! {
+                output = &mut future => return Some(output),
+                frame = self.frames.recv() => {
+                    let frame = frame?;
+                    if self.pending.len() < self.policy.get_buffer_limit() {
+                        self.pending.push_back(frame);
+                    } else {
+                        self.dropped += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method puts frames that could not be written back in front of the frames to write.
+    //
+    // # Parameters:
+    // * frames - The frames, in the order they must be written.
+    pub(crate) fn requeue(&mut self, frames: Vec<Bytes>) {
+        for frame in frames.into_iter().rev() {
+            self.pending.push_front(frame);
+        }
+    }
+
+    // # Method Description:
+    // This method waits for the next frame to write.
+    //
+    // # Returns:
+    // * The frame, or `None` once every transmitter to the peer is dropped.
+    pub(crate) async fn next_frame(&mut self) -> Option<Bytes> {
+        match self.pending.pop_front() {
+            Some(frame) => Some(frame),
+            None => self.frames.recv().await,
+        }
+    }
+
+    // # Method Description:
+    // This method returns the next frame to write, if one is already pending.
+    pub(crate) fn try_next_frame(&mut self) -> Option<Bytes> {
+        self.pending.pop_front().or_else(|| self.frames.try_recv().ok())
+    }
+}
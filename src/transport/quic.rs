@@ -0,0 +1,426 @@
+use std::{collections::BTreeSet, net::SocketAddr, sync::Arc, time::Duration};
+use bytes::Bytes;
+use async_trait::async_trait;
+use quinn::{ClientConfig, Connection, Endpoint, RecvStream, SendStream, ServerConfig, TransportConfig};
+use quinn::crypto::rustls::QuicClientConfig;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, join};
+use tokio::sync::{broadcast, mpsc::{self, Sender}};
+
+use crate::capabilities::{Capabilities, Protocol};
+use crate::config::ClusterConfig;
+use crate::multiplex::{Lane, attribute_frame, untag_frame};
+use crate::logging::log;
+use super::{Transport, TransportChannels, ConnectionEvent, PeerAddressBook, PeerLink, ReconnectPolicy, Admission, await_startup_barrier, exchange_capabilities, CONNECTION_EVENT_BUFFER_SIZE, MAX_FRAME_SIZE};
+
+// The server name every thread presents in its self-signed certificate.
+const SERVER_NAME: &str = "reliable-broadcast";
+
+// The idle timeout of a connection, which also bounds an attempt to connect to a peer that is not listening yet.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The interval between two keep-alive packets on an idle connection.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(1);
+
+// # Struct Description:
+// This struct is a transport over QUIC, hosting a single thread of the cluster in this process, like
+// `TcpTransport`. Frames to another thread travel on one connection per peer, on a separate stream per lane
+// (basic messages, signals, and reports), so that a lost packet only delays the frames of its own lane
+// instead of every frame behind it. Connections that drop are reopened following the transport's
+// `ReconnectPolicy`, like those of `TcpTransport`; the frame whose write failed is written again, but frames
+// the connection accepted before it dropped may be lost. Every thread presents a self-signed certificate
+// that peers accept without verification: the transport is meant for experiments, and authenticates no one.
+// With a membership (see `ClusterConfig::with_membership`), connections that do not come from the address of
+// a member are refused. Every connection starts with a stream on which the connecting thread announces its ID
+// and `Capabilities`, and the accepting thread answers with its own; connections whose capabilities are
+// refused (see `Capabilities::negotiate`) are closed before any frame is read.
+//
+// # Fields:
+// * local_id - The ID of the thread hosted by this process.
+// * endpoint - The QUIC endpoint of the thread, accepting connections and connecting to peers.
+// * address_book - The address of every thread of the cluster.
+// * reconnect_policy - How connections to the peers are retried.
+// * protocols - The protocols the thread announces to its peers.
+// * connection_events - The channel on which `ConnectionEvent`s are published.
+// * startup_timeout - How long opening the transport waits for every peer to acknowledge a connection, if
+//   it waits (see `with_startup_barrier`).
+// * open - Whether the transport has been opened already.
+#[derive(Debug)]
+pub struct QuicTransport {
+    local_id: u32,
+    endpoint: Endpoint,
+    address_book: PeerAddressBook,
+    reconnect_policy: ReconnectPolicy,
+    protocols: BTreeSet<Protocol>,
+    connection_events: broadcast::Sender<ConnectionEvent>,
+    startup_timeout: Option<Duration>,
+    open: bool,
+}
+
+impl QuicTransport {
+    // # Function Description:
+    // This function binds the endpoint of the thread hosted by this process.
+    //
+    // # Parameters:
+    // * local_id - The ID of the thread hosted by this process.
+    // * address - The UDP address to listen on; port 0 binds an ephemeral port (see `get_local_address`).
+    //
+    // # Returns:
+    // * The transport, or a description of why the endpoint could not be created.
+    pub fn bind(local_id: u32, address: SocketAddr) -> Result<Self, String> {
+        let certified_key = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()]).map_err(|error| error.to_string())?;
+        let certificate = CertificateDer::from(certified_key.cert);
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified_key.key_pair.serialize_der()));
+        let transport_config = Arc::new(create_transport_config());
+
+        let mut server_config = ServerConfig::with_single_cert(vec![certificate], key).map_err(|error| error.to_string())?;
+        server_config.transport_config(transport_config.clone());
+        let provider = Arc::new(ring::default_provider());
+        let client_crypto = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .map_err(|error| error.to_string())?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+            .with_no_client_auth();
+        let mut client_config = ClientConfig::new(Arc::new(QuicClientConfig::try_from(client_crypto).map_err(|error| error.to_string())?));
+        client_config.transport_config(transport_config);
+
+        let mut endpoint = Endpoint::server(server_config, address).map_err(|error| format!("could not listen on {address}: {error}"))?;
+        endpoint.set_default_client_config(client_config);
+        Ok(Self {
+            local_id,
+            endpoint,
+            address_book: PeerAddressBook::default(),
+            reconnect_policy: ReconnectPolicy::default(),
+            protocols: Protocol::ALL.into(),
+            connection_events: broadcast::channel(CONNECTION_EVENT_BUFFER_SIZE).0,
+            startup_timeout: None,
+            open: false,
+        })
+    }
+
+    // # Method Description:
+    // This method sets the address every thread of the cluster listens on.
+    //
+    // # Parameters:
+    // * peer_addresses - The address of every thread, indexed by thread ID, including this one.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_peer_addresses(mut self, peer_addresses: Vec<SocketAddr>) -> Self {
+        self.address_book = PeerAddressBook::new(peer_addresses);
+        self
+    }
+
+    // # Method Description:
+    // This method sets how connections to the peers are retried.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
+    }
+
+    // # Method Description:
+    // This method sets the protocols the thread announces to its peers, every protocol by default. The codec
+    // and authentication it announces are those of the configuration the transport is opened with.
+    //
+    // # Parameters:
+    // * protocols - The protocols run by the communicators of the thread.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_protocols(mut self, protocols: &[Protocol]) -> Self {
+        self.protocols = protocols.iter().copied().collect();
+        self
+    }
+
+    // # Method Description:
+    // This method makes opening the transport a startup barrier, like `TcpTransport::with_startup_barrier`:
+    // `open` only returns once every peer answered the handshake of a connection.
+    //
+    // # Parameters:
+    // * timeout - How long `open` waits for the peers before failing with the list of the missing ones.
+    //
+    // # Returns:
+    // * The updated transport.
+    pub fn with_startup_barrier(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = Some(timeout);
+        self
+    }
+
+    pub fn get_local_address(&self) -> Result<SocketAddr, String> {
+        self.endpoint.local_addr().map_err(|error| error.to_string())
+    }
+
+    pub fn get_address_book(&self) -> &PeerAddressBook {
+        &self.address_book
+    }
+
+    // # Method Description:
+    // This method subscribes to the changes in the connections of the local thread to its peers. Only
+    // changes that happen after the subscription are received.
+    //
+    // # Returns:
+    // * A `broadcast::Receiver` of `ConnectionEvent`s.
+    pub fn subscribe_connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection_events.subscribe()
+    }
+}
+
+#[async_trait]
+impl Transport for QuicTransport {
+    fn get_local_ids(&self, _config: &ClusterConfig) -> Vec<u32> {
+        vec![self.local_id]
+    }
+
+    async fn open(&mut self, config: &ClusterConfig) -> Result<TransportChannels, String> {
+        let thread_count = config.get_thread_count();
+        if self.address_book.len() != thread_count as usize {
+            return Err(format!("{} peer addresses given for {} threads", self.address_book.len(), thread_count))
+        }
+        if self.local_id >= thread_count {
+            return Err(format!("thread {} is not part of a cluster of {} threads", self.local_id, thread_count))
+        }
+        if self.open {
+            return Err(String::from("the transport is already open"))
+        }
+        self.open = true;
+
+        let capabilities = Capabilities::new(self.protocols.clone(), config.get_codec(), config.get_authentication());
+        let admission = Admission::new(self.local_id, config.get_membership().cloned(), capabilities.clone(), self.connection_events.clone());
+        let (local_tx, local_rx) = mpsc::channel(config.get_buffer_size());
+        tokio::spawn(accept_connections(self.endpoint.clone(), local_tx.clone(), admission));
+        let startup_events = self.connection_events.subscribe();
+
+        let mut transmitters = vec![];
+        let mut receivers = vec![];
+        let mut local_rx = Some(local_rx);
+        for id in 0..thread_count {
+            if id == self.local_id {
+                transmitters.push(local_tx.clone());
+                receivers.push(local_rx.take().unwrap());
+            } else {
+                let (tx, rx) = mpsc::channel(config.get_buffer_size());
+                let link = PeerLink::new(self.local_id, id, self.address_book.clone(), self.reconnect_policy, capabilities.clone(), self.connection_events.clone(), rx)
+                    .with_eager_connect(self.startup_timeout.is_some());
+                tokio::spawn(write_connection(self.endpoint.clone(), link));
+                transmitters.push(tx);
+                receivers.push(mpsc::channel(1).1);
+            }
+        }
+        if let Some(timeout) = self.startup_timeout {
+            await_startup_barrier(self.local_id, thread_count, startup_events, timeout).await?;
+        }
+
+        Ok(TransportChannels {
+            transmitters,
+            receivers
+        })
+    }
+}
+
+// # Function Description:
+// This function creates the transport configuration of every connection: connections to a peer that is not
+// listening yet fail after the idle timeout (and are retried), while established connections are kept alive.
+fn create_transport_config() -> TransportConfig {
+    let mut transport_config = TransportConfig::default();
+    transport_config.max_idle_timeout(IDLE_TIMEOUT.try_into().ok());
+    transport_config.keep_alive_interval(Some(KEEP_ALIVE_INTERVAL));
+    transport_config
+}
+
+// # Function Description:
+// This function accepts the connections of the other threads, answers their handshake, and forwards the frames
+// received on every stream of each of them to the local thread.
+//
+// # Parameters:
+// * endpoint - The endpoint of the local thread.
+// * local_tx - The transmitter to the local thread.
+// * admission - What the connections are checked against.
+async fn accept_connections(endpoint: Endpoint, local_tx: Sender<Bytes>, admission: Admission) {
+    while let Some(incoming) = endpoint.accept().await {
+        let remote = incoming.remote_address();
+        if admission.get_membership().is_some_and(|membership| !membership.admits(None, remote)) {
+            log!(warn, "refused the connection of {remote}, which is not the address of a member");
+            incoming.refuse();
+            continue
+        }
+        let local_tx = local_tx.clone();
+        let admission = admission.clone();
+        tokio::spawn(async move {
+            let Ok(connection) = incoming.await else { return };
+            let Ok((send, mut recv)) = connection.accept_bi().await else { return };
+            let Ok(id) = recv.read_u32().await else { return };
+            let mut handshake = join(recv, send);
+            let Ok(capabilities) = exchange_capabilities(&mut handshake, admission.get_capabilities()).await else { return };
+            if !admission.negotiate(id, &capabilities) {
+                connection.close(0u32.into(), b"capabilities refused");
+                return
+            }
+            let _ = handshake.writer_mut().finish();
+            while let Ok(stream) = connection.accept_uni().await {
+                tokio::spawn(read_stream(stream, id, local_tx.clone()));
+            }
+        });
+    }
+}
+
+// # Function Description:
+// This function reads the frames of one stream until it finishes or sends a malformed frame.
+//
+// # Parameters:
+// * stream - The stream.
+// * peer - The ID the peer announced in the handshake of the connection.
+// * local_tx - The transmitter to the local thread.
+async fn read_stream(mut stream: RecvStream, peer: u32, local_tx: Sender<Bytes>) {
+    let mut size = [0; 4];
+    while stream.read_exact(&mut size).await.is_ok() {
+        let size = u32::from_be_bytes(size);
+        if size > MAX_FRAME_SIZE {
+            return
+        }
+        let mut frame = vec![0; size as usize];
+        if stream.read_exact(&mut frame).await.is_err() {
+            return
+        }
+        // frames are forwarded as is, attributed to the peer: a frame that cannot be decoded is reported by the receiving handle
+        if local_tx.send(attribute_frame(peer, Bytes::from(frame))).await.is_err() {
+            return
+        }
+    }
+}
+
+// # Function Description:
+// This function connects to a peer on the first frame sent to it (or right away behind a startup barrier),
+// then writes every frame sent to it on the stream of the frame's lane, opening each stream on its first
+// frame, and reconnects whenever the connection drops. Frames that are not tagged with a lane travel on the
+// stream of the basic lane. It stops when every transmitter to the peer is dropped.
+//
+// # Parameters:
+// * endpoint - The endpoint of the local thread.
+// * link - The link to the peer.
+async fn write_connection(endpoint: Endpoint, mut link: PeerLink) {
+    if !link.wait_first_frame().await {
+        return
+    }
+    let capabilities = link.get_capabilities().clone();
+    loop {
+        let local_id = link.local_id;
+        let Some((connection, remote)) = link.connect(|address| connect_quic(&endpoint, local_id, &capabilities, address)).await else { return };
+        if !link.negotiate(&remote) {
+            connection.close(0u32.into(), b"capabilities refused");
+            return
+        }
+        match write_frames(&mut link, &connection).await {
+            Ok(streams) => {
+                for mut stream in streams.into_iter().flatten() {
+                    let _ = stream.finish();
+                }
+                return
+            },
+            Err(error) => link.disconnected(error),
+        }
+    }
+}
+
+// # Function Description:
+// This function opens a connection to a peer, announces the ID of the local thread on its handshake stream,
+// and exchanges capabilities with the peer.
+//
+// # Parameters:
+// * endpoint - The endpoint of the local thread.
+// * local_id - The ID of the local thread.
+// * capabilities - The capabilities of the local thread.
+// * address - The address of the peer.
+//
+// # Returns:
+// * The connection and the capabilities of the peer, or a description of why it could not be opened.
+async fn connect_quic(endpoint: &Endpoint, local_id: u32, capabilities: &Capabilities, address: SocketAddr) -> Result<(Connection, Capabilities), String> {
+    let connection = endpoint.connect(address, SERVER_NAME).map_err(|error| error.to_string())?.await.map_err(|error| error.to_string())?;
+    let (mut send, recv) = connection.open_bi().await.map_err(|error| error.to_string())?;
+    AsyncWriteExt::write_u32(&mut send, local_id).await.map_err(|error| error.to_string())?;
+    let mut handshake = join(recv, send);
+    let remote = exchange_capabilities(&mut handshake, capabilities).await?;
+    let _ = handshake.writer_mut().finish();
+    Ok((connection, remote))
+}
+
+// # Function Description:
+// This function writes the frames of a link on the streams of a connection. When the connection fails, the
+// frame whose write failed is requeued on the link.
+//
+// # Parameters:
+// * link - The link to the peer.
+// * connection - The connection to the peer.
+//
+// # Returns:
+// * The streams of the lanes once every transmitter to the peer is dropped, or the error that broke the connection.
+async fn write_frames(link: &mut PeerLink, connection: &Connection) -> Result<[Option<SendStream>; 3], String> {
+    let mut streams: [Option<SendStream>; 3] = [None, None, None];
+    while let Some(frame) = link.next_frame().await {
+        let index = match untag_frame(&frame) {
+            Some((Lane::Signal, _)) => 1,
+            Some((Lane::Report, _)) => 2,
+            _ => 0,
+        };
+        let written = match open_stream(connection, &mut streams[index]).await {
+            Ok(stream) => match stream.write_all(&(frame.len() as u32).to_be_bytes()).await {
+                Ok(()) => stream.write_all(&frame).await.map_err(|error| error.to_string()),
+                Err(error) => Err(error.to_string()),
+            },
+            Err(error) => Err(error),
+        };
+        if let Err(error) = written {
+            link.requeue(vec![frame]);
+            return Err(error)
+        }
+    }
+    Ok(streams)
+}
+
+// # Function Description:
+// This function returns the stream of a lane, opening it if needed.
+//
+// # Parameters:
+// * connection - The connection to the peer.
+// * stream - The stream of the lane, if it was opened already.
+//
+// # Returns:
+// * The stream, or a description of why it could not be opened.
+async fn open_stream<'a>(connection: &Connection, stream: &'a mut Option<SendStream>) -> Result<&'a mut SendStream, String> {
+    match stream {
+        Some(stream) => Ok(stream),
+        None => Ok(stream.insert(connection.open_uni().await.map_err(|error| error.to_string())?)),
+    }
+}
+
+// # Struct Description:
+// This struct accepts the certificate of any peer, checking only that its handshake signatures are valid.
+//
+// # Fields:
+// * 0 - The cryptographic provider checking the signatures.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(&self, _end_entity: &CertificateDer<'_>, _intermediates: &[CertificateDer<'_>], _server_name: &ServerName<'_>, _ocsp_response: &[u8], _now: UnixTime) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], certificate: &CertificateDer<'_>, signature: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, certificate, signature, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], certificate: &CertificateDer<'_>, signature: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, certificate, signature, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
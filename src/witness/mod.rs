@@ -1,13 +1,32 @@
 use core::panic;
-use std::{vec, fmt::Debug, hash::Hash, collections::{HashMap, HashSet}, marker::PhantomData};
+use std::{vec, fmt::Debug, hash::Hash, collections::{BTreeMap, BTreeSet, HashMap, HashSet}, marker::PhantomData, path::Path, time::Duration};
+use bytes::Bytes;
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
-use tokio::{task::JoinHandle, sync::mpsc::{self, Receiver, Sender}};
+use tokio::{task::JoinHandle, sync::{broadcast, oneshot, mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender}}};
+use futures::{future::join_all, stream::{self, Stream}};
+use tracing::Instrument;
 use async_trait::async_trait; 
 
-use crate::{barycentric_agreement::BarycentricReport,  basic::{BasicCommunication, BasicQueues, Message, MessageChannels, RecvObject}}; 
-use crate::reliable::{ReliableCommunication, Signal, SignalType, ChannelType, ObjectContent, SignalChannels, ReliableInstanceMonitor}; 
+use crate::delivered::Delivered;
+use crate::{barycentric_agreement::{BarycentricReport, BuddyCertificate},  basic::{BasicCommunication, BasicQueues, CollectError, Message, MessageChannels, RecvObject, validate_wiring}}; 
+use crate::reliable::{ReliableCommunication, Signal, SignalType, ChannelType, ObjectContent, SignalChannels, ReliableHandleCommand, DeliveryWatches, recovery_config}; 
 use crate::aggregated_witness::{AggregatedReport};
-use crate::json::{JsonConversion};
+use crate::json::{Codec, FrameKind, JsonConversion, WireFormat, frame_kinds};
+use crate::config::{ClusterConfig, Thresholds};
+use crate::multiplex::{Lane, LaneReceivers, tag_frame, open_lanes};
+use crate::drive::{HandleMode, PolledHandle, PolledHandles, ShutdownReport, ShutdownResponder, drive};
+use crate::events::{Event, DecodeFailureReporter};
+use crate::metrics::{FrameStatistics, Metrics, RoundStatus};
+use crate::health::RunningHandle;
+use crate::inspect::{Inspect, InspectResponder, WitnessRoundSnapshot};
+use crate::retention::RetentionTracker;
+use crate::trace::TraceSampler;
+use crate::capabilities::{Capabilities, Protocol};
+use crate::round::{Instance, Round};
+use crate::divergence::{DivergenceDetector, RoundDigests, combine_digests};
+use crate::logging::log;
+use crate::snapshot::{ClusterSnapshot, SnapshotTap};
+use crate::descriptor::{MessageTypeDescriptor, PhaseDescriptor, ProtocolDescriptor, Quorum};
 
 // # Trait Description:
 // This trait defines the behavior for threads participating in a witness-based reliable broadcast protocol.
@@ -23,34 +42,36 @@ where
     // into witnesses based on the contained messages matching the expected values.
     // # Parameters:
     // * thread_id - The ID of the current thread processing the reports.
+    // * thresholds - The thresholds of the cluster, giving the weight every witness counts for.
     // * count - A mutable reference to the `WitnessRoundCount` tracking the number of witnesses.
     // * content - A mutable reference to the `WitnessRoundContent` containing reports and witnesses.
-    fn update_witnesses(thread_id: u32, count: &mut WitnessRoundCount, content: &mut WitnessRoundContent<T>) {
+    fn update_witnesses(thread_id: u32, thresholds: &Thresholds, count: &mut WitnessRoundCount, content: &mut WitnessRoundContent<T>) {
         for report in &mut content.reports {
             if report.get_report_type() == &ReportType::Report {
-                Self::initialize_witnesses(thread_id, report, &mut content.witnesses, count, content.values.clone());
+                Self::initialize_witnesses(thread_id, thresholds, report, &mut content.witnesses, count, content.values.clone());
             }
         }
     }
 
     // # Function Description:
     // This function checks if a report’s messages are a subset of expected values and, if so, converts it
-    // into a witness, adding it to the list of witnesses and updating the count.
+    // into a witness, adding it to the list of witnesses and adding the weight of its sender to the count.
     // # Parameters:
     // * thread_id - The ID of the current thread processing the report.
+    // * thresholds - The thresholds of the cluster, giving the weight every witness counts for.
     // * report - A mutable reference to the `Report` to potentially convert into a witness.
     // * witnesses - A mutable vector of `Report`s representing collected witnesses.
     // * count - A mutable reference to the `WitnessRoundCount` to update witness count.
     // * values - A vector of `Message`s representing expected values for this round.
-    fn initialize_witnesses(thread_id: u32, report: &mut Report<T>, witnesses: &mut Vec<Report<T>>, count: &mut WitnessRoundCount, values: Vec<Message<T>>) {
+    fn initialize_witnesses(thread_id: u32, thresholds: &Thresholds, report: &mut Report<T>, witnesses: &mut Vec<Report<T>>, count: &mut WitnessRoundCount, values: Vec<Message<T>>) {
         let values_set: HashSet<Message<T>> = values.into_iter().collect();
         let report_set: HashSet<Message<T>> = report.get_messages().clone().into_iter().collect();
 
         if report_set.is_subset(&values_set) {
             report.report_type = ReportType::Witness;
             witnesses.push(report.clone());
-            println!("id: {thread_id}: converted report by id: {} to a witness", report.get_id());
-            count.witnesses += 1; 
+            log!(debug, "id: {thread_id}: converted report by id: {} to a witness", report.get_id());
+            count.witnesses += thresholds.get_weight(report.get_id()); 
         }       
     }
 
@@ -61,108 +82,892 @@ where
     // * round_number - The round number associated with this witness broadcast.
     // # Returns:
     // * A future that broadcasts the signal to all signal receivers.
-    fn witness_broadcast(&mut self, message: T, round_number: u32) -> impl Future<Output = ()> {
+    fn witness_broadcast(&mut self, message: T, round_number: Round) -> impl Future<Output = ()> {
         let protocol_information = String::from("witness");
-        let instance_number = 0; 
+        let instance_number = Instance(0);
         let sent_message = Message::new(protocol_information, *self.get_id(), message, None, Some(instance_number), round_number);
         let input = Signal::new(SignalType::Input, ObjectContent::Message(sent_message), instance_number, round_number);
         self.get_signal_channels().broadcast_signal(input)
     }
 
+    // # Method Description:
+    // This method starts a commit-reveal witness round by reliably broadcasting a `Commitment` to a value
+    // instead of the value itself. The value is revealed in the next round with `witness_reveal`, and witness
+    // handles only accept the reveal if it matches the commitment, so no thread can pick its value after
+    // seeing the values of the others. The digest is the FNV-1a digest of the reveal message: it binds the
+    // value to its sender and round, but does not hide values drawn from a small domain, which can be
+    // recovered by hashing every candidate.
+    // # Parameters:
+    // * message - The value to commit to.
+    // * round_number - The round number of the commitment; the value is revealed in `round_number + 1`.
+    // # Returns:
+    // * A future that broadcasts the commitment to all signal receivers.
+    fn witness_commit(&mut self, message: T, round_number: Round) -> impl Future<Output = ()> {
+        let protocol_information = String::from("witness commit");
+        let instance_number = Instance(0);
+        let reveal = Message::new(String::from("witness reveal"), *self.get_id(), message, None, Some(instance_number), round_number + 1);
+        let commitment = Commitment::new(protocol_information, *self.get_id(), reveal.get_digest(), round_number);
+        let input = Signal::new(SignalType::Input, ObjectContent::Commitment(commitment), instance_number, round_number);
+        self.get_signal_channels().broadcast_signal(input)
+    }
+
+    // # Method Description:
+    // This method reveals a value committed to with `witness_commit`, by broadcasting it as a witness value
+    // in the next round. The revealed values are collected with `witness_collect(round_number + 1)`.
+    // # Parameters:
+    // * message - The committed value.
+    // * round_number - The round number of the commitment.
+    // # Returns:
+    // * A future that broadcasts the reveal to all signal receivers.
+    fn witness_reveal(&mut self, message: T, round_number: Round) -> impl Future<Output = ()> {
+        let protocol_information = String::from("witness reveal");
+        let instance_number = Instance(0);
+        let reveal = Message::new(protocol_information, *self.get_id(), message, None, Some(instance_number), round_number + 1);
+        let input = Signal::new(SignalType::Input, ObjectContent::Message(reveal), instance_number, round_number + 1);
+        self.get_signal_channels().broadcast_signal(input)
+    }
+
     // # Method Description:
     // This method collects all witness reports for the given round by retrieving a collection from the local queue.
+    // Collecting a round again answers according to the configured `RepeatedCollectPolicy`.
+    // Panics if the round cannot be collected (see `try_witness_collect`).
     // # Parameters:
     // * round_number - The round number to collect witness reports.
     // # Returns:
-    // * A vector of `Message`s contained in the collected witness report.
-    async fn witness_collect(&mut self, round_number: u32) -> Vec<Message<T>>{
+    // * A vector of `Message`s contained in the collected witness report, in the configured `ValueOrdering`.
+    async fn witness_collect(&mut self, round_number: Round) -> Vec<Message<T>>{
+        match self.try_witness_collect(round_number).await {
+            Ok(collection) => collection,
+            Err(error) => panic!("Error: witness round {round_number} could not be collected: {error:?}"),
+        }
+    }
+
+    // # Method Description:
+    // This method collects the round like `witness_collect`, in the `Delivered` envelope shared by every protocol.
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // # Returns:
+    // * The `Delivered` collection of the round.
+    async fn witness_collect_delivered(&mut self, round_number: Round) -> Delivered<T> {
+        let messages = self.witness_collect(round_number).await;
+        Delivered::from_collection(*self.get_id(), String::from("witness"), round_number, messages)
+    }
+
+    // # Method Description:
+    // This method collects the round like `witness_collect`, but returns an error instead of panicking if
+    // the round was aborted by an undecodable frame, which only happens under `DecodePolicy::Strict`, or was
+    // already collected under `RepeatedCollectPolicy::AlreadyConsumed`.
+    //
+    // # Parameters:
+    // * round_number - The round number to collect.
+    //
+    // # Returns:
+    // * The collected `Message`s, or the `CollectError` preventing the collection.
+    async fn try_witness_collect(&mut self, round_number: Round) -> Result<Vec<Message<T>>, CollectError> {
         let protocol_information = String::from("witness");
         let thread_id = self.get_id().clone();
 
-        match self.get_queues().basic_recv(Some(thread_id), protocol_information, Some(0), round_number).await {
+        match self.get_queues().collect_round(thread_id, protocol_information, Some(Instance(0)), round_number).await? {
             RecvObject::Message(_) => {panic!("Error: retreived Message instead of Vec<Message>")},
             RecvObject::Collection(report) => {
-                println!("witness collected: {:?}", &report.get_messages());    
-                let collection = report.get_messages().clone();
-                return collection;
+                log!(info, "witness collected: {:?}", &report.get_messages());    
+                Ok(report.get_messages().clone())
             },
         }
     }
 
+    // # Method Description:
+    // This method collects the round like `try_witness_collect`, but stops waiting after a timeout, e.g. so
+    // that an application can retry the round or change views on top of it. A round delivered after the
+    // timeout can still be collected.
+    // # Parameters:
+    // * round_number - The round number to collect.
+    // * timeout - How long to wait for the round.
+    // # Returns:
+    // * The collected `Message`s, or the `CollectError` preventing the collection (`CollectError::Timeout` if
+    //   the round was not delivered in time).
+    async fn witness_collect_with_timeout(&mut self, round_number: Round, timeout: Duration) -> Result<Vec<Message<T>>, CollectError> {
+        match tokio::time::timeout(timeout, self.try_witness_collect(round_number)).await {
+            Ok(collection) => collection,
+            Err(_) => Err(CollectError::Timeout(String::from("witness"), round_number)),
+        }
+    }
+
+    // # Method Description:
+    // This method collects a window of consecutive witness rounds, for experiments that treat several rounds
+    // as one logical epoch. It waits until every round of the window was delivered, and panics if one of
+    // them cannot be collected (see `try_witness_reduce_window`).
+    // # Parameters:
+    // * start_round - The first round of the window.
+    // * len - The number of rounds in the window.
+    // # Returns:
+    // * The union of the `Message`s collected in the window, round by round, each round in the configured `ValueOrdering`.
+    async fn witness_collect_window(&mut self, start_round: Round, len: u32) -> Vec<Message<T>> {
+        self.witness_reduce_window(start_round, len, vec![], |mut union, value| {
+            if !union.contains(&value) {
+                union.push(value);
+            }
+            union
+        }).await
+    }
+
+    // # Method Description:
+    // This method reduces a window of consecutive witness rounds like `try_witness_reduce_window`, but panics
+    // if one of the rounds cannot be collected.
+    // # Parameters:
+    // * start_round - The first round of the window.
+    // * len - The number of rounds in the window.
+    // * init - The initial aggregate.
+    // * reducer - The function folding every collected `Message` into the aggregate.
+    // # Returns:
+    // * The aggregate of the window.
+    async fn witness_reduce_window<R, F>(&mut self, start_round: Round, len: u32, init: R, reducer: F) -> R
+    where
+        R: Send,
+        F: FnMut(R, Message<T>) -> R + Send,
+    {
+        match self.try_witness_reduce_window(start_round, len, init, reducer).await {
+            Ok(aggregate) => aggregate,
+            Err(error) => panic!("Error: witness window of {len} rounds from round {start_round} could not be collected: {error:?}"),
+        }
+    }
+
+    // # Method Description:
+    // This method collects every round of a window of consecutive witness rounds, in order, and folds the
+    // collected values into an aggregate. It only returns once every round of the window was delivered.
+    // Rounds collected before a round that cannot be collected stay consumed, and are answered again
+    // according to the configured `RepeatedCollectPolicy`.
+    // # Parameters:
+    // * start_round - The first round of the window.
+    // * len - The number of rounds in the window.
+    // * init - The initial aggregate.
+    // * reducer - The function folding every collected `Message` into the aggregate, round by round, each
+    //   round in the configured `ValueOrdering`.
+    // # Returns:
+    // * The aggregate of the window, or the `CollectError` of the first round that could not be collected.
+    async fn try_witness_reduce_window<R, F>(&mut self, start_round: Round, len: u32, init: R, mut reducer: F) -> Result<R, CollectError>
+    where
+        R: Send,
+        F: FnMut(R, Message<T>) -> R + Send,
+    {
+        let mut aggregate = init;
+        for round_number in (start_round.0..start_round.0.saturating_add(len)).map(Round) {
+            for value in self.try_witness_collect(round_number).await? {
+                aggregate = reducer(aggregate, value);
+            }
+        }
+        Ok(aggregate)
+    }
+
+    // # Method Description:
+    // This method subscribes to the outcome of a witness round. Unlike `witness_collect`, which consumes 
+    // the delivered report from the local queue, a subscription is served by the witness handle itself: 
+    // rounds that already completed resolve immediately from the handle's record of delivered rounds, 
+    // and pending rounds resolve once delivered, regardless of when the subscription is made. The
+    // subscription to a round collected under the configured `RetentionPolicy` is closed without values.
+    // # Parameters:
+    // * round_number - The round number to subscribe to.
+    // # Returns:
+    // * A `oneshot::Receiver` that yields the values delivered in the round.
+    fn witness_subscribe(&self, round_number: Round) -> oneshot::Receiver<Vec<Message<T>>> {
+        let protocol_information = String::from("witness");
+        let (responder, subscription) = oneshot::channel();
+        let command = WitnessHandleCommand::Subscribe(protocol_information, round_number, responder);
+        let _ = self.get_witness_command_channel().send(command);
+        subscription
+    }
+
+    // # Method Description:
+    // This method streams the outcome of every witness round from a first round on, in round order, like a
+    // subscription to each round in turn. The stream holds none of the communicator, so that an application
+    // can await its next item in a `select!` loop that also broadcasts with the communicator, instead of
+    // spawning a task per round. Deliveries are still queued for `witness_collect` as well. The stream ends
+    // when it reaches a round collected under the configured `RetentionPolicy`, or once the witness handle
+    // stopped; under `HandleMode::Polling`, it only progresses while the communicator is driven.
+    // # Parameters:
+    // * first_round - The first round to stream.
+    // # Returns:
+    // * A `Stream` yielding every round with the values delivered in it.
+    fn witness_deliveries(&self, first_round: Round) -> impl Stream<Item = (Round, Vec<Message<T>>)> + Send + 'static {
+        let command_channel = self.get_witness_command_channel().clone();
+        stream::unfold(first_round, move |round_number| {
+            let command_channel = command_channel.clone();
+            async move {
+                let (responder, subscription) = oneshot::channel();
+                command_channel.send(WitnessHandleCommand::Subscribe(String::from("witness"), round_number, responder)).ok()?;
+                let values = subscription.await.ok()?;
+                Some(((round_number, values), round_number.next()))
+            }
+        })
+    }
+
+    // # Method Description:
+    // This method queries the witness handle for a snapshot of a round it monitors. Like a subscription,
+    // the query is answered by the handle itself, once it has processed the frames received before it.
+    // # Parameters:
+    // * round_number - The round number to inspect.
+    // # Returns:
+    // * A `oneshot::Receiver` that yields the `WitnessRoundSnapshot` of the round, or `None` if the handle has not seen it.
+    fn witness_inspect(&self, round_number: Round) -> oneshot::Receiver<Option<WitnessRoundSnapshot>> {
+        let (responder, snapshot) = oneshot::channel();
+        let _ = self.get_witness_command_channel().send(WitnessHandleCommand::Inspect(round_number, responder));
+        snapshot
+    }
+
     // # Method Description:
     // This method terminates the asynchronous task responsible for handling witness messages.
     // # Parameters:
     // * witness_handle - The `JoinHandle<()>` representing the spawned witness task to terminate.
     fn terminate_witness_handle(&self, witness_handle: JoinHandle<()>) {
-        println!("id: {}, terminating witness_handle...", self.get_id());
+        log!(debug, { node = *self.get_id() }, "id: {}, terminating witness_handle...", self.get_id());
         witness_handle.abort();
     }
 
-    async fn reliable_broadcast_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, dimension: Option<u32>, round_number: u32, protocol_information: String); 
+    // # Method Description:
+    // This method stops the witness handle cooperatively, unlike `terminate_witness_handle`: the handle first
+    // processes every frame it already received, so that in-flight reports and deliveries complete, then
+    // reports the rounds it had not delivered and exits. In `HandleMode::Polling`, the communicator is
+    // driven until the handle stopped.
+    // # Parameters:
+    // * witness_handle - The `JoinHandle` returned by `initialize_witness_handle`.
+    // # Returns:
+    // * The `ShutdownReport` of the handle, `None` if the handle was no longer running.
+    async fn shutdown_witness_handle(&mut self, witness_handle: JoinHandle<()>) -> Option<ShutdownReport>
+    where
+        Self: Sized,
+    {
+        log!(debug, { node = *self.get_id() }, "id: {}, shutting down witness_handle...", self.get_id());
+        let (responder, report) = oneshot::channel();
+        self.get_witness_command_channel().send(WitnessHandleCommand::Shutdown(responder)).ok()?;
+        if let HandleMode::Polling = self.get_config().get_handle_mode() {
+            drive(self).await;
+        }
+        let report = report.await.ok();
+        let _ = witness_handle.await;
+        report
+    }
+
+    async fn reliable_broadcast_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, dimension: Option<u32>, round_number: Round, protocol_information: String, statistics: Option<FrameStatistics>); 
     fn initialize_witness_handle(&mut self) -> JoinHandle<()>; 
-    fn take_witness_handle_rx(&mut self) -> Receiver<String>;
+    fn take_witness_handle_rx(&mut self) -> Receiver<Bytes>;
+    fn take_witness_command_rx(&mut self) -> UnboundedReceiver<WitnessHandleCommand<T>>;
+    fn get_witness_command_channel(&self) -> &UnboundedSender<WitnessHandleCommand<T>>;
     fn get_report_channels(&self) -> &ReportChannels<T>;
 
 }
 
+// # Struct Description:
+// This struct holds the state of a thread's witness handle and processes the witness messages, reports,
+// and commands it receives. It tracks the state of each round, updates collected values and reports,
+// converts eligible reports to witnesses, and triggers reliable broadcasts or sends values to the message
+// channel when thresholds are met. Reports that cannot yet be validated are buffered in their round and
+// re-evaluated whenever a new value arrives, and every delivered round is recorded to serve round
+// subscriptions made at any time. Collected values are kept in the configured `ValueOrdering`, which
+// reports and deliveries inherit. In commit-reveal rounds, revealed values are only collected once they
+// are checked against their sender's commitment (see `CommitmentLedger`). Delivered rounds are collected
+// according to the configured `RetentionPolicy`, after which they can no longer be inspected nor subscribed
+// to, and their late frames are ignored.
+//
+// # Fields:
+// * thread_id - The ID of the thread.
+// * thread_channel - The channels on which delivered rounds are sent.
+// * thread_signal_channel - The channels on which reports are reliably broadcast.
+// * report_channels - The channels on which the digests of the rounds are gossiped.
+// * event_channel - The channel on which `Event::RevealMismatch`, `Event::DivergenceSuspected`, and `Event::RoundCollected` events are published.
+// * metrics - The thread's metrics, in which the status of every round is recorded.
+// * decode_failures - The reporter of the frames that cannot be decoded.
+// * receiver - The receiver of the thread's `Report` lane.
+// * command_receiver - The receiver of the commands sent to the handle.
+// * processing_delay - The processing delay of the thread's `Fault::Slowdown`, if any.
+// * value_ordering - The order in which the values of a round are kept.
+// * round_values - Whether the values of a round start empty or from the values of the previous round.
+// * validity_threshold - The number of values (and witnesses) needed to report (and deliver) a round.
+// * thresholds - The thresholds the thread counts with (see `SelfCounting`), giving the weight every value and
+//   witness counts for.
+// * witness_monitor - The monitor of every round, by round number.
+// * commitments - The commitments and the reveals awaiting them.
+// * subscriptions - The round subscriptions made so far.
+// * retention - The tracker of the delivered rounds, which decides when they are collected.
+// * trace_sampler - The sampler deciding which rounds record their round status.
+// * frame_statistics - Whether the reports broadcast embed their `FrameStatistics`.
+// * divergence - The detector comparing the round digests gossiped by the other threads with the handle's own.
+// * stopped - Whether the handle was shut down (see `WitnessCommunication::shutdown_witness_handle`).
+// * running - The record of the handle running in the thread's `HealthProbe`, marked stopped once the handle stops.
+pub struct WitnessHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    thread_id: u32,
+    thread_channel: MessageChannels<T>,
+    thread_signal_channel: SignalChannels<T>,
+    report_channels: ReportChannels<T>,
+    event_channel: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    decode_failures: DecodeFailureReporter<T>,
+    receiver: Receiver<Bytes>,
+    command_receiver: UnboundedReceiver<WitnessHandleCommand<T>>,
+    processing_delay: Option<Duration>,
+    value_ordering: ValueOrdering,
+    round_values: RoundValues,
+    validity_threshold: u64,
+    thresholds: Thresholds,
+    witness_monitor: HashMap<Round, WitnessRoundMonitor<T>>,
+    commitments: CommitmentLedger<T>,
+    subscriptions: RoundSubscriptions<T>,
+    retention: RetentionTracker<Round>,
+    trace_sampler: TraceSampler,
+    frame_statistics: bool,
+    divergence: DivergenceDetector,
+    stopped: bool,
+    running: RunningHandle,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<T, C> WitnessHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: WitnessCommunication<T> + 'static,
+{
+    fn new(communicator: &mut C) -> Self {
+        let thread_id = *communicator.get_id();
+        let restored = communicator.get_signal_channels().take_restored_rounds();
+        let mut subscriptions = RoundSubscriptions::new();
+        let mut retention = RetentionTracker::new(communicator.get_config().get_retention_policy());
+        for (round_number, monitor) in restored.iter().filter(|(_, monitor)| monitor.state.witnesses) {
+            subscriptions.complete(String::from("witness"), *round_number, &monitor.content.values);
+            retention.complete(*round_number);
+        }
+
+        Self {
+            thread_id,
+            thread_channel: communicator.get_channels().clone(),
+            thread_signal_channel: communicator.get_signal_channels().clone(),
+            report_channels: communicator.get_report_channels().clone(),
+            event_channel: communicator.get_event_channel().clone(),
+            metrics: communicator.get_metrics().clone(),
+            decode_failures: DecodeFailureReporter::new(communicator, Lane::Report),
+            receiver: communicator.take_witness_handle_rx(),
+            command_receiver: communicator.take_witness_command_rx(),
+            processing_delay: communicator.get_config().get_fault_script().get_processing_delay(thread_id),
+            value_ordering: communicator.get_config().get_value_ordering(),
+            round_values: communicator.get_config().get_round_values(),
+            validity_threshold: communicator.get_config().get_counting_thresholds(thread_id).get_validity_threshold(),
+            thresholds: communicator.get_config().get_counting_thresholds(thread_id),
+            witness_monitor: restored.into_iter().collect(),
+            commitments: CommitmentLedger::new(),
+            subscriptions,
+            retention,
+            trace_sampler: communicator.get_config().get_trace_sampler(),
+            frame_statistics: communicator.get_config().get_frame_statistics(),
+            divergence: DivergenceDetector::new(thread_id, communicator.get_config().get_digest_gossip()),
+            stopped: false,
+            running: communicator.get_metrics().get_health_probe().start_handle("witness"),
+            _marker: PhantomData,
+        }
+    }
+
+    // # Method Description:
+    // This method processes commands and frames as they arrive, and gossips the digests of the rounds at
+    // the configured interval, until the handle's task is aborted or the handle is shut down.
+    async fn run(mut self) {
+        let mut gossip = self.divergence.get_interval().map(|interval| {
+            let mut gossip = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            gossip.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            gossip
+        });
+        loop {
+            tokio::select! {
+                _ = next_gossip(&mut gossip) => {
+                    self.gossip_digests().await;
+                }
+                Some(command) = self.command_receiver.recv() => {
+                    if let Some(responder) = self.process_command(command) {
+                        self.shutdown(responder).await;
+                        return
+                    }
+                }
+                Some(received_object) = self.receiver.recv() => {
+                    if let Some(delay) = self.processing_delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    self.process_object(received_object).await;
+                }
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method processes a single command sent to the handle.
+    //
+    // # Parameters:
+    // * command - The command.
+    //
+    // # Returns:
+    // * The responder of a `Shutdown` command, which the caller must answer by shutting the handle down.
+    fn process_command(&mut self, command: WitnessHandleCommand<T>) -> Option<ShutdownResponder> {
+        match command {
+            // the responder of a collected round is dropped, closing the subscription
+            WitnessHandleCommand::Subscribe(protocol_information, round_number, responder) => {
+                if !self.retention.is_collected(&round_number) {
+                    self.subscriptions.subscribe(protocol_information, round_number, responder);
+                }
+            },
+            WitnessHandleCommand::Inspect(round_number, responder) => {
+                let _ = responder.send(self.witness_monitor.get(&round_number).map(|monitor| monitor.snapshot()));
+            },
+            WitnessHandleCommand::Snapshot(responder) => {
+                let _ = responder.send(self.witness_monitor.iter().map(|(round_number, monitor)| (*round_number, monitor.clone())).collect());
+            },
+            WitnessHandleCommand::Shutdown(responder) => return Some(responder),
+        }
+        None
+    }
+
+    // # Method Description:
+    // This method shuts the handle down: it processes the frames already received, then reports the rounds
+    // that were not delivered.
+    //
+    // # Parameters:
+    // * responder - The sender on which the `ShutdownReport` is answered.
+    async fn shutdown(&mut self, responder: ShutdownResponder) {
+        let mut flushed = 0;
+        while let Ok(received_object) = self.receiver.try_recv() {
+            self.process_object(received_object).await;
+            flushed += 1;
+        }
+        let mut open: Vec<Round> = self.witness_monitor.iter()
+            .filter(|(_, monitor)| !monitor.state.witnesses)
+            .map(|(round_number, _)| *round_number)
+            .collect();
+        open.sort();
+        log!(debug, "id: {}, witness handle stopped, {} frames flushed, {} rounds open", self.thread_id, flushed, open.len());
+        self.stopped = true;
+        self.running.stop();
+        let open = open.iter().map(|round_number| format!("witness round {round_number}")).collect();
+        let _ = responder.send(ShutdownReport::new(self.thread_id, String::from("witness"), flushed, open));
+    }
+
+    // # Method Description:
+    // This method sends the digest of every active round of the handle to the other threads (see
+    // `DigestGossip`). Nothing is sent while the handle holds no round.
+    async fn gossip_digests(&mut self) {
+        let mut round_digests: Vec<(Round, u64)> = self.witness_monitor.iter()
+            .map(|(round_number, monitor)| (*round_number, monitor.content.get_digest()))
+            .collect();
+        if round_digests.is_empty() {
+            return
+        }
+        round_digests.sort();
+        log!(trace, { node = self.thread_id }, "id: {}, gossiping the digests of {} rounds", self.thread_id, round_digests.len());
+        let round_digests = RoundDigests::new(self.thread_id, round_digests);
+        for id in 0..self.report_channels.get_handle_channels().len() as u32 {
+            if id != self.thread_id {
+                self.report_channels.send_round_digests(id, &round_digests).await;
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method compares the round digests gossiped by another thread with the handle's own, and
+    // publishes `Event::DivergenceSuspected` for every round on which the thread is newly suspected.
+    //
+    // # Parameters:
+    // * round_digests - The gossiped digests.
+    fn compare_digests(&mut self, round_digests: RoundDigests) {
+        let witness_monitor = &self.witness_monitor;
+        let suspicions = self.divergence.compare(&round_digests, |round_number| {
+            witness_monitor.get(&round_number).map(|monitor| monitor.content.get_digest())
+        });
+        for suspicion in suspicions {
+            log!(warn, { node = self.thread_id, peer = suspicion.get_peer(), round = %suspicion.get_round_number() }, "id: {}, round {} of id: {} diverged in {} consecutive gossips", self.thread_id, suspicion.get_round_number(), suspicion.get_peer(), suspicion.get_gossips());
+            self.metrics.get_health_probe().record_suspicion(suspicion.get_peer());
+            let _ = self.event_channel.send(Event::DivergenceSuspected(suspicion));
+        }
+    }
+
+    // # Method Description:
+    // This method processes a single value, report, commitment, or round digest gossip received on the
+    // thread's `Report` lane.
+    //
+    // # Parameters:
+    // * received_object - The serialized `Message`, `Report`, `Commitment`, or `RoundDigests`.
+    async fn process_object(&mut self, received_object: Bytes) {
+        self.metrics.get_health_probe().record_frame();
+        let object: ObjectContent<T>; 
+        if let Ok(message) = Message::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::Message(message);
+        } else if let Ok(report) = Report::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::Report(report);
+        } else if let Ok(commitment) = Commitment::read_frame(self.thread_channel.get_codec(), &received_object) {
+            object = ObjectContent::Commitment(commitment);
+        } else if let Ok(round_digests) = RoundDigests::read_frame(self.thread_channel.get_codec(), &received_object) {
+            self.compare_digests(round_digests);
+            return
+        } else {
+            self.decode_failures.report(&received_object).await;
+            return
+        }
+        // everything logged while the object is processed is emitted in the span of its round
+        let span = tracing::debug_span!("round", protocol = "witness", round = %object.get_round_number(), node = self.thread_id);
+        self.process_round_object(object).instrument(span).await;
+    }
+
+    // # Method Description:
+    // This method processes a value, report, or commitment decoded by `process_object`.
+    //
+    // # Parameters:
+    // * object - The value, report, or commitment.
+    async fn process_round_object(&mut self, object: ObjectContent<T>) {
+        match object {
+            ObjectContent::Commitment(commitment) => {
+                if let Some(reveal) = self.commitments.commit(commitment) {
+                    self.process_reveal(reveal).await;
+                }
+            },
+            ObjectContent::Message(message) if message.get_protocol_information() == "witness reveal" => {
+                self.process_reveal(message).await;
+            },
+            object => self.accept_object(object).await,
+        }
+    }
+
+    // # Method Description:
+    // This method checks a revealed value against its sender's commitment: a matching reveal is collected
+    // as a value of its round, a mismatching reveal is discarded and published as `Event::RevealMismatch`,
+    // and a reveal whose commitment has not been delivered yet is kept until it is.
+    //
+    // # Parameters:
+    // * reveal - The revealed value.
+    async fn process_reveal(&mut self, reveal: Message<T>) {
+        match self.commitments.check(reveal) {
+            RevealStatus::Valid(reveal) => {
+                self.accept_object(ObjectContent::Message(reveal)).await;
+            },
+            RevealStatus::Mismatch(reveal, digest) => {
+                log!(warn, { node = self.thread_id, round = %reveal.get_round_number() }, "id: {}, discarding reveal by id: {} that does not match its commitment", self.thread_id, reveal.get_id());
+                let _ = self.event_channel.send(Event::RevealMismatch(self.thread_id, reveal, digest));
+            },
+            RevealStatus::Pending => {},
+        }
+    }
+
+    // # Method Description:
+    // This method delivers a round once the weight of its witnesses reaches the validity threshold, unless
+    // it was already delivered. Under `RoundValues::Cumulative`, a round also waits for the delivery of the
+    // previous round, if the handle holds it, so that the values delivered only grow from round to round;
+    // delivering a round then delivers the next one if it was waiting.
+    //
+    // # Parameters:
+    // * round_number - The round.
+    async fn deliver_witnessed(&mut self, round_number: Round) {
+        if self.round_values == RoundValues::Cumulative && round_number.0 > 0 {
+            let previous = Round(round_number.0 - 1);
+            if self.witness_monitor.get(&previous).is_some_and(|monitor| !monitor.state.witnesses) {
+                return
+            }
+        }
+        let Some(instance) = self.witness_monitor.get_mut(&round_number) else {
+            return
+        };
+        if instance.count.witnesses >= self.validity_threshold && !instance.state.witnesses {
+            let content = &instance.content;
+            let protocol_information = String::from("witness");
+            let instance_number = Instance(0);
+            let values = Report::new(ReportType::Witness, protocol_information.clone(), self.thread_id, content.values.clone(), None, instance_number, round_number); 
+            self.thread_channel.send_values(self.thread_id, values).await;
+            self.subscriptions.complete(protocol_information, round_number, &content.values);
+            instance.state.witnesses = true; 
+            self.retention.complete(round_number);
+            if self.round_values == RoundValues::Cumulative {
+                Box::pin(self.deliver_witnessed(round_number.next())).await;
+            }
+        }
+    }
+
+    // # Method Description:
+    // This method carries a value, under `RoundValues::Cumulative`, into every later round the handle holds
+    // and has not delivered yet, without counting it towards their values, and delivers the rounds whose
+    // reports became witnesses.
+    //
+    // # Parameters:
+    // * message - The value, collected in its own round.
+    async fn carry_forward(&mut self, message: &Message<T>) {
+        let round_number = message.get_round_number();
+        let mut carried = vec![];
+        for (later, monitor) in self.witness_monitor.iter_mut() {
+            if *later > round_number && monitor.carry(message, self.value_ordering) {
+                C::update_witnesses(self.thread_id, &self.thresholds, &mut monitor.count, &mut monitor.content);
+                carried.push(*later);
+            }
+        }
+        for later in carried {
+            self.deliver_witnessed(later).await;
+        }
+    }
+
+    // # Method Description:
+    // This method adds a value or report to its round, and broadcasts the thread's report or delivers
+    // the round once the thresholds are met.
+    //
+    // # Parameters:
+    // * object - The `Message` or `Report`.
+    async fn accept_object(&mut self, object: ObjectContent<T>) {
+        let round_number =  object.get_round_number(); 
+        let protocol_information = object.get_protocol_information().clone();
+        for collected in self.retention.collect() {
+            self.witness_monitor.remove(&collected);
+            self.subscriptions.forget(collected);
+            self.divergence.forget(collected);
+            let _ = self.event_channel.send(Event::RoundCollected(self.thread_id, String::from("witness"), collected));
+        }
+        if self.retention.is_collected(&round_number) {
+            return
+        }
+        if !self.witness_monitor.contains_key(&round_number) {
+            let monitor = match self.round_values {
+                RoundValues::PerRound => WitnessRoundMonitor::new(),
+                RoundValues::Cumulative => {
+                    let previous = self.witness_monitor.iter()
+                        .filter(|(earlier, _)| **earlier < round_number)
+                        .max_by_key(|(earlier, _)| **earlier)
+                        .map(|(_, monitor)| monitor);
+                    WitnessRoundMonitor::carried_over(previous)
+                },
+            };
+            self.witness_monitor.insert(round_number, monitor);
+        }
+        if let (RoundValues::Cumulative, ObjectContent::Message(message)) = (self.round_values, &object) {
+            self.carry_forward(message).await;
+        }
+
+        let instance = self.witness_monitor.get_mut(&round_number).unwrap(); 
+        let content = &mut instance.content;
+        let state = &mut instance.state;
+        let count = &mut instance.count;
+
+        match object {
+            ObjectContent::Message(message) => {
+                if !content.values.contains(&message) {
+                    count.values += self.thresholds.get_weight(message.get_id());  
+                    self.value_ordering.insert(&mut content.values, message);
+                    C::update_witnesses(self.thread_id, &self.thresholds, count, content);
+                }
+            },
+            ObjectContent::Report(report) => {
+                if !content.reports.contains(&report) {
+                    content.reports.push(report);
+                    count.reports += 1;  
+                    let report = content.reports.get_mut((count.reports - 1) as usize).unwrap(); 
+                    C::initialize_witnesses(self.thread_id, &self.thresholds, report, &mut content.witnesses, count, content.values.clone()); 
+                }
+            },
+            ObjectContent::AggregatedReport(_) => {                        
+                panic!("Error: received incompatible object type (AggregatedReport) for witness broadcast");
+            },
+            ObjectContent::BarycentricReport(_) => {                        
+                panic!("Error: received incompatible object type (BarycentricReport) for witness broadcast");
+            },
+            ObjectContent::Commitment(_) => {                        
+                panic!("Error: received commitment as a value or report for witness broadcast");
+            },
+            ObjectContent::Fragment(_) => {                        
+                panic!("Error: received incompatible object type (Fragment) for witness broadcast");
+            },
+            ObjectContent::Extension { .. } => {
+                panic!("Error: received incompatible object type (Extension) for witness broadcast");
+            },
+        }
+
+        if count.values >= self.validity_threshold && state.report == false {
+            let statistics = self.frame_statistics.then(|| FrameStatistics::new(content.values.len(), content.witnesses.len(), &self.thresholds));
+            C::reliable_broadcast_report(self.thread_id, &self.thread_signal_channel, content, None, round_number, protocol_information, statistics).await;
+            state.report = true; 
+        }
+
+        self.deliver_witnessed(round_number).await;
+
+        let instance = self.witness_monitor.get(&round_number).unwrap(); 
+        let state = &instance.state;
+        let count = &instance.count;
+        let round_status = RoundStatus::new(String::from("witness"), None, round_number, state.witnesses)
+            .with_stage("values", count.values, self.validity_threshold)
+            .with_stage("witnesses", count.witnesses, self.validity_threshold);
+        if self.trace_sampler.is_sampled(&format!("{}::round::{}", round_status.get_protocol_information(), round_number)) {
+            self.metrics.record_round_status(round_status);
+        }
+    }
+}
+
+#[async_trait]
+impl<T, C> PolledHandle for WitnessHandleLoop<T, C>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
+    C: WitnessCommunication<T> + 'static,
+{
+    async fn process_pending(&mut self) -> usize {
+        if self.stopped {
+            // commands sent to a stopped handle are dropped, so that their senders are not left waiting
+            while self.command_receiver.try_recv().is_ok() {}
+            return 0
+        }
+        let mut processed = 0;
+        while let Ok(command) = self.command_receiver.try_recv() {
+            processed += 1;
+            if let Some(responder) = self.process_command(command) {
+                self.shutdown(responder).await;
+                return processed
+            }
+        }
+        while let Ok(received_object) = self.receiver.try_recv() {
+            self.process_object(received_object).await;
+            processed += 1;
+        }
+        if self.divergence.is_due() {
+            self.gossip_digests().await;
+        }
+        processed
+    }
+}
+
+// # Function Description:
+// This function waits for the next tick of a periodic gossip of a handle (the digest gossip of a witness
+// handle, or the anti-entropy of a reliable handle), forever if the gossip is disabled.
+//
+// # Parameters:
+// * gossip - The interval of the gossip, if enabled.
+pub(crate) async fn next_gossip(gossip: &mut Option<tokio::time::Interval>) {
+    match gossip {
+        Some(gossip) => {
+            gossip.tick().await;
+        },
+        None => std::future::pending().await,
+    }
+}
+
 // # Struct Description:
 // This struct manages a collection of `WitnessCommunicator` instances, each representing a thread
 // participating in witness-based reliable broadcast. It handles initialization of communication
 // channels for both reliable and witness-specific messaging and provides access to individual communicators.
 // # Fields:
 // * witness_communicators - A vector containing all `WitnessCommunicator` instances managed by this hub.
+// * config - The configuration shared by every communicator created from this hub.
+// * snapshot_taps - The command channels of the handles of every thread, snapshot by `snapshot`.
 pub struct WitnessHub<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    witness_communicators: Vec<WitnessCommunicator<T>>
+    witness_communicators: Vec<WitnessCommunicator<T>>,
+    config: ClusterConfig,
+    snapshot_taps: Vec<SnapshotTap<T>>,
 }
  
 impl<T> WitnessHub<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    pub fn new(transmitters: Vec<Sender<String>>, mut receivers: Vec<Receiver<String>>, thread_count: u32) -> Self {  
-        let mut witness_communicators = vec![];
-        let mut reliable_handle_transmitters = vec![];
-        let mut reliable_handle_receivers = vec![];
-
-        let mut witness_handle_transmitters = vec![];
-        let mut witness_handle_receivers = vec![];
-
-        for _ in 0..(thread_count) {
-            let (reliable_handle_tx, reliable_handle_rx) = mpsc::channel(256); 
-            let (witness_handle_tx, witness_handle_rx) = mpsc::channel(256); 
+    pub fn new(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, thread_count: u32) -> Self {  
+        Self::with_config(transmitters, receivers, ClusterConfig::new(thread_count))
+    }
 
-            reliable_handle_transmitters.push(reliable_handle_tx);
-            reliable_handle_receivers.push(reliable_handle_rx);
+    // # Function Description:
+    // This function creates a hub whose quorums are weighted: every Echo, Vote, value, and witness counts
+    // for the weight (stake) of its sender, and the thresholds are derived from the total weight following
+    // the quorum configuration of `config` (see `QuorumConfig::with_weights`).
+    //
+    // # Parameters:
+    // * transmitters - The transmitter of every thread.
+    // * receivers - The receiver of every thread.
+    // * config - The configuration of the cluster.
+    // * weights - The weight of every thread, indexed by thread ID.
+    //
+    // # Returns:
+    // * The hub, or a description of why the weights cannot be used (see `QuorumConfig::thresholds`): weights
+    //   that do not cover every thread, a total weight of zero, or a total that does not fit a `u64`.
+    pub fn with_weights(transmitters: Vec<Sender<Bytes>>, receivers: Vec<Receiver<Bytes>>, config: ClusterConfig, weights: Vec<u64>) -> Result<Self, String> {
+        let quorum_config = config.get_quorum_config().clone().with_weights(weights);
+        Ok(Self::with_config(transmitters, receivers, config.with_quorum_config(quorum_config)?))
+    }
 
-            witness_handle_transmitters.push(witness_handle_tx);
-            witness_handle_receivers.push(witness_handle_rx);
-        }
+    pub fn with_config(transmitters: Vec<Sender<Bytes>>, mut receivers: Vec<Receiver<Bytes>>, config: ClusterConfig) -> Self {  
+        let thread_count = config.get_thread_count();
+        let mut witness_communicators = vec![];
         
         for i in 0..(thread_count) {
-            let reliable_handle_rx = reliable_handle_receivers.remove(0);
-            let witness_handle_rx = witness_handle_receivers.remove(0);
-            let rx: Receiver<String> = receivers.remove(0);
-            witness_communicators.push(WitnessCommunicator::new(transmitters.clone(), rx, 
-                thread_count, i as u32, reliable_handle_transmitters.clone(), reliable_handle_rx, witness_handle_transmitters.clone(), witness_handle_rx));
+            let lanes = open_lanes(i, config.link_receiver(i, receivers.remove(0)), &config);
+            let (witness_command_tx, witness_command_rx) = mpsc::unbounded_channel(); 
+            witness_communicators.push(WitnessCommunicator::new(config.link_transmitters(i, &transmitters), lanes, 
+                i, witness_command_tx, witness_command_rx, config.clone()));
         }
+        let snapshot_taps = witness_communicators.iter().map(WitnessCommunicator::get_snapshot_tap).collect();
         
         Self {
-            witness_communicators
+            witness_communicators,
+            config,
+            snapshot_taps,
         }
     }
+
+    pub fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
  
     pub fn create_witness_communicator(&mut self) -> WitnessCommunicator<T>{
         self.witness_communicators.remove(0)
     }
+
+    // # Method Description:
+    // This method snapshots the running handles of every thread created from the hub (see `SnapshotTap`),
+    // including the threads whose communicator was handed out. The basic queues of a thread are only
+    // captured by the `snapshot` of its communicator. In `HandleMode::Polling`, the handles answer once
+    // their communicators are driven.
+    //
+    // # Returns:
+    // * The `ClusterSnapshot` of the cluster, serializable through `JsonConversion`.
+    pub async fn snapshot(&self) -> ClusterSnapshot<T> {
+        ClusterSnapshot::new(join_all(self.snapshot_taps.iter().map(SnapshotTap::snapshot)).await)
+    }
+
+    // # Method Description:
+    // This method restores a snapshot of the cluster into the communicators the hub still holds (see
+    // `ReliableCommunication::restore`). It must be called before the communicators are created.
+    //
+    // # Parameters:
+    // * snapshot - The `ClusterSnapshot` to restore.
+    //
+    // # Returns:
+    // * `Ok(())`, or a description of why a thread of the snapshot cannot be restored.
+    pub fn restore(&mut self, snapshot: ClusterSnapshot<T>) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        for thread in snapshot.into_threads() {
+            let id = thread.get_id();
+            let Some(communicator) = self.witness_communicators.iter_mut().find(|communicator| *communicator.get_id() == id) else {
+                return Err(format!("the communicator of id: {id} is not held by the hub"))
+            };
+            communicator.restore(thread)?;
+        }
+        Ok(())
+    }
+
+    // # Method Description:
+    // This method checks that every channel of the hub reaches the thread it is meant for (see `validate_wiring`).
+    // It must be called before any communicator is created from the hub.
+    // # Returns:
+    // * `Ok(())` if the wiring is correct, or a description of every mis-wired channel.
+    pub async fn validate(&mut self) -> Result<(), String>
+    where
+        T: Send + Sync + 'static,
+    {
+        validate_wiring(&mut self.witness_communicators).await
+    }
  }
 
 // # Struct Description:
 // This struct represents a single thread in a witness-based reliable broadcast system. 
 // It extends basic communication with both reliable and witness-specific protocols, maintaining
-// dedicated channels for standard messages, protocol-level signals, and report aggregation.
+// dedicated channels for standard messages, protocol-level signals, and report aggregation, multiplexed
+// as lanes over the thread's single channel.
 //
 // # Fields:
 // * id - The unique identifier for this thread.
@@ -172,6 +977,15 @@ where
 // * queues - Stores incoming messages for this thread.
 // * reliable_handle_rx - A receiver for incoming reliable broadcast signals.
 // * witness_handle_rx - A receiver for incoming witness broadcast signals.
+// * witness_command_tx - A transmitter for sending commands (e.g. round subscriptions) to the witness handle.
+// * witness_command_rx - A receiver for commands, taken by the witness handle.
+// * reliable_command_tx - A transmitter for sending commands (e.g. inspection queries) to the reliable handle.
+// * reliable_command_rx - A receiver for commands, taken by the reliable handle.
+// * event_tx - A transmitter on which the background handles publish `Event`s.
+// * metrics - The metrics of the thread, in which the reliable handle records its phase latencies.
+// * delivery_watches - The watch channels on which the reliable handle publishes deliveries.
+// * polled_handles - The parts of the thread driven manually in `HandleMode::Polling`.
+// * config - The configuration shared by every communicator created from the same hub.
 pub struct WitnessCommunicator<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
@@ -181,22 +995,43 @@ where
     signal_channels: SignalChannels<T>, 
     report_channels: ReportChannels<T>,
     queues: BasicQueues<T>,
-    reliable_handle_rx: Option<Receiver<String>>, 
-    witness_handle_rx: Option<Receiver<String>>, 
+    reliable_handle_rx: Option<Receiver<Bytes>>, 
+    witness_handle_rx: Option<Receiver<Bytes>>, 
+    witness_command_tx: UnboundedSender<WitnessHandleCommand<T>>,
+    witness_command_rx: Option<UnboundedReceiver<WitnessHandleCommand<T>>>,
+    reliable_command_tx: UnboundedSender<ReliableHandleCommand>,
+    reliable_command_rx: Option<UnboundedReceiver<ReliableHandleCommand>>,
+    event_tx: broadcast::Sender<Event<T>>,
+    metrics: Metrics,
+    delivery_watches: DeliveryWatches<T>,
+    polled_handles: PolledHandles,
+    config: ClusterConfig,
 }
 
 impl<T> WitnessCommunicator<T> 
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {
-    fn new(transmitters: Vec<Sender<String>>, receiver: Receiver<String>, 
-            thread_count: u32, id: u32, reliable_handle_transmitters: Vec<Sender<String>>, reliable_handle_rx: Receiver<String>, witness_handle_transmitters: Vec<Sender<String>>, witness_handle_rx: Receiver<String>) -> Self {
-        let basic_channels = MessageChannels::new(transmitters.clone());
-        let signal_channels = SignalChannels::new(reliable_handle_transmitters.clone());
-        let report_channels = ReportChannels::new(witness_handle_transmitters.clone());
-        let queues = BasicQueues::new(receiver, thread_count);
-        let reliable_handle_rx = Some(reliable_handle_rx);
-        let witness_handle_rx = Some(witness_handle_rx);
+    fn get_snapshot_tap(&self) -> SnapshotTap<T> {
+        SnapshotTap::with_channels(self.id, self.reliable_command_tx.clone(), Some(self.witness_command_tx.clone()), self.metrics.get_health_probe().clone())
+    }
+
+    fn new(transmitters: Vec<Sender<Bytes>>, lanes: LaneReceivers, id: u32, 
+            witness_command_tx: UnboundedSender<WitnessHandleCommand<T>>, witness_command_rx: UnboundedReceiver<WitnessHandleCommand<T>>, config: ClusterConfig) -> Self {
+        let basic_channels = MessageChannels::new(transmitters.clone()).with_codec(config.get_codec());
+        let metrics = lanes.metrics;
+        let signal_channels = SignalChannels::new(transmitters.clone()).with_dissemination(id, &config).with_metrics(&metrics).with_signing(id, &config).with_journal(id, &config).with_codec(config.get_codec()).with_batching(&config);
+        let report_channels = ReportChannels::new(transmitters).with_codec(config.get_codec());
+        let (event_tx, _) = broadcast::channel(config.get_buffer_size());
+        let queues = BasicQueues::new(lanes.basic, config.get_thread_count()).with_repeated_collect_policy(config.get_repeated_collect_policy())
+            .with_validation(id, &config).with_metrics(metrics.clone()).with_event_channel(event_tx.clone());
+        let (reliable_command_tx, reliable_command_rx) = mpsc::unbounded_channel();
+        let reliable_command_rx = Some(reliable_command_rx);
+        let delivery_watches = DeliveryWatches::new();
+        let reliable_handle_rx = Some(lanes.signal);
+        let witness_handle_rx = Some(lanes.report);
+        let witness_command_rx = Some(witness_command_rx);
+        let polled_handles = PolledHandles::new(lanes.demultiplexer);
 
         Self {
             id, 
@@ -206,8 +1041,39 @@ where
             queues,
             reliable_handle_rx,
             witness_handle_rx,
+            witness_command_tx,
+            witness_command_rx,
+            reliable_command_tx,
+            reliable_command_rx,
+            event_tx,
+            metrics,
+            delivery_watches,
+            polled_handles,
+            config,
         }
     }
+
+    // # Function Description:
+    // This function creates the communicator of a thread restarted after a crash from the journal it kept
+    // under a path (see `ReliableCommunicator::recover`). Its reliable handle replays the journal of the
+    // thread when it starts, delivering the values and reports of the witness rounds again, so that the
+    // witness handle restores the rounds the thread held before the crash.
+    //
+    // # Parameters:
+    // * path - The root of the storage the thread journaled to before the crash.
+    // * transmitters - The transmitter of every thread.
+    // * receiver - The new receiver of the thread.
+    // * id - The ID of the thread.
+    // * config - The configuration of the cluster, enabling journaling.
+    //
+    // # Returns:
+    // * The communicator, or a description of why the thread cannot be recovered.
+    pub fn recover(path: &Path, transmitters: Vec<Sender<Bytes>>, receiver: Receiver<Bytes>, id: u32, config: ClusterConfig) -> Result<Self, String> {
+        let config = recovery_config::<T>(path, id, config)?;
+        let lanes = open_lanes(id, config.link_receiver(id, receiver), &config);
+        let (witness_command_tx, witness_command_rx) = mpsc::unbounded_channel();
+        Ok(Self::new(config.link_transmitters(id, &transmitters), lanes, id, witness_command_tx, witness_command_rx, config))
+    }
 }
 
 #[async_trait]
@@ -216,89 +1082,20 @@ where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash + Send + Sync + 'static,
 {
     // # Method Description:
-    // This method spawns an asynchronous background task that listens for incoming witness messages and reports.
-    // It tracks the state of each round, updates collected values and reports, converts eligible reports to witnesses,
-    // and triggers reliable broadcasts or sends values to the message channel when thresholds are met.
+    // This method starts the thread's witness handle, which listens for incoming witness messages and reports
+    // (see `WitnessHandleLoop`). In `HandleMode::Spawned` the handle runs in its own task; in `HandleMode::Polling`
+    // it is parked in the communicator and only processes frames and commands when the communicator is driven.
     //
     // # Returns:
-    // * A `JoinHandle<()>` representing the spawned asynchronous task.
+    // * A `JoinHandle<()>` representing the spawned asynchronous task (already completed in polling mode).
     fn initialize_witness_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing witness handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let thread_count = thread_channel.get_channels().len() as u32; 
-        let mut receiver = self.take_witness_handle_rx(); 
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let mut witness_monitor: HashMap<u32, WitnessRoundMonitor<T>> = HashMap::new();
-    
-        let handle = tokio::spawn(async move {
-            loop  {
-                tokio::select! {
-                    Some(received_object) = receiver.recv() => {
-                        let object: ObjectContent<T>; 
-                        if let Ok(message) = Message::read_json(&received_object) {
-                            object = ObjectContent::Message(message);
-                        } else if let Ok(report) = Report::read_json(&received_object) {
-                            object = ObjectContent::Report(report);
-                        } else {
-                            continue
-                        }
-
-                        let round_number =  object.get_round_number(); 
-                        let protocol_information = object.get_protocol_information().clone();
-                        let _ =  witness_monitor.entry(round_number).or_insert(WitnessRoundMonitor::new());
-
-                        let instance = witness_monitor.get_mut(&round_number).unwrap(); 
-                        let content = &mut instance.content;
-                        let state = &mut instance.state;
-                        let count = &mut instance.count;
-
-                        match object {
-                            ObjectContent::Message(message) => {
-                                if !content.values.contains(&message) {
-                                    content.values.push(message);
-                                    count.values += 1;  
-                                    if count.values > validity_threshold {
-                                        Self::update_witnesses(thread_id, count, content);
-                                    }
-                                }
-                            },
-                            ObjectContent::Report(report) => {
-                                if !content.reports.contains(&report) {
-                                    content.reports.push(report);
-                                    count.reports += 1;  
-                                    let report = content.reports.get_mut((count.reports - 1) as usize).unwrap(); 
-                                    Self::initialize_witnesses(thread_id, report, &mut content.witnesses, count, content.values.clone()); 
-                                }
-                            },
-                            ObjectContent::AggregatedReport(_) => {                        
-                                panic!("Error: received incompatible object type (AggregatedReport) for witness broadcast");
-                            },
-                            ObjectContent::BarycentricReport(_) => {                        
-                                panic!("Error: received incompatible object type (BarycentricReport) for witness broadcast");
-                            },
-                        }
-
-                        if count.values >= validity_threshold && state.report == false {
-                            Self::reliable_broadcast_report(thread_id, &thread_signal_channel, content, None, round_number, protocol_information).await;
-                            state.report = true; 
-                        }
-
-                        if count.witnesses >= validity_threshold && state.witnesses == false {
-                            let protocol_information = String::from("witness");
-                            let instance_number = 0; 
-                            let values = Report::new(ReportType::Witness, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number); 
-                            thread_channel.send_values(thread_id, values).await;
-                            state.witnesses = true; 
-                        }
-                    }
-                }
-            }
-        });
-        handle
+        log!(debug, "initializing witness handle...");
+
+        let witness_handle: WitnessHandleLoop<T, Self> = WitnessHandleLoop::new(self);
+        match self.get_config().get_handle_mode() {
+            HandleMode::Spawned => tokio::spawn(witness_handle.run()),
+            HandleMode::Polling => self.get_polled_handles().park(Box::new(witness_handle)),
+        }
     } 
 
     // # Method Description:
@@ -312,15 +1109,17 @@ where
     // * content - The `WitnessRoundContent` containing collected messages for the report.
     // * round_number - The current round number for the witness collection.
     // * protocol_information - A string representing the protocol type.
+    // * statistics - The statistics embedded in the report, if any.
     //
     // # Returns:
     // * A future that broadcasts the report to all signal receivers.`
-    async fn reliable_broadcast_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, _dimension: Option<u32>, round_number: u32, protocol_information: String){
+    async fn reliable_broadcast_report(thread_id: u32, thread_signal_channel: &SignalChannels<T>, content: &mut WitnessRoundContent<T>, _dimension: Option<u32>, round_number: Round, protocol_information: String, statistics: Option<FrameStatistics>){
         let protocol_information = protocol_information;
-        let instance_number = 0; 
-        let report = Report::new(ReportType::Report, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number); 
+        let instance_number = Instance(0);
+        let report = Report::new(ReportType::Report, protocol_information, thread_id, content.values.clone(), None, instance_number, round_number)
+            .with_statistics(statistics); 
         let input = Signal::new(SignalType::Input, ObjectContent::Report(report.clone()), report.get_instance_number(), report.get_round_number());
-        println!("id: {thread_id}, broadcasting report...");
+        log!(debug, "id: {thread_id}, broadcasting report...");
         thread_signal_channel.broadcast_signal(input).await;
     }
 
@@ -328,9 +1127,17 @@ where
         &self.report_channels
     }
 
-    fn take_witness_handle_rx(&mut self) -> Receiver<String> {
+    fn take_witness_handle_rx(&mut self) -> Receiver<Bytes> {
         self.witness_handle_rx.take().unwrap()
     }
+
+    fn take_witness_command_rx(&mut self) -> UnboundedReceiver<WitnessHandleCommand<T>> {
+        self.witness_command_rx.take().unwrap()
+    }
+
+    fn get_witness_command_channel(&self) -> &UnboundedSender<WitnessHandleCommand<T>> {
+        &self.witness_command_tx
+    }
 }
 
 #[async_trait]
@@ -342,98 +1149,40 @@ where
         &self.signal_channels
     }
 
-    fn take_reliable_handle_rx(&mut self) -> Receiver<String> {
+    fn get_delivery_report_channels(&self) -> Option<&ReportChannels<T>> {
+        Some(&self.report_channels)
+    }
+
+    fn get_round_command_channel(&self) -> Option<&UnboundedSender<WitnessHandleCommand<T>>> {
+        Some(self.get_witness_command_channel())
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::from_config(self.get_config(), &[Protocol::Reliable, Protocol::Witness])
+    }
+
+    fn get_event_channel(&self) -> &broadcast::Sender<Event<T>> {
+        &self.event_tx
+    }
+
+    fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn get_delivery_watches(&self) -> &DeliveryWatches<T> {
+        &self.delivery_watches
+    }
+
+    fn take_reliable_handle_rx(&mut self) -> Receiver<Bytes> {
         self.reliable_handle_rx.take().unwrap()
     }
 
-    // # Method Description:
-    // This method spawns an asynchronous background task that manages reliable broadcast signals.
-    // It listens for incoming signals, updates the state of each instance,
-    // broadcasts signals based on protocol thresholds, and delivers messages or reports when conditions are met.
-    // # Returns:
-    // * A `JoinHandle<()>` representing the spawned async task.
-    fn initialize_reliable_handle(&mut self) -> JoinHandle<()>{
-        println!("initializing reliable handle...");
-
-        let thread_id = *self.get_id(); 
-        let thread_channel = self.get_channels().clone(); 
-        let thread_signal_channel = self.get_signal_channels().clone();
-        let report_channel = self.get_report_channels().clone(); 
-        let thread_count = report_channel.get_handle_channels().len() as u32; 
-        let mut receiver = self.take_reliable_handle_rx(); 
-        let faulty_threads = (thread_count - 1) / 3;
-        let validity_threshold = thread_count - faulty_threads + 1;
-        let agreement_threshold = faulty_threads + 1;
-
-        let mut reliable_broadcast_monitor: HashMap<String, ReliableInstanceMonitor> = HashMap::new();
-
-        let handle = tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    Some(received_signal) = receiver.recv() => {
-                        let signal = match Signal::read_json(&received_signal) {
-                            Ok(correct_signal) => correct_signal,
-                            Err(_)=> { continue },
-                        };
-
-                        let instance_id = Self::get_instance_id(thread_id, signal.clone()); 
-
-                        if let SignalType::Input = signal.get_signal() {
-                            match reliable_broadcast_monitor.get(&instance_id) {
-                                Some(_) => {
-                                    panic!("Error: instance id ({}) already used", instance_id)
-                                },
-                                None => {
-                                    reliable_broadcast_monitor.insert(instance_id.clone(), ReliableInstanceMonitor::new());
-                                },
-                            }
-                        }
-
-                        let instance = reliable_broadcast_monitor.get_mut(&instance_id).unwrap(); 
-                        let state = &mut instance.state; 
-                        let count = &mut instance.count; 
-
-                        match signal.get_signal()
-                        {
-                            SignalType::Input => {
-                                if state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Echo => {
-                                count.echo += 1;
-                                if count.echo >= validity_threshold && state.vote == false{
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else if count.echo >= agreement_threshold && state.echo == false {
-                                    Self::upon_input(thread_id, &thread_signal_channel, signal).await;
-                                    state.echo = true;
-                                } else { continue }
-                            },
-                            SignalType::Vote => {
-                                count.vote += 1;
-                                if count.vote >= validity_threshold && state.deliver == false {
-                                    if signal.get_content().get_protocol_information() == "reliable" {
-                                        let channel = ChannelType::MessageChannels(thread_channel.clone());
-                                         Self::upon_vote(thread_id, channel, signal).await;
-                                    } else {
-                                        let channel = ChannelType::ReportChannels(report_channel.clone());
-                                        Self::upon_vote(thread_id, channel, signal).await;
-                                    }
-                                   
-                                    state.deliver = true;
-                                } else if count.vote >= agreement_threshold && state.vote == false {
-                                    Self::upon_echo(thread_id, &thread_signal_channel, signal).await;
-                                    state.vote = true;
-                                } else { continue }
-                            }
-                        }
-                    }
-                }
-            }
-        });
-        handle
+    fn take_reliable_command_rx(&mut self) -> UnboundedReceiver<ReliableHandleCommand> {
+        self.reliable_command_rx.take().unwrap()
+    }
+
+    fn get_reliable_command_channel(&self) -> &UnboundedSender<ReliableHandleCommand> {
+        &self.reliable_command_tx
     }
 
     // # Method Description:
@@ -473,7 +1222,7 @@ where
     // * channel - The channel used to deliver the final message (`MessageChannels` or `ReportChannels`).
     // * signal - The received `Vote` signal.
     async fn upon_vote(thread_id: u32, channel: ChannelType<T>, signal: Signal<T>)  {
-        let object = signal.get_content().clone(); 
+        let object = signal.into_content();
 
         match channel {
             ChannelType::MessageChannels(thread_channel) => {
@@ -494,7 +1243,16 @@ where
                     },
                     ObjectContent::BarycentricReport(_) => {
                         panic!("Error: received incompatible object type (BarycentricReport) for witness broadcast");
-                    }
+                    },
+                    ObjectContent::Commitment(commitment) => {
+                        report_channel.send_commitment(thread_id, commitment).await;
+                    },
+                    ObjectContent::Fragment(_) => {
+                        panic!("Error: received incompatible object type (Fragment) for witness broadcast");
+                    },
+                    ObjectContent::Extension { .. } => {
+                        panic!("Error: received incompatible object type (Extension) for witness broadcast");
+                    },
                 }
             },
         }
@@ -519,8 +1277,8 @@ where
         async move {
             match self.get_channels().get(id as usize) {
                 Some(channel) => {
-                    println!("id: {id}, delivering values...");
-                    let _ = channel.send(values.write_json()).await;
+                    log!(debug, "id: {id}, delivering values...");
+                    let _ = channel.send(tag_frame(Lane::Basic, values.write_frame(self.get_codec()))).await;
                 },
                 None => panic!("Error: received incompatible object type (aggregated_report) for witness broadcast"),
             }
@@ -543,6 +1301,14 @@ where
     fn get_id(& self) -> &u32 {
         &self.id
     }
+
+    fn get_config(&self) -> &ClusterConfig {
+        &self.config
+    }
+
+    fn get_polled_handles(&mut self) -> &mut PolledHandles {
+        &mut self.polled_handles
+    }
 }
 
 // # Struct Description:
@@ -550,13 +1316,15 @@ where
 // and aggregated reports between threads in a witness-based reliable communication protocol.
 //
 // # Fields:
-// * witness_handle_transmitters - A vector of `Sender<String>` channels used to send serialized reports to target threads.
+// * witness_handle_transmitters - A vector of `Sender<Bytes>` channels used to send serialized reports to target threads.
+// * codec - The wire format of the frames sent.
 #[derive(Clone)]
 pub struct ReportChannels<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash, 
 {
-   witness_handle_transmitters: Vec<Sender<String>>,
+   witness_handle_transmitters: Vec<Sender<Bytes>>,
+    codec: Codec,
     _marker: PhantomData<T>,
 }
 
@@ -577,7 +1345,7 @@ where
         async move {
             match self.get_handle_channels().get(id as usize) {
                 Some(channel) => {
-                    let _ = channel.send(message.write_json()).await;
+                    let _ = channel.send(tag_frame(Lane::Report, message.write_frame(self.codec))).await;
                 },
                 None => panic!("Error: failed to find channel"),
             }
@@ -599,7 +1367,7 @@ where
                 Some(channel) => {
                     match &report.get_report_type() {
                         ReportType::Report => {
-                            let _ = channel.send(report.write_json()).await;
+                            let _ = channel.send(tag_frame(Lane::Report, report.write_frame(self.codec))).await;
                         },
                         ReportType::Witness => {
                             panic!("Error: received incompatible object type (witness) for reliable delivery");
@@ -626,7 +1394,7 @@ where
                 Some(channel) => {
                     match &aggregated_report.get_report_type() {
                         ReportType::Report => {
-                            let _ = channel.send(aggregated_report.write_json()).await;
+                            let _ = channel.send(tag_frame(Lane::Report, aggregated_report.write_frame(self.codec))).await;
                         },
                         ReportType::Witness => {
                             panic!("Error: received incompatible object type (witness) for reliable delivery");
@@ -642,23 +1410,69 @@ where
         async move {
             match self.get_handle_channels().get(id as usize) {
                 Some(channel) => {
-                    let _ = channel.send(barycentric_report.write_json()).await;
+                    let _ = channel.send(tag_frame(Lane::Report, barycentric_report.write_frame(self.codec))).await;
                 },
                 None => panic!("Error: failed to find channel"),
             }
         }
     }
 
-    pub fn get_handle_channels(&self) -> &Vec<Sender<String>> {
+    // # Method Description:
+    // This method sends a `Commitment` to a specific thread via its corresponding witness channel.
+    //
+    // # Parameters:
+    // * id - The ID of the target thread.
+    // * commitment - The `Commitment` instance to send.
+    pub(crate) async fn send_commitment(&self, id: u32, commitment: Commitment) {
+        match self.get_handle_channels().get(id as usize) {
+            Some(channel) => {
+                let _ = channel.send(tag_frame(Lane::Report, commitment.write_frame(self.codec))).await;
+            },
+            None => panic!("Error: failed to find channel"),
+        }
+    }
+
+    // # Method Description:
+    // This method gossips the digests of the rounds of a thread to a specific thread via its corresponding
+    // witness channel.
+    //
+    // # Parameters:
+    // * id - The ID of the target thread.
+    // * round_digests - The `RoundDigests` to send.
+    pub(crate) async fn send_round_digests(&self, id: u32, round_digests: &RoundDigests) {
+        match self.get_handle_channels().get(id as usize) {
+            Some(channel) => {
+                let _ = channel.send(tag_frame(Lane::Report, round_digests.write_frame(self.codec))).await;
+            },
+            None => panic!("Error: failed to find channel"),
+        }
+    }
+
+    pub fn get_handle_channels(&self) -> &Vec<Sender<Bytes>> {
        &self.witness_handle_transmitters
     }
 
-    pub fn new(witness_handle_transmitters: Vec<Sender<String>>) -> Self {
+    pub fn new(witness_handle_transmitters: Vec<Sender<Bytes>>) -> Self {
        Self {
            witness_handle_transmitters,
+           codec: Codec::Json,
            _marker: PhantomData,
        }
     }
+
+    // # Method Description:
+    // This method sets the wire format of the frames sent over the channels.
+    //
+    // # Returns:
+    // * The updated channels.
+    pub fn with_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
 }
 
 // # Enum Description:
@@ -687,6 +1501,8 @@ pub enum ReportType{
 // * messages - A vector of `Message`s contained in this report.
 // * instance_number - The consensus instance associated with this report.
 // * round_number - The round number of the protocol in which this report was created.
+// * certificate - The buddy attestations that justified the delivery of a barycentric agreement round, if any.
+// * statistics - The statistics of the report when it was created, if its creator embeds them.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Report<T>
 {
@@ -695,8 +1511,12 @@ pub struct Report<T>
     id: u32, 
     messages: Vec<Message<T>>, 
     dimension: Option<u32>,
-    instance_number: u32,
-    round_number: u32
+    instance_number: Instance,
+    round_number: Round,
+    #[serde(default)]
+    certificate: Option<BuddyCertificate>,
+    #[serde(default)]
+    statistics: Option<FrameStatistics>
 }
 
 impl<T> Report<T>
@@ -723,15 +1543,23 @@ where
         self.dimension
     }
 
-    pub fn get_instance_number(&self) -> u32 {
+    pub fn get_instance_number(&self) -> Instance {
         self.instance_number
     }
 
-    pub fn get_round_number(&self) -> u32 {
+    pub fn get_round_number(&self) -> Round {
         self.round_number
     }
 
-    pub fn new(report_type: ReportType, protocol_information: String, id: u32, messages: Vec<Message<T>>, dimension: Option<u32>,instance_number: u32, round_number: u32) -> Self {
+    pub fn get_certificate(&self) -> Option<&BuddyCertificate> {
+        self.certificate.as_ref()
+    }
+
+    pub fn get_statistics(&self) -> Option<&FrameStatistics> {
+        self.statistics.as_ref()
+    }
+
+    pub fn new(report_type: ReportType, protocol_information: String, id: u32, messages: Vec<Message<T>>, dimension: Option<u32>,instance_number: Instance, round_number: Round) -> Self {
         Self {
             report_type,
             protocol_information,
@@ -739,9 +1567,29 @@ where
             messages,
             dimension, 
             instance_number,
-            round_number
+            round_number,
+            certificate: None,
+            statistics: None
         }
     }
+
+    pub fn with_certificate(mut self, certificate: BuddyCertificate) -> Self {
+        self.certificate = Some(certificate);
+        self
+    }
+
+    // # Method Description:
+    // This method embeds statistics in the report (see `ClusterConfig::with_frame_statistics`).
+    //
+    // # Parameters:
+    // * statistics - The statistics, if any.
+    //
+    // # Returns:
+    // * The updated report.
+    pub fn with_statistics(mut self, statistics: Option<FrameStatistics>) -> Self {
+        self.statistics = statistics;
+        self
+    }
 }
 
 impl<T> JsonConversion<Report<T>> for Report<T> 
@@ -749,6 +1597,302 @@ where
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {}
 
+impl<T> FrameKind for Report<T> {
+    const FRAME_KIND: u64 = frame_kinds::REPORT_FRAME;
+}
+
+// # Struct Description:
+// This struct represents the commitment of a thread to the value it reveals in the next witness round.
+//
+// # Fields:
+// * protocol_information - A string identifying the protocol or message type.
+// * id - The ID of the thread that committed.
+// * digest - The digest of the reveal `Message` the thread committed to.
+// * round_number - The round number of the commitment; the value is revealed in the next round.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Commitment {
+    protocol_information: String,
+    id: u32,
+    digest: u64,
+    round_number: Round
+}
+
+impl Commitment {
+    pub fn get_protocol_information(&self) -> &String {
+        &self.protocol_information
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_digest(&self) -> u64 {
+        self.digest
+    }
+
+    pub fn get_round_number(&self) -> Round {
+        self.round_number
+    }
+
+    pub fn new(protocol_information: String, id: u32, digest: u64, round_number: Round) -> Self {
+        Self {
+            protocol_information,
+            id,
+            digest,
+            round_number
+        }
+    }
+}
+
+impl JsonConversion<Commitment> for Commitment {}
+
+impl FrameKind for Commitment {
+    const FRAME_KIND: u64 = frame_kinds::COMMITMENT_FRAME;
+}
+
+// # Enum Description:
+// This enum represents the outcome of checking a revealed value against the commitments of a witness handle.
+//
+// # Variants:
+// * Valid - The reveal matches its sender's commitment.
+// * Mismatch - The reveal does not match its sender's commitment, whose digest is enclosed.
+// * Pending - The sender's commitment has not been delivered yet; the reveal is kept until it is.
+pub enum RevealStatus<T> {
+    Valid(Message<T>),
+    Mismatch(Message<T>, u64),
+    Pending,
+}
+
+// # Struct Description:
+// This struct records the commitments delivered to a witness handle, and the reveals delivered before
+// their commitment. As commitments are reliably broadcast, every correct thread records the same one per
+// sender and round, and only the first is kept.
+//
+// # Fields:
+// * commitments - The committed digests per (reveal round, sender ID) pair.
+// * pending_reveals - The reveals awaiting their commitment per (reveal round, sender ID) pair.
+pub struct CommitmentLedger<T> {
+    commitments: HashMap<(Round, u32), u64>,
+    pending_reveals: HashMap<(Round, u32), Message<T>>,
+}
+
+impl<T> CommitmentLedger<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new() -> Self {
+        let commitments = HashMap::new();
+        let pending_reveals = HashMap::new();
+        Self {
+            commitments,
+            pending_reveals
+        }
+    }
+
+    // # Method Description:
+    // This method records a commitment.
+    //
+    // # Parameters:
+    // * commitment - The delivered `Commitment`.
+    //
+    // # Returns:
+    // * The reveal that was awaiting the commitment, if any, to be checked again.
+    pub fn commit(&mut self, commitment: Commitment) -> Option<Message<T>> {
+        let key = (commitment.get_round_number() + 1, commitment.get_id());
+        self.commitments.entry(key).or_insert(commitment.get_digest());
+        self.pending_reveals.remove(&key)
+    }
+
+    // # Method Description:
+    // This method checks a revealed value against its sender's commitment, keeping it if the commitment is missing.
+    //
+    // # Parameters:
+    // * reveal - The revealed value.
+    //
+    // # Returns:
+    // * The `RevealStatus` of the reveal.
+    pub fn check(&mut self, reveal: Message<T>) -> RevealStatus<T> {
+        let key = (reveal.get_round_number(), reveal.get_id());
+        match self.commitments.get(&key) {
+            Some(digest) if *digest == reveal.get_digest() => RevealStatus::Valid(reveal),
+            Some(digest) => RevealStatus::Mismatch(reveal, *digest),
+            None => {
+                self.pending_reveals.insert(key, reveal);
+                RevealStatus::Pending
+            },
+        }
+    }
+}
+
+impl<T> Default for CommitmentLedger<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// # Enum Description:
+// This enum represents a command sent by a communicator to its witness handle.
+//
+// # Variants:
+// * Subscribe - Requests the values delivered for a (protocol, round) pair, answered on the enclosed 
+//   `oneshot::Sender` once the round is delivered, or immediately if it already was.
+// * Inspect - Requests a snapshot of a round, answered immediately on the enclosed `oneshot::Sender`
+//   (with `None` if the handle has not seen the round).
+// * Snapshot - Requests the monitor of every round held by the handle, answered immediately on the enclosed
+//   `oneshot::Sender` (see `ThreadSnapshot`).
+// * Shutdown - Requests the handle to flush the frames it received and stop, answered with its
+//   `ShutdownReport` on the enclosed `oneshot::Sender`.
+pub enum WitnessHandleCommand<T> 
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    Subscribe(String, Round, RoundSubscriber<T>),
+    Inspect(Round, InspectResponder<Option<WitnessRoundSnapshot>>),
+    Snapshot(InspectResponder<BTreeMap<Round, WitnessRoundMonitor<T>>>),
+    Shutdown(ShutdownResponder),
+}
+
+pub type RoundSubscriber<T> = oneshot::Sender<Vec<Message<T>>>;
+
+// # Struct Description:
+// This struct records the rounds delivered by a witness handle and the subscribers waiting on rounds 
+// that are still pending, so that a subscription succeeds regardless of when it is made.
+//
+// # Fields:
+// * completed - The values delivered per (protocol, round) pair.
+// * subscribers - The subscribers waiting per (protocol, round) pair that has not been delivered yet.
+pub struct RoundSubscriptions<T> {
+    completed: HashMap<(String, Round), Vec<Message<T>>>,
+    subscribers: HashMap<(String, Round), Vec<RoundSubscriber<T>>>,
+}
+
+impl<T> RoundSubscriptions<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    pub fn new() -> Self {
+        let completed = HashMap::new();
+        let subscribers = HashMap::new();
+        Self {
+            completed,
+            subscribers
+        }
+    }
+
+    // # Method Description:
+    // This method registers a subscriber for a round, answering it immediately if the round was already delivered.
+    // The subscribers of the round whose subscription was dropped meanwhile are released.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol whose delivery is subscribed to.
+    // * round_number - The round whose delivery is subscribed to.
+    // * responder - The `oneshot::Sender` on which the delivered values are sent.
+    pub fn subscribe(&mut self, protocol_information: String, round_number: Round, responder: RoundSubscriber<T>) {
+        match self.completed.get(&(protocol_information.clone(), round_number)) {
+            Some(values) => {
+                let _ = responder.send(values.clone());
+            },
+            None => {
+                let subscribers = self.subscribers.entry((protocol_information, round_number)).or_default();
+                subscribers.retain(|subscriber| !subscriber.is_closed());
+                subscribers.push(responder);
+            },
+        }
+    }
+
+    // # Method Description:
+    // This method records the delivery of a round and answers every subscriber waiting on it.
+    //
+    // # Parameters:
+    // * protocol_information - The protocol that delivered the round.
+    // * round_number - The delivered round.
+    // * values - The values delivered in the round.
+    pub fn complete(&mut self, protocol_information: String, round_number: Round, values: &[Message<T>]) {
+        let key = (protocol_information, round_number);
+        if let Some(subscribers) = self.subscribers.remove(&key) {
+            for responder in subscribers {
+                let _ = responder.send(values.to_vec());
+            }
+        }
+        self.completed.insert(key, values.to_vec());
+    }
+
+    // # Method Description:
+    // This method forgets the values delivered in a round, for every protocol, once the round is collected.
+    //
+    // # Parameters:
+    // * round_number - The collected round.
+    pub fn forget(&mut self, round_number: Round) {
+        self.completed.retain(|(_, completed_round), _| *completed_round != round_number);
+    }
+}
+
+impl<T> Default for RoundSubscriptions<T>
+where 
+    T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// # Enum Description:
+// This enum represents the order in which witness handles keep the values collected in a round. The
+// same order is used in the reports they broadcast and in the values they deliver.
+//
+// # Variants:
+// * Canonical - Values are ordered by sender ID, then by digest, so that every thread holding the same
+//   values reports and delivers them identically.
+// * Arrival - Values are kept in the order they were received, e.g. to study message timing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ValueOrdering {
+    Canonical,
+    Arrival,
+}
+
+impl ValueOrdering {
+    // # Method Description:
+    // This method adds a value to the values collected in a round, at the position given by the ordering.
+    //
+    // # Parameters:
+    // * values - The values collected so far, already in this ordering.
+    // * message - The value to add.
+    pub fn insert<T>(&self, values: &mut Vec<Message<T>>, message: Message<T>)
+    where 
+        T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
+    {
+        match self {
+            ValueOrdering::Canonical => {
+                let key = (message.get_id(), message.get_digest());
+                let position = values.partition_point(|value| (value.get_id(), value.get_digest()) <= key);
+                values.insert(position, message);
+            },
+            ValueOrdering::Arrival => values.push(message),
+        }
+    }
+}
+
+// # Enum Description:
+// This enum represents the values a witness round starts from (see `ClusterConfig::with_round_values`).
+// It applies to the witness protocol; aggregated witness rounds always start empty.
+//
+// # Variants:
+// * PerRound - Every round starts empty, and holds only the values broadcast in it.
+// * Cumulative - Every round starts from the values collected in the latest earlier round the handle
+//   holds, values arriving late in a round are carried into the later rounds, and a round is delivered
+//   after the previous one, so that the values delivered only grow from round to round, e.g. for protocols
+//   building monotone knowledge, without the applications re-broadcasting the values of earlier rounds.
+//   Carried values do not count towards the values a round needs before the thread reports it. Rounds
+//   collected by the retention policy are no longer carried from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RoundValues {
+    PerRound,
+    Cumulative,
+}
+
 // # Struct Description:
 // This struct monitors the progress of a single witness round, tracking its content, state, and counts.
 //
@@ -756,6 +1900,8 @@ where
 // * content - The messages, reports, and aggregated reports/witnesses collected in this round.
 // * state - The current state flags indicating which milestones have been reached.
 // * count - Counters for how many messages, reports, and witnesses have been observed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct WitnessRoundMonitor<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
@@ -779,6 +1925,38 @@ where
             count
         }
     }
+
+    // # Function Description:
+    // This function creates the monitor of a round under `RoundValues::Cumulative`, holding the values
+    // collected in an earlier round, none of which count towards the values of the round.
+    //
+    // # Parameters:
+    // * previous - The monitor of the latest earlier round, if the handle holds one.
+    pub fn carried_over(previous: Option<&Self>) -> Self {
+        let mut monitor = Self::new();
+        if let Some(previous) = previous {
+            monitor.content.values = previous.content.values.clone();
+        }
+        monitor
+    }
+
+    // # Method Description:
+    // This method carries a value of an earlier round into this round, without counting it, unless the
+    // round was already delivered or holds the value.
+    //
+    // # Parameters:
+    // * message - The value.
+    // * value_ordering - The order in which the values of the round are kept.
+    //
+    // # Returns:
+    // * Whether the value was carried.
+    pub fn carry(&mut self, message: &Message<T>, value_ordering: ValueOrdering) -> bool {
+        if self.state.witnesses || self.content.values.contains(message) {
+            return false
+        }
+        value_ordering.insert(&mut self.content.values, message.clone());
+        true
+    }
 }
 // # Struct Description:
 // This struct represents the completion state of a witness round.
@@ -787,10 +1965,13 @@ where
 // * report - Indicates whether a report has been reliably broadcasted.
 // * witnesses - Indicates whether witnesses have been collected.
 // * aggregated_witnesses - Indicates whether aggregated witnesses have been collected.
+// * nested_witnesses - The aggregation levels above 2 whose witnesses have been collected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WitnessRoundState {
     pub report: bool,
     pub witnesses: bool,
-    pub aggregated_witnesses: bool
+    pub aggregated_witnesses: bool,
+    pub nested_witnesses: BTreeSet<u32>,
 }
 
 impl WitnessRoundState {
@@ -798,10 +1979,28 @@ impl WitnessRoundState {
         let report = false; 
         let witnesses = false;
         let aggregated_witnesses = false;
+        let nested_witnesses = BTreeSet::new();
         Self {
             report,
             witnesses,
-            aggregated_witnesses
+            aggregated_witnesses,
+            nested_witnesses
+        }
+    }
+
+    // # Method Description:
+    // This method checks whether the witnesses of an aggregation level have been collected.
+    //
+    // # Parameters:
+    // * level - The aggregation level, starting at 1.
+    //
+    // # Returns:
+    // * `true` if the level completed.
+    pub fn is_level_complete(&self, level: u32) -> bool {
+        match level {
+            1 => self.witnesses,
+            2 => self.aggregated_witnesses,
+            _ => self.nested_witnesses.contains(&level),
         }
     }
 }
@@ -810,11 +2009,15 @@ impl WitnessRoundState {
 // This struct holds all collected data during a witness round.
 //
 // # Fields:
-// * values - Messages collected in the current round.
+// * values - Messages collected in the current round, in the configured `ValueOrdering`.
 // * reports - Reports received from threads.
 // * witnesses - Reports validated as witnesses.
 // * aggregated_reports - Aggregated reports collected in the round.
 // * aggregated_witnesses - Aggregated witness reports collected in the round.
+// * nested_reports - Aggregated reports of the levels above 2 collected in the round, by level.
+// * nested_witnesses - Aggregated witness reports of the levels above 2 collected in the round, by level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct WitnessRoundContent<T>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
@@ -827,8 +2030,10 @@ where
     pub barycentric_witnesses: Vec<Report<Vec<T>>>,
     pub aggregated_reports: Vec<AggregatedReport<T>>,
     pub aggregated_witnesses: Vec<AggregatedReport<T>>,
+    pub nested_reports: BTreeMap<u32, Vec<AggregatedReport<T>>>,
+    pub nested_witnesses: BTreeMap<u32, Vec<AggregatedReport<T>>>,
     pub dimension: Option<u32>, 
-    pub instance_number: u32, 
+    pub instance_number: Instance, 
 }
 
 impl<T> WitnessRoundContent<T>
@@ -844,8 +2049,10 @@ where
         let barycentric_witnesses = vec![];
         let aggregated_reports = vec![];
         let aggregated_witnesses = vec![];
+        let nested_reports = BTreeMap::new();
+        let nested_witnesses = BTreeMap::new();
         let dimension = None;
-        let instance_number = 0; 
+        let instance_number = Instance(0);
 
         Self {
             values,
@@ -856,10 +2063,40 @@ where
             barycentric_witnesses,
             aggregated_reports,
             aggregated_witnesses,
+            nested_reports,
+            nested_witnesses,
             dimension,
             instance_number
         }
     }
+
+    // # Method Description:
+    // This method computes the digest of the values collected in the round, gossiped to detect diverging
+    // nodes (see `DigestGossip`). The digest does not depend on the `ValueOrdering`, so that nodes holding
+    // the same values agree on it.
+    //
+    // # Returns:
+    // * The digest of the round.
+    pub fn get_digest(&self) -> u64 {
+        combine_digests(self.values.iter().map(|value| (value.get_id(), value.get_digest())).collect())
+    }
+
+    // # Method Description:
+    // This method provides the aggregated witnesses of an aggregation level, i.e. the reports a report of
+    // the level above is built from. Level 1 witnesses are plain `Report`s, kept in `witnesses`.
+    //
+    // # Parameters:
+    // * level - The aggregation level, at least 2.
+    //
+    // # Returns:
+    // * The aggregated witnesses of the level collected so far.
+    pub fn get_level_witnesses(&self, level: u32) -> &[AggregatedReport<T>] {
+        match level {
+            0 | 1 => panic!("Error: level {level} witnesses are not aggregated reports"),
+            2 => &self.aggregated_witnesses,
+            _ => self.nested_witnesses.get(&level).map(Vec::as_slice).unwrap_or_default(),
+        }
+    }
 }
 
 // # Struct Description:
@@ -871,12 +2108,17 @@ where
 // * witnesses - Count of validated witness reports.
 // * aggregated_reports - Count of aggregated reports received.
 // * aggregated_witnesses - Count of aggregated witnesses collected.
+// * nested_reports - Count of aggregated reports of the levels above 2 received, by level.
+// * nested_witnesses - Count of aggregated witnesses of the levels above 2 collected, by level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WitnessRoundCount {
-    pub values: u32,
-    pub reports: u32,
-    pub witnesses: u32,
-    pub aggregated_reports: u32,
-    pub aggregated_witnesses: u32
+    pub values: u64,
+    pub reports: u64,
+    pub witnesses: u64,
+    pub aggregated_reports: u64,
+    pub aggregated_witnesses: u64,
+    pub nested_reports: BTreeMap<u32, u64>,
+    pub nested_witnesses: BTreeMap<u32, u64>,
 }
 
 impl WitnessRoundCount {
@@ -886,12 +2128,48 @@ impl WitnessRoundCount {
         let witnesses = 0; 
         let aggregated_reports = 0; 
         let aggregated_witnesses = 0; 
+        let nested_reports = BTreeMap::new();
+        let nested_witnesses = BTreeMap::new();
         Self {
             values,
             reports,
             witnesses,
             aggregated_reports,
-            aggregated_witnesses
+            aggregated_witnesses,
+            nested_reports,
+            nested_witnesses
+        }
+    }
+
+    // # Method Description:
+    // This method provides the number of reports received at an aggregation level.
+    //
+    // # Parameters:
+    // * level - The aggregation level, starting at 1.
+    //
+    // # Returns:
+    // * The number of reports (level 1) or aggregated reports (level 2 and above) received.
+    pub fn get_level_reports(&self, level: u32) -> u64 {
+        match level {
+            1 => self.reports,
+            2 => self.aggregated_reports,
+            _ => self.nested_reports.get(&level).copied().unwrap_or_default(),
+        }
+    }
+
+    // # Method Description:
+    // This method provides the number of witnesses collected at an aggregation level.
+    //
+    // # Parameters:
+    // * level - The aggregation level, starting at 1.
+    //
+    // # Returns:
+    // * The number of witnesses (level 1) or aggregated witnesses (level 2 and above) collected.
+    pub fn get_level_witnesses(&self, level: u32) -> u64 {
+        match level {
+            1 => self.witnesses,
+            2 => self.aggregated_witnesses,
+            _ => self.nested_witnesses.get(&level).copied().unwrap_or_default(),
         }
     }
 }
@@ -900,3 +2178,27 @@ impl<T> JsonConversion<Vec<Message<T>>> for Vec<Message<T>>
 where 
     T: Debug + Clone + Serialize +  DeserializeOwned + PartialEq + Eq + Hash,
 {}
+
+// # Function Description:
+// This function describes witness broadcast as the witness handle runs it (see `WitnessHandleLoop`), for
+// the tooling reading `ProtocolDescriptor`s.
+//
+// # Parameters:
+// * config - The configuration of the cluster, in which the quorums are evaluated.
+//
+// # Returns:
+// * The descriptor of witness broadcast.
+pub fn protocol_descriptor(config: &ClusterConfig) -> ProtocolDescriptor {
+    ProtocolDescriptor::new(Protocol::Witness, "witness", "witness broadcast: every correct thread delivers the values of a round, which include the values of every other correct thread delivering it but at most f", config)
+        .with_builds_on(&[Protocol::Reliable])
+        .with_message_type(MessageTypeDescriptor::new("Value", "ObjectContent::Message", Lane::Report, "the value of a thread in the round, reliably broadcast with witness_broadcast"))
+        .with_message_type(MessageTypeDescriptor::new("Commitment", "ObjectContent::Commitment", Lane::Report, "the digest of a value revealed in the next round, reliably broadcast with witness_commit"))
+        .with_message_type(MessageTypeDescriptor::new("Report", "ObjectContent::Report", Lane::Report, "the values a thread received once they reached the validity quorum, reliably broadcast"))
+        .with_message_type(MessageTypeDescriptor::new("Collection", "ReportType::Witness", Lane::Basic, "the delivered values of the round, queued for witness_collect"))
+        .with_phase(PhaseDescriptor::new("values", "every thread reliably broadcasts its value, and reports the values it received once they reach the validity quorum")
+            .with_threshold("Value", Quorum::Validity)
+            .with_sends(&["Report"]), config)
+        .with_phase(PhaseDescriptor::new("witnesses", "a report becomes a witness once the thread received every value it holds; the thread delivers the values it received once the witnesses reach the validity quorum")
+            .with_threshold("Report", Quorum::Validity)
+            .with_sends(&["Collection"]), config)
+}
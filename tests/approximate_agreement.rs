@@ -0,0 +1,74 @@
+use futures::future::join_all;
+use rust_project::approximate_agreement::{ApproximateAgreement, FixedPoint};
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::reliable::ReliableCommunication;
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+const EPSILON: f64 = 0.001;
+const BOUNDS: (f64, f64) = (0.0, 100.0);
+
+// # Function Description:
+// This function provides the input of a node in an agreement: the inputs are spread over the bounds, in
+// reverse order in the second agreement.
+//
+// # Returns:
+// * The input.
+fn input(id: u32, agreement: u32) -> f64 {
+    let rank = if agreement == 0 { id } else { THREAD_COUNT - 1 - id };
+    BOUNDS.1 * rank as f64 / (THREAD_COUNT - 1) as f64
+}
+
+// # Function Description:
+// This function runs two agreements in a row over the same witness communicators, on the first nodes of a
+// cluster while the others stay silent. In both agreements, the outputs of the running nodes must be within
+// epsilon of each other and within the range of their inputs.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * running - The number of running nodes.
+async fn agree_twice(config: ClusterConfig, running: u32) {
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub: WitnessHub<FixedPoint> = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+    let (mut threads, mut silent_communicators) = (vec![], vec![]);
+    for id in 0..THREAD_COUNT {
+        let communicator = witness_hub.create_witness_communicator();
+        if id >= running {
+            silent_communicators.push(communicator);
+            continue
+        }
+        let mut agreement = ApproximateAgreement::new(communicator, EPSILON, BOUNDS).unwrap();
+        threads.push(tokio::spawn(async move {
+            let reliable_handle = agreement.get_communicator().initialize_reliable_handle();
+            let witness_handle = agreement.get_communicator().initialize_witness_handle();
+            agreement.agree(input(id, 0)).await;
+            agreement.agree(input(id, 1)).await;
+            agreement.get_communicator().terminate_witness_handle(witness_handle);
+            agreement.get_communicator().terminate_reliable_handle(reliable_handle);
+            agreement.get_decisions().clone()
+        }));
+    }
+    let outputs: Vec<Vec<f64>> = join_all(threads).await.into_iter().map(|thread| thread.unwrap()).collect();
+    drop(silent_communicators);
+    for agreement in 0..2 {
+        let inputs: Vec<f64> = (0..running).map(|id| input(id, agreement)).collect();
+        let (lowest_input, highest_input) = (inputs.iter().cloned().fold(f64::MAX, f64::min), inputs.iter().cloned().fold(f64::MIN, f64::max));
+        let decided: Vec<f64> = outputs.iter().map(|decisions| decisions[agreement as usize]).collect();
+        let (lowest, highest) = (decided.iter().cloned().fold(f64::MAX, f64::min), decided.iter().cloned().fold(f64::MIN, f64::max));
+        assert!(highest - lowest <= EPSILON, "agreement {agreement}: outputs in [{lowest}, {highest}]");
+        assert!(lowest >= lowest_input && highest <= highest_input, "agreement {agreement}: outputs in [{lowest}, {highest}], inputs in [{lowest_input}, {highest_input}]");
+    }
+}
+
+#[tokio::test]
+async fn nodes_agree_within_epsilon() {
+    agree_twice(ClusterConfig::new(THREAD_COUNT), THREAD_COUNT).await;
+}
+
+// Under `n - f` quorums, the running nodes complete their rounds without the silent node.
+#[tokio::test]
+async fn nodes_agree_within_epsilon_without_a_silent_node() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap();
+    agree_twice(config, THREAD_COUNT - 1).await;
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::barycentric_agreement::{BarycentricCommunication, BarycentricCommunicator, BarycentricHub};
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::ReliableCommunication;
+use rust_project::round::Round;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const DIMENSIONS: u32 = 3;
+
+// # Function Description:
+// This function provides the coordinate a node proposes in a dimension.
+//
+// # Returns:
+// * The coordinate.
+fn coordinate(id: u32, dimension: u32) -> String {
+    format!("coordinate {dimension} of node {id}")
+}
+
+// Every node proposes a point of three coordinates in round 0, then a one-dimensional value in round 1 over
+// the same handles: every dimension of round 0 holds a quorum of trusted coordinates of that dimension only,
+// proposed by their senders, and round 1 is collected.
+#[tokio::test]
+async fn every_dimension_collects_a_quorum_of_its_coordinates() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let quorum = (THREAD_COUNT - config.get_thresholds().get_faulty_threads() as u32) as usize;
+    let channels = ChannelTransport::create_channels(&config);
+    let mut barycentric_hub = BarycentricHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut barycentric_communicator: BarycentricCommunicator<String> = barycentric_hub.create_barycentric_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = barycentric_communicator.initialize_reliable_handle();
+            let barycentric_handle = barycentric_communicator.initialize_barycentric_handle();
+            barycentric_communicator.barycentric_agreement_nd((0..DIMENSIONS).map(|dimension| coordinate(id, dimension)).collect(), Round(0)).await;
+            let collections = barycentric_communicator.barycentric_collect_nd(Round(0), DIMENSIONS).await;
+            barycentric_communicator.barycentric_agreement(format!("barycentric agreement broadcast message by {id}"), Round(1)).await;
+            let collected = barycentric_communicator.barycentric_collect_with_timeout(Round(1), Duration::from_secs(10)).await;
+            barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+            barycentric_communicator.terminate_reliable_handle(reliable_handle);
+
+            assert_eq!(collections.len() as u32, DIMENSIONS, "id {id}");
+            for (dimension, messages) in collections.iter().enumerate() {
+                assert!(messages.len() >= quorum, "id {id}, dimension {dimension}: {messages:?}");
+                for message in messages {
+                    assert_eq!(message.get_dimension(), Some(dimension as u32), "id {id}");
+                    assert_eq!(*message.get_message(), coordinate(message.get_id(), dimension as u32), "id {id}");
+                }
+            }
+            assert!(collected.is_ok(), "id {id}: {collected:?}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
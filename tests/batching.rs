@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const ROUNDS: u32 = 4;
+
+// # Function Description:
+// This function makes every node reliably broadcast in a few rounds at once, and deliver every message.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The number of signals the cluster sent, and of signal batches.
+async fn broadcast_rounds(config: ClusterConfig) -> (u64, u64) {
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for round in 0..ROUNDS {
+                reliable_communicator.reliable_broadcast(format!("message of {id} in round {round}"), Instance(0), Round(round)).await;
+            }
+            for round in 0..ROUNDS {
+                for sender in 0..THREAD_COUNT {
+                    let delivered = reliable_communicator.reliable_recv_with_timeout(Some(sender), Instance(0), Round(round), Duration::from_secs(5)).await;
+                    assert_eq!(delivered.map(|message| message.get_message().clone()), Ok(format!("message of {sender} in round {round}")), "id {id}");
+                }
+            }
+            barrier.wait().await;
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+            let metrics = reliable_communicator.get_metrics();
+            let signals: u64 = (0..ROUNDS).map(|round| metrics.get_sent_signals("reliable", Round(round))).sum();
+            (signals, metrics.get_signal_batches().get_batches())
+        })
+    }).collect::<Vec<_>>();
+    join_all(threads).await.into_iter().map(|thread| thread.unwrap()).fold((0, 0), |(signals, batches), sent| (signals + sent.0, batches + sent.1))
+}
+
+#[tokio::test]
+async fn unbatched_signals_are_delivered() {
+    let (signals, batches) = broadcast_rounds(ClusterConfig::new(THREAD_COUNT)).await;
+    assert!(signals > 0);
+    assert_eq!(batches, 0);
+}
+
+// With the signals sent to the same peer batched for a millisecond, every message is delivered, and the
+// signals are sent in fewer frames.
+#[tokio::test]
+async fn batched_signals_are_delivered_in_fewer_frames() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_signal_batching(Duration::from_millis(1), 64);
+    let (signals, batches) = broadcast_rounds(config).await;
+    assert!(batches > 0 && batches < signals, "{signals} signals sent in {batches} frames");
+}
@@ -0,0 +1,66 @@
+use futures::future::join_all;
+use rust_project::binary_agreement::{BinaryAgreement, BinaryVote};
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+// # Function Description:
+// This function runs an agreement instance in which the correct threads all propose `true`, while the last
+// thread reliably broadcasts `false` in every step, as if it had been reported by a majority.
+//
+// # Returns:
+// * The decisions of the correct threads.
+async fn decide_against_byzantine_thread(thread_count: u32) -> Vec<bool> {
+    let config = ClusterConfig::new(thread_count);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<BinaryVote> = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut handles = vec![];
+    for _ in 0..thread_count - 1 {
+        let mut binary_agreement = BinaryAgreement::new(reliable_hub.create_reliable_communicator());
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = binary_agreement.get_communicator().initialize_reliable_handle();
+            binary_agreement.propose(true, Instance(1)).await;
+            let decision = binary_agreement.decide(Instance(1)).await;
+            binary_agreement.get_communicator().terminate_reliable_handle(reliable_handle);
+            decision
+        }));
+    }
+    let mut byzantine = reliable_hub.create_reliable_communicator();
+    let reliable_handle = byzantine.initialize_reliable_handle();
+    let votes = [BinaryVote::Estimate(false), BinaryVote::Majority(false), BinaryVote::Proposal(Some(false))];
+    for (round_number, vote) in votes.into_iter().cycle().take(6).enumerate() {
+        byzantine.reliable_broadcast(vote, Instance(1), Round(round_number as u32)).await;
+    }
+    let decisions = join_all(handles).await.into_iter().map(|result| result.unwrap()).collect();
+    byzantine.terminate_reliable_handle(reliable_handle);
+    decisions
+}
+
+#[tokio::test]
+async fn unanimous_correct_threads_decide_their_input() {
+    for thread_count in [4, 7] {
+        let decisions = decide_against_byzantine_thread(thread_count).await;
+        assert!(decisions.iter().all(|decision| *decision), "n = {thread_count}: {decisions:?}");
+    }
+}
+
+#[tokio::test]
+async fn split_proposals_reach_agreement() {
+    let config = ClusterConfig::new(4);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<BinaryVote> = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut handles = vec![];
+    for id in 0..4 {
+        let mut binary_agreement = BinaryAgreement::new(reliable_hub.create_reliable_communicator());
+        handles.push(tokio::spawn(async move {
+            let reliable_handle = binary_agreement.get_communicator().initialize_reliable_handle();
+            binary_agreement.propose(id % 2 == 0, Instance(1)).await;
+            let decision = binary_agreement.decide(Instance(1)).await;
+            binary_agreement.get_communicator().terminate_reliable_handle(reliable_handle);
+            decision
+        }));
+    }
+    let decisions: Vec<bool> = join_all(handles).await.into_iter().map(|result| result.unwrap()).collect();
+    assert!(decisions.iter().all(|decision| *decision == decisions[0]), "{decisions:?}");
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::basic::Message;
+use rust_project::byzantine::ByzantineCommunicator;
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::emulation::{LinkConditions, NetworkEmulation};
+use rust_project::faults::{ByzantineBehavior, Fault};
+use rust_project::json::JsonConversion;
+use rust_project::multiplex::{Lane, tag_frame};
+use rust_project::reliable::{ObjectContent, ReliableCommunication, ReliableCommunicator, ReliableHub, Signal, SignalType};
+use rust_project::round::{Instance, Round};
+use rust_project::trace::{DeliveryRecord, TraceChecker, Violation};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+
+const BYZANTINE: u32 = THREAD_COUNT - 1;
+
+const SEEDS: u64 = 10;
+
+const BEHAVIORS: [ByzantineBehavior; 3] = [ByzantineBehavior::Equivocate, ByzantineBehavior::DropEchoes, ByzantineBehavior::VoteWithoutEcho];
+
+// # Function Description:
+// This function runs node 0's instance 0 and the Byzantine thread's instance 1, and collects the message
+// every correct thread delivered for each instance within the timeout.
+//
+// # Returns:
+// * The messages delivered for instances 0 and 1, by correct thread.
+async fn run_instances(config: ClusterConfig, behavior: ByzantineBehavior, timeout: Duration) -> Vec<(Option<Message<String>>, Option<Message<String>>)> {
+    let config = config.with_fault(Fault::Byzantine(BYZANTINE, behavior));
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..BYZANTINE).map(|id| {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            let mut genuine = reliable_communicator.watch_delivery(Instance(0), Round(0));
+            let mut byzantine = reliable_communicator.watch_delivery(Instance(1), Round(0));
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+            }
+            let _ = tokio::join!(
+                tokio::time::timeout(timeout, async { genuine.wait_for(Option::is_some).await.is_ok() }),
+                tokio::time::timeout(timeout, async { byzantine.wait_for(Option::is_some).await.is_ok() }),
+            );
+            let delivered = (genuine.borrow().clone(), byzantine.borrow().clone());
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            delivered
+        })
+    }).collect::<Vec<_>>();
+    let mut byzantine_communicator = ByzantineCommunicator::new(reliable_hub.create_reliable_communicator(), behavior, |message: &String| format!("conflicting {message}"));
+    let byzantine_handle = byzantine_communicator.initialize_byzantine_handle();
+    byzantine_communicator.reliable_broadcast(format!("reliable broadcast message by {BYZANTINE}"), Instance(1), Round(0)).await;
+    let delivered = join_all(threads).await.into_iter().map(Result::unwrap).collect();
+    byzantine_communicator.terminate_byzantine_handle(byzantine_handle);
+    delivered
+}
+
+// # Function Description:
+// This function provides the configuration of a cluster whose links add up to 5 ms of jitter, so that the
+// signals of the conflicting payloads reach every thread in a different order from run to run.
+fn jittery_config(seed: u64, formula: QuorumFormula) -> ClusterConfig {
+    let emulation = NetworkEmulation::new().with_conditions(LinkConditions::new(Duration::from_millis(1), None).with_jitter(Duration::from_millis(5)));
+    ClusterConfig::new(THREAD_COUNT).with_seed(seed).with_network_emulation(emulation)
+        .with_quorum_config(QuorumConfig::new().with_formula(formula)).unwrap()
+}
+
+// # Function Description:
+// This function checks the deliveries of the correct threads for agreement with a `TraceChecker`. The
+// Byzantine thread's instance may be delivered by some correct threads only within the timeout, so
+// totality is not checked.
+fn assert_agreement(run: &str, delivered: &[(Option<Message<String>>, Option<Message<String>>)]) {
+    let mut checker = TraceChecker::new(THREAD_COUNT).with_faulty(BYZANTINE);
+    for (id, (genuine, byzantine)) in delivered.iter().enumerate() {
+        for message in [genuine, byzantine].into_iter().flatten() {
+            checker.check(&DeliveryRecord::from_message(id as u32, message));
+        }
+    }
+    let violations: Vec<Violation> = checker.finish().into_iter()
+        .filter(|violation| !matches!(violation, Violation::Totality(..)))
+        .collect();
+    assert!(violations.is_empty(), "{run}: {violations:?}");
+}
+
+#[tokio::test]
+async fn correct_threads_agree_despite_an_equivocating_thread() {
+    for formula in [QuorumFormula::NMinusFPlusOne, QuorumFormula::NMinusF] {
+        for seed in 0..SEEDS {
+            let delivered = run_instances(jittery_config(seed, formula), ByzantineBehavior::Equivocate, Duration::from_millis(300)).await;
+            assert_agreement(&format!("{formula:?}, seed {seed}"), &delivered);
+        }
+    }
+}
+
+// With Bracha's n - f quorums, the correct threads reach every quorum without the Byzantine thread, so they
+// deliver node 0's instance whatever it does.
+#[tokio::test]
+async fn correct_threads_deliver_and_agree_under_every_behavior() {
+    for behavior in BEHAVIORS {
+        for seed in 0..3 {
+            let delivered = run_instances(jittery_config(seed, QuorumFormula::NMinusF), behavior, Duration::from_secs(1)).await;
+            for (id, (genuine, _)) in delivered.iter().enumerate() {
+                assert!(genuine.is_some(), "{behavior:?}, seed {seed}: instance 0 not delivered by id {id}");
+            }
+            assert_agreement(&format!("{behavior:?}, seed {seed}"), &delivered);
+        }
+    }
+}
+
+// A Byzantine thread floods the other threads with copies of its Echo and Vote for an instance no one
+// broadcast, many more than the validity threshold, followed by Echoes that do not name their sender. Every
+// thread counts it once per signal type and ignores the anonymous Echoes, so the fabricated instance never
+// reaches a quorum, while node 0's genuine instance is still delivered by every thread.
+#[tokio::test]
+async fn flooded_echoes_and_votes_are_counted_once() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let copies = config.get_thresholds().get_validity_threshold() * 3;
+    let channels = ChannelTransport::create_channels(&config);
+    let injector = channels.transmitters.clone();
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config.clone());
+
+    let message = Message::new(String::from("reliable"), 0, String::from("fabricated message"), None, Some(Instance(0)), Round(0));
+    let mut frames = vec![];
+    for signal_type in [SignalType::Echo, SignalType::Vote] {
+        let mut signal: serde_json::Value = serde_json::from_str(&Signal::new(signal_type, ObjectContent::Message(message.clone()), Instance(0), Round(0)).write_json()).unwrap();
+        signal["origin"] = BYZANTINE.into();
+        let signal = Signal::<String>::read_json(&signal.to_string()).unwrap();
+        let frame = tag_frame(Lane::Signal, signal.write_signal_frame(config.get_codec()));
+        frames.extend((0..copies).map(|_| frame.clone()));
+    }
+    let anonymous = tag_frame(Lane::Signal, Signal::new(SignalType::Echo, ObjectContent::Message(message), Instance(0), Round(0)).write_signal_frame(config.get_codec()));
+    frames.extend((0..copies).map(|_| anonymous.clone()));
+    for transmitter in &injector[..BYZANTINE as usize] {
+        for frame in &frames {
+            transmitter.send(frame.clone()).await.unwrap();
+        }
+    }
+
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+            }
+            let genuine = reliable_communicator.reliable_recv_with_timeout(Some(0), Instance(1), Round(0), Duration::from_secs(5)).await;
+            let snapshots = reliable_communicator.reliable_inspect().await.unwrap_or_default();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            assert!(genuine.is_ok(), "id {id}: {genuine:?}");
+            match snapshots.get(&format!("{id}::reliable::0::message::0::0")) {
+                Some(fabricated) => {
+                    assert_eq!((fabricated.get_echo_count(), fabricated.get_vote_count()), (1, 1), "id {id}");
+                    assert!(!fabricated.get_echoed() && !fabricated.get_voted() && !fabricated.get_delivered(), "id {id}: {fabricated:?}");
+                },
+                None => assert_eq!(id, BYZANTINE, "id {id}: the fabricated instance was never opened"),
+            }
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
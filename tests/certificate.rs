@@ -0,0 +1,90 @@
+use std::net::SocketAddr;
+use futures::future::join_all;
+use rust_project::certificate::DeliveryCertificate;
+use rust_project::config::ClusterConfig;
+use rust_project::identity::IdentityStore;
+use rust_project::json::JsonConversion;
+use rust_project::membership::MembershipDocument;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::signing::Authentication;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function provides the membership document of a cluster, listing the identities its seed generates.
+//
+// # Returns:
+// * The document.
+fn membership_document(config: &ClusterConfig) -> MembershipDocument {
+    let addresses: Vec<SocketAddr> = (0..THREAD_COUNT).map(|id| SocketAddr::from(([127, 0, 0, 1], 9000 + id as u16))).collect();
+    MembershipDocument::from_identities(&IdentityStore::generate_cluster(config), &addresses).unwrap()
+}
+
+// # Function Description:
+// This function runs a signed cluster in which every node reliably broadcasts an instance, and receives
+// every instance with its `DeliveryCertificate`, which must certify the delivered message and verify
+// against the membership document of the cluster.
+//
+// # Returns:
+// * The configuration and membership document of the cluster, and the certificate of the instance of node 0.
+async fn certify() -> (ClusterConfig, MembershipDocument, DeliveryCertificate<String>) {
+    let config = ClusterConfig::new(THREAD_COUNT).with_authentication(Authentication::Ed25519);
+    let document = membership_document(&config);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let document = document.clone();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            reliable_communicator.reliable_broadcast(format!("certified message by {id}"), Instance(id), Round(0)).await;
+            let mut certificates = vec![];
+            for sender in 0..THREAD_COUNT {
+                let (message, certificate) = reliable_communicator.reliable_recv_with_proof(Some(sender), Instance(sender), Round(0)).await;
+                assert_eq!(certificate.get_message(), &message, "id {id}");
+                assert!(certificate.verify(&document).is_ok(), "id {id}, sender {sender}");
+                certificates.push(certificate);
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            certificates.swap_remove(0)
+        })
+    }).collect::<Vec<_>>();
+    let certificate = join_all(threads).await.into_iter().map(|thread| thread.unwrap()).next().unwrap();
+    (config, document, certificate)
+}
+
+#[tokio::test]
+async fn delivery_certificates_verify_against_the_membership_document() {
+    let (_, document, certificate) = certify().await;
+    let certificate = DeliveryCertificate::<String>::read_json(&certificate.write_json()).unwrap();
+    assert!(certificate.verify(&document).is_ok());
+}
+
+// Copies of a certificate with another message, with too few Votes, or with a Vote counted twice are
+// rejected, and so is the certificate under the membership document of another cluster.
+#[tokio::test]
+async fn tampered_delivery_certificates_are_rejected() {
+    let (config, document, certificate) = certify().await;
+    let tamper = |edit: &dyn Fn(&mut serde_json::Value)| {
+        let mut value: serde_json::Value = serde_json::from_str(&certificate.write_json()).unwrap();
+        edit(&mut value);
+        DeliveryCertificate::<String>::read_json(&value.to_string()).unwrap()
+    };
+    let validity_threshold = document.get_thresholds().get_validity_threshold() as usize;
+    let other_document = membership_document(&config.clone().with_seed(config.get_seed() + 1));
+    let rejections = [
+        ("another message", tamper(&|value| value["message"]["message"] = "forged message".into()).verify(&document)),
+        ("too few votes", tamper(&|value| value["votes"].as_array_mut().unwrap().truncate(validity_threshold - 1)).verify(&document)),
+        ("a vote counted twice", tamper(&|value| {
+            let votes = value["votes"].as_array_mut().unwrap();
+            votes.truncate(validity_threshold - 1);
+            votes.push(votes[0].clone());
+        }).verify(&document)),
+        ("another cluster", certificate.verify(&other_document)),
+    ];
+    for (tampering, result) in rejections {
+        assert!(result.is_err(), "{tampering}: {result:?}");
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::chained_consensus::{ChainedConsensus, ChainedPayload, GENESIS, QuorumCertificate, SignedVote};
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::identity::IdentityStore;
+use rust_project::leader::LeaderElection;
+use rust_project::reliable::{ReliableCommunication, ReliableHub};
+use rust_project::signing::{Authentication, SignalSigner};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const ENTRIES: u32 = 2;
+
+fn signers() -> Vec<SignalSigner> {
+    let config = ClusterConfig::new(4).with_authentication(Authentication::Ed25519);
+    IdentityStore::generate_cluster(&config).iter().map(|identity| SignalSigner::from_identity(identity).unwrap()).collect()
+}
+
+#[test]
+fn certificates_hold_the_signed_votes_of_a_quorum() {
+    let signers = signers();
+    let votes: Vec<SignedVote> = signers[..3].iter().map(|signer| SignedVote::sign(5, 42, signer)).collect();
+    assert!(votes.iter().all(|vote| vote.verify(&signers[3])));
+    assert!(QuorumCertificate::new(5, 42, &votes).is_valid(4, 3, &signers[3]));
+    assert!(!QuorumCertificate::new(5, 42, &votes[..2]).is_valid(4, 3, &signers[3]));
+    // votes for another block are not counted
+    assert!(!QuorumCertificate::new(5, 43, &votes).is_valid(4, 3, &signers[3]));
+    assert!(QuorumCertificate::new(0, GENESIS, &[]).is_valid(4, 3, &signers[3]));
+}
+
+#[test]
+fn forged_votes_are_rejected() {
+    let signers = signers();
+    let mut votes: Vec<SignedVote> = signers[..2].iter().map(|signer| SignedVote::sign(5, 42, signer)).collect();
+    // node 1 votes again in the name of node 2
+    let mut forged = serde_json::to_value(SignedVote::sign(5, 42, &signers[1])).unwrap();
+    forged["voter"] = 2.into();
+    let forged: SignedVote = serde_json::from_value(forged).unwrap();
+    assert!(!forged.verify(&signers[0]));
+    votes.push(forged);
+    assert!(!QuorumCertificate::new(5, 42, &votes).is_valid(4, 3, &signers[0]));
+
+    // a certificate whose votes are moved to another block
+    let votes: Vec<SignedVote> = signers[..3].iter().map(|signer| SignedVote::sign(5, 42, signer)).collect();
+    let mut moved = serde_json::to_value(QuorumCertificate::new(5, 42, &votes)).unwrap();
+    moved["block"] = 43.into();
+    let moved: QuorumCertificate = serde_json::from_value(moved).unwrap();
+    assert!(!moved.is_valid(4, 3, &signers[0]));
+}
+
+// # Function Description:
+// This function provides an entry a node submits.
+//
+// # Returns:
+// * The entry.
+fn entry(id: u32, index: u32) -> String {
+    format!("entry {index} of node {id}")
+}
+
+// # Function Description:
+// This function makes the first nodes of a signed cluster submit entries and run views of chained
+// consensus, while the others stay silent. The committed logs of the running nodes must be prefixes of each
+// other, hold entries, and hold only submitted entries, each once.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * running - The number of running nodes.
+// * views - The number of views every running node runs.
+//
+// # Returns:
+// * The committed logs of the running nodes.
+async fn commit_logs(config: ClusterConfig, running: u32, views: u32) -> Vec<Vec<String>> {
+    let config = config.with_authentication(Authentication::Ed25519);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<ChainedPayload<String>> = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let (mut threads, mut silent_communicators) = (vec![], vec![]);
+    let finished = Arc::new(Barrier::new(running as usize));
+    for id in 0..THREAD_COUNT {
+        let communicator = reliable_hub.create_reliable_communicator();
+        if id >= running {
+            silent_communicators.push(communicator);
+            continue
+        }
+        let mut chained_consensus = ChainedConsensus::new(communicator).with_view_timeout(Duration::from_millis(300));
+        let finished = finished.clone();
+        threads.push(tokio::spawn(async move {
+            let reliable_handle = chained_consensus.get_communicator().initialize_reliable_handle();
+            for index in 0..ENTRIES {
+                chained_consensus.submit(entry(id, index));
+            }
+            let log: Vec<String> = chained_consensus.run(views).await.iter().map(|message| message.get_message().clone()).collect();
+            // keeps answering the signals of the others until every node ran its views
+            finished.wait().await;
+            chained_consensus.get_communicator().terminate_reliable_handle(reliable_handle);
+            log
+        }));
+    }
+    let logs: Vec<Vec<String>> = join_all(threads).await.into_iter().map(|thread| thread.unwrap()).collect();
+    drop(silent_communicators);
+
+    let submitted: BTreeSet<String> = (0..running).flat_map(|id| (0..ENTRIES).map(move |index| entry(id, index))).collect();
+    for log in &logs {
+        assert!(logs.iter().all(|other| log.starts_with(other) || other.starts_with(log)), "{logs:?}");
+        assert!(!log.is_empty(), "{logs:?}");
+        assert!(log.iter().all(|entry| submitted.contains(entry)), "{logs:?}");
+        assert_eq!(log.iter().collect::<BTreeSet<&String>>().len(), log.len(), "{logs:?}");
+    }
+    logs
+}
+
+// With every node running under round-robin leaders, every node commits every submitted entry.
+#[tokio::test]
+async fn every_node_commits_every_entry() {
+    let logs = commit_logs(ClusterConfig::new(THREAD_COUNT), THREAD_COUNT, ENTRIES * THREAD_COUNT + 4).await;
+    assert!(logs.iter().all(|log| log.len() == (ENTRIES * THREAD_COUNT) as usize), "{logs:?}");
+}
+
+// With the last node silent under seeded leaders, the views it leads time out and change, and the logs of
+// the running nodes still agree and grow.
+#[tokio::test]
+async fn views_led_by_a_silent_node_change() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_leader_election(LeaderElection::Seeded)
+        .with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap();
+    commit_logs(config, THREAD_COUNT - 1, 6 * THREAD_COUNT).await;
+}
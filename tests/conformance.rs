@@ -0,0 +1,41 @@
+use std::time::Duration;
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::conformance::{ConformanceProtocol, conformance_suite, run_case, tolerated_crash_faults};
+
+// # Function Description:
+// This function runs the conformance suite of the reliable and witness protocols on a cluster.
+//
+// # Returns:
+// * The name of every case that did not match its expectation.
+async fn failed_cases(config: &ClusterConfig) -> Vec<String> {
+    let mut failed = vec![];
+    for protocol in [ConformanceProtocol::Reliable, ConformanceProtocol::Witness] {
+        for case in conformance_suite(protocol, config) {
+            let result = run_case(&case, Duration::from_millis(500)).await;
+            if !result.get_passed() {
+                failed.push(format!("{protocol:?}, {}: expected {:?}, {} delivered", case.get_name(), case.get_expectation(), result.get_delivered()));
+            }
+        }
+    }
+    failed
+}
+
+// With the default `n - t + 1` quorums, a cluster of 7 threads tolerates t - 1 = 1 crashed thread rather
+// than the textbook t = 2, and stalls with 2.
+#[tokio::test]
+async fn default_quorums_tolerate_one_crash_less_than_t() {
+    let config = ClusterConfig::new(7);
+    assert_eq!(config.get_thresholds().get_faulty_threads(), 2);
+    assert_eq!(tolerated_crash_faults(&config), 1);
+    let failed = failed_cases(&config).await;
+    assert!(failed.is_empty(), "{failed:?}");
+}
+
+// With Bracha's `n - t` quorums, a cluster of 7 threads tolerates the textbook t = 2 crashed threads.
+#[tokio::test]
+async fn bracha_quorums_tolerate_t_crashes() {
+    let config = ClusterConfig::new(7).with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap();
+    assert_eq!(tolerated_crash_faults(&config), 2);
+    let failed = failed_cases(&config).await;
+    assert!(failed.is_empty(), "{failed:?}");
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::basic::Message;
+use rust_project::config::ClusterConfig;
+use rust_project::consistent::ConsistentCommunication;
+use rust_project::json::JsonConversion;
+use rust_project::multiplex::{Lane, tag_frame};
+use rust_project::reliable::{ObjectContent, ReliableCommunication, ReliableCommunicator, ReliableHub, Signal, SignalType};
+use rust_project::round::{Instance, Round};
+use rust_project::signing::Authentication;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function runs every thread's consistent broadcast and reliable broadcast of an instance, and checks
+// that every thread delivers every broadcast. With a forger, a Vote for another instance of thread 0,
+// endorsed by a single thread, is also sent to every thread, which must not deliver it.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * forge - Whether to send the forged Vote.
+//
+// # Returns:
+// * The number of signals sent for consistent and for reliable broadcast.
+async fn run_consistent(config: ClusterConfig, forge: bool) -> (u64, u64) {
+    let channels = ChannelTransport::create_channels(&config);
+    let forger = channels.transmitters.clone();
+    let codec = config.get_codec();
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            reliable_communicator.consistent_broadcast(format!("consistent broadcast message by {id}"), Instance(0), Round(0)).await;
+            reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(0), Round(0)).await;
+            for sender in 0..THREAD_COUNT {
+                let consistent = reliable_communicator.consistent_recv(Some(sender), Instance(0), Round(0)).await;
+                assert_eq!(*consistent.get_message(), format!("consistent broadcast message by {sender}"), "id {id}");
+                let reliable = reliable_communicator.reliable_recv(Some(sender), Instance(0), Round(0)).await;
+                assert_eq!(*reliable.get_message(), format!("reliable broadcast message by {sender}"), "id {id}");
+            }
+            if forge {
+                let forged = tokio::time::timeout(Duration::from_millis(500), reliable_communicator.consistent_recv(Some(0), Instance(1), Round(0))).await;
+                assert!(forged.is_err(), "id {id}: {forged:?}");
+            }
+            barrier.wait().await;
+            let metrics = reliable_communicator.get_metrics();
+            let sent = (metrics.get_sent_signals("consistent", Round(0)), metrics.get_sent_signals("reliable", Round(0)));
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            sent
+        })
+    }).collect::<Vec<_>>();
+
+    if forge {
+        let message = Message::new(String::from("consistent"), 0, String::from("forged message"), None, Some(Instance(1)), Round(0));
+        let mut vote: serde_json::Value = serde_json::from_str(&Signal::new(SignalType::Vote, ObjectContent::Message(message), Instance(1), Round(0)).write_json()).unwrap();
+        vote["origin"] = 0.into();
+        vote["endorsements"] = serde_json::json!([{ "echoer": 0, "signature": null }]);
+        let vote = Signal::<String>::read_json(&vote.to_string()).unwrap();
+        let frame = tag_frame(Lane::Signal, vote.write_signal_frame(codec));
+        for transmitter in &forger {
+            transmitter.send(frame.clone()).await.unwrap();
+        }
+    }
+
+    let completed = tokio::time::timeout(Duration::from_secs(30), join_all(threads)).await.expect("a thread did not complete the run");
+    completed.into_iter().map(|thread| thread.unwrap()).fold((0, 0), |(consistent, reliable), sent| (consistent + sent.0, reliable + sent.1))
+}
+
+// Consistent broadcast sends at most half the signals of reliable broadcast, and a forged Vote with too few
+// endorsements is not delivered.
+#[tokio::test]
+async fn consistent_broadcast_is_delivered_with_half_the_signals() {
+    let (consistent, reliable) = run_consistent(ClusterConfig::new(THREAD_COUNT), true).await;
+    assert!(consistent * 2 <= reliable, "{consistent} signals sent for consistent broadcast, {reliable} for reliable broadcast");
+}
+
+#[tokio::test]
+async fn signed_consistent_broadcast_is_delivered_with_half_the_signals() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_authentication(Authentication::Ed25519);
+    let (consistent, reliable) = run_consistent(config, false).await;
+    assert!(consistent * 2 <= reliable, "{consistent} signals sent for consistent broadcast, {reliable} for reliable broadcast");
+}
@@ -0,0 +1,66 @@
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use bytes::Bytes;
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::dispersal::{DispersalCommunication, Fragment, data_fragment_count, disperse, reconstruct};
+use rust_project::json::JsonConversion;
+use rust_project::multiplex::{Lane, tag_frame};
+use rust_project::reliable::{ObjectContent, ReliableCommunication, ReliableCommunicator, ReliableHub, Signal, SignalType};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function provides a large payload, of varied bytes.
+//
+// # Returns:
+// * The payload.
+fn payload() -> Bytes {
+    Bytes::from((0..65536u32).map(|index| (index * 31 % 251) as u8).collect::<Vec<u8>>())
+}
+
+#[test]
+fn payload_is_recovered_from_the_parity_fragments_alone() {
+    let payload = payload();
+    let data_fragments = data_fragment_count(THREAD_COUNT, ClusterConfig::new(THREAD_COUNT).get_thresholds().get_faulty_threads() as u32);
+    let fragments = disperse(0, &payload, Round(0), THREAD_COUNT, data_fragments);
+    let parity: Vec<&Fragment> = fragments.iter().rev().take(data_fragments as usize).collect();
+    assert_eq!(reconstruct(&parity, THREAD_COUNT), Some(payload));
+}
+
+// Node 0 reliably broadcasts a large payload after a forger sent every node a copy of its fragment with
+// tampered data: every node discards the tampered fragment, and reconstructs the payload from the fragments
+// echoed by the others, although it only received a fragment of `1 / (n - 2t)` of the payload from the sender.
+#[tokio::test]
+async fn large_payload_is_delivered_despite_tampered_fragments() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let payload = payload();
+    let data_fragments = data_fragment_count(THREAD_COUNT, config.get_thresholds().get_faulty_threads() as u32);
+    let channels = ChannelTransport::create_channels(&config);
+    for (id, fragment) in disperse(0, &payload, Round(0), THREAD_COUNT, data_fragments).into_iter().enumerate() {
+        let mut forged: serde_json::Value = serde_json::from_str(&fragment.write_json()).unwrap();
+        forged["data"] = BASE64.encode(vec![0u8; fragment.decode_data().unwrap().len()]).into();
+        let forged = Fragment::read_json(&forged.to_string()).unwrap();
+        let input = Signal::<String>::new(SignalType::Input, ObjectContent::Fragment(forged), Instance(0), Round(0));
+        channels.transmitters[id].send(tag_frame(Lane::Signal, input.write_signal_frame(config.get_codec()))).await.unwrap();
+    }
+
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let payload = payload.clone();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast_large(payload.clone(), Instance(0), Round(0)).await;
+            }
+            let delivered = reliable_communicator.reliable_recv_large(0, Instance(0), Round(0)).await;
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            assert_eq!(delivered, Some(payload), "id {id}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
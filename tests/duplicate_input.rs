@@ -0,0 +1,130 @@
+use futures::future::join_all;
+use tokio::sync::broadcast;
+use rust_project::barycentric_agreement::{BarycentricCommunication, BarycentricHub};
+use rust_project::config::ClusterConfig;
+use rust_project::events::Event;
+use rust_project::reliable::{DuplicateInputPolicy, ReliableCommunication, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// The instance node 0 reliably broadcasts after its duplicate Input. Every frame reaching a thread goes
+// through its single inbound channel in order, so a thread that delivered the fence has processed the
+// duplicate, and published its event, already.
+const FENCE: Instance = Instance(1000);
+
+const POLICIES: [DuplicateInputPolicy; 3] = [DuplicateInputPolicy::FirstWins, DuplicateInputPolicy::Reject, DuplicateInputPolicy::Evidence];
+
+// # Function Description:
+// This function sends the fence from node 0 and waits for it, then takes the events the thread received.
+//
+// # Returns:
+// * The events about duplicate Inputs published on the thread.
+async fn fence<C: ReliableCommunication<String> + Send>(id: u32, communicator: &mut C, events: &mut broadcast::Receiver<Event<String>>) -> Vec<Event<String>> {
+    if id == 0 {
+        communicator.reliable_broadcast(String::from("fence"), FENCE, Round(0)).await;
+    }
+    communicator.reliable_recv(Some(0), FENCE, Round(0)).await;
+    let mut duplicates = vec![];
+    while let Ok(event) = events.try_recv() {
+        if matches!(event, Event::DuplicateInput(..) | Event::Equivocation(_)) {
+            duplicates.push(event);
+        }
+    }
+    duplicates
+}
+
+// # Function Description:
+// This function checks the events every thread observed against the policy.
+fn assert_policy(protocol: &str, policy: DuplicateInputPolicy, observed: Vec<Vec<Event<String>>>) {
+    for (id, events) in observed.into_iter().enumerate() {
+        let expected = match policy {
+            DuplicateInputPolicy::FirstWins => events.is_empty(),
+            DuplicateInputPolicy::Reject => matches!(events.as_slice(), [Event::DuplicateInput(..)]),
+            DuplicateInputPolicy::Evidence => matches!(events.as_slice(), [Event::Equivocation(_)]),
+        };
+        assert!(expected, "{protocol} handle, {policy:?}, id {id}: {events:?}");
+    }
+}
+
+#[tokio::test]
+async fn reliable_handle_applies_the_duplicate_input_policy() {
+    for policy in POLICIES {
+        let config = ClusterConfig::new(THREAD_COUNT).with_duplicate_input_policy(policy);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+            tokio::spawn(async move {
+                let mut events = reliable_communicator.subscribe_events();
+                let reliable_handle = reliable_communicator.initialize_reliable_handle();
+                if id == 0 {
+                    reliable_communicator.reliable_broadcast(String::from("first input by 0"), Instance(0), Round(0)).await;
+                    reliable_communicator.reliable_broadcast(String::from("second input by 0"), Instance(0), Round(0)).await;
+                }
+                let message = reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+                assert_eq!(message.get_message(), "first input by 0");
+                let duplicates = fence(id, &mut reliable_communicator, &mut events).await;
+                reliable_communicator.terminate_reliable_handle(reliable_handle);
+                duplicates
+            })
+        }).collect::<Vec<_>>();
+        assert_policy("reliable", policy, join_all(threads).await.into_iter().map(Result::unwrap).collect());
+    }
+}
+
+#[tokio::test]
+async fn witness_handle_applies_the_duplicate_input_policy() {
+    for policy in POLICIES {
+        let config = ClusterConfig::new(THREAD_COUNT).with_duplicate_input_policy(policy);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut witness_communicator = witness_hub.create_witness_communicator();
+            tokio::spawn(async move {
+                let mut events = witness_communicator.subscribe_events();
+                let reliable_handle = witness_communicator.initialize_reliable_handle();
+                let witness_handle = witness_communicator.initialize_witness_handle();
+                witness_communicator.witness_broadcast(format!("first input by {id}"), Round(0)).await;
+                if id == 0 {
+                    witness_communicator.witness_broadcast(String::from("second input by 0"), Round(0)).await;
+                }
+                witness_communicator.witness_collect(Round(0)).await;
+                let duplicates = fence(id, &mut witness_communicator, &mut events).await;
+                witness_communicator.terminate_reliable_handle(reliable_handle);
+                witness_communicator.terminate_witness_handle(witness_handle);
+                duplicates
+            })
+        }).collect::<Vec<_>>();
+        assert_policy("witness", policy, join_all(threads).await.into_iter().map(Result::unwrap).collect());
+    }
+}
+
+#[tokio::test]
+async fn barycentric_handle_applies_the_duplicate_input_policy() {
+    for policy in POLICIES {
+        let config = ClusterConfig::new(THREAD_COUNT).with_duplicate_input_policy(policy);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut barycentric_hub = BarycentricHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut barycentric_communicator = barycentric_hub.create_barycentric_communicator();
+            tokio::spawn(async move {
+                let mut events = barycentric_communicator.subscribe_events();
+                let reliable_handle = barycentric_communicator.initialize_reliable_handle();
+                let barycentric_handle = barycentric_communicator.initialize_barycentric_handle();
+                barycentric_communicator.barycentric_agreement(format!("first input by {id}"), Round(0)).await;
+                if id == 0 {
+                    barycentric_communicator.barycentric_agreement(String::from("second input by 0"), Round(0)).await;
+                }
+                barycentric_communicator.barycentric_collect(Round(0)).await;
+                let duplicates = fence(id, &mut barycentric_communicator, &mut events).await;
+                barycentric_communicator.terminate_reliable_handle(reliable_handle);
+                barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+                duplicates
+            })
+        }).collect::<Vec<_>>();
+        assert_policy("barycentric", policy, join_all(threads).await.into_iter().map(Result::unwrap).collect());
+    }
+}
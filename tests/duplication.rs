@@ -0,0 +1,169 @@
+use std::{sync::Arc, time::Duration};
+use bytes::Bytes;
+use futures::future::join_all;
+use rand::{SeedableRng, rngs::StdRng};
+use tokio::sync::{Barrier, mpsc};
+use rust_project::basic::{BasicCommunication, Message};
+use rust_project::config::ClusterConfig;
+use rust_project::emulation::{LinkConditioner, LinkConditions, NetworkEmulation};
+use rust_project::json::WireFormat;
+use rust_project::multiplex::{Lane, tag_frame};
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::retention::RetentionPolicy;
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function sends frames through a `LinkConditioner` and receives them on the other side.
+//
+// # Returns:
+// * The number of every frame received, in receiving order.
+async fn condition_frames(conditions: LinkConditions, seed: u64, frames: u32) -> Vec<u32> {
+    let (destination, mut received) = mpsc::channel(frames as usize + 1);
+    let link = LinkConditioner::new(conditions, StdRng::seed_from_u64(seed)).wrap(destination, frames as usize + 1);
+    for frame in 0..frames {
+        let _ = link.send(Bytes::from(frame.to_string())).await;
+    }
+    drop(link);
+    let mut delivered = vec![];
+    while let Some(frame) = received.recv().await {
+        delivered.push(String::from_utf8_lossy(&frame).parse().unwrap());
+    }
+    delivered
+}
+
+#[tokio::test]
+async fn links_deliver_the_configured_copies() {
+    let mut tripled = condition_frames(LinkConditions::new(Duration::ZERO, None).with_jitter(Duration::from_millis(5)).with_duplication(1.0, 3), 0, 200).await;
+    tripled.sort();
+    assert_eq!(tripled, (0..200).flat_map(|frame| [frame; 3]).collect::<Vec<u32>>());
+
+    let duplicated = condition_frames(LinkConditions::new(Duration::ZERO, None).with_duplication(0.5, 2), 0, 1000).await;
+    assert!((1400..=1600).contains(&duplicated.len()), "{} frames delivered for 1000 sent", duplicated.len());
+}
+
+// Every node basic broadcasts a message and node 0 reliably broadcasts one, over links delivering every
+// frame three times: each message is received once, each Echo and Vote counted once per sender, and the
+// two extra copies of every basic message counted as duplicates.
+#[tokio::test]
+async fn tripled_frames_are_received_and_counted_once() {
+    let ms = Duration::from_millis;
+    let conditions = LinkConditions::new(ms(1), None).with_jitter(ms(10)).with_reordering(0.5).with_duplication(1.0, 3);
+    let config = ClusterConfig::new(THREAD_COUNT).with_network_emulation(NetworkEmulation::new().with_conditions(conditions));
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            reliable_communicator.basic_broadcast(format!("basic message by {id}"), Round(0)).await;
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable message by 0"), Instance(1), Round(0)).await;
+            }
+            for sender in 0..THREAD_COUNT {
+                reliable_communicator.basic_recv(Some(sender), Round(0)).await;
+            }
+            reliable_communicator.reliable_recv(Some(0), Instance(1), Round(0)).await;
+            // every copy arrives within the latency and jitter of the links
+            let basic_again = reliable_communicator.basic_recv_with_timeout(None, Round(0), ms(200)).await;
+            let reliable_again = reliable_communicator.reliable_recv_with_timeout(None, Instance(1), Round(0), ms(50)).await;
+            barrier.wait().await;
+            let snapshots = reliable_communicator.reliable_inspect().await.unwrap_or_default();
+            let duplicates: u64 = reliable_communicator.metrics_report().get_duplicates().values().sum();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            assert!(basic_again.is_err(), "id {id}: {basic_again:?}");
+            assert!(reliable_again.is_err(), "id {id}: {reliable_again:?}");
+            assert!(!snapshots.is_empty(), "id {id}: no instance to inspect");
+            for snapshot in snapshots.values() {
+                assert_eq!((snapshot.get_echo_count(), snapshot.get_vote_count()), (THREAD_COUNT as u64, THREAD_COUNT as u64), "id {id}");
+            }
+            assert_eq!(duplicates, 2 * (THREAD_COUNT as u64 - 1), "id {id}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
+
+// # Function Description:
+// This function sends node 1 a basic message of node 0, as node 0's link would.
+async fn send_message(transmitter: &mpsc::Sender<Bytes>, config: &ClusterConfig, message: &str) {
+    let message = Message::new(String::from("basic"), 0, String::from(message), None, None, Round(0));
+    transmitter.send(tag_frame(Lane::Basic, message.write_frame(config.get_codec()))).await.unwrap();
+}
+
+// A message sent again after it was received is not received twice while its queues keep it, and is
+// received again once the retention policy dropped it, so that the queues do not grow with every message.
+#[tokio::test]
+async fn stored_messages_are_forgotten_following_the_retention_policy() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_retention_policy(RetentionPolicy::Capacity(1));
+    let channels = ChannelTransport::create_channels(&config);
+    let injector = channels.transmitters[1].clone();
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    let mut communicators: Vec<ReliableCommunicator<String>> = (0..THREAD_COUNT).map(|_| reliable_hub.create_reliable_communicator()).collect();
+    let receiver = &mut communicators[1];
+    let timeout = Duration::from_millis(100);
+
+    send_message(&injector, &config, "first message").await;
+    assert_eq!(receiver.basic_recv(Some(0), Round(0)).await.get_message(), "first message");
+    send_message(&injector, &config, "first message").await;
+    let repeated = receiver.basic_recv_with_timeout(Some(0), Round(0), timeout).await;
+    assert!(repeated.is_err(), "{repeated:?}");
+    assert_eq!(receiver.metrics_report().get_duplicates().get(&0), Some(&1));
+
+    for message in ["second message", "third message"] {
+        send_message(&injector, &config, message).await;
+        assert_eq!(receiver.basic_recv(Some(0), Round(0)).await.get_message(), message);
+    }
+    send_message(&injector, &config, "first message").await;
+    let forgotten = receiver.basic_recv_with_timeout(Some(0), Round(0), timeout).await;
+    assert!(forgotten.as_ref().is_ok_and(|message| message.get_message() == "first message"), "{forgotten:?}");
+}
+
+// Basic messages are numbered by their sender: sending the same value twice in a round delivers it twice,
+// whatever the retention policy, while a copy of one of them would still be dropped.
+#[tokio::test]
+async fn identical_sends_are_both_received() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut communicators: Vec<ReliableCommunicator<String>> = (0..THREAD_COUNT).map(|_| reliable_hub.create_reliable_communicator()).collect();
+    for _ in 0..2 {
+        communicators[0].basic_send(1, String::from("repeated message"), Round(0)).await;
+    }
+    let receiver = &mut communicators[1];
+    let first = receiver.basic_recv(Some(0), Round(0)).await;
+    let second = receiver.basic_recv_with_timeout(Some(0), Round(0), Duration::from_millis(100)).await;
+    assert_eq!(second.as_ref().map(|message| message.get_message()), Ok(first.get_message()));
+    assert_ne!(second.map(|message| message.get_sequence()), Ok(first.get_sequence()));
+    assert_eq!(receiver.metrics_report().get_duplicates().get(&0), None);
+}
+
+// Under the default `KeepAll` policy, the queues still forget the oldest messages past their window of
+// 4096, so that a copy of the first message of a long run is stored again rather than remembered forever.
+#[tokio::test]
+async fn stored_messages_are_forgotten_past_the_duplicate_window() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let injector = channels.transmitters[1].clone();
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    let mut communicators: Vec<ReliableCommunicator<String>> = (0..THREAD_COUNT).map(|_| reliable_hub.create_reliable_communicator()).collect();
+    let receiver = &mut communicators[1];
+
+    for number in 0..4096 {
+        send_message(&injector, &config, &format!("message {number}")).await;
+        receiver.basic_recv(Some(0), Round(0)).await;
+    }
+    send_message(&injector, &config, "message 0").await;
+    let remembered = receiver.basic_recv_with_timeout(Some(0), Round(0), Duration::from_millis(100)).await;
+    assert!(remembered.is_err(), "{remembered:?}");
+
+    send_message(&injector, &config, "message 4096").await;
+    receiver.basic_recv(Some(0), Round(0)).await;
+    send_message(&injector, &config, "message 0").await;
+    assert_eq!(receiver.basic_recv(Some(0), Round(0)).await.get_message(), "message 0");
+}
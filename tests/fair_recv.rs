@@ -0,0 +1,43 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::basic::{BasicCommunication, BasicCommunicator, BasicHub};
+use rust_project::config::ClusterConfig;
+use rust_project::round::Round;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+
+// Every thread but thread 0 sends 10 basic messages to thread 0, except thread 1, which floods it with 40,
+// before thread 0 receives them with `basic_recv(None, ..)`. The queues are drained round-robin: until the
+// other senders run out, every window of n - 1 consecutive messages holds one message of every sender.
+#[tokio::test]
+async fn flooding_sender_does_not_starve_the_others() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut basic_hub: BasicHub<String> = BasicHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut receiver: BasicCommunicator<String> = basic_hub.create_basic_communicator();
+    let senders = (1..THREAD_COUNT).map(|id| {
+        let mut basic_communicator: BasicCommunicator<String> = basic_hub.create_basic_communicator();
+        tokio::spawn(async move {
+            let count = if id == 1 { 40 } else { 10 };
+            for message_number in 0..count {
+                basic_communicator.basic_send(0, format!("message {message_number} from {id}"), Round(0)).await;
+            }
+        })
+    }).collect::<Vec<_>>();
+    for sender in join_all(senders).await {
+        sender.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut senders = vec![];
+    for _ in 0..(THREAD_COUNT - 2) * 10 + 40 {
+        senders.push(receiver.basic_recv(None, Round(0)).await.get_id());
+    }
+    let senders_per_window = (THREAD_COUNT - 1) as usize;
+    for window in senders[..senders_per_window * 10].chunks(senders_per_window) {
+        assert_eq!(window.iter().copied().collect::<BTreeSet<u32>>().len(), senders_per_window, "{senders:?}");
+    }
+    assert!(senders[senders_per_window * 10..].iter().all(|id| *id == 1), "{senders:?}");
+}
@@ -0,0 +1,38 @@
+use bytes::Bytes;
+use rust_project::basic::Message;
+use rust_project::events::DecodeFailure;
+use rust_project::json::{Codec, Frame, FrameKind, WireFormat};
+use rust_project::round::Round;
+use rust_project::witness::{Commitment, Report};
+
+// The kind of a frame is a constant tag of its payload type, written as the first 8 bytes of the frame,
+// so it must not change with the compiler or the crate version: threads built apart dispatch each other's
+// frames on it.
+#[test]
+fn frame_kinds_are_constant_tags() {
+    assert_eq!(<Message<String> as FrameKind>::FRAME_KIND, 1);
+    assert_eq!(<Report<String> as FrameKind>::FRAME_KIND, 3);
+    assert_eq!(<Commitment as FrameKind>::FRAME_KIND, 6);
+    assert_eq!(<DecodeFailure as FrameKind>::FRAME_KIND, 9);
+
+    let message = Message::new(String::from("basic"), 0, String::from("basic message"), None, None, Round(0));
+    for codec in [Codec::Json, Codec::Bincode, Codec::Cbor] {
+        let frame: Bytes = message.write_frame(codec);
+        assert_eq!(frame[..8], 1u64.to_be_bytes());
+        let parsed = Frame::parse(&frame).unwrap();
+        assert_eq!(parsed.get_kind(), 1);
+        assert!(parsed.is::<Message<String>>() && !parsed.is::<Report<String>>());
+        assert_eq!(parsed.read_payload::<Message<String>>(codec), Ok(message.clone()));
+    }
+}
+
+// A generic type has one tag whatever its parameters: the receiving hub decodes the payload with its own.
+#[test]
+fn generic_frame_types_share_their_tag() {
+    assert_eq!(<Message<String> as FrameKind>::FRAME_KIND, <Message<u32> as FrameKind>::FRAME_KIND);
+    let frame = Message::new(String::from("basic"), 0, 7u32, None, None, Round(0)).write_frame(Codec::Json);
+    let parsed = Frame::parse(&frame).unwrap();
+    assert!(parsed.is::<Message<String>>());
+    assert!(parsed.read_payload::<Message<String>>(Codec::Json).is_err());
+    assert!(parsed.read_payload::<Message<u32>>(Codec::Json).is_ok());
+}
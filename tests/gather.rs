@@ -0,0 +1,78 @@
+use std::collections::BTreeSet;
+use futures::future::join_all;
+use rust_project::basic::Message;
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::gather::{Gather, GatherPayload};
+use rust_project::reliable::{ReliableCommunication, ReliableHub};
+use rust_project::round::Round;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const ROUNDS: u32 = 2;
+
+// # Function Description:
+// This function provides the value a node gathers in a round.
+//
+// # Returns:
+// * The value.
+fn value(id: u32, round: u32) -> String {
+    format!("value {round} of node {id}")
+}
+
+// # Function Description:
+// This function makes the first nodes of a cluster gather their values in a few rounds, while the others
+// stay silent. In every round, every running node must output at least `n - t` values, each the value of
+// its sender, and the outputs must share a common core of at least `n - t` values.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * running - The number of running nodes.
+async fn gather_rounds(config: ClusterConfig, running: u32) {
+    let quorum = (THREAD_COUNT - config.get_thresholds().get_faulty_threads() as u32) as usize;
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<GatherPayload<String>> = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let (mut threads, mut silent_communicators) = (vec![], vec![]);
+    for id in 0..THREAD_COUNT {
+        let communicator = reliable_hub.create_reliable_communicator();
+        if id >= running {
+            silent_communicators.push(communicator);
+            continue
+        }
+        let mut gather = Gather::new(communicator);
+        threads.push(tokio::spawn(async move {
+            let reliable_handle = gather.get_communicator().initialize_reliable_handle();
+            let mut outputs = vec![];
+            for round in 0..ROUNDS {
+                outputs.push(gather.gather(value(id, round), Round(round)).await);
+            }
+            gather.get_communicator().terminate_reliable_handle(reliable_handle);
+            outputs
+        }));
+    }
+    let outputs: Vec<Vec<Vec<Message<String>>>> = join_all(threads).await.into_iter().map(|thread| thread.unwrap()).collect();
+    drop(silent_communicators);
+    for round in 0..ROUNDS {
+        let round_outputs: Vec<&Vec<Message<String>>> = outputs.iter().map(|outputs| &outputs[round as usize]).collect();
+        for output in &round_outputs {
+            assert!(output.len() >= quorum, "round {round}: {output:?}");
+            assert!(output.iter().all(|message| *message.get_message() == value(message.get_id(), round)), "round {round}: {output:?}");
+        }
+        let core = round_outputs.iter()
+            .map(|output| output.iter().map(|message| message.get_id()).collect::<BTreeSet<u32>>())
+            .reduce(|core, senders| core.intersection(&senders).copied().collect())
+            .unwrap_or_default();
+        assert!(core.len() >= quorum, "round {round}: common core {core:?}");
+    }
+}
+
+#[tokio::test]
+async fn gathered_values_share_a_common_core() {
+    gather_rounds(ClusterConfig::new(THREAD_COUNT), THREAD_COUNT).await;
+}
+
+// Under `n - t` quorums, the running nodes gather their values without the silent node.
+#[tokio::test]
+async fn gathered_values_share_a_common_core_without_a_silent_node() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap();
+    gather_rounds(config, THREAD_COUNT - 1).await;
+}
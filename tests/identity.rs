@@ -0,0 +1,19 @@
+use rust_project::config::ClusterConfig;
+use rust_project::identity::{IdentityStore, KeyPair};
+use rust_project::signing::Authentication;
+
+#[test]
+fn identity_is_created_once_and_reloaded() {
+    let path = std::env::temp_dir().join(format!("identity-test-{}.identity.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let created = IdentityStore::load_or_create(&path, 3).unwrap();
+    let reloaded = IdentityStore::load_or_create(&path, 3).unwrap();
+    assert_eq!(created, reloaded);
+    assert_eq!(created.get_key_pair().get_scheme(), KeyPair::ED25519);
+    assert!(IdentityStore::load_or_create(&path, 2).is_err());
+
+    let config = ClusterConfig::new(4).with_authentication(Authentication::Ed25519);
+    let seeded = IdentityStore::generate_cluster(&config).swap_remove(3);
+    assert_ne!(created.get_key_pair().get_public_key(), seeded.get_key_pair().get_public_key());
+    let _ = std::fs::remove_file(&path);
+}
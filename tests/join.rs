@@ -0,0 +1,77 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+// # Function Description:
+// This function reliably broadcasts one instance from a node, and receives it on every member.
+//
+// # Parameters:
+// * nodes - The communicators of every node that ever joined, with their reliable handles, indexed by ID.
+// * members - The IDs of the current members.
+// * origin - The ID of the broadcasting node.
+// * instance - The instance number.
+// * epoch - The epoch the instance is expected to be stamped with.
+async fn assert_delivered(nodes: &mut [(ReliableCommunicator<String>, JoinHandle<()>)], members: &[u32], origin: u32, instance: u32, epoch: u32) {
+    nodes[origin as usize].0.reliable_broadcast(format!("reliable broadcast message by {origin} in epoch {epoch}"), Instance(instance), Round(0)).await;
+    for id in members {
+        let delivered = nodes[*id as usize].0.reliable_recv_with_timeout(Some(origin), Instance(instance), Round(0), Duration::from_secs(1)).await;
+        assert!(delivered.as_ref().is_ok_and(|message| message.get_epoch() == epoch), "id {id}, epoch {epoch}: {delivered:?}");
+    }
+}
+
+// # Function Description:
+// This function runs a cluster through two membership changes. The nodes deliver an instance in epoch 0; a
+// new node joins, and every node, the new one included, delivers an instance it broadcasts in epoch 1, with
+// the thresholds of the grown cluster; node 1 then leaves, and the remaining nodes deliver an instance in
+// epoch 2 without it, while node 1 receives nothing and its own broadcasts are ignored.
+//
+// # Parameters:
+// * thread_count - The number of nodes before the join.
+async fn join_and_leave(thread_count: u32) {
+    let config = ClusterConfig::new(thread_count);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    let mut nodes = vec![];
+    for _ in 0..thread_count {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        let reliable_handle = reliable_communicator.initialize_reliable_handle();
+        nodes.push((reliable_communicator, reliable_handle));
+    }
+    let members: Vec<u32> = (0..thread_count).collect();
+    assert_delivered(&mut nodes, &members, 0, 0, 0).await;
+
+    let mut reliable_communicator = reliable_hub.join().unwrap();
+    let reliable_handle = reliable_communicator.initialize_reliable_handle();
+    nodes.push((reliable_communicator, reliable_handle));
+    let grown = config.get_quorum_config().thresholds(thread_count + 1).unwrap();
+    assert_eq!(reliable_hub.get_config().get_thresholds().get_validity_threshold(), grown.get_validity_threshold());
+    let members: Vec<u32> = (0..=thread_count).collect();
+    assert_delivered(&mut nodes, &members, thread_count, 1, 1).await;
+
+    let epoch = reliable_hub.leave(1).unwrap();
+    assert_eq!(epoch, 2);
+    let members: Vec<u32> = (0..=thread_count).filter(|id| *id != 1).collect();
+    assert_delivered(&mut nodes, &members, 2, 2, epoch).await;
+    let departed = nodes[1].0.reliable_recv_with_timeout(Some(2), Instance(2), Round(0), Duration::from_millis(300)).await;
+    assert!(departed.is_err(), "{departed:?}");
+    nodes[1].0.reliable_broadcast(String::from("reliable broadcast message by departed node 1"), Instance(3), Round(0)).await;
+    let ignored = nodes[0].0.reliable_recv_with_timeout(Some(1), Instance(3), Round(0), Duration::from_millis(300)).await;
+    assert!(ignored.is_err(), "{ignored:?}");
+
+    for (reliable_communicator, reliable_handle) in nodes {
+        reliable_communicator.terminate_reliable_handle(reliable_handle);
+    }
+}
+
+#[tokio::test]
+async fn nodes_join_and_leave_a_cluster_of_4() {
+    join_and_leave(4).await;
+}
+
+#[tokio::test]
+async fn nodes_join_and_leave_a_cluster_of_7() {
+    join_and_leave(7).await;
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use bytes::Bytes;
+use futures::future::join_all;
+use tokio::sync::{Barrier, mpsc::{self, Receiver, Sender}};
+use rust_project::config::ClusterConfig;
+use rust_project::events::Event;
+use rust_project::reliable::{DuplicateInputPolicy, ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::storage::StorageBackend;
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+const RESTARTED: u32 = THREAD_COUNT - 1;
+
+// The channel a forwarder hands the frames of a thread to, `None` while the thread is down.
+type ForwardTarget = Arc<Mutex<Option<Sender<Bytes>>>>;
+
+// # Function Description:
+// This function provides the configuration of a journaling cluster storing its files under a fresh root.
+//
+// # Returns:
+// * The root, and the configuration.
+fn journaling_config(name: &str) -> (PathBuf, ClusterConfig) {
+    let root = std::env::temp_dir().join(format!("journal-test-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let config = ClusterConfig::new(THREAD_COUNT).with_storage_backend(StorageBackend::File(root.clone())).with_journaling(true)
+        .with_duplicate_input_policy(DuplicateInputPolicy::Evidence);
+    (root, config)
+}
+
+// # Function Description:
+// This function makes the frames sent to a thread go through a forwarder, so that the thread can be
+// crashed and restarted with a new receiver: frames are dropped while the forwarder has no target.
+//
+// # Returns:
+// * The receiver of the thread, and the target of the forwarder, to replace on restart.
+fn forward_frames(mut inbound: Receiver<Bytes>, buffer_size: usize) -> (Receiver<Bytes>, ForwardTarget) {
+    let (forward_tx, forward_rx) = mpsc::channel(buffer_size);
+    let target = Arc::new(Mutex::new(Some(forward_tx)));
+    let forwarder_target = target.clone();
+    tokio::spawn(async move {
+        while let Some(frame) = inbound.recv().await {
+            let forward_tx = forwarder_target.lock().unwrap().clone();
+            if let Some(forward_tx) = forward_tx {
+                let _ = forward_tx.send(frame).await;
+            }
+        }
+    });
+    (forward_rx, target)
+}
+
+// # Function Description:
+// This function crashes a thread: the frames sent to it are lost until it is restarted.
+//
+// # Returns:
+// * The receiver of the restarted thread.
+fn restart(target: &ForwardTarget, buffer_size: usize) -> Receiver<Bytes> {
+    *target.lock().unwrap() = None;
+    let (forward_tx, receiver) = mpsc::channel(buffer_size);
+    *target.lock().unwrap() = Some(forward_tx);
+    receiver
+}
+
+#[tokio::test]
+async fn recovery_needs_a_journal_under_the_path() {
+    let (root, config) = journaling_config("refused");
+    let (_, receiver) = mpsc::channel(1);
+    let missing = ReliableCommunicator::<String>::recover(&root, vec![], receiver, RESTARTED, config.clone());
+    assert!(missing.is_err_and(|reason| reason.starts_with("no journal of thread 3")));
+
+    std::fs::create_dir_all(root.join(format!("node-{RESTARTED}"))).unwrap();
+    let (_, receiver) = mpsc::channel(1);
+    let disabled = ReliableCommunicator::<String>::recover(&root, vec![], receiver, RESTARTED, config.with_journaling(false));
+    assert!(disabled.is_err_and(|reason| reason.starts_with("journaling is disabled")));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+// The last thread delivers instance 0, crashes, and is restarted from its journal: it delivers instance 0
+// again from the journal alone, does not equivocate when asked to broadcast another value in the instance
+// it already broadcast, and takes part in instance 1.
+#[tokio::test]
+async fn restarted_thread_restores_its_instances_without_equivocating() {
+    let (root, config) = journaling_config("reliable");
+    let channels = ChannelTransport::create_channels(&config);
+    let (transmitters, mut receivers) = (channels.transmitters, channels.receivers);
+    let (receiver, target) = forward_frames(receivers.pop().unwrap(), config.get_buffer_size());
+    receivers.push(receiver);
+    let mut reliable_hub: ReliableHub<String> = ReliableHub::with_config(transmitters.clone(), receivers, config.clone());
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let threads = (0..RESTARTED).map(|id| {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let mut events = reliable_communicator.subscribe_events();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for instance_number in 0..2 {
+                reliable_communicator.reliable_broadcast(format!("value {instance_number} by {id}"), Instance(instance_number), Round(0)).await;
+                for sender in 0..THREAD_COUNT {
+                    reliable_communicator.reliable_recv(Some(sender), Instance(instance_number), Round(0)).await;
+                }
+                barrier.wait().await;
+            }
+            barrier.wait().await;
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            let mut equivocations = 0;
+            while let Ok(event) = events.try_recv() {
+                if let Event::Equivocation(_) = event {
+                    equivocations += 1;
+                }
+            }
+            equivocations
+        })
+    }).collect::<Vec<_>>();
+
+    let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+    let reliable_handle = reliable_communicator.initialize_reliable_handle();
+    reliable_communicator.reliable_broadcast(format!("value 0 by {RESTARTED}"), Instance(0), Round(0)).await;
+    let mut before = vec![];
+    for sender in 0..THREAD_COUNT {
+        before.push(reliable_communicator.reliable_recv(Some(sender), Instance(0), Round(0)).await.get_message().clone());
+    }
+    reliable_communicator.terminate_reliable_handle(reliable_handle);
+    drop(reliable_communicator);
+
+    let receiver = restart(&target, config.get_buffer_size());
+    let mut reliable_communicator = ReliableCommunicator::<String>::recover(&root, transmitters, receiver, RESTARTED, config).unwrap();
+    let reliable_handle = reliable_communicator.initialize_reliable_handle();
+    let mut after = vec![];
+    for sender in 0..THREAD_COUNT {
+        let restored = reliable_communicator.reliable_recv_with_timeout(Some(sender), Instance(0), Round(0), Duration::from_secs(5)).await;
+        after.push(restored.unwrap().get_message().clone());
+    }
+    reliable_communicator.reliable_broadcast(format!("another value 0 by {RESTARTED}"), Instance(0), Round(0)).await;
+    barrier.wait().await;
+    reliable_communicator.reliable_broadcast(format!("value 1 by {RESTARTED}"), Instance(1), Round(0)).await;
+    for sender in 0..THREAD_COUNT {
+        let delivered = reliable_communicator.reliable_recv_with_timeout(Some(sender), Instance(1), Round(0), Duration::from_secs(5)).await;
+        assert!(delivered.is_ok(), "sender {sender}: {delivered:?}");
+    }
+    barrier.wait().await;
+    barrier.wait().await;
+    reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+    assert_eq!(after, before);
+    for thread in join_all(threads).await {
+        assert_eq!(thread.unwrap(), 0);
+    }
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+// The last thread collects a witness round, crashes, and is restarted from its journal: its witness
+// handle restores the round from the deliveries its reliable handle replays, and collects the same values.
+#[tokio::test]
+async fn restarted_thread_restores_its_witness_rounds() {
+    let (root, config) = journaling_config("witness");
+    let channels = ChannelTransport::create_channels(&config);
+    let (transmitters, mut receivers) = (channels.transmitters, channels.receivers);
+    let (receiver, target) = forward_frames(receivers.pop().unwrap(), config.get_buffer_size());
+    receivers.push(receiver);
+    let mut witness_hub = WitnessHub::with_config(transmitters.clone(), receivers, config.clone());
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let mut threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness value by {id}"), Round(0)).await;
+            let collected: BTreeSet<(u32, String)> = witness_communicator.witness_collect(Round(0)).await.into_iter()
+                .map(|message| (message.get_id(), message.get_message().clone()))
+                .collect();
+            // the other threads stay up until the restarted thread collected the round again
+            if id != RESTARTED {
+                barrier.wait().await;
+            }
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            collected
+        })
+    }).collect::<Vec<_>>();
+    let before = threads.pop().unwrap().await.unwrap();
+
+    let receiver = restart(&target, config.get_buffer_size());
+    let mut witness_communicator = WitnessCommunicator::<String>::recover(&root, transmitters, receiver, RESTARTED, config).unwrap();
+    let reliable_handle = witness_communicator.initialize_reliable_handle();
+    let witness_handle = witness_communicator.initialize_witness_handle();
+    let after: BTreeSet<(u32, String)> = tokio::time::timeout(Duration::from_secs(5), witness_communicator.witness_collect(Round(0))).await.unwrap()
+        .into_iter()
+        .map(|message| (message.get_id(), message.get_message().clone()))
+        .collect();
+    witness_communicator.terminate_witness_handle(witness_handle);
+    witness_communicator.terminate_reliable_handle(reliable_handle);
+    barrier.wait().await;
+    join_all(threads).await;
+
+    assert!(!before.is_empty());
+    assert_eq!(after, before);
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::config::ClusterConfig;
+use rust_project::events::Event;
+use rust_project::leader::LeaderElection;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const ROUNDS: u32 = 3;
+
+// # Function Description:
+// This function runs a few rounds in which every node reliably broadcasts a message, but only the leader of
+// the round may. Every node must deliver the message of the leader, deliver none of the messages of the
+// other nodes, and publish an `Event::NonLeaderInput` for each of them.
+//
+// # Parameters:
+// * leader_election - How the leaders of the rounds are elected.
+async fn only_leaders_are_delivered(leader_election: LeaderElection) {
+    let config = ClusterConfig::new(THREAD_COUNT).with_leader_election(leader_election);
+    let leader_schedule = config.get_leader_schedule();
+    let leaders: Vec<u32> = (0..ROUNDS).map(|round| leader_schedule.get_leader(Round(round)).unwrap()).collect();
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        let leaders = leaders.clone();
+        tokio::spawn(async move {
+            let mut events = reliable_communicator.subscribe_events();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for round in 0..ROUNDS {
+                reliable_communicator.reliable_broadcast(format!("proposal of {id} in round {round}"), Instance(0), Round(round)).await;
+            }
+
+            for (round, leader) in leaders.iter().enumerate() {
+                let round_number = Round(round as u32);
+                let delivered = reliable_communicator.reliable_recv_with_timeout(Some(*leader), Instance(0), round_number, Duration::from_secs(5)).await;
+                assert_eq!(delivered.map(|message| message.get_message().clone()), Ok(format!("proposal of {leader} in round {round}")), "id {id}");
+                for sender in (0..THREAD_COUNT).filter(|sender| sender != leader) {
+                    let delivered = reliable_communicator.reliable_recv_with_timeout(Some(sender), Instance(0), round_number, Duration::from_millis(200)).await;
+                    assert!(delivered.is_err(), "id {id}, sender {sender}: {delivered:?}");
+                }
+            }
+            barrier.wait().await;
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+            let mut rejected = 0;
+            while let Ok(event) = events.try_recv() {
+                if let Event::NonLeaderInput(observer, leader, message) = event
+                    && observer == id && leaders.get(message.get_round_number().0 as usize) == Some(&leader) {
+                    rejected += 1;
+                }
+            }
+            assert_eq!(rejected, ROUNDS * (THREAD_COUNT - 1), "id {id}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn only_round_robin_leaders_are_delivered() {
+    only_leaders_are_delivered(LeaderElection::RoundRobin).await;
+}
+
+#[tokio::test]
+async fn only_seeded_leaders_are_delivered() {
+    only_leaders_are_delivered(LeaderElection::Seeded).await;
+}
@@ -0,0 +1,162 @@
+use std::{net::SocketAddr, time::Duration};
+use bytes::Bytes;
+use futures::future::join_all;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use rust_project::basic::{BasicCommunication, Message, QuarantineReason};
+use rust_project::config::ClusterConfig;
+use rust_project::events::Event;
+use rust_project::identity::IdentityStore;
+use rust_project::json::{JsonConversion, WireFormat};
+use rust_project::membership::{MembershipCertificate, MembershipDocument};
+use rust_project::multiplex::{Lane, tag_frame};
+use rust_project::reliable::{ObjectContent, ReliableCommunication, ReliableCommunicator, ReliableHub, Signal, SignalType};
+use rust_project::round::{Instance, Round};
+use rust_project::signing::Authentication;
+use rust_project::transport::{ChannelTransport, TcpTransport, Transport};
+
+const THREAD_COUNT: u32 = 4;
+const UNINVITED: u32 = THREAD_COUNT + 5;
+
+// # Function Description:
+// This function writes the membership document of a cluster at the given addresses, signed by some of its
+// members.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+// * addresses - The address of every node.
+// * signers - The IDs of the members signing the document.
+//
+// # Returns:
+// * The certificate.
+fn membership_certificate(config: &ClusterConfig, addresses: &[SocketAddr], signers: std::ops::Range<usize>) -> MembershipCertificate {
+    let identities = IdentityStore::generate_cluster(config);
+    let certificate = MembershipCertificate::new(MembershipDocument::from_identities(&identities, addresses).unwrap());
+    identities[signers].iter().try_fold(certificate, |certificate, identity| certificate.signed_by(identity)).unwrap()
+}
+
+// # Function Description:
+// This function builds the Input signal and the basic frame of a message sent by a node outside the membership.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The signal frame, and the message frame.
+fn uninvited_frames(config: &ClusterConfig) -> (Bytes, Bytes) {
+    let message = Message::new(String::from("reliable"), UNINVITED, String::from("uninvited message"), None, Some(Instance(UNINVITED)), Round(0));
+    let signal = Signal::new(SignalType::Input, ObjectContent::Message(message.clone()), Instance(UNINVITED), Round(0));
+    (tag_frame(Lane::Signal, signal.write_signal_frame(config.get_codec())), tag_frame(Lane::Basic, message.write_frame(config.get_codec())))
+}
+
+// A certificate signed by every member survives a round trip through a file, while a copy moving a member to
+// another address and a copy missing a signature are rejected.
+#[test]
+fn certificate_is_reloaded_and_changed_copies_are_rejected() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_authentication(Authentication::Ed25519);
+    let addresses: Vec<SocketAddr> = (0..THREAD_COUNT as u16).map(|id| SocketAddr::from(([127, 0, 0, 1], 9200 + id))).collect();
+    let certificate = membership_certificate(&config, &addresses, 0..THREAD_COUNT as usize);
+    let path = std::env::temp_dir().join(format!("membership-test-{}.json", std::process::id()));
+    certificate.save(&path).unwrap();
+    assert_eq!(MembershipCertificate::load(&path), Ok(certificate.clone()));
+
+    std::fs::write(&path, certificate.write_json().replacen(&addresses[1].to_string(), "127.0.0.1:1", 1)).unwrap();
+    assert!(MembershipCertificate::load(&path).is_err());
+    let _ = std::fs::remove_file(path);
+    assert!(membership_certificate(&config, &addresses, 1..THREAD_COUNT as usize).verify().is_err());
+}
+
+// The members of a loopback TCP cluster deliver each other's broadcasts, while an uninvited node connecting to
+// node 0 and announcing an ID outside the membership is disconnected.
+#[tokio::test]
+async fn uninvited_connections_are_closed() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_authentication(Authentication::Ed25519);
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let mut transports = vec![];
+    for id in 0..THREAD_COUNT {
+        transports.push(TcpTransport::bind(id, loopback).await.unwrap());
+    }
+    let addresses: Vec<SocketAddr> = transports.iter().map(|transport| transport.get_local_address()).collect();
+    let certificate = membership_certificate(&config, &addresses, 0..THREAD_COUNT as usize);
+    let config = config.with_membership(certificate);
+
+    let nodes = transports.into_iter().map(|transport| {
+        let mut transport = transport.with_peer_addresses(addresses.clone());
+        let config = config.clone();
+        tokio::spawn(async move {
+            let local_ids = transport.get_local_ids(&config);
+            let channels = transport.open(&config).await.unwrap();
+            let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+            let communicators: Vec<ReliableCommunicator<String>> = (0..THREAD_COUNT)
+                .map(|_| reliable_hub.create_reliable_communicator())
+                .filter(|communicator| local_ids.contains(communicator.get_id()))
+                .collect();
+            join_all(communicators.into_iter().map(|mut reliable_communicator| async move {
+                let id = *reliable_communicator.get_id();
+                let reliable_handle = reliable_communicator.initialize_reliable_handle();
+                reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(id), Round(0)).await;
+                for sender in 0..THREAD_COUNT {
+                    let message = reliable_communicator.reliable_recv(Some(sender), Instance(sender), Round(0)).await;
+                    assert_eq!(*message.get_message(), format!("reliable broadcast message by {sender}"), "id {id}");
+                }
+                reliable_communicator.terminate_reliable_handle(reliable_handle);
+            })).await;
+        })
+    }).collect::<Vec<_>>();
+
+    let mut stream = TcpStream::connect(addresses[0]).await.unwrap();
+    let (signal, _) = uninvited_frames(&config);
+    stream.write_u32(UNINVITED).await.unwrap();
+    stream.write_u32(signal.len() as u32).await.unwrap();
+    stream.write_all(&signal).await.unwrap();
+    let mut buffer = [0; 1];
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buffer)).await.expect("the uninvited connection was kept open");
+    assert!(matches!(read, Ok(0) | Err(_)), "{read:?}");
+
+    let completed = tokio::time::timeout(Duration::from_secs(20), join_all(nodes)).await.expect("the members did not complete the workload");
+    for node in completed {
+        node.unwrap();
+    }
+}
+
+// Every node of an in-process cluster with a membership is sent a signal and a message from a node outside
+// it: the signal is rejected, publishing `Event::NonMember`, the message is quarantined, and the broadcast of
+// a member is still delivered.
+#[tokio::test]
+async fn signals_and_messages_of_non_members_are_rejected() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_authentication(Authentication::Ed25519);
+    let addresses: Vec<SocketAddr> = (0..THREAD_COUNT as u16).map(|id| SocketAddr::from(([127, 0, 0, 1], 9300 + id))).collect();
+    let certificate = membership_certificate(&config, &addresses, 0..THREAD_COUNT as usize);
+    let config = config.with_membership(certificate);
+    let channels = ChannelTransport::create_channels(&config);
+    let (signal, message) = uninvited_frames(&config);
+    for transmitter in &channels.transmitters {
+        transmitter.send(signal.clone()).await.unwrap();
+        transmitter.send(message.clone()).await.unwrap();
+    }
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let mut events = reliable_communicator.subscribe_events();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+            }
+            let genuine = reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+            let mut rejected = false;
+            while let Ok(Ok(event)) = tokio::time::timeout(Duration::from_millis(500), events.recv()).await {
+                rejected |= matches!(event, Event::NonMember(observer, non_member, _) if observer == id && non_member == UNINVITED);
+            }
+            let quarantined = reliable_communicator.quarantined();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+            assert_eq!(genuine.get_message(), "reliable broadcast message by 0", "id {id}");
+            assert!(rejected, "id {id}");
+            assert!(quarantined.iter().any(|frame| frame.get_reason() == &QuarantineReason::NonMember(UNINVITED)), "id {id}: {quarantined:?}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
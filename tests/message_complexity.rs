@@ -0,0 +1,90 @@
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::dissemination::Dissemination;
+use rust_project::metrics::Metrics;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::testkit::{assert_message_complexity_at_most, check_message_complexity, reliable_broadcast_bound};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function runs a round in which every node reliably broadcasts one instance and delivers every
+// instance.
+//
+// # Returns:
+// * The metrics of every node.
+async fn run_reliable_round(config: ClusterConfig) -> Vec<Metrics> {
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut nodes = vec![];
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        nodes.push(reliable_communicator.get_metrics().clone());
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(id), Round(0)).await;
+            for sender in 0..THREAD_COUNT {
+                reliable_communicator.reliable_recv(Some(sender), Instance(sender), Round(0)).await;
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+    nodes
+}
+
+// # Function Description:
+// This function runs a witness round in which every node broadcasts a value and collects the round.
+//
+// # Returns:
+// * The metrics of every node.
+async fn run_witness_round(config: ClusterConfig) -> Vec<Metrics> {
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut nodes = vec![];
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        nodes.push(witness_communicator.get_metrics().clone());
+        tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            witness_communicator.witness_collect(Round(0)).await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+    nodes
+}
+
+// With direct and with spanning-tree dissemination, a round of n reliable broadcasts sends at most n times
+// the O(n²) frames of one instance.
+#[tokio::test]
+async fn reliable_rounds_stay_within_n_instances() {
+    let instance_bound = reliable_broadcast_bound(THREAD_COUNT);
+    for dissemination in [Dissemination::Direct, Dissemination::SpanningTree(2)] {
+        let nodes = run_reliable_round(ClusterConfig::new(THREAD_COUNT).with_dissemination(dissemination)).await;
+        assert_message_complexity_at_most(&nodes, "reliable", Round(0), THREAD_COUNT as u64 * instance_bound);
+    }
+}
+
+// A witness round sends at most 2n times the frames of one instance (one broadcast of the value and one of
+// the report per node). As a sanity check of the counters, it exceeds the bound of a single instance.
+#[tokio::test]
+async fn witness_rounds_stay_within_2n_instances() {
+    let instance_bound = reliable_broadcast_bound(THREAD_COUNT);
+    for dissemination in [Dissemination::Direct, Dissemination::SpanningTree(2)] {
+        let nodes = run_witness_round(ClusterConfig::new(THREAD_COUNT).with_dissemination(dissemination)).await;
+        assert_message_complexity_at_most(&nodes, "witness", Round(0), 2 * THREAD_COUNT as u64 * instance_bound);
+        let single_instance = check_message_complexity(&nodes, "witness", Round(0), instance_bound);
+        assert!(single_instance.is_err(), "{dissemination:?}: {single_instance:?}");
+    }
+}
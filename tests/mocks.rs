@@ -0,0 +1,68 @@
+use rust_project::basic::BasicCommunication;
+use rust_project::config::ClusterConfig;
+use rust_project::mock::{MockReliableCommunicator, MockWitnessCommunicator};
+use rust_project::reliable::ReliableCommunication;
+use rust_project::round::{Instance, Round};
+use rust_project::witness::{ValueOrdering, WitnessCommunication};
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function stands for the logic of a downstream application: it reliably broadcasts a proposal, sends
+// its peer an acknowledgement, and adopts the value the peer reliably broadcast in the same instance.
+//
+// # Returns:
+// * The adopted value.
+async fn propose_and_adopt<C: ReliableCommunication<String> + Send>(communicator: &mut C, peer: u32, instance: Instance) -> String {
+    communicator.reliable_broadcast(String::from("proposal"), instance, Round(0)).await;
+    communicator.basic_send(peer, String::from("ack"), Round(0)).await;
+    communicator.reliable_recv(Some(peer), instance, Round(0)).await.get_message().clone()
+}
+
+#[tokio::test]
+async fn reliable_mock_records_sends_and_returns_scripted_deliveries() {
+    let mut reliable_mock: MockReliableCommunicator<String> = MockReliableCommunicator::new(0, THREAD_COUNT);
+    reliable_mock.deliver_reliable(2, String::from("value of 2"), Instance(0), Round(0));
+    let mut watch = reliable_mock.watch_delivery(Instance(0), Round(0));
+    assert_eq!(propose_and_adopt(&mut reliable_mock, 2, Instance(0)).await, "value of 2");
+    assert_eq!(watch.wait_for(Option::is_some).await.unwrap().as_ref().map(|message| message.get_id()), Some(2));
+
+    let broadcasts = reliable_mock.get_recorded_broadcasts().clone();
+    assert_eq!(broadcasts.len(), 1);
+    assert_eq!((broadcasts[0].get_message().as_str(), broadcasts[0].get_instance_number()), ("proposal", Some(Instance(0))));
+    let sends = reliable_mock.get_recorded_sends().clone();
+    assert_eq!(sends.len(), 1);
+    assert_eq!((sends[0].0, sends[0].1.get_message().as_str()), (2, "ack"));
+
+    // a message scripted twice is received twice, like a value its sender sent twice
+    for _ in 0..2 {
+        reliable_mock.deliver_basic(3, String::from("basic message by 3"), Round(1));
+    }
+    for _ in 0..2 {
+        let received = reliable_mock.basic_recv(None, Round(1)).await;
+        assert_eq!((received.get_id(), received.get_message().as_str()), (3, "basic message by 3"));
+    }
+}
+
+// Witness rounds are scripted out of order, and collected in canonical order unless arrival order is
+// configured.
+#[tokio::test]
+async fn witness_mock_collects_scripted_rounds() {
+    let round_values = vec![(1, String::from("witness value by 1")), (0, String::from("witness value by 0"))];
+    let mut witness_mock: MockWitnessCommunicator<String> = MockWitnessCommunicator::new(0, THREAD_COUNT);
+    let witness_handle = witness_mock.initialize_witness_handle();
+    witness_mock.witness_broadcast(String::from("witness value by 0"), Round(0)).await;
+    witness_mock.deliver_witness_round(Round(0), round_values.clone());
+    let collected = witness_mock.witness_collect(Round(0)).await;
+    let subscribed = witness_mock.witness_subscribe(Round(0)).await.unwrap_or_default();
+    witness_mock.terminate_witness_handle(witness_handle);
+    assert_eq!(witness_mock.get_recorded_broadcasts().len(), 1);
+    assert_eq!(collected.iter().map(|message| message.get_id()).collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(subscribed, collected);
+
+    let config = ClusterConfig::new(THREAD_COUNT).with_value_ordering(ValueOrdering::Arrival);
+    let mut arrival_mock: MockWitnessCommunicator<String> = MockWitnessCommunicator::with_config(0, config);
+    arrival_mock.deliver_witness_round(Round(0), round_values);
+    let arrival_collected = arrival_mock.witness_collect(Round(0)).await;
+    assert_eq!(arrival_collected.iter().map(|message| message.get_id()).collect::<Vec<_>>(), vec![1, 0]);
+}
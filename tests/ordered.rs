@@ -0,0 +1,94 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::emulation::{LinkConditions, NetworkEmulation};
+use rust_project::ordering::OrderedCommunication;
+use rust_project::reliable::{ReliableCommunication, ReliableHub};
+use rust_project::round::Instance;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const FIFO_BROADCASTS: u32 = 5;
+
+// # Function Description:
+// This function provides a hub whose links delay frames by random amounts and reorder them, so that
+// reliable broadcast instances are delivered out of order.
+//
+// # Returns:
+// * The hub.
+fn reordering_hub() -> ReliableHub<String> {
+    let conditions = LinkConditions::new(Duration::from_millis(2), None).with_jitter(Duration::from_millis(20)).with_reordering(0.5);
+    let config = ClusterConfig::new(THREAD_COUNT).with_network_emulation(NetworkEmulation::default().with_conditions(conditions));
+    let channels = ChannelTransport::create_channels(&config);
+    ReliableHub::with_config(channels.transmitters, channels.receivers, config)
+}
+
+// Every thread FIFO broadcasts a few messages, which every thread receives in the order they were sent,
+// from any sender, then one sender at a time.
+#[tokio::test]
+async fn fifo_broadcasts_are_received_in_the_order_they_were_sent() {
+    let mut reliable_hub = reordering_hub();
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for message_number in 0..FIFO_BROADCASTS {
+                reliable_communicator.fifo_broadcast(format!("fifo message {message_number} from {id}")).await;
+            }
+            let mut next = vec![0; THREAD_COUNT as usize];
+            for _ in 0..THREAD_COUNT * FIFO_BROADCASTS {
+                let message = reliable_communicator.fifo_recv(None).await;
+                let sender = message.get_id();
+                let expected = next[sender as usize];
+                assert_eq!(message.get_instance_number(), Some(Instance(expected)), "id {id}");
+                assert_eq!(*message.get_message(), format!("fifo message {expected} from {sender}"), "id {id}");
+                next[sender as usize] += 1;
+            }
+            reliable_communicator.fifo_broadcast(format!("fifo message {FIFO_BROADCASTS} from {id}")).await;
+            for sender in (0..THREAD_COUNT).rev() {
+                let message = reliable_communicator.fifo_recv(Some(sender)).await;
+                assert_eq!((message.get_id(), message.get_instance_number()), (sender, Some(Instance(FIFO_BROADCASTS))), "id {id}");
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+        })
+    }).collect::<Vec<_>>();
+    let completed = tokio::time::timeout(Duration::from_secs(30), join_all(threads)).await.expect("the broadcasts were not all received");
+    for thread in completed {
+        thread.unwrap();
+    }
+}
+
+// Thread 0 causally broadcasts a question, which thread 1 answers once received: every thread receives the
+// question before the answer, and the clock of the question happened before the clock of the answer.
+#[tokio::test]
+async fn causal_broadcasts_are_received_after_the_broadcasts_they_depend_on() {
+    let mut reliable_hub = reordering_hub();
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.causal_broadcast(String::from("question")).await;
+            }
+            let mut causal = vec![];
+            if id == 1 {
+                causal.push(reliable_communicator.causal_recv().await);
+                reliable_communicator.causal_broadcast(String::from("answer")).await;
+            }
+            while causal.len() < 2 {
+                causal.push(reliable_communicator.causal_recv().await);
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            let contents: Vec<&String> = causal.iter().map(|message| message.get_message()).collect();
+            assert_eq!(contents, vec!["question", "answer"], "id {id}");
+            let (Some(question), Some(answer)) = (causal[0].get_clock(), causal[1].get_clock()) else {
+                panic!("id {id}: a causal broadcast was received without a clock");
+            };
+            assert!(question.happened_before(answer), "id {id}: {question:?}, {answer:?}");
+        })
+    }).collect::<Vec<_>>();
+    let completed = tokio::time::timeout(Duration::from_secs(30), join_all(threads)).await.expect("the broadcasts were not all received");
+    for thread in completed {
+        thread.unwrap();
+    }
+}
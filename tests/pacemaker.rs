@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::broadcast;
+use rust_project::basic::Message;
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::events::Event;
+use rust_project::json::JsonConversion;
+use rust_project::leader::LeaderElection;
+use rust_project::multiplex::{Lane, tag_frame};
+use rust_project::reliable::{ObjectContent, ReliableCommunication, ReliableCommunicator, ReliableHub, Signal, SignalType};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const SILENT: u32 = THREAD_COUNT - 1;
+
+// A cluster with a silent node, round-robin leaders, `n - t` quorums, and a pacemaker. Every running node
+// broadcasts in the rounds 0 to n - 1, of which the silent node leads the last: that round changes views, so
+// that node 0 leads it and its queued Input is delivered. An Echo of the silent node is also injected in
+// round n for an instance it never sent, which can never be delivered: that round changes views too,
+// instead of hanging. No other round changes views.
+#[tokio::test]
+async fn pacemaker_changes_the_views_of_stuck_rounds_only() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap()
+        .with_leader_election(LeaderElection::RoundRobin)
+        .with_pacemaker(Duration::from_millis(500));
+    let leader_schedule = config.get_leader_schedule();
+    let stuck_round = Round(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let injector = channels.transmitters.clone();
+    let codec = config.get_codec();
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+
+    let message = Message::new(String::from("reliable"), SILENT, String::from("never sent"), None, Some(Instance(0)), stuck_round);
+    let mut echo: serde_json::Value = serde_json::from_str(&Signal::new(SignalType::Echo, ObjectContent::Message(message), Instance(0), stuck_round).write_json()).unwrap();
+    echo["origin"] = SILENT.into();
+    let echo = Signal::<String>::read_json(&echo.to_string()).unwrap();
+    let echo = tag_frame(Lane::Signal, echo.write_signal_frame(codec));
+    for transmitter in &injector[..SILENT as usize] {
+        transmitter.send(echo.clone()).await.unwrap();
+    }
+
+    let (mut threads, mut silent_communicators) = (vec![], vec![]);
+    for id in 0..THREAD_COUNT {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        if id == SILENT {
+            silent_communicators.push(reliable_communicator);
+            continue
+        }
+        let leader_schedule = leader_schedule.clone();
+        threads.push(tokio::spawn(async move {
+            let mut events = reliable_communicator.subscribe_events();
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for round in 0..THREAD_COUNT {
+                reliable_communicator.reliable_broadcast(format!("proposal of {id} in round {round}"), Instance(0), Round(round)).await;
+            }
+
+            for round in 0..THREAD_COUNT {
+                let view = if leader_schedule.get_leader(Round(round)) == Some(SILENT) { 1 } else { 0 };
+                let leader = leader_schedule.get_leader_in_view(Round(round), view).unwrap();
+                let delivered = reliable_communicator.reliable_recv_with_timeout(Some(leader), Instance(0), Round(round), Duration::from_secs(5)).await;
+                assert_eq!(delivered.map(|message| message.get_message().clone()), Ok(format!("proposal of {leader} in round {round}")), "id {id}");
+            }
+
+            let mut views: BTreeMap<Round, u32> = BTreeMap::new();
+            let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+            while !views.contains_key(&stuck_round) {
+                match tokio::time::timeout_at(deadline, events.recv()).await {
+                    Ok(Ok(Event::ViewChanged(observer, round_number, view))) if observer == id => {
+                        views.insert(round_number, view);
+                    },
+                    Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) => {},
+                    _ => break,
+                }
+            }
+            while let Ok(event) = events.try_recv() {
+                if let Event::ViewChanged(observer, round_number, view) = event && observer == id {
+                    views.insert(round_number, view);
+                }
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+            let led_by_silent: Vec<Round> = (0..THREAD_COUNT).map(Round).filter(|round_number| leader_schedule.get_leader(*round_number) == Some(SILENT)).collect();
+            assert!(views.keys().all(|round_number| *round_number == stuck_round || led_by_silent.contains(round_number)), "id {id}: {views:?}");
+            assert!(led_by_silent.iter().all(|round_number| views.get(round_number) == Some(&1)), "id {id}: {views:?}");
+            assert!(views.get(&stuck_round).is_some_and(|view| *view >= 1), "id {id}: {views:?}");
+        }));
+    }
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+    drop(silent_communicators);
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use bytes::Bytes;
+use futures::future::join_all;
+use rust_project::basic::{BasicCommunication, Message, QuarantineReason};
+use rust_project::config::ClusterConfig;
+use rust_project::json::WireFormat;
+use rust_project::multiplex::{Lane, attribute_frame, tag_frame};
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const LAST: u32 = THREAD_COUNT - 1;
+
+// With a maximum payload size and a validator rejecting the payloads containing "forbidden" in round 1 only,
+// node 0 broadcasts an accepted round 0 payload containing "forbidden", a rejected round 1 payload, and an
+// oversized payload, and a frame that is not a message is injected into every node, attributed to the last
+// node as if a network transport had received it from that node. Every node quarantines the three rejected
+// frames with their reasons, counts them per reason and per sender, and still receives the accepted messages.
+#[tokio::test]
+async fn rejected_frames_are_quarantined_with_their_reasons() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_max_payload_size(300);
+    let channels = ChannelTransport::create_channels(&config);
+    for transmitter in &channels.transmitters {
+        transmitter.send(attribute_frame(LAST, tag_frame(Lane::Basic, Bytes::from_static(b"{\"unexpected\": 1}")))).await.unwrap();
+    }
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            reliable_communicator.set_payload_validator(|message: &Message<String>| match message.get_round_number() == Round(1) && message.get_message().contains("forbidden") {
+                true => Err(String::from("forbidden payload in round 1")),
+                false => Ok(()),
+            });
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            if id == 0 {
+                reliable_communicator.basic_broadcast(String::from("forbidden is allowed in round 0"), Round(0)).await;
+                reliable_communicator.basic_broadcast(String::from("forbidden in round 1"), Round(1)).await;
+                reliable_communicator.basic_broadcast("oversized ".repeat(40), Round(2)).await;
+                reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+                reliable_communicator.basic_broadcast(String::from("last message"), Round(3)).await;
+            }
+            let accepted = reliable_communicator.basic_recv(Some(0), Round(0)).await;
+            let delivered = reliable_communicator.reliable_recv(Some(0), Instance(1), Round(0)).await;
+            // node 0 sent the last message after the rejected ones, which are quarantined by the time it is received
+            reliable_communicator.basic_recv(Some(0), Round(3)).await;
+            let quarantined = reliable_communicator.quarantined();
+            let report = reliable_communicator.metrics_report();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+            assert_eq!(accepted.get_message(), "forbidden is allowed in round 0", "id {id}");
+            assert_eq!(delivered.get_message(), "reliable broadcast message by 0", "id {id}");
+            assert_eq!(quarantined.len(), 3, "id {id}: {quarantined:?}");
+            assert!(quarantined.iter().any(|frame| matches!(frame.get_reason(), QuarantineReason::Schema(_))
+                && frame.get_peer() == Some(LAST) && frame.get_sender() == Some(LAST)), "id {id}: {quarantined:?}");
+            assert!(quarantined.iter().any(|frame| *frame.get_reason() == QuarantineReason::Predicate(String::from("forbidden payload in round 1"))
+                && frame.get_source() == Some(0) && frame.get_round_number() == Some(Round(1))), "id {id}: {quarantined:?}");
+            assert!(quarantined.iter().any(|frame| matches!(frame.get_reason(), QuarantineReason::Size(_, 300))
+                && frame.get_round_number() == Some(Round(2))), "id {id}: {quarantined:?}");
+            assert_eq!(report.get_quarantined().values().sum::<u64>(), 3, "id {id}");
+            assert_eq!(report.get_quarantined_senders(), &BTreeMap::from([(0, 2), (LAST, 1)]), "id {id}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
+
+// A message the last node sent twice, as after a reconnection, is stored and received once, and counted as a
+// duplicate of that node.
+#[tokio::test]
+async fn a_message_sent_twice_is_received_once() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let repeated = Message::new(String::from("basic"), LAST, String::from("message sent twice"), None, None, Round(4));
+    for transmitter in &channels.transmitters {
+        for _ in 0..2 {
+            transmitter.send(attribute_frame(LAST, tag_frame(Lane::Basic, repeated.write_frame(config.get_codec())))).await.unwrap();
+        }
+    }
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            let received = reliable_communicator.basic_recv(Some(LAST), Round(4)).await;
+            let received_again = tokio::time::timeout(Duration::from_millis(100), reliable_communicator.basic_recv(Some(LAST), Round(4))).await;
+            let report = reliable_communicator.metrics_report();
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+
+            assert_eq!(received.get_message(), "message sent twice", "id {id}");
+            assert!(received_again.is_err(), "id {id}");
+            assert_eq!(report.get_duplicates(), &BTreeMap::from([(LAST, 1)]), "id {id}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::basic::{BasicCommunication, Message, QueueEviction, RecvObject, SenderQueue};
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::Round;
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const CAPACITY: usize = 8;
+const EVICTED_ROUNDS: u32 = 20;
+const GRACE_PERIOD: Duration = Duration::from_millis(50);
+
+// # Function Description:
+// This function provides the basic message a sender sends in a round.
+//
+// # Returns:
+// * The message.
+fn message(sender: u32, round: u32) -> String {
+    format!("message of {sender} in round {round}")
+}
+
+// # Function Description:
+// This function wraps the basic message of sender 0 in a round as a received object.
+//
+// # Returns:
+// * The object.
+fn object(round: u32, text: &str) -> RecvObject<String> {
+    RecvObject::Message(Message::new(String::from("basic"), 0, String::from(text), None, None, Round(round)))
+}
+
+// # Function Description:
+// This function provides the texts of received objects, in order.
+//
+// # Returns:
+// * The texts.
+fn texts(objects: impl IntoIterator<Item = RecvObject<String>>) -> Vec<String> {
+    objects.into_iter().map(|object| match object {
+        RecvObject::Message(message) => message.get_message().clone(),
+        RecvObject::Collection(_) => panic!("Error: retreived Vec<Message> instead of Message"),
+    }).collect()
+}
+
+// Objects are taken by round, oldest first, after the objects pushed ahead of the others.
+#[test]
+fn sender_queue_takes_the_objects_of_a_round_in_order() {
+    let mut queue = SenderQueue::new();
+    queue.push_back(object(0, "first"));
+    queue.push_back(object(1, "other round"));
+    queue.push_back(object(0, "second"));
+    queue.push_front(object(0, "restored"));
+    assert_eq!(texts(queue.iter().cloned()), vec!["restored", "first", "other round", "second"]);
+
+    let key = (String::from("basic"), None, Round(0));
+    let taken = std::iter::from_fn(|| queue.take(&key));
+    assert_eq!(texts(taken), vec!["restored", "first", "second"]);
+    assert_eq!(texts(queue.iter().cloned()), vec!["other round"]);
+}
+
+// A capacity evicts the objects stored first, the objects pushed ahead of the others included, and keeps
+// the index consistent: evicted objects can no longer be taken.
+#[test]
+fn sender_queue_evicts_beyond_its_capacity() {
+    let mut queue = SenderQueue::new();
+    for round in 0..4 {
+        queue.push_back(object(round, &format!("round {round}")));
+    }
+    queue.push_front(object(4, "restored"));
+    assert!(queue.evict(QueueEviction::Never).is_empty());
+    assert_eq!(texts(queue.evict(QueueEviction::Capacity(2))), vec!["restored", "round 0", "round 1"]);
+    assert_eq!(queue.len(), 2);
+    assert!(queue.take(&(String::from("basic"), None, Round(4))).is_none());
+    assert!(queue.take(&(String::from("basic"), None, Round(0))).is_none());
+    assert_eq!(texts(queue.take(&(String::from("basic"), None, Round(3)))), vec!["round 3"]);
+}
+
+// A grace period evicts the objects that waited for it, and keeps the ones stored since.
+#[tokio::test]
+async fn sender_queue_evicts_past_its_grace_period() {
+    let mut queue = SenderQueue::new();
+    queue.push_back(object(0, "old"));
+    queue.push_front(object(0, "restored"));
+    tokio::time::sleep(GRACE_PERIOD * 2).await;
+    queue.push_back(object(1, "new"));
+    assert_eq!(texts(queue.evict(QueueEviction::GracePeriod(GRACE_PERIOD))), vec!["restored", "old"]);
+    assert_eq!(texts(queue.iter().cloned()), vec!["new"]);
+}
+
+// # Function Description:
+// This function runs a cluster under an eviction policy: every node runs its script, then waits for the
+// others, and checks how many messages of every sender its queues evicted.
+//
+// # Parameters:
+// * queue_eviction - The eviction policy of the cluster.
+// * script - The script of a node, given the node ID, returning the number of messages of every sender it expects evicted.
+async fn assert_evicted<F>(queue_eviction: QueueEviction, script: fn(u32, ReliableCommunicator<String>) -> F)
+where
+    F: Future<Output = (u64, ReliableCommunicator<String>)> + Send + 'static,
+{
+    let config = ClusterConfig::new(THREAD_COUNT).with_queue_eviction(queue_eviction);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let (expected_evicted, reliable_communicator) = script(id, reliable_communicator).await;
+            barrier.wait().await;
+            let evicted = reliable_communicator.get_metrics().get_evicted();
+            for sender in 0..THREAD_COUNT {
+                assert_eq!(evicted.get(&sender).copied().unwrap_or(0), expected_evicted, "{queue_eviction:?}, id {id}: {evicted:?}");
+            }
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
+
+// Every node broadcasts more rounds than the capacity and only receives the last one: the rounds stored
+// first are evicted, and every queue keeps the capacity less the message received.
+#[tokio::test]
+async fn communicators_evict_beyond_the_capacity() {
+    assert_evicted(QueueEviction::Capacity(CAPACITY), |id, mut reliable_communicator| async move {
+        for round in 0..EVICTED_ROUNDS {
+            reliable_communicator.basic_broadcast(message(id, round), Round(round)).await;
+        }
+        let last = EVICTED_ROUNDS - 1;
+        for sender in 0..THREAD_COUNT {
+            assert_eq!(*reliable_communicator.basic_recv(Some(sender), Round(last)).await.get_message(), message(sender, last));
+        }
+        assert!(reliable_communicator.get_queues().get_queues().values().all(|queue| queue.len() == CAPACITY - 1));
+        (EVICTED_ROUNDS as u64 - CAPACITY as u64, reliable_communicator)
+    }).await;
+}
+
+// Every node broadcasts two rounds and only receives the second: the first is evicted once the grace period
+// passed, while a round received after it is not.
+#[tokio::test]
+async fn communicators_evict_past_the_grace_period() {
+    assert_evicted(QueueEviction::GracePeriod(GRACE_PERIOD), |id, mut reliable_communicator| async move {
+        for round in 0..2 {
+            reliable_communicator.basic_broadcast(message(id, round), Round(round)).await;
+        }
+        for sender in 0..THREAD_COUNT {
+            assert_eq!(*reliable_communicator.basic_recv(Some(sender), Round(1)).await.get_message(), message(sender, 1));
+        }
+        tokio::time::sleep(GRACE_PERIOD * 2).await;
+        reliable_communicator.basic_broadcast(message(id, 2), Round(2)).await;
+        for sender in 0..THREAD_COUNT {
+            assert_eq!(*reliable_communicator.basic_recv(Some(sender), Round(2)).await.get_message(), message(sender, 2));
+        }
+        (1, reliable_communicator)
+    }).await;
+}
+
+// Every node broadcasts a few rounds, and receives all but one message of each from the last round to the
+// first once every message arrived: `basic_recv(None, ..)` takes the senders in turn, every round starting
+// after the sender it received from last, and nothing is evicted without an eviction policy.
+#[tokio::test]
+async fn receiving_from_any_sender_takes_the_senders_in_turn() {
+    assert_evicted(QueueEviction::Never, |id, mut reliable_communicator| async move {
+        let rounds = 8;
+        for round in 0..rounds {
+            reliable_communicator.basic_broadcast(message(id, round), Round(round)).await;
+        }
+        for sender in 0..THREAD_COUNT {
+            reliable_communicator.basic_recv(Some(sender), Round(rounds - 1)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut senders = vec![];
+        for round in (0..rounds - 1).rev() {
+            for _ in 0..THREAD_COUNT - 1 {
+                let received = reliable_communicator.basic_recv(None, Round(round)).await;
+                assert_eq!(*received.get_message(), message(received.get_id(), round));
+                senders.push(received.get_id());
+            }
+        }
+        let expected: Vec<u32> = (0..senders.len() as u32).map(|turn| turn % THREAD_COUNT).collect();
+        assert_eq!(senders, expected, "id {id}");
+        (0, reliable_communicator)
+    }).await;
+}
@@ -0,0 +1,44 @@
+use rust_project::config::{ClusterConfig, QuorumConfig};
+use rust_project::reliable::ReliableHub;
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::WitnessHub;
+
+#[test]
+fn weighted_thresholds_are_derived_from_the_total_weight() {
+    let thresholds = QuorumConfig::new().with_weights(vec![3, 1, 1, 1]).thresholds(4).unwrap();
+    assert_eq!(thresholds.get_faulty_threads(), 1);
+    assert_eq!(thresholds.get_validity_threshold(), 6);
+    assert_eq!(thresholds.get_weight(0), 3);
+
+    let stake = QuorumConfig::new().with_weights(vec![u32::MAX as u64, u32::MAX as u64, 1]).thresholds(3).unwrap();
+    assert_eq!(stake.get_faulty_threads(), (2 * u32::MAX as u64) / 3);
+    assert_eq!(stake.get_weight(1), u32::MAX as u64);
+
+    let overflowing = QuorumConfig::new().with_weights(vec![u64::MAX, 1]).thresholds(2);
+    assert!(overflowing.is_err());
+}
+
+#[tokio::test]
+async fn hubs_refuse_weights_they_cannot_use() {
+    let config = ClusterConfig::new(4);
+    for weights in [vec![1, 1, 1], vec![0, 0, 0, 0]] {
+        let channels = ChannelTransport::create_channels(&config);
+        let reliable_hub = ReliableHub::<String>::with_weights(channels.transmitters, channels.receivers, config.clone(), weights.clone());
+        assert!(reliable_hub.is_err(), "{weights:?}");
+        let channels = ChannelTransport::create_channels(&config);
+        let witness_hub = WitnessHub::<String>::with_weights(channels.transmitters, channels.receivers, config.clone(), weights.clone());
+        assert!(witness_hub.is_err(), "{weights:?}");
+    }
+    let channels = ChannelTransport::create_channels(&config);
+    let reliable_hub = ReliableHub::<String>::with_weights(channels.transmitters, channels.receivers, config, vec![3, 1, 1, 1]);
+    assert!(reliable_hub.is_ok_and(|hub| hub.get_config().get_thresholds().get_validity_threshold() == 6));
+}
+
+#[test]
+fn configurations_refuse_quorums_the_cluster_cannot_satisfy() {
+    let config = ClusterConfig::new(4);
+    let refused = config.clone().with_quorum_config(QuorumConfig::new().with_weights(vec![1; 5]));
+    assert_eq!(refused.err(), QuorumConfig::new().with_weights(vec![1; 5]).thresholds(4).err());
+    let weighted = config.with_quorum_config(QuorumConfig::new().with_weights(vec![3, 1, 1, 1])).unwrap();
+    assert_eq!(weighted.get_thresholds().get_validity_threshold(), 6);
+}
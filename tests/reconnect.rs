@@ -0,0 +1,109 @@
+use std::{net::{Ipv4Addr, SocketAddr, SocketAddrV4}, time::Duration};
+use bytes::Bytes;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc::Sender};
+use rust_project::config::ClusterConfig;
+use rust_project::transport::{ConnectionEvent, ReconnectPolicy, TcpTransport, Transport};
+
+const THREAD_COUNT: u32 = 4;
+const LOOPBACK: SocketAddr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0));
+
+// # Function Description:
+// This function opens a `TcpTransport` for node 0 whose every peer is expected at one address.
+//
+// # Parameters:
+// * policy - The reconnection policy of the transport.
+// * peer_address - The address of every peer.
+//
+// # Returns:
+// * The transport, the transmitter of its frames to node 1, and the receiver of its connection events.
+async fn open_reconnecting_transport(policy: ReconnectPolicy, peer_address: SocketAddr) -> (TcpTransport, Sender<Bytes>, broadcast::Receiver<ConnectionEvent>) {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let mut transport = TcpTransport::bind(0, LOOPBACK).await.unwrap();
+    let mut addresses = vec![peer_address; THREAD_COUNT as usize];
+    addresses[0] = transport.get_local_address();
+    transport = transport.with_peer_addresses(addresses).with_reconnect_policy(policy);
+    let events = transport.subscribe_connection_events();
+    let mut channels = transport.open(&config).await.unwrap();
+    (transport, channels.transmitters.swap_remove(1), events)
+}
+
+// # Function Description:
+// This function accepts the connection of node 0 on a listener standing in for node 1, answers its
+// handshake with the capabilities node 0 announced, and reads its frames until the expected last one.
+//
+// # Parameters:
+// * listener - The listener of node 1.
+// * last - The last frame to read.
+//
+// # Returns:
+// * The connection, and the frames read.
+async fn read_frames_until(listener: &TcpListener, last: &str) -> (TcpStream, Vec<String>) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        stream.read_u32().await.unwrap();
+        let mut capabilities = vec![0; stream.read_u32().await.unwrap() as usize];
+        stream.read_exact(&mut capabilities).await.unwrap();
+        stream.write_u32(capabilities.len() as u32).await.unwrap();
+        stream.write_all(&capabilities).await.unwrap();
+        let mut frames = vec![];
+        while frames.last().is_none_or(|frame: &String| frame != last) {
+            let mut frame = vec![0; stream.read_u32().await.unwrap() as usize];
+            stream.read_exact(&mut frame).await.unwrap();
+            frames.push(String::from_utf8_lossy(&frame).into_owned());
+        }
+        (stream, frames)
+    }).await.unwrap_or_else(|_| panic!("{last} never arrived"))
+}
+
+// While node 1 is unreachable, node 0 buffers the frames sent to it up to the limit of its policy and drops
+// the others, then delivers the buffered frames once node 1 listens, at the address given to the address book.
+#[tokio::test]
+async fn frames_are_buffered_up_to_the_limit_while_a_peer_is_unreachable() {
+    let unreachable = TcpListener::bind(LOOPBACK).await.unwrap().local_addr().unwrap();
+    let policy = ReconnectPolicy::new(Duration::from_millis(10), Duration::from_millis(100), 2);
+    let (transport, transmitter, mut events) = open_reconnecting_transport(policy, unreachable).await;
+    for index in 0..5 {
+        transmitter.send(Bytes::from(format!("frame {index}"))).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let listener = TcpListener::bind(LOOPBACK).await.unwrap();
+    transport.get_address_book().set_address(1, listener.local_addr().unwrap()).unwrap();
+    let accepted = tokio::spawn(async move { read_frames_until(&listener, "frame 5").await.1 });
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv()).await.unwrap().unwrap();
+    transmitter.send(Bytes::from_static(b"frame 5")).await.unwrap();
+    assert_eq!(accepted.await.unwrap(), ["frame 0", "frame 1", "frame 5"]);
+    assert!(matches!(event, ConnectionEvent::Connected(0, 1, attempts, 3) if attempts > 1), "{event:?}");
+}
+
+// When the connection to node 1 drops, node 0 publishes `ConnectionEvent::Disconnected`, reconnects to node 1
+// at its new address, delivers every frame that could not be written meanwhile, and publishes
+// `ConnectionEvent::Reconnected`.
+#[tokio::test]
+async fn frames_are_delivered_after_reconnecting_to_a_peer() {
+    let policy = ReconnectPolicy::new(Duration::from_millis(10), Duration::from_millis(100), 64);
+    let listener = TcpListener::bind(LOOPBACK).await.unwrap();
+    let (transport, transmitter, mut events) = open_reconnecting_transport(policy, listener.local_addr().unwrap()).await;
+    transmitter.send(Bytes::from_static(b"frame 0")).await.unwrap();
+    let first_connection = read_frames_until(&listener, "frame 0").await;
+    // node 1 goes down: its connection and its listener close
+    drop(first_connection);
+    drop(listener);
+    for index in 1..3 {
+        transmitter.send(Bytes::from(format!("frame {index}"))).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    for index in 3..6 {
+        transmitter.send(Bytes::from(format!("frame {index}"))).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let listener = TcpListener::bind(LOOPBACK).await.unwrap();
+    transport.get_address_book().set_address(1, listener.local_addr().unwrap()).unwrap();
+    let (_, frames) = read_frames_until(&listener, "frame 5").await;
+    assert!(frames.ends_with(&[String::from("frame 2"), String::from("frame 3"), String::from("frame 4"), String::from("frame 5")]), "{frames:?}");
+    let events: Vec<ConnectionEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+    assert!(matches!(events.as_slice(), [ConnectionEvent::Connected(0, 1, 1, 0), ConnectionEvent::Disconnected(0, 1, _), ConnectionEvent::Reconnected(0, 1, _, 0)]), "{events:?}");
+}
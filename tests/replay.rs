@@ -0,0 +1,72 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::replay::{ReplayAction, ReplayRecording, ReplayTap};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const RECORDED: u32 = 1;
+
+// # Function Description:
+// This function runs a reliable cluster in which every node broadcasts an instance, while the frames
+// received by the recorded node are tapped, and records the log of the recorded node from them. The
+// recorded node must deliver every instance live, and the log must deliver every instance too.
+//
+// # Returns:
+// * The configuration of the cluster, and the recorded log.
+async fn record() -> (ClusterConfig, ReplayRecording) {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut receivers = channels.receivers;
+    let (receiver, tap) = ReplayTap::tap(receivers.remove(RECORDED as usize), config.get_buffer_size());
+    receivers.insert(RECORDED as usize, receiver);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, receivers, config.clone());
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(0), Round(0)).await;
+            for sender in 0..THREAD_COUNT {
+                let delivered = reliable_communicator.reliable_recv_with_timeout(Some(sender), Instance(0), Round(0), Duration::from_secs(5)).await;
+                assert!(delivered.is_ok(), "id {id}, sender {sender}: {delivered:?}");
+            }
+            // lets the signals still in flight reach every node, so that the recorded log is complete
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+
+    let recording = ReplayRecording::record::<String>(config.clone(), RECORDED, tap.get_inputs()).await.unwrap();
+    let replayed_deliveries = recording.get_steps().iter().flat_map(|step| step.get_actions()).filter(|action| matches!(action, ReplayAction::Delivered(_))).count();
+    assert_eq!(replayed_deliveries as u32, THREAD_COUNT);
+    (config, recording)
+}
+
+// The log is saved and loaded back, and replaying it with the recorded configuration emits the recorded
+// actions at every step.
+#[tokio::test]
+async fn saved_log_replays_without_divergence() {
+    let (_, recording) = record().await;
+    let path = std::env::temp_dir().join(format!("replay-test-{}.json", std::process::id()));
+    recording.save(&path).unwrap();
+    let loaded = ReplayRecording::load(&path);
+    let _ = std::fs::remove_file(&path);
+    let loaded = loaded.unwrap();
+    assert_eq!(loaded.verify::<String>(loaded.get_config().clone()).await, Ok(None));
+}
+
+// Replaying the log with deliveries to self disabled, standing in for a modified version, diverges at the
+// step delivering the recorded node's own instance.
+#[tokio::test]
+async fn modified_replay_diverges_at_the_first_changed_action() {
+    let (config, recording) = record().await;
+    let own_delivery = format!("reliable broadcast message by {RECORDED}");
+    let divergence = recording.verify::<String>(config.with_deliver_to_self(false)).await.unwrap().expect("the modified replay diverges");
+    assert!(divergence.get_expected().iter().any(|action| matches!(action, ReplayAction::Delivered(frame) if String::from_utf8_lossy(frame).contains(&own_delivery))), "{divergence}");
+    assert!(!divergence.get_actual().iter().any(|action| matches!(action, ReplayAction::Delivered(_))), "{divergence}");
+}
@@ -0,0 +1,41 @@
+use futures::future::join_all;
+use rust_project::basic::CollectError;
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::ReliableCommunication;
+use rust_project::retention::RetentionPolicy;
+use rust_project::round::Round;
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// Under a capacity of one, the local queues keep the collection of the round collected last only: it is
+// collected again from the cache, while the round collected before it is reported as consumed instead of
+// waiting for a second delivery.
+#[tokio::test]
+async fn queues_drop_the_collections_of_the_retention_policy() {
+    let config = ClusterConfig::new(THREAD_COUNT).with_retention_policy(RetentionPolicy::Capacity(1));
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            let mut collected = vec![];
+            for round in 0..2 {
+                witness_communicator.witness_broadcast(format!("witness broadcast message {round} by {id}"), Round(round)).await;
+                collected.push(witness_communicator.witness_collect(Round(round)).await);
+            }
+            let kept = witness_communicator.try_witness_collect(Round(1)).await;
+            let dropped = witness_communicator.try_witness_collect(Round(0)).await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            assert_eq!(kept.as_ref(), Ok(&collected[1]), "id {id}");
+            assert!(matches!(dropped, Err(CollectError::AlreadyConsumed(_, Round(0)))), "id {id}: {dropped:?}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
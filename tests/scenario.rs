@@ -0,0 +1,51 @@
+use std::path::Path;
+use std::time::Duration;
+use rust_project::config::ClusterConfig;
+use rust_project::json::JsonConversion;
+use rust_project::round::Round;
+use rust_project::scenario::{NodeScript, RoundDeadline, Scenario, ScenarioAction};
+
+#[test]
+fn bundled_scenario_is_read_from_toml() {
+    let scenario = Scenario::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/witness.toml"))).unwrap();
+    assert_eq!(scenario.get_thread_count(), Some(4));
+    assert_eq!(scenario.get_timeout(), Duration::from_secs(10));
+    assert_eq!(scenario.get_nodes().len(), 4);
+    assert_eq!(scenario.get_deadlines()[1], RoundDeadline::new("reliable", Round(1), Duration::from_secs(2)));
+    assert_eq!(scenario.get_script(0).get_delays()[0].get_to(), 3);
+    assert_eq!(scenario.get_script(3).get_actions().last(), Some(&ScenarioAction::Crash));
+}
+
+#[test]
+fn toml_and_json_scenarios_decode_alike() {
+    let scenario = Scenario::new(vec![NodeScript::new(0, vec![ScenarioAction::Sleep { millis: 5 }, ScenarioAction::Crash])
+        .with_delay(1, Duration::from_millis(200))])
+        .with_deadline(RoundDeadline::new("witness", Round(0), Duration::from_millis(500)));
+    let toml = r#"
+        timeout_millis = 10000
+        deadlines = [{ protocol = "witness", round = 0, millis = 500 }]
+
+        [[nodes]]
+        id = 0
+        delays = [{ to = 1, millis = 200 }]
+        actions = [{ action = "sleep", millis = 5 }, { action = "crash" }]
+    "#;
+    assert_eq!(Scenario::read_toml(toml), Ok(scenario.clone()));
+    assert_eq!(Scenario::read_json(&scenario.write_json()).unwrap(), scenario);
+    assert!(Scenario::read_toml("nodes = 0").is_err());
+}
+
+// The bundled scenario only scripts 4 nodes expecting 3 values each, so it refuses any other cluster size,
+// while a scenario that declares no size runs on any cluster.
+#[test]
+fn scenarios_reject_clusters_of_another_size() {
+    let bundled = Scenario::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/witness.toml"))).unwrap();
+    assert!(bundled.configure(ClusterConfig::new(4)).is_ok());
+    for thread_count in [3, 7] {
+        let rejected = bundled.configure(ClusterConfig::new(thread_count));
+        assert_eq!(rejected.err(), Some(format!("the scenario is written for a cluster of 4 threads, not {thread_count}")));
+    }
+    let scripts = vec![NodeScript::new(0, vec![ScenarioAction::Crash])];
+    assert!(Scenario::new(scripts.clone()).configure(ClusterConfig::new(7)).is_ok());
+    assert!(Scenario::new(scripts).with_thread_count(7).configure(ClusterConfig::new(7)).is_ok());
+}
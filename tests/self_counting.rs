@@ -0,0 +1,120 @@
+use std::{sync::Arc, time::Duration};
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::barycentric_agreement::{BarycentricCommunication, BarycentricCommunicator, BarycentricHub};
+use rust_project::config::{ClusterConfig, SelfCounting};
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function configures a cluster to count, or not, the signals of every thread towards its own quorums.
+//
+// # Returns:
+// * The configuration, and the number of threads whose signals every thread counts.
+fn self_counting_config(self_counting: SelfCounting) -> (ClusterConfig, u64) {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let config = config.clone().with_quorum_config(config.get_quorum_config().clone().with_self_counting(self_counting)).unwrap();
+    let counted = match self_counting {
+        SelfCounting::Include => u64::from(THREAD_COUNT),
+        SelfCounting::Exclude => u64::from(THREAD_COUNT) - 1,
+    };
+    (config, counted)
+}
+
+// Every node reliably broadcasts an instance, which every node delivers. Once the signals settled, every
+// instance counts the Echo and Vote signals of every thread, except the node's own under `SelfCounting::Exclude`.
+#[tokio::test]
+async fn reliable_instances_count_their_own_signals_as_configured() {
+    for self_counting in [SelfCounting::Include, SelfCounting::Exclude] {
+        let (config, counted) = self_counting_config(self_counting);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+        let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                let reliable_handle = reliable_communicator.initialize_reliable_handle();
+                reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(id), Round(0)).await;
+                for sender in 0..THREAD_COUNT {
+                    let delivered = reliable_communicator.reliable_recv_with_timeout(Some(sender), Instance(sender), Round(0), Duration::from_secs(5)).await;
+                    assert!(delivered.is_ok(), "id {id}, {self_counting:?}: instance {sender} not delivered");
+                }
+                barrier.wait().await;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                let snapshots = reliable_communicator.reliable_inspect().await.unwrap_or_default();
+                reliable_communicator.terminate_reliable_handle(reliable_handle);
+                assert_eq!(snapshots.len(), THREAD_COUNT as usize, "id {id}, {self_counting:?}");
+                for snapshot in snapshots.values() {
+                    assert_eq!((snapshot.get_echo_count(), snapshot.get_vote_count()), (counted, counted), "id {id}, {self_counting:?}");
+                }
+            })
+        }).collect::<Vec<_>>();
+        for thread in join_all(threads).await {
+            thread.unwrap();
+        }
+    }
+}
+
+// Every node runs a witness round, which every node collects. Once the signals settled, the round counts
+// the values and witnesses of every thread, except the node's own under `SelfCounting::Exclude`.
+#[tokio::test]
+async fn witness_rounds_count_their_own_values_as_configured() {
+    for self_counting in [SelfCounting::Include, SelfCounting::Exclude] {
+        let (config, counted) = self_counting_config(self_counting);
+        let quorum = (THREAD_COUNT - config.get_thresholds().get_faulty_threads() as u32) as usize;
+        let channels = ChannelTransport::create_channels(&config);
+        let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+        let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                let reliable_handle = witness_communicator.initialize_reliable_handle();
+                let witness_handle = witness_communicator.initialize_witness_handle();
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+                let collected = witness_communicator.witness_collect_with_timeout(Round(0), Duration::from_secs(5)).await.unwrap_or_default();
+                barrier.wait().await;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                let round = witness_communicator.witness_inspect(Round(0)).await.ok().flatten();
+                witness_communicator.terminate_witness_handle(witness_handle);
+                witness_communicator.terminate_reliable_handle(reliable_handle);
+                assert!(collected.len() >= quorum, "id {id}, {self_counting:?}: {} values collected", collected.len());
+                let counts = round.as_ref().map(|round| (round.get_value_count(), round.get_witness_count()));
+                assert_eq!(counts, Some((counted, counted)), "id {id}, {self_counting:?}");
+            })
+        }).collect::<Vec<_>>();
+        for thread in join_all(threads).await {
+            thread.unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn barycentric_rounds_deliver_under_both_settings() {
+    for self_counting in [SelfCounting::Include, SelfCounting::Exclude] {
+        let (config, _) = self_counting_config(self_counting);
+        let quorum = (THREAD_COUNT - config.get_thresholds().get_faulty_threads() as u32) as usize;
+        let channels = ChannelTransport::create_channels(&config);
+        let mut barycentric_hub = BarycentricHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut barycentric_communicator: BarycentricCommunicator<String> = barycentric_hub.create_barycentric_communicator();
+            tokio::spawn(async move {
+                let reliable_handle = barycentric_communicator.initialize_reliable_handle();
+                let barycentric_handle = barycentric_communicator.initialize_barycentric_handle();
+                barycentric_communicator.barycentric_agreement(format!("barycentric agreement message by {id}"), Round(0)).await;
+                let collected = barycentric_communicator.barycentric_collect_with_timeout(Round(0), Duration::from_secs(5)).await.unwrap_or_default();
+                barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+                barycentric_communicator.terminate_reliable_handle(reliable_handle);
+                assert!(collected.len() >= quorum, "id {id}, {self_counting:?}: {} trusted values collected", collected.len());
+            })
+        }).collect::<Vec<_>>();
+        for thread in join_all(threads).await {
+            thread.unwrap();
+        }
+    }
+}
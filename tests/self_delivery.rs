@@ -0,0 +1,100 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::basic::{BasicCommunication, BasicCommunicator, BasicHub};
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function checks, for every thread, whether it received node 0's broadcast against the setting:
+// every other thread always receives it, node 0 only with self-delivery.
+fn assert_self_delivery(layer: &str, deliver_to_self: bool, received: Vec<bool>) {
+    for (id, received) in received.into_iter().enumerate() {
+        assert_eq!(received, id != 0 || deliver_to_self, "{layer} broadcast, deliver to self {deliver_to_self}, id {id}");
+    }
+}
+
+#[tokio::test]
+async fn basic_broadcast_reaches_its_sender_only_with_self_delivery() {
+    for deliver_to_self in [true, false] {
+        let config = ClusterConfig::new(THREAD_COUNT).with_deliver_to_self(deliver_to_self);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut basic_hub = BasicHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut basic_communicator: BasicCommunicator<String> = basic_hub.create_basic_communicator();
+            tokio::spawn(async move {
+                if id != 0 {
+                    basic_communicator.basic_recv(Some(0), Round(0)).await;
+                    if id == 1 {
+                        basic_communicator.basic_broadcast(String::from("fence"), Round(1)).await;
+                    }
+                    return true
+                }
+                basic_communicator.basic_broadcast(String::from("basic broadcast message by 0"), Round(0)).await;
+                // node 1 only sends the fence once node 0's broadcast was sent, so its own copy, if any, is
+                // received before it
+                basic_communicator.basic_recv(Some(1), Round(1)).await;
+                basic_communicator.basic_recv_with_timeout(Some(0), Round(0), Duration::ZERO).await.is_ok()
+            })
+        }).collect::<Vec<_>>();
+        assert_self_delivery("basic", deliver_to_self, join_all(threads).await.into_iter().map(Result::unwrap).collect());
+    }
+}
+
+#[tokio::test]
+async fn reliable_broadcast_reaches_its_sender_only_with_self_delivery() {
+    for deliver_to_self in [true, false] {
+        let config = ClusterConfig::new(THREAD_COUNT).with_deliver_to_self(deliver_to_self);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+            tokio::spawn(async move {
+                let reliable_handle = reliable_communicator.initialize_reliable_handle();
+                let received = if id != 0 {
+                    reliable_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+                    true
+                } else {
+                    reliable_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+                    // the handle completes node 0's own instance once its Vote quorum is reached, whether or not
+                    // it delivers it to node 0
+                    while !reliable_communicator.reliable_inspect().await.unwrap().values().any(|instance| instance.get_delivered()) {
+                        tokio::task::yield_now().await;
+                    }
+                    reliable_communicator.reliable_recv_with_timeout(Some(0), Instance(0), Round(0), Duration::ZERO).await.is_ok()
+                };
+                reliable_communicator.terminate_reliable_handle(reliable_handle);
+                received
+            })
+        }).collect::<Vec<_>>();
+        assert_self_delivery("reliable", deliver_to_self, join_all(threads).await.into_iter().map(Result::unwrap).collect());
+    }
+}
+
+#[tokio::test]
+async fn witness_round_includes_the_own_value_either_way() {
+    for deliver_to_self in [true, false] {
+        let config = ClusterConfig::new(THREAD_COUNT).with_deliver_to_self(deliver_to_self);
+        let channels = ChannelTransport::create_channels(&config);
+        let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+        let threads = (0..THREAD_COUNT).map(|id| {
+            let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+            tokio::spawn(async move {
+                let reliable_handle = witness_communicator.initialize_reliable_handle();
+                let witness_handle = witness_communicator.initialize_witness_handle();
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+                let own_value = witness_communicator.witness_collect(Round(0)).await.iter().any(|message| message.get_id() == id);
+                witness_communicator.terminate_witness_handle(witness_handle);
+                witness_communicator.terminate_reliable_handle(reliable_handle);
+                own_value
+            })
+        }).collect::<Vec<_>>();
+        for (id, own_value) in join_all(threads).await.into_iter().enumerate() {
+            assert!(own_value.unwrap(), "witness round, deliver to self {deliver_to_self}, id {id}");
+        }
+    }
+}
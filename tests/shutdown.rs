@@ -0,0 +1,80 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::basic::BasicCommunication;
+use rust_project::config::{ClusterConfig, QuorumConfig, QuorumFormula};
+use rust_project::drive::{HandleMode, drive};
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function drives every communicator of a cluster operated in `HandleMode::Polling`, in ID order,
+// until none of them has anything left to process.
+async fn drive_cluster<C: BasicCommunication<String>>(communicators: &mut [C]) {
+    loop {
+        let mut pending = 0;
+        for communicator in communicators.iter_mut() {
+            pending += drive(communicator).await;
+        }
+        if pending == 0 {
+            return
+        }
+    }
+}
+
+// Node 1 is shut down before any other node echoed node 0's instance: it flushes the Input it already
+// received and reports the instance as open. With Bracha's n - f quorums, the three other nodes still
+// deliver the instance without it, and report nothing open.
+#[tokio::test]
+async fn early_shutdown_reports_the_open_instance_and_the_others_still_deliver() {
+    let config = ClusterConfig::new(THREAD_COUNT)
+        .with_quorum_config(QuorumConfig::new().with_formula(QuorumFormula::NMinusF)).unwrap()
+        .with_handle_mode(HandleMode::Polling);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let mut communicators: Vec<ReliableCommunicator<String>> = (0..THREAD_COUNT).map(|_| reliable_hub.create_reliable_communicator()).collect();
+    let mut handles: Vec<_> = communicators.iter_mut().map(|communicator| communicator.initialize_reliable_handle()).collect();
+
+    communicators[0].reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(0), Round(0)).await;
+    let early_report = communicators[1].shutdown_reliable_handle(handles.remove(1)).await.unwrap();
+    assert_eq!((early_report.get_flushed(), early_report.get_open_count()), (1, 1), "{early_report:?}");
+
+    communicators.remove(1);
+    drive_cluster(&mut communicators).await;
+    for (communicator, handle) in communicators.iter_mut().zip(handles) {
+        let id = *communicator.get_id();
+        let delivered = tokio::time::timeout(Duration::from_millis(100), communicator.reliable_recv(Some(0), Instance(0), Round(0))).await;
+        assert!(delivered.is_ok(), "id {id}: instance 0 not delivered");
+        let report = communicator.shutdown_reliable_handle(handle).await;
+        assert!(report.as_ref().is_some_and(|report| report.get_open_count() == 0), "id {id}: {report:?}");
+        let repeated_report = communicator.shutdown_reliable_handle(tokio::spawn(async {})).await;
+        assert!(repeated_report.is_none(), "id {id}: {repeated_report:?}");
+    }
+}
+
+#[tokio::test]
+async fn spawned_handles_shut_down_after_their_round() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            let collected = tokio::time::timeout(Duration::from_secs(5), witness_communicator.witness_collect(Round(0))).await.is_ok();
+            let witness_report = witness_communicator.shutdown_witness_handle(witness_handle).await;
+            let reliable_report = witness_communicator.shutdown_reliable_handle(reliable_handle).await;
+            assert!(collected, "id {id}: round 0 not collected");
+            assert!(witness_report.as_ref().is_some_and(|report| report.get_open_count() == 0), "id {id}: {witness_report:?}");
+            assert!(reliable_report.is_some(), "id {id}: the reliable handle did not report");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
@@ -0,0 +1,31 @@
+use std::net::SocketAddr;
+use rand::rngs::OsRng;
+use rust_project::config::ClusterConfig;
+use rust_project::identity::{IdentityStore, KeyPair};
+use rust_project::membership::{MembershipCertificate, MembershipDocument};
+use rust_project::signing::{Authentication, SignalSigner, verify_with_key};
+
+#[test]
+fn signer_uses_the_persisted_identity_of_the_node() {
+    let identities: Vec<IdentityStore> = (0..4).map(|id| IdentityStore::new(id, KeyPair::generate_ed25519(&mut OsRng))).collect();
+    let addresses: Vec<SocketAddr> = (0..4).map(|id| SocketAddr::from(([127, 0, 0, 1], 9100 + id))).collect();
+    let certificate = identities.iter()
+        .try_fold(MembershipCertificate::new(MembershipDocument::from_identities(&identities, &addresses).unwrap()), |certificate, identity| certificate.signed_by(identity))
+        .unwrap();
+    let path = std::env::temp_dir().join(format!("signing-test-{}.identity.json", std::process::id()));
+    identities[2].save(&path).unwrap();
+    let config = ClusterConfig::new(4)
+        .with_authentication(Authentication::Ed25519)
+        .with_membership(certificate)
+        .with_identity_file(path.clone());
+
+    let signer = SignalSigner::from_config(2, &config).unwrap();
+    let signature = signer.sign(b"signal");
+    assert!(verify_with_key(identities[2].get_key_pair().get_public_key(), b"signal", &signature));
+    let seeded = IdentityStore::generate_cluster(&config).swap_remove(2);
+    assert!(!verify_with_key(seeded.get_key_pair().get_public_key(), b"signal", &signature));
+    let peer_signature = SignalSigner::from_identity(&identities[0]).unwrap().sign(b"signal");
+    assert!(signer.verify(0, b"signal", &peer_signature));
+    assert!(SignalSigner::from_config(0, &config).is_none());
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::faults::Fault;
+use rust_project::reliable::{ReliableCommunication, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+
+const DELAY: Duration = Duration::from_millis(10);
+const INSTANCES: u32 = 5;
+
+// # Function Description:
+// This function slows the last node of a cluster down while node 0 reliably broadcasts a few instances.
+// Every frame the slowed node processes waits for the delay first, so the node needs at least
+// `validity_threshold - 1` more frames, hence as many delays, after the first signal of an instance to
+// reach its Echo quorum, whatever the speed of the other nodes.
+//
+// # Parameters:
+// * thread_count - The number of nodes.
+async fn slowed_node_waits_for_its_frames(thread_count: u32) {
+    let slowed = thread_count - 1;
+    let config = ClusterConfig::new(thread_count).with_fault(Fault::Slowdown(slowed, DELAY));
+    assert_eq!(config.get_fault_script().get_processing_delay(slowed), Some(DELAY));
+    assert_eq!(config.get_fault_script().get_processing_delay(0), None);
+    let validity_threshold = config.get_thresholds().get_validity_threshold() as u32;
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..thread_count).map(|id| {
+        let mut reliable_communicator = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for instance_number in 0..INSTANCES {
+                if id == 0 {
+                    reliable_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(instance_number), Round(0)).await;
+                }
+                reliable_communicator.reliable_recv(Some(0), Instance(instance_number), Round(0)).await;
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            reliable_communicator.phase_latencies("reliable").get_input_to_echo_quorum().clone()
+        })
+    }).collect::<Vec<_>>();
+    let latencies: Vec<_> = join_all(threads).await.into_iter().map(|thread| thread.unwrap()).collect();
+
+    let slowed_latencies = &latencies[slowed as usize];
+    assert_eq!(slowed_latencies.get_count(), INSTANCES as u64);
+    assert!(slowed_latencies.get_min() >= DELAY * (validity_threshold - 1), "n = {thread_count}: {slowed_latencies:?}");
+}
+
+#[tokio::test]
+async fn slowed_node_reaches_its_quorums_late_in_a_cluster_of_4() {
+    slowed_node_waits_for_its_frames(4).await;
+}
+
+#[tokio::test]
+async fn slowed_node_reaches_its_quorums_late_in_a_cluster_of_7() {
+    slowed_node_waits_for_its_frames(7).await;
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::json::JsonConversion;
+use rust_project::reliable::ReliableCommunication;
+use rust_project::round::{Instance, Round};
+use rust_project::snapshot::{ClusterSnapshot, ThreadSnapshot};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+const LAST: u32 = THREAD_COUNT - 1;
+
+// # Function Description:
+// This function runs a witness cluster that delivers a reliable instance and a witness round, except for the
+// last node, which leaves them in its queues, and leaves a second round open, for which only nodes 0 and 1
+// broadcast. The cluster is then snapshot and torn down.
+//
+// # Parameters:
+// * config - The configuration of the cluster.
+//
+// # Returns:
+// * The snapshots taken by the communicators, and the snapshot taken by the hub.
+async fn snapshot_cluster(config: &ClusterConfig) -> (ClusterSnapshot<String>, ClusterSnapshot<String>) {
+    let channels = ChannelTransport::create_channels(config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            if id == 0 {
+                witness_communicator.reliable_broadcast(String::from("snapshot instance"), Instance(0), Round(0)).await;
+            }
+            witness_communicator.witness_broadcast(format!("value of node {id}"), Round(1)).await;
+            if id != LAST {
+                witness_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+                witness_communicator.witness_collect(Round(1)).await;
+            }
+            if id < 2 {
+                witness_communicator.witness_broadcast(format!("late value of node {id}"), Round(2)).await;
+            }
+            (witness_communicator, reliable_handle, witness_handle)
+        })
+    }).collect::<Vec<_>>();
+    let completed = tokio::time::timeout(Duration::from_secs(30), join_all(threads)).await.expect("the cluster did not complete its rounds");
+    let mut threads: Vec<_> = completed.into_iter().map(|thread| thread.unwrap()).collect();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let tapped = witness_hub.snapshot().await;
+    let mut thread_snapshots = vec![];
+    for (witness_communicator, ..) in threads.iter_mut() {
+        thread_snapshots.push(witness_communicator.snapshot().await);
+    }
+    for (witness_communicator, reliable_handle, witness_handle) in threads {
+        witness_communicator.terminate_witness_handle(witness_handle);
+        witness_communicator.terminate_reliable_handle(reliable_handle);
+    }
+    (ClusterSnapshot::new(thread_snapshots), tapped)
+}
+
+// Every thread is snapshot with the second round open and holding the two values broadcast in it, and the last
+// thread with the instance and the first round in its queues. The hub snapshot has the same instances, but no
+// queues.
+#[tokio::test]
+async fn snapshots_list_the_open_rounds_and_the_queued_messages() {
+    let (taken, tapped) = snapshot_cluster(&ClusterConfig::new(THREAD_COUNT)).await;
+    for thread in taken.get_threads() {
+        let id = thread.get_id();
+        let late_values = thread.get_rounds().get(&Round(2)).map(|monitor| monitor.content.values.len());
+        let queued: usize = thread.get_queues().values().map(Vec::len).sum();
+        assert_eq!(thread.get_open_rounds(), vec![Round(2)], "id {id}");
+        assert_eq!(late_values, Some(2), "id {id}");
+        assert!(thread.get_open_instances().is_empty(), "id {id}: {:?}", thread.get_open_instances());
+        assert_eq!(queued, if id == LAST { 2 } else { 0 }, "id {id}");
+        let tapped_thread = tapped.get_thread(id).unwrap();
+        assert_eq!(tapped_thread.get_instances().len(), thread.get_instances().len(), "id {id}");
+        assert!(tapped_thread.get_queues().is_empty(), "id {id}");
+    }
+}
+
+// A snapshot read back from its JSON describes the same state. The senders of an instance are a set, so the
+// snapshots are compared through the state they describe rather than through their JSON.
+#[tokio::test]
+async fn snapshot_round_trips_through_json() {
+    let (taken, _) = snapshot_cluster(&ClusterConfig::new(THREAD_COUNT)).await;
+    let read = ClusterSnapshot::<String>::read_json(&taken.write_json()).unwrap();
+    assert_eq!(read.get_threads().len(), taken.get_threads().len());
+    for (taken, read) in taken.get_threads().iter().zip(read.get_threads()) {
+        let id = taken.get_id();
+        assert_eq!(read.get_id(), id);
+        assert!(taken.get_instances().keys().eq(read.get_instances().keys()), "id {id}");
+        assert_eq!(taken.get_open_rounds(), read.get_open_rounds(), "id {id}");
+        assert_eq!(taken.get_queues().len(), read.get_queues().len(), "id {id}");
+    }
+}
+
+// A new cluster restored from the snapshot refuses the snapshot of another thread and a restore once its handles
+// started. Its last thread receives the messages queued before the teardown, and every thread completes the open
+// round with values broadcast before the teardown, which are only known from the snapshot.
+#[tokio::test]
+async fn restored_cluster_receives_the_queued_messages_and_completes_the_open_round() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let (taken, _) = snapshot_cluster(&config).await;
+    let snapshot = ClusterSnapshot::<String>::read_json(&taken.write_json()).unwrap();
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+    witness_hub.restore(snapshot).unwrap();
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        tokio::spawn(async move {
+            assert!(witness_communicator.restore(ThreadSnapshot::new((id + 1) % THREAD_COUNT)).is_err(), "id {id}");
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            assert!(witness_communicator.restore(ThreadSnapshot::new(id)).is_err(), "id {id}");
+            if id == LAST {
+                let instance = witness_communicator.reliable_recv(Some(0), Instance(0), Round(0)).await;
+                assert_eq!(instance.get_message(), "snapshot instance");
+                assert!(!witness_communicator.witness_collect(Round(1)).await.is_empty());
+            }
+            if id >= 2 {
+                witness_communicator.witness_broadcast(format!("late value of node {id}"), Round(2)).await;
+            }
+            let second_round = witness_communicator.witness_collect(Round(2)).await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            assert!(second_round.iter().any(|message| message.get_id() < 2), "id {id}: {second_round:?}");
+        })
+    }).collect::<Vec<_>>();
+    let completed = tokio::time::timeout(Duration::from_secs(30), join_all(threads)).await.expect("the restored cluster did not complete the open round");
+    for thread in completed {
+        thread.unwrap();
+    }
+}
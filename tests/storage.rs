@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+use rust_project::config::ClusterConfig;
+use rust_project::storage::{Storage, StorageBackend};
+
+// # Function Description:
+// This function writes, deletes, and scans a few records in a storage, as a write-ahead log would, and
+// checks that the storage returns exactly the records written and not deleted.
+//
+// # Parameters:
+// * storage - The storage to exercise.
+fn exercise_storage(storage: &dyn Storage) {
+    storage.put("wal/0002", b"second entry").unwrap();
+    storage.put("wal/0001", b"first entry").unwrap();
+    storage.put("wal/0003", b"deleted entry").unwrap();
+    storage.put("checkpoint/1", b"checkpoint").unwrap();
+    storage.delete("wal/0003").unwrap();
+    storage.flush().unwrap();
+    assert_eq!(storage.scan_prefix("wal/"), Ok(vec![
+        (String::from("wal/0001"), b"first entry".to_vec()),
+        (String::from("wal/0002"), b"second entry".to_vec()),
+    ]));
+}
+
+// # Function Description:
+// This function provides a fresh directory for the files of a backend.
+//
+// # Returns:
+// * The directory.
+fn storage_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("storage-test-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    root
+}
+
+// # Function Description:
+// This function exercises the storage of a durable backend selected through `ClusterConfig`, then reopens
+// it: the records must be returned again.
+//
+// # Parameters:
+// * backend - The backend.
+fn assert_persisted(backend: StorageBackend) {
+    let config = ClusterConfig::new(4).with_storage_backend(backend);
+    exercise_storage(config.open_storage(0).unwrap().as_ref());
+    let reopened = config.open_storage(0).unwrap();
+    assert_eq!(reopened.get("wal/0001"), Ok(Some(b"first entry".to_vec())));
+    assert_eq!(reopened.get("wal/0003"), Ok(None));
+}
+
+#[test]
+fn memory_backend_stores_deletes_and_scans_records() {
+    let config = ClusterConfig::new(4).with_storage_backend(StorageBackend::Memory);
+    exercise_storage(config.open_storage(0).unwrap().as_ref());
+}
+
+#[test]
+fn file_backend_keeps_its_records_across_reopening() {
+    let root = storage_root("file");
+    assert_persisted(StorageBackend::File(root.clone()));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[cfg(feature = "sled")]
+#[test]
+fn sled_backend_keeps_its_records_across_reopening() {
+    let root = storage_root("sled");
+    assert_persisted(StorageBackend::Sled(root.clone()));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[cfg(not(feature = "sled"))]
+#[test]
+fn sled_backend_is_unavailable_without_the_sled_feature() {
+    let root = storage_root("sled");
+    let config = ClusterConfig::new(4).with_storage_backend(StorageBackend::Sled(root.clone()));
+    assert!(config.open_storage(0).is_err());
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::join_all;
+use tokio::sync::Barrier;
+use rust_project::barycentric_agreement::{BarycentricCommunication, BarycentricCommunicator, BarycentricHub};
+use rust_project::basic::{CollectError, RecvError};
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::ReliableCommunication;
+use rust_project::round::{Instance, Round};
+use rust_project::transport::ChannelTransport;
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+const SHORT: Duration = Duration::from_millis(200);
+const LONG: Duration = Duration::from_secs(5);
+
+// Every node waits with a timeout for a reliable instance, a witness round, and a barycentric agreement
+// round that were not started, and gets a timeout error instead of blocking. Node 0 then broadcasts the
+// instance and the remaining nodes broadcast their witness values, and the same calls succeed, the witness
+// round although a first call timed out.
+#[tokio::test]
+async fn waits_time_out_before_the_broadcasts_and_succeed_after() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let early_senders = config.get_thresholds().get_validity_threshold() as u32 - 1;
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT as usize));
+    let channels = ChannelTransport::create_channels(&config);
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config.clone());
+    let channels = ChannelTransport::create_channels(&config);
+    let mut barycentric_hub = BarycentricHub::with_config(channels.transmitters, channels.receivers, config);
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut witness_communicator: WitnessCommunicator<String> = witness_hub.create_witness_communicator();
+        let mut barycentric_communicator: BarycentricCommunicator<String> = barycentric_hub.create_barycentric_communicator();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let reliable_handle = witness_communicator.initialize_reliable_handle();
+            let witness_handle = witness_communicator.initialize_witness_handle();
+            let barycentric_reliable_handle = barycentric_communicator.initialize_reliable_handle();
+            let barycentric_handle = barycentric_communicator.initialize_barycentric_handle();
+            if id < early_senders {
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            }
+            let reliable_timeout = witness_communicator.reliable_recv_with_timeout(Some(0), Instance(1), Round(0), SHORT).await;
+            let witness_timeout = witness_communicator.witness_collect_with_timeout(Round(0), SHORT).await;
+            let barycentric_timeout = barycentric_communicator.barycentric_collect_with_timeout(Round(0), SHORT).await;
+            barrier.wait().await;
+
+            if id == 0 {
+                witness_communicator.reliable_broadcast(String::from("reliable broadcast message by 0"), Instance(1), Round(0)).await;
+            }
+            if id >= early_senders {
+                witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(0)).await;
+            }
+            let reliable_delivery = witness_communicator.reliable_recv_with_timeout(Some(0), Instance(1), Round(0), LONG).await;
+            let witness_delivery = witness_communicator.witness_collect_with_timeout(Round(0), LONG).await;
+            witness_communicator.terminate_witness_handle(witness_handle);
+            witness_communicator.terminate_reliable_handle(reliable_handle);
+            barycentric_communicator.terminate_barycentric_handle(barycentric_handle);
+            barycentric_communicator.terminate_reliable_handle(barycentric_reliable_handle);
+
+            assert_eq!(reliable_timeout, Err(RecvError::Timeout(String::from("reliable"), Some(Instance(1)), Round(0))), "id {id}");
+            assert_eq!(witness_timeout, Err(CollectError::Timeout(String::from("witness"), Round(0))), "id {id}");
+            assert_eq!(barycentric_timeout, Err(CollectError::Timeout(String::from("barycentric"), Round(0))), "id {id}");
+            assert!(reliable_delivery.is_ok(), "id {id}: {reliable_delivery:?}");
+            assert!(witness_delivery.is_ok(), "id {id}: {witness_delivery:?}");
+        })
+    }).collect::<Vec<_>>();
+    for thread in join_all(threads).await {
+        thread.unwrap();
+    }
+}
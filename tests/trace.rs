@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+use futures::future::join_all;
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::{ReliableCommunication, ReliableCommunicator, ReliableHub};
+use rust_project::round::{Instance, Round};
+use rust_project::trace::{DeliveryRecord, TraceChecker, TraceQuery, Violation, check_trace, query_trace, write_trace};
+use rust_project::transport::ChannelTransport;
+
+const THREAD_COUNT: u32 = 4;
+const INSTANCES: u32 = 3;
+
+// # Function Description:
+// This function records a reliable run: every node reliably broadcasts a few instances and delivers every
+// instance, recording each delivery with its time since the start of the run.
+//
+// # Returns:
+// * The delivery records of every node, node by node.
+async fn record_reliable_run() -> Vec<DeliveryRecord> {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let channels = ChannelTransport::create_channels(&config);
+    let mut reliable_hub = ReliableHub::with_config(channels.transmitters, channels.receivers, config);
+    let start = Instant::now();
+    let threads = (0..THREAD_COUNT).map(|id| {
+        let mut reliable_communicator: ReliableCommunicator<String> = reliable_hub.create_reliable_communicator();
+        tokio::spawn(async move {
+            let reliable_handle = reliable_communicator.initialize_reliable_handle();
+            for instance_number in 0..INSTANCES {
+                reliable_communicator.reliable_broadcast(format!("reliable broadcast message {instance_number} by {id}"), Instance(instance_number), Round(0)).await;
+            }
+            let mut records = vec![];
+            for sender in 0..THREAD_COUNT {
+                for instance_number in 0..INSTANCES {
+                    let message = reliable_communicator.reliable_recv(Some(sender), Instance(instance_number), Round(0)).await;
+                    records.push(DeliveryRecord::from_message(id, &message).with_delivered_at(start.elapsed()));
+                }
+            }
+            reliable_communicator.terminate_reliable_handle(reliable_handle);
+            records
+        })
+    }).collect::<Vec<_>>();
+    join_all(threads).await.into_iter().flat_map(|thread| thread.unwrap()).collect()
+}
+
+#[tokio::test]
+async fn recorded_trace_has_no_violations() {
+    let records = record_reliable_run().await;
+    assert_eq!(records.len() as u32, THREAD_COUNT * THREAD_COUNT * INSTANCES);
+    let checked = check_trace(write_trace(&records).as_bytes(), TraceChecker::new(THREAD_COUNT));
+    assert_eq!(checked, Ok((records.len() as u64, vec![])));
+}
+
+// The first node delivers another message than the other nodes for one instance: every other node disagrees
+// with it, and a node delivering the instance twice is reported once.
+#[tokio::test]
+async fn diverging_and_repeated_deliveries_are_flagged() {
+    let records = record_reliable_run().await;
+    let mut diverging = records.clone();
+    let first = &records[0];
+    diverging[0] = DeliveryRecord::new(first.get_id(), String::from("reliable"), first.get_sender(), first.get_instance_number(), Round(0), first.get_digest() + 1);
+    let (_, violations) = check_trace(write_trace(&diverging).as_bytes(), TraceChecker::new(THREAD_COUNT)).unwrap();
+    assert_eq!(violations.len() as u32, THREAD_COUNT - 1, "{violations:?}");
+    assert!(violations.iter().all(|violation| matches!(violation, Violation::Agreement(..))), "{violations:?}");
+
+    let mut repeated = records.clone();
+    repeated.push(first.clone());
+    let (_, violations) = check_trace(write_trace(&repeated).as_bytes(), TraceChecker::new(THREAD_COUNT)).unwrap();
+    assert!(matches!(violations.as_slice(), [Violation::Duplicate(.., id)] if *id == first.get_id()), "{violations:?}");
+}
+
+// The first node misses one delivery: the instance lacks totality, unless the node is declared faulty.
+#[tokio::test]
+async fn missed_delivery_is_a_totality_violation_unless_the_node_is_faulty() {
+    let records = record_reliable_run().await;
+    let missing = write_trace(&records[1..]);
+    let (_, violations) = check_trace(missing.as_bytes(), TraceChecker::new(THREAD_COUNT)).unwrap();
+    assert!(matches!(violations.as_slice(), [Violation::Totality(.., missing)] if missing == &vec![records[0].get_id()]), "{violations:?}");
+
+    let (_, violations) = check_trace(missing.as_bytes(), TraceChecker::new(THREAD_COUNT).with_faulty(records[0].get_id())).unwrap();
+    assert!(violations.is_empty(), "{violations:?}");
+}
+
+#[tokio::test]
+async fn queries_select_the_deliveries_of_a_trace() {
+    let records = record_reliable_run().await;
+    let trace = write_trace(&records);
+    let first = &records[0];
+
+    let by_node = query_trace(trace.as_bytes(), &TraceQuery::new().with_node(first.get_id())).unwrap();
+    let expected: Vec<_> = records.iter().filter(|record| record.get_id() == first.get_id()).cloned().collect();
+    assert_eq!(by_node, expected);
+
+    let by_instance = query_trace(trace.as_bytes(), &TraceQuery::new().with_protocol(String::from("reliable")).with_sender(first.get_sender())
+        .with_instance(first.get_instance_number().unwrap()).with_round(Round(0))).unwrap();
+    assert_eq!(by_instance.len() as u32, THREAD_COUNT);
+    assert!(by_instance.iter().all(|record| record.get_sender() == first.get_sender() && record.get_instance_number() == first.get_instance_number()));
+
+    let midpoint = records.iter().filter_map(|record| record.get_delivered_at()).max().unwrap() / 2;
+    let by_time = query_trace(trace.as_bytes(), &TraceQuery::new().with_node(first.get_id()).with_until(midpoint)).unwrap();
+    let expected = expected.iter().filter(|record| record.get_delivered_at().is_some_and(|delivered_at| delivered_at <= midpoint)).count();
+    assert_eq!(by_time.len(), expected);
+
+    // records without a delivery time never match a time range
+    let untimed = DeliveryRecord::new(first.get_id(), String::from("reliable"), first.get_sender(), first.get_instance_number(), Round(0), first.get_digest());
+    let matching = query_trace(write_trace(&[untimed]).as_bytes(), &TraceQuery::new().with_from(Duration::ZERO)).unwrap();
+    assert!(matching.is_empty());
+
+    assert!(query_trace("not a record\n".as_bytes(), &TraceQuery::new()).is_err());
+}
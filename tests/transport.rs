@@ -0,0 +1,154 @@
+use std::{net::SocketAddr, time::Duration};
+use futures::future::join_all;
+use tokio::net::TcpListener;
+use rust_project::basic::BasicCommunication;
+use rust_project::config::ClusterConfig;
+use rust_project::reliable::ReliableCommunication;
+use rust_project::round::{Instance, Round};
+use rust_project::transport::{TcpTransport, Transport};
+use rust_project::witness::{WitnessCommunication, WitnessCommunicator, WitnessHub};
+
+const THREAD_COUNT: u32 = 4;
+
+// # Function Description:
+// This function opens a transport, as a node running in its own process would, and runs the workload of
+// the threads it hosts: every thread reliably broadcasts one instance in round 0 and receives every
+// instance, then takes part in witness round 1, which must deliver a quorum of values.
+//
+// # Parameters:
+// * transport - The transport connecting the node to the cluster.
+// * config - The configuration of the cluster.
+async fn run_transport_workload(transport: &mut dyn Transport, config: ClusterConfig) {
+    let local_ids = transport.get_local_ids(&config);
+    let channels = transport.open(&config).await.unwrap();
+    let quorum = (THREAD_COUNT - config.get_thresholds().get_faulty_threads() as u32) as usize;
+    let mut witness_hub = WitnessHub::with_config(channels.transmitters, channels.receivers, config);
+    let communicators: Vec<WitnessCommunicator<String>> = (0..THREAD_COUNT)
+        .map(|_| witness_hub.create_witness_communicator())
+        .filter(|communicator| local_ids.contains(communicator.get_id()))
+        .collect();
+    join_all(communicators.into_iter().map(|mut witness_communicator| async move {
+        let id = *witness_communicator.get_id();
+        let reliable_handle = witness_communicator.initialize_reliable_handle();
+        let witness_handle = witness_communicator.initialize_witness_handle();
+        witness_communicator.reliable_broadcast(format!("reliable broadcast message by {id}"), Instance(id), Round(0)).await;
+        for sender in 0..THREAD_COUNT {
+            let message = witness_communicator.reliable_recv(Some(sender), Instance(sender), Round(0)).await;
+            assert_eq!(*message.get_message(), format!("reliable broadcast message by {sender}"), "id {id}");
+        }
+        witness_communicator.witness_broadcast(format!("witness broadcast message by {id}"), Round(1)).await;
+        let values = witness_communicator.witness_collect(Round(1)).await;
+        witness_communicator.terminate_witness_handle(witness_handle);
+        witness_communicator.terminate_reliable_handle(reliable_handle);
+        assert!(values.len() >= quorum, "id {id}: {values:?}");
+    })).await;
+}
+
+// Every node gets its own `TcpTransport` on a loopback port, as if it ran in its own process.
+#[tokio::test]
+async fn nodes_connected_over_tcp_complete_the_workload() {
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let mut transports = vec![];
+    for id in 0..THREAD_COUNT {
+        transports.push(TcpTransport::bind(id, loopback).await.unwrap());
+    }
+    let addresses: Vec<SocketAddr> = transports.iter().map(|transport| transport.get_local_address()).collect();
+    let nodes = transports.into_iter().map(|transport| {
+        let mut transport = transport.with_peer_addresses(addresses.clone()).with_startup_barrier(Duration::from_secs(10));
+        let config = config.clone();
+        tokio::spawn(async move { run_transport_workload(&mut transport, config).await })
+    }).collect::<Vec<_>>();
+    let completed = tokio::time::timeout(Duration::from_secs(20), join_all(nodes)).await.expect("the nodes did not complete the workload");
+    for node in completed {
+        node.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn startup_barrier_opens_once_every_peer_acknowledged() {
+    let config = ClusterConfig::new(3);
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let mut transports = vec![];
+    for id in 0..3 {
+        transports.push(TcpTransport::bind(id, loopback).await.unwrap());
+    }
+    let addresses: Vec<SocketAddr> = transports.iter().map(|transport| transport.get_local_address()).collect();
+    let opened = join_all(transports.into_iter().map(|transport| {
+        let config = config.clone();
+        let mut transport = transport.with_peer_addresses(addresses.clone()).with_startup_barrier(Duration::from_secs(10));
+        async move { transport.open(&config).await.map(|_| transport) }
+    })).await;
+    assert!(opened.iter().all(|result| result.is_ok()));
+}
+
+#[tokio::test]
+async fn startup_barrier_times_out_naming_the_missing_peers() {
+    let config = ClusterConfig::new(3);
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let first = TcpTransport::bind(0, loopback).await.unwrap();
+    let second = TcpTransport::bind(1, loopback).await.unwrap();
+    // node 2 never starts: its port is released before the cluster opens
+    let missing = TcpListener::bind(loopback).await.unwrap().local_addr().unwrap();
+    let addresses = vec![first.get_local_address(), second.get_local_address(), missing];
+
+    let opened = join_all([first, second].into_iter().map(|transport| {
+        let config = config.clone();
+        let mut transport = transport.with_peer_addresses(addresses.clone()).with_startup_barrier(Duration::from_millis(500));
+        async move { transport.open(&config).await.map(|_| transport) }
+    })).await;
+    for result in opened {
+        let error = result.err().unwrap();
+        assert!(error.contains("timed out"), "{error}");
+        assert!(error.ends_with("nodes {2}"), "{error}");
+    }
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn quic_startup_barrier_times_out_naming_the_missing_peers() {
+    use rust_project::transport::QuicTransport;
+
+    let config = ClusterConfig::new(3);
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let first = QuicTransport::bind(0, loopback).unwrap();
+    let second = QuicTransport::bind(1, loopback).unwrap();
+    // node 2 never starts: its port is released before the cluster opens
+    let missing = std::net::UdpSocket::bind(loopback).unwrap().local_addr().unwrap();
+    let addresses = vec![first.get_local_address().unwrap(), second.get_local_address().unwrap(), missing];
+
+    let opened = join_all([first, second].into_iter().map(|transport| {
+        let config = config.clone();
+        let mut transport = transport.with_peer_addresses(addresses.clone()).with_startup_barrier(Duration::from_secs(1));
+        async move { transport.open(&config).await.map(|_| transport) }
+    })).await;
+    for result in opened {
+        let error = result.err().unwrap();
+        assert!(error.ends_with("nodes {2}"), "{error}");
+    }
+}
+
+// Every node gets its own `QuicTransport` on a loopback port, with a separate stream per lane to every peer.
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn nodes_connected_over_quic_complete_the_workload() {
+    use rust_project::transport::QuicTransport;
+
+    let config = ClusterConfig::new(THREAD_COUNT);
+    let loopback: SocketAddr = ([127, 0, 0, 1], 0).into();
+    let transports: Vec<QuicTransport> = (0..THREAD_COUNT).map(|id| QuicTransport::bind(id, loopback).unwrap()).collect();
+    let addresses: Vec<SocketAddr> = transports.iter().map(|transport| transport.get_local_address().unwrap()).collect();
+    let nodes = transports.into_iter().map(|transport| {
+        let mut transport = transport.with_peer_addresses(addresses.clone()).with_startup_barrier(Duration::from_secs(10));
+        let config = config.clone();
+        tokio::spawn(async move {
+            run_transport_workload(&mut transport, config).await;
+            // the endpoint closes its connections when dropped, so it stays up until every node completed
+            transport
+        })
+    }).collect::<Vec<_>>();
+    let completed = tokio::time::timeout(Duration::from_secs(20), join_all(nodes)).await.expect("the nodes did not complete the workload");
+    for node in completed {
+        node.unwrap();
+    }
+}